@@ -918,6 +918,13 @@ fn extension_only_provides_context_server(manifest: &ExtensionManifest) -> bool
         && manifest.indexed_docs_providers.is_empty()
         && manifest.snippets.is_none()
         && manifest.debug_locators.is_empty()
+        && manifest.editor_commands.is_empty()
+        && manifest.formatters.is_empty()
+        && manifest.code_action_providers.is_empty()
+        && manifest.task_sources.is_empty()
+        && manifest.web_search_providers.is_empty()
+        && manifest.language_model_providers.is_empty()
+        && manifest.panels.is_empty()
 }
 
 pub(crate) fn resolve_extension_for_context_server(
@@ -25,8 +25,8 @@ use assistant_tool::ToolRegistry;
 use copy_path_tool::CopyPathTool;
 use gpui::{App, Entity};
 use http_client::HttpClientWithUrl;
-use language_model::LanguageModelRegistry;
 use move_path_tool::MovePathTool;
+use web_search::WebSearchRegistry;
 use web_search_tool::WebSearchTool;
 
 pub(crate) use templates::*;
@@ -68,25 +68,17 @@ pub fn init(http_client: Arc<HttpClientWithUrl>, cx: &mut App) {
     registry.register_tool(FetchTool::new(http_client));
     registry.register_tool(EditFileTool);
 
-    register_web_search_tool(&LanguageModelRegistry::global(cx), cx);
-    cx.subscribe(
-        &LanguageModelRegistry::global(cx),
-        move |registry, event, cx| match event {
-            language_model::Event::DefaultModelChanged => {
-                register_web_search_tool(&registry, cx);
-            }
-            _ => {}
-        },
-    )
-    .detach();
+    if let Some(web_search_registry) = WebSearchRegistry::try_global(cx) {
+        register_web_search_tool(&web_search_registry, cx);
+        cx.observe(&web_search_registry, |registry, cx| {
+            register_web_search_tool(&registry, cx);
+        })
+        .detach();
+    }
 }
 
-fn register_web_search_tool(registry: &Entity<LanguageModelRegistry>, cx: &mut App) {
-    let using_zed_provider = registry
-        .read(cx)
-        .default_model()
-        .map_or(false, |default| default.is_provided_by_zed());
-    if using_zed_provider {
+fn register_web_search_tool(registry: &Entity<WebSearchRegistry>, cx: &mut App) {
+    if registry.read(cx).active_provider().is_some() {
         ToolRegistry::global(cx).register_tool(WebSearchTool);
     } else {
         ToolRegistry::global(cx).unregister_tool(WebSearchTool);
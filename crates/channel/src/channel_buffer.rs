@@ -1,7 +1,7 @@
 use crate::{Channel, ChannelStore};
 use anyhow::Result;
-use client::{ChannelId, Client, Collaborator, UserStore, ZED_ALWAYS_ACTIVE};
-use collections::HashMap;
+use client::{ChannelId, Client, Collaborator, User, UserStore, ZED_ALWAYS_ACTIVE};
+use collections::{HashMap, HashSet};
 use gpui::{App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, Task};
 use language::proto::serialize_version;
 use rpc::{
@@ -10,6 +10,7 @@ use rpc::{
 };
 use std::{sync::Arc, time::Duration};
 use text::BufferId;
+use time::OffsetDateTime;
 use util::ResultExt;
 
 pub const ACKNOWLEDGE_DEBOUNCE_INTERVAL: Duration = Duration::from_millis(250);
@@ -42,6 +43,15 @@ pub enum ChannelBufferEvent {
 
 impl EventEmitter<ChannelBufferEvent> for ChannelBuffer {}
 
+/// A version of the channel notes that was saved by the server, after they were edited.
+#[derive(Clone, Debug)]
+pub struct ChannelBufferRevision {
+    pub id: u64,
+    pub timestamp: OffsetDateTime,
+    /// The users who edited the notes since the previous revision.
+    pub authors: Vec<Arc<User>>,
+}
+
 impl ChannelBuffer {
     pub(crate) async fn new(
         channel: Arc<Channel>,
@@ -269,6 +279,75 @@ impl ChannelBuffer {
         cx.notify()
     }
 
+    /// Loads the saved revisions of the notes, oldest first.
+    pub fn revisions(&self, cx: &Context<Self>) -> Task<Result<Vec<ChannelBufferRevision>>> {
+        let client = self.client.clone();
+        let user_store = self.user_store.clone();
+        let channel_id = self.channel_id;
+        cx.spawn(async move |_, cx| {
+            let response = client
+                .request(proto::GetChannelBufferRevisions {
+                    channel_id: channel_id.0,
+                })
+                .await?;
+            let author_ids = response
+                .revisions
+                .iter()
+                .flat_map(|revision| revision.author_ids.iter().copied())
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect();
+            let authors = user_store
+                .update(cx, |user_store, cx| user_store.get_users(author_ids, cx))?
+                .await?
+                .into_iter()
+                .map(|user| (user.id, user))
+                .collect::<HashMap<_, _>>();
+
+            response
+                .revisions
+                .into_iter()
+                .map(|revision| {
+                    Ok(ChannelBufferRevision {
+                        id: revision.id,
+                        timestamp: OffsetDateTime::from_unix_timestamp(revision.timestamp as i64)?,
+                        authors: revision
+                            .author_ids
+                            .iter()
+                            .filter_map(|author_id| authors.get(author_id).cloned())
+                            .collect(),
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// Loads the text of the notes as of the given revision.
+    pub fn revision_text(&self, revision_id: u64, cx: &App) -> Task<Result<String>> {
+        let request = self.client.request(proto::GetChannelBufferRevisionText {
+            channel_id: self.channel_id.0,
+            revision_id,
+        });
+        cx.background_spawn(async move { Ok(request.await?.text) })
+    }
+
+    /// Replaces the notes with the text of an old revision. The replacement is made as
+    /// an ordinary edit, so that it can be undone, and later revisions are kept.
+    pub fn restore_revision(&self, revision_id: u64, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let text = self.revision_text(revision_id, cx);
+        let buffer = self.buffer.clone();
+        cx.spawn(async move |_, cx| {
+            let text = text.await?;
+            let diff = buffer.update(cx, |buffer, cx| buffer.diff(text, cx))?.await;
+            buffer.update(cx, |buffer, cx| {
+                buffer.finalize_last_transaction();
+                buffer.apply_diff(diff, cx);
+                buffer.finalize_last_transaction();
+            })?;
+            Ok(())
+        })
+    }
+
     pub fn is_connected(&self) -> bool {
         self.connected
     }
@@ -52,6 +52,15 @@ pub struct ChannelMessage {
     pub edited_at: Option<OffsetDateTime>,
 }
 
+/// A page of the messages in a channel that match a search query.
+#[derive(Clone, Debug)]
+pub struct ChannelMessageSearchResults {
+    /// The matching messages, most recent first.
+    pub messages: Vec<ChannelMessage>,
+    /// Whether there are no older matches left to load.
+    pub done: bool,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ChannelMessageId {
     Saved(u64),
@@ -306,6 +315,35 @@ impl ChannelChat {
         }))
     }
 
+    /// Searches the channel's messages on the server. To load older matches, pass the
+    /// oldest match that was found so far as `before_message_id`.
+    pub fn search_messages(
+        &self,
+        query: String,
+        before_message_id: Option<u64>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<ChannelMessageSearchResults>> {
+        let rpc = self.rpc.clone();
+        let user_store = self.user_store.clone();
+        let channel_id = self.channel_id;
+        cx.spawn(async move |_, cx| {
+            let response = rpc
+                .request(proto::SearchChannelMessages {
+                    channel_id: channel_id.0,
+                    query,
+                    before_message_id,
+                })
+                .await?;
+            let mut messages =
+                ChannelMessage::from_proto_vec(response.messages, &user_store, cx).await?;
+            messages.reverse();
+            Ok(ChannelMessageSearchResults {
+                messages,
+                done: response.done,
+            })
+        })
+    }
+
     pub fn first_loaded_message_id(&mut self) -> Option<u64> {
         self.first_loaded_message_id
     }
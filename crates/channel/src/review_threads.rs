@@ -0,0 +1,488 @@
+use crate::{MessageParams, mentions_to_proto};
+use anyhow::{Context as _, Result};
+use client::{ChannelId, Client, Subscription, TypedEnvelope, proto, user::User, user::UserStore};
+use collections::HashMap;
+use gpui::{App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, Global, Task};
+use std::{ops::Range, sync::Arc};
+use text::{Bias, BufferSnapshot, Point};
+use time::OffsetDateTime;
+
+/// The maximum number of bytes of a thread's range that are stored to find it again
+/// after its file has changed.
+const MAX_ANCHOR_TEXT_LEN: usize = 256;
+
+pub fn init(client: &Arc<Client>, user_store: Entity<UserStore>, cx: &mut App) {
+    let review_thread_store =
+        cx.new(|cx| ReviewThreadStore::new(client.clone(), user_store.clone(), cx));
+    cx.set_global(GlobalReviewThreadStore(review_thread_store));
+}
+
+/// Stores the review comment threads that are attached to files, for the files
+/// that have been loaded.
+///
+/// Threads belong to a channel, and are identified by the path of the file that
+/// they are attached to, including the name of its worktree.
+pub struct ReviewThreadStore {
+    client: Arc<Client>,
+    user_store: Entity<UserStore>,
+    threads_by_file: HashMap<(ChannelId, Arc<str>), Vec<ReviewThread>>,
+    _subscription: Subscription,
+}
+
+#[derive(Clone, Debug)]
+pub struct ReviewThread {
+    pub id: u64,
+    pub channel_id: ChannelId,
+    pub path: Arc<str>,
+    pub location: ReviewThreadLocation,
+    pub author_id: u64,
+    pub resolved_by: Option<u64>,
+    pub comments: Vec<ReviewComment>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ReviewComment {
+    pub id: u64,
+    pub sender: Arc<User>,
+    pub body: String,
+    pub timestamp: OffsetDateTime,
+}
+
+/// Where a review thread is attached in its file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReviewThreadLocation {
+    pub range: Range<Point>,
+    /// The beginning of the text within `range` when the location was recorded.
+    pub anchor_text: String,
+    /// The git blob of the file's contents when the location was recorded.
+    pub blob_sha: Option<String>,
+}
+
+/// A [`ReviewThreadLocation`] resolved against the current contents of its file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResolvedReviewThreadLocation {
+    pub range: Range<Point>,
+    /// Whether the text the thread was attached to could not be found, in which case
+    /// `range` is the thread's original range, clipped to the file.
+    pub outdated: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReviewThreadStoreEvent {
+    ThreadsUpdated {
+        channel_id: ChannelId,
+        path: Arc<str>,
+    },
+}
+
+impl EventEmitter<ReviewThreadStoreEvent> for ReviewThreadStore {}
+
+struct GlobalReviewThreadStore(Entity<ReviewThreadStore>);
+
+impl Global for GlobalReviewThreadStore {}
+
+impl ReviewThreadStore {
+    pub fn global(cx: &App) -> Entity<Self> {
+        cx.global::<GlobalReviewThreadStore>().0.clone()
+    }
+
+    pub fn new(client: Arc<Client>, user_store: Entity<UserStore>, cx: &mut Context<Self>) -> Self {
+        Self {
+            _subscription: client
+                .add_message_handler(cx.weak_entity(), Self::handle_update_review_thread),
+            client,
+            user_store,
+            threads_by_file: HashMap::default(),
+        }
+    }
+
+    /// Returns the threads attached to the given file, if they have been loaded.
+    pub fn threads_for_path(&self, channel_id: ChannelId, path: &str) -> Option<&[ReviewThread]> {
+        self.threads_by_file
+            .get(&(channel_id, Arc::from(path)))
+            .map(Vec::as_slice)
+    }
+
+    pub fn thread(&self, channel_id: ChannelId, thread_id: u64) -> Option<&ReviewThread> {
+        self.threads_by_file
+            .iter()
+            .filter(|((thread_channel_id, _), _)| *thread_channel_id == channel_id)
+            .flat_map(|(_, threads)| threads)
+            .find(|thread| thread.id == thread_id)
+    }
+
+    /// Fetches the threads attached to the given file. Once loaded, the file's
+    /// threads are kept up to date as collaborators change them.
+    pub fn load_threads(
+        &mut self,
+        channel_id: ChannelId,
+        path: Arc<str>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let request = self.client.request(proto::GetReviewThreads {
+            channel_id: channel_id.0,
+            path: path.to_string(),
+        });
+        let user_store = self.user_store.clone();
+        cx.spawn(async move |this, cx| {
+            let response = request.await?;
+            let threads = ReviewThread::from_proto_vec(response.threads, &user_store, cx).await?;
+            this.update(cx, |this, cx| {
+                this.threads_by_file
+                    .insert((channel_id, path.clone()), threads);
+                cx.emit(ReviewThreadStoreEvent::ThreadsUpdated { channel_id, path });
+            })
+        })
+    }
+
+    pub fn create_thread(
+        &mut self,
+        channel_id: ChannelId,
+        path: Arc<str>,
+        location: ReviewThreadLocation,
+        comment: MessageParams,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<u64>> {
+        let request = self.client.request(proto::CreateReviewThread {
+            channel_id: channel_id.0,
+            path: path.to_string(),
+            location: Some(location.to_proto()),
+            body: comment.text,
+            mentions: mentions_to_proto(&comment.mentions),
+        });
+        self.handle_thread_response(request, cx)
+    }
+
+    pub fn reply(
+        &mut self,
+        channel_id: ChannelId,
+        thread_id: u64,
+        comment: MessageParams,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<u64>> {
+        let request = self.client.request(proto::ReplyToReviewThread {
+            channel_id: channel_id.0,
+            thread_id,
+            body: comment.text,
+            mentions: mentions_to_proto(&comment.mentions),
+        });
+        self.handle_thread_response(request, cx)
+    }
+
+    pub fn set_resolved(
+        &mut self,
+        channel_id: ChannelId,
+        thread_id: u64,
+        resolved: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<u64>> {
+        let request = self.client.request(proto::ResolveReviewThread {
+            channel_id: channel_id.0,
+            thread_id,
+            resolved,
+        });
+        self.handle_thread_response(request, cx)
+    }
+
+    /// Records that a thread's text has moved within its file.
+    pub fn move_thread(
+        &mut self,
+        channel_id: ChannelId,
+        thread_id: u64,
+        location: ReviewThreadLocation,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<u64>> {
+        let request = self.client.request(proto::MoveReviewThread {
+            channel_id: channel_id.0,
+            thread_id,
+            location: Some(location.to_proto()),
+        });
+        self.handle_thread_response(request, cx)
+    }
+
+    fn handle_thread_response(
+        &mut self,
+        request: impl Future<Output = Result<proto::ReviewThreadResponse>> + 'static,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<u64>> {
+        let user_store = self.user_store.clone();
+        cx.spawn(async move |this, cx| {
+            let thread = request.await?.thread.context("invalid review thread")?;
+            let thread = ReviewThread::from_proto(thread, &user_store, cx).await?;
+            let thread_id = thread.id;
+            this.update(cx, |this, cx| this.insert_thread(thread, cx))?;
+            Ok(thread_id)
+        })
+    }
+
+    fn insert_thread(&mut self, thread: ReviewThread, cx: &mut Context<Self>) {
+        let channel_id = thread.channel_id;
+        let path = thread.path.clone();
+        let Some(threads) = self.threads_by_file.get_mut(&(channel_id, path.clone())) else {
+            return;
+        };
+        match threads.binary_search_by_key(&thread.id, |thread| thread.id) {
+            Ok(ix) => threads[ix] = thread,
+            Err(ix) => threads.insert(ix, thread),
+        }
+        cx.emit(ReviewThreadStoreEvent::ThreadsUpdated { channel_id, path });
+    }
+
+    async fn handle_update_review_thread(
+        this: Entity<Self>,
+        message: TypedEnvelope<proto::UpdateReviewThread>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let thread = message.payload.thread.context("invalid review thread")?;
+        let user_store = this.read_with(&mut cx, |this, _| this.user_store.clone())?;
+        let thread = ReviewThread::from_proto(thread, &user_store, &mut cx).await?;
+        this.update(&mut cx, |this, cx| this.insert_thread(thread, cx))
+    }
+}
+
+impl ReviewThread {
+    pub fn is_resolved(&self) -> bool {
+        self.resolved_by.is_some()
+    }
+
+    pub async fn from_proto(
+        thread: proto::ReviewThread,
+        user_store: &Entity<UserStore>,
+        cx: &mut AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self::from_proto_vec(vec![thread], user_store, cx)
+            .await?
+            .pop()
+            .context("invalid review thread")?)
+    }
+
+    pub async fn from_proto_vec(
+        threads: Vec<proto::ReviewThread>,
+        user_store: &Entity<UserStore>,
+        cx: &mut AsyncApp,
+    ) -> Result<Vec<Self>> {
+        let sender_ids = threads
+            .iter()
+            .flat_map(|thread| &thread.comments)
+            .map(|comment| comment.sender_id)
+            .collect();
+        let senders = user_store
+            .update(cx, |user_store, cx| user_store.get_users(sender_ids, cx))?
+            .await?
+            .into_iter()
+            .map(|user| (user.id, user))
+            .collect::<HashMap<_, _>>();
+
+        threads
+            .into_iter()
+            .map(|thread| {
+                let location = thread.location.context("invalid review thread location")?;
+                Ok(Self {
+                    id: thread.id,
+                    channel_id: ChannelId(thread.channel_id),
+                    path: thread.path.into(),
+                    location: ReviewThreadLocation::from_proto(location),
+                    author_id: thread.author_id,
+                    resolved_by: thread.resolved_by,
+                    comments: thread
+                        .comments
+                        .into_iter()
+                        .map(|comment| {
+                            Ok(ReviewComment {
+                                id: comment.id,
+                                sender: senders
+                                    .get(&comment.sender_id)
+                                    .cloned()
+                                    .context("unknown comment sender")?,
+                                body: comment.body,
+                                timestamp: OffsetDateTime::from_unix_timestamp(
+                                    comment.timestamp as i64,
+                                )?,
+                            })
+                        })
+                        .collect::<Result<_>>()?,
+                })
+            })
+            .collect()
+    }
+}
+
+impl ReviewThreadLocation {
+    /// Records a location for the given range of a file, so that it can be found
+    /// again after the file has changed.
+    pub fn new(range: Range<Point>, snapshot: &BufferSnapshot, blob_sha: Option<String>) -> Self {
+        let mut anchor_text = String::new();
+        for chunk in snapshot.text_for_range(range.clone()) {
+            anchor_text.push_str(chunk);
+            if anchor_text.len() >= MAX_ANCHOR_TEXT_LEN {
+                break;
+            }
+        }
+        if anchor_text.len() > MAX_ANCHOR_TEXT_LEN {
+            let mut len = MAX_ANCHOR_TEXT_LEN;
+            while !anchor_text.is_char_boundary(len) {
+                len -= 1;
+            }
+            anchor_text.truncate(len);
+        }
+
+        Self {
+            range,
+            anchor_text,
+            blob_sha,
+        }
+    }
+
+    /// Finds this location in the current contents of its file.
+    ///
+    /// The recorded range is used as is when the file is unchanged, either because
+    /// its git blob is the same or because the anchor text is still at the same
+    /// position. Otherwise, the occurrence of the anchor text that is closest to
+    /// the recorded range is used.
+    pub fn resolve(
+        &self,
+        snapshot: &BufferSnapshot,
+        blob_sha: Option<&str>,
+    ) -> ResolvedReviewThreadLocation {
+        let clipped_range = snapshot.clip_point(self.range.start, Bias::Left)
+            ..snapshot.clip_point(self.range.end, Bias::Right);
+        let is_same_blob = self.blob_sha.is_some() && self.blob_sha.as_deref() == blob_sha;
+        let is_same_text = clipped_range.start == self.range.start
+            && snapshot.contains_str_at(self.range.start, &self.anchor_text);
+        if is_same_blob || is_same_text {
+            return ResolvedReviewThreadLocation {
+                range: clipped_range,
+                outdated: false,
+            };
+        }
+
+        if !self.anchor_text.is_empty() {
+            let text = snapshot.text();
+            let closest_start = text
+                .match_indices(self.anchor_text.as_str())
+                .map(|(offset, _)| snapshot.offset_to_point(offset))
+                .min_by_key(|start| start.row.abs_diff(self.range.start.row));
+            if let Some(start) = closest_start {
+                // Locations come from other collaborators, so don't assume they're well formed.
+                let row_count = self.range.end.row.saturating_sub(self.range.start.row);
+                let end = if row_count == 0 {
+                    let column_count = self
+                        .range
+                        .end
+                        .column
+                        .saturating_sub(self.range.start.column);
+                    Point::new(start.row, start.column.saturating_add(column_count))
+                } else {
+                    Point::new(start.row.saturating_add(row_count), self.range.end.column)
+                };
+                return ResolvedReviewThreadLocation {
+                    range: start..snapshot.clip_point(end, Bias::Right),
+                    outdated: false,
+                };
+            }
+        }
+
+        ResolvedReviewThreadLocation {
+            range: clipped_range,
+            outdated: true,
+        }
+    }
+
+    pub fn to_proto(&self) -> proto::ReviewThreadLocation {
+        proto::ReviewThreadLocation {
+            start_row: self.range.start.row,
+            start_column: self.range.start.column,
+            end_row: self.range.end.row,
+            end_column: self.range.end.column,
+            anchor_text: self.anchor_text.clone(),
+            blob_sha: self.blob_sha.clone(),
+        }
+    }
+
+    pub fn from_proto(location: proto::ReviewThreadLocation) -> Self {
+        Self {
+            range: Point::new(location.start_row, location.start_column)
+                ..Point::new(location.end_row, location.end_column),
+            anchor_text: location.anchor_text,
+            blob_sha: location.blob_sha,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use text::{Buffer, BufferId};
+
+    fn snapshot(text: &str) -> BufferSnapshot {
+        Buffer::new(0, BufferId::new(1).unwrap(), text).snapshot()
+    }
+
+    #[test]
+    fn test_resolve_review_thread_location() {
+        let original = snapshot("fn a() {}\n\nfn main() {\n    a();\n}\n");
+        let location = ReviewThreadLocation::new(
+            Point::new(2, 3)..Point::new(3, 7),
+            &original,
+            Some("1111111111111111111111111111111111111111".into()),
+        );
+        assert_eq!(location.anchor_text, "main() {\n    a()");
+
+        // The file is unchanged.
+        assert_eq!(
+            location.resolve(&original, None),
+            ResolvedReviewThreadLocation {
+                range: Point::new(2, 3)..Point::new(3, 7),
+                outdated: false,
+            }
+        );
+
+        // Lines were inserted above the range, so the nearest occurrence of its text is used.
+        let edited = snapshot("fn main() {}\n\nfn a() {}\n\n\nfn main() {\n    a();\n}\n");
+        assert_eq!(
+            location.resolve(&edited, None),
+            ResolvedReviewThreadLocation {
+                range: Point::new(5, 3)..Point::new(6, 7),
+                outdated: false,
+            }
+        );
+
+        // The file's git blob matches the one the thread was created on, even though
+        // the anchor text isn't there anymore.
+        let same_blob = snapshot("fn a() {}\n\nfn b() {\n    a();\n}\n");
+        assert_eq!(
+            location.resolve(&same_blob, Some("1111111111111111111111111111111111111111")),
+            ResolvedReviewThreadLocation {
+                range: Point::new(2, 3)..Point::new(3, 7),
+                outdated: false,
+            }
+        );
+
+        // The text was removed, so the original range is used, clipped to the file.
+        let removed = snapshot("fn a() {}\n\nfn b");
+        assert_eq!(
+            location.resolve(&removed, Some("2222222222222222222222222222222222222222")),
+            ResolvedReviewThreadLocation {
+                range: Point::new(2, 3)..Point::new(2, 4),
+                outdated: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_resolve_malformed_review_thread_location() {
+        // A range that ends before it starts is resolved without panicking.
+        let location = ReviewThreadLocation {
+            range: Point::new(3, 0)..Point::new(1, 2),
+            anchor_text: "main".into(),
+            blob_sha: None,
+        };
+        assert_eq!(
+            location.resolve(&snapshot("fn a() {}\n\nfn main() {}\n"), None),
+            ResolvedReviewThreadLocation {
+                range: Point::new(2, 3)..Point::new(2, 5),
+                outdated: false,
+            }
+        );
+    }
+}
@@ -86,11 +86,15 @@ actions!(client, [SignIn, SignOut, Reconnect]);
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct ClientSettingsContent {
     server_url: Option<String>,
+    rpc_url: Option<String>,
 }
 
 #[derive(Deserialize)]
 pub struct ClientSettings {
     pub server_url: String,
+    /// The URL of the collaboration server to connect to. When unset, it is looked up
+    /// from the `server_url`, which is how Zed's hosted servers are found.
+    pub rpc_url: Option<String>,
 }
 
 impl Settings for ClientSettings {
@@ -1058,6 +1062,7 @@ impl Client {
         &self,
         http: Arc<HttpClientWithUrl>,
         release_channel: Option<ReleaseChannel>,
+        configured_url: Option<String>,
     ) -> impl Future<Output = Result<url::Url>> + use<> {
         #[cfg(any(test, feature = "test-support"))]
         let url_override = self.rpc_url.read().clone();
@@ -1072,6 +1077,10 @@ impl Client {
                 return Url::parse(url).context("invalid rpc url");
             }
 
+            if let Some(url) = configured_url {
+                return Url::parse(&url).context("invalid rpc_url setting");
+            }
+
             let mut url = http.build_url("/rpc");
             if let Some(preview_param) =
                 release_channel.and_then(|channel| channel.release_query_param())
@@ -1111,10 +1120,15 @@ impl Client {
             .ok()
             .unwrap_or_default();
 
+        let configured_rpc_url = cx
+            .update(|cx| ClientSettings::get_global(cx).rpc_url.clone())
+            .ok()
+            .flatten();
+
         let http = self.http.clone();
         let proxy = http.proxy().cloned();
         let credentials = credentials.clone();
-        let rpc_url = self.rpc_url(http, release_channel);
+        let rpc_url = self.rpc_url(http, release_channel, configured_rpc_url);
         let system_id = self.telemetry.system_id();
         let metrics_id = self.telemetry.metrics_id();
         cx.spawn(async move |cx| {
@@ -1392,7 +1406,7 @@ impl Client {
 
         // Use the collab server's admin API to retrieve the ID
         // of the impersonated user.
-        let mut url = self.rpc_url(http.clone(), None).await?;
+        let mut url = self.rpc_url(http.clone(), None, None).await?;
         url.set_path("/user");
         url.set_query(Some(
             &query_params
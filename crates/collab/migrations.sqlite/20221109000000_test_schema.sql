@@ -13,11 +13,15 @@ CREATE TABLE "users" (
     "github_user_id" INTEGER NOT NULL,
     "accepted_tos_at" TIMESTAMP WITHOUT TIME ZONE,
     "github_user_created_at" TIMESTAMP WITHOUT TIME ZONE,
-    "custom_llm_monthly_allowance_in_cents" INTEGER
+    "custom_llm_monthly_allowance_in_cents" INTEGER,
+    "password_hash" VARCHAR,
+    "oidc_subject" VARCHAR
 );
 
 CREATE UNIQUE INDEX "index_users_github_login" ON "users" ("github_login");
 
+CREATE UNIQUE INDEX "uix_users_on_oidc_subject" ON "users" ("oidc_subject");
+
 CREATE UNIQUE INDEX "index_invite_code_users" ON "users" ("invite_code");
 
 CREATE INDEX "index_users_on_email_address" ON "users" ("email_address");
@@ -545,3 +549,50 @@ CREATE TABLE IF NOT EXISTS "breakpoints" (
 );
 
 CREATE INDEX "index_breakpoints_on_project_id" ON "breakpoints" ("project_id");
+
+CREATE TABLE IF NOT EXISTS "review_threads" (
+    "id" INTEGER PRIMARY KEY AUTOINCREMENT,
+    "channel_id" INTEGER NOT NULL REFERENCES channels (id) ON DELETE CASCADE,
+    "path" TEXT NOT NULL,
+    "start_row" INTEGER NOT NULL,
+    "start_column" INTEGER NOT NULL,
+    "end_row" INTEGER NOT NULL,
+    "end_column" INTEGER NOT NULL,
+    "anchor_text" TEXT NOT NULL,
+    "blob_sha" VARCHAR NULL,
+    "created_by" INTEGER NOT NULL REFERENCES users (id),
+    "created_at" TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "resolved_by" INTEGER NULL REFERENCES users (id),
+    "resolved_at" TIMESTAMP NULL
+);
+
+CREATE INDEX "index_review_threads_on_channel_id_and_path" ON "review_threads" ("channel_id", "path");
+
+CREATE TABLE IF NOT EXISTS "review_comments" (
+    "id" INTEGER PRIMARY KEY AUTOINCREMENT,
+    "thread_id" INTEGER NOT NULL REFERENCES review_threads (id) ON DELETE CASCADE,
+    "sender_id" INTEGER NOT NULL REFERENCES users (id),
+    "body" TEXT NOT NULL,
+    "sent_at" TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX "index_review_comments_on_thread_id" ON "review_comments" ("thread_id");
+
+CREATE TABLE IF NOT EXISTS "channel_buffer_revisions" (
+    "id" INTEGER PRIMARY KEY AUTOINCREMENT,
+    "buffer_id" INTEGER NOT NULL REFERENCES buffers (id) ON DELETE CASCADE,
+    "text" TEXT NOT NULL,
+    "created_at" TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX "index_channel_buffer_revisions_on_buffer_id" ON "channel_buffer_revisions" ("buffer_id");
+
+CREATE TABLE IF NOT EXISTS "channel_buffer_revision_authors" (
+    "id" INTEGER PRIMARY KEY AUTOINCREMENT,
+    "buffer_id" INTEGER NOT NULL REFERENCES buffers (id) ON DELETE CASCADE,
+    "revision_id" INTEGER NULL REFERENCES channel_buffer_revisions (id) ON DELETE CASCADE,
+    "user_id" INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    "created_at" TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX "index_channel_buffer_revision_authors_on_buffer_id_and_revision_id" ON "channel_buffer_revision_authors" ("buffer_id", "revision_id");
@@ -0,0 +1,27 @@
+CREATE TABLE IF NOT EXISTS "review_threads" (
+    "id" SERIAL PRIMARY KEY,
+    "channel_id" INTEGER NOT NULL REFERENCES channels (id) ON DELETE CASCADE,
+    "path" TEXT NOT NULL,
+    "start_row" INTEGER NOT NULL,
+    "start_column" INTEGER NOT NULL,
+    "end_row" INTEGER NOT NULL,
+    "end_column" INTEGER NOT NULL,
+    "anchor_text" TEXT NOT NULL,
+    "blob_sha" VARCHAR NULL,
+    "created_by" INTEGER NOT NULL REFERENCES users (id),
+    "created_at" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    "resolved_by" INTEGER NULL REFERENCES users (id),
+    "resolved_at" TIMESTAMP WITHOUT TIME ZONE NULL
+);
+
+CREATE INDEX "index_review_threads_on_channel_id_and_path" ON "review_threads" ("channel_id", "path");
+
+CREATE TABLE IF NOT EXISTS "review_comments" (
+    "id" SERIAL PRIMARY KEY,
+    "thread_id" INTEGER NOT NULL REFERENCES review_threads (id) ON DELETE CASCADE,
+    "sender_id" INTEGER NOT NULL REFERENCES users (id),
+    "body" TEXT NOT NULL,
+    "sent_at" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX "index_review_comments_on_thread_id" ON "review_comments" ("thread_id");
@@ -0,0 +1,6 @@
+alter table users
+    add column password_hash varchar;
+alter table users
+    add column oidc_subject varchar;
+
+create unique index uix_users_on_oidc_subject on users (oidc_subject);
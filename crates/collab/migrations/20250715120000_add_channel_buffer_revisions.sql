@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS "channel_buffer_revisions" (
+    "id" SERIAL PRIMARY KEY,
+    "buffer_id" INTEGER NOT NULL REFERENCES buffers (id) ON DELETE CASCADE,
+    "text" TEXT NOT NULL,
+    "created_at" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX "index_channel_buffer_revisions_on_buffer_id" ON "channel_buffer_revisions" ("buffer_id");
+
+CREATE TABLE IF NOT EXISTS "channel_buffer_revision_authors" (
+    "id" SERIAL PRIMARY KEY,
+    "buffer_id" INTEGER NOT NULL REFERENCES buffers (id) ON DELETE CASCADE,
+    "revision_id" INTEGER NULL REFERENCES channel_buffer_revisions (id) ON DELETE CASCADE,
+    "user_id" INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    "created_at" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX "index_channel_buffer_revision_authors_on_buffer_id_and_revision_id" ON "channel_buffer_revision_authors" ("buffer_id", "revision_id");
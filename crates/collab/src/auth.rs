@@ -14,7 +14,7 @@ use prometheus::{Histogram, exponential_buckets, register_histogram};
 pub use rpc::auth::random_token;
 use scrypt::{
     Scrypt,
-    password_hash::{PasswordHash, PasswordVerifier},
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
 };
 use serde::{Deserialize, Serialize};
 use sha2::Digest;
@@ -166,6 +166,36 @@ pub fn encrypt_access_token(access_token: &str, public_key: String) -> Result<St
     Ok(encrypted_access_token)
 }
 
+/// Hashes the password of a local user on a self-hosted server.
+pub fn hash_password(password: &str) -> Result<String> {
+    // Avoid slow hashing in tests.
+    let params = if cfg!(test) {
+        scrypt::Params::new(1, 1, 1, scrypt::Params::RECOMMENDED_LEN).unwrap()
+    } else {
+        scrypt::Params::recommended()
+    };
+
+    Ok(Scrypt
+        .hash_password_customized(
+            password.as_bytes(),
+            None,
+            None,
+            params,
+            &SaltString::generate(rand::thread_rng()),
+        )
+        .map_err(anyhow::Error::new)?
+        .to_string())
+}
+
+/// Checks a password against a hash created by [`hash_password`].
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash).is_ok_and(|password_hash| {
+        Scrypt
+            .verify_password(password.as_bytes(), &password_hash)
+            .is_ok()
+    })
+}
+
 pub struct VerifyAccessTokenResult {
     pub is_valid: bool,
     pub impersonator_id: Option<UserId>,
@@ -228,7 +258,6 @@ pub async fn verify_access_token(
 #[cfg(test)]
 mod test {
     use rand::thread_rng;
-    use scrypt::password_hash::{PasswordHasher, SaltString};
     use sea_orm::EntityTrait;
 
     use super::*;
@@ -322,6 +351,21 @@ mod test {
         ));
     }
 
+    #[test]
+    fn test_verify_password() {
+        let password_hash = hash_password("correct horse battery staple").unwrap();
+        assert!(password_hash.starts_with("$scrypt$"));
+        assert!(verify_password(
+            "correct horse battery staple",
+            &password_hash
+        ));
+        assert!(!verify_password("Tr0ub4dor&3", &password_hash));
+        assert!(!verify_password(
+            "correct horse battery staple",
+            "not a hash"
+        ));
+    }
+
     async fn create_previous_access_token(
         user_id: UserId,
         impersonated_user_id: Option<UserId>,
@@ -486,6 +486,12 @@ pub struct CreatedChannelMessage {
     pub notifications: NotificationBatch,
 }
 
+pub struct UpdatedReviewThread {
+    pub thread: proto::ReviewThread,
+    pub channel: channel::Model,
+    pub notifications: NotificationBatch,
+}
+
 pub struct UpdatedChannelMessage {
     pub message_id: MessageId,
     pub participant_connection_ids: Vec<ConnectionId>,
@@ -75,6 +75,7 @@ id_type!(BillingSubscriptionId);
 id_type!(BillingPreferencesId);
 id_type!(BufferId);
 id_type!(ChannelBufferCollaboratorId);
+id_type!(ChannelBufferRevisionId);
 id_type!(ChannelChatParticipantId);
 id_type!(ChannelId);
 id_type!(ChannelMemberId);
@@ -89,6 +90,8 @@ id_type!(NotificationKindId);
 id_type!(ProjectCollaboratorId);
 id_type!(ProjectId);
 id_type!(ReplicaId);
+id_type!(ReviewCommentId);
+id_type!(ReviewThreadId);
 id_type!(RoomId);
 id_type!(RoomParticipantId);
 id_type!(ServerId);
@@ -14,6 +14,7 @@ pub mod messages;
 pub mod notifications;
 pub mod processed_stripe_events;
 pub mod projects;
+pub mod review_threads;
 pub mod rooms;
 pub mod servers;
 pub mod users;
@@ -2,6 +2,10 @@ use super::*;
 use anyhow::Context as _;
 use prost::Message;
 use text::{EditOperation, UndoOperation};
+use time::OffsetDateTime;
+
+/// How long a channel buffer can be edited before its text is saved as a new revision.
+const CHANNEL_BUFFER_REVISION_INTERVAL: time::Duration = time::Duration::minutes(10);
 
 pub struct LeftChannelBuffer {
     pub channel_id: ChannelId,
@@ -487,6 +491,11 @@ impl Database {
                 .filter_map(|op| operation_to_storage(op, &buffer, serialization_version))
                 .collect::<Vec<_>>();
 
+            if requires_write_permission {
+                self.record_channel_buffer_author(buffer.id, user, &tx)
+                    .await?;
+            }
+
             let max_version;
 
             if !operations.is_empty() {
@@ -707,6 +716,16 @@ impl Database {
         let base_text = text_buffer.text();
         let epoch = buffer.epoch + 1;
 
+        // Everyone has closed the buffer, so save the edits made since the previous revision.
+        if self
+            .pending_channel_buffer_revision_started_at(buffer.id, tx)
+            .await?
+            .is_some()
+        {
+            self.create_channel_buffer_revision(buffer.id, base_text.clone(), tx)
+                .await?;
+        }
+
         buffer_snapshot::Model {
             buffer_id: buffer.id,
             epoch,
@@ -731,6 +750,203 @@ impl Database {
         Ok(())
     }
 
+    /// Returns the saved revisions of a channel's notes, oldest first.
+    pub async fn get_channel_buffer_revisions(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+    ) -> Result<Vec<proto::ChannelBufferRevision>> {
+        #[derive(Debug, FromQueryResult)]
+        struct Revision {
+            id: ChannelBufferRevisionId,
+            created_at: PrimitiveDateTime,
+        }
+
+        self.transaction(|tx| async move {
+            let channel = self.get_channel_internal(channel_id, &tx).await?;
+            self.check_user_is_channel_participant(&channel, user_id, &tx)
+                .await?;
+            let buffer = self.get_channel_buffer(channel_id, &tx).await?;
+
+            let revisions = channel_buffer_revision::Entity::find()
+                .filter(channel_buffer_revision::Column::BufferId.eq(buffer.id))
+                .select_only()
+                .column(channel_buffer_revision::Column::Id)
+                .column(channel_buffer_revision::Column::CreatedAt)
+                .order_by_asc(channel_buffer_revision::Column::Id)
+                .into_model::<Revision>()
+                .all(&*tx)
+                .await?;
+
+            let mut author_ids_by_revision =
+                HashMap::<ChannelBufferRevisionId, Vec<u64>>::default();
+            let mut authors = channel_buffer_revision_author::Entity::find()
+                .filter(channel_buffer_revision_author::Column::BufferId.eq(buffer.id))
+                .filter(channel_buffer_revision_author::Column::RevisionId.is_not_null())
+                .order_by_asc(channel_buffer_revision_author::Column::Id)
+                .stream(&*tx)
+                .await?;
+            while let Some(author) = authors.next().await {
+                let author = author?;
+                if let Some(revision_id) = author.revision_id {
+                    author_ids_by_revision
+                        .entry(revision_id)
+                        .or_default()
+                        .push(author.user_id.to_proto());
+                }
+            }
+            drop(authors);
+
+            Ok(revisions
+                .into_iter()
+                .map(|revision| proto::ChannelBufferRevision {
+                    id: revision.id.to_proto(),
+                    timestamp: revision.created_at.assume_utc().unix_timestamp() as u64,
+                    author_ids: author_ids_by_revision
+                        .remove(&revision.id)
+                        .unwrap_or_default(),
+                })
+                .collect())
+        })
+        .await
+    }
+
+    /// Returns the text of a channel's notes as of the given revision.
+    pub async fn get_channel_buffer_revision_text(
+        &self,
+        channel_id: ChannelId,
+        revision_id: ChannelBufferRevisionId,
+        user_id: UserId,
+    ) -> Result<String> {
+        self.transaction(|tx| async move {
+            let channel = self.get_channel_internal(channel_id, &tx).await?;
+            self.check_user_is_channel_participant(&channel, user_id, &tx)
+                .await?;
+            let buffer = self.get_channel_buffer(channel_id, &tx).await?;
+
+            let revision = channel_buffer_revision::Entity::find_by_id(revision_id)
+                .filter(channel_buffer_revision::Column::BufferId.eq(buffer.id))
+                .one(&*tx)
+                .await?
+                .context("no such revision")?;
+            Ok(revision.text)
+        })
+        .await
+    }
+
+    /// Records that a user is editing a channel buffer, so that they're credited as an
+    /// author of the next revision. This runs on every edit, so it's a single statement
+    /// that only inserts the author if they aren't pending already.
+    async fn record_channel_buffer_author(
+        &self,
+        buffer_id: BufferId,
+        user_id: UserId,
+        tx: &DatabaseTransaction,
+    ) -> Result<()> {
+        let now = OffsetDateTime::now_utc();
+        let now = PrimitiveDateTime::new(now.date(), now.time());
+        tx.execute(Statement::from_sql_and_values(
+            self.pool.get_database_backend(),
+            "
+            INSERT INTO channel_buffer_revision_authors (buffer_id, user_id, created_at)
+            SELECT $1, $2, $3
+            WHERE NOT EXISTS (
+                SELECT 1 FROM channel_buffer_revision_authors
+                WHERE buffer_id = $1 AND user_id = $2 AND revision_id IS NULL
+            )
+            ",
+            [buffer_id.into(), user_id.into(), now.into()],
+        ))
+        .await?;
+        Ok(())
+    }
+
+    /// Saves the text of channel buffers as new revisions, once the edits made to them
+    /// since their previous revision started long enough ago.
+    pub async fn save_channel_buffer_revisions(&self) -> Result<()> {
+        #[derive(Debug, Clone, Copy, EnumIter, DeriveColumn)]
+        enum QueryBufferIds {
+            BufferId,
+        }
+
+        self.transaction(|tx| async move {
+            let now = OffsetDateTime::now_utc();
+            let now = PrimitiveDateTime::new(now.date(), now.time());
+            let buffer_ids: Vec<BufferId> = channel_buffer_revision_author::Entity::find()
+                .select_only()
+                .column(channel_buffer_revision_author::Column::BufferId)
+                .distinct()
+                .filter(channel_buffer_revision_author::Column::RevisionId.is_null())
+                .filter(
+                    channel_buffer_revision_author::Column::CreatedAt
+                        .lte(now - CHANNEL_BUFFER_REVISION_INTERVAL),
+                )
+                .into_values::<_, QueryBufferIds>()
+                .all(&*tx)
+                .await?;
+
+            for buffer_id in buffer_ids {
+                let Some(buffer) = buffer::Entity::find_by_id(buffer_id).one(&*tx).await? else {
+                    continue;
+                };
+                let (base_text, operations, _) = self.get_buffer_state(&buffer, &tx).await?;
+                let mut text_buffer =
+                    text::Buffer::new(0, text::BufferId::new(1).unwrap(), base_text);
+                text_buffer.apply_ops(operations.into_iter().filter_map(operation_from_wire));
+                self.create_channel_buffer_revision(buffer.id, text_buffer.text(), &tx)
+                    .await?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Returns when the edits that haven't been saved in a revision yet were started.
+    async fn pending_channel_buffer_revision_started_at(
+        &self,
+        buffer_id: BufferId,
+        tx: &DatabaseTransaction,
+    ) -> Result<Option<PrimitiveDateTime>> {
+        Ok(channel_buffer_revision_author::Entity::find()
+            .filter(channel_buffer_revision_author::Column::BufferId.eq(buffer_id))
+            .filter(channel_buffer_revision_author::Column::RevisionId.is_null())
+            .order_by_asc(channel_buffer_revision_author::Column::CreatedAt)
+            .one(tx)
+            .await?
+            .map(|author| author.created_at))
+    }
+
+    /// Saves the given text as a revision, crediting everyone who edited the buffer
+    /// since the previous revision.
+    async fn create_channel_buffer_revision(
+        &self,
+        buffer_id: BufferId,
+        text: String,
+        tx: &DatabaseTransaction,
+    ) -> Result<()> {
+        let now = OffsetDateTime::now_utc();
+        let revision = channel_buffer_revision::ActiveModel {
+            id: ActiveValue::NotSet,
+            buffer_id: ActiveValue::Set(buffer_id),
+            text: ActiveValue::Set(text),
+            created_at: ActiveValue::Set(PrimitiveDateTime::new(now.date(), now.time())),
+        }
+        .insert(tx)
+        .await?;
+
+        channel_buffer_revision_author::Entity::update_many()
+            .filter(channel_buffer_revision_author::Column::BufferId.eq(buffer_id))
+            .filter(channel_buffer_revision_author::Column::RevisionId.is_null())
+            .set(channel_buffer_revision_author::ActiveModel {
+                revision_id: ActiveValue::Set(Some(revision.id)),
+                ..Default::default()
+            })
+            .exec(tx)
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn observe_buffer_version(
         &self,
         buffer_id: BufferId,
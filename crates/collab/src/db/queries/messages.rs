@@ -1,7 +1,7 @@
 use super::*;
 use anyhow::Context as _;
 use rpc::Notification;
-use sea_orm::{SelectColumns, TryInsertResult};
+use sea_orm::{DbBackend, SelectColumns, TryInsertResult};
 use time::OffsetDateTime;
 use util::ResultExt;
 
@@ -112,6 +112,59 @@ impl Database {
         .await
     }
 
+    /// Searches the messages in the specified channel, returning the most recent matches.
+    ///
+    /// Use `before_message_id` to paginate through the matches.
+    pub async fn search_channel_messages(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+        query: &str,
+        count: usize,
+        before_message_id: Option<MessageId>,
+    ) -> Result<Vec<proto::ChannelMessage>> {
+        self.transaction(|tx| async move {
+            let channel = self.get_channel_internal(channel_id, &tx).await?;
+            self.check_user_is_channel_participant(&channel, user_id, &tx)
+                .await?;
+
+            let mut condition =
+                Condition::all().add(channel_message::Column::ChannelId.eq(channel_id));
+
+            if let Some(before_message_id) = before_message_id {
+                condition = condition.add(channel_message::Column::Id.lt(before_message_id));
+            }
+
+            if cfg!(any(test, feature = "sqlite"))
+                && self.pool.get_database_backend() == DbBackend::Sqlite
+            {
+                // SQLite doesn't have full-text search built in, so fall back to
+                // matching each of the words in the query.
+                for word in query.split_whitespace() {
+                    condition = condition.add(Expr::cust_with_values(
+                        "UPPER(body) LIKE ? ESCAPE '\\'",
+                        [format!("%{}%", escape_like(&word.to_uppercase()))],
+                    ));
+                }
+            } else {
+                condition = condition.add(Expr::cust_with_values(
+                    "to_tsvector('english', body) @@ websearch_to_tsquery('english', $1)",
+                    [query],
+                ));
+            }
+
+            let rows = channel_message::Entity::find()
+                .filter(condition)
+                .order_by_desc(channel_message::Column::Id)
+                .limit(count as u64)
+                .all(&*tx)
+                .await?;
+
+            self.load_channel_messages(rows, &tx).await
+        })
+        .await
+    }
+
     /// Returns the channel messages with the given IDs.
     pub async fn get_channel_messages_by_id(
         &self,
@@ -723,3 +776,14 @@ impl Database {
         .await
     }
 }
+
+fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        if matches!(character, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
@@ -0,0 +1,319 @@
+use super::*;
+use anyhow::Context as _;
+use time::OffsetDateTime;
+
+impl Database {
+    /// Returns the review threads attached to the given file in a channel.
+    pub async fn get_review_threads(
+        &self,
+        channel_id: ChannelId,
+        path: &str,
+        user_id: UserId,
+    ) -> Result<Vec<proto::ReviewThread>> {
+        self.transaction(|tx| async move {
+            let channel = self.get_channel_internal(channel_id, &tx).await?;
+            self.check_user_is_channel_participant(&channel, user_id, &tx)
+                .await?;
+
+            let threads = review_thread::Entity::find()
+                .filter(review_thread::Column::ChannelId.eq(channel_id))
+                .filter(review_thread::Column::Path.eq(path))
+                .order_by_asc(review_thread::Column::Id)
+                .all(&*tx)
+                .await?;
+
+            self.load_review_threads(threads, &tx).await
+        })
+        .await
+    }
+
+    /// Starts a new review thread on a range of a file, with an initial comment.
+    pub async fn create_review_thread(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+        path: &str,
+        location: &proto::ReviewThreadLocation,
+        body: &str,
+        mentions: &[proto::ChatMention],
+        timestamp: OffsetDateTime,
+    ) -> Result<UpdatedReviewThread> {
+        validate_review_thread_location(location)?;
+        self.transaction(|tx| async move {
+            let channel = self.get_channel_internal(channel_id, &tx).await?;
+            self.check_user_is_channel_member(&channel, user_id, &tx)
+                .await?;
+
+            let thread = review_thread::ActiveModel {
+                id: ActiveValue::NotSet,
+                channel_id: ActiveValue::Set(channel_id),
+                path: ActiveValue::Set(path.to_string()),
+                start_row: ActiveValue::Set(location.start_row as i32),
+                start_column: ActiveValue::Set(location.start_column as i32),
+                end_row: ActiveValue::Set(location.end_row as i32),
+                end_column: ActiveValue::Set(location.end_column as i32),
+                anchor_text: ActiveValue::Set(location.anchor_text.clone()),
+                blob_sha: ActiveValue::Set(location.blob_sha.clone()),
+                created_by: ActiveValue::Set(user_id),
+                created_at: ActiveValue::Set(primitive_timestamp(timestamp)),
+                resolved_by: ActiveValue::Set(None),
+                resolved_at: ActiveValue::Set(None),
+            }
+            .insert(&*tx)
+            .await?;
+
+            let notifications = self
+                .create_review_comment(&thread, user_id, body, mentions, timestamp, &tx)
+                .await?;
+            self.updated_review_thread(thread, channel, notifications, &tx)
+                .await
+        })
+        .await
+    }
+
+    /// Adds a comment to an existing review thread.
+    pub async fn reply_to_review_thread(
+        &self,
+        channel_id: ChannelId,
+        thread_id: ReviewThreadId,
+        user_id: UserId,
+        body: &str,
+        mentions: &[proto::ChatMention],
+        timestamp: OffsetDateTime,
+    ) -> Result<UpdatedReviewThread> {
+        self.transaction(|tx| async move {
+            let channel = self.get_channel_internal(channel_id, &tx).await?;
+            self.check_user_is_channel_member(&channel, user_id, &tx)
+                .await?;
+            let thread = self
+                .get_review_thread_internal(channel_id, thread_id, &tx)
+                .await?;
+
+            let notifications = self
+                .create_review_comment(&thread, user_id, body, mentions, timestamp, &tx)
+                .await?;
+            self.updated_review_thread(thread, channel, notifications, &tx)
+                .await
+        })
+        .await
+    }
+
+    /// Marks a review thread as resolved, or reopens it.
+    pub async fn set_review_thread_resolved(
+        &self,
+        channel_id: ChannelId,
+        thread_id: ReviewThreadId,
+        user_id: UserId,
+        resolved: bool,
+        timestamp: OffsetDateTime,
+    ) -> Result<UpdatedReviewThread> {
+        self.transaction(|tx| async move {
+            let channel = self.get_channel_internal(channel_id, &tx).await?;
+            self.check_user_is_channel_member(&channel, user_id, &tx)
+                .await?;
+            let thread = self
+                .get_review_thread_internal(channel_id, thread_id, &tx)
+                .await?;
+
+            let (resolved_by, resolved_at) = if resolved {
+                (Some(user_id), Some(primitive_timestamp(timestamp)))
+            } else {
+                (None, None)
+            };
+            let thread = review_thread::ActiveModel {
+                id: ActiveValue::Unchanged(thread.id),
+                resolved_by: ActiveValue::Set(resolved_by),
+                resolved_at: ActiveValue::Set(resolved_at),
+                ..Default::default()
+            }
+            .update(&*tx)
+            .await?;
+
+            self.updated_review_thread(thread, channel, Vec::new(), &tx)
+                .await
+        })
+        .await
+    }
+
+    /// Updates the location of a review thread, after the file it is attached to has changed.
+    pub async fn move_review_thread(
+        &self,
+        channel_id: ChannelId,
+        thread_id: ReviewThreadId,
+        user_id: UserId,
+        location: &proto::ReviewThreadLocation,
+    ) -> Result<UpdatedReviewThread> {
+        validate_review_thread_location(location)?;
+        self.transaction(|tx| async move {
+            let channel = self.get_channel_internal(channel_id, &tx).await?;
+            self.check_user_is_channel_member(&channel, user_id, &tx)
+                .await?;
+            let thread = self
+                .get_review_thread_internal(channel_id, thread_id, &tx)
+                .await?;
+
+            let thread = review_thread::ActiveModel {
+                id: ActiveValue::Unchanged(thread.id),
+                start_row: ActiveValue::Set(location.start_row as i32),
+                start_column: ActiveValue::Set(location.start_column as i32),
+                end_row: ActiveValue::Set(location.end_row as i32),
+                end_column: ActiveValue::Set(location.end_column as i32),
+                anchor_text: ActiveValue::Set(location.anchor_text.clone()),
+                blob_sha: ActiveValue::Set(location.blob_sha.clone()),
+                ..Default::default()
+            }
+            .update(&*tx)
+            .await?;
+
+            self.updated_review_thread(thread, channel, Vec::new(), &tx)
+                .await
+        })
+        .await
+    }
+
+    async fn get_review_thread_internal(
+        &self,
+        channel_id: ChannelId,
+        thread_id: ReviewThreadId,
+        tx: &DatabaseTransaction,
+    ) -> Result<review_thread::Model> {
+        Ok(review_thread::Entity::find_by_id(thread_id)
+            .filter(review_thread::Column::ChannelId.eq(channel_id))
+            .one(tx)
+            .await?
+            .context("no such review thread")?)
+    }
+
+    async fn create_review_comment(
+        &self,
+        thread: &review_thread::Model,
+        user_id: UserId,
+        body: &str,
+        mentions: &[proto::ChatMention],
+        timestamp: OffsetDateTime,
+        tx: &DatabaseTransaction,
+    ) -> Result<NotificationBatch> {
+        let comment = review_comment::ActiveModel {
+            id: ActiveValue::NotSet,
+            thread_id: ActiveValue::Set(thread.id),
+            sender_id: ActiveValue::Set(user_id),
+            body: ActiveValue::Set(body.to_string()),
+            sent_at: ActiveValue::Set(primitive_timestamp(timestamp)),
+        }
+        .insert(tx)
+        .await?;
+
+        let mentioned_user_ids = mentions
+            .iter()
+            .map(|mention| UserId::from_proto(mention.user_id))
+            .filter(|mentioned_user_id| *mentioned_user_id != user_id)
+            .collect::<BTreeSet<_>>();
+        let mut notifications = NotificationBatch::new();
+        for mentioned_user_id in mentioned_user_ids {
+            notifications.extend(
+                self.create_notification(
+                    mentioned_user_id,
+                    rpc::Notification::ReviewCommentMention {
+                        comment_id: comment.id.to_proto(),
+                        thread_id: thread.id.to_proto(),
+                        sender_id: user_id.to_proto(),
+                        channel_id: thread.channel_id.to_proto(),
+                        path: thread.path.clone(),
+                    },
+                    false,
+                    tx,
+                )
+                .await?,
+            );
+        }
+        Ok(notifications)
+    }
+
+    async fn updated_review_thread(
+        &self,
+        thread: review_thread::Model,
+        channel: channel::Model,
+        notifications: NotificationBatch,
+        tx: &DatabaseTransaction,
+    ) -> Result<UpdatedReviewThread> {
+        let thread = self
+            .load_review_threads(vec![thread], tx)
+            .await?
+            .pop()
+            .context("review thread not found")?;
+        Ok(UpdatedReviewThread {
+            thread,
+            channel,
+            notifications,
+        })
+    }
+
+    async fn load_review_threads(
+        &self,
+        threads: Vec<review_thread::Model>,
+        tx: &DatabaseTransaction,
+    ) -> Result<Vec<proto::ReviewThread>> {
+        let mut comments_by_thread =
+            HashMap::<ReviewThreadId, Vec<proto::ReviewComment>>::default();
+        let mut comments = review_comment::Entity::find()
+            .filter(review_comment::Column::ThreadId.is_in(threads.iter().map(|thread| thread.id)))
+            .order_by_asc(review_comment::Column::Id)
+            .stream(tx)
+            .await?;
+        while let Some(comment) = comments.next().await {
+            let comment = comment?;
+            comments_by_thread
+                .entry(comment.thread_id)
+                .or_default()
+                .push(proto::ReviewComment {
+                    id: comment.id.to_proto(),
+                    sender_id: comment.sender_id.to_proto(),
+                    body: comment.body,
+                    timestamp: comment.sent_at.assume_utc().unix_timestamp() as u64,
+                });
+        }
+        drop(comments);
+
+        Ok(threads
+            .into_iter()
+            .map(|thread| proto::ReviewThread {
+                id: thread.id.to_proto(),
+                channel_id: thread.channel_id.to_proto(),
+                location: Some(proto::ReviewThreadLocation {
+                    start_row: thread.start_row as u32,
+                    start_column: thread.start_column as u32,
+                    end_row: thread.end_row as u32,
+                    end_column: thread.end_column as u32,
+                    anchor_text: thread.anchor_text,
+                    blob_sha: thread.blob_sha,
+                }),
+                path: thread.path,
+                author_id: thread.created_by.to_proto(),
+                resolved_by: thread.resolved_by.map(|user_id| user_id.to_proto()),
+                comments: comments_by_thread.remove(&thread.id).unwrap_or_default(),
+            })
+            .collect())
+    }
+}
+
+fn primitive_timestamp(timestamp: OffsetDateTime) -> PrimitiveDateTime {
+    let timestamp = timestamp.to_offset(time::UtcOffset::UTC);
+    PrimitiveDateTime::new(timestamp.date(), timestamp.time())
+}
+
+/// Checks that a review thread's range doesn't end before it starts, and that its
+/// rows and columns fit in the database.
+fn validate_review_thread_location(location: &proto::ReviewThreadLocation) -> Result<()> {
+    let start = (location.start_row, location.start_column);
+    let end = (location.end_row, location.end_column);
+    if start > end {
+        Err(anyhow!("review thread range ends before it starts"))?;
+    }
+    if [end.0, start.1, end.1]
+        .into_iter()
+        .any(|value| value > i32::MAX as u32)
+    {
+        Err(anyhow!("review thread range is out of bounds"))?;
+    }
+    Ok(())
+}
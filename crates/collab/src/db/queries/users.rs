@@ -220,6 +220,125 @@ impl Database {
         Ok(None)
     }
 
+    /// Creates a user that isn't backed by a GitHub account, for self-hosted servers.
+    pub async fn create_local_user(
+        &self,
+        login: &str,
+        email_address: Option<&str>,
+        name: Option<&str>,
+        admin: bool,
+    ) -> Result<User> {
+        self.transaction(|tx| async move {
+            self.create_local_user_tx(login, email_address, name, admin, None, &tx)
+                .await
+        })
+        .await
+    }
+
+    /// Local users are given negative GitHub user IDs, so that they can never collide
+    /// with a real GitHub account.
+    async fn create_local_user_tx(
+        &self,
+        login: &str,
+        email_address: Option<&str>,
+        name: Option<&str>,
+        admin: bool,
+        oidc_subject: Option<&str>,
+        tx: &DatabaseTransaction,
+    ) -> Result<User> {
+        let min_github_user_id: Option<i32> = user::Entity::find()
+            .select_only()
+            .column_as(user::Column::GithubUserId.min(), "min_github_user_id")
+            .into_tuple::<Option<i32>>()
+            .one(tx)
+            .await?
+            .flatten();
+        let github_user_id = min_github_user_id.unwrap_or(0).min(0) - 1;
+
+        Ok(user::Entity::insert(user::ActiveModel {
+            email_address: ActiveValue::set(email_address.map(|email| email.into())),
+            name: ActiveValue::set(name.map(|name| name.into())),
+            github_login: ActiveValue::set(login.into()),
+            github_user_id: ActiveValue::set(github_user_id),
+            admin: ActiveValue::set(admin),
+            metrics_id: ActiveValue::set(Uuid::new_v4()),
+            oidc_subject: ActiveValue::set(oidc_subject.map(|subject| subject.into())),
+            ..Default::default()
+        })
+        .exec_with_returning(tx)
+        .await?)
+    }
+
+    /// Sets the password hash that a local user signs in with.
+    pub async fn set_user_password_hash(&self, id: UserId, password_hash: &str) -> Result<()> {
+        self.transaction(|tx| async move {
+            user::Entity::update_many()
+                .filter(user::Column::Id.eq(id))
+                .set(user::ActiveModel {
+                    password_hash: ActiveValue::set(Some(password_hash.into())),
+                    ..Default::default()
+                })
+                .exec(&*tx)
+                .await?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Sets whether the user is an administrator of the server.
+    pub async fn set_user_is_admin(&self, id: UserId, admin: bool) -> Result<()> {
+        self.transaction(|tx| async move {
+            user::Entity::update_many()
+                .filter(user::Column::Id.eq(id))
+                .set(user::ActiveModel {
+                    admin: ActiveValue::set(admin),
+                    ..Default::default()
+                })
+                .exec(&*tx)
+                .await?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Returns the user with the given subject identifier at the OIDC provider, creating
+    /// them the first time they sign in.
+    pub async fn get_or_create_user_by_oidc_subject(
+        &self,
+        oidc_subject: &str,
+        preferred_login: &str,
+        email_address: Option<&str>,
+        name: Option<&str>,
+    ) -> Result<User> {
+        self.transaction(|tx| async move {
+            if let Some(user) = user::Entity::find()
+                .filter(user::Column::OidcSubject.eq(oidc_subject))
+                .one(&*tx)
+                .await?
+            {
+                return Ok(user);
+            }
+
+            // Logins must be unique, but the provider's usernames may clash with
+            // existing local accounts, which must not be taken over.
+            let mut login = preferred_login.to_string();
+            let mut suffix = 1;
+            while user::Entity::find()
+                .filter(user::Column::GithubLogin.eq(login.as_str()))
+                .one(&*tx)
+                .await?
+                .is_some()
+            {
+                suffix += 1;
+                login = format!("{preferred_login}-{suffix}");
+            }
+
+            self.create_local_user_tx(&login, email_address, name, false, Some(oidc_subject), &tx)
+                .await
+        })
+        .await
+    }
+
     /// get_all_users returns the next page of users. To get more call again with
     /// the same limit and the page incremented by 1.
     pub async fn get_all_users(&self, page: u32, limit: u32) -> Result<Vec<User>> {
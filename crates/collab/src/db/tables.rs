@@ -7,6 +7,8 @@ pub mod buffer_operation;
 pub mod buffer_snapshot;
 pub mod channel;
 pub mod channel_buffer_collaborator;
+pub mod channel_buffer_revision;
+pub mod channel_buffer_revision_author;
 pub mod channel_chat_participant;
 pub mod channel_member;
 pub mod channel_message;
@@ -28,6 +30,8 @@ pub mod project;
 pub mod project_collaborator;
 pub mod project_repository;
 pub mod project_repository_statuses;
+pub mod review_comment;
+pub mod review_thread;
 pub mod room;
 pub mod room_participant;
 pub mod server;
@@ -0,0 +1,34 @@
+use crate::db::{BufferId, ChannelBufferRevisionId, UserId};
+use sea_orm::entity::prelude::*;
+use time::PrimitiveDateTime;
+
+/// A user who edited a channel buffer. Authors without a revision have edited
+/// the buffer since its latest revision was saved.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "channel_buffer_revision_authors")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub buffer_id: BufferId,
+    pub revision_id: Option<ChannelBufferRevisionId>,
+    pub user_id: UserId,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::channel_buffer_revision::Entity",
+        from = "Column::RevisionId",
+        to = "super::channel_buffer_revision::Column::Id"
+    )]
+    Revision,
+}
+
+impl Related<super::channel_buffer_revision::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Revision.def()
+    }
+}
@@ -0,0 +1,32 @@
+use crate::db::{ReviewCommentId, ReviewThreadId, UserId};
+use sea_orm::entity::prelude::*;
+use time::PrimitiveDateTime;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "review_comments")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: ReviewCommentId,
+    pub thread_id: ReviewThreadId,
+    pub sender_id: UserId,
+    pub body: String,
+    pub sent_at: PrimitiveDateTime,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::review_thread::Entity",
+        from = "Column::ThreadId",
+        to = "super::review_thread::Column::Id"
+    )]
+    Thread,
+}
+
+impl Related<super::review_thread::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Thread.def()
+    }
+}
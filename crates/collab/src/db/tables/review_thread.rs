@@ -0,0 +1,48 @@
+use crate::db::{ChannelId, ReviewThreadId, UserId};
+use sea_orm::entity::prelude::*;
+use time::PrimitiveDateTime;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "review_threads")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: ReviewThreadId,
+    pub channel_id: ChannelId,
+    pub path: String,
+    pub start_row: i32,
+    pub start_column: i32,
+    pub end_row: i32,
+    pub end_column: i32,
+    pub anchor_text: String,
+    pub blob_sha: Option<String>,
+    pub created_by: UserId,
+    pub created_at: PrimitiveDateTime,
+    pub resolved_by: Option<UserId>,
+    pub resolved_at: Option<PrimitiveDateTime>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::channel::Entity",
+        from = "Column::ChannelId",
+        to = "super::channel::Column::Id"
+    )]
+    Channel,
+    #[sea_orm(has_many = "super::review_comment::Entity")]
+    Comments,
+}
+
+impl Related<super::channel::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Channel.def()
+    }
+}
+
+impl Related<super::review_comment::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Comments.def()
+    }
+}
@@ -23,6 +23,11 @@ pub struct Model {
     pub created_at: NaiveDateTime,
     pub accepted_tos_at: Option<NaiveDateTime>,
     pub custom_llm_monthly_allowance_in_cents: Option<i32>,
+    /// The hash of the user's password, for local accounts on self-hosted servers.
+    #[serde(skip)]
+    pub password_hash: Option<String>,
+    /// The user's subject identifier at the OIDC provider, for self-hosted servers.
+    pub oidc_subject: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -10,6 +10,7 @@ mod extension_tests;
 mod feature_flag_tests;
 mod message_tests;
 mod processed_stripe_event_tests;
+mod review_thread_tests;
 mod user_tests;
 
 use crate::migrations::run_database_migrations;
@@ -323,6 +323,128 @@ async fn test_channel_buffers_last_operations(db: &Database) {
     );
 }
 
+test_both_dbs!(
+    test_channel_buffer_revisions,
+    test_channel_buffer_revisions_postgres,
+    test_channel_buffer_revisions_sqlite
+);
+
+async fn test_channel_buffer_revisions(db: &Arc<Database>) {
+    let a_id = new_test_user(db, "user_a@example.com").await;
+    let b_id = new_test_user(db, "user_b@example.com").await;
+    let c_id = new_test_user(db, "user_c@example.com").await;
+    let server_id = db.create_server("production").await.unwrap();
+    let channel_id = db.create_root_channel("zed", a_id).await.unwrap();
+    db.invite_channel_member(channel_id, b_id, a_id, ChannelRole::Member)
+        .await
+        .unwrap();
+    db.respond_to_channel_invite(channel_id, b_id, true)
+        .await
+        .unwrap();
+
+    // Edits are saved as a revision once everyone has left the buffer.
+    let connection_a = new_test_connection(server_id);
+    let response = db
+        .join_channel_buffer(channel_id, a_id, connection_a)
+        .await
+        .unwrap();
+    let mut buffer = Buffer::new(
+        response.replica_id as u16,
+        text::BufferId::new(1).unwrap(),
+        response.base_text,
+    );
+    update_buffer(channel_id, a_id, db, vec![buffer.edit([(0..0, "hello")])]).await;
+    db.leave_channel_buffer(channel_id, connection_a)
+        .await
+        .unwrap();
+
+    // Opening and closing the buffer without editing it doesn't create a revision.
+    let connection_b = new_test_connection(server_id);
+    let response = db
+        .join_channel_buffer(channel_id, b_id, connection_b)
+        .await
+        .unwrap();
+    db.leave_channel_buffer(channel_id, connection_b)
+        .await
+        .unwrap();
+
+    let connection_a = new_test_connection(server_id);
+    let connection_b = new_test_connection(server_id);
+    db.join_channel_buffer(channel_id, a_id, connection_a)
+        .await
+        .unwrap();
+    let response_b = db
+        .join_channel_buffer(channel_id, b_id, connection_b)
+        .await
+        .unwrap();
+    assert_eq!(response_b.base_text, response.base_text);
+    let mut buffer = Buffer::new(
+        response_b.replica_id as u16,
+        text::BufferId::new(1).unwrap(),
+        response_b.base_text,
+    );
+    update_buffer(channel_id, b_id, db, vec![buffer.edit([(5..5, " world")])]).await;
+    update_buffer(channel_id, a_id, db, vec![buffer.edit([(0..1, "H")])]).await;
+    update_buffer(channel_id, b_id, db, vec![buffer.edit([(11..11, "!")])]).await;
+
+    // Recent edits aren't saved as a revision while the buffer is open.
+    db.save_channel_buffer_revisions().await.unwrap();
+    assert_eq!(
+        db.get_channel_buffer_revisions(channel_id, b_id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    db.leave_channel_buffer(channel_id, connection_a)
+        .await
+        .unwrap();
+    db.leave_channel_buffer(channel_id, connection_b)
+        .await
+        .unwrap();
+
+    let revisions = db
+        .get_channel_buffer_revisions(channel_id, b_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        revisions
+            .iter()
+            .map(|revision| revision.author_ids.clone())
+            .collect::<Vec<_>>(),
+        [
+            vec![a_id.to_proto()],
+            vec![b_id.to_proto(), a_id.to_proto()]
+        ]
+    );
+
+    let mut texts = Vec::new();
+    for revision in &revisions {
+        texts.push(
+            db.get_channel_buffer_revision_text(
+                channel_id,
+                ChannelBufferRevisionId::from_proto(revision.id),
+                b_id,
+            )
+            .await
+            .unwrap(),
+        );
+    }
+    assert_eq!(texts, ["hello", "Hello world!"]);
+
+    // Users outside of the channel can't see its history.
+    db.get_channel_buffer_revisions(channel_id, c_id)
+        .await
+        .unwrap_err();
+    db.get_channel_buffer_revision_text(
+        channel_id,
+        ChannelBufferRevisionId::from_proto(revisions[0].id),
+        c_id,
+    )
+    .await
+    .unwrap_err();
+}
+
 async fn update_buffer(
     channel_id: ChannelId,
     user_id: UserId,
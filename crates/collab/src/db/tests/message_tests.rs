@@ -65,6 +65,103 @@ async fn test_channel_message_retrieval(db: &Arc<Database>) {
     assert_eq!(messages, &all_messages[2..6]);
 }
 
+test_both_dbs!(
+    test_search_channel_messages,
+    test_search_channel_messages_postgres,
+    test_search_channel_messages_sqlite
+);
+
+async fn test_search_channel_messages(db: &Arc<Database>) {
+    let user_a = new_test_user(db, "user_a@example.com").await;
+    let user_b = new_test_user(db, "user_b@example.com").await;
+    let channel = db.create_root_channel("channel", user_a).await.unwrap();
+    let other_channel = db.create_root_channel("other", user_a).await.unwrap();
+    let owner_id = db.create_server("test").await.unwrap().0 as u32;
+    for (id, channel) in [channel, other_channel].into_iter().enumerate() {
+        db.join_channel_chat(
+            channel,
+            rpc::ConnectionId {
+                owner_id,
+                id: id as u32,
+            },
+            user_a,
+        )
+        .await
+        .unwrap();
+    }
+
+    let mut message_ids = Vec::new();
+    for (ix, (channel, body)) in [
+        (channel, "the build is broken"),
+        (channel, "who broke the BUILD?"),
+        (other_channel, "the build is fine here"),
+        (channel, "let's ship it"),
+        (channel, "100% of the builds pass now"),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        message_ids.push(
+            db.create_channel_message(
+                channel,
+                user_a,
+                body,
+                &[],
+                OffsetDateTime::now_utc(),
+                ix as u128,
+                None,
+            )
+            .await
+            .unwrap()
+            .message_id
+            .to_proto(),
+        );
+    }
+
+    // Matches are case-insensitive, and only include messages in the given channel.
+    let matches = db
+        .search_channel_messages(channel, user_a, "build", 10, None)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|message| message.id)
+        .collect::<Vec<_>>();
+    assert!(matches.starts_with(&[message_ids[0], message_ids[1]]));
+    assert!(!matches.contains(&message_ids[2]));
+    assert!(!matches.contains(&message_ids[3]));
+
+    // Every word in the query has to match.
+    let matches = db
+        .search_channel_messages(channel, user_a, "build broken", 10, None)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|message| message.id)
+        .collect::<Vec<_>>();
+    assert_eq!(matches, &[message_ids[0]]);
+
+    // Results are paginated from the most recent match.
+    let matches = db
+        .search_channel_messages(
+            channel,
+            user_a,
+            "build",
+            1,
+            Some(MessageId::from_proto(message_ids[1])),
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|message| message.id)
+        .collect::<Vec<_>>();
+    assert_eq!(matches, &[message_ids[0]]);
+
+    // Users outside of the channel can't search it.
+    db.search_channel_messages(channel, user_b, "build", 10, None)
+        .await
+        .unwrap_err();
+}
+
 test_both_dbs!(
     test_channel_message_nonces,
     test_channel_message_nonces_postgres,
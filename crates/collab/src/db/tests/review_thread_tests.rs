@@ -0,0 +1,175 @@
+use super::new_test_user;
+use crate::{
+    db::{ChannelRole, Database, ReviewThreadId},
+    test_both_dbs,
+};
+use channel::mentions_to_proto;
+use rpc::{Notification, proto};
+use std::sync::Arc;
+use time::OffsetDateTime;
+
+test_both_dbs!(
+    test_review_threads,
+    test_review_threads_postgres,
+    test_review_threads_sqlite
+);
+
+async fn test_review_threads(db: &Arc<Database>) {
+    let user_a = new_test_user(db, "user_a@example.com").await;
+    let user_b = new_test_user(db, "user_b@example.com").await;
+    let user_c = new_test_user(db, "user_c@example.com").await;
+    let channel = db.create_root_channel("channel", user_a).await.unwrap();
+    db.invite_channel_member(channel, user_b, user_a, ChannelRole::Member)
+        .await
+        .unwrap();
+    db.respond_to_channel_invite(channel, user_b, true)
+        .await
+        .unwrap();
+    db.invite_channel_member(channel, user_c, user_a, ChannelRole::Guest)
+        .await
+        .unwrap();
+    db.respond_to_channel_invite(channel, user_c, true)
+        .await
+        .unwrap();
+
+    let location = proto::ReviewThreadLocation {
+        start_row: 3,
+        start_column: 4,
+        end_row: 5,
+        end_column: 0,
+        anchor_text: "fn main() {".into(),
+        blob_sha: Some("8ab686eafeb1f44702738c8b0f24f2567c36da6d".into()),
+    };
+
+    // Creating a thread notifies the mentioned users.
+    let created = db
+        .create_review_thread(
+            channel,
+            user_a,
+            "zed/src/main.rs",
+            &location,
+            "what about @user_b?",
+            &mentions_to_proto(&[(11..18, user_b.to_proto())]),
+            OffsetDateTime::now_utc(),
+        )
+        .await
+        .unwrap();
+    let thread = created.thread;
+    assert_eq!(thread.path, "zed/src/main.rs");
+    assert_eq!(thread.location.as_ref(), Some(&location));
+    assert_eq!(thread.author_id, user_a.to_proto());
+    assert_eq!(thread.comments.len(), 1);
+    assert_eq!(created.notifications.len(), 1);
+    assert_eq!(created.notifications[0].0, user_b);
+    assert_eq!(
+        Notification::from_proto(&created.notifications[0].1),
+        Some(Notification::ReviewCommentMention {
+            comment_id: thread.comments[0].id,
+            thread_id: thread.id,
+            sender_id: user_a.to_proto(),
+            channel_id: channel.to_proto(),
+            path: "zed/src/main.rs".into(),
+        })
+    );
+
+    // Guests can read threads, but can't comment on them.
+    let thread_id = ReviewThreadId::from_proto(thread.id);
+    db.reply_to_review_thread(
+        channel,
+        thread_id,
+        user_c,
+        "I'm just a guest",
+        &[],
+        OffsetDateTime::now_utc(),
+    )
+    .await
+    .unwrap_err();
+    let threads = db
+        .get_review_threads(channel, "zed/src/main.rs", user_c)
+        .await
+        .unwrap();
+    assert_eq!(threads, vec![thread.clone()]);
+
+    let replied = db
+        .reply_to_review_thread(
+            channel,
+            thread_id,
+            user_b,
+            "looks fine to me",
+            &[],
+            OffsetDateTime::now_utc(),
+        )
+        .await
+        .unwrap();
+    assert!(replied.notifications.is_empty());
+    assert_eq!(
+        replied
+            .thread
+            .comments
+            .iter()
+            .map(|comment| (comment.sender_id, comment.body.as_str()))
+            .collect::<Vec<_>>(),
+        &[
+            (user_a.to_proto(), "what about @user_b?"),
+            (user_b.to_proto(), "looks fine to me"),
+        ]
+    );
+
+    // Ranges that end before they start are rejected.
+    let reversed_location = proto::ReviewThreadLocation {
+        start_row: 5,
+        end_row: 3,
+        ..location.clone()
+    };
+    db.create_review_thread(
+        channel,
+        user_a,
+        "zed/src/main.rs",
+        &reversed_location,
+        "backwards",
+        &[],
+        OffsetDateTime::now_utc(),
+    )
+    .await
+    .unwrap_err();
+    db.move_review_thread(channel, thread_id, user_b, &reversed_location)
+        .await
+        .unwrap_err();
+
+    let moved_location = proto::ReviewThreadLocation {
+        start_row: 10,
+        end_row: 12,
+        blob_sha: None,
+        ..location.clone()
+    };
+    let moved = db
+        .move_review_thread(channel, thread_id, user_b, &moved_location)
+        .await
+        .unwrap();
+    assert_eq!(moved.thread.location, Some(moved_location));
+
+    let resolved = db
+        .set_review_thread_resolved(channel, thread_id, user_b, true, OffsetDateTime::now_utc())
+        .await
+        .unwrap();
+    assert_eq!(resolved.thread.resolved_by, Some(user_b.to_proto()));
+    let reopened = db
+        .set_review_thread_resolved(channel, thread_id, user_a, false, OffsetDateTime::now_utc())
+        .await
+        .unwrap();
+    assert_eq!(reopened.thread.resolved_by, None);
+
+    // Threads are scoped to their file.
+    assert!(
+        db.get_review_threads(channel, "zed/src/lib.rs", user_a)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        db.get_review_threads(channel, "zed/src/main.rs", user_a)
+            .await
+            .unwrap(),
+        vec![reopened.thread]
+    );
+}
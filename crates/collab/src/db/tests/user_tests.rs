@@ -44,3 +44,66 @@ async fn test_accepted_tos(db: &Arc<Database>) {
     let user = db.get_user_by_id(user_id).await.unwrap().unwrap();
     assert!(user.accepted_tos_at.is_none());
 }
+
+test_both_dbs!(
+    test_local_users,
+    test_local_users_postgres,
+    test_local_users_sqlite
+);
+
+async fn test_local_users(db: &Arc<Database>) {
+    let github_user_id = db
+        .create_user(
+            "github@example.com",
+            None,
+            false,
+            NewUserParams {
+                github_login: "github-user".to_string(),
+                github_user_id: 1,
+            },
+        )
+        .await
+        .unwrap()
+        .user_id;
+
+    // Local users never collide with GitHub users.
+    let alice = db
+        .create_local_user("alice", Some("alice@example.com"), None, true)
+        .await
+        .unwrap();
+    let bob = db
+        .create_local_user("bob", None, None, false)
+        .await
+        .unwrap();
+    assert!(alice.admin);
+    assert_eq!(alice.github_user_id, -1);
+    assert_eq!(bob.github_user_id, -2);
+    assert_ne!(alice.id, github_user_id);
+    assert!(alice.password_hash.is_none());
+
+    db.set_user_password_hash(alice.id, "$scrypt$hash")
+        .await
+        .unwrap();
+    let alice = db.get_user_by_github_login("alice").await.unwrap().unwrap();
+    assert_eq!(alice.password_hash.as_deref(), Some("$scrypt$hash"));
+
+    db.set_user_is_admin(bob.id, true).await.unwrap();
+    let bob = db.get_user_by_github_login("bob").await.unwrap().unwrap();
+    assert!(bob.admin);
+
+    // OIDC users are created on first sign-in, without taking over existing logins.
+    let oidc_alice = db
+        .get_or_create_user_by_oidc_subject("subject-1", "alice", None, Some("Alice"))
+        .await
+        .unwrap();
+    assert_ne!(oidc_alice.id, alice.id);
+    assert_eq!(oidc_alice.github_login, "alice-2");
+    assert_eq!(oidc_alice.github_user_id, -3);
+    assert_eq!(oidc_alice.oidc_subject.as_deref(), Some("subject-1"));
+
+    let signed_in_again = db
+        .get_or_create_user_by_oidc_subject("subject-1", "alice", None, Some("Alice"))
+        .await
+        .unwrap();
+    assert_eq!(signed_in_again.id, oidc_alice.id);
+}
@@ -26,6 +26,22 @@ impl Executor {
         }
     }
 
+    /// Runs CPU-bound work on a thread where it won't block other requests.
+    pub async fn spawn_blocking<F, R>(&self, f: F) -> R
+    where
+        F: 'static + Send + FnOnce() -> R,
+        R: 'static + Send,
+    {
+        match self {
+            Executor::Production => match tokio::task::spawn_blocking(f).await {
+                Ok(result) => result,
+                Err(error) => std::panic::resume_unwind(error.into_panic()),
+            },
+            #[cfg(test)]
+            Executor::Deterministic(_) => f(),
+        }
+    }
+
     pub fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + use<> {
         let this = self.clone();
         async move {
@@ -8,6 +8,7 @@ pub mod llm;
 pub mod migrations;
 pub mod rpc;
 pub mod seed;
+pub mod self_hosted;
 pub mod stripe_billing;
 pub mod stripe_client;
 pub mod user_backfiller;
@@ -184,6 +185,28 @@ pub struct Config {
     pub stripe_api_key: Option<String>,
     pub supermaven_admin_api_key: Option<Arc<str>>,
     pub user_backfiller_github_access_token: Option<Arc<str>>,
+    /// Whether this server runs outside of Zed's hosted infrastructure, in which case
+    /// billing and LLM features are disabled and users sign in with [`Config::auth_provider`].
+    pub self_hosted: Option<bool>,
+    pub auth_provider: Option<AuthProvider>,
+    /// The URL at which clients reach this server, used to build OIDC redirect URLs.
+    pub public_url: Option<String>,
+    pub oidc_issuer_url: Option<String>,
+    pub oidc_client_id: Option<String>,
+    pub oidc_client_secret: Option<String>,
+}
+
+/// How users sign in to a self-hosted server.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthProvider {
+    /// Users sign in through zed.dev with their GitHub account.
+    #[default]
+    Github,
+    /// Users sign in with a username and password stored by this server.
+    Password,
+    /// Users sign in with a generic OpenID Connect provider.
+    Oidc,
 }
 
 impl Config {
@@ -191,6 +214,14 @@ impl Config {
         self.zed_environment == "development".into()
     }
 
+    pub fn is_self_hosted(&self) -> bool {
+        self.self_hosted.unwrap_or(false)
+    }
+
+    pub fn auth_provider(&self) -> AuthProvider {
+        self.auth_provider.unwrap_or_default()
+    }
+
     /// Returns the base `zed.dev` URL.
     pub fn zed_dot_dev_url(&self) -> &str {
         match self.zed_environment.as_ref() {
@@ -243,6 +274,12 @@ impl Config {
             kinesis_access_key: None,
             kinesis_secret_key: None,
             kinesis_stream: None,
+            self_hosted: None,
+            auth_provider: None,
+            public_url: None,
+            oidc_issuer_url: None,
+            oidc_client_id: None,
+            oidc_client_secret: None,
         }
     }
 }
@@ -288,7 +325,9 @@ impl AppState {
         let mut db = Database::new(db_options, Executor::Production).await?;
         db.initialize_notification_kinds().await?;
 
-        let llm_db = if let Some((llm_database_url, llm_database_max_connections)) = config
+        let llm_db = if config.is_self_hosted() {
+            None
+        } else if let Some((llm_database_url, llm_database_max_connections)) = config
             .llm_database_url
             .clone()
             .zip(config.llm_database_max_connections)
@@ -318,7 +357,11 @@ impl AppState {
         };
 
         let db = Arc::new(db);
-        let stripe_client = build_stripe_client(&config).map(Arc::new).log_err();
+        let stripe_client = if config.is_self_hosted() {
+            None
+        } else {
+            build_stripe_client(&config).map(Arc::new).log_err()
+        };
         let this = Self {
             db: db.clone(),
             llm_db,
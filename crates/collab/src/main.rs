@@ -13,8 +13,8 @@ use collab::llm::db::LlmDatabase;
 use collab::migrations::run_database_migrations;
 use collab::user_backfiller::spawn_user_backfiller;
 use collab::{
-    AppState, Config, Result, api::fetch_extensions_from_blob_store_periodically, db, env,
-    executor::Executor, rpc::ResultExt,
+    AppState, AuthProvider, Config, Result, api::fetch_extensions_from_blob_store_periodically, db,
+    env, executor::Executor, rpc::ResultExt,
 };
 use collab::{ServiceMode, api::billing::poll_stripe_events_periodically};
 use db::Database;
@@ -55,6 +55,14 @@ async fn main() -> Result<()> {
             let config = envy::from_env::<Config>().expect("error loading config");
             setup_app_database(&config).await?;
         }
+        Some("set-password") => {
+            let login = args
+                .next()
+                .context("usage: collab set-password <login> [--admin]")?;
+            let admin = args.next().as_deref() == Some("--admin");
+            let config = envy::from_env::<Config>().expect("error loading config");
+            set_password(&config, &login, admin).await?;
+        }
         Some("seed") => {
             let config = envy::from_env::<Config>().expect("error loading config");
             let db_options = db::ConnectOptions::new(config.database_url.clone());
@@ -78,7 +86,7 @@ async fn main() -> Result<()> {
                 Some("all") => ServiceMode::All,
                 _ => {
                     return Err(anyhow!(
-                        "usage: collab <version | migrate | seed | serve <api|collab|all>>"
+                        "usage: collab <version | migrate | seed | set-password <login> | serve <api|collab|all>>"
                     ))?;
                 }
             };
@@ -99,7 +107,9 @@ async fn main() -> Result<()> {
 
             if mode.is_collab() || mode.is_api() {
                 setup_app_database(&config).await?;
-                setup_llm_database(&config).await?;
+                if !config.is_self_hosted() {
+                    setup_llm_database(&config).await?;
+                }
 
                 let state = AppState::new(config, Executor::Production).await?;
 
@@ -119,41 +129,53 @@ async fn main() -> Result<()> {
                         .await?;
                     let rpc_server = collab::rpc::Server::new(epoch, state.clone());
                     rpc_server.start().await?;
+                    collab::rpc::save_channel_buffer_revisions_periodically(state.clone());
 
-                    poll_stripe_events_periodically(state.clone(), rpc_server.clone());
+                    if !state.config.is_self_hosted() {
+                        poll_stripe_events_periodically(state.clone(), rpc_server.clone());
+                    }
 
                     app = app
                         .merge(collab::api::routes(rpc_server.clone()))
                         .merge(collab::rpc::routes(rpc_server.clone()));
 
+                    if state.config.is_self_hosted()
+                        && state.config.auth_provider() != AuthProvider::Github
+                    {
+                        app = app.merge(collab::self_hosted::routes());
+                    }
+
                     on_shutdown = Some(Box::new(move || rpc_server.teardown()));
                 }
 
                 if mode.is_api() {
                     fetch_extensions_from_blob_store_periodically(state.clone());
-                    spawn_user_backfiller(state.clone());
-
-                    let llm_db = maybe!(async {
-                        let database_url = state
-                            .config
-                            .llm_database_url
-                            .as_ref()
-                            .context("missing LLM_DATABASE_URL")?;
-                        let max_connections = state
-                            .config
-                            .llm_database_max_connections
-                            .context("missing LLM_DATABASE_MAX_CONNECTIONS")?;
-
-                        let mut db_options = db::ConnectOptions::new(database_url);
-                        db_options.max_connections(max_connections);
-                        LlmDatabase::new(db_options, state.executor.clone()).await
-                    })
-                    .await
-                    .trace_err();
 
-                    if let Some(mut llm_db) = llm_db {
-                        llm_db.initialize().await?;
-                        sync_llm_request_usage_with_stripe_periodically(state.clone());
+                    if !state.config.is_self_hosted() {
+                        spawn_user_backfiller(state.clone());
+
+                        let llm_db = maybe!(async {
+                            let database_url = state
+                                .config
+                                .llm_database_url
+                                .as_ref()
+                                .context("missing LLM_DATABASE_URL")?;
+                            let max_connections = state
+                                .config
+                                .llm_database_max_connections
+                                .context("missing LLM_DATABASE_MAX_CONNECTIONS")?;
+
+                            let mut db_options = db::ConnectOptions::new(database_url);
+                            db_options.max_connections(max_connections);
+                            LlmDatabase::new(db_options, state.executor.clone()).await
+                        })
+                        .await
+                        .trace_err();
+
+                        if let Some(mut llm_db) = llm_db {
+                            llm_db.initialize().await?;
+                            sync_llm_request_usage_with_stripe_periodically(state.clone());
+                        }
                     }
 
                     app = app
@@ -244,7 +266,7 @@ async fn main() -> Result<()> {
         }
         _ => {
             Err(anyhow!(
-                "usage: collab <version | migrate | seed | serve <api|collab|llm|all>>"
+                "usage: collab <version | migrate | seed | set-password <login> | serve <api|collab|llm|all>>"
             ))?;
         }
     }
@@ -283,6 +305,38 @@ async fn setup_app_database(config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Sets the password of a local user on a self-hosted server, creating the user if needed.
+/// When `admin` is true, the user is also made an administrator. The password is read from
+/// the first line of stdin.
+async fn set_password(config: &Config, login: &str, admin: bool) -> Result<()> {
+    let db_options = db::ConnectOptions::new(config.database_url.clone());
+    let db = Database::new(db_options, Executor::Production).await?;
+
+    let mut password = String::new();
+    std::io::stdin()
+        .read_line(&mut password)
+        .context("failed to read password from stdin")?;
+    let password = password.trim_end_matches(['\r', '\n']);
+    if password.is_empty() {
+        Err(anyhow!("password must not be empty"))?;
+    }
+
+    let user = match db.get_user_by_github_login(login).await? {
+        Some(user) => {
+            if admin && !user.admin {
+                db.set_user_is_admin(user.id, true).await?;
+            }
+            user
+        }
+        None => db.create_local_user(login, None, None, admin).await?,
+    };
+    db.set_user_password_hash(user.id, &collab::auth::hash_password(password)?)
+        .await?;
+    println!("set password for {login}");
+
+    Ok(())
+}
+
 async fn setup_llm_database(config: &Config) -> Result<()> {
     let database_url = config
         .llm_database_url
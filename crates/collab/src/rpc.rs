@@ -12,10 +12,11 @@ use crate::stripe_client::StripeCustomerId;
 use crate::{
     AppState, Error, Result, auth,
     db::{
-        self, BufferId, Capability, Channel, ChannelId, ChannelRole, ChannelsForUser,
-        CreatedChannelMessage, Database, InviteMemberResult, MembershipUpdated, MessageId,
-        NotificationId, ProjectId, RejoinedProject, RemoveChannelMemberResult,
-        RespondToChannelInvite, RoomId, ServerId, UpdatedChannelMessage, User, UserId,
+        self, BufferId, Capability, Channel, ChannelBufferRevisionId, ChannelId, ChannelRole,
+        ChannelsForUser, CreatedChannelMessage, Database, InviteMemberResult, MembershipUpdated,
+        MessageId, NotificationId, ProjectId, RejoinedProject, RemoveChannelMemberResult,
+        RespondToChannelInvite, ReviewThreadId, RoomId, ServerId, UpdatedChannelMessage,
+        UpdatedReviewThread, User, UserId,
     },
     executor::Executor,
 };
@@ -86,6 +87,9 @@ pub const RECONNECT_TIMEOUT: Duration = Duration::from_secs(30);
 // kubernetes gives terminated pods 10s to shutdown gracefully. After they're gone, we can clean up old resources.
 pub const CLEANUP_TIMEOUT: Duration = Duration::from_secs(15);
 
+/// How often to check for channel buffers whose edits should be saved as a revision.
+const SAVE_CHANNEL_BUFFER_REVISIONS_INTERVAL: Duration = Duration::from_secs(60);
+
 const MESSAGE_COUNT_PER_PAGE: usize = 100;
 const MAX_MESSAGE_LEN: usize = 1024;
 const NOTIFICATION_COUNT_PER_PAGE: usize = 50;
@@ -405,6 +409,8 @@ impl Server {
             .add_request_handler(leave_channel_buffer)
             .add_message_handler(update_channel_buffer)
             .add_request_handler(rejoin_channel_buffers)
+            .add_request_handler(get_channel_buffer_revisions)
+            .add_request_handler(get_channel_buffer_revision_text)
             .add_request_handler(get_channel_members)
             .add_request_handler(respond_to_channel_invite)
             .add_request_handler(join_channel)
@@ -415,6 +421,12 @@ impl Server {
             .add_request_handler(update_channel_message)
             .add_request_handler(get_channel_messages)
             .add_request_handler(get_channel_messages_by_id)
+            .add_request_handler(search_channel_messages)
+            .add_request_handler(get_review_threads)
+            .add_request_handler(create_review_thread)
+            .add_request_handler(reply_to_review_thread)
+            .add_request_handler(resolve_review_thread)
+            .add_request_handler(move_review_thread)
             .add_request_handler(get_notifications)
             .add_request_handler(mark_notification_as_read)
             .add_request_handler(move_channel)
@@ -449,7 +461,15 @@ impl Server {
             .add_request_handler(forward_mutating_project_request::<proto::GitChangeBranch>)
             .add_request_handler(forward_mutating_project_request::<proto::CheckForPushedCommits>)
             .add_message_handler(broadcast_project_message_from_host::<proto::AdvertiseContexts>)
-            .add_message_handler(update_context);
+            .add_message_handler(update_context)
+            .add_message_handler(broadcast_project_message_from_host::<proto::ShareTerminal>)
+            .add_message_handler(broadcast_project_message_from_host::<proto::UnshareTerminal>)
+            .add_message_handler(broadcast_project_message_from_host::<proto::UpdateSharedTerminal>)
+            .add_request_handler(forward_mutating_project_request::<proto::SharedTerminalInput>)
+            .add_message_handler(broadcast_project_message_from_host::<proto::ShareDebugSession>)
+            .add_message_handler(broadcast_project_message_from_host::<proto::UnshareDebugSession>)
+            .add_message_handler(broadcast_project_message_from_host::<proto::DebugSessionEvent>)
+            .add_request_handler(forward_read_only_project_request::<proto::DebugSessionRequest>);
 
         Arc::new(server)
     }
@@ -1140,6 +1160,25 @@ impl Header for AppVersionHeader {
     }
 }
 
+/// Saves channel buffers that have been edited for a while as new revisions, so that
+/// buffers that stay open still get a history.
+pub fn save_channel_buffer_revisions_periodically(app_state: Arc<AppState>) {
+    let executor = app_state.executor.clone();
+    executor.spawn_detached({
+        let executor = executor.clone();
+        async move {
+            loop {
+                executor.sleep(SAVE_CHANNEL_BUFFER_REVISIONS_INTERVAL).await;
+                app_state
+                    .db
+                    .save_channel_buffer_revisions()
+                    .await
+                    .trace_err();
+            }
+        }
+    });
+}
+
 pub fn routes(server: Arc<Server>) -> Router<(), Body> {
     Router::new()
         .route("/rpc", get(handle_websocket_request))
@@ -3635,6 +3674,40 @@ async fn leave_channel_buffer(
     Ok(())
 }
 
+/// List the saved revisions of the channel notes
+async fn get_channel_buffer_revisions(
+    request: proto::GetChannelBufferRevisions,
+    response: Response<proto::GetChannelBufferRevisions>,
+    session: Session,
+) -> Result<()> {
+    let revisions = session
+        .db()
+        .await
+        .get_channel_buffer_revisions(ChannelId::from_proto(request.channel_id), session.user_id())
+        .await?;
+    response.send(proto::GetChannelBufferRevisionsResponse { revisions })?;
+    Ok(())
+}
+
+/// Get the text of the channel notes as of a saved revision
+async fn get_channel_buffer_revision_text(
+    request: proto::GetChannelBufferRevisionText,
+    response: Response<proto::GetChannelBufferRevisionText>,
+    session: Session,
+) -> Result<()> {
+    let text = session
+        .db()
+        .await
+        .get_channel_buffer_revision_text(
+            ChannelId::from_proto(request.channel_id),
+            ChannelBufferRevisionId::from_proto(request.revision_id),
+            session.user_id(),
+        )
+        .await?;
+    response.send(proto::GetChannelBufferRevisionTextResponse { text })?;
+    Ok(())
+}
+
 fn channel_buffer_updated<T: EnvelopedMessage>(
     sender_id: ConnectionId,
     collaborators: impl IntoIterator<Item = ConnectionId>,
@@ -4029,6 +4102,188 @@ async fn get_channel_messages_by_id(
     Ok(())
 }
 
+/// Search the chat messages in a channel
+async fn search_channel_messages(
+    request: proto::SearchChannelMessages,
+    response: Response<proto::SearchChannelMessages>,
+    session: Session,
+) -> Result<()> {
+    let query = request.query.trim();
+    if query.is_empty() {
+        return Err(anyhow!("search query can't be blank"))?;
+    }
+
+    let messages = session
+        .db()
+        .await
+        .search_channel_messages(
+            ChannelId::from_proto(request.channel_id),
+            session.user_id(),
+            query,
+            MESSAGE_COUNT_PER_PAGE,
+            request.before_message_id.map(MessageId::from_proto),
+        )
+        .await?;
+    response.send(proto::GetChannelMessagesResponse {
+        done: messages.len() < MESSAGE_COUNT_PER_PAGE,
+        messages,
+    })?;
+    Ok(())
+}
+
+/// Retrieve the review threads attached to a file
+async fn get_review_threads(
+    request: proto::GetReviewThreads,
+    response: Response<proto::GetReviewThreads>,
+    session: Session,
+) -> Result<()> {
+    let threads = session
+        .db()
+        .await
+        .get_review_threads(
+            ChannelId::from_proto(request.channel_id),
+            &request.path,
+            session.user_id(),
+        )
+        .await?;
+    response.send(proto::GetReviewThreadsResponse { threads })?;
+    Ok(())
+}
+
+/// Start a review thread on a range of a file
+async fn create_review_thread(
+    request: proto::CreateReviewThread,
+    response: Response<proto::CreateReviewThread>,
+    session: Session,
+) -> Result<()> {
+    let body = validate_review_comment(&request.body)?;
+    let location = request.location.context("invalid location")?;
+    let result = session
+        .db()
+        .await
+        .create_review_thread(
+            ChannelId::from_proto(request.channel_id),
+            session.user_id(),
+            &request.path,
+            &location,
+            body,
+            &request.mentions,
+            OffsetDateTime::now_utc(),
+        )
+        .await?;
+    send_updated_review_thread(result, response, &session).await
+}
+
+/// Add a comment to a review thread
+async fn reply_to_review_thread(
+    request: proto::ReplyToReviewThread,
+    response: Response<proto::ReplyToReviewThread>,
+    session: Session,
+) -> Result<()> {
+    let body = validate_review_comment(&request.body)?;
+    let result = session
+        .db()
+        .await
+        .reply_to_review_thread(
+            ChannelId::from_proto(request.channel_id),
+            ReviewThreadId::from_proto(request.thread_id),
+            session.user_id(),
+            body,
+            &request.mentions,
+            OffsetDateTime::now_utc(),
+        )
+        .await?;
+    send_updated_review_thread(result, response, &session).await
+}
+
+/// Resolve or reopen a review thread
+async fn resolve_review_thread(
+    request: proto::ResolveReviewThread,
+    response: Response<proto::ResolveReviewThread>,
+    session: Session,
+) -> Result<()> {
+    let result = session
+        .db()
+        .await
+        .set_review_thread_resolved(
+            ChannelId::from_proto(request.channel_id),
+            ReviewThreadId::from_proto(request.thread_id),
+            session.user_id(),
+            request.resolved,
+            OffsetDateTime::now_utc(),
+        )
+        .await?;
+    send_updated_review_thread(result, response, &session).await
+}
+
+/// Update where a review thread is attached after its file was edited
+async fn move_review_thread(
+    request: proto::MoveReviewThread,
+    response: Response<proto::MoveReviewThread>,
+    session: Session,
+) -> Result<()> {
+    let location = request.location.context("invalid location")?;
+    let result = session
+        .db()
+        .await
+        .move_review_thread(
+            ChannelId::from_proto(request.channel_id),
+            ReviewThreadId::from_proto(request.thread_id),
+            session.user_id(),
+            &location,
+        )
+        .await?;
+    send_updated_review_thread(result, response, &session).await
+}
+
+fn validate_review_comment(body: &str) -> Result<&str> {
+    let body = body.trim();
+    if body.len() > MAX_MESSAGE_LEN {
+        return Err(anyhow!("comment is too long"))?;
+    }
+    if body.is_empty() {
+        return Err(anyhow!("comment can't be blank"))?;
+    }
+    Ok(body)
+}
+
+async fn send_updated_review_thread<T>(
+    result: UpdatedReviewThread,
+    response: Response<T>,
+    session: &Session,
+) -> Result<()>
+where
+    T: RequestMessage<Response = proto::ReviewThreadResponse>,
+{
+    let UpdatedReviewThread {
+        thread,
+        channel,
+        notifications,
+    } = result;
+    response.send(proto::ReviewThreadResponse {
+        thread: Some(thread.clone()),
+    })?;
+
+    let pool = &*session.connection_pool().await;
+    let update = proto::UpdateReviewThread {
+        channel_id: channel.id.to_proto(),
+        thread: Some(thread),
+    };
+    let connection_ids =
+        pool.channel_connection_ids(channel.root_id())
+            .filter_map(|(connection_id, role)| {
+                role.can_see_channel(channel.visibility)
+                    .then_some(connection_id)
+            });
+    broadcast(
+        Some(session.connection_id),
+        connection_ids,
+        |connection_id| session.peer.send(connection_id, update.clone()),
+    );
+    send_notifications(pool, &session.peer, notifications);
+    Ok(())
+}
+
 /// Retrieve the current users notifications
 async fn get_notifications(
     request: proto::GetNotifications,
@@ -68,13 +68,20 @@ pub async fn seed(config: &Config, db: &Database, force: bool) -> anyhow::Result
     }
 
     for admin_login in seed_config.admins {
-        let user = fetch_github::<GithubUser>(
-            &client,
-            &format!("https://api.github.com/users/{admin_login}"),
-        )
-        .await;
-        let user = db
-            .create_user(
+        // Self-hosted servers don't use GitHub accounts, so admins are created as local
+        // users, who can be given a password with `collab set-password`.
+        let user_id = if config.is_self_hosted() {
+            db.create_local_user(&admin_login, None, None, true)
+                .await
+                .context("failed to create admin user")?
+                .id
+        } else {
+            let user = fetch_github::<GithubUser>(
+                &client,
+                &format!("https://api.github.com/users/{admin_login}"),
+            )
+            .await;
+            db.create_user(
                 &user.email.unwrap_or(format!("{admin_login}@example.com")),
                 user.name.as_deref(),
                 true,
@@ -84,20 +91,20 @@ pub async fn seed(config: &Config, db: &Database, force: bool) -> anyhow::Result
                 },
             )
             .await
-            .context("failed to create admin user")?;
+            .context("failed to create admin user")?
+            .user_id
+        };
         if first_user.is_none() {
-            first_user = Some(user.user_id);
+            first_user = Some(user_id);
         } else {
-            others.push(user.user_id)
+            others.push(user_id)
         }
 
         for flag in &flags {
-            db.add_user_flag(user.user_id, *flag)
-                .await
-                .context(format!(
-                    "Unable to enable flag '{}' for user '{}'",
-                    flag, user.user_id
-                ))?;
+            db.add_user_flag(user_id, *flag).await.context(format!(
+                "Unable to enable flag '{}' for user '{}'",
+                flag, user_id
+            ))?;
         }
     }
 
@@ -119,6 +126,10 @@ pub async fn seed(config: &Config, db: &Database, force: bool) -> anyhow::Result
         }
     }
 
+    if config.is_self_hosted() {
+        return Ok(());
+    }
+
     let github_users_filepath = seed_path.parent().unwrap().join("seed/github_users.json");
     let github_users: Vec<GithubUser> =
         serde_json::from_str(&fs::read_to_string(github_users_filepath)?)?;
@@ -0,0 +1,557 @@
+//! Sign-in for self-hosted servers.
+//!
+//! When signing in, the Zed client opens `{server_url}/native_app_signin` in the
+//! browser, and waits for the browser to be redirected to a port on the local
+//! machine with the user's ID and an encrypted access token. These routes serve
+//! that flow on the collab server itself, in place of zed.dev, authenticating
+//! users with a local password or an OpenID Connect provider.
+
+use crate::{AppState, AuthProvider, Error, Result, auth, db::User};
+use anyhow::Context as _;
+use axum::{
+    Extension, Form, Router,
+    body::Body,
+    extract::{ConnectInfo, Query},
+    http::StatusCode,
+    response::{Html, IntoResponse, Redirect, Response},
+    routing::get,
+};
+use collections::HashMap;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, LazyLock},
+    time::{Duration, Instant},
+};
+
+/// How long a user has to finish signing in with the OIDC provider.
+const OIDC_SIGN_IN_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+const OIDC_CALLBACK_PATH: &str = "/auth/oidc/callback";
+
+/// How many times a login's password can be entered incorrectly from one address before
+/// that address is locked out of it.
+const MAX_FAILED_PASSWORD_SIGN_INS: u32 = 5;
+
+/// How long password sign-ins are rejected after too many failed attempts.
+const PASSWORD_SIGN_IN_LOCKOUT: Duration = Duration::from_secs(15 * 60);
+
+/// A hash to verify passwords against when the login doesn't exist or has no password,
+/// so that the response takes as long as it would for a real user.
+static DUMMY_PASSWORD_HASH: LazyLock<String> =
+    LazyLock::new(|| auth::hash_password("").expect("failed to hash dummy password"));
+
+pub fn routes() -> Router<(), Body> {
+    Router::new()
+        .route(
+            "/native_app_signin",
+            get(native_app_sign_in).post(sign_in_with_password),
+        )
+        .route(
+            "/native_app_signin_succeeded",
+            get(native_app_sign_in_succeeded),
+        )
+        .route(OIDC_CALLBACK_PATH, get(oidc_callback))
+        .layer(Extension(Arc::new(PendingOidcSignIns::default())))
+        .layer(Extension(Arc::new(FailedPasswordSignIns::default())))
+}
+
+#[derive(Deserialize)]
+struct NativeAppSignInParams {
+    native_app_port: u16,
+    native_app_public_key: String,
+}
+
+async fn native_app_sign_in(
+    Extension(app): Extension<Arc<AppState>>,
+    Extension(pending_oidc_sign_ins): Extension<Arc<PendingOidcSignIns>>,
+    Query(params): Query<NativeAppSignInParams>,
+) -> Result<Response> {
+    match app.config.auth_provider() {
+        AuthProvider::Password => Ok(Html(password_form(&params, None)).into_response()),
+        AuthProvider::Oidc => {
+            let provider = OidcProvider::discover(&app).await?;
+            let state = auth::random_token();
+            pending_oidc_sign_ins.insert(state.clone(), params);
+            let authorization_url = reqwest::Url::parse_with_params(
+                &provider.metadata.authorization_endpoint,
+                &[
+                    ("response_type", "code"),
+                    ("client_id", &provider.client_id),
+                    ("redirect_uri", &provider.redirect_uri),
+                    ("scope", "openid profile email"),
+                    ("state", &state),
+                ],
+            )
+            .context("invalid OIDC authorization endpoint")?;
+            Ok(Redirect::to(authorization_url.as_str()).into_response())
+        }
+        AuthProvider::Github => Err(Error::http(
+            StatusCode::NOT_FOUND,
+            "users of this server sign in through zed.dev".to_string(),
+        )),
+    }
+}
+
+#[derive(Deserialize)]
+struct PasswordSignInForm {
+    login: String,
+    password: String,
+    native_app_port: u16,
+    native_app_public_key: String,
+}
+
+async fn sign_in_with_password(
+    Extension(app): Extension<Arc<AppState>>,
+    Extension(failed_sign_ins): Extension<Arc<FailedPasswordSignIns>>,
+    ConnectInfo(address): ConnectInfo<SocketAddr>,
+    Form(form): Form<PasswordSignInForm>,
+) -> Result<Response> {
+    if app.config.auth_provider() != AuthProvider::Password {
+        return Err(Error::http(
+            StatusCode::NOT_FOUND,
+            "password sign-in is disabled".to_string(),
+        ));
+    }
+
+    let params = NativeAppSignInParams {
+        native_app_port: form.native_app_port,
+        native_app_public_key: form.native_app_public_key,
+    };
+    let login = form.login.trim();
+    let ip = address.ip();
+    if failed_sign_ins.is_locked_out(ip, login) {
+        return Ok((
+            StatusCode::TOO_MANY_REQUESTS,
+            Html(password_form(
+                &params,
+                Some("Too many failed sign-in attempts. Try again later."),
+            )),
+        )
+            .into_response());
+    }
+
+    let user = app.db.get_user_by_github_login(login).await?;
+    let password_hash = user
+        .as_ref()
+        .and_then(|user| user.password_hash.clone())
+        .unwrap_or_else(|| DUMMY_PASSWORD_HASH.clone());
+    let password = form.password;
+    let is_valid = app
+        .executor
+        .spawn_blocking(move || auth::verify_password(&password, &password_hash))
+        .await;
+    let Some(user) = user.filter(|user| is_valid && user.password_hash.is_some()) else {
+        failed_sign_ins.record_failure(ip, login);
+        return Ok((
+            StatusCode::UNAUTHORIZED,
+            Html(password_form(
+                &params,
+                Some("Incorrect username or password."),
+            )),
+        )
+            .into_response());
+    };
+
+    failed_sign_ins.clear(ip, login);
+    redirect_to_native_app(&app, &user, &params).await
+}
+
+async fn native_app_sign_in_succeeded() -> Html<&'static str> {
+    Html(concat!(
+        "<!DOCTYPE html><html><head><title>Signed in to Zed</title></head>",
+        "<body><p>You are signed in. You can close this page and return to Zed.</p></body></html>"
+    ))
+}
+
+#[derive(Deserialize)]
+struct OidcCallbackParams {
+    state: String,
+    code: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OidcTokenResponse {
+    access_token: String,
+}
+
+#[derive(Deserialize)]
+struct OidcUserInfo {
+    sub: String,
+    preferred_username: Option<String>,
+    email: Option<String>,
+    name: Option<String>,
+}
+
+async fn oidc_callback(
+    Extension(app): Extension<Arc<AppState>>,
+    Extension(pending_oidc_sign_ins): Extension<Arc<PendingOidcSignIns>>,
+    Query(callback): Query<OidcCallbackParams>,
+) -> Result<Response> {
+    let params = pending_oidc_sign_ins.take(&callback.state).ok_or_else(|| {
+        Error::http(
+            StatusCode::BAD_REQUEST,
+            "sign-in expired, please sign in from Zed again".to_string(),
+        )
+    })?;
+    if let Some(error) = callback.error {
+        return Err(Error::http(
+            StatusCode::UNAUTHORIZED,
+            format!("sign-in failed: {error}"),
+        ));
+    }
+    let code = callback
+        .code
+        .ok_or_else(|| Error::http(StatusCode::BAD_REQUEST, "missing code".to_string()))?;
+
+    let provider = OidcProvider::discover(&app).await?;
+    let client = reqwest::Client::new();
+    let token = client
+        .post(&provider.metadata.token_endpoint)
+        .form(&[
+            ("grant_type", "authorization_code"),
+            ("code", &code),
+            ("redirect_uri", &provider.redirect_uri),
+            ("client_id", &provider.client_id),
+            ("client_secret", &provider.client_secret),
+        ])
+        .send()
+        .await
+        .context("failed to exchange OIDC authorization code")?
+        .error_for_status()
+        .context("failed to exchange OIDC authorization code")?
+        .json::<OidcTokenResponse>()
+        .await
+        .context("invalid OIDC token response")?;
+    let user_info = client
+        .get(&provider.metadata.userinfo_endpoint)
+        .bearer_auth(&token.access_token)
+        .send()
+        .await
+        .context("failed to fetch OIDC user info")?
+        .error_for_status()
+        .context("failed to fetch OIDC user info")?
+        .json::<OidcUserInfo>()
+        .await
+        .context("invalid OIDC user info")?;
+
+    let login = user_info
+        .preferred_username
+        .clone()
+        .or_else(|| {
+            let email = user_info.email.as_deref()?;
+            Some(
+                email
+                    .split_once('@')
+                    .map_or(email, |(name, _)| name)
+                    .to_string(),
+            )
+        })
+        .unwrap_or_else(|| user_info.sub.clone());
+    let user = app
+        .db
+        .get_or_create_user_by_oidc_subject(
+            &user_info.sub,
+            &login,
+            user_info.email.as_deref(),
+            user_info.name.as_deref(),
+        )
+        .await?;
+
+    redirect_to_native_app(&app, &user, &params).await
+}
+
+/// Redirects the browser to the Zed client that started signing in, passing it an
+/// access token that only that client can decrypt.
+async fn redirect_to_native_app(
+    app: &AppState,
+    user: &User,
+    params: &NativeAppSignInParams,
+) -> Result<Response> {
+    let access_token = auth::create_access_token(&app.db, user.id, None).await?;
+    let encrypted_access_token =
+        auth::encrypt_access_token(&access_token, params.native_app_public_key.clone())?;
+    let native_app_url = reqwest::Url::parse_with_params(
+        &format!("http://127.0.0.1:{}/", params.native_app_port),
+        &[
+            ("user_id", user.id.to_string()),
+            ("access_token", encrypted_access_token),
+        ],
+    )
+    .context("invalid native app url")?;
+    Ok(Redirect::to(native_app_url.as_str()).into_response())
+}
+
+/// The sign-ins that were handed off to the OIDC provider, keyed by their `state` parameter.
+#[derive(Default)]
+struct PendingOidcSignIns(Mutex<HashMap<String, (NativeAppSignInParams, Instant)>>);
+
+impl PendingOidcSignIns {
+    fn insert(&self, state: String, params: NativeAppSignInParams) {
+        let mut pending = self.0.lock();
+        pending.retain(|_, (_, started_at)| started_at.elapsed() < OIDC_SIGN_IN_TIMEOUT);
+        pending.insert(state, (params, Instant::now()));
+    }
+
+    fn take(&self, state: &str) -> Option<NativeAppSignInParams> {
+        let (params, started_at) = self.0.lock().remove(state)?;
+        (started_at.elapsed() < OIDC_SIGN_IN_TIMEOUT).then_some(params)
+    }
+}
+
+/// The failed password sign-ins for each login from each address, and when the last one
+/// happened.
+///
+/// Lockouts are per address so that others can't lock a user out of their account by
+/// entering wrong passwords for it.
+#[derive(Default)]
+struct FailedPasswordSignIns(Mutex<HashMap<(IpAddr, String), (u32, Instant)>>);
+
+impl FailedPasswordSignIns {
+    fn is_locked_out(&self, ip: IpAddr, login: &str) -> bool {
+        self.0
+            .lock()
+            .get(&(ip, login.to_string()))
+            .is_some_and(|(failures, failed_at)| {
+                *failures >= MAX_FAILED_PASSWORD_SIGN_INS
+                    && failed_at.elapsed() < PASSWORD_SIGN_IN_LOCKOUT
+            })
+    }
+
+    fn record_failure(&self, ip: IpAddr, login: &str) {
+        let mut failed = self.0.lock();
+        failed.retain(|_, (_, failed_at)| failed_at.elapsed() < PASSWORD_SIGN_IN_LOCKOUT);
+        let (failures, failed_at) = failed
+            .entry((ip, login.to_string()))
+            .or_insert((0, Instant::now()));
+        *failures += 1;
+        *failed_at = Instant::now();
+    }
+
+    fn clear(&self, ip: IpAddr, login: &str) {
+        self.0.lock().remove(&(ip, login.to_string()));
+    }
+}
+
+#[derive(Deserialize)]
+struct OidcProviderMetadata {
+    authorization_endpoint: String,
+    token_endpoint: String,
+    userinfo_endpoint: String,
+}
+
+struct OidcProvider {
+    metadata: OidcProviderMetadata,
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+}
+
+impl OidcProvider {
+    async fn discover(app: &AppState) -> anyhow::Result<Self> {
+        let config = &app.config;
+        let issuer_url = config
+            .oidc_issuer_url
+            .as_deref()
+            .context("missing OIDC_ISSUER_URL")?;
+        let client_id = config
+            .oidc_client_id
+            .clone()
+            .context("missing OIDC_CLIENT_ID")?;
+        let client_secret = config
+            .oidc_client_secret
+            .clone()
+            .context("missing OIDC_CLIENT_SECRET")?;
+        let public_url = config.public_url.as_deref().context("missing PUBLIC_URL")?;
+
+        let discovery_url = format!(
+            "{}/.well-known/openid-configuration",
+            issuer_url.trim_end_matches('/')
+        );
+        let metadata = reqwest::get(&discovery_url)
+            .await
+            .with_context(|| format!("failed to fetch {discovery_url}"))?
+            .error_for_status()
+            .with_context(|| format!("failed to fetch {discovery_url}"))?
+            .json::<OidcProviderMetadata>()
+            .await
+            .context("invalid OIDC provider metadata")?;
+
+        Ok(Self {
+            metadata,
+            client_id,
+            client_secret,
+            redirect_uri: format!("{}{OIDC_CALLBACK_PATH}", public_url.trim_end_matches('/')),
+        })
+    }
+}
+
+fn password_form(params: &NativeAppSignInParams, error: Option<&str>) -> String {
+    let error = error
+        .map(|error| format!("<p class=\"error\">{}</p>", escape_html(error)))
+        .unwrap_or_default();
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head><title>Sign in to Zed</title></head>
+<body>
+<h1>Sign in to Zed</h1>
+{error}
+<form method="post" action="/native_app_signin">
+<input type="hidden" name="native_app_port" value="{port}">
+<input type="hidden" name="native_app_public_key" value="{public_key}">
+<p><label>Username <input name="login" autocomplete="username" required autofocus></label></p>
+<p><label>Password <input name="password" type="password" autocomplete="current-password" required></label></p>
+<p><button type="submit">Sign in</button></p>
+</form>
+</body>
+</html>"#,
+        port = params.native_app_port,
+        public_key = escape_html(&params.native_app_public_key),
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Config,
+        db::{Database, NewUserParams, TestDb},
+        executor::Executor,
+    };
+    use axum::http::{Request, header};
+    use gpui::BackgroundExecutor;
+    use tower::ServiceExt as _;
+
+    #[test]
+    fn test_failed_password_sign_ins() {
+        let failed_sign_ins = FailedPasswordSignIns::default();
+        let ip = IpAddr::from([10, 0, 0, 1]);
+        let other_ip = IpAddr::from([10, 0, 0, 2]);
+        for _ in 0..MAX_FAILED_PASSWORD_SIGN_INS - 1 {
+            failed_sign_ins.record_failure(ip, "alice");
+        }
+        assert!(!failed_sign_ins.is_locked_out(ip, "alice"));
+
+        failed_sign_ins.record_failure(ip, "alice");
+        assert!(failed_sign_ins.is_locked_out(ip, "alice"));
+        assert!(!failed_sign_ins.is_locked_out(ip, "bob"));
+        assert!(!failed_sign_ins.is_locked_out(other_ip, "alice"));
+
+        failed_sign_ins.clear(ip, "alice");
+        assert!(!failed_sign_ins.is_locked_out(ip, "alice"));
+    }
+
+    #[cfg(target_os = "macos")]
+    #[gpui::test]
+    async fn test_password_sign_in_postgres(cx: &mut gpui::TestAppContext) {
+        let test_db = TestDb::postgres(cx.executor());
+        test_password_sign_in(test_db.db(), cx.executor()).await;
+    }
+
+    #[gpui::test]
+    async fn test_password_sign_in_sqlite(cx: &mut gpui::TestAppContext) {
+        let test_db = TestDb::sqlite(cx.executor());
+        test_password_sign_in(test_db.db(), cx.executor()).await;
+    }
+
+    async fn test_password_sign_in(db: &Arc<Database>, executor: BackgroundExecutor) {
+        let user_id = db
+            .create_user(
+                "alice@example.com",
+                None,
+                false,
+                NewUserParams {
+                    github_login: "alice".into(),
+                    github_user_id: 1,
+                },
+            )
+            .await
+            .unwrap()
+            .user_id;
+        db.set_user_password_hash(user_id, &auth::hash_password("hunter2").unwrap())
+            .await
+            .unwrap();
+
+        let mut config = Config::test();
+        config.auth_provider = Some(AuthProvider::Password);
+        let app_state = Arc::new(AppState {
+            db: db.clone(),
+            llm_db: None,
+            livekit_client: None,
+            blob_store_client: None,
+            real_stripe_client: None,
+            stripe_client: None,
+            stripe_billing: None,
+            executor: Executor::Deterministic(executor),
+            kinesis_client: None,
+            config,
+        });
+        let app = routes().layer(Extension(app_state));
+        let (public_key, private_key) = rpc::auth::keypair().unwrap();
+        let public_key = String::try_from(public_key).unwrap();
+
+        let sign_in = |ip: [u8; 4], login: &str, password: &str| {
+            let mut request = Request::post("/native_app_signin")
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::from(format!(
+                    "login={login}&password={password}&native_app_port=1234&native_app_public_key={public_key}"
+                )))
+                .unwrap();
+            request
+                .extensions_mut()
+                .insert(ConnectInfo(SocketAddr::from((ip, 4321))));
+            app.clone().oneshot(request)
+        };
+
+        // Wrong passwords and unknown logins are rejected alike.
+        let response = sign_in([10, 0, 0, 1], "alice", "hunter3").await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = sign_in([10, 0, 0, 1], "bob", "hunter2").await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        // The right password redirects to the native app with an access token for the user.
+        let response = sign_in([10, 0, 0, 1], "alice", "hunter2").await.unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        let location = response.headers()[header::LOCATION].to_str().unwrap();
+        let location = reqwest::Url::parse(location).unwrap();
+        assert_eq!(location.host_str(), Some("127.0.0.1"));
+        assert_eq!(location.port(), Some(1234));
+        let query = location.query_pairs().collect::<HashMap<_, _>>();
+        assert_eq!(query["user_id"], user_id.to_string());
+        let access_token = private_key.decrypt_string(&query["access_token"]).unwrap();
+        assert!(
+            auth::verify_access_token(&access_token, user_id, db)
+                .await
+                .unwrap()
+                .is_valid
+        );
+
+        // Too many failures lock out the address they came from, but not others.
+        for _ in 0..MAX_FAILED_PASSWORD_SIGN_INS {
+            let response = sign_in([10, 0, 0, 2], "alice", "hunter3").await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+        let response = sign_in([10, 0, 0, 2], "alice", "hunter2").await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let response = sign_in([10, 0, 0, 1], "alice", "hunter2").await.unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    }
+}
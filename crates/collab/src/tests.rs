@@ -9,6 +9,7 @@ mod channel_guest_tests;
 mod channel_message_tests;
 mod channel_tests;
 // mod debug_panel_tests;
+mod debug_session_sharing_tests;
 mod editor_tests;
 mod following_tests;
 mod git_tests;
@@ -19,6 +20,7 @@ mod random_project_collaboration_tests;
 mod randomized_test_helpers;
 mod remote_editing_collaboration_tests;
 mod stripe_billing_tests;
+mod terminal_sharing_tests;
 mod test_server;
 
 use language::{Language, LanguageConfig, LanguageMatcher, tree_sitter_rust};
@@ -0,0 +1,226 @@
+use crate::tests::{TestClient, TestServer};
+use call::ActiveCall;
+use dap::{
+    StackFrame,
+    adapters::DebugTaskDefinition,
+    requests::{Scopes, StackTrace, Threads},
+};
+use debugger_ui::debugger_panel::DebugPanel;
+use gpui::{Entity, Focusable as _, TestAppContext, VisualTestContext};
+use rpc::proto;
+use serde_json::json;
+use task::TaskContext;
+use workspace::{FollowableItem, Workspace};
+
+fn init_test(cx: &mut TestAppContext) {
+    cx.update(|cx| {
+        command_palette_hooks::init(cx);
+        debugger_ui::init(cx);
+        dap_adapters::init(cx);
+    });
+}
+
+async fn setup_two_member_test(
+    server: &mut TestServer,
+    host_cx: &mut TestAppContext,
+    remote_cx: &mut TestAppContext,
+) -> (TestClient, TestClient) {
+    let host_client = server.create_client(host_cx, "user_host").await;
+    let remote_client = server.create_client(remote_cx, "user_remote").await;
+
+    init_test(host_cx);
+    init_test(remote_cx);
+
+    server
+        .create_room(&mut [(&host_client, host_cx), (&remote_client, remote_cx)])
+        .await;
+
+    (host_client, remote_client)
+}
+
+async fn add_debugger_panel(workspace: &Entity<Workspace>, cx: &mut VisualTestContext) {
+    let debugger_panel = workspace
+        .update_in(cx, |_, window, cx| cx.spawn_in(window, DebugPanel::load))
+        .await
+        .unwrap();
+
+    workspace.update_in(cx, |workspace, window, cx| {
+        workspace.add_panel(debugger_panel, window, cx);
+    });
+}
+
+#[gpui::test]
+async fn test_following_into_shared_debug_session(
+    host_cx: &mut TestAppContext,
+    remote_cx: &mut TestAppContext,
+) {
+    let executor = host_cx.executor();
+    let mut server = TestServer::start(executor.clone()).await;
+    let (host_client, remote_client) = setup_two_member_test(&mut server, host_cx, remote_cx).await;
+
+    host_client
+        .fs()
+        .insert_tree("/project", json!({ "main.rs": "fn main() {}" }))
+        .await;
+    let (host_project, _) = host_client.build_local_project("/project", host_cx).await;
+    let project_id = host_cx
+        .read(ActiveCall::global)
+        .update(host_cx, |call, cx| {
+            call.share_project(host_project.clone(), cx)
+        })
+        .await
+        .unwrap();
+    let remote_project = remote_client
+        .join_remote_project(project_id, remote_cx)
+        .await;
+
+    let _subscription = project::debugger::test::intercept_debug_sessions(host_cx, |client| {
+        client.on_request::<Threads, _>(|_, _| {
+            Ok(dap::ThreadsResponse {
+                threads: vec![dap::Thread {
+                    id: 1,
+                    name: "Thread 1".into(),
+                }],
+            })
+        });
+        client.on_request::<StackTrace, _>(|_, _| {
+            Ok(dap::StackTraceResponse {
+                stack_frames: vec![stack_frame(1, "main"), stack_frame(2, "helper")],
+                total_frames: None,
+            })
+        });
+        client.on_request::<Scopes, _>(|_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+    });
+
+    let (host_workspace, host_cx) = host_client.build_workspace(&host_project, host_cx);
+    add_debugger_panel(&host_workspace, host_cx).await;
+    let (remote_workspace, remote_cx) = remote_client.build_workspace(&remote_project, remote_cx);
+    add_debugger_panel(&remote_workspace, remote_cx).await;
+
+    // The host starts a debug session, which is shared with the guest.
+    host_workspace
+        .update_in(host_cx, |workspace, window, cx| {
+            workspace.start_debug_session(
+                DebugTaskDefinition {
+                    adapter: "fake-adapter".into(),
+                    label: "test".into(),
+                    config: json!({ "request": "launch" }),
+                    tcp_connection: None,
+                }
+                .to_scenario(),
+                TaskContext::default(),
+                None,
+                window,
+                cx,
+            )
+        })
+        .unwrap();
+    executor.run_until_parked();
+
+    let host_session = host_project.read_with(host_cx, |project, cx| {
+        project
+            .dap_store()
+            .read(cx)
+            .sessions()
+            .next()
+            .unwrap()
+            .clone()
+    });
+    let session_id = host_session.read_with(host_cx, |session, _| session.session_id());
+    remote_project.read_with(remote_cx, |project, cx| {
+        let session = project
+            .dap_store()
+            .read(cx)
+            .session_by_id(session_id)
+            .expect("debug session was not shared with the guest");
+        assert!(session.read(cx).is_read_only());
+    });
+
+    // The program stops, and the host selects the thread that stopped.
+    let client = host_session.read_with(host_cx, |session, _| session.adapter_client().unwrap());
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    executor.run_until_parked();
+
+    let host_debug_session = host_workspace.read_with(host_cx, |workspace, cx| {
+        workspace
+            .panel::<DebugPanel>(cx)
+            .unwrap()
+            .read(cx)
+            .active_session()
+            .unwrap()
+    });
+    host_debug_session.update_in(host_cx, |debug_session, window, cx| {
+        debug_session.focus_handle(cx).focus(window);
+    });
+    executor.run_until_parked();
+
+    // The guest follows the host into the shared debug session.
+    let host_peer_id = host_client.peer_id().unwrap();
+    remote_workspace.update_in(remote_cx, |workspace, window, cx| {
+        workspace.follow(host_peer_id, window, cx)
+    });
+    executor.run_until_parked();
+
+    let remote_debug_session = remote_workspace.read_with(remote_cx, |workspace, cx| {
+        workspace
+            .panel::<DebugPanel>(cx)
+            .unwrap()
+            .read(cx)
+            .active_session()
+            .expect("guest is not shown the shared debug session")
+    });
+    let host_state = follow_state(&host_debug_session, host_cx);
+    assert_eq!(host_state.session_id, session_id.to_proto());
+    let host_follow_state = host_state.state.as_ref().unwrap();
+    assert_eq!(host_follow_state.thread_id, Some(1));
+    assert_eq!(host_follow_state.stack_frame_id, Some(1));
+    assert_eq!(follow_state(&remote_debug_session, remote_cx), host_state);
+}
+
+fn follow_state(
+    debug_session: &Entity<impl FollowableItem>,
+    cx: &mut VisualTestContext,
+) -> proto::view::DebugSession {
+    let state = debug_session.update_in(cx, |debug_session, window, cx| {
+        debug_session.to_state_proto(window, cx)
+    });
+    let Some(proto::view::Variant::DebugSession(state)) = state else {
+        panic!("debug session has no follow state");
+    };
+    state
+}
+
+fn stack_frame(id: u64, name: &str) -> StackFrame {
+    StackFrame {
+        id,
+        name: name.into(),
+        source: Some(dap::Source {
+            name: Some("main.rs".into()),
+            path: Some("/project/main.rs".into()),
+            source_reference: None,
+            presentation_hint: None,
+            origin: None,
+            sources: None,
+            adapter_data: None,
+            checksums: None,
+        }),
+        line: id,
+        column: 1,
+        end_line: None,
+        end_column: None,
+        can_restart: None,
+        instruction_pointer_reference: None,
+        module_id: None,
+        presentation_hint: None,
+    }
+}
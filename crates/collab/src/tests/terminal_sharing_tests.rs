@@ -0,0 +1,259 @@
+use crate::tests::TestServer;
+use call::ActiveCall;
+use futures::channel::mpsc;
+use gpui::{AppContext as _, BackgroundExecutor, Entity, TestAppContext};
+use project::{Project, terminals::SHARED_TERMINAL_UPDATE_INTERVAL};
+use rpc::proto;
+use serde_json::json;
+use terminal::{
+    Terminal, TerminalBuilder,
+    terminal_settings::{AlternateScroll, CursorShape},
+};
+
+#[gpui::test]
+async fn test_sharing_terminals(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a
+        .fs()
+        .insert_tree("/a", json!({ "a.txt": "a-contents" }))
+        .await;
+    let (project_a, _) = client_a.build_local_project("/a", cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+
+    // Client A shares a terminal that guests can type into.
+    let (terminal_a, mut input_rx) = build_terminal("shell", cx_a);
+    terminal_a.update(cx_a, |terminal, cx| {
+        terminal.write_output(b"$ echo hello\r\nhello\r\n$ ", cx)
+    });
+    let terminal_id = project_a
+        .update(cx_a, |project, cx| {
+            project.share_terminal(terminal_a.clone(), true, cx)
+        })
+        .unwrap();
+    executor.run_until_parked();
+
+    let terminal_b = remote_terminal(&project_b, terminal_id, cx_b);
+    terminal_b.read_with(cx_b, |terminal, _| {
+        assert!(terminal.is_display_only());
+        assert_eq!(terminal.title(false), "shell");
+        assert_eq!(
+            screen_lines(terminal),
+            ["$ echo hello", "hello", "$"].as_slice()
+        );
+    });
+
+    // Output on the host is streamed to the guest.
+    terminal_a.update(cx_a, |terminal, cx| {
+        terminal.write_output(b"ls\r\na.txt\r\n$ ", cx)
+    });
+    executor.advance_clock(SHARED_TERMINAL_UPDATE_INTERVAL);
+    executor.run_until_parked();
+    terminal_b.read_with(cx_b, |terminal, _| {
+        assert_eq!(
+            screen_lines(terminal),
+            ["$ echo hello", "hello", "$ ls", "a.txt", "$"].as_slice()
+        );
+    });
+
+    // Input typed by the guest is written to the host's terminal.
+    terminal_b.update(cx_b, |terminal, _| terminal.input(b"pwd\r".to_vec()));
+    executor.run_until_parked();
+    assert_eq!(input_rx.try_next().unwrap(), Some(b"pwd\r".to_vec()));
+
+    // Client A shares a second terminal read-only.
+    let (read_only_terminal_a, mut read_only_input_rx) = build_terminal("server logs", cx_a);
+    let read_only_terminal_id = project_a
+        .update(cx_a, |project, cx| {
+            project.share_terminal(read_only_terminal_a.clone(), false, cx)
+        })
+        .unwrap();
+    executor.run_until_parked();
+
+    let read_only_terminal_b = remote_terminal(&project_b, read_only_terminal_id, cx_b);
+    read_only_terminal_b.update(cx_b, |terminal, _| terminal.input(b"q".to_vec()));
+    executor.run_until_parked();
+    assert!(read_only_input_rx.try_next().is_err());
+
+    // The host rejects input sent for read-only terminals regardless of the client.
+    let response = client_b
+        .client()
+        .request(proto::SharedTerminalInput {
+            project_id,
+            terminal_id: read_only_terminal_id,
+            input: b"q".to_vec(),
+        })
+        .await;
+    assert!(response.is_err());
+    assert!(read_only_input_rx.try_next().is_err());
+
+    // Sharing the terminal again switches its mode without changing its id.
+    let reshared_terminal_id = project_a
+        .update(cx_a, |project, cx| {
+            project.share_terminal(read_only_terminal_a.clone(), true, cx)
+        })
+        .unwrap();
+    assert_eq!(reshared_terminal_id, read_only_terminal_id);
+    executor.run_until_parked();
+    project_b.read_with(cx_b, |project, _| {
+        assert!(project.remote_terminals()[&read_only_terminal_id].read_write);
+    });
+    read_only_terminal_b.update(cx_b, |terminal, _| terminal.input(b"q".to_vec()));
+    executor.run_until_parked();
+    assert_eq!(read_only_input_rx.try_next().unwrap(), Some(b"q".to_vec()));
+
+    // Guests stop seeing terminals once they are unshared or dropped.
+    project_a
+        .update(cx_a, |project, cx| {
+            project.unshare_terminal(terminal_id, cx)
+        })
+        .unwrap();
+    cx_a.update(|_| drop(read_only_terminal_a));
+    executor.run_until_parked();
+    project_b.read_with(cx_b, |project, _| {
+        assert!(project.remote_terminals().is_empty());
+    });
+}
+
+#[gpui::test]
+async fn test_shared_terminal_input_respects_channel_role(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    let channel_id = server
+        .make_public_channel("the-channel", &client_a, cx_a)
+        .await;
+
+    // Client A shares a project in the channel, along with a read-write terminal.
+    let project_a = client_a.build_test_project(cx_a).await;
+    active_call_a
+        .update(cx_a, |call, cx| call.join_channel(channel_id, cx))
+        .await
+        .unwrap();
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let (terminal_a, mut input_rx) = build_terminal("shell", cx_a);
+    terminal_a.update(cx_a, |terminal, cx| terminal.write_output(b"$ ", cx));
+    let terminal_id = project_a
+        .update(cx_a, |project, cx| {
+            project.share_terminal(terminal_a.clone(), true, cx)
+        })
+        .unwrap();
+    executor.run_until_parked();
+
+    // Client B joins the channel as a guest, and sees the terminal that was shared before.
+    cx_b.update(|cx| workspace::join_channel(channel_id, client_b.app_state.clone(), None, cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    let active_call_b = cx_b.read(ActiveCall::global);
+    let project_b =
+        active_call_b.read_with(cx_b, |call, _| call.location().unwrap().upgrade().unwrap());
+    assert!(project_b.read_with(cx_b, |project, cx| project.is_read_only(cx)));
+    let terminal_b = remote_terminal(&project_b, terminal_id, cx_b);
+    terminal_b.read_with(cx_b, |terminal, _| {
+        assert_eq!(screen_lines(terminal), ["$"].as_slice());
+    });
+
+    // Guests cannot type into the terminal, even by talking to the server directly.
+    terminal_b.update(cx_b, |terminal, _| terminal.input(b"rm -rf /\r".to_vec()));
+    executor.run_until_parked();
+    assert!(input_rx.try_next().is_err());
+    let response = client_b
+        .client()
+        .request(proto::SharedTerminalInput {
+            project_id,
+            terminal_id,
+            input: b"rm -rf /\r".to_vec(),
+        })
+        .await;
+    assert!(response.is_err());
+    assert!(input_rx.try_next().is_err());
+
+    // Once promoted to a member, B can type into the terminal.
+    active_call_a
+        .update(cx_a, |call, cx| {
+            call.room().unwrap().update(cx, |room, cx| {
+                room.set_participant_role(
+                    client_b.user_id().unwrap(),
+                    proto::ChannelRole::Member,
+                    cx,
+                )
+            })
+        })
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    assert!(project_b.read_with(cx_b, |project, cx| !project.is_read_only(cx)));
+
+    terminal_b.update(cx_b, |terminal, _| terminal.input(b"ls\r".to_vec()));
+    executor.run_until_parked();
+    assert_eq!(input_rx.try_next().unwrap(), Some(b"ls\r".to_vec()));
+}
+
+/// Builds a terminal that stands in for one running a shell, returning the input typed into it.
+fn build_terminal(
+    title: &str,
+    cx: &mut TestAppContext,
+) -> (Entity<Terminal>, mpsc::UnboundedReceiver<Vec<u8>>) {
+    let (input_tx, input_rx) = mpsc::unbounded();
+    let title = title.to_string();
+    let terminal = cx.new(|cx| {
+        TerminalBuilder::new_display_only(
+            Some(title.into()),
+            CursorShape::default(),
+            AlternateScroll::On,
+            None,
+            input_tx,
+        )
+        .subscribe(cx)
+    });
+    (terminal, input_rx)
+}
+
+fn remote_terminal(
+    project: &Entity<Project>,
+    terminal_id: u64,
+    cx: &mut TestAppContext,
+) -> Entity<Terminal> {
+    project.read_with(cx, |project, _| {
+        project
+            .remote_terminals()
+            .get(&terminal_id)
+            .expect("terminal was not shared with the guest")
+            .terminal
+            .clone()
+    })
+}
+
+fn screen_lines(terminal: &Terminal) -> Vec<String> {
+    terminal
+        .get_content()
+        .lines()
+        .map(|line| line.trim_end().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
@@ -308,6 +308,7 @@ impl TestServer {
             notifications::init(client.clone(), user_store, cx);
             collab_ui::init(&app_state, cx);
             file_finder::init(cx);
+            terminal::init(cx);
             menu::init();
             cx.bind_keys(
                 settings::KeymapFile::load_asset_allow_partial_failure(os_keymap, cx).unwrap(),
@@ -570,6 +571,12 @@ impl TestServer {
                 kinesis_stream: None,
                 kinesis_access_key: None,
                 kinesis_secret_key: None,
+                self_hosted: None,
+                auth_provider: None,
+                public_url: None,
+                oidc_issuer_url: None,
+                oidc_client_id: None,
+                oidc_client_secret: None,
             },
         })
     }
@@ -0,0 +1,425 @@
+use anyhow::Result;
+use buffer_diff::{BufferDiff, BufferDiffSnapshot};
+use channel::{ChannelBuffer, ChannelBufferEvent, ChannelBufferRevision};
+use collections::HashMap;
+use editor::{Editor, EditorEvent, ExcerptRange, MultiBuffer};
+use gpui::{
+    AnyElement, AnyView, App, AppContext as _, AsyncApp, ClickEvent, Context, Entity, EventEmitter,
+    FocusHandle, Focusable, Subscription, Task, Window,
+};
+use language::{Buffer, Capability, Language, LanguageRegistry, LineEnding, Rope, TextBuffer};
+use project::Project;
+use std::{any::TypeId, sync::Arc, time::Duration};
+use time::{OffsetDateTime, UtcOffset};
+use ui::{ListItem, ListItemSpacing, Tooltip, prelude::*};
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, TabContentParams},
+    searchable::SearchableItemHandle,
+};
+
+/// How long to wait after the notes are edited before updating a diff of their current text.
+const EDIT_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Lists the saved revisions of a channel's notes, and shows the changes between
+/// any two of them.
+pub struct ChannelNotesHistory {
+    channel_buffer: Entity<ChannelBuffer>,
+    editor: Entity<Editor>,
+    multibuffer: Entity<MultiBuffer>,
+    revisions: Vec<ChannelBufferRevision>,
+    revision_texts: HashMap<u64, String>,
+    /// The version whose changes are shown, as an index into `revisions`. An index
+    /// of `revisions.len()` refers to the current text of the notes.
+    new_version: usize,
+    /// The version that `new_version` is compared with, or `None` to compare with the
+    /// empty notes that the channel started out with.
+    old_version: Option<usize>,
+    local_timezone: UtcOffset,
+    load_revisions_task: Task<Result<()>>,
+    update_diff_task: Task<Result<()>>,
+    _subscription: Subscription,
+}
+
+impl ChannelNotesHistory {
+    pub fn open(
+        channel_buffer: Entity<ChannelBuffer>,
+        project: Entity<Project>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let existing = workspace
+            .active_pane()
+            .read(cx)
+            .items_of_type::<Self>()
+            .find(|history| history.read(cx).channel_buffer == channel_buffer);
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, window, cx);
+            existing.update(cx, |history, cx| history.load_revisions(cx));
+            return;
+        }
+
+        let history = cx.new(|cx| Self::new(channel_buffer, project, window, cx));
+        workspace.add_item_to_active_pane(Box::new(history), None, true, window, cx);
+    }
+
+    fn new(
+        channel_buffer: Entity<ChannelBuffer>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let multibuffer = cx.new(|_| MultiBuffer::new(Capability::ReadOnly));
+        let editor = cx.new(|cx| {
+            let mut editor =
+                Editor::for_multibuffer(multibuffer.clone(), Some(project), window, cx);
+            editor.disable_inline_diagnostics();
+            editor.set_expand_all_diff_hunks(cx);
+            editor
+        });
+        let subscription = cx.subscribe(&channel_buffer, |this, _, event, cx| {
+            if let ChannelBufferEvent::BufferEdited = event {
+                if this.new_version == this.revisions.len() {
+                    this.update_diff(EDIT_DEBOUNCE, cx);
+                }
+            }
+        });
+        let local_offset = chrono::Local::now().offset().local_minus_utc();
+
+        let mut this = Self {
+            channel_buffer,
+            editor,
+            multibuffer,
+            revisions: Vec::new(),
+            revision_texts: HashMap::default(),
+            new_version: 0,
+            old_version: None,
+            local_timezone: UtcOffset::from_whole_seconds(local_offset).unwrap(),
+            load_revisions_task: Task::ready(Ok(())),
+            update_diff_task: Task::ready(Ok(())),
+            _subscription: subscription,
+        };
+        this.load_revisions(cx);
+        this
+    }
+
+    /// Reloads the list of revisions, and compares the current notes with the latest one.
+    fn load_revisions(&mut self, cx: &mut Context<Self>) {
+        let revisions = self.channel_buffer.read(cx).revisions(cx);
+        self.load_revisions_task = cx.spawn(async move |this, cx| {
+            let revisions = revisions.await?;
+            this.update(cx, |this, cx| {
+                this.new_version = revisions.len();
+                this.old_version = revisions.len().checked_sub(1);
+                this.revisions = revisions;
+                this.update_diff(Duration::ZERO, cx);
+                cx.notify();
+            })
+        });
+    }
+
+    /// Shows the changes made in the given version. When `compare` is true, the
+    /// version is instead compared with the version that is already shown.
+    fn select_version(&mut self, ix: usize, compare: bool, cx: &mut Context<Self>) {
+        if compare {
+            if ix == self.new_version {
+                return;
+            }
+            self.old_version = Some(ix.min(self.new_version));
+            self.new_version = ix.max(self.new_version);
+        } else {
+            self.new_version = ix;
+            self.old_version = ix.checked_sub(1);
+        }
+        self.update_diff(Duration::ZERO, cx);
+        cx.notify();
+    }
+
+    fn restore(&mut self, _: &ClickEvent, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(revision_id) = self
+            .revisions
+            .get(self.new_version)
+            .map(|revision| revision.id)
+        else {
+            return;
+        };
+        let restore = self.channel_buffer.update(cx, |channel_buffer, cx| {
+            channel_buffer.restore_revision(revision_id, cx)
+        });
+        cx.spawn(async move |this, cx| {
+            restore.await?;
+            this.update(cx, |this, cx| {
+                this.select_version(this.revisions.len(), false, cx)
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn can_restore(&self, cx: &App) -> bool {
+        let channel_buffer = self.channel_buffer.read(cx);
+        self.new_version < self.revisions.len()
+            && channel_buffer.is_connected()
+            && !channel_buffer.buffer().read(cx).read_only()
+    }
+
+    fn version_text(&self, ix: usize, cx: &mut Context<Self>) -> Task<Result<String>> {
+        let Some(revision) = self.revisions.get(ix) else {
+            let buffer = self.channel_buffer.read(cx).buffer();
+            return Task::ready(Ok(buffer.read(cx).text()));
+        };
+        if let Some(text) = self.revision_texts.get(&revision.id) {
+            return Task::ready(Ok(text.clone()));
+        }
+
+        let revision_id = revision.id;
+        let text = self.channel_buffer.read(cx).revision_text(revision_id, cx);
+        cx.spawn(async move |this, cx| {
+            let text = text.await?;
+            this.update(cx, |this, _| {
+                this.revision_texts.insert(revision_id, text.clone())
+            })?;
+            Ok(text)
+        })
+    }
+
+    fn update_diff(&mut self, delay: Duration, cx: &mut Context<Self>) {
+        let old_text = match self.old_version {
+            Some(ix) => self.version_text(ix, cx),
+            None => Task::ready(Ok(String::new())),
+        };
+        let new_text = self.version_text(self.new_version, cx);
+        let buffer = self.channel_buffer.read(cx).buffer();
+        let language = buffer.read(cx).language().cloned();
+        let language_registry = buffer.read(cx).language_registry();
+
+        self.update_diff_task = cx.spawn(async move |this, cx| {
+            if !delay.is_zero() {
+                cx.background_executor().timer(delay).await;
+            }
+            let (old_text, new_text) = futures::join!(old_text, new_text);
+            let buffer = build_buffer(new_text?, language, cx)?;
+            let diff = build_buffer_diff(old_text?, &buffer, language_registry, cx).await?;
+            this.update(cx, |this, cx| {
+                this.multibuffer.update(cx, |multibuffer, cx| {
+                    let len = buffer.read(cx).len();
+                    multibuffer.clear(cx);
+                    multibuffer.push_excerpts(buffer, [ExcerptRange::new(0..len)], cx);
+                    multibuffer.add_diff(diff, cx);
+                });
+            })
+        });
+    }
+
+    fn render_version(&self, ix: usize, cx: &mut Context<Self>) -> ListItem {
+        let (title, authors) = match self.revisions.get(ix) {
+            Some(revision) => (
+                time_format::format_localized_timestamp(
+                    revision.timestamp,
+                    OffsetDateTime::now_utc(),
+                    self.local_timezone,
+                    time_format::TimestampFormat::EnhancedAbsolute,
+                ),
+                revision
+                    .authors
+                    .iter()
+                    .map(|author| author.github_login.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            None => ("Current version".to_string(), String::new()),
+        };
+        let is_old_version = self.old_version == Some(ix);
+
+        ListItem::new(("notes-version", ix))
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(ix == self.new_version || is_old_version)
+            .child(
+                v_flex()
+                    .child(Label::new(title))
+                    .when(!authors.is_empty(), |this| {
+                        this.child(
+                            Label::new(authors)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    }),
+            )
+            .end_slot::<Label>(is_old_version.then(|| {
+                Label::new("base")
+                    .size(LabelSize::XSmall)
+                    .color(Color::Muted)
+            }))
+            .tooltip(Tooltip::text(
+                "Click to view changes, or secondary-click to compare with the selected version",
+            ))
+            .on_click(cx.listener(move |this, event: &ClickEvent, _, cx| {
+                this.select_version(ix, event.modifiers().secondary(), cx)
+            }))
+    }
+}
+
+fn build_buffer(
+    mut text: String,
+    language: Option<Arc<Language>>,
+    cx: &mut AsyncApp,
+) -> Result<Entity<Buffer>> {
+    let line_ending = LineEnding::detect(&text);
+    LineEnding::normalize(&mut text);
+    cx.new(|cx| {
+        let buffer = TextBuffer::new_normalized(
+            0,
+            cx.entity_id().as_non_zero_u64().into(),
+            line_ending,
+            Rope::from(text),
+        );
+        let mut buffer = Buffer::build(buffer, None, Capability::ReadOnly);
+        buffer.set_language(language, cx);
+        buffer
+    })
+}
+
+async fn build_buffer_diff(
+    mut old_text: String,
+    buffer: &Entity<Buffer>,
+    language_registry: Option<Arc<LanguageRegistry>>,
+    cx: &mut AsyncApp,
+) -> Result<Entity<BufferDiff>> {
+    LineEnding::normalize(&mut old_text);
+    let buffer = cx.update(|cx| buffer.read(cx).snapshot())?;
+
+    let base_buffer = cx
+        .update(|cx| {
+            Buffer::build_snapshot(
+                old_text.as_str().into(),
+                buffer.language().cloned(),
+                language_registry,
+                cx,
+            )
+        })?
+        .await;
+
+    let diff_snapshot = cx
+        .update(|cx| {
+            BufferDiffSnapshot::new_with_base_buffer(
+                buffer.text.clone(),
+                Some(Arc::new(old_text)),
+                base_buffer,
+                cx,
+            )
+        })?
+        .await;
+
+    cx.new(|cx| {
+        let mut diff = BufferDiff::new(&buffer.text, cx);
+        diff.set_snapshot(diff_snapshot, &buffer.text, cx);
+        diff
+    })
+}
+
+impl EventEmitter<EditorEvent> for ChannelNotesHistory {}
+
+impl Focusable for ChannelNotesHistory {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Render for ChannelNotesHistory {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let can_restore = self.can_restore(cx);
+
+        h_flex()
+            .size_full()
+            .child(
+                v_flex()
+                    .w(px(280.))
+                    .h_full()
+                    .border_r_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(
+                        h_flex()
+                            .p_2()
+                            .justify_between()
+                            .border_b_1()
+                            .border_color(cx.theme().colors().border_variant)
+                            .child(Label::new("History"))
+                            .child(
+                                Button::new("restore-notes-version", "Restore")
+                                    .disabled(!can_restore)
+                                    .tooltip(Tooltip::text(
+                                        "Replace the notes with the selected version",
+                                    ))
+                                    .on_click(cx.listener(Self::restore)),
+                            ),
+                    )
+                    .child(
+                        v_flex()
+                            .id("notes-versions")
+                            .flex_1()
+                            .overflow_y_scroll()
+                            .py_1()
+                            .children(
+                                (0..=self.revisions.len())
+                                    .rev()
+                                    .map(|ix| self.render_version(ix, cx)),
+                            ),
+                    ),
+            )
+            .child(div().flex_1().h_full().child(self.editor.clone()))
+    }
+}
+
+impl Item for ChannelNotesHistory {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _: &Window, _: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::HistoryRerun).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(params.text_color())
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _: usize, cx: &App) -> SharedString {
+        match self.channel_buffer.read(cx).channel(cx) {
+            Some(channel) => format!("{} - history", channel.name).into(),
+            None => "Notes history".into(),
+        }
+    }
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.to_any())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+}
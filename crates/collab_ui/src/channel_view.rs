@@ -1,3 +1,4 @@
+use crate::channel_notes_history::ChannelNotesHistory;
 use anyhow::Result;
 use call::ActiveCall;
 use channel::{Channel, ChannelBuffer, ChannelBufferEvent, ChannelStore};
@@ -30,7 +31,7 @@ use workspace::{
 };
 use workspace::{item::Dedup, notifications::NotificationId};
 
-actions!(collab, [CopyLink]);
+actions!(collab, [CopyLink, OpenNotesHistory]);
 
 pub fn init(cx: &mut App) {
     workspace::FollowableViewRegistry::register::<ChannelView>(cx)
@@ -219,6 +220,7 @@ impl ChannelView {
                         })
                         .ok();
                     })
+                    .action("View History", Box::new(OpenNotesHistory))
                 }))
             });
             editor
@@ -345,6 +347,21 @@ impl ChannelView {
             .ok();
     }
 
+    fn open_notes_history(
+        &mut self,
+        _: &OpenNotesHistory,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let channel_buffer = self.channel_buffer.clone();
+        let project = self.project.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                ChannelNotesHistory::open(channel_buffer, project, workspace, window, cx)
+            })
+            .ok();
+    }
+
     pub fn channel(&self, cx: &App) -> Option<Arc<Channel>> {
         self.channel_buffer.read(cx).channel(cx)
     }
@@ -430,6 +447,7 @@ impl Render for ChannelView {
         div()
             .size_full()
             .on_action(cx.listener(Self::copy_link))
+            .on_action(cx.listener(Self::open_notes_history))
             .child(self.editor.clone())
     }
 }
@@ -1,7 +1,10 @@
 use crate::{ChatPanelButton, ChatPanelSettings, collab_panel};
 use anyhow::Result;
 use call::{ActiveCall, room};
-use channel::{ChannelChat, ChannelChatEvent, ChannelMessage, ChannelMessageId, ChannelStore};
+use channel::{
+    ChannelChat, ChannelChatEvent, ChannelMessage, ChannelMessageId, ChannelMessageSearchResults,
+    ChannelStore,
+};
 use client::{ChannelId, Client};
 use collections::HashMap;
 use db::kvp::KEY_VALUE_STORE;
@@ -22,8 +25,8 @@ use settings::Settings;
 use std::{sync::Arc, time::Duration};
 use time::{OffsetDateTime, UtcOffset};
 use ui::{
-    Avatar, Button, ContextMenu, IconButton, IconName, KeyBinding, Label, PopoverMenu, Tab, TabBar,
-    Tooltip, prelude::*,
+    Avatar, Button, ContextMenu, HighlightedLabel, IconButton, IconName, KeyBinding, Label,
+    PopoverMenu, Tab, TabBar, Tooltip, prelude::*,
 };
 use util::{ResultExt, TryFutureExt};
 use workspace::{
@@ -31,9 +34,11 @@ use workspace::{
     dock::{DockPosition, Panel, PanelEvent},
 };
 
-mod message_editor;
+pub(crate) mod message_editor;
 
 const MESSAGE_LOADING_THRESHOLD: usize = 50;
+/// How many earlier messages to show above a message that was jumped to.
+const JUMP_CONTEXT_MESSAGE_COUNT: usize = 3;
 const CHAT_PANEL_KEY: &str = "ChatPanel";
 
 pub fn init(cx: &mut App) {
@@ -64,6 +69,17 @@ pub struct ChatPanel {
     open_context_menu: Option<(u64, Subscription)>,
     highlighted_message: Option<(u64, Task<()>)>,
     last_acknowledged_message_id: Option<u64>,
+    search: Option<ChatSearch>,
+}
+
+/// A search of the active chat's messages.
+struct ChatSearch {
+    editor: Entity<Editor>,
+    /// The query that `results` were found for.
+    query: String,
+    results: Vec<ChannelMessage>,
+    done: bool,
+    pending_search: Option<Task<Result<()>>>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -71,7 +87,7 @@ struct SerializedChatPanel {
     width: Option<Pixels>,
 }
 
-actions!(chat_panel, [ToggleFocus]);
+actions!(chat_panel, [ToggleFocus, ToggleSearch]);
 
 impl ChatPanel {
     pub fn new(
@@ -140,6 +156,7 @@ impl ChatPanel {
                 open_context_menu: None,
                 highlighted_message: None,
                 last_acknowledged_message_id: None,
+                search: None,
             };
 
             if let Some(channel_id) = ActiveCall::global(cx)
@@ -250,6 +267,7 @@ impl ChatPanel {
             });
             let subscription = cx.subscribe(&chat, Self::channel_did_change);
             self.active_chat = Some((chat, subscription));
+            self.search = None;
             self.acknowledge_last_message(cx);
             cx.notify();
         }
@@ -877,10 +895,31 @@ impl ChatPanel {
             })?;
 
             if let Some(message_id) = scroll_to_message_id {
-                if let Some(item_ix) =
+                if let Some(mut item_ix) =
                     ChannelChat::load_history_since_message(chat.clone(), message_id, cx.clone())
                         .await
                 {
+                    // When jumping to a specific message, load the messages that came just
+                    // before it too, so that it's shown in context.
+                    if highlight_message_id.is_some() && item_ix < JUMP_CONTEXT_MESSAGE_COUNT {
+                        let load_more = chat.update(cx, |chat, cx| chat.load_more_messages(cx))?;
+                        if let Some(load_more) = load_more {
+                            load_more.await;
+                            item_ix = ChannelChat::load_history_since_message(
+                                chat.clone(),
+                                message_id,
+                                cx.clone(),
+                            )
+                            .await
+                            .unwrap_or(item_ix);
+                        }
+                    }
+                    let item_ix = if highlight_message_id.is_some() {
+                        item_ix.saturating_sub(JUMP_CONTEXT_MESSAGE_COUNT)
+                    } else {
+                        item_ix
+                    };
+
                     this.update(cx, |this, cx| {
                         if let Some(highlight_message_id) = highlight_message_id {
                             let task = cx.spawn(async move |this, cx| {
@@ -910,6 +949,172 @@ impl ChatPanel {
         })
     }
 
+    fn toggle_search(&mut self, _: &ToggleSearch, window: &mut Window, cx: &mut Context<Self>) {
+        if self.search.take().is_some() || self.active_chat.is_none() {
+            window.focus(&self.message_editor.focus_handle(cx));
+        } else {
+            let editor = cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text("Search messages…", cx);
+                editor
+            });
+            window.focus(&editor.focus_handle(cx));
+            self.search = Some(ChatSearch {
+                editor,
+                query: String::new(),
+                results: Vec::new(),
+                done: true,
+                pending_search: None,
+            });
+        }
+        cx.notify();
+    }
+
+    fn dismiss_search(&mut self, _: &actions::Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.search.take().is_some() {
+            window.focus(&self.message_editor.focus_handle(cx));
+            cx.notify();
+        }
+    }
+
+    fn confirm_search(&mut self, _: &Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        let query = search.editor.read(cx).text(cx).trim().to_string();
+        if query.is_empty() {
+            return;
+        }
+        search.query = query;
+        search.results.clear();
+        search.done = false;
+        self.load_search_results(cx);
+    }
+
+    fn load_search_results(&mut self, cx: &mut Context<Self>) {
+        let Some(((chat, _), search)) = self.active_chat.as_ref().zip(self.search.as_mut()) else {
+            return;
+        };
+        let query = search.query.clone();
+        let before_message_id = search.results.last().and_then(|message| message.id.into());
+        let results = chat.update(cx, |chat, cx| {
+            chat.search_messages(query.clone(), before_message_id, cx)
+        });
+        search.pending_search = Some(cx.spawn(async move |this, cx| {
+            let results = results.await;
+            this.update(cx, |this, cx| {
+                let Some(search) = this.search.as_mut().filter(|search| search.query == query)
+                else {
+                    return Ok(());
+                };
+                search.pending_search = None;
+                cx.notify();
+                let ChannelMessageSearchResults { messages, done } = results?;
+                search.results.extend(messages);
+                search.done = done;
+                Ok(())
+            })?
+        }));
+        cx.notify();
+    }
+
+    fn jump_to_search_result(&mut self, message_id: u64, cx: &mut Context<Self>) {
+        let Some(channel_id) = self.channel_id(cx) else {
+            return;
+        };
+        self.search = None;
+        self.select_channel(channel_id, Some(message_id), cx)
+            .detach_and_log_err(cx);
+        cx.notify();
+    }
+
+    fn render_search_results(&self, search: &ChatSearch, cx: &mut Context<Self>) -> AnyElement {
+        if search.results.is_empty() {
+            let status = if search.pending_search.is_some() {
+                "Searching…"
+            } else if search.query.is_empty() {
+                "Type a search and press enter."
+            } else {
+                "No messages found."
+            };
+            return div()
+                .p_2()
+                .child(
+                    Label::new(status)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any_element();
+        }
+
+        let words = search
+            .query
+            .split_whitespace()
+            .map(|word| word.to_ascii_lowercase())
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .id("chat-search-results")
+            .size_full()
+            .overflow_y_scroll()
+            .children(search.results.iter().filter_map(|message| {
+                let ChannelMessageId::Saved(message_id) = message.id else {
+                    return None;
+                };
+                let body = message.body.replace('\n', " ");
+                let highlight_indices = match_indices(&body, &words);
+                Some(
+                    v_flex()
+                        .id(("chat-search-result", message_id))
+                        .w_full()
+                        .p_1()
+                        .rounded_sm()
+                        .cursor(CursorStyle::PointingHand)
+                        .hover(|style| style.bg(cx.theme().colors().element_hover))
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .child(Avatar::new(message.sender.avatar_uri.clone()))
+                                .child(
+                                    Label::new(message.sender.github_login.clone())
+                                        .size(LabelSize::Small)
+                                        .weight(FontWeight::BOLD),
+                                )
+                                .child(
+                                    Label::new(time_format::format_localized_timestamp(
+                                        message.timestamp,
+                                        OffsetDateTime::now_utc(),
+                                        self.local_timezone,
+                                        time_format::TimestampFormat::EnhancedAbsolute,
+                                    ))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                                ),
+                        )
+                        .child(
+                            HighlightedLabel::new(body, highlight_indices)
+                                .size(LabelSize::Small)
+                                .single_line(),
+                        )
+                        .tooltip(Tooltip::text("Go to message"))
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.jump_to_search_result(message_id, cx)
+                        })),
+                )
+            }))
+            .when(!search.done, |this| {
+                this.child(
+                    div().p_1().child(
+                        Button::new("load-more-search-results", "Load more")
+                            .full_width()
+                            .disabled(search.pending_search.is_some())
+                            .on_click(cx.listener(|this, _, _, cx| this.load_search_results(cx))),
+                    ),
+                )
+            })
+            .into_any_element()
+    }
+
     fn close_reply_preview(&mut self, cx: &mut Context<Self>) {
         self.message_editor
             .update(cx, |editor, _| editor.clear_reply_to_message_id());
@@ -953,6 +1158,7 @@ impl Render for ChatPanel {
             .track_focus(&self.focus_handle)
             .size_full()
             .on_action(cx.listener(Self::send))
+            .on_action(cx.listener(Self::toggle_search))
             .child(
                 h_flex().child(
                     TabBar::new("chat_header").child(
@@ -960,6 +1166,7 @@ impl Render for ChatPanel {
                             .w_full()
                             .h(Tab::container_height(cx))
                             .px_2()
+                            .justify_between()
                             .child(Label::new(
                                 self.active_chat
                                     .as_ref()
@@ -967,12 +1174,41 @@ impl Render for ChatPanel {
                                         Some(format!("#{}", c.0.read(cx).channel(cx)?.name))
                                     })
                                     .unwrap_or("Chat".to_string()),
-                            )),
+                            ))
+                            .when(self.active_chat.is_some(), |this| {
+                                this.child(
+                                    IconButton::new(
+                                        "toggle-chat-search",
+                                        IconName::MagnifyingGlass,
+                                    )
+                                    .icon_size(IconSize::Small)
+                                    .toggle_state(self.search.is_some())
+                                    .tooltip(Tooltip::text("Search Messages"))
+                                    .on_click(
+                                        |_, window, cx| {
+                                            window.dispatch_action(ToggleSearch.boxed_clone(), cx)
+                                        },
+                                    ),
+                                )
+                            }),
                     ),
                 ),
             )
+            .when_some(self.search.as_ref(), |this, search| {
+                this.child(
+                    h_flex()
+                        .p_2()
+                        .border_b_1()
+                        .border_color(cx.theme().colors().border)
+                        .on_action(cx.listener(Self::confirm_search))
+                        .on_action(cx.listener(Self::dismiss_search))
+                        .child(search.editor.clone()),
+                )
+            })
             .child(div().flex_grow().px_2().map(|this| {
-                if self.active_chat.is_some() {
+                if let Some(search) = self.search.as_ref() {
+                    this.child(self.render_search_results(search, cx))
+                } else if self.active_chat.is_some() {
                     this.child(list(self.message_list.clone()).size_full())
                 } else {
                     this.child(
@@ -1106,9 +1342,30 @@ impl Render for ChatPanel {
     }
 }
 
+/// Returns the byte indices of the characters in `text` that are part of a case-insensitive
+/// match for one of `words`, which must be lowercase.
+fn match_indices(text: &str, words: &[String]) -> Vec<usize> {
+    let lowercase_text = text.to_ascii_lowercase();
+    let mut indices = Vec::new();
+    for word in words.iter().filter(|word| !word.is_empty()) {
+        for (start, _) in lowercase_text.match_indices(word.as_str()) {
+            indices.extend(
+                text[start..start + word.len()]
+                    .char_indices()
+                    .map(|(ix, _)| start + ix),
+            );
+        }
+    }
+    indices.sort_unstable();
+    indices.dedup();
+    indices
+}
+
 impl Focusable for ChatPanel {
     fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
-        if self.active_chat.is_some() {
+        if let Some(search) = self.search.as_ref() {
+            search.editor.focus_handle(cx)
+        } else if self.active_chat.is_some() {
             self.message_editor.read(cx).focus_handle(cx)
         } else {
             self.focus_handle.clone()
@@ -1,6 +1,6 @@
 use anyhow::{Context as _, Result};
 use channel::{ChannelChat, ChannelStore, MessageParams};
-use client::{UserId, UserStore};
+use client::{ChannelId, UserId, UserStore};
 use collections::HashSet;
 use editor::{AnchorRangeExt, CompletionProvider, Editor, EditorElement, EditorStyle, ExcerptId};
 use fuzzy::{StringMatch, StringMatchCandidate};
@@ -46,6 +46,7 @@ pub struct MessageEditor {
     pub editor: Entity<Editor>,
     user_store: Entity<UserStore>,
     channel_chat: Option<Entity<ChannelChat>>,
+    mention_channel_id: Option<ChannelId>,
     mentions: Vec<UserId>,
     mentions_task: Option<Task<()>>,
     reply_to_message_id: Option<u64>,
@@ -141,6 +142,7 @@ impl MessageEditor {
             editor,
             user_store,
             channel_chat,
+            mention_channel_id: None,
             mentions: Vec::new(),
             mentions_task: None,
             reply_to_message_id: None,
@@ -188,6 +190,12 @@ impl MessageEditor {
         });
     }
 
+    /// Suggests the participants of the given channel when mentioning users, for
+    /// editors that aren't associated with a channel's chat.
+    pub fn set_mention_channel_id(&mut self, channel_id: Option<ChannelId>) {
+        self.mention_channel_id = channel_id;
+    }
+
     pub fn take_message(&mut self, window: &mut Window, cx: &mut Context<Self>) -> MessageParams {
         self.editor.update(cx, |editor, cx| {
             let highlights = editor.text_highlights::<Self>(cx);
@@ -381,6 +389,13 @@ impl MessageEditor {
             {
                 names.insert(message.sender.github_login.clone());
             }
+        } else if let Some(channel_id) = self.mention_channel_id {
+            for participant in ChannelStore::global(cx)
+                .read(cx)
+                .channel_participants(channel_id)
+            {
+                names.insert(participant.github_login.clone());
+            }
         }
 
         let candidates = names
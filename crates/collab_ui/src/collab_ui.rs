@@ -1,9 +1,11 @@
+pub mod channel_notes_history;
 pub mod channel_view;
 pub mod chat_panel;
 pub mod collab_panel;
 pub mod notification_panel;
 pub mod notifications;
 mod panel_settings;
+pub mod review_threads;
 
 use std::{rc::Rc, sync::Arc};
 
@@ -32,6 +34,7 @@ pub fn init(app_state: &Arc<AppState>, cx: &mut App) {
     collab_panel::init(cx);
     notification_panel::init(cx);
     notifications::init(app_state, cx);
+    review_threads::init(cx);
     title_bar::init(cx);
 }
 
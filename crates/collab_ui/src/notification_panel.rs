@@ -12,11 +12,11 @@ use gpui::{
     WeakEntity, Window, actions, div, img, list, px,
 };
 use notifications::{NotificationEntry, NotificationEvent, NotificationStore};
-use project::Fs;
+use project::{Fs, ProjectPath};
 use rpc::proto;
 use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsStore};
-use std::{sync::Arc, time::Duration};
+use std::{path::Path, sync::Arc, time::Duration};
 use time::{OffsetDateTime, UtcOffset};
 use ui::{
     Avatar, Button, Icon, IconButton, IconName, Label, Tab, Tooltip, h_flex, prelude::*, v_flex,
@@ -425,6 +425,25 @@ impl NotificationPanel {
                     can_navigate: true,
                 })
             }
+            Notification::ReviewCommentMention {
+                sender_id,
+                channel_id,
+                ref path,
+                ..
+            } => {
+                let sender = user_store.get_cached_user(sender_id)?;
+                let channel = channel_store.channel_for_id(ChannelId(channel_id))?;
+                Some(NotificationPresenter {
+                    icon: "icons/conversations.svg",
+                    text: format!(
+                        "{} mentioned you in a review of {path} in #{}",
+                        sender.github_login, channel.name,
+                    ),
+                    needs_response: false,
+                    actor: Some(sender),
+                    can_navigate: true,
+                })
+            }
         }
     }
 
@@ -439,7 +458,8 @@ impl NotificationPanel {
             Notification::ContactRequestAccepted { .. } => true,
             Notification::ContactRequest { .. }
             | Notification::ChannelInvitation { .. }
-            | Notification::ChannelMessageMention { .. } => false,
+            | Notification::ChannelMessageMention { .. }
+            | Notification::ReviewCommentMention { .. } => false,
         };
 
         if should_mark_as_read {
@@ -486,6 +506,38 @@ impl NotificationPanel {
                     });
                 });
             }
+        } else if let Notification::ReviewCommentMention { path, .. } = notification.clone() {
+            if let Some(workspace) = self.workspace.upgrade() {
+                window.defer(cx, move |window, cx| {
+                    workspace.update(cx, |workspace, cx| {
+                        // Review threads are keyed by paths that start with their worktree's root name.
+                        let Some((root_name, path)) = path.split_once('/') else {
+                            return;
+                        };
+                        let Some(worktree_id) = workspace
+                            .project()
+                            .read(cx)
+                            .visible_worktrees(cx)
+                            .find(|worktree| worktree.read(cx).root_name() == root_name)
+                            .map(|worktree| worktree.read(cx).id())
+                        else {
+                            return;
+                        };
+                        workspace
+                            .open_path(
+                                ProjectPath {
+                                    worktree_id,
+                                    path: Arc::from(Path::new(path)),
+                                },
+                                None,
+                                true,
+                                window,
+                                cx,
+                            )
+                            .detach_and_log_err(cx);
+                    });
+                });
+            }
         }
     }
 
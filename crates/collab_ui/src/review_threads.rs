@@ -0,0 +1,747 @@
+use crate::chat_panel::message_editor::MessageEditor;
+use call::ActiveCall;
+use channel::{
+    ChannelStore, ReviewThread, ReviewThreadLocation, ReviewThreadStore, ReviewThreadStoreEvent,
+};
+use client::ChannelId;
+use collections::{HashMap, HashSet};
+use editor::{
+    Editor,
+    display_map::{BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId},
+};
+use gpui::{
+    AnyElement, App, AppContext as _, Context, Entity, Subscription, Task, WeakEntity, Window,
+    actions,
+};
+use language::{Anchor, AnchorRangeExt as _, Buffer, BufferEvent, LineEnding, Point, ToPoint as _};
+use project::Project;
+use rpc::proto::ToProto as _;
+use std::{ops::Range, sync::Arc, time::Duration};
+use time::{OffsetDateTime, UtcOffset};
+use ui::{Avatar, Tooltip, prelude::*};
+use util::ResultExt as _;
+
+actions!(collab, [StartReviewThread]);
+
+/// How long to wait after a file is edited before recording where its review threads moved.
+const UPDATE_LOCATIONS_DEBOUNCE: Duration = Duration::from_millis(500);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|editor: &mut Editor, window, cx| {
+        if let Some(window) = window {
+            register_editor(editor, window, cx);
+        }
+    })
+    .detach();
+}
+
+struct ReviewThreadsAddon {
+    _review_threads: Entity<EditorReviewThreads>,
+}
+
+impl editor::Addon for ReviewThreadsAddon {
+    fn to_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+fn register_editor(editor: &mut Editor, window: &mut Window, cx: &mut Context<Editor>) {
+    if !editor.mode().is_full() {
+        return;
+    }
+    let Some(project) = editor.project.clone() else {
+        return;
+    };
+    let Some(buffer) = editor.buffer().read(cx).as_singleton() else {
+        return;
+    };
+
+    let editor_handle = cx.weak_entity();
+    let review_threads =
+        cx.new(|cx| EditorReviewThreads::new(editor_handle, buffer, project, window, cx));
+    editor
+        .register_action({
+            let review_threads = review_threads.downgrade();
+            move |_: &StartReviewThread, window, cx| {
+                review_threads
+                    .update(cx, |review_threads, cx| {
+                        review_threads.start_thread(window, cx)
+                    })
+                    .ok();
+            }
+        })
+        .detach();
+    editor.register_addon(ReviewThreadsAddon {
+        _review_threads: review_threads,
+    });
+}
+
+/// Shows the review threads attached to the file of a singleton editor, for the
+/// channel whose call the user is in.
+struct EditorReviewThreads {
+    editor: WeakEntity<Editor>,
+    buffer: Entity<Buffer>,
+    project: Entity<Project>,
+    file: Option<(ChannelId, Arc<str>)>,
+    threads: HashMap<u64, ThreadBlock>,
+    draft: Option<DraftThread>,
+    local_timezone: UtcOffset,
+    /// The blob id of the buffer's contents, and the version and line ending it was computed for.
+    blob_sha: Option<(clock::Global, LineEnding, Option<String>)>,
+    update_locations_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+struct ThreadBlock {
+    /// The location stored on the server that `range` was resolved from.
+    location: ReviewThreadLocation,
+    range: Range<Anchor>,
+    outdated: bool,
+    block_id: Option<CustomBlockId>,
+    reply_editor: Entity<MessageEditor>,
+}
+
+struct DraftThread {
+    range: Range<Anchor>,
+    block_id: CustomBlockId,
+    editor: Entity<MessageEditor>,
+}
+
+enum ReviewThreadHighlight {}
+
+impl EditorReviewThreads {
+    fn new(
+        editor: WeakEntity<Editor>,
+        buffer: Entity<Buffer>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let local_offset = chrono::Local::now().offset().local_minus_utc();
+        let mut subscriptions = vec![
+            cx.subscribe_in(
+                &ReviewThreadStore::global(cx),
+                window,
+                |this, _, event, window, cx| match event {
+                    ReviewThreadStoreEvent::ThreadsUpdated { channel_id, path } => {
+                        if this.file.as_ref() == Some(&(*channel_id, path.clone())) {
+                            this.refresh_blocks(window, cx);
+                        }
+                    }
+                },
+            ),
+            cx.subscribe_in(&buffer, window, |this, _, event, window, cx| match event {
+                BufferEvent::Edited | BufferEvent::Saved | BufferEvent::Reloaded => {
+                    this.schedule_location_updates(cx)
+                }
+                BufferEvent::FileHandleChanged => this.file_changed(window, cx),
+                _ => {}
+            }),
+        ];
+        if let Some(active_call) = ActiveCall::try_global(cx) {
+            subscriptions.push(cx.observe_in(&active_call, window, |this, _, window, cx| {
+                this.file_changed(window, cx)
+            }));
+        }
+
+        let mut this = Self {
+            editor,
+            buffer,
+            project,
+            file: None,
+            threads: HashMap::default(),
+            draft: None,
+            local_timezone: UtcOffset::from_whole_seconds(local_offset).unwrap(),
+            blob_sha: None,
+            update_locations_task: Task::ready(()),
+            _subscriptions: subscriptions,
+        };
+        this.file_changed(window, cx);
+        this
+    }
+
+    /// Determines which threads to show after the user joined or left a channel's
+    /// call, or after the buffer's file changed.
+    fn file_changed(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let file = active_channel_id(cx).zip(self.path(cx));
+        if file == self.file {
+            return;
+        }
+
+        self.file = file.clone();
+        self.cancel_draft(cx);
+        self.threads.clear();
+        self.refresh_blocks(window, cx);
+        if let Some((channel_id, path)) = file {
+            ReviewThreadStore::global(cx)
+                .update(cx, |store, cx| store.load_threads(channel_id, path, cx))
+                .detach_and_log_err(cx);
+        }
+    }
+
+    fn path(&self, cx: &App) -> Option<Arc<str>> {
+        let file = self.buffer.read(cx).file()?;
+        Some(file.full_path(cx).to_proto().into())
+    }
+
+    fn start_thread(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((channel_id, _)) = self.file.clone() else {
+            return;
+        };
+        let Some(editor) = self.editor.upgrade() else {
+            return;
+        };
+        self.cancel_draft(cx);
+
+        let buffer = self.buffer.read(cx).snapshot();
+        let selection = editor.read(cx).selections.newest_anchor().clone();
+        let mut range = selection.start.text_anchor.to_point(&buffer)
+            ..selection.end.text_anchor.to_point(&buffer);
+        if range.is_empty() {
+            range = Point::new(range.start.row, 0)
+                ..Point::new(range.start.row, buffer.line_len(range.start.row));
+        }
+        let range = buffer.anchor_after(range.start)..buffer.anchor_before(range.end);
+
+        let draft_editor =
+            self.build_message_editor(channel_id, "Start a review thread…", window, cx);
+        let Some(block_id) = self.insert_block(
+            &range,
+            4,
+            {
+                let draft_editor = draft_editor.clone();
+                let this = cx.weak_entity();
+                Arc::new(move |cx| render_draft(&draft_editor, this.clone(), cx))
+            },
+            cx,
+        ) else {
+            return;
+        };
+        draft_editor.read(cx).editor.focus_handle(cx).focus(window);
+        self.draft = Some(DraftThread {
+            range,
+            block_id,
+            editor: draft_editor,
+        });
+    }
+
+    fn submit_draft(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some((channel_id, path)), Some(draft)) = (self.file.clone(), self.draft.as_ref())
+        else {
+            return;
+        };
+        let comment = draft
+            .editor
+            .update(cx, |editor, cx| editor.take_message(window, cx));
+        if comment.text.trim().is_empty() {
+            return;
+        }
+
+        let range = draft.range.clone();
+        let blob_sha = self.blob_sha(cx);
+        let snapshot = self.buffer.read(cx).snapshot();
+        let location = ReviewThreadLocation::new(range.to_point(&snapshot), &snapshot, blob_sha);
+        ReviewThreadStore::global(cx)
+            .update(cx, |store, cx| {
+                store.create_thread(channel_id, path, location, comment, cx)
+            })
+            .detach_and_log_err(cx);
+        self.cancel_draft(cx);
+    }
+
+    fn cancel_draft(&mut self, cx: &mut Context<Self>) {
+        if let Some(draft) = self.draft.take() {
+            self.editor
+                .update(cx, |editor, cx| {
+                    editor.remove_blocks(HashSet::from_iter([draft.block_id]), None, cx)
+                })
+                .ok();
+        }
+    }
+
+    fn reply(&mut self, thread_id: u64, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some((channel_id, _)), Some(thread)) =
+            (self.file.clone(), self.threads.get(&thread_id))
+        else {
+            return;
+        };
+        let comment = thread
+            .reply_editor
+            .update(cx, |editor, cx| editor.take_message(window, cx));
+        if comment.text.trim().is_empty() {
+            return;
+        }
+        ReviewThreadStore::global(cx)
+            .update(cx, |store, cx| {
+                store.reply(channel_id, thread_id, comment, cx)
+            })
+            .detach_and_log_err(cx);
+    }
+
+    fn set_resolved(&mut self, thread_id: u64, resolved: bool, cx: &mut Context<Self>) {
+        let Some((channel_id, _)) = self.file.clone() else {
+            return;
+        };
+        ReviewThreadStore::global(cx)
+            .update(cx, |store, cx| {
+                store.set_resolved(channel_id, thread_id, resolved, cx)
+            })
+            .detach_and_log_err(cx);
+    }
+
+    /// Re-creates the blocks showing each thread, after threads were loaded or changed.
+    fn refresh_blocks(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let removed_block_ids = self
+            .threads
+            .values_mut()
+            .filter_map(|thread| thread.block_id.take())
+            .collect::<HashSet<_>>();
+        self.editor
+            .update(cx, |editor, cx| {
+                editor.remove_blocks(removed_block_ids, None, cx);
+                editor.clear_background_highlights::<ReviewThreadHighlight>(cx);
+            })
+            .ok();
+
+        let Some((channel_id, path)) = self.file.clone() else {
+            return;
+        };
+        let review_thread_store = ReviewThreadStore::global(cx);
+        let Some(threads) = review_thread_store
+            .read(cx)
+            .threads_for_path(channel_id, &path)
+            .map(|threads| threads.to_vec())
+        else {
+            return;
+        };
+
+        let blob_sha = self.blob_sha(cx);
+        let snapshot = self.buffer.read(cx).snapshot();
+        let mut highlighted_ranges = Vec::new();
+        for thread in threads {
+            let existing = self.threads.remove(&thread.id);
+            let mut thread_block = match existing {
+                // Keep the anchors of threads that haven't moved on the server, since
+                // they already account for any edits made since they were resolved.
+                Some(existing) if existing.location == thread.location => existing,
+                existing => {
+                    let resolved = thread.location.resolve(&snapshot, blob_sha.as_deref());
+                    let reply_editor = match existing {
+                        Some(existing) => existing.reply_editor,
+                        None => self.build_message_editor(channel_id, "Reply…", window, cx),
+                    };
+                    ThreadBlock {
+                        location: thread.location.clone(),
+                        range: snapshot.anchor_after(resolved.range.start)
+                            ..snapshot.anchor_before(resolved.range.end),
+                        outdated: resolved.outdated,
+                        block_id: None,
+                        reply_editor,
+                    }
+                }
+            };
+
+            if !thread.is_resolved() {
+                highlighted_ranges.push(thread_block.range.clone());
+            }
+            let height = thread_block_height(&thread);
+            thread_block.block_id = self.insert_block(
+                &thread_block.range,
+                height,
+                {
+                    let reply_editor = thread_block.reply_editor.clone();
+                    let outdated = thread_block.outdated;
+                    let local_timezone = self.local_timezone;
+                    let this = cx.weak_entity();
+                    Arc::new(move |cx| {
+                        render_thread(
+                            &thread,
+                            outdated,
+                            &reply_editor,
+                            local_timezone,
+                            this.clone(),
+                            cx,
+                        )
+                    })
+                },
+                cx,
+            );
+            self.threads.insert(thread.id, thread_block);
+        }
+
+        self.editor
+            .update(cx, |editor, cx| {
+                let multibuffer = editor.buffer().read(cx).snapshot(cx);
+                let Some((excerpt_id, _, _)) = multibuffer.as_singleton() else {
+                    return;
+                };
+                let ranges = highlighted_ranges
+                    .into_iter()
+                    .filter_map(|range| {
+                        Some(
+                            multibuffer.anchor_in_excerpt(*excerpt_id, range.start)?
+                                ..multibuffer.anchor_in_excerpt(*excerpt_id, range.end)?,
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                editor.highlight_background::<ReviewThreadHighlight>(
+                    &ranges,
+                    |theme| theme.colors().editor_document_highlight_bracket_background,
+                    cx,
+                );
+            })
+            .ok();
+    }
+
+    fn insert_block(
+        &self,
+        range: &Range<Anchor>,
+        height: u32,
+        render: editor::display_map::RenderBlock,
+        cx: &mut Context<Self>,
+    ) -> Option<CustomBlockId> {
+        self.editor
+            .update(cx, |editor, cx| {
+                let multibuffer = editor.buffer().read(cx).snapshot(cx);
+                let (excerpt_id, _, _) = multibuffer.as_singleton()?;
+                let anchor = multibuffer.anchor_in_excerpt(*excerpt_id, range.end)?;
+                editor
+                    .insert_blocks(
+                        [BlockProperties {
+                            placement: BlockPlacement::Below(anchor),
+                            height: Some(height),
+                            style: BlockStyle::Sticky,
+                            render,
+                            priority: 0,
+                            render_in_minimap: false,
+                        }],
+                        None,
+                        cx,
+                    )
+                    .pop()
+            })
+            .ok()
+            .flatten()
+    }
+
+    /// Records where threads moved after their file was edited. Only the host of a
+    /// file records this, so that collaborators don't race to move the same threads.
+    fn schedule_location_updates(&mut self, cx: &mut Context<Self>) {
+        let is_local = self
+            .buffer
+            .read(cx)
+            .file()
+            .map_or(false, |file| file.is_local());
+        if !is_local || self.file.is_none() {
+            return;
+        }
+
+        self.update_locations_task = cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(UPDATE_LOCATIONS_DEBOUNCE)
+                .await;
+            this.update(cx, |this, cx| this.update_locations(cx)).ok();
+        });
+    }
+
+    fn update_locations(&mut self, cx: &mut Context<Self>) {
+        let Some((channel_id, _)) = self.file.clone() else {
+            return;
+        };
+        let blob_sha = self.blob_sha(cx);
+        let snapshot = self.buffer.read(cx).snapshot();
+        let moved_threads = self
+            .threads
+            .iter()
+            .filter(|(_, thread)| !thread.outdated)
+            .filter_map(|(thread_id, thread)| {
+                let location = ReviewThreadLocation::new(
+                    thread.range.to_point(&snapshot),
+                    &snapshot,
+                    blob_sha.clone(),
+                );
+                let has_moved = location.range != thread.location.range
+                    || location.anchor_text != thread.location.anchor_text
+                    || (location.blob_sha.is_some()
+                        && location.blob_sha != thread.location.blob_sha);
+                has_moved.then_some((*thread_id, location))
+            })
+            .collect::<Vec<_>>();
+
+        for (thread_id, location) in moved_threads {
+            if let Some(thread) = self.threads.get_mut(&thread_id) {
+                thread.location = location.clone();
+            }
+            ReviewThreadStore::global(cx)
+                .update(cx, |store, cx| {
+                    store.move_thread(channel_id, thread_id, location, cx)
+                })
+                .detach_and_log_err(cx);
+        }
+    }
+
+    /// Returns the id of the git blob for the buffer's contents, if they are saved.
+    /// Hashing the whole buffer is expensive, so the result is reused until the
+    /// buffer changes.
+    fn blob_sha(&mut self, cx: &App) -> Option<String> {
+        let buffer = self.buffer.read(cx);
+        if buffer.is_dirty() || buffer.file().is_none() {
+            return None;
+        }
+        let version = buffer.version();
+        let line_ending = buffer.line_ending();
+        if let Some((cached_version, cached_line_ending, blob_sha)) = &self.blob_sha {
+            if *cached_version == version && *cached_line_ending == line_ending {
+                return blob_sha.clone();
+            }
+        }
+
+        let blob_sha = buffer_blob_sha(buffer);
+        self.blob_sha = Some((version, line_ending, blob_sha.clone()));
+        blob_sha
+    }
+
+    fn build_message_editor(
+        &self,
+        channel_id: ChannelId,
+        placeholder: &'static str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<MessageEditor> {
+        let languages = self.project.read(cx).languages().clone();
+        let user_store = self.project.read(cx).user_store();
+        cx.new(|cx| {
+            let editor = cx.new(|cx| {
+                let mut editor = Editor::auto_height(1, 6, window, cx);
+                editor.set_placeholder_text(placeholder, cx);
+                editor
+            });
+            let mut message_editor =
+                MessageEditor::new(languages, user_store, None, editor, window, cx);
+            message_editor.set_mention_channel_id(Some(channel_id));
+            message_editor
+        })
+    }
+}
+
+fn active_channel_id(cx: &App) -> Option<ChannelId> {
+    let active_call = ActiveCall::try_global(cx)?;
+    active_call.read(cx).room()?.read(cx).channel_id()
+}
+
+fn buffer_blob_sha(buffer: &Buffer) -> Option<String> {
+    let mut text = buffer.text();
+    if buffer.line_ending() == LineEnding::Windows {
+        text = text.replace('\n', "\r\n");
+    }
+    git::Oid::for_blob(text.as_bytes())
+        .log_err()
+        .map(|oid| oid.to_string())
+}
+
+fn thread_block_height(thread: &ReviewThread) -> u32 {
+    if thread.is_resolved() {
+        return 2;
+    }
+    let comment_lines = thread
+        .comments
+        .iter()
+        .map(|comment| 1 + comment.body.lines().count().max(1) as u32)
+        .sum::<u32>();
+    comment_lines + 4
+}
+
+fn render_thread(
+    thread: &ReviewThread,
+    outdated: bool,
+    reply_editor: &Entity<MessageEditor>,
+    local_timezone: UtcOffset,
+    this: WeakEntity<EditorReviewThreads>,
+    cx: &mut BlockContext,
+) -> AnyElement {
+    let thread_id = thread.id;
+    let is_resolved = thread.is_resolved();
+    let channel_name = ChannelStore::global(cx)
+        .read(cx)
+        .channel_for_id(thread.channel_id)
+        .map(|channel| channel.name.clone());
+    let mut header = match channel_name {
+        Some(channel_name) => format!("Review thread in #{channel_name}"),
+        None => "Review thread".to_string(),
+    };
+    if is_resolved {
+        header.push_str(" (resolved)");
+    }
+    let now = OffsetDateTime::now_utc();
+
+    v_flex()
+        .id(("review-thread", thread_id as usize))
+        .block_mouse_except_scroll()
+        .ml(cx.margins.gutter.full_width())
+        .mr_4()
+        .my_1()
+        .p_2()
+        .gap_1()
+        .max_w(rems(48.))
+        .rounded_md()
+        .border_1()
+        .border_color(cx.theme().colors().border)
+        .bg(cx.theme().colors().elevated_surface_background)
+        .child(
+            h_flex()
+                .justify_between()
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            Label::new(header)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .when(outdated, |this| {
+                            this.child(
+                                Label::new("outdated")
+                                    .size(LabelSize::Small)
+                                    .color(Color::Warning),
+                            )
+                        }),
+                )
+                .child(
+                    Button::new(
+                        ("resolve-review-thread", thread_id as usize),
+                        if is_resolved { "Reopen" } else { "Resolve" },
+                    )
+                    .label_size(LabelSize::Small)
+                    .on_click({
+                        let this = this.clone();
+                        move |_, _, cx| {
+                            this.update(cx, |this, cx| {
+                                this.set_resolved(thread_id, !is_resolved, cx)
+                            })
+                            .ok();
+                        }
+                    }),
+                ),
+        )
+        .when(!is_resolved, |parent| {
+            parent
+                .children(thread.comments.iter().map(|comment| {
+                    v_flex()
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .child(
+                                    Avatar::new(comment.sender.avatar_uri.clone()).size(rems(1.)),
+                                )
+                                .child(
+                                    Label::new(comment.sender.github_login.clone())
+                                        .size(LabelSize::Small),
+                                )
+                                .child(
+                                    Label::new(time_format::format_localized_timestamp(
+                                        comment.timestamp,
+                                        now,
+                                        local_timezone,
+                                        time_format::TimestampFormat::EnhancedAbsolute,
+                                    ))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                                ),
+                        )
+                        .child(div().text_ui_sm(cx).child(comment.body.clone()))
+                }))
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            div()
+                                .flex_1()
+                                .key_context("ReviewThreadEditor")
+                                .on_action({
+                                    let this = this.clone();
+                                    move |_: &menu::Confirm, window, cx| {
+                                        this.update(cx, |this, cx| {
+                                            this.reply(thread_id, window, cx)
+                                        })
+                                        .ok();
+                                    }
+                                })
+                                .child(reply_editor.clone()),
+                        )
+                        .child(
+                            Button::new(("reply-to-review-thread", thread_id as usize), "Reply")
+                                .label_size(LabelSize::Small)
+                                .tooltip(|window, cx| {
+                                    Tooltip::for_action("Reply", &menu::Confirm, window, cx)
+                                })
+                                .on_click(move |_, window, cx| {
+                                    this.update(cx, |this, cx| this.reply(thread_id, window, cx))
+                                        .ok();
+                                }),
+                        ),
+                )
+        })
+        .into_any_element()
+}
+
+fn render_draft(
+    draft_editor: &Entity<MessageEditor>,
+    this: WeakEntity<EditorReviewThreads>,
+    cx: &mut BlockContext,
+) -> AnyElement {
+    v_flex()
+        .id(cx.block_id)
+        .block_mouse_except_scroll()
+        .ml(cx.margins.gutter.full_width())
+        .mr_4()
+        .my_1()
+        .p_2()
+        .gap_1()
+        .max_w(rems(48.))
+        .rounded_md()
+        .border_1()
+        .border_color(cx.theme().colors().border_focused)
+        .bg(cx.theme().colors().elevated_surface_background)
+        .key_context("ReviewThreadEditor")
+        .on_action({
+            let this = this.clone();
+            move |_: &menu::Confirm, window, cx| {
+                this.update(cx, |this, cx| this.submit_draft(window, cx))
+                    .ok();
+            }
+        })
+        .on_action({
+            let this = this.clone();
+            move |_: &menu::Cancel, _, cx| {
+                this.update(cx, |this, cx| this.cancel_draft(cx)).ok();
+            }
+        })
+        .child(draft_editor.clone())
+        .child(
+            h_flex()
+                .justify_end()
+                .gap_1()
+                .child(
+                    Button::new("cancel-review-thread", "Cancel")
+                        .label_size(LabelSize::Small)
+                        .on_click({
+                            let this = this.clone();
+                            move |_, _, cx| {
+                                this.update(cx, |this, cx| this.cancel_draft(cx)).ok();
+                            }
+                        }),
+                )
+                .child(
+                    Button::new("submit-review-thread", "Comment")
+                        .label_size(LabelSize::Small)
+                        .tooltip(|window, cx| {
+                            Tooltip::for_action("Comment", &menu::Confirm, window, cx)
+                        })
+                        .on_click(move |_, window, cx| {
+                            this.update(cx, |this, cx| this.submit_draft(window, cx))
+                                .ok();
+                        }),
+                ),
+        )
+        .into_any_element()
+}
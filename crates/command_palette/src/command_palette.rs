@@ -9,7 +9,7 @@ use std::{
 
 use client::parse_zed_link;
 use command_palette_hooks::{
-    CommandInterceptResult, CommandPaletteFilter, CommandPaletteInterceptor,
+    CommandInterceptResult, CommandPaletteCommands, CommandPaletteFilter, CommandPaletteInterceptor,
 };
 
 use fuzzy::{StringMatch, StringMatchCandidate};
@@ -94,7 +94,7 @@ impl CommandPalette {
     ) -> Self {
         let filter = CommandPaletteFilter::try_global(cx);
 
-        let commands = window
+        let mut commands = window
             .available_actions(cx)
             .into_iter()
             .filter_map(|action| {
@@ -107,7 +107,24 @@ impl CommandPalette {
                     action,
                 })
             })
-            .collect();
+            .collect::<Vec<_>>();
+
+        let runtime_commands = CommandPaletteCommands::try_global(cx)
+            .map(|registered| {
+                registered
+                    .commands()
+                    .map(|(name, action)| Command {
+                        name: name.to_string(),
+                        action: action.boxed_clone(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        commands.extend(
+            runtime_commands
+                .into_iter()
+                .filter(|command| window.is_action_available(&*command.action, cx)),
+        );
 
         let delegate =
             CommandPaletteDelegate::new(cx.entity().downgrade(), commands, previous_focus_handle);
@@ -4,14 +4,15 @@
 
 use std::any::TypeId;
 
-use collections::HashSet;
+use collections::{BTreeMap, HashSet};
 use derive_more::{Deref, DerefMut};
-use gpui::{Action, App, BorrowAppContext, Global};
+use gpui::{Action, App, BorrowAppContext, Global, SharedString};
 
 /// Initializes the command palette hooks.
 pub fn init(cx: &mut App) {
     cx.set_global(GlobalCommandPaletteFilter::default());
     cx.set_global(GlobalCommandPaletteInterceptor::default());
+    cx.set_global(GlobalCommandPaletteCommands::default());
 }
 
 /// A filter for the command palette.
@@ -152,3 +153,56 @@ impl CommandPaletteInterceptor {
         self.0 = Some(handler);
     }
 }
+
+/// Commands that are added to the command palette at runtime, such as those
+/// provided by extensions.
+///
+/// Unlike the rest of the command palette, these commands are not derived from
+/// the set of registered action types, which allows a single parameterized action
+/// to appear in the command palette several times.
+#[derive(Default)]
+pub struct CommandPaletteCommands {
+    commands: BTreeMap<SharedString, Box<dyn Action>>,
+}
+
+#[derive(Deref, DerefMut, Default)]
+struct GlobalCommandPaletteCommands(CommandPaletteCommands);
+
+impl Global for GlobalCommandPaletteCommands {}
+
+impl CommandPaletteCommands {
+    /// Returns the global [`CommandPaletteCommands`], if one is set.
+    pub fn try_global(cx: &App) -> Option<&CommandPaletteCommands> {
+        cx.try_global::<GlobalCommandPaletteCommands>()
+            .map(|commands| &commands.0)
+    }
+
+    /// Updates the global [`CommandPaletteCommands`] using the given closure.
+    pub fn update_global<F>(cx: &mut App, update: F)
+    where
+        F: FnOnce(&mut Self, &mut App),
+    {
+        if cx.has_global::<GlobalCommandPaletteCommands>() {
+            cx.update_global(|this: &mut GlobalCommandPaletteCommands, cx| update(&mut this.0, cx))
+        }
+    }
+
+    /// Adds a command with the given name that dispatches the given action.
+    ///
+    /// This will replace any existing command with the same name.
+    pub fn register(&mut self, name: impl Into<SharedString>, action: Box<dyn Action>) {
+        self.commands.insert(name.into(), action);
+    }
+
+    /// Removes the command with the given name.
+    pub fn unregister(&mut self, name: &str) {
+        self.commands.remove(name);
+    }
+
+    /// Returns the registered commands, ordered by name.
+    pub fn commands(&self) -> impl Iterator<Item = (&SharedString, &dyn Action)> {
+        self.commands
+            .iter()
+            .map(|(name, action)| (name, action.as_ref()))
+    }
+}
@@ -6,7 +6,7 @@ use std::sync::{Arc, LazyLock};
 
 use anyhow::Result;
 use futures::FutureExt as _;
-use gpui::{App, AsyncApp};
+use gpui::{App, AsyncApp, Global};
 use release_channel::ReleaseChannel;
 
 /// An environment variable whose presence indicates that the system keychain
@@ -51,15 +51,30 @@ pub trait CredentialsProvider: Send + Sync {
     ) -> Pin<Box<dyn Future<Output = Result<()>> + 'a>>;
 }
 
+/// A [`CredentialsProvider`] that replaces the one chosen for the release channel.
+struct GlobalCredentialsProvider(Arc<dyn CredentialsProvider>);
+
+impl Global for GlobalCredentialsProvider {}
+
 impl dyn CredentialsProvider {
     /// Returns the global [`CredentialsProvider`].
     pub fn global(cx: &App) -> Arc<Self> {
+        if let Some(provider) = cx.try_global::<GlobalCredentialsProvider>() {
+            return provider.0.clone();
+        }
+
         // The `CredentialsProvider` trait has `Send + Sync` bounds on it, so it
         // seems like this is a false positive from Clippy.
         #[allow(clippy::arc_with_non_send_sync)]
         Self::new(cx)
     }
 
+    /// Replaces the global [`CredentialsProvider`], such as with a
+    /// [`FakeCredentialsProvider`] in tests.
+    pub fn set_global(provider: Arc<Self>, cx: &mut App) {
+        cx.set_global(GlobalCredentialsProvider(provider));
+    }
+
     fn new(cx: &App) -> Arc<Self> {
         let use_development_provider = match ReleaseChannel::try_global(cx) {
             Some(ReleaseChannel::Dev) => {
@@ -197,3 +212,58 @@ impl CredentialsProvider for DevelopmentCredentialsProvider {
         .boxed_local()
     }
 }
+
+/// A credentials provider for tests, which stores credentials in memory.
+#[cfg(any(test, feature = "test-support"))]
+#[derive(Default)]
+pub struct FakeCredentialsProvider {
+    credentials: std::sync::Mutex<HashMap<String, (String, Vec<u8>)>>,
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl FakeCredentialsProvider {
+    /// Returns the username and password stored for the given URL.
+    pub fn credentials(&self, url: &str) -> Option<(String, Vec<u8>)> {
+        self.credentials.lock().unwrap().get(url).cloned()
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl CredentialsProvider for FakeCredentialsProvider {
+    fn read_credentials<'a>(
+        &'a self,
+        url: &'a str,
+        _cx: &'a AsyncApp,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<(String, Vec<u8>)>>> + 'a>> {
+        async move { Ok(self.credentials(url)) }.boxed_local()
+    }
+
+    fn write_credentials<'a>(
+        &'a self,
+        url: &'a str,
+        username: &'a str,
+        password: &'a [u8],
+        _cx: &'a AsyncApp,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + 'a>> {
+        async move {
+            self.credentials
+                .lock()
+                .unwrap()
+                .insert(url.to_string(), (username.to_string(), password.to_vec()));
+            Ok(())
+        }
+        .boxed_local()
+    }
+
+    fn delete_credentials<'a>(
+        &'a self,
+        url: &'a str,
+        _cx: &'a AsyncApp,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + 'a>> {
+        async move {
+            self.credentials.lock().unwrap().remove(url);
+            Ok(())
+        }
+        .boxed_local()
+    }
+}
@@ -373,6 +373,14 @@ pub trait DebugAdapter: 'static + Send + Sync {
         }
     }
 
+    /// Whether the given JSON config loads a core dump rather than running or attaching to a process.
+    ///
+    /// Sessions started from such a config are read-only: threads, stacks and variables can be
+    /// inspected, but execution can't be resumed.
+    fn is_core_dump(&self, _config: &serde_json::Value) -> bool {
+        false
+    }
+
     fn dap_schema(&self) -> serde_json::Value;
 
     fn label_for_child_session(&self, _args: &StartDebuggingRequestArguments) -> Option<String> {
@@ -412,12 +420,19 @@ impl DebugAdapter for FakeAdapter {
         let request = match request {
             "launch" => dap_types::StartDebuggingRequestArgumentsRequest::Launch,
             "attach" => dap_types::StartDebuggingRequestArgumentsRequest::Attach,
+            "coredump" => dap_types::StartDebuggingRequestArgumentsRequest::Launch,
             _ => unreachable!("Wrong fake adapter input for request field"),
         };
 
         Ok(request)
     }
 
+    fn is_core_dump(&self, config: &serde_json::Value) -> bool {
+        config
+            .get("request")
+            .is_some_and(|request| request == "coredump")
+    }
+
     fn adapter_language_name(&self) -> Option<LanguageName> {
         None
     }
@@ -1,5 +1,6 @@
 use crate::{
     adapters::DebugAdapterBinary,
+    recording::DapRecording,
     transport::{IoKind, LogKind, TransportDelegate},
 };
 use anyhow::Result;
@@ -7,7 +8,7 @@ use dap_types::{
     messages::{Message, Response},
     requests::Request,
 };
-use futures::channel::oneshot;
+use futures::channel::{mpsc, oneshot};
 use gpui::AsyncApp;
 use std::{
     hash::Hash,
@@ -57,6 +58,50 @@ impl DebugAdapterClient {
         Ok(this)
     }
 
+    /// Starts a client that is answered from a recorded session instead of a
+    /// running debug adapter.
+    pub async fn start_replay(
+        id: SessionId,
+        binary: DebugAdapterBinary,
+        recording: &DapRecording,
+        message_handler: DapMessageHandler,
+        cx: &mut AsyncApp,
+    ) -> Result<Self> {
+        let transport_delegate = TransportDelegate::start_replay(recording, cx)?;
+        let this = Self {
+            id,
+            binary,
+            transport_delegate,
+            sequence_count: AtomicU64::new(1),
+        };
+        this.connect(message_handler, cx).await?;
+
+        Ok(this)
+    }
+
+    /// Starts a client for a debug adapter that runs elsewhere, such as on the host of a
+    /// shared project. Requests are sent to `requests_tx`, and the adapter's responses and
+    /// events are read from `messages_rx`.
+    pub async fn start_remote(
+        id: SessionId,
+        binary: DebugAdapterBinary,
+        requests_tx: mpsc::UnboundedSender<crate::messages::Request>,
+        messages_rx: mpsc::UnboundedReceiver<Message>,
+        message_handler: DapMessageHandler,
+        cx: &mut AsyncApp,
+    ) -> Result<Self> {
+        let transport_delegate = TransportDelegate::start_remote(requests_tx, messages_rx, cx);
+        let this = Self {
+            id,
+            binary,
+            transport_delegate,
+            sequence_count: AtomicU64::new(1),
+        };
+        this.connect(message_handler, cx).await?;
+
+        Ok(this)
+    }
+
     pub fn should_reconnect_for_ssh(&self) -> bool {
         self.transport_delegate.tcp_arguments().is_some()
             && self.binary.command.as_deref() == Some("ssh")
@@ -97,15 +142,42 @@ impl DebugAdapterClient {
     /// Note: This function will block until a response is sent back from the adapter
     pub async fn request<R: Request>(&self, arguments: R::Arguments) -> Result<R::Response> {
         let serialized_arguments = serde_json::to_value(arguments)?;
+        let response = self
+            .request_raw(R::COMMAND, Some(serialized_arguments))
+            .await?;
+        match response.success {
+            true => {
+                if let Some(json) = response.body {
+                    Ok(serde_json::from_value(json)?)
+                // Note: dap types configure themselves to return `None` when an empty object is received,
+                // which then fails here...
+                } else if let Ok(result) =
+                    serde_json::from_value(serde_json::Value::Object(Default::default()))
+                {
+                    Ok(result)
+                } else {
+                    Ok(serde_json::from_value(Default::default())?)
+                }
+            }
+            false => anyhow::bail!("Request failed: {}", response.message.unwrap_or_default()),
+        }
+    }
 
+    /// Sends a request whose command and arguments are only known at runtime, and returns
+    /// the adapter's response as is.
+    pub async fn request_raw(
+        &self,
+        command: &str,
+        arguments: Option<serde_json::Value>,
+    ) -> Result<Response> {
         let (callback_tx, callback_rx) = oneshot::channel::<Result<Response>>();
 
         let sequence_id = self.next_sequence_id();
 
         let request = crate::messages::Request {
             seq: sequence_id,
-            command: R::COMMAND.to_string(),
-            arguments: Some(serialized_arguments),
+            command: command.to_string(),
+            arguments,
         };
         self.transport_delegate
             .add_pending_request(sequence_id, callback_tx);
@@ -113,14 +185,12 @@ impl DebugAdapterClient {
         log::debug!(
             "Client {} send `{}` request with sequence_id: {}",
             self.id.0,
-            R::COMMAND,
+            command,
             sequence_id
         );
 
         self.send_message(Message::Request(request)).await?;
 
-        let command = R::COMMAND.to_string();
-
         let response = callback_rx.await??;
         log::debug!(
             "Client {} received response for: `{}` sequence_id: {}",
@@ -128,22 +198,7 @@ impl DebugAdapterClient {
             command,
             sequence_id
         );
-        match response.success {
-            true => {
-                if let Some(json) = response.body {
-                    Ok(serde_json::from_value(json)?)
-                // Note: dap types configure themselves to return `None` when an empty object is received,
-                // which then fails here...
-                } else if let Ok(result) =
-                    serde_json::from_value(serde_json::Value::Object(Default::default()))
-                {
-                    Ok(result)
-                } else {
-                    Ok(serde_json::from_value(Default::default())?)
-                }
-            }
-            false => anyhow::bail!("Request failed: {}", response.message.unwrap_or_default()),
-        }
+        Ok(response)
     }
 
     pub async fn send_message(&self, message: Message) -> Result<()> {
@@ -193,6 +248,15 @@ impl DebugAdapterClient {
             .on_request::<R, F>(handler);
     }
 
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn replay(&self, recording: &DapRecording) -> Result<()> {
+        self.transport_delegate
+            .transport
+            .lock()
+            .as_fake()
+            .replay(recording)
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub async fn fake_reverse_request<R: dap_types::requests::Request>(&self, args: R::Arguments) {
         self.send_message(Message::Request(dap_types::messages::Request {
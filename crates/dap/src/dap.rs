@@ -3,6 +3,7 @@ pub mod client;
 pub mod debugger_settings;
 pub mod inline_value;
 pub mod proto_conversions;
+pub mod recording;
 mod registry;
 pub mod transport;
 
@@ -3,6 +3,7 @@ use gpui::{App, Global};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsSources};
+use std::path::PathBuf;
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -12,7 +13,7 @@ pub enum DebugPanelDockPosition {
     Right,
 }
 
-#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy)]
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
 #[serde(default)]
 pub struct DebuggerSettings {
     /// Determines the stepping granularity.
@@ -23,6 +24,11 @@ pub struct DebuggerSettings {
     ///
     /// Default: true
     pub save_breakpoints: bool,
+    /// Whether to keep a separate set of breakpoints for each git branch,
+    /// switching between them when the checked out branch changes.
+    ///
+    /// Default: false
+    pub breakpoints_per_branch: bool,
     /// Whether to show the debug button in the status bar.
     ///
     /// Default: true
@@ -39,6 +45,16 @@ pub struct DebuggerSettings {
     ///
     /// Default: true
     pub format_dap_log_messages: bool,
+    /// Whether to keep a full recording of the messages exchanged with debug adapters,
+    /// which can be saved from the debug adapter logs and replayed in tests.
+    ///
+    /// Default: false
+    pub record_dap_sessions: bool,
+    /// Directory to look for core dumps in when starting a post-mortem debug session.
+    /// When unset, the root of the worktree is searched for files named like cores.
+    ///
+    /// Default: null
+    pub core_dump_directory: Option<PathBuf>,
     /// The dock position of the debug panel
     ///
     /// Default: Bottom
@@ -50,10 +66,13 @@ impl Default for DebuggerSettings {
         Self {
             button: true,
             save_breakpoints: true,
+            breakpoints_per_branch: false,
             stepping_granularity: SteppingGranularity::Line,
             timeout: 2000,
             log_dap_communications: true,
             format_dap_log_messages: true,
+            record_dap_sessions: false,
+            core_dump_directory: None,
             dock: DebugPanelDockPosition::Bottom,
         }
     }
@@ -0,0 +1,225 @@
+use anyhow::{Context as _, Result};
+use dap_types::messages::{Message, Request, Response};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    client::DebugAdapterClient,
+    transport::{IoKind, LogKind},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageDirection {
+    /// Sent by Zed to the debug adapter.
+    ToAdapter,
+    /// Sent by the debug adapter to Zed.
+    FromAdapter,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RecordedMessage {
+    /// Milliseconds elapsed since the recording started.
+    pub timestamp_ms: u64,
+    pub direction: MessageDirection,
+    pub message: Message,
+}
+
+/// The full DAP traffic of a debug session, in the order it went over the wire.
+///
+/// Recordings are saved as JSON and can be replayed through the fake transport,
+/// which turns a real adapter session into a test that doesn't need the adapter.
+#[derive(Default, Serialize, Deserialize)]
+pub struct DapRecording {
+    pub adapter: String,
+    /// When the recording started, in milliseconds since the Unix epoch.
+    pub started_at: u64,
+    pub messages: Vec<RecordedMessage>,
+}
+
+impl DapRecording {
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).context("parsing DAP recording")
+    }
+}
+
+/// Records the messages exchanged with a debug adapter client.
+///
+/// Messages are captured through the client's RPC log handlers, so nothing is
+/// recorded while `debugger.log_dap_communications` is disabled.
+#[derive(Clone)]
+pub struct DapRecorder {
+    started_at: Instant,
+    recording: Arc<Mutex<DapRecording>>,
+}
+
+impl DapRecorder {
+    pub fn new(adapter: impl Into<String>) -> Self {
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as u64);
+        Self {
+            started_at: Instant::now(),
+            recording: Arc::new(Mutex::new(DapRecording {
+                adapter: adapter.into(),
+                started_at,
+                messages: Vec::new(),
+            })),
+        }
+    }
+
+    pub fn attach(&self, client: &DebugAdapterClient) {
+        let this = self.clone();
+        client.add_log_handler(
+            move |kind, _, message| this.record(kind, message),
+            LogKind::Rpc,
+        );
+    }
+
+    fn record(&self, kind: IoKind, message: &str) {
+        let direction = match kind {
+            IoKind::StdIn => MessageDirection::ToAdapter,
+            IoKind::StdOut | IoKind::StdErr => MessageDirection::FromAdapter,
+        };
+        let message = match serde_json::from_str::<Message>(message) {
+            Ok(message) => message,
+            Err(error) => {
+                log::error!("Failed to record DAP message: {error}");
+                return;
+            }
+        };
+        self.recording.lock().messages.push(RecordedMessage {
+            timestamp_ms: self.started_at.elapsed().as_millis() as u64,
+            direction,
+            message,
+        });
+    }
+
+    /// A file name for the recording, unique to the adapter and the time it started.
+    pub fn file_name(&self) -> String {
+        let recording = self.recording.lock();
+        format!("{}-{}.json", recording.adapter, recording.started_at)
+    }
+
+    pub fn message_count(&self) -> usize {
+        self.recording.lock().messages.len()
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(&*self.recording.lock()).context("serializing DAP recording")
+    }
+}
+
+/// The responses and adapter-initiated messages of a recording, keyed by the
+/// command of the request that preceded them.
+pub(crate) struct Replay {
+    entries: collections::HashMap<String, std::collections::VecDeque<ReplayEntry>>,
+    last_responses: collections::HashMap<String, serde_json::Value>,
+    pending_messages: Vec<String>,
+}
+
+struct ReplayEntry {
+    response: serde_json::Value,
+    /// Events and reverse requests the adapter sent after this response.
+    follow_ups: Vec<String>,
+}
+
+impl Replay {
+    pub(crate) fn new(recording: &DapRecording) -> Result<Self> {
+        Self::build(recording, true)
+    }
+
+    /// Like [`Self::new`], but drops the reverse requests the adapter sent, so
+    /// that reviewing a recording doesn't launch terminals or child sessions.
+    pub(crate) fn for_review(recording: &DapRecording) -> Result<Self> {
+        Self::build(recording, false)
+    }
+
+    fn build(recording: &DapRecording, include_reverse_requests: bool) -> Result<Self> {
+        let mut entries = collections::HashMap::<String, std::collections::VecDeque<_>>::default();
+        let mut pending_messages = Vec::new();
+        let mut last_entry = None;
+
+        for recorded in &recording.messages {
+            if recorded.direction == MessageDirection::ToAdapter {
+                continue;
+            }
+            match &recorded.message {
+                Message::Response(response) => {
+                    let queue = entries.entry(response.command.clone()).or_default();
+                    queue.push_back(ReplayEntry {
+                        response: serde_json::to_value(&recorded.message)?,
+                        follow_ups: Vec::new(),
+                    });
+                    last_entry = Some((response.command.clone(), queue.len() - 1));
+                }
+                Message::Request(_) if !include_reverse_requests => {}
+                Message::Event(_) | Message::Request(_) => {
+                    let message = serde_json::to_string(&recorded.message)?;
+                    match &last_entry {
+                        Some((command, ix)) => entries
+                            .get_mut(command)
+                            .and_then(|queue| queue.get_mut(*ix))
+                            .context("missing replay entry")?
+                            .follow_ups
+                            .push(message),
+                        None => pending_messages.push(message),
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            entries,
+            last_responses: Default::default(),
+            pending_messages,
+        })
+    }
+
+    /// Returns the serialized messages to send back for a request, starting with
+    /// its response.
+    ///
+    /// Requests are answered in recorded order. Once the recorded responses for a
+    /// command run out, the last one is repeated without its follow-up messages.
+    pub(crate) fn respond(&mut self, command: &str, seq: u64) -> Option<Vec<String>> {
+        let (mut response, follow_ups) = match self
+            .entries
+            .get_mut(command)
+            .and_then(|queue| queue.pop_front())
+        {
+            Some(entry) => {
+                self.last_responses
+                    .insert(command.to_string(), entry.response.clone());
+                (entry.response, entry.follow_ups)
+            }
+            None => (self.last_responses.get(command)?.clone(), Vec::new()),
+        };
+
+        response["seq"] = (seq + 1).into();
+        response["request_seq"] = seq.into();
+
+        let mut messages = vec![serde_json::to_string(&response).ok()?];
+        messages.append(&mut self.pending_messages);
+        messages.extend(follow_ups);
+        Some(messages)
+    }
+}
+
+/// The response to a request that has no recorded response to replay.
+pub(crate) fn unrecorded_response(request: &Request) -> Response {
+    Response {
+        seq: request.seq + 1,
+        request_seq: request.seq,
+        success: false,
+        command: request.command.clone(),
+        body: None,
+        message: Some(format!(
+            "`{}` request is not in the recording",
+            request.command
+        )),
+    }
+}
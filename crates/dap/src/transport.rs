@@ -1,11 +1,14 @@
 use anyhow::{Context as _, Result, anyhow, bail};
-#[cfg(any(test, feature = "test-support"))]
 use async_pipe::{PipeReader, PipeWriter};
 use dap_types::{
     ErrorResponse,
     messages::{Message, Response},
 };
-use futures::{AsyncRead, AsyncReadExt as _, AsyncWrite, FutureExt as _, channel::oneshot, select};
+use futures::{
+    AsyncRead, AsyncReadExt as _, AsyncWrite, FutureExt as _, StreamExt as _,
+    channel::{mpsc, oneshot},
+    select,
+};
 use gpui::{AppContext as _, AsyncApp, BackgroundExecutor, Task};
 use parking_lot::Mutex;
 use proto::ErrorExt;
@@ -24,12 +27,13 @@ use std::{
     time::Duration,
 };
 use task::TcpArgumentsTemplate;
-use util::ConnectionResult;
+use util::{ConnectionResult, ResultExt as _};
 
 use crate::{
     adapters::{DebugAdapterBinary, TcpArguments},
     client::DapMessageHandler,
     debugger_settings::DebuggerSettings,
+    recording::{DapRecording, Replay, unrecorded_response},
 };
 
 pub(crate) type IoMessage = str;
@@ -109,13 +113,31 @@ impl TransportDelegate {
     pub(crate) async fn start(binary: &DebugAdapterBinary, cx: &mut AsyncApp) -> Result<Self> {
         let log_handlers: LogHandlers = Default::default();
         let transport = start(binary, log_handlers.clone(), cx).await?;
-        Ok(Self {
+        Ok(Self::new(transport, log_handlers))
+    }
+
+    pub(crate) fn start_replay(recording: &DapRecording, cx: &mut AsyncApp) -> Result<Self> {
+        let transport = ReplayTransport::start(recording, cx)?;
+        Ok(Self::new(Box::new(transport), Default::default()))
+    }
+
+    pub(crate) fn start_remote(
+        requests_tx: mpsc::UnboundedSender<crate::messages::Request>,
+        messages_rx: mpsc::UnboundedReceiver<Message>,
+        cx: &mut AsyncApp,
+    ) -> Self {
+        let transport = RemoteTransport::start(requests_tx, messages_rx, cx);
+        Self::new(Box::new(transport), Default::default())
+    }
+
+    fn new(transport: Box<dyn Transport>, log_handlers: LogHandlers) -> Self {
+        Self {
             transport: Mutex::new(transport),
             log_handlers,
             server_tx: Default::default(),
             pending_requests: Default::default(),
             tasks: Default::default(),
-        })
+        }
     }
 
     pub async fn connect(
@@ -702,6 +724,192 @@ impl Drop for StdioTransport {
     }
 }
 
+/// Answers requests from a recorded session instead of a running debug adapter,
+/// so that a session can be reviewed after it has ended.
+pub struct ReplayTransport {
+    stdin_writer: Option<PipeWriter>,
+    stdout_reader: Option<PipeReader>,
+    message_handler: Option<Task<()>>,
+}
+
+impl ReplayTransport {
+    fn start(recording: &DapRecording, cx: &mut AsyncApp) -> Result<Self> {
+        let mut replay = Replay::for_review(recording)?;
+        let (stdin_writer, stdin_reader) = async_pipe::pipe();
+        let (mut stdout_writer, stdout_reader) = async_pipe::pipe();
+
+        let message_handler = cx.background_spawn(async move {
+            let mut reader = BufReader::new(stdin_reader);
+            let mut buffer = String::new();
+            loop {
+                let message =
+                    match TransportDelegate::receive_server_message(&mut reader, &mut buffer, None)
+                        .await
+                    {
+                        ConnectionResult::Result(Ok(message)) => message,
+                        ConnectionResult::Result(Err(error)) => {
+                            log::error!("Failed to read message during replay: {error}");
+                            break;
+                        }
+                        ConnectionResult::Timeout | ConnectionResult::ConnectionReset => break,
+                    };
+                let Message::Request(request) = message else {
+                    continue;
+                };
+
+                let messages = match replay.respond(&request.command, request.seq) {
+                    Some(messages) => messages,
+                    None => {
+                        let response = Message::Response(unrecorded_response(&request));
+                        let Some(response) = serde_json::to_string(&response).log_err() else {
+                            continue;
+                        };
+                        vec![response]
+                    }
+                };
+                for message in messages {
+                    let message = TransportDelegate::build_rpc_message(message);
+                    if stdout_writer.write_all(message.as_bytes()).await.is_err() {
+                        return;
+                    }
+                }
+                if stdout_writer.flush().await.is_err() {
+                    return;
+                }
+            }
+        });
+
+        Ok(Self {
+            stdin_writer: Some(stdin_writer),
+            stdout_reader: Some(stdout_reader),
+            message_handler: Some(message_handler),
+        })
+    }
+}
+
+impl Transport for ReplayTransport {
+    fn has_adapter_logs(&self) -> bool {
+        false
+    }
+
+    fn tcp_arguments(&self) -> Option<TcpArguments> {
+        None
+    }
+
+    fn connect(
+        &mut self,
+    ) -> Task<
+        Result<(
+            Box<dyn AsyncWrite + Unpin + Send + 'static>,
+            Box<dyn AsyncRead + Unpin + Send + 'static>,
+        )>,
+    > {
+        let result = util::maybe!({
+            Ok((
+                Box::new(self.stdin_writer.take().context("Cannot reconnect")?) as _,
+                Box::new(self.stdout_reader.take().context("Cannot reconnect")?) as _,
+            ))
+        });
+        Task::ready(result)
+    }
+
+    fn kill(&mut self) {
+        self.message_handler.take();
+    }
+}
+
+/// Forwards requests to a debug adapter that runs elsewhere, such as on the host of a
+/// shared project, and passes on the responses and events it sends back.
+pub struct RemoteTransport {
+    stdin_writer: Option<PipeWriter>,
+    stdout_reader: Option<PipeReader>,
+    tasks: Vec<Task<()>>,
+}
+
+impl RemoteTransport {
+    fn start(
+        requests_tx: mpsc::UnboundedSender<crate::messages::Request>,
+        mut messages_rx: mpsc::UnboundedReceiver<Message>,
+        cx: &mut AsyncApp,
+    ) -> Self {
+        let (stdin_writer, stdin_reader) = async_pipe::pipe();
+        let (mut stdout_writer, stdout_reader) = async_pipe::pipe();
+
+        let forward_requests = cx.background_spawn(async move {
+            let mut reader = BufReader::new(stdin_reader);
+            let mut buffer = String::new();
+            loop {
+                let message =
+                    match TransportDelegate::receive_server_message(&mut reader, &mut buffer, None)
+                        .await
+                    {
+                        ConnectionResult::Result(Ok(message)) => message,
+                        ConnectionResult::Result(Err(error)) => {
+                            log::error!("Failed to read message for remote debug adapter: {error}");
+                            break;
+                        }
+                        ConnectionResult::Timeout | ConnectionResult::ConnectionReset => break,
+                    };
+                if let Message::Request(request) = message {
+                    if requests_tx.unbounded_send(request).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        let receive_messages = cx.background_spawn(async move {
+            while let Some(message) = messages_rx.next().await {
+                let Some(message) = serde_json::to_string(&message).log_err() else {
+                    continue;
+                };
+                let message = TransportDelegate::build_rpc_message(message);
+                if stdout_writer.write_all(message.as_bytes()).await.is_err()
+                    || stdout_writer.flush().await.is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            stdin_writer: Some(stdin_writer),
+            stdout_reader: Some(stdout_reader),
+            tasks: vec![forward_requests, receive_messages],
+        }
+    }
+}
+
+impl Transport for RemoteTransport {
+    fn has_adapter_logs(&self) -> bool {
+        false
+    }
+
+    fn tcp_arguments(&self) -> Option<TcpArguments> {
+        None
+    }
+
+    fn connect(
+        &mut self,
+    ) -> Task<
+        Result<(
+            Box<dyn AsyncWrite + Unpin + Send + 'static>,
+            Box<dyn AsyncRead + Unpin + Send + 'static>,
+        )>,
+    > {
+        let result = util::maybe!({
+            Ok((
+                Box::new(self.stdin_writer.take().context("Cannot reconnect")?) as _,
+                Box::new(self.stdout_reader.take().context("Cannot reconnect")?) as _,
+            ))
+        });
+        Task::ready(result)
+    }
+
+    fn kill(&mut self) {
+        self.tasks.clear();
+    }
+}
+
 #[cfg(any(test, feature = "test-support"))]
 type RequestHandler =
     Box<dyn Send + FnMut(u64, serde_json::Value) -> dap_types::messages::Response>;
@@ -716,6 +924,9 @@ pub struct FakeTransport {
     // for reverse request responses
     response_handlers: Arc<Mutex<HashMap<&'static str, ResponseHandler>>>,
 
+    // recorded session to answer requests from, before falling back to the request handlers
+    replay: Arc<Mutex<Option<Replay>>>,
+
     stdin_writer: Option<PipeWriter>,
     stdout_reader: Option<PipeReader>,
     message_handler: Option<Task<Result<()>>>,
@@ -763,6 +974,14 @@ impl FakeTransport {
             .insert(R::COMMAND, Box::new(handler));
     }
 
+    /// Answers requests with the responses, events and reverse requests of a
+    /// recorded session. Requests that weren't recorded fall back to the
+    /// registered request handlers, and fail if there's none.
+    pub fn replay(&self, recording: &DapRecording) -> Result<()> {
+        *self.replay.lock() = Some(Replay::new(recording)?);
+        Ok(())
+    }
+
     async fn start(cx: &mut AsyncApp) -> Result<Self> {
         use dap_types::requests::{Request, RunInTerminal, StartDebugging};
         use serde_json::json;
@@ -773,6 +992,7 @@ impl FakeTransport {
         let mut this = Self {
             request_handlers: Arc::new(Mutex::new(HashMap::default())),
             response_handlers: Arc::new(Mutex::new(HashMap::default())),
+            replay: Arc::new(Mutex::new(None)),
             stdin_writer: Some(stdin_writer),
             stdout_reader: Some(stdout_reader),
             message_handler: None,
@@ -780,6 +1000,7 @@ impl FakeTransport {
 
         let request_handlers = this.request_handlers.clone();
         let response_handlers = this.response_handlers.clone();
+        let replay = this.replay.clone();
         let stdout_writer = Arc::new(smol::lock::Mutex::new(stdout_writer));
 
         this.message_handler = Some(cx.background_spawn(async move {
@@ -818,25 +1039,41 @@ impl FakeTransport {
                                         .unwrap();
                                     writer.flush().await.unwrap();
                                 } else {
-                                    let response = if let Some(handle) =
-                                        request_handlers.lock().get_mut(request.command.as_str())
-                                    {
-                                        handle(request.seq, request.arguments.unwrap_or(json!({})))
+                                    let replayed = replay.lock().as_mut().map(|replay| {
+                                        replay.respond(&request.command, request.seq)
+                                    });
+                                    let messages = if let Some(Some(messages)) = replayed {
+                                        messages
                                     } else {
-                                        panic!("No request handler for {}", request.command);
+                                        let response = if let Some(handle) = request_handlers
+                                            .lock()
+                                            .get_mut(request.command.as_str())
+                                        {
+                                            handle(
+                                                request.seq,
+                                                request.arguments.unwrap_or(json!({})),
+                                            )
+                                        } else if replayed.is_some() {
+                                            unrecorded_response(&request)
+                                        } else {
+                                            panic!("No request handler for {}", request.command);
+                                        };
+                                        vec![
+                                            serde_json::to_string(&Message::Response(response))
+                                                .unwrap(),
+                                        ]
                                     };
-                                    let message =
-                                        serde_json::to_string(&Message::Response(response))
-                                            .unwrap();
 
                                     let mut writer = stdout_writer.lock().await;
-                                    writer
-                                        .write_all(
-                                            TransportDelegate::build_rpc_message(message)
-                                                .as_bytes(),
-                                        )
-                                        .await
-                                        .unwrap();
+                                    for message in messages {
+                                        writer
+                                            .write_all(
+                                                TransportDelegate::build_rpc_message(message)
+                                                    .as_bytes(),
+                                            )
+                                            .await
+                                            .unwrap();
+                                    }
                                     writer.flush().await.unwrap();
                                 }
                             }
@@ -858,6 +1095,8 @@ impl FakeTransport {
                                     response_handlers.lock().get(response.command.as_str())
                                 {
                                     handle(response);
+                                } else if replay.lock().is_some() {
+                                    // Responses to replayed reverse requests were recorded too.
                                 } else {
                                     log::error!("No response handler for {}", response.command);
                                 }
@@ -92,7 +92,7 @@ impl DebugAdapter for CodeLldbDebugAdapter {
     async fn config_from_zed_format(&self, zed_scenario: ZedDebugConfig) -> Result<DebugScenario> {
         let mut configuration = json!({
             "request": match zed_scenario.request {
-                DebugRequest::Launch(_) => "launch",
+                DebugRequest::Launch(_) | DebugRequest::CoreDump(_) => "launch",
                 DebugRequest::Attach(_) => "attach",
             },
         });
@@ -122,6 +122,19 @@ impl DebugAdapter for CodeLldbDebugAdapter {
                     map.insert("cwd".into(), cwd.to_string_lossy().into_owned().into());
                 }
             }
+            DebugRequest::CoreDump(core_dump) => {
+                map.insert("program".into(), core_dump.program.clone().into());
+                // Loading the core creates the target; no process is launched.
+                map.insert(
+                    "targetCreateCommands".into(),
+                    json!([format!(
+                        "target create --core {} {}",
+                        lldb_quote(&core_dump.core_file.to_string_lossy()),
+                        lldb_quote(&core_dump.program)
+                    )]),
+                );
+                map.insert("processCreateCommands".into(), json!([]));
+            }
         }
 
         Ok(DebugScenario {
@@ -133,6 +146,19 @@ impl DebugAdapter for CodeLldbDebugAdapter {
         })
     }
 
+    fn is_core_dump(&self, config: &serde_json::Value) -> bool {
+        config
+            .get("targetCreateCommands")
+            .and_then(|commands| commands.as_array())
+            .is_some_and(|commands| {
+                commands.iter().any(|command| {
+                    command
+                        .as_str()
+                        .is_some_and(|command| command.contains("--core"))
+                })
+            })
+    }
+
     fn dap_schema(&self) -> serde_json::Value {
         json!({
             "properties": {
@@ -377,3 +403,54 @@ impl DebugAdapter for CodeLldbDebugAdapter {
         })
     }
 }
+
+/// Quotes an argument for LLDB's command interpreter, which only treats `\` and `"` as
+/// special inside double quotes.
+fn lldb_quote(argument: &str) -> String {
+    let mut quoted = String::with_capacity(argument.len() + 2);
+    quoted.push('"');
+    for char in argument.chars() {
+        if matches!(char, '\\' | '"') {
+            quoted.push('\\');
+        }
+        quoted.push(char);
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use task::CoreDumpRequest;
+
+    #[test]
+    fn test_lldb_quote() {
+        assert_eq!(lldb_quote("/tmp/core"), r#""/tmp/core""#);
+        assert_eq!(lldb_quote("/tmp/my core"), r#""/tmp/my core""#);
+        assert_eq!(lldb_quote(r#"/tmp/a"b"#), r#""/tmp/a\"b""#);
+        assert_eq!(lldb_quote(r"C:\dumps\app.dmp"), r#""C:\\dumps\\app.dmp""#);
+    }
+
+    #[gpui::test]
+    async fn test_core_dump_target_create_command() {
+        let scenario = CodeLldbDebugAdapter::default()
+            .config_from_zed_format(ZedDebugConfig {
+                label: "core".into(),
+                adapter: CodeLldbDebugAdapter::ADAPTER_NAME.into(),
+                request: DebugRequest::CoreDump(CoreDumpRequest {
+                    program: "/home/me/my app".into(),
+                    core_file: PathBuf::from("/tmp/core \"1\""),
+                }),
+                stop_on_entry: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            scenario.config["targetCreateCommands"],
+            json!([r#"target create --core "/tmp/core \"1\"" "/home/me/my app""#])
+        );
+        assert!(CodeLldbDebugAdapter::default().is_core_dump(&scenario.config));
+    }
+}
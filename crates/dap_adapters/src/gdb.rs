@@ -52,6 +52,16 @@ impl DebugAdapter for GdbDebugAdapter {
                     obj.insert("cwd".into(), cwd.to_string_lossy().into_owned().into());
                 }
             }
+
+            // GDB loads core files when launching, rather than attaching.
+            dap::DebugRequest::CoreDump(core_dump) => {
+                obj.insert("request".into(), "launch".into());
+                obj.insert("program".into(), core_dump.program.clone().into());
+                obj.insert(
+                    "coreFile".into(),
+                    core_dump.core_file.to_string_lossy().into_owned().into(),
+                );
+            }
         }
 
         Ok(DebugScenario {
@@ -111,6 +121,10 @@ impl DebugAdapter for GdbDebugAdapter {
                                     "type": "boolean",
                                     "description": "When true, GDB will set a temporary breakpoint at the program's first instruction, like the 'starti' command.",
                                     "default": false
+                                },
+                                "coreFile": {
+                                    "type": "string",
+                                    "description": "The core dump to load instead of running the program. This corresponds to the GDB 'core-file' command."
                                 }
                             },
                             "required": ["program"]
@@ -154,6 +168,10 @@ impl DebugAdapter for GdbDebugAdapter {
         })
     }
 
+    fn is_core_dump(&self, config: &serde_json::Value) -> bool {
+        config.get("coreFile").is_some()
+    }
+
     async fn get_binary(
         &self,
         delegate: &Arc<dyn DapDelegate>,
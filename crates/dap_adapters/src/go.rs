@@ -361,6 +361,9 @@ impl DebugAdapter for GoDebugAdapter {
                     "processId": attach_config.process_id,
                 })
             }
+            dap::DebugRequest::CoreDump(_) => {
+                bail!("Delve adapter doesn't support loading core dumps")
+            }
             dap::DebugRequest::Launch(launch_config) => {
                 let mode = if launch_config.program != "." {
                     "exec"
@@ -184,6 +184,9 @@ impl DebugAdapter for JsDebugAdapter {
             "request": match zed_scenario.request {
                 DebugRequest::Launch(_) => "launch",
                 DebugRequest::Attach(_) => "attach",
+                DebugRequest::CoreDump(_) => {
+                    anyhow::bail!("JavaScript adapter doesn't support loading core dumps")
+                }
             },
         });
 
@@ -192,6 +195,8 @@ impl DebugAdapter for JsDebugAdapter {
             DebugRequest::Attach(attach) => {
                 map.insert("processId".into(), attach.process_id.into());
             }
+            // Rejected above
+            DebugRequest::CoreDump(_) => {}
             DebugRequest::Launch(launch) => {
                 if launch.program.starts_with("http://") {
                     map.insert("url".into(), launch.program.clone().into());
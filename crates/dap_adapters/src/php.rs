@@ -317,6 +317,9 @@ impl DebugAdapter for PhpDebugAdapter {
             dap::DebugRequest::Attach(_) => {
                 bail!("Php adapter doesn't support attaching")
             }
+            dap::DebugRequest::CoreDump(_) => {
+                bail!("Php adapter doesn't support loading core dumps")
+            }
             dap::DebugRequest::Launch(launch_config) => json!({
                 "program": launch_config.program,
                 "cwd": launch_config.cwd,
@@ -223,6 +223,9 @@ impl DebugAdapter for PythonDebugAdapter {
             "request": match zed_scenario.request {
                 DebugRequest::Launch(_) => "launch",
                 DebugRequest::Attach(_) => "attach",
+                DebugRequest::CoreDump(_) => {
+                    anyhow::bail!("Python adapter doesn't support loading core dumps")
+                }
             },
             "subProcess": true,
             "redirectOutput": true,
@@ -233,6 +236,8 @@ impl DebugAdapter for PythonDebugAdapter {
             DebugRequest::Attach(attach) => {
                 map.insert("processId".into(), attach.process_id.into());
             }
+            // Rejected above
+            DebugRequest::CoreDump(_) => {}
             DebugRequest::Launch(launch) => {
                 map.insert("program".into(), launch.program.clone().into());
                 map.insert("args".into(), launch.args.clone().into());
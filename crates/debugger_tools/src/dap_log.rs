@@ -2,6 +2,7 @@ use dap::{
     adapters::DebugAdapterName,
     client::SessionId,
     debugger_settings::DebuggerSettings,
+    recording::DapRecorder,
     transport::{IoKind, LogKind},
 };
 use editor::{Editor, EditorEvent};
@@ -12,6 +13,7 @@ use futures::{
 use gpui::{
     App, AppContext, Context, Empty, Entity, EventEmitter, FocusHandle, Focusable, IntoElement,
     ParentElement, Render, SharedString, Styled, Subscription, WeakEntity, Window, actions, div,
+    prelude::FluentBuilder,
 };
 use project::{
     Project,
@@ -80,6 +82,7 @@ struct DebugAdapterState {
     adapter_name: DebugAdapterName,
     has_adapter_logs: bool,
     is_terminated: bool,
+    recorder: Option<DapRecorder>,
 }
 
 struct RpcMessages {
@@ -129,6 +132,7 @@ impl DebugAdapterState {
             adapter_name,
             has_adapter_logs,
             is_terminated: false,
+            recorder: None,
         }
     }
 }
@@ -205,6 +209,9 @@ impl LogStore {
                                 };
                                 if let Some(state) = this.get_debug_adapter_state(&id) {
                                     state.is_terminated = true;
+                                    if let Some(recorder) = state.recorder.take() {
+                                        save_recording_to_logs_dir(recorder, cx);
+                                    }
                                 }
 
                                 this.clean_sessions(cx);
@@ -391,6 +398,13 @@ impl LogStore {
             let io_tx = self.rpc_tx.clone();
 
             let client = session.read(cx).adapter_client()?;
+            if DebuggerSettings::get_global(cx).record_dap_sessions {
+                let state = self.get_debug_adapter_state(&id)?;
+                let recorder = DapRecorder::new(state.adapter_name.to_string());
+                recorder.attach(&client);
+                state.recorder = Some(recorder);
+            }
+
             let project = id.project.clone();
             let session_id = id.session_id;
             client.add_log_handler(
@@ -458,6 +472,11 @@ impl LogStore {
             .map(|state| &mut state.rpc_messages.messages)
     }
 
+    fn recorder_for_session(&mut self, id: &LogStoreEntryIdentifier<'_>) -> Option<DapRecorder> {
+        self.get_debug_adapter_state(id)
+            .and_then(|state| state.recorder.clone())
+    }
+
     fn initialization_sequence_for_session(
         &mut self,
         id: &LogStoreEntryIdentifier<'_>,
@@ -602,9 +621,29 @@ impl Render for DapLogToolbarItemView {
                 .into()
             });
 
+        let is_recording =
+            log_view.update(cx, |log_view, cx| log_view.current_recorder(cx).is_some());
+
         h_flex()
             .size_full()
             .child(dap_menu)
+            .when(is_recording, |this| {
+                this.child(
+                    div()
+                        .child(
+                            Button::new("save_recording_button", "Save Recording").on_click(
+                                cx.listener(|this, _, window, cx| {
+                                    if let Some(log_view) = this.log_view.as_ref() {
+                                        log_view.update(cx, |log_view, cx| {
+                                            log_view.save_recording(window, cx);
+                                        })
+                                    }
+                                }),
+                            ),
+                        )
+                        .ml_2(),
+                )
+            })
             .child(
                 div()
                     .child(
@@ -772,6 +811,38 @@ impl DapLogView {
             })
     }
 
+    fn current_recorder(&self, cx: &mut App) -> Option<DapRecorder> {
+        let (session_id, _) = self.current_view?;
+        let id = LogStoreEntryIdentifier {
+            session_id,
+            project: Cow::Owned(self.project.downgrade()),
+        };
+        self.log_store
+            .update(cx, |log_store, _| log_store.recorder_for_session(&id))
+    }
+
+    fn save_recording(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(recorder) = self.current_recorder(cx) else {
+            return;
+        };
+        let directory = self
+            .project
+            .read(cx)
+            .visible_worktrees(cx)
+            .next()
+            .map(|worktree| worktree.read(cx).abs_path().to_path_buf())
+            .unwrap_or_else(|| paths::home_dir().clone());
+        let path = cx.prompt_for_new_path(&directory);
+        cx.spawn_in(window, async move |_, _| {
+            let Some(path) = path.await?? else {
+                return anyhow::Ok(());
+            };
+            smol::fs::write(&path, recorder.to_json()?).await?;
+            Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn show_rpc_trace_for_server(
         &mut self,
         id: &LogStoreEntryIdentifier<'_>,
@@ -885,6 +956,21 @@ impl DapLogView {
     }
 }
 
+fn save_recording_to_logs_dir(recorder: DapRecorder, cx: &mut App) {
+    if recorder.message_count() == 0 {
+        return;
+    }
+    cx.background_spawn(async move {
+        let directory = paths::logs_dir().join("dap_recordings");
+        smol::fs::create_dir_all(&directory).await?;
+        let path = directory.join(recorder.file_name());
+        smol::fs::write(&path, recorder.to_json()?).await?;
+        log::info!("Saved DAP recording to {path:?}");
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
 fn log_contents(lines: impl Iterator<Item = SharedString>) -> String {
     lines.fold(String::new(), |mut acc, el| {
         acc.push_str(&el);
@@ -223,8 +223,8 @@ impl PickerDelegate for AttachModalDelegate {
             DebugRequest::Attach(config) => {
                 config.process_id = Some(candidate.pid);
             }
-            DebugRequest::Launch(_) => {
-                debug_panic!("Debugger attach modal used on launch debug config");
+            DebugRequest::Launch(_) | DebugRequest::CoreDump(_) => {
+                debug_panic!("Debugger attach modal used on non-attach debug config");
                 return;
             }
         }
@@ -11,6 +11,7 @@ use crate::{
 use anyhow::{Context as _, Result, anyhow};
 use dap::adapters::DebugAdapterName;
 use dap::debugger_settings::DebugPanelDockPosition;
+use dap::recording::DapRecording;
 use dap::{
     ContinuedEvent, LoadedSourceEvent, ModuleEvent, OutputEvent, StoppedEvent, ThreadEvent,
     client::SessionId, debugger_settings::DebuggerSettings,
@@ -24,6 +25,7 @@ use gpui::{
 
 use itertools::Itertools as _;
 use language::Buffer;
+use project::debugger::dap_store::DapStoreEvent;
 use project::debugger::session::{Session, SessionStateEvent};
 use project::{Fs, ProjectPath, WorktreeId};
 use project::{Project, debugger::session::ThreadStatus};
@@ -38,6 +40,7 @@ use workspace::SplitDirection;
 use workspace::{
     Pane, Workspace,
     dock::{DockPosition, Panel, PanelEvent},
+    item::ItemHandle,
 };
 use zed_actions::ToggleFocus;
 
@@ -71,7 +74,7 @@ pub struct DebugPanel {
     pub(crate) session_picker_menu_handle: PopoverMenuHandle<ContextMenu>,
     fs: Arc<dyn Fs>,
     is_zoomed: bool,
-    _subscriptions: [Subscription; 1],
+    _subscriptions: [Subscription; 2],
     breakpoint_list: Entity<BreakpointList>,
 }
 
@@ -95,6 +98,18 @@ impl DebugPanel {
                 },
             );
 
+            let dap_store = project.read(cx).dap_store();
+            let dap_store_subscription = cx.subscribe_in(
+                &dap_store,
+                window,
+                |this, _, event: &DapStoreEvent, window, cx| {
+                    if let DapStoreEvent::DebugSessionShared(session_id) = event {
+                        this.register_shared_session(*session_id, window, cx)
+                            .detach_and_log_err(cx);
+                    }
+                },
+            );
+
             Self {
                 size: px(300.),
                 sessions: vec![],
@@ -114,7 +129,7 @@ impl DebugPanel {
                 thread_picker_menu_handle,
                 session_picker_menu_handle,
                 is_zoomed: false,
-                _subscriptions: [focus_subscription],
+                _subscriptions: [focus_subscription, dap_store_subscription],
                 debug_scenario_scheduled_last: true,
             }
         })
@@ -262,6 +277,52 @@ impl DebugPanel {
         .detach_and_log_err(cx);
     }
 
+    /// Opens a saved DAP recording as a read-only session, so that a session can
+    /// be reviewed after it has ended.
+    pub fn open_recording(
+        &mut self,
+        recording: DapRecording,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(worktree) = self.project.read(cx).visible_worktrees(cx).next() else {
+            log::debug!("Could not find a worktree to open the recording in");
+            return;
+        };
+        let dap_store = self.project.read(cx).dap_store();
+        let session = dap_store.update(cx, |dap_store, cx| {
+            dap_store.new_session(
+                format!("{} (recording)", recording.adapter).into(),
+                DebugAdapterName(recording.adapter.clone().into()),
+                TaskContext::default(),
+                None,
+                cx,
+            )
+        });
+        let recording = Arc::new(recording);
+
+        cx.spawn_in(window, async move |this, cx| {
+            Self::register_session(this, session.clone(), true, cx).await?;
+            let task = session.update(cx, |session, cx| {
+                session.boot_replay(recording, worktree, dap_store.downgrade(), cx)
+            })?;
+            if let Err(error) = task.await {
+                session
+                    .update(cx, |session, cx| {
+                        session
+                            .console_output(cx)
+                            .unbounded_send(format!("error: {}", error))
+                            .ok();
+                        session.shutdown(cx)
+                    })?
+                    .await;
+                return Err(error);
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub(crate) fn rerun_last_session(
         &mut self,
         workspace: &mut Workspace,
@@ -329,6 +390,38 @@ impl DebugPanel {
         Ok(debug_session)
     }
 
+    /// Shows a debug session that the host of the project shared with us, without focusing
+    /// it.
+    pub(crate) fn register_shared_session(
+        &mut self,
+        session_id: SessionId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<DebugSession>>> {
+        let Some(session) = self
+            .project
+            .read(cx)
+            .dap_store()
+            .read(cx)
+            .session_by_id(session_id)
+        else {
+            return Task::ready(Err(anyhow!(
+                "debug session {} was not shared",
+                session_id.0
+            )));
+        };
+        cx.spawn_in(window, async move |this, cx| {
+            let debug_session = register_session_inner(&this, session, cx).await?;
+            this.update(cx, |this, cx| {
+                if this.active_session.is_none() {
+                    this.active_session = Some(debug_session.clone());
+                    cx.notify();
+                }
+            })?;
+            Ok(debug_session)
+        })
+    }
+
     pub(crate) fn handle_restart_request(
         &mut self,
         mut curr_session: Entity<Session>,
@@ -348,6 +441,7 @@ impl DebugPanel {
         let label = curr_session.read(cx).label().clone();
         let adapter = curr_session.read(cx).adapter().clone();
         let binary = curr_session.read(cx).binary().cloned().unwrap();
+        let recording = curr_session.read(cx).recording().cloned();
         let task = curr_session.update(cx, |session, cx| session.shutdown(cx));
         let task_context = curr_session.read(cx).task_context().clone();
 
@@ -357,8 +451,11 @@ impl DebugPanel {
             let (session, task) = dap_store_handle.update(cx, |dap_store, cx| {
                 let session = dap_store.new_session(label, adapter, task_context, None, cx);
 
-                let task = session.update(cx, |session, cx| {
-                    session.boot(binary, worktree, dap_store_handle.downgrade(), cx)
+                let task = session.update(cx, |session, cx| match recording {
+                    Some(recording) => {
+                        session.boot_replay(recording, worktree, dap_store_handle.downgrade(), cx)
+                    }
+                    None => session.boot(binary, worktree, dap_store_handle.downgrade(), cx),
                 });
                 (session, task)
             })?;
@@ -621,6 +718,8 @@ impl DebugPanel {
                                     let capabilities = running_state.read(cx).capabilities(cx);
                                     let supports_detach =
                                         running_state.read(cx).session().read(cx).is_attached();
+                                    let is_read_only =
+                                        running_state.read(cx).session().read(cx).is_read_only();
                                     this.map(|this| {
                                         if thread_status == ThreadStatus::Running {
                                             this.child(
@@ -661,7 +760,10 @@ impl DebugPanel {
                                                     &running_state,
                                                     |this, _, _window, cx| this.continue_thread(cx),
                                                 ))
-                                                .disabled(thread_status != ThreadStatus::Stopped)
+                                                .disabled(
+                                                    thread_status != ThreadStatus::Stopped
+                                                        || is_read_only,
+                                                )
                                                 .tooltip({
                                                     let focus_handle = focus_handle.clone();
                                                     move |window, cx| {
@@ -687,7 +789,10 @@ impl DebugPanel {
                                                     this.step_over(cx);
                                                 },
                                             ))
-                                            .disabled(thread_status != ThreadStatus::Stopped)
+                                            .disabled(
+                                                thread_status != ThreadStatus::Stopped
+                                                    || is_read_only,
+                                            )
                                             .tooltip({
                                                 let focus_handle = focus_handle.clone();
                                                 move |window, cx| {
@@ -714,7 +819,9 @@ impl DebugPanel {
                                                 this.step_in(cx);
                                             },
                                         ))
-                                        .disabled(thread_status != ThreadStatus::Stopped)
+                                        .disabled(
+                                            thread_status != ThreadStatus::Stopped || is_read_only,
+                                        )
                                         .tooltip({
                                             let focus_handle = focus_handle.clone();
                                             move |window, cx| {
@@ -738,7 +845,10 @@ impl DebugPanel {
                                                     this.step_out(cx);
                                                 },
                                             ))
-                                            .disabled(thread_status != ThreadStatus::Stopped)
+                                            .disabled(
+                                                thread_status != ThreadStatus::Stopped
+                                                    || is_read_only,
+                                            )
                                             .tooltip({
                                                 let focus_handle = focus_handle.clone();
                                                 move |window, cx| {
@@ -1129,6 +1239,16 @@ async fn register_session_inner(
     .ok();
     let serialized_layout = persistence::get_serialized_layout(adapter_name).await;
     let debug_session = this.update_in(cx, |this, window, cx| {
+        // Shared sessions can be registered both when they're shared and when a leader is
+        // followed into them.
+        let session_id = session.read(cx).session_id();
+        if let Some(debug_session) = this
+            .sessions
+            .iter()
+            .find(|debug_session| debug_session.read(cx).session_id(cx) == session_id)
+        {
+            return debug_session.clone();
+        }
         let parent_session = this
             .sessions
             .iter()
@@ -1265,6 +1385,26 @@ impl Panel for DebugPanel {
         None
     }
 
+    fn active_item_for_followers(&self, _: &App) -> Option<Box<dyn ItemHandle>> {
+        Some(Box::new(self.active_session.clone()?))
+    }
+
+    fn activate_item_for_follower(
+        &mut self,
+        item: &dyn ItemHandle,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // Unlike `activate_session`, this doesn't focus the session or open its selected stack
+        // frame, either of which would stop following the leader.
+        if let Some(session) = item.downcast::<DebugSession>() {
+            if self.sessions.contains(&session) {
+                self.active_session = Some(session);
+                cx.notify();
+            }
+        }
+    }
+
     fn activation_priority(&self) -> u32 {
         9
     }
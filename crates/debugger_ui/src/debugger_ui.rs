@@ -1,12 +1,24 @@
-use std::any::TypeId;
+use std::{any::TypeId, path::Path, sync::Arc};
+
+use anyhow::{Context as _, Result};
 
 use dap::debugger_settings::DebuggerSettings;
+use dap::recording::DapRecording;
 use debugger_panel::DebugPanel;
 use editor::Editor;
-use gpui::{App, DispatchPhase, EntityInputHandler, actions};
+use gpui::{
+    App, Context, DispatchPhase, EntityInputHandler, PathPromptOptions, Task, Window, actions,
+};
 use new_process_modal::{NewProcessModal, NewProcessMode};
 use onboarding_modal::DebuggerOnboardingModal;
-use project::debugger::{self, breakpoint_store::SourceBreakpoint, session::ThreadStatus};
+use project::{
+    DirectoryLister, Fs as _,
+    debugger::{
+        self,
+        breakpoint_store::{BreakpointsFile, SourceBreakpoint},
+        session::ThreadStatus,
+    },
+};
 use session::DebugSession;
 use settings::Settings;
 use stack_trace_view::StackTraceView;
@@ -56,6 +68,9 @@ actions!(
         ToggleSessionPicker,
         RerunLastSession,
         ToggleExpandItem,
+        ExportBreakpoints,
+        ImportBreakpoints,
+        OpenRecording,
     ]
 );
 
@@ -94,6 +109,15 @@ pub fn init(cx: &mut App) {
                     })
                 },
             )
+            .register_action(|workspace: &mut Workspace, _: &ExportBreakpoints, _, cx| {
+                export_breakpoints(workspace, cx).detach_and_log_err(cx);
+            })
+            .register_action(|workspace: &mut Workspace, _: &ImportBreakpoints, _, cx| {
+                import_breakpoints(workspace, cx).detach_and_log_err(cx);
+            })
+            .register_action(|workspace: &mut Workspace, _: &OpenRecording, window, cx| {
+                open_recording(workspace, window, cx).detach_and_log_err(cx);
+            })
             .register_action(|workspace, _: &OpenOnboardingModal, window, cx| {
                 DebuggerOnboardingModal::toggle(workspace, window, cx)
             })
@@ -117,6 +141,7 @@ pub fn init(cx: &mut App) {
                 let supports_step_back = caps.supports_step_back.unwrap_or_default();
                 let supports_detach = running_state.session().read(cx).is_attached();
                 let status = running_state.thread_status(cx);
+                let is_read_only = running_state.session().read(cx).is_read_only();
 
                 let active_item = active_item.downgrade();
                 div.when(status == Some(ThreadStatus::Running), |div| {
@@ -128,37 +153,39 @@ pub fn init(cx: &mut App) {
                     })
                 })
                 .when(status == Some(ThreadStatus::Stopped), |div| {
-                    div.on_action({
-                        let active_item = active_item.clone();
-                        move |_: &StepInto, _, cx| {
-                            active_item.update(cx, |item, cx| item.step_in(cx)).ok();
-                        }
-                    })
-                    .on_action({
-                        let active_item = active_item.clone();
-                        move |_: &StepOver, _, cx| {
-                            active_item.update(cx, |item, cx| item.step_over(cx)).ok();
-                        }
-                    })
-                    .on_action({
-                        let active_item = active_item.clone();
-                        move |_: &StepOut, _, cx| {
-                            active_item.update(cx, |item, cx| item.step_out(cx)).ok();
-                        }
-                    })
-                    .when(supports_step_back, |div| {
-                        let active_item = active_item.clone();
-                        div.on_action(move |_: &StepBack, _, cx| {
-                            active_item.update(cx, |item, cx| item.step_back(cx)).ok();
+                    div.when(!is_read_only, |div| {
+                        div.on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepInto, _, cx| {
+                                active_item.update(cx, |item, cx| item.step_in(cx)).ok();
+                            }
+                        })
+                        .on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepOver, _, cx| {
+                                active_item.update(cx, |item, cx| item.step_over(cx)).ok();
+                            }
+                        })
+                        .on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepOut, _, cx| {
+                                active_item.update(cx, |item, cx| item.step_out(cx)).ok();
+                            }
+                        })
+                        .when(supports_step_back, |div| {
+                            let active_item = active_item.clone();
+                            div.on_action(move |_: &StepBack, _, cx| {
+                                active_item.update(cx, |item, cx| item.step_back(cx)).ok();
+                            })
+                        })
+                        .on_action({
+                            let active_item = active_item.clone();
+                            move |_: &Continue, _, cx| {
+                                active_item
+                                    .update(cx, |item, cx| item.continue_thread(cx))
+                                    .ok();
+                            }
                         })
-                    })
-                    .on_action({
-                        let active_item = active_item.clone();
-                        move |_: &Continue, _, cx| {
-                            active_item
-                                .update(cx, |item, cx| item.continue_thread(cx))
-                                .ok();
-                        }
                     })
                     .on_action(cx.listener(
                         |workspace, _: &ShowStackTrace, window, cx| {
@@ -245,58 +272,111 @@ pub fn init(cx: &mut App) {
                         return;
                     };
                     let editor = cx.entity().downgrade();
-                    window.on_action(TypeId::of::<editor::actions::RunToCursor>(), {
-                        let editor = editor.clone();
-                        let active_session = active_session.clone();
-                        move |_, phase, _, cx| {
-                            if phase != DispatchPhase::Bubble {
-                                return;
-                            }
-                            maybe!({
-                                let (buffer, position, _) = editor
-                                    .update(cx, |editor, cx| {
-                                        let cursor_point: language::Point =
-                                            editor.selections.newest(cx).head();
+                    let session = active_session.read(cx).running_state().read(cx).session();
+                    let is_read_only = session.read(cx).is_read_only();
+                    let supports_goto_targets = session.read(cx).supports_goto_targets();
+                    if !is_read_only {
+                        window.on_action(TypeId::of::<editor::actions::RunToCursor>(), {
+                            let editor = editor.clone();
+                            let active_session = active_session.clone();
+                            move |_, phase, _, cx| {
+                                if phase != DispatchPhase::Bubble {
+                                    return;
+                                }
+                                maybe!({
+                                    let (buffer, position, _) = editor
+                                        .update(cx, |editor, cx| {
+                                            let cursor_point: language::Point =
+                                                editor.selections.newest(cx).head();
 
-                                        editor
-                                            .buffer()
-                                            .read(cx)
-                                            .point_to_buffer_point(cursor_point, cx)
-                                    })
-                                    .ok()??;
+                                            editor
+                                                .buffer()
+                                                .read(cx)
+                                                .point_to_buffer_point(cursor_point, cx)
+                                        })
+                                        .ok()??;
 
-                                let path =
-                                debugger::breakpoint_store::BreakpointStore::abs_path_from_buffer(
-                                    &buffer, cx,
-                                )?;
-
-                                let source_breakpoint = SourceBreakpoint {
-                                    row: position.row,
-                                    path,
-                                    message: None,
-                                    condition: None,
-                                    hit_condition: None,
-                                    state: debugger::breakpoint_store::BreakpointState::Enabled,
-                                };
+                                    let path =
+                                    debugger::breakpoint_store::BreakpointStore::abs_path_from_buffer(
+                                        &buffer, cx,
+                                    )?;
 
-                                active_session.update(cx, |session, cx| {
-                                    session.running_state().update(cx, |state, cx| {
-                                        if let Some(thread_id) = state.selected_thread_id() {
-                                            state.session().update(cx, |session, cx| {
-                                                session.run_to_position(
-                                                    source_breakpoint,
-                                                    thread_id,
-                                                    cx,
-                                                );
-                                            })
-                                        }
+                                    let source_breakpoint = SourceBreakpoint {
+                                        row: position.row,
+                                        path,
+                                        message: None,
+                                        condition: None,
+                                        hit_condition: None,
+                                        state: debugger::breakpoint_store::BreakpointState::Enabled,
+                                        group: None,
+                                    };
+
+                                    active_session.update(cx, |session, cx| {
+                                        session.running_state().update(cx, |state, cx| {
+                                            if let Some(thread_id) = state.selected_thread_id() {
+                                                state.session().update(cx, |session, cx| {
+                                                    session.run_to_position(
+                                                        source_breakpoint,
+                                                        thread_id,
+                                                        cx,
+                                                    );
+                                                })
+                                            }
+                                        });
                                     });
+
+                                    Some(())
                                 });
+                            }
+                        });
+                    }
 
-                                Some(())
-                            });
-                        }
-                    });
+                    if supports_goto_targets {
+                        window.on_action(TypeId::of::<editor::actions::JumpToCursor>(), {
+                            let editor = editor.clone();
+                            let active_session = active_session.clone();
+                            move |_, phase, _, cx| {
+                                if phase != DispatchPhase::Bubble {
+                                    return;
+                                }
+                                maybe!({
+                                    let (buffer, position, _) = editor
+                                        .update(cx, |editor, cx| {
+                                            let cursor_point: language::Point =
+                                                editor.selections.newest(cx).head();
+
+                                            editor
+                                                .buffer()
+                                                .read(cx)
+                                                .point_to_buffer_point(cursor_point, cx)
+                                        })
+                                        .ok()??;
+
+                                    let path =
+                                    debugger::breakpoint_store::BreakpointStore::abs_path_from_buffer(
+                                        &buffer, cx,
+                                    )?;
+
+                                    active_session.update(cx, |session, cx| {
+                                        session.running_state().update(cx, |state, cx| {
+                                            if let Some(thread_id) = state.selected_thread_id() {
+                                                state.session().update(cx, |session, cx| {
+                                                    session.jump_to_position(
+                                                        path,
+                                                        position.row,
+                                                        thread_id,
+                                                        cx,
+                                                    );
+                                                })
+                                            }
+                                        });
+                                    });
+
+                                    Some(())
+                                });
+                            }
+                        });
+                    }
 
                     window.on_action(
                         TypeId::of::<editor::actions::EvaluateSelectedText>(),
@@ -336,6 +416,104 @@ pub fn init(cx: &mut App) {
     .detach();
 }
 
+/// Returns the root of the first visible worktree, where `.zed/breakpoints.json` lives.
+fn breakpoints_file_root(workspace: &Workspace, cx: &App) -> Result<Arc<Path>> {
+    let project = workspace.project().read(cx);
+    anyhow::ensure!(
+        project.is_local(),
+        "Breakpoints can only be imported and exported in local projects"
+    );
+    let worktree = project
+        .visible_worktrees(cx)
+        .next()
+        .context("No worktree to store breakpoints in")?;
+    Ok(worktree.read(cx).abs_path())
+}
+
+fn export_breakpoints(workspace: &mut Workspace, cx: &mut Context<Workspace>) -> Task<Result<()>> {
+    let root = match breakpoints_file_root(workspace, cx) {
+        Ok(root) => root,
+        Err(error) => return Task::ready(Err(error)),
+    };
+    let project = workspace.project().read(cx);
+    let file = project
+        .breakpoint_store()
+        .read(cx)
+        .export_breakpoints(&root, cx);
+    let fs = project.fs().clone();
+
+    cx.background_spawn(async move {
+        let path = root.join(paths::local_breakpoints_file_relative_path());
+        let contents = serde_json::to_string_pretty(&file)?;
+        if let Some(parent) = path.parent() {
+            fs.create_dir(parent).await?;
+        }
+        fs.atomic_write(path, contents).await
+    })
+}
+
+fn import_breakpoints(workspace: &mut Workspace, cx: &mut Context<Workspace>) -> Task<Result<()>> {
+    let root = match breakpoints_file_root(workspace, cx) {
+        Ok(root) => root,
+        Err(error) => return Task::ready(Err(error)),
+    };
+    let project = workspace.project().read(cx);
+    let breakpoint_store = project.breakpoint_store();
+    let fs = project.fs().clone();
+
+    cx.spawn(async move |_, cx| {
+        let path = root.join(paths::local_breakpoints_file_relative_path());
+        let contents = fs
+            .load(&path)
+            .await
+            .with_context(|| format!("reading breakpoints from {path:?}"))?;
+        let file: BreakpointsFile = serde_json_lenient::from_str(&contents)
+            .with_context(|| format!("parsing breakpoints from {path:?}"))?;
+        breakpoint_store
+            .update(cx, |breakpoint_store, cx| {
+                breakpoint_store.import_breakpoints(&root, file, cx)
+            })?
+            .await
+    })
+}
+
+/// Prompts for a DAP recording saved by `debugger.record_dap_sessions`, and opens
+/// it in the debug panel as a read-only session.
+fn open_recording(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) -> Task<Result<()>> {
+    let Some(debug_panel) = workspace.panel::<DebugPanel>(cx) else {
+        return Task::ready(Ok(()));
+    };
+    let fs = workspace.app_state().fs.clone();
+    let prompt = workspace.prompt_for_open_path(
+        PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+        },
+        DirectoryLister::Local(workspace.project().clone(), fs.clone()),
+        window,
+        cx,
+    );
+
+    cx.spawn_in(window, async move |_, cx| {
+        let Some(path) = prompt.await?.and_then(|mut paths| paths.pop()) else {
+            return Ok(());
+        };
+        let contents = fs
+            .load(&path)
+            .await
+            .with_context(|| format!("reading DAP recording from {path:?}"))?;
+        let recording = DapRecording::from_json(&contents)?;
+        debug_panel.update_in(cx, |debug_panel, window, cx| {
+            debug_panel.open_recording(recording, window, cx)
+        })
+    })
+}
+
 fn spawn_task_or_modal(
     workspace: &mut Workspace,
     action: &Spawn,
@@ -6,15 +6,17 @@ use std::{
     borrow::Cow,
     path::{Path, PathBuf},
     sync::Arc,
-    time::Duration,
+    time::{Duration, SystemTime},
     usize,
 };
 use tasks_ui::{TaskOverrides, TasksModal};
 
 use dap::{
-    DapRegistry, DebugRequest, TelemetrySpawnLocation, adapters::DebugAdapterName, send_telemetry,
+    DapRegistry, DebugRequest, TelemetrySpawnLocation, adapters::DebugAdapterName,
+    debugger_settings::DebuggerSettings, send_telemetry,
 };
 use editor::{Editor, EditorElement, EditorStyle};
+use futures::StreamExt as _;
 use fuzzy::{StringMatch, StringMatchCandidate};
 use gpui::{
     Action, App, AppContext, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
@@ -23,7 +25,7 @@ use gpui::{
 };
 use itertools::Itertools as _;
 use picker::{Picker, PickerDelegate, highlighted_match_with_paths::HighlightedMatch};
-use project::{ProjectPath, TaskContexts, TaskSourceKind, task_store::TaskStore};
+use project::{Fs, ProjectPath, TaskContexts, TaskSourceKind, task_store::TaskStore};
 use settings::{Settings, initial_local_debug_tasks_content};
 use task::{DebugScenario, RevealTarget, ZedDebugConfig};
 use theme::ThemeSettings;
@@ -52,10 +54,11 @@ pub(super) struct NewProcessModal {
     debug_picker: Entity<Picker<DebugDelegate>>,
     attach_mode: Entity<AttachMode>,
     configure_mode: Entity<ConfigureMode>,
+    core_dump_mode: Entity<CoreDumpMode>,
     task_mode: TaskMode,
     debugger: Option<DebugAdapterName>,
     save_scenario_state: Option<SaveScenarioState>,
-    _subscriptions: [Subscription; 3],
+    _subscriptions: [Subscription; 4],
 }
 
 fn suggested_label(request: &DebugRequest, debugger: &str) -> SharedString {
@@ -73,6 +76,15 @@ fn suggested_label(request: &DebugRequest, debugger: &str) -> SharedString {
             config.process_id.unwrap_or(u32::MAX)
         )
         .into(),
+        DebugRequest::CoreDump(config) => format!(
+            "{} ({debugger})",
+            config
+                .core_file
+                .file_name()
+                .unwrap_or(config.core_file.as_os_str())
+                .to_string_lossy()
+        )
+        .into(),
     }
 }
 
@@ -89,6 +101,7 @@ impl NewProcessModal {
         };
         let task_store = workspace.project().read(cx).task_store().clone();
         let languages = workspace.app_state().languages.clone();
+        let core_dumps = core_dump_search(workspace, cx);
 
         cx.spawn_in(window, async move |workspace, cx| {
             let task_contexts = workspace.update_in(cx, |workspace, window, cx| {
@@ -107,6 +120,8 @@ impl NewProcessModal {
 
                     let configure_mode = ConfigureMode::new(window, cx);
 
+                    let core_dump_mode = CoreDumpMode::new(core_dumps, window, cx);
+
                     let task_overrides = Some(TaskOverrides { reveal_target });
 
                     let task_mode = TaskMode {
@@ -136,6 +151,12 @@ impl NewProcessModal {
                         cx.subscribe(&task_mode.task_modal, |_, _, _: &DismissEvent, cx| {
                             cx.emit(DismissEvent)
                         }),
+                        cx.subscribe(
+                            &core_dump_mode.read(cx).core_picker.clone(),
+                            |_, _, _, cx| {
+                                cx.emit(DismissEvent);
+                            },
+                        ),
                     ];
 
                     cx.spawn_in(window, {
@@ -260,6 +281,7 @@ impl NewProcessModal {
                         debug_picker,
                         attach_mode,
                         configure_mode,
+                        core_dump_mode,
                         task_mode,
                         debugger: None,
                         mode,
@@ -292,6 +314,9 @@ impl NewProcessModal {
             NewProcessMode::Launch => self.configure_mode.update(cx, |this, cx| {
                 this.clone().render(dap_menu, window, cx).into_any_element()
             }),
+            NewProcessMode::CoreDump => self.core_dump_mode.update(cx, |this, cx| {
+                this.render(dap_menu, window, cx).into_any_element()
+            }),
             NewProcessMode::Debug => v_flex()
                 .w(rems(34.))
                 .child(self.debug_picker.clone())
@@ -304,6 +329,7 @@ impl NewProcessModal {
             NewProcessMode::Task => self.task_mode.task_modal.focus_handle(cx),
             NewProcessMode::Attach => self.attach_mode.read(cx).attach_picker.focus_handle(cx),
             NewProcessMode::Launch => self.configure_mode.read(cx).program.focus_handle(cx),
+            NewProcessMode::CoreDump => self.core_dump_mode.read(cx).program.focus_handle(cx),
             NewProcessMode::Debug => self.debug_picker.focus_handle(cx),
         }
     }
@@ -316,6 +342,10 @@ impl NewProcessModal {
             NewProcessMode::Attach => {
                 DebugRequest::Attach(self.attach_mode.read(cx).debug_request())
             }
+            NewProcessMode::CoreDump => match self.core_dump_mode.read(cx).debug_request(cx) {
+                Some(request) => DebugRequest::CoreDump(request),
+                None => return Task::ready(None),
+            },
             _ => return Task::ready(None),
         };
         let label = suggested_label(&request, debugger);
@@ -615,6 +645,7 @@ pub(crate) enum NewProcessMode {
     Launch,
     Attach,
     Debug,
+    CoreDump,
 }
 
 impl std::fmt::Display for NewProcessMode {
@@ -624,6 +655,7 @@ impl std::fmt::Display for NewProcessMode {
             NewProcessMode::Debug => "Debug",
             NewProcessMode::Attach => "Attach",
             NewProcessMode::Launch => "Launch",
+            NewProcessMode::CoreDump => "Core Dump",
         };
 
         write!(f, "{}", mode)
@@ -699,7 +731,8 @@ impl Render for NewProcessModal {
                     NewProcessMode::Task => NewProcessMode::Debug,
                     NewProcessMode::Debug => NewProcessMode::Attach,
                     NewProcessMode::Attach => NewProcessMode::Launch,
-                    NewProcessMode::Launch => NewProcessMode::Task,
+                    NewProcessMode::Launch => NewProcessMode::CoreDump,
+                    NewProcessMode::CoreDump => NewProcessMode::Task,
                 };
 
                 this.mode_focus_handle(cx).focus(window);
@@ -707,10 +740,11 @@ impl Render for NewProcessModal {
             .on_action(
                 cx.listener(|this, _: &pane::ActivatePreviousItem, window, cx| {
                     this.mode = match this.mode {
-                        NewProcessMode::Task => NewProcessMode::Launch,
+                        NewProcessMode::Task => NewProcessMode::CoreDump,
                         NewProcessMode::Debug => NewProcessMode::Task,
                         NewProcessMode::Attach => NewProcessMode::Debug,
                         NewProcessMode::Launch => NewProcessMode::Attach,
+                        NewProcessMode::CoreDump => NewProcessMode::Launch,
                     };
 
                     this.mode_focus_handle(cx).focus(window);
@@ -793,6 +827,22 @@ impl Render for NewProcessModal {
                             cx.notify();
                         }))
                         .tooltip(Tooltip::text("Launch a new process with a debugger"))
+                        .middle(),
+                    )
+                    .child(
+                        ToggleButton::new(
+                            "debugger-session-ui-core-dump-button",
+                            NewProcessMode::CoreDump.to_string(),
+                        )
+                        .size(ButtonSize::Default)
+                        .toggle_state(matches!(self.mode, NewProcessMode::CoreDump))
+                        .style(ui::ButtonStyle::Subtle)
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.mode = NewProcessMode::CoreDump;
+                            this.mode_focus_handle(cx).focus(window);
+                            cx.notify();
+                        }))
+                        .tooltip(Tooltip::text("Inspect a core dump of a crashed process"))
                         .last(),
                     ),
             )
@@ -882,6 +932,18 @@ impl Render for NewProcessModal {
                                     ),
                             ),
                     ),
+                    NewProcessMode::CoreDump => el.child(
+                        container.justify_end().child(
+                            Button::new("debugger-spawn", "Start")
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.start_new_session(window, cx)
+                                }))
+                                .disabled(
+                                    self.debugger.is_none()
+                                        || self.core_dump_mode.read(cx).debug_request(cx).is_none(),
+                                ),
+                        ),
+                    ),
                     NewProcessMode::Debug => el,
                     NewProcessMode::Task => el,
                 }
@@ -1103,6 +1165,299 @@ impl AttachMode {
     }
 }
 
+pub(super) struct CoreDumpMode {
+    program: Entity<Editor>,
+    core_picker: Entity<Picker<CoreDumpDelegate>>,
+}
+
+impl CoreDumpMode {
+    pub(super) fn new(
+        search: Task<Vec<CoreDumpCandidate>>,
+        window: &mut Window,
+        cx: &mut Context<NewProcessModal>,
+    ) -> Entity<Self> {
+        let program = cx.new(|cx| Editor::single_line(window, cx));
+        program.update(cx, |this, cx| {
+            this.set_placeholder_text("Ex: target/debug/program", cx);
+        });
+
+        let new_process_modal = cx.weak_entity();
+        let core_picker = cx.new(|cx| {
+            Picker::uniform_list(CoreDumpDelegate::new(new_process_modal), window, cx)
+                .modal(false)
+                .max_height(Some(rems(20.).into()))
+        });
+
+        cx.spawn_in(window, {
+            let core_picker = core_picker.downgrade();
+            async move |_, cx| {
+                let candidates = search.await;
+                core_picker.update_in(cx, |picker, window, cx| {
+                    picker.delegate.candidates = candidates.into();
+                    picker.refresh(window, cx);
+                })
+            }
+        })
+        .detach_and_log_err(cx);
+
+        cx.new(|_| Self {
+            program,
+            core_picker,
+        })
+    }
+
+    pub(super) fn debug_request(&self, cx: &App) -> Option<task::CoreDumpRequest> {
+        let program = self.program.read(cx).text(cx);
+        if program.is_empty() {
+            return None;
+        }
+        let core_file = self.core_picker.read(cx).delegate.selected_core()?;
+
+        Some(task::CoreDumpRequest { program, core_file })
+    }
+
+    fn render(
+        &mut self,
+        adapter_menu: DropdownMenu,
+        window: &mut Window,
+        cx: &mut ui::Context<Self>,
+    ) -> impl IntoElement {
+        v_flex()
+            .p_2()
+            .w_full()
+            .gap_2()
+            .track_focus(&self.program.focus_handle(cx))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Label::new("Debugger")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(adapter_menu),
+            )
+            .child(
+                v_flex()
+                    .gap_0p5()
+                    .child(
+                        Label::new("Program")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(render_editor(&self.program, window, cx)),
+            )
+            .child(
+                v_flex()
+                    .gap_0p5()
+                    .child(
+                        Label::new("Core Dump")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(self.core_picker.clone()),
+            )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct CoreDumpCandidate {
+    pub(super) path: PathBuf,
+    pub(super) modified: SystemTime,
+}
+
+/// Lists the core dumps in the configured `core_dump_directory`, or the files named
+/// like cores in the root of the first worktree, most recent first.
+fn core_dump_search(workspace: &Workspace, cx: &App) -> Task<Vec<CoreDumpCandidate>> {
+    let project = workspace.project().read(cx);
+    if !project.is_local() {
+        return Task::ready(Vec::new());
+    }
+    let fs = project.fs().clone();
+    let (directory, only_named_cores) =
+        match DebuggerSettings::get_global(cx).core_dump_directory.clone() {
+            Some(directory) => (directory, false),
+            None => match project.visible_worktrees(cx).next() {
+                Some(worktree) => (worktree.read(cx).abs_path().to_path_buf(), true),
+                None => return Task::ready(Vec::new()),
+            },
+        };
+
+    cx.background_spawn(async move {
+        find_core_dumps(fs.as_ref(), &directory, only_named_cores)
+            .await
+            .log_err()
+            .unwrap_or_default()
+    })
+}
+
+pub(super) async fn find_core_dumps(
+    fs: &dyn Fs,
+    directory: &Path,
+    only_named_cores: bool,
+) -> anyhow::Result<Vec<CoreDumpCandidate>> {
+    let mut entries = fs.read_dir(directory).await?;
+    let mut candidates = Vec::new();
+    while let Some(path) = entries.next().await {
+        let path = path?;
+        if only_named_cores && !is_named_like_core_dump(&path) {
+            continue;
+        }
+        let Some(metadata) = fs.metadata(&path).await? else {
+            continue;
+        };
+        if metadata.is_dir {
+            continue;
+        }
+        candidates.push(CoreDumpCandidate {
+            path,
+            modified: metadata.mtime.timestamp_for_user(),
+        });
+    }
+    candidates.sort_by(|a, b| b.modified.cmp(&a.modified));
+    Ok(candidates)
+}
+
+fn is_named_like_core_dump(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name == "core" || name.starts_with("core.") || name.ends_with(".core"))
+}
+
+pub(super) struct CoreDumpDelegate {
+    candidates: Arc<[CoreDumpCandidate]>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    query: String,
+    new_process_modal: WeakEntity<NewProcessModal>,
+}
+
+impl CoreDumpDelegate {
+    fn new(new_process_modal: WeakEntity<NewProcessModal>) -> Self {
+        Self {
+            candidates: Arc::default(),
+            matches: Vec::new(),
+            selected_index: 0,
+            query: String::new(),
+            new_process_modal,
+        }
+    }
+
+    /// The selected core dump, or the typed query when it doesn't match any of the cores found.
+    fn selected_core(&self) -> Option<PathBuf> {
+        match self.matches.get(self.selected_index) {
+            Some(hit) => Some(self.candidates.get(hit.candidate_id)?.path.clone()),
+            None => (!self.query.is_empty()).then(|| PathBuf::from(&self.query)),
+        }
+    }
+}
+
+impl PickerDelegate for CoreDumpDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+        self.new_process_modal.update(cx, |_, cx| cx.notify()).ok();
+    }
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select a core dump, or enter its path".into()
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let candidates = self.candidates.clone();
+        cx.spawn(async move |this, cx| {
+            let matches = fuzzy::match_strings(
+                &candidates
+                    .iter()
+                    .enumerate()
+                    .map(|(id, candidate)| {
+                        StringMatchCandidate::new(id, &candidate.path.to_string_lossy())
+                    })
+                    .collect::<Vec<_>>(),
+                &query,
+                true,
+                true,
+                100,
+                &Default::default(),
+                cx.background_executor().clone(),
+            )
+            .await;
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.query = query;
+                delegate.selected_index = 0;
+                delegate
+                    .new_process_modal
+                    .update(cx, |_, cx| cx.notify())
+                    .ok();
+            })
+            .ok();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.new_process_modal
+            .update(cx, |modal, cx| modal.start_new_session(window, cx))
+            .ok();
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let hit = &self.matches[ix];
+        let candidate = self.candidates.get(hit.candidate_id)?;
+        let file_name = candidate
+            .path
+            .file_name()
+            .unwrap_or(candidate.path.as_os_str())
+            .to_string_lossy()
+            .into_owned();
+
+        Some(
+            ListItem::new(SharedString::from(format!("core-dump-entry-{ix}")))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    v_flex().items_start().child(Label::new(file_name)).child(
+                        Label::new(candidate.path.to_string_lossy().into_owned())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+                ),
+        )
+    }
+}
+
 #[derive(Clone)]
 pub(super) struct TaskMode {
     pub(super) task_modal: Entity<TasksModal>,
@@ -1533,6 +1888,7 @@ impl PickerDelegate for DebugDelegate {
             Some(TaskSourceKind::UserInput) => Some(Icon::new(IconName::Terminal)),
             Some(TaskSourceKind::AbsPath { .. }) => Some(Icon::new(IconName::Settings)),
             Some(TaskSourceKind::Worktree { .. }) => Some(Icon::new(IconName::FileTree)),
+            Some(TaskSourceKind::Extension { .. }) => Some(Icon::new(IconName::Blocks)),
             Some(TaskSourceKind::Lsp {
                 language_name: name,
                 ..
@@ -1,12 +1,23 @@
 pub mod running;
 
-use crate::{StackTraceView, persistence::SerializedLayout, session::running::DebugTerminal};
-use dap::client::SessionId;
+use crate::{
+    StackTraceView,
+    debugger_panel::DebugPanel,
+    persistence::SerializedLayout,
+    session::running::{
+        DebugTerminal,
+        stack_frame_list::StackFrameListEvent,
+        variable_list::{EntryPath, VariableListEvent},
+    },
+};
+use anyhow::anyhow;
+use dap::{StackFrameId, client::SessionId};
+use editor::EditorEvent;
 use gpui::{
-    App, Axis, Entity, EventEmitter, FocusHandle, Focusable, Subscription, Task, WeakEntity,
+    App, Axis, Entity, EventEmitter, FocusHandle, Focusable, Subscription, Task, WeakEntity, point,
 };
 use project::Project;
-use project::debugger::session::Session;
+use project::debugger::session::{Session, ThreadId};
 use project::worktree_store::WorktreeStore;
 use rpc::proto;
 use running::RunningState;
@@ -14,7 +25,7 @@ use std::{cell::OnceCell, sync::OnceLock};
 use ui::{Indicator, Tooltip, prelude::*};
 use workspace::{
     CollaboratorId, FollowableItem, ViewId, Workspace,
-    item::{self, Item},
+    item::{self, Item, LEADER_UPDATE_THROTTLE},
 };
 
 pub struct DebugSession {
@@ -24,7 +35,11 @@ pub struct DebugSession {
     stack_trace_view: OnceCell<Entity<StackTraceView>>,
     _worktree_store: WeakEntity<WorktreeStore>,
     workspace: WeakEntity<Workspace>,
-    _subscriptions: [Subscription; 1],
+    /// A stack frame that the leader selected before it was loaded here.
+    pending_followed_stack_frame_id: Option<StackFrameId>,
+    console_scroll_y: f32,
+    update_followers_task: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
 }
 
 #[derive(Debug)]
@@ -57,16 +72,53 @@ impl DebugSession {
             )
         });
 
+        let stack_frame_list = running_state.read(cx).stack_frame_list().clone();
+        let variable_list = running_state.read(cx).variable_list().clone();
+        let console_editor = running_state.read(cx).console().read(cx).editor().clone();
+
         cx.new(|cx| Self {
-            _subscriptions: [cx.subscribe(&running_state, |_, _, _, cx| {
-                cx.notify();
-            })],
+            _subscriptions: vec![
+                cx.subscribe(&running_state, |_, _, _, cx| {
+                    cx.notify();
+                }),
+                cx.subscribe_in(
+                    &stack_frame_list,
+                    window,
+                    |this, _, event, window, cx| match event {
+                        StackFrameListEvent::SelectedStackFrameChanged(_) => {
+                            this.update_followers(window, cx)
+                        }
+                        StackFrameListEvent::BuiltEntries => {
+                            this.select_pending_followed_stack_frame(cx)
+                        }
+                    },
+                ),
+                cx.subscribe_in(
+                    &variable_list,
+                    window,
+                    |this, _, _: &VariableListEvent, window, cx| this.update_followers(window, cx),
+                ),
+                cx.subscribe_in(
+                    &console_editor,
+                    window,
+                    |this, editor, event, window, cx| {
+                        if let EditorEvent::ScrollPositionChanged { local: true, .. } = event {
+                            this.console_scroll_y =
+                                editor.update(cx, |editor, cx| editor.scroll_position(cx).y);
+                            this.update_followers(window, cx);
+                        }
+                    },
+                ),
+            ],
             remote_id: None,
             running_state,
             label: OnceLock::new(),
             stack_trace_view: OnceCell::new(),
             _worktree_store: project.read(cx).worktree_store().downgrade(),
             workspace,
+            pending_followed_stack_frame_id: None,
+            console_scroll_y: 0.,
+            update_followers_task: None,
         })
     }
 
@@ -125,6 +177,98 @@ impl DebugSession {
         &self.running_state
     }
 
+    fn follow_state(&self, cx: &App) -> proto::DebugSessionFollowState {
+        let running_state = self.running_state.read(cx);
+        proto::DebugSessionFollowState {
+            thread_id: running_state
+                .selected_thread_id()
+                .map(|thread_id| thread_id.0),
+            stack_frame_id: running_state.selected_stack_frame_id(cx),
+            expanded_variables: running_state
+                .variable_list()
+                .read(cx)
+                .expanded_entries()
+                .iter()
+                .map(EntryPath::to_proto)
+                .collect(),
+            console_scroll_y: self.console_scroll_y,
+        }
+    }
+
+    fn apply_follow_state(
+        &mut self,
+        state: proto::DebugSessionFollowState,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // Avoid opening stack frames in an editor, which would stop following the leader.
+        self.running_state.update(cx, |running_state, cx| {
+            if let Some(thread_id) = state.thread_id {
+                running_state.select_thread_without_navigating(ThreadId(thread_id), window, cx);
+            }
+            running_state
+                .variable_list()
+                .update(cx, |variable_list, cx| {
+                    variable_list.set_expanded_entries(
+                        state
+                            .expanded_variables
+                            .into_iter()
+                            .map(EntryPath::from_proto)
+                            .collect(),
+                        cx,
+                    )
+                });
+            let console_editor = running_state.console().read(cx).editor().clone();
+            console_editor.update(cx, |editor, cx| {
+                let scroll_x = editor.scroll_position(cx).x;
+                editor.set_scroll_position(point(scroll_x, state.console_scroll_y), window, cx);
+            });
+        });
+        self.pending_followed_stack_frame_id = state.stack_frame_id;
+        self.select_pending_followed_stack_frame(cx);
+    }
+
+    fn select_pending_followed_stack_frame(&mut self, cx: &mut Context<Self>) {
+        let Some(stack_frame_id) = self.pending_followed_stack_frame_id else {
+            return;
+        };
+        let stack_frame_list = self.running_state.read(cx).stack_frame_list().clone();
+        if stack_frame_list.update(cx, |list, cx| list.select_stack_frame(stack_frame_id, cx)) {
+            self.pending_followed_stack_frame_id = None;
+        }
+    }
+
+    /// Sends this session's state to followers, as it isn't shown in a pane whose items'
+    /// events are forwarded to followers automatically.
+    fn update_followers(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.update_followers_task.is_some() {
+            return;
+        }
+        self.update_followers_task = Some(cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(LEADER_UPDATE_THROTTLE).await;
+            let Some(this) = this.upgrade() else {
+                return;
+            };
+            cx.update(|window, cx| {
+                let (workspace, state) = this.update(cx, |this, cx| {
+                    this.update_followers_task = None;
+                    (this.workspace.clone(), this.follow_state(cx))
+                });
+                workspace
+                    .update(cx, |workspace, cx| {
+                        workspace.update_followers_for_panel_item(
+                            &this,
+                            proto::update_view::Variant::DebugSession(state),
+                            window,
+                            cx,
+                        )
+                    })
+                    .ok();
+            })
+            .ok();
+        }));
+    }
+
     pub(crate) fn label_element(&self, depth: usize, cx: &App) -> AnyElement {
         let label = self.label(cx);
 
@@ -188,18 +332,58 @@ impl FollowableItem for DebugSession {
         self.remote_id
     }
 
-    fn to_state_proto(&self, _window: &Window, _cx: &App) -> Option<proto::view::Variant> {
-        None
+    fn to_state_proto(&self, _window: &Window, cx: &App) -> Option<proto::view::Variant> {
+        Some(proto::view::Variant::DebugSession(
+            proto::view::DebugSession {
+                session_id: self.session_id(cx).to_proto(),
+                state: Some(self.follow_state(cx)),
+            },
+        ))
     }
 
     fn from_state_proto(
-        _workspace: Entity<Workspace>,
-        _remote_id: ViewId,
-        _state: &mut Option<proto::view::Variant>,
-        _window: &mut Window,
-        _cx: &mut App,
+        workspace: Entity<Workspace>,
+        remote_id: ViewId,
+        state: &mut Option<proto::view::Variant>,
+        window: &mut Window,
+        cx: &mut App,
     ) -> Option<gpui::Task<anyhow::Result<Entity<Self>>>> {
-        None
+        let Some(proto::view::Variant::DebugSession(_)) = state else {
+            return None;
+        };
+        let Some(proto::view::Variant::DebugSession(state)) = state.take() else {
+            unreachable!()
+        };
+
+        // Debug sessions can't be created from a leader's state, so follow the leader into
+        // our own view of the same session. Guests only see the host's sessions once they were
+        // shared, so show the shared session if the panel doesn't show it yet.
+        let session_id = SessionId::from_proto(state.session_id);
+        let Some(panel) = workspace.read(cx).panel::<DebugPanel>(cx) else {
+            return Some(Task::ready(Err(anyhow!("debug panel is not available"))));
+        };
+        let session = panel
+            .read(cx)
+            .sessions()
+            .into_iter()
+            .find(|session| session.read(cx).session_id(cx) == session_id);
+        let session = match session {
+            Some(session) => Task::ready(Ok(session)),
+            None => panel.update(cx, |panel, cx| {
+                panel.register_shared_session(session_id, window, cx)
+            }),
+        };
+
+        Some(window.spawn(cx, async move |cx| {
+            let session = session.await?;
+            session.update_in(cx, |session, window, cx| {
+                session.remote_id = Some(remote_id);
+                if let Some(state) = state.state {
+                    session.apply_follow_state(state, window, cx);
+                }
+            })?;
+            Ok(session)
+        }))
     }
 
     fn add_event_to_update_proto(
@@ -209,19 +393,25 @@ impl FollowableItem for DebugSession {
         _window: &Window,
         _cx: &App,
     ) -> bool {
-        // update.get_or_insert_with(|| proto::update_view::Variant::DebugPanel(Default::default()));
-
-        true
+        // Debug sessions aren't shown in a pane, so they send their updates to followers
+        // themselves.
+        false
     }
 
     fn apply_update_proto(
         &mut self,
         _project: &Entity<project::Project>,
-        _message: proto::update_view::Variant,
-        _window: &mut Window,
-        _cx: &mut Context<Self>,
+        message: proto::update_view::Variant,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) -> gpui::Task<anyhow::Result<()>> {
-        Task::ready(Ok(()))
+        match message {
+            proto::update_view::Variant::DebugSession(state) => {
+                self.apply_follow_state(state, window, cx);
+                Task::ready(Ok(()))
+            }
+            _ => Task::ready(Err(anyhow!("unexpected view update for a debug session"))),
+        }
     }
 
     fn set_leader_id(
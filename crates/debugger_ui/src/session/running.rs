@@ -1153,7 +1153,8 @@ impl RunningState {
             terminal.read_with(cx, |terminal, _| {
                 terminal
                     .pty_info
-                    .pid()
+                    .as_ref()
+                    .and_then(|pty_info| pty_info.pid())
                     .map(|pid| pid.as_u32())
                     .context("Terminal was spawned but PID was not available")
             })?
@@ -1452,6 +1453,27 @@ impl RunningState {
         thread_id: ThreadId,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) {
+        self.select_thread_inner(thread_id, true, window, cx);
+    }
+
+    /// Selects a thread without opening the location of its top stack frame, such as when
+    /// following a collaborator.
+    pub(crate) fn select_thread_without_navigating(
+        &mut self,
+        thread_id: ThreadId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_thread_inner(thread_id, false, window, cx);
+    }
+
+    fn select_thread_inner(
+        &mut self,
+        thread_id: ThreadId,
+        open_first_stack_frame: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
         if self.thread_id.is_some_and(|id| id == thread_id) {
             return;
@@ -1459,8 +1481,9 @@ impl RunningState {
 
         self.thread_id = Some(thread_id);
 
-        self.stack_frame_list
-            .update(cx, |list, cx| list.schedule_refresh(true, window, cx));
+        self.stack_frame_list.update(cx, |list, cx| {
+            list.schedule_refresh(open_first_stack_frame, window, cx)
+        });
     }
 
     pub fn continue_thread(&mut self, cx: &mut Context<Self>) {
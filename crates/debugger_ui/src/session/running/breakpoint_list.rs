@@ -15,7 +15,9 @@ use language::Point;
 use project::{
     Project,
     debugger::{
-        breakpoint_store::{BreakpointEditAction, BreakpointStore, SourceBreakpoint},
+        breakpoint_store::{
+            BreakpointEditAction, BreakpointState, BreakpointStore, SourceBreakpoint,
+        },
         session::Session,
     },
     worktree_store::WorktreeStore,
@@ -33,7 +35,11 @@ use zed_actions::{ToggleEnableBreakpoint, UnsetBreakpoint};
 
 actions!(
     debugger,
-    [PreviousBreakpointProperty, NextBreakpointProperty]
+    [
+        PreviousBreakpointProperty,
+        NextBreakpointProperty,
+        ToggleBreakpointGroup
+    ]
 );
 #[derive(Clone, Copy, PartialEq)]
 pub(crate) enum SelectedBreakpointKind {
@@ -431,6 +437,41 @@ impl BreakpointList {
         cx.notify();
     }
 
+    fn toggle_breakpoint_group(
+        &mut self,
+        _: &ToggleBreakpointGroup,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.strip_mode.is_some() {
+            if self.input.focus_handle(cx).contains_focused(window, cx) {
+                cx.propagate();
+                return;
+            }
+        }
+        let Some(group) = self
+            .selected_ix
+            .and_then(|ix| self.breakpoints.get(ix))
+            .and_then(|entry| match &entry.kind {
+                BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
+                    line_breakpoint.breakpoint.group.clone()
+                }
+                BreakpointEntryKind::ExceptionBreakpoint(_) => None,
+            })
+        else {
+            return;
+        };
+
+        self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+            let state = match breakpoint_store.breakpoint_groups().get(&group) {
+                Some(BreakpointState::Enabled) => BreakpointState::Disabled,
+                _ => BreakpointState::Enabled,
+            };
+            breakpoint_store.set_group_state(&group, state, cx);
+        });
+        cx.notify();
+    }
+
     fn previous_breakpoint_property(
         &mut self,
         _: &PreviousBreakpointProperty,
@@ -733,6 +774,7 @@ impl Render for BreakpointList {
             .on_action(cx.listener(Self::confirm))
             .on_action(cx.listener(Self::toggle_enable_breakpoint))
             .on_action(cx.listener(Self::unset_breakpoint))
+            .on_action(cx.listener(Self::toggle_breakpoint_group))
             .on_action(cx.listener(Self::next_breakpoint_property))
             .on_action(cx.listener(Self::previous_breakpoint_property))
             .size_full()
@@ -897,6 +939,12 @@ impl LineBreakpoint {
                                     .line_height_style(ui::LineHeightStyle::UiLabel)
                                     .truncate(),
                             )
+                        }))
+                        .children(self.breakpoint.group.as_ref().map(|group| {
+                            Label::new(group.to_string())
+                                .color(Color::Accent)
+                                .size(LabelSize::Small)
+                                .line_height_style(ui::LineHeightStyle::UiLabel)
                         })),
                 )
                 .when_some(self.dir.as_ref(), |this, parent_dir| {
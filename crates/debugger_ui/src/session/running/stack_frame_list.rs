@@ -259,8 +259,34 @@ impl StackFrameList {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
-        let Some(stack_frame) = self
-            .entries
+        let Some(stack_frame) = self.find_stack_frame(stack_frame_id).cloned() else {
+            return Task::ready(Err(anyhow!("No stack frame for ID")));
+        };
+        self.go_to_stack_frame_inner(stack_frame, window, cx)
+    }
+
+    /// Selects a stack frame without opening its location in an editor, such as when
+    /// following a collaborator. Returns false if the stack frame hasn't been loaded.
+    pub(crate) fn select_stack_frame(
+        &mut self,
+        stack_frame_id: StackFrameId,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if self.find_stack_frame(stack_frame_id).is_none() {
+            return false;
+        }
+        if self.opened_stack_frame_id != Some(stack_frame_id) {
+            self.opened_stack_frame_id = Some(stack_frame_id);
+            cx.emit(StackFrameListEvent::SelectedStackFrameChanged(
+                stack_frame_id,
+            ));
+            cx.notify();
+        }
+        true
+    }
+
+    fn find_stack_frame(&self, stack_frame_id: StackFrameId) -> Option<&dap::StackFrame> {
+        self.entries
             .iter()
             .flat_map(|entry| match entry {
                 StackFrameEntry::Label(stack_frame) => std::slice::from_ref(stack_frame),
@@ -268,11 +294,6 @@ impl StackFrameList {
                 StackFrameEntry::Collapsed(stack_frames) => stack_frames.as_slice(),
             })
             .find(|stack_frame| stack_frame.id == stack_frame_id)
-            .cloned()
-        else {
-            return Task::ready(Err(anyhow!("No stack frame for ID")));
-        };
-        self.go_to_stack_frame_inner(stack_frame, window, cx)
     }
 
     fn go_to_stack_frame_inner(
@@ -6,11 +6,13 @@ use dap::{
 use editor::Editor;
 use gpui::{
     Action, AnyElement, ClickEvent, ClipboardItem, Context, DismissEvent, Empty, Entity,
-    FocusHandle, Focusable, Hsla, MouseButton, MouseDownEvent, Point, Stateful, Subscription,
-    TextStyleRefinement, UniformListScrollHandle, actions, anchored, deferred, uniform_list,
+    EventEmitter, FocusHandle, Focusable, Hsla, MouseButton, MouseDownEvent, Point, Stateful,
+    Subscription, TextStyleRefinement, UniformListScrollHandle, actions, anchored, deferred,
+    uniform_list,
 };
 use menu::{SelectFirst, SelectLast, SelectNext, SelectPrevious};
 use project::debugger::session::{Session, SessionEvent, Watcher};
+use rpc::proto;
 use std::{collections::HashMap, ops::Range, sync::Arc};
 use ui::{ContextMenu, ListItem, ScrollableHandle, Scrollbar, ScrollbarState, Tooltip, prelude::*};
 use util::debug_panic;
@@ -76,6 +78,26 @@ impl EntryPath {
                 .collect(),
         }
     }
+
+    pub(crate) fn to_proto(&self) -> proto::DebugVariablePath {
+        proto::DebugVariablePath {
+            leaf_name: self.leaf_name.as_ref().map(ToString::to_string),
+            indices: self.indices.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    pub(crate) fn from_proto(path: proto::DebugVariablePath) -> Self {
+        Self {
+            leaf_name: path.leaf_name.map(SharedString::from),
+            indices: path.indices.into_iter().map(SharedString::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum VariableListEvent {
+    /// An entry was expanded or collapsed.
+    ExpandedEntriesChanged,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -411,6 +433,36 @@ impl VariableList {
         };
 
         entry.is_expanded = !entry.is_expanded;
+        cx.emit(VariableListEvent::ExpandedEntriesChanged);
+        self.build_entries(cx);
+    }
+
+    /// The paths of the entries that are currently expanded.
+    pub(crate) fn expanded_entries(&self) -> Vec<EntryPath> {
+        self.entry_states
+            .iter()
+            .filter(|(_, state)| state.is_expanded)
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    /// Expands exactly the entries at the given paths, such as when following a collaborator.
+    /// Entries that haven't been loaded yet are expanded once they are.
+    pub(crate) fn set_expanded_entries(&mut self, paths: Vec<EntryPath>, cx: &mut Context<Self>) {
+        for state in self.entry_states.values_mut() {
+            state.is_expanded = false;
+        }
+        for path in paths {
+            self.entry_states
+                .entry(path)
+                .and_modify(|state| state.is_expanded = true)
+                .or_insert_with_key(|path| EntryState {
+                    depth: path.indices.len(),
+                    is_expanded: true,
+                    has_children: true,
+                    parent_reference: 0,
+                });
+        }
         self.build_entries(cx);
     }
 
@@ -529,6 +581,7 @@ impl VariableList {
                 self.select_prev(&SelectPrevious, window, cx);
             } else {
                 entry_state.is_expanded = false;
+                cx.emit(VariableListEvent::ExpandedEntriesChanged);
                 self.build_entries(cx);
             }
         }
@@ -550,6 +603,7 @@ impl VariableList {
                 self.select_next(&SelectNext, window, cx);
             } else {
                 entry_state.is_expanded = true;
+                cx.emit(VariableListEvent::ExpandedEntriesChanged);
                 self.build_entries(cx);
             }
         }
@@ -1323,6 +1377,8 @@ impl VariableList {
     }
 }
 
+impl EventEmitter<VariableListEvent> for VariableList {}
+
 impl Focusable for VariableList {
     fn focus_handle(&self, _: &App) -> gpui::FocusHandle {
         self.focus_handle.clone()
@@ -29,6 +29,8 @@ mod new_process_modal;
 #[cfg(test)]
 mod persistence;
 #[cfg(test)]
+mod replay;
+#[cfg(test)]
 mod stack_frame_list;
 #[cfg(test)]
 mod variable_list;
@@ -9,8 +9,8 @@ use dap::{
     adapters::DebugTaskDefinition,
     client::SessionId,
     requests::{
-        Continue, Disconnect, Launch, Next, RunInTerminal, SetBreakpoints, StackTrace,
-        StartDebugging, StepBack, StepIn, StepOut, Threads,
+        Continue, Disconnect, Goto, GotoTargets, Launch, Next, RunInTerminal, SetBreakpoints,
+        StackTrace, StartDebugging, StepBack, StepIn, StepOut, Threads,
     },
 };
 use editor::{
@@ -1079,6 +1079,222 @@ async fn test_debug_panel_item_thread_status_reset_on_failure(
     }
 }
 
+#[gpui::test]
+async fn test_jump_to_position_uses_first_goto_target(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<dap::requests::Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_goto_targets_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<GotoTargets, _>(move |_, args| {
+        assert_eq!(
+            args.source.path,
+            Some(path!("/project/main.rs").to_string())
+        );
+        assert_eq!(args.line, 3);
+
+        Ok(dap::GotoTargetsResponse {
+            targets: vec![
+                dap::GotoTarget {
+                    id: 42,
+                    label: "Third line".into(),
+                    line: 3,
+                    column: None,
+                    end_line: None,
+                    end_column: None,
+                    instruction_pointer_reference: None,
+                },
+                dap::GotoTarget {
+                    id: 43,
+                    label: "Third line (inlined)".into(),
+                    line: 3,
+                    column: None,
+                    end_line: None,
+                    end_column: None,
+                    instruction_pointer_reference: None,
+                },
+            ],
+        })
+    });
+
+    let called_goto = Arc::new(AtomicBool::new(false));
+    client.on_request::<Goto, _>({
+        let called_goto = called_goto.clone();
+        move |_, args| {
+            assert_eq!(args.thread_id, 1);
+            assert_eq!(args.target_id, 42);
+            called_goto.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    session.update(cx, |session, cx| {
+        assert!(session.supports_goto_targets());
+        session.jump_to_position(
+            Arc::from(Path::new(path!("/project/main.rs"))),
+            2,
+            ThreadId(1),
+            cx,
+        );
+    });
+
+    cx.run_until_parked();
+
+    assert!(
+        called_goto.load(Ordering::SeqCst),
+        "Expected a goto request for the first goto target"
+    );
+}
+
+#[gpui::test]
+async fn test_core_dump_session_is_read_only(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session_with(
+        &workspace,
+        cx,
+        DebugTaskDefinition {
+            adapter: "fake-adapter".into(),
+            label: "core dump".into(),
+            config: json!({
+                "request": "coredump",
+                "program": path!("/project/target/debug/program"),
+                "core_file": path!("/project/core"),
+            }),
+            tcp_connection: None,
+        },
+        |_| {},
+    )
+    .unwrap();
+
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Continue, _>(move |_, _| {
+        panic!("Core dump sessions should not continue threads");
+    });
+
+    client.on_request::<Next, _>(move |_, _| {
+        panic!("Core dump sessions should not step threads");
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Exception,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: Some(true),
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    session.update(cx, |session, cx| {
+        assert!(session.is_read_only());
+        assert_eq!(1, session.threads(cx).len());
+
+        session.continue_thread(ThreadId(1), cx);
+        session.step_over(ThreadId(1), dap::SteppingGranularity::Line, cx);
+    });
+
+    cx.run_until_parked();
+
+    session.update(cx, |session, _| {
+        assert_eq!(ThreadStatus::Stopped, session.thread_status(ThreadId(1)));
+    });
+}
+
 #[gpui::test]
 async fn test_send_breakpoints_when_editor_has_been_saved(
     executor: BackgroundExecutor,
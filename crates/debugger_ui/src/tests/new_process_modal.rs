@@ -8,6 +8,7 @@ use task::{DebugRequest, DebugScenario, LaunchRequest, TaskContext, VariableName
 use util::path;
 
 // use crate::new_process_modal::NewProcessMode;
+use crate::new_process_modal::find_core_dumps;
 use crate::tests::{init_test, init_test_workspace};
 
 #[gpui::test]
@@ -348,3 +349,54 @@ async fn test_dap_adapter_config_conversion_and_validation(cx: &mut TestAppConte
         expected_adapters
     );
 }
+
+#[gpui::test]
+async fn test_find_core_dumps(executor: BackgroundExecutor) {
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "core": "",
+            "core.1234": "",
+            "program.core": "",
+            "main.rs": "fn main() {}",
+            "core.d": {
+                "notes.txt": "",
+            },
+        }),
+    )
+    .await;
+    fs.insert_tree(
+        path!("/var/crash"),
+        json!({
+            "program.1234.crash": "",
+        }),
+    )
+    .await;
+
+    let file_names = |cores: Vec<crate::new_process_modal::CoreDumpCandidate>| {
+        let mut names = cores
+            .into_iter()
+            .map(|core| {
+                core.path
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    };
+
+    let cores = find_core_dumps(fs.as_ref(), path!("/project").as_ref(), true)
+        .await
+        .unwrap();
+    assert_eq!(file_names(cores), vec!["core", "core.1234", "program.core"]);
+
+    // Every file of a configured core dump directory is a candidate
+    let cores = find_core_dumps(fs.as_ref(), path!("/var/crash").as_ref(), false)
+        .await
+        .unwrap();
+    assert_eq!(file_names(cores), vec!["program.1234.crash"]);
+}
@@ -0,0 +1,187 @@
+use crate::{
+    debugger_panel::DebugPanel,
+    tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
+};
+use dap::{StackFrame, recording::DapRecording};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext, WindowHandle};
+use project::{FakeFs, Project};
+use serde_json::json;
+use util::path;
+use workspace::Workspace;
+
+#[gpui::test]
+async fn test_replay_recorded_session(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "fn main() {\n    println!(\"Hello, world!\");\n}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let _session = start_debug_session(&workspace, cx, move |client| {
+        client.replay(&recording()).unwrap();
+    })
+    .unwrap();
+
+    cx.run_until_parked();
+    assert_replayed_stack_frames(workspace, cx);
+}
+
+#[gpui::test]
+async fn test_open_recording(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "fn main() {\n    println!(\"Hello, world!\");\n}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    workspace
+        .update(cx, |workspace, window, cx| {
+            let debug_panel = workspace.panel::<DebugPanel>(cx).unwrap();
+            debug_panel.update(cx, |debug_panel, cx| {
+                debug_panel.open_recording(recording(), window, cx)
+            });
+        })
+        .unwrap();
+
+    cx.run_until_parked();
+
+    active_debug_session_panel(workspace, cx).update(cx, |session, cx| {
+        let session = session.running_state().read(cx).session().read(cx);
+        assert!(session.is_read_only());
+        assert!(session.recording().is_some());
+    });
+
+    assert_replayed_stack_frames(workspace, cx);
+}
+
+fn assert_replayed_stack_frames(workspace: WindowHandle<Workspace>, cx: &mut VisualTestContext) {
+    // trigger to load threads
+    active_debug_session_panel(workspace, cx).update(cx, |session, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state
+                .session()
+                .update(cx, |session, cx| session.threads(cx));
+        });
+    });
+
+    cx.run_until_parked();
+
+    // select first thread
+    active_debug_session_panel(workspace, cx).update_in(cx, |session, window, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state.select_current_thread(
+                &running_state
+                    .session()
+                    .update(cx, |session, cx| session.threads(cx)),
+                window,
+                cx,
+            );
+        });
+    });
+
+    cx.run_until_parked();
+
+    active_debug_session_panel(workspace, cx).update(cx, |session, cx| {
+        let stack_frame_list = session
+            .running_state()
+            .update(cx, |state, _| state.stack_frame_list().clone());
+
+        stack_frame_list.update(cx, |stack_frame_list, cx| {
+            let stack_frames = stack_frame_list.dap_stack_frames(cx);
+            assert_eq!(
+                stack_frames
+                    .iter()
+                    .map(|frame: &StackFrame| (frame.id, frame.name.as_str(), frame.line))
+                    .collect::<Vec<_>>(),
+                vec![(1, "main", 2)]
+            );
+            assert_eq!(Some(1), stack_frame_list.opened_stack_frame_id());
+        });
+    });
+}
+
+fn recording() -> DapRecording {
+    DapRecording::from_json(
+        &json!({
+            "adapter": "fake-adapter",
+            "started_at": 0,
+            "messages": [
+                {
+                    "timestamp_ms": 0,
+                    "direction": "to_adapter",
+                    "message": { "type": "request", "seq": 1, "command": "initialize", "arguments": { "adapterID": "fake-adapter" } }
+                },
+                {
+                    "timestamp_ms": 12,
+                    "direction": "from_adapter",
+                    "message": { "type": "response", "seq": 1, "request_seq": 1, "success": true, "command": "initialize", "body": { "supportsConfigurationDoneRequest": true } }
+                },
+                {
+                    "timestamp_ms": 13,
+                    "direction": "from_adapter",
+                    "message": { "type": "event", "seq": 2, "event": "initialized" }
+                },
+                {
+                    "timestamp_ms": 20,
+                    "direction": "from_adapter",
+                    "message": { "type": "response", "seq": 3, "request_seq": 2, "success": true, "command": "launch" }
+                },
+                {
+                    "timestamp_ms": 25,
+                    "direction": "from_adapter",
+                    "message": { "type": "response", "seq": 4, "request_seq": 3, "success": true, "command": "configurationDone" }
+                },
+                {
+                    "timestamp_ms": 140,
+                    "direction": "from_adapter",
+                    "message": { "type": "event", "seq": 5, "event": "stopped", "body": { "reason": "breakpoint", "threadId": 1, "allThreadsStopped": true } }
+                },
+                {
+                    "timestamp_ms": 150,
+                    "direction": "from_adapter",
+                    "message": { "type": "response", "seq": 6, "request_seq": 4, "success": true, "command": "threads", "body": { "threads": [{ "id": 1, "name": "main" }] } }
+                },
+                {
+                    "timestamp_ms": 160,
+                    "direction": "from_adapter",
+                    "message": {
+                        "type": "response", "seq": 7, "request_seq": 5, "success": true, "command": "stackTrace",
+                        "body": {
+                            "stackFrames": [{
+                                "id": 1,
+                                "name": "main",
+                                "line": 2,
+                                "column": 5,
+                                "source": { "name": "main.rs", "path": path!("/project/main.rs") }
+                            }]
+                        }
+                    }
+                },
+                {
+                    "timestamp_ms": 170,
+                    "direction": "from_adapter",
+                    "message": { "type": "response", "seq": 8, "request_seq": 6, "success": true, "command": "scopes", "body": { "scopes": [] } }
+                }
+            ]
+        })
+        .to_string(),
+    )
+    .unwrap()
+}
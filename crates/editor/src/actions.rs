@@ -238,7 +238,7 @@ pub enum UuidVersion {
     V7,
 }
 
-actions!(debugger, [RunToCursor, EvaluateSelectedText]);
+actions!(debugger, [RunToCursor, JumpToCursor, EvaluateSelectedText]);
 
 actions!(
     go_to_line,
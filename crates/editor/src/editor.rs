@@ -7858,6 +7858,12 @@ impl Editor {
             "Set Hit Condition Breakpoint"
         };
 
+        let group_breakpoint_msg = if breakpoint.as_ref().is_some_and(|bp| bp.1.group.is_some()) {
+            "Edit Breakpoint Group"
+        } else {
+            "Set Breakpoint Group"
+        };
+
         let set_breakpoint_msg = if breakpoint.as_ref().is_some() {
             "Unset Breakpoint"
         } else {
@@ -7865,6 +7871,7 @@ impl Editor {
         };
 
         let run_to_cursor = window.is_action_available(&RunToCursor, cx);
+        let jump_to_cursor = window.is_action_available(&JumpToCursor, cx);
 
         let toggle_state_msg = breakpoint.as_ref().map_or(None, |bp| match bp.1.state {
             BreakpointState::Enabled => Some("Disable"),
@@ -7893,6 +7900,24 @@ impl Editor {
 
                         window.dispatch_action(Box::new(RunToCursor), cx);
                     })
+                    .when(!jump_to_cursor, |this| this.separator())
+                })
+                .when(jump_to_cursor, |this| {
+                    let weak_editor = weak_editor.clone();
+                    this.entry("Jump to cursor", None, move |window, cx| {
+                        weak_editor
+                            .update(cx, |editor, cx| {
+                                editor.change_selections(
+                                    SelectionEffects::no_scroll(),
+                                    window,
+                                    cx,
+                                    |s| s.select_ranges([Point::new(row, 0)..Point::new(row, 0)]),
+                                );
+                            })
+                            .ok();
+
+                        window.dispatch_action(Box::new(JumpToCursor), cx);
+                    })
                     .separator()
                 })
                 .when_some(toggle_state_msg, |this, msg| {
@@ -7963,13 +7988,30 @@ impl Editor {
                             .log_err();
                     }
                 })
-                .entry(hit_condition_breakpoint_msg, None, move |window, cx| {
+                .entry(hit_condition_breakpoint_msg, None, {
+                    let breakpoint = breakpoint.clone();
+                    let weak_editor = weak_editor.clone();
+                    move |window, cx| {
+                        weak_editor
+                            .update(cx, |this, cx| {
+                                this.add_edit_breakpoint_block(
+                                    anchor,
+                                    breakpoint.as_ref(),
+                                    BreakpointPromptEditAction::HitCondition,
+                                    window,
+                                    cx,
+                                );
+                            })
+                            .log_err();
+                    }
+                })
+                .entry(group_breakpoint_msg, None, move |window, cx| {
                     weak_editor
                         .update(cx, |this, cx| {
                             this.add_edit_breakpoint_block(
                                 anchor,
                                 breakpoint.as_ref(),
-                                BreakpointPromptEditAction::HitCondition,
+                                BreakpointPromptEditAction::Group,
                                 window,
                                 cx,
                             );
@@ -10471,6 +10513,7 @@ impl Editor {
                 state: BreakpointState::Enabled,
                 condition: None,
                 hit_condition: None,
+                group: None,
             });
 
             self.add_edit_breakpoint_block(
@@ -12090,10 +12133,11 @@ impl Editor {
 
     pub fn move_left(&mut self, _: &MoveLeft, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+        let text_layout_details = self.text_layout_details(window);
         self.change_selections(Default::default(), window, cx, |s| {
             s.move_with(|map, selection| {
                 let cursor = if selection.is_empty() {
-                    movement::left(map, selection.start)
+                    movement::visual_left(map, selection.start, &text_layout_details)
                 } else {
                     selection.start
                 };
@@ -12104,17 +12148,24 @@ impl Editor {
 
     pub fn select_left(&mut self, _: &SelectLeft, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+        let text_layout_details = self.text_layout_details(window);
         self.change_selections(Default::default(), window, cx, |s| {
-            s.move_heads_with(|map, head, _| (movement::left(map, head), SelectionGoal::None));
+            s.move_heads_with(|map, head, _| {
+                (
+                    movement::visual_left(map, head, &text_layout_details),
+                    SelectionGoal::None,
+                )
+            });
         })
     }
 
     pub fn move_right(&mut self, _: &MoveRight, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+        let text_layout_details = self.text_layout_details(window);
         self.change_selections(Default::default(), window, cx, |s| {
             s.move_with(|map, selection| {
                 let cursor = if selection.is_empty() {
-                    movement::right(map, selection.end)
+                    movement::visual_right(map, selection.end, &text_layout_details)
                 } else {
                     selection.end
                 };
@@ -12125,8 +12176,14 @@ impl Editor {
 
     pub fn select_right(&mut self, _: &SelectRight, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+        let text_layout_details = self.text_layout_details(window);
         self.change_selections(Default::default(), window, cx, |s| {
-            s.move_heads_with(|map, head, _| (movement::right(map, head), SelectionGoal::None));
+            s.move_heads_with(|map, head, _| {
+                (
+                    movement::visual_right(map, head, &text_layout_details),
+                    SelectionGoal::None,
+                )
+            });
         })
     }
 
@@ -23021,6 +23078,7 @@ enum BreakpointPromptEditAction {
     Log,
     Condition,
     HitCondition,
+    Group,
 }
 
 struct BreakpointPromptEditor {
@@ -23049,6 +23107,7 @@ impl BreakpointPromptEditor {
             BreakpointPromptEditAction::Log => breakpoint.message.as_ref(),
             BreakpointPromptEditAction::Condition => breakpoint.condition.as_ref(),
             BreakpointPromptEditAction::HitCondition => breakpoint.hit_condition.as_ref(),
+            BreakpointPromptEditAction::Group => breakpoint.group.as_ref(),
         }
         .map(|msg| msg.to_string())
         .unwrap_or_default();
@@ -23074,6 +23133,7 @@ impl BreakpointPromptEditor {
                     BreakpointPromptEditAction::Log => "Message to log when a breakpoint is hit. Expressions within {} are interpolated.",
                     BreakpointPromptEditAction::Condition => "Condition when a breakpoint is hit. Expressions within {} are interpolated.",
                     BreakpointPromptEditAction::HitCondition => "How many breakpoint hits to ignore",
+                    BreakpointPromptEditAction::Group => "Name of the group to enable and disable this breakpoint with",
                 },
                 cx,
             );
@@ -23124,6 +23184,9 @@ impl BreakpointPromptEditor {
                         BreakpointPromptEditAction::HitCondition => {
                             BreakpointEditAction::EditHitCondition(message.into())
                         }
+                        BreakpointPromptEditAction::Group => {
+                            BreakpointEditAction::EditGroup(message.trim().into())
+                        }
                     },
                     cx,
                 );
@@ -20115,6 +20115,7 @@ fn assert_breakpoint(
                         state: breakpoint.state,
                         condition: breakpoint.condition.clone(),
                         hit_condition: breakpoint.hit_condition.clone(),
+                        group: breakpoint.group.clone(),
                     },
                 )
             })
@@ -20614,6 +20615,333 @@ async fn test_breakpoint_enabling_and_disabling(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_breakpoint_groups_and_breakpoints_file(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let sample_text = "First line\nSecond line\nThird line\nFourth line".to_string();
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "main.rs": sample_text,
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*workspace.deref(), cx);
+    let worktree_id = workspace
+        .update(cx, |workspace, _window, cx| {
+            workspace.project().update(cx, |project, cx| {
+                project.worktrees(cx).next().unwrap().read(cx).id()
+            })
+        })
+        .unwrap();
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, "main.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(buffer, cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+
+    let project_path = editor.update(cx, |editor, cx| editor.project_path(cx).unwrap());
+    let abs_path = project.read_with(cx, |project, cx| {
+        project
+            .absolute_path(&project_path, cx)
+            .map(|path_buf| Arc::from(path_buf.to_owned()))
+            .unwrap()
+    });
+
+    // Put the breakpoints on the first and last lines into the same group
+    editor.update_in(cx, |editor, window, cx| {
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+        editor.move_to_end(&MoveToEnd, window, cx);
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+        editor.move_up(&MoveUp, window, cx);
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+        editor.move_to_beginning(&MoveToBeginning, window, cx);
+        for _ in 0..2 {
+            let (anchor, breakpoint) = editor
+                .breakpoints_at_cursors(window, cx)
+                .first()
+                .and_then(|(anchor, breakpoint)| Some((*anchor, breakpoint.clone()?)))
+                .unwrap();
+            editor.edit_breakpoint_at_anchor(
+                anchor,
+                breakpoint,
+                BreakpointEditAction::EditGroup("startup".into()),
+                cx,
+            );
+            editor.move_to_end(&MoveToEnd, window, cx);
+        }
+    });
+
+    let breakpoint_store = editor.update(cx, |editor, _| editor.breakpoint_store().unwrap());
+    let grouped_breakpoint = |state| {
+        let mut bp = Breakpoint::new_standard();
+        bp.group = Some("startup".into());
+        bp.state = state;
+        bp
+    };
+
+    breakpoint_store.update(cx, |store, cx| {
+        assert_eq!(
+            store.breakpoint_groups(),
+            BTreeMap::from_iter([("startup".into(), BreakpointState::Enabled)])
+        );
+        store.set_group_state("startup", BreakpointState::Disabled, cx);
+    });
+
+    let breakpoints = breakpoint_store.read_with(cx, |store, cx| store.all_source_breakpoints(cx));
+    assert_breakpoint(
+        &breakpoints,
+        &abs_path,
+        vec![
+            (0, grouped_breakpoint(BreakpointState::Disabled)),
+            (2, Breakpoint::new_standard()),
+            (3, grouped_breakpoint(BreakpointState::Disabled)),
+        ],
+    );
+
+    // Exported paths are relative to the root and lines are one-based
+    let file = breakpoint_store.read_with(cx, |store, cx| {
+        store.export_breakpoints(Path::new(path!("/a")), cx)
+    });
+    assert_eq!(
+        file.breakpoints
+            .iter()
+            .map(|entry| (
+                entry.path.as_path(),
+                entry.line,
+                entry.group.as_deref(),
+                entry.disabled
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (Path::new("main.rs"), 1, Some("startup"), true),
+            (Path::new("main.rs"), 3, None, false),
+            (Path::new("main.rs"), 4, Some("startup"), true),
+        ]
+    );
+
+    breakpoint_store.update(cx, |store, cx| store.clear_breakpoints(cx));
+    breakpoint_store
+        .update(cx, |store, cx| {
+            store.import_breakpoints(Path::new(path!("/a")), file, cx)
+        })
+        .await
+        .unwrap();
+
+    let imported = breakpoint_store.read_with(cx, |store, cx| store.all_source_breakpoints(cx));
+    assert_breakpoint(
+        &imported,
+        &abs_path,
+        vec![
+            (0, grouped_breakpoint(BreakpointState::Disabled)),
+            (2, Breakpoint::new_standard()),
+            (3, grouped_breakpoint(BreakpointState::Disabled)),
+        ],
+    );
+}
+
+#[gpui::test]
+async fn test_breakpoints_per_branch(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line",
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*workspace.deref(), cx);
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, "main.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(buffer, cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+    let abs_path: Arc<Path> = Path::new(path!("/a/main.rs")).into();
+    let breakpoint_store = editor.update(cx, |editor, _| editor.breakpoint_store().unwrap());
+    let breakpoints = |cx: &mut VisualTestContext| {
+        breakpoint_store.read_with(cx, |store, cx| store.all_source_breakpoints(cx))
+    };
+
+    // The first branch that is reported keeps the breakpoints that are already set
+    editor.update_in(cx, |editor, window, cx| {
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+    });
+    breakpoint_store
+        .update(cx, |store, cx| store.set_active_branch("main".into(), cx))
+        .await
+        .unwrap();
+    assert_breakpoint(
+        &breakpoints(cx),
+        &abs_path,
+        vec![(0, Breakpoint::new_standard())],
+    );
+
+    breakpoint_store
+        .update(cx, |store, cx| {
+            store.set_active_branch("feature".into(), cx)
+        })
+        .await
+        .unwrap();
+    assert_breakpoint(&breakpoints(cx), &abs_path, vec![]);
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.move_to_end(&MoveToEnd, window, cx);
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+    });
+
+    breakpoint_store
+        .update(cx, |store, cx| store.set_active_branch("main".into(), cx))
+        .await
+        .unwrap();
+    assert_breakpoint(
+        &breakpoints(cx),
+        &abs_path,
+        vec![(0, Breakpoint::new_standard())],
+    );
+
+    breakpoint_store
+        .update(cx, |store, cx| {
+            store.set_active_branch("feature".into(), cx)
+        })
+        .await
+        .unwrap();
+    assert_breakpoint(
+        &breakpoints(cx),
+        &abs_path,
+        vec![(2, Breakpoint::new_standard())],
+    );
+}
+
+#[gpui::test]
+async fn test_restoring_breakpoints_of_another_branch(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line",
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let breakpoint_store = project.read_with(cx, |project, _| project.breakpoint_store());
+    let abs_path: Arc<Path> = Path::new(path!("/a/main.rs")).into();
+    let breakpoints_at = |row| {
+        BTreeMap::from_iter([(
+            abs_path.clone(),
+            vec![SourceBreakpoint {
+                row,
+                path: abs_path.clone(),
+                message: None,
+                condition: None,
+                hit_condition: None,
+                state: BreakpointState::Enabled,
+                group: None,
+            }],
+        )])
+    };
+    let breakpoints = |cx: &mut TestAppContext| {
+        breakpoint_store.read_with(cx, |store, cx| store.all_source_breakpoints(cx))
+    };
+
+    // Breakpoints saved on another branch than the one reported first are kept aside for it.
+    breakpoint_store
+        .update(cx, |store, cx| {
+            store.with_serialized_branch_breakpoints(
+                Some("main".into()),
+                breakpoints_at(0),
+                BTreeMap::from_iter([("feature".into(), breakpoints_at(2))]),
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+    breakpoint_store
+        .update(cx, |store, cx| {
+            store.set_active_branch("feature".into(), cx)
+        })
+        .await
+        .unwrap();
+    assert_breakpoint(
+        &breakpoints(cx),
+        &abs_path,
+        vec![(2, Breakpoint::new_standard())],
+    );
+    breakpoint_store
+        .update(cx, |store, cx| store.set_active_branch("main".into(), cx))
+        .await
+        .unwrap();
+    assert_breakpoint(
+        &breakpoints(cx),
+        &abs_path,
+        vec![(0, Breakpoint::new_standard())],
+    );
+
+    // Breakpoints restored after another branch was reported are kept aside as well.
+    breakpoint_store
+        .update(cx, |store, cx| {
+            store.with_serialized_branch_breakpoints(
+                Some("feature".into()),
+                breakpoints_at(1),
+                BTreeMap::from_iter([("main".into(), breakpoints_at(0))]),
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+    assert_breakpoint(
+        &breakpoints(cx),
+        &abs_path,
+        vec![(0, Breakpoint::new_standard())],
+    );
+    breakpoint_store
+        .update(cx, |store, cx| {
+            store.set_active_branch("feature".into(), cx)
+        })
+        .await
+        .unwrap();
+    assert_breakpoint(
+        &breakpoints(cx),
+        &abs_path,
+        vec![(1, Breakpoint::new_standard())],
+    );
+}
+
 #[gpui::test]
 async fn test_rename_with_duplicate_edits(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -22349,6 +22677,45 @@ pub(crate) fn update_test_project_settings(
     });
 }
 
+#[gpui::test]
+fn test_editor_accessibility_node(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let single_line = cx.add_window(|window, cx| {
+        let mut editor = Editor::single_line(window, cx);
+        editor.set_placeholder_text("Search", cx);
+        editor.set_text("needle", window, cx);
+        editor
+    });
+    let tree = cx.accessibility_tree(single_line.into());
+    let node = tree
+        .find(gpui::AccessibilityRole::TextInput, "Search")
+        .unwrap();
+    assert_eq!(node.value.as_deref(), Some("needle"));
+
+    _ = single_line.update(cx, |editor, window, cx| {
+        editor.set_text("haystack", window, cx);
+    });
+    let tree = cx.accessibility_tree(single_line.into());
+    let node = tree
+        .find(gpui::AccessibilityRole::TextInput, "Search")
+        .unwrap();
+    assert_eq!(node.value.as_deref(), Some("haystack"));
+
+    // Full editors are only exposed by their role, since their buffers can be arbitrarily large.
+    let full = cx.add_window(|window, cx| {
+        let buffer = MultiBuffer::build_simple("fn main() {}\n", cx);
+        build_editor(buffer, window, cx)
+    });
+    let tree = cx.accessibility_tree(full.into());
+    let node = tree
+        .nodes()
+        .iter()
+        .find(|node| node.role == gpui::AccessibilityRole::MultilineTextInput)
+        .unwrap();
+    assert_eq!(node.value, None);
+}
+
 pub(crate) fn init_test(cx: &mut TestAppContext, f: fn(&mut AllLanguageSettingsContent)) {
     cx.update(|cx| {
         assets::Assets.load_test_fonts(cx);
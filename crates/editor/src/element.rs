@@ -39,15 +39,16 @@ use git::{
     status::FileStatus,
 };
 use gpui::{
-    Action, Along, AnyElement, App, AppContext, AvailableSpace, Axis as ScrollbarAxis, BorderStyle,
-    Bounds, ClickEvent, ContentMask, Context, Corner, Corners, CursorStyle, DispatchPhase, Edges,
-    Element, ElementInputHandler, Entity, Focusable as _, FontId, GlobalElementId, Hitbox,
-    HitboxBehavior, Hsla, InteractiveElement, IntoElement, IsZero, Keystroke, Length,
-    ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad,
-    ParentElement, Pixels, ScrollDelta, ScrollHandle, ScrollWheelEvent, ShapedLine, SharedString,
-    Size, StatefulInteractiveElement, Style, Styled, TextRun, TextStyleRefinement, WeakEntity,
-    Window, anchored, deferred, div, fill, linear_color_stop, linear_gradient, outline, point, px,
-    quad, relative, size, solid_background, transparent_black,
+    AccessibilityNode, AccessibilityRole, Action, Along, AnyElement, App, AppContext,
+    AvailableSpace, Axis as ScrollbarAxis, BorderStyle, Bounds, ClickEvent, ContentMask, Context,
+    Corner, Corners, CursorStyle, DispatchPhase, Edges, Element, ElementInputHandler, Entity,
+    FocusHandle, Focusable as _, FontId, GlobalElementId, Hitbox, HitboxBehavior, Hsla,
+    InteractiveElement, IntoElement, IsZero, Keystroke, Length, ModifiersChangedEvent, MouseButton,
+    MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad, ParentElement, Pixels, ScrollDelta,
+    ScrollHandle, ScrollWheelEvent, ShapedLine, SharedString, Size, StatefulInteractiveElement,
+    Style, Styled, TextRun, TextStyleRefinement, WeakEntity, Window, anchored, deferred, div, fill,
+    linear_color_stop, linear_gradient, outline, point, px, quad, relative, size, solid_background,
+    transparent_black,
 };
 use itertools::Itertools;
 use language::language_settings::{
@@ -6335,6 +6336,59 @@ impl EditorElement {
                     ..cmp::min(range.end.row().next_row(), end_row)
             };
 
+            let line_layout =
+                |row: DisplayRow| &layout.position_map.line_layouts[row.minus(start_row) as usize];
+            if row_range.iter_rows().any(|row| line_layout(row).has_rtl()) {
+                // Selections across a change of text direction cover several disjoint extents of
+                // a line, so paint each of them separately.
+                let origin_x =
+                    layout.content_origin.x - layout.position_map.scroll_pixel_position.x;
+                for row in row_range.iter_rows() {
+                    let line_layout = line_layout(row);
+                    let start_column = if row == range.start.row() {
+                        range.start.column() as usize
+                    } else {
+                        0
+                    };
+                    let end_column = if row == range.end.row() {
+                        range.end.column() as usize
+                    } else {
+                        usize::MAX
+                    };
+                    let mut x_ranges = line_layout.x_ranges_for_range(start_column..end_column);
+                    if row != range.end.row() {
+                        match x_ranges.last_mut() {
+                            Some(last) if last.end == line_layout.width => {
+                                last.end += line_end_overshoot
+                            }
+                            _ => x_ranges
+                                .push(line_layout.width..line_layout.width + line_end_overshoot),
+                        }
+                    }
+
+                    for x_range in x_ranges {
+                        HighlightedRange {
+                            color,
+                            line_height: layout.position_map.line_height,
+                            corner_radius,
+                            start_y: layout.content_origin.y
+                                + row.as_f32() * layout.position_map.line_height
+                                - layout.position_map.scroll_pixel_position.y,
+                            lines: vec![HighlightedRangeLine {
+                                start_x: origin_x + x_range.start,
+                                end_x: origin_x + x_range.end,
+                            }],
+                        }
+                        .paint(
+                            fill,
+                            layout.position_map.text_hitbox.bounds,
+                            window,
+                        );
+                    }
+                }
+                return;
+            }
+
             let highlighted_range = HighlightedRange {
                 color,
                 line_height: layout.position_map.line_height,
@@ -7592,6 +7646,52 @@ impl LineWithInvisibles {
         fragment_start_x
     }
 
+    /// Whether any of this line is laid out right-to-left.
+    pub fn has_rtl(&self) -> bool {
+        self.fragments.iter().any(|fragment| match fragment {
+            LineFragment::Text(shaped_line) => shaped_line.has_rtl(),
+            LineFragment::Element { .. } => false,
+        })
+    }
+
+    /// The horizontal extents covered by the given range of the line, in visual order.
+    pub fn x_ranges_for_range(&self, range: Range<usize>) -> SmallVec<[Range<Pixels>; 1]> {
+        let mut x_ranges = SmallVec::<[Range<Pixels>; 1]>::new();
+        let mut push = |x_range: Range<Pixels>| match x_ranges.last_mut() {
+            Some(last) if last.end == x_range.start => last.end = x_range.end,
+            _ => x_ranges.push(x_range),
+        };
+
+        let mut fragment_start_x = Pixels::ZERO;
+        let mut fragment_start_index = 0;
+        for fragment in &self.fragments {
+            let (fragment_len, fragment_width) = match fragment {
+                LineFragment::Text(shaped_line) => (shaped_line.len, shaped_line.width),
+                LineFragment::Element { len, size, .. } => (*len, size.width),
+            };
+            let fragment_end_index = fragment_start_index + fragment_len;
+            let start = range.start.max(fragment_start_index);
+            let end = range.end.min(fragment_end_index);
+            if start < end {
+                match fragment {
+                    LineFragment::Text(shaped_line) => {
+                        for x_range in shaped_line.x_ranges_for_range(
+                            start - fragment_start_index..end - fragment_start_index,
+                        ) {
+                            push(fragment_start_x + x_range.start..fragment_start_x + x_range.end);
+                        }
+                    }
+                    LineFragment::Element { .. } => {
+                        push(fragment_start_x..fragment_start_x + fragment_width);
+                    }
+                }
+            }
+            fragment_start_x += fragment_width;
+            fragment_start_index = fragment_end_index;
+        }
+        x_ranges
+    }
+
     pub fn index_for_x(&self, x: Pixels) -> Option<usize> {
         let mut fragment_start_x = Pixels::ZERO;
         let mut fragment_start_index = 0;
@@ -7713,6 +7813,37 @@ impl EditorElement {
             Some(self.editor.clone())
         }
     }
+
+    /// Single-line and auto-height editors are exposed with their text as their value,
+    /// full editors only by role, since their buffers can be arbitrarily large.
+    fn accessibility_node(
+        &self,
+        bounds: Bounds<Pixels>,
+        focus_handle: &FocusHandle,
+        cx: &App,
+    ) -> Option<AccessibilityNode> {
+        let editor = self.editor.read(cx);
+        let mut node = match editor.mode() {
+            EditorMode::SingleLine { .. } => {
+                AccessibilityNode::new(AccessibilityRole::TextInput).value(editor.text(cx))
+            }
+            EditorMode::AutoHeight { .. } => {
+                AccessibilityNode::new(AccessibilityRole::MultilineTextInput).value(editor.text(cx))
+            }
+            EditorMode::Full { .. } => {
+                AccessibilityNode::new(AccessibilityRole::MultilineTextInput)
+            }
+            EditorMode::Minimap { .. } => return None,
+        };
+        if let Some(placeholder) = editor.placeholder_text() {
+            node = node.name(placeholder.to_string());
+        }
+        Some(
+            node.id(self.editor.entity_id())
+                .bounds(bounds)
+                .focus_handle(focus_handle),
+        )
+    }
 }
 
 impl Element for EditorElement {
@@ -8952,42 +9083,48 @@ impl Element for EditorElement {
             ..Default::default()
         };
         let rem_size = self.rem_size(cx);
-        window.with_rem_size(rem_size, |window| {
-            window.with_text_style(Some(text_style), |window| {
-                window.with_content_mask(Some(ContentMask { bounds }), |window| {
-                    self.paint_mouse_listeners(layout, window, cx);
-                    self.paint_background(layout, window, cx);
-                    self.paint_indent_guides(layout, window, cx);
+        let accessibility_node = window
+            .accessibility_enabled()
+            .then(|| self.accessibility_node(bounds, &focus_handle, cx))
+            .flatten();
+        window.with_accessibility_node(accessibility_node, |window| {
+            window.with_rem_size(rem_size, |window| {
+                window.with_text_style(Some(text_style), |window| {
+                    window.with_content_mask(Some(ContentMask { bounds }), |window| {
+                        self.paint_mouse_listeners(layout, window, cx);
+                        self.paint_background(layout, window, cx);
+                        self.paint_indent_guides(layout, window, cx);
+
+                        if layout.gutter_hitbox.size.width > Pixels::ZERO {
+                            self.paint_blamed_display_rows(layout, window, cx);
+                            self.paint_line_numbers(layout, window, cx);
+                        }
 
-                    if layout.gutter_hitbox.size.width > Pixels::ZERO {
-                        self.paint_blamed_display_rows(layout, window, cx);
-                        self.paint_line_numbers(layout, window, cx);
-                    }
+                        self.paint_text(layout, window, cx);
 
-                    self.paint_text(layout, window, cx);
+                        if layout.gutter_hitbox.size.width > Pixels::ZERO {
+                            self.paint_gutter_highlights(layout, window, cx);
+                            self.paint_gutter_indicators(layout, window, cx);
+                        }
 
-                    if layout.gutter_hitbox.size.width > Pixels::ZERO {
-                        self.paint_gutter_highlights(layout, window, cx);
-                        self.paint_gutter_indicators(layout, window, cx);
-                    }
+                        if !layout.blocks.is_empty() {
+                            window.with_element_namespace("blocks", |window| {
+                                self.paint_blocks(layout, window, cx);
+                            });
+                        }
 
-                    if !layout.blocks.is_empty() {
                         window.with_element_namespace("blocks", |window| {
-                            self.paint_blocks(layout, window, cx);
+                            if let Some(mut sticky_header) = layout.sticky_buffer_header.take() {
+                                sticky_header.paint(window, cx)
+                            }
                         });
-                    }
 
-                    window.with_element_namespace("blocks", |window| {
-                        if let Some(mut sticky_header) = layout.sticky_buffer_header.take() {
-                            sticky_header.paint(window, cx)
-                        }
+                        self.paint_minimap(layout, window, cx);
+                        self.paint_scrollbars(layout, window, cx);
+                        self.paint_inline_completion_popover(layout, window, cx);
+                        self.paint_mouse_context_menu(layout, window, cx);
                     });
-
-                    self.paint_minimap(layout, window, cx);
-                    self.paint_scrollbars(layout, window, cx);
-                    self.paint_inline_completion_popover(layout, window, cx);
-                    self.paint_mouse_context_menu(layout, window, cx);
-                });
+                })
             })
         })
     }
@@ -322,6 +322,7 @@ impl FollowableItem for Editor {
                 }
                 _ => false,
             },
+            _ => false,
         }
     }
 
@@ -332,7 +333,9 @@ impl FollowableItem for Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
-        let update_view::Variant::Editor(message) = message;
+        let update_view::Variant::Editor(message) = message else {
+            return Task::ready(Err(anyhow!("unexpected view update for an editor")));
+        };
         let project = project.clone();
         cx.spawn_in(window, async move |this, cx| {
             update_editor_from_message(this, project, message, cx).await
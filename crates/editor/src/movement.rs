@@ -3,7 +3,7 @@
 
 use super::{Bias, DisplayPoint, DisplaySnapshot, SelectionGoal, ToDisplayPoint};
 use crate::{DisplayRow, EditorStyle, ToOffset, ToPoint, scroll::ScrollAnchor};
-use gpui::{Pixels, WindowTextSystem};
+use gpui::{Pixels, WindowTextSystem, is_rtl_char};
 use language::Point;
 use multi_buffer::{MultiBufferRow, MultiBufferSnapshot};
 use serde::Deserialize;
@@ -77,6 +77,61 @@ pub fn saturating_right(map: &DisplaySnapshot, mut point: DisplayPoint) -> Displ
     map.clip_point(point, Bias::Right)
 }
 
+/// Returns the point visually to the left of the current point, wrapping to the previous line
+/// at the line's left edge. This matches [`left`], except within right-to-left text, where
+/// moving left advances through the text.
+pub fn visual_left(
+    map: &DisplaySnapshot,
+    point: DisplayPoint,
+    text_layout_details: &TextLayoutDetails,
+) -> DisplayPoint {
+    if !row_has_rtl(map, point.row()) {
+        return left(map, point);
+    }
+    let line = map.layout_row(point.row(), text_layout_details);
+    match line.visual_index_left_of(point.column() as usize) {
+        Some(column) => map.clip_point(DisplayPoint::new(point.row(), column as u32), Bias::Left),
+        None => left(map, DisplayPoint::new(point.row(), 0)),
+    }
+}
+
+/// Returns the point visually to the right of the current point, wrapping to the next line at
+/// the line's right edge. This matches [`right`], except within right-to-left text, where
+/// moving right goes back through the text.
+pub fn visual_right(
+    map: &DisplaySnapshot,
+    point: DisplayPoint,
+    text_layout_details: &TextLayoutDetails,
+) -> DisplayPoint {
+    if !row_has_rtl(map, point.row()) {
+        return right(map, point);
+    }
+    let line = map.layout_row(point.row(), text_layout_details);
+    match line.visual_index_right_of(point.column() as usize) {
+        Some(column) => map.clip_point(DisplayPoint::new(point.row(), column as u32), Bias::Right),
+        None => right(
+            map,
+            DisplayPoint::new(point.row(), map.line_len(point.row())),
+        ),
+    }
+}
+
+/// Whether the given row contains right-to-left text. Rows without any are moved through
+/// logically, without laying them out. Rows with some reuse the layout cached when they were
+/// last painted.
+fn row_has_rtl(map: &DisplaySnapshot, row: DisplayRow) -> bool {
+    for chunk in map.text_chunks(row) {
+        let line = chunk.split('\n').next().unwrap_or_default();
+        if line.chars().any(is_rtl_char) {
+            return true;
+        }
+        if line.len() < chunk.len() {
+            return false;
+        }
+    }
+    false
+}
+
 /// Returns a display point for the preceding displayed line (which might be a soft-wrapped line).
 pub fn up(
     map: &DisplaySnapshot,
@@ -1229,6 +1284,82 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_visual_movement_in_mixed_direction_lines(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| {
+            init_test(cx);
+        });
+
+        let mut cx = EditorTestContext::new(cx).await;
+        let editor = cx.editor.clone();
+        let window = cx.window;
+        _ = cx.update_window(window, |_, window, cx| {
+            let text_layout_details = editor.read(cx).text_layout_details(window);
+
+            // Hebrew letters are two bytes each: א is at column 3 and ב at column 5.
+            let buffer = MultiBuffer::build_simple("ab אב cd\nxy", cx);
+            let display_map = cx.new(|cx| {
+                DisplayMap::new(
+                    buffer,
+                    font("Helvetica"),
+                    px(14.0),
+                    None,
+                    0,
+                    1,
+                    FoldPlaceholder::test(),
+                    DiagnosticSeverity::Warning,
+                    cx,
+                )
+            });
+            let snapshot = display_map.update(cx, |map, cx| map.snapshot(cx));
+
+            let mut point = DisplayPoint::new(DisplayRow(0), 0);
+            let mut rightward = vec![point.column()];
+            for _ in 0..snapshot.line_len(DisplayRow(0)) {
+                point = visual_right(&snapshot, point, &text_layout_details);
+                if point.row() != DisplayRow(0) {
+                    break;
+                }
+                rightward.push(point.column());
+            }
+            // Moving right through the right-to-left run goes back through the text.
+            assert_eq!(rightward, [0, 1, 2, 5, 3, 8, 9, 10]);
+            assert_eq!(point, DisplayPoint::new(DisplayRow(1), 0));
+
+            let mut point = DisplayPoint::new(DisplayRow(0), 10);
+            let mut leftward = vec![point.column()];
+            for _ in 0..snapshot.line_len(DisplayRow(0)) {
+                let next = visual_left(&snapshot, point, &text_layout_details);
+                if next == point {
+                    break;
+                }
+                point = next;
+                leftward.push(point.column());
+            }
+            assert_eq!(leftward, [10, 9, 8, 7, 5, 2, 1, 0]);
+
+            // Left-to-right rows move logically, wrapping onto the mixed row at its edges.
+            assert_eq!(
+                visual_right(
+                    &snapshot,
+                    DisplayPoint::new(DisplayRow(1), 1),
+                    &text_layout_details
+                ),
+                DisplayPoint::new(DisplayRow(1), 2)
+            );
+            assert_eq!(
+                visual_left(
+                    &snapshot,
+                    DisplayPoint::new(DisplayRow(1), 0),
+                    &text_layout_details
+                ),
+                DisplayPoint::new(DisplayRow(0), 10)
+            );
+            assert!(row_has_rtl(&snapshot, DisplayRow(0)));
+            assert!(!row_has_rtl(&snapshot, DisplayRow(1)));
+        });
+    }
+
     fn init_test(cx: &mut gpui::App) {
         let settings_store = SettingsStore::test(cx);
         cx.set_global(settings_store);
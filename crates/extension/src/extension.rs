@@ -2,8 +2,11 @@ pub mod extension_builder;
 mod extension_events;
 mod extension_host_proxy;
 mod extension_manifest;
+#[cfg(any(test, feature = "test-support"))]
+mod fake_extension;
 mod types;
 
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -19,6 +22,8 @@ use task::{SpawnInTerminal, ZedDebugConfig};
 pub use crate::extension_events::*;
 pub use crate::extension_host_proxy::*;
 pub use crate::extension_manifest::*;
+#[cfg(any(test, feature = "test-support"))]
+pub use crate::fake_extension::*;
 pub use crate::types::*;
 
 /// Initializes the `extension` crate.
@@ -44,6 +49,25 @@ pub trait KeyValueStoreDelegate: Send + Sync + 'static {
     fn insert(&self, key: String, docs: String) -> Task<Result<()>>;
 }
 
+/// A buffer open in an editor, exposed to an extension while it runs an [`EditorCommand`].
+pub trait BufferDelegate: Send + Sync + 'static {
+    fn text(&self) -> String;
+    fn language_name(&self) -> Option<String>;
+    fn path(&self) -> Option<String>;
+    fn version(&self) -> u64;
+    fn selections(&self) -> Vec<Selection>;
+    fn apply_edits(&self, edits: Vec<BufferEdit>, cx: &mut App) -> Result<()>;
+    fn set_selections(&self, selections: Vec<Selection>, cx: &mut App) -> Result<()>;
+}
+
+/// The stream that an extension sends the events of a completion to, while it runs
+/// [`Extension::llm_stream_completion`].
+pub trait LlmCompletionStreamDelegate: Send + Sync + 'static {
+    /// Sends an event to the consumer of the completion, returning an error once the
+    /// completion has been cancelled.
+    fn emit(&self, event: LlmCompletionEvent) -> Result<()>;
+}
+
 #[async_trait]
 pub trait Extension: Send + Sync + 'static {
     /// Returns the [`ExtensionManifest`] for this extension.
@@ -165,6 +189,69 @@ pub trait Extension: Send + Sync + 'static {
         locator_name: String,
         config: SpawnInTerminal,
     ) -> Result<DebugRequest>;
+
+    async fn run_editor_command(
+        &self,
+        command_id: Arc<str>,
+        buffer: Arc<dyn BufferDelegate>,
+    ) -> Result<()>;
+
+    async fn format_buffer(
+        &self,
+        formatter_id: Arc<str>,
+        language_name: LanguageName,
+        text: String,
+        options: FormattingOptions,
+    ) -> Result<Vec<BufferEdit>>;
+
+    async fn code_actions(
+        &self,
+        provider_id: Arc<str>,
+        language_name: LanguageName,
+        text: String,
+        range: Range<usize>,
+        diagnostics: Vec<Diagnostic>,
+    ) -> Result<Vec<CodeAction>>;
+
+    async fn worktree_tasks(
+        &self,
+        task_source_id: Arc<str>,
+        worktree: Arc<dyn WorktreeDelegate>,
+    ) -> Result<WorktreeTasks>;
+
+    async fn task_variables(
+        &self,
+        task_source_id: Arc<str>,
+        worktree: Arc<dyn WorktreeDelegate>,
+        path: Option<PathBuf>,
+    ) -> Result<EnvVars>;
+
+    async fn web_search(
+        &self,
+        provider_id: Arc<str>,
+        query: String,
+    ) -> Result<Vec<WebSearchResult>>;
+
+    async fn llm_provider_models(&self, provider_id: Arc<str>) -> Result<Vec<LlmModel>>;
+
+    async fn llm_count_tokens(
+        &self,
+        provider_id: Arc<str>,
+        model_id: Arc<str>,
+        request: LlmCompletionRequest,
+    ) -> Result<u64>;
+
+    async fn llm_stream_completion(
+        &self,
+        provider_id: Arc<str>,
+        model_id: Arc<str>,
+        request: LlmCompletionRequest,
+        stream: Arc<dyn LlmCompletionStreamDelegate>,
+    ) -> Result<()>;
+
+    async fn render_panel(&self, panel_id: Arc<str>) -> Result<PanelView>;
+
+    async fn handle_panel_event(&self, panel_id: Arc<str>, event: PanelEvent) -> Result<PanelView>;
 }
 
 pub fn parse_wasm_extension_version(
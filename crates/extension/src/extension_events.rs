@@ -39,6 +39,8 @@ pub enum Event {
     ExtensionUninstalled(Arc<ExtensionManifest>),
     ExtensionsInstalledChanged,
     ConfigureExtensionRequested(Arc<ExtensionManifest>),
+    /// A dev extension was rebuilt from its sources and reloaded.
+    DevExtensionRebuilt(Arc<ExtensionManifest>),
 }
 
 impl EventEmitter<Event> for ExtensionEvents {}
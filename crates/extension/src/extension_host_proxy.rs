@@ -8,7 +8,9 @@ use language::{BinaryStatus, LanguageMatcher, LanguageName, LoadedLanguage};
 use lsp::LanguageServerName;
 use parking_lot::RwLock;
 
-use crate::{Extension, SlashCommand};
+use crate::{
+    EditorCommand, Extension, LanguageModelProviderManifestEntry, PanelManifestEntry, SlashCommand,
+};
 
 #[derive(Default)]
 struct GlobalExtensionHostProxy(Arc<ExtensionHostProxy>);
@@ -30,6 +32,13 @@ pub struct ExtensionHostProxy {
     context_server_proxy: RwLock<Option<Arc<dyn ExtensionContextServerProxy>>>,
     indexed_docs_provider_proxy: RwLock<Option<Arc<dyn ExtensionIndexedDocsProviderProxy>>>,
     debug_adapter_provider_proxy: RwLock<Option<Arc<dyn ExtensionDebugAdapterProviderProxy>>>,
+    editor_command_proxy: RwLock<Option<Arc<dyn ExtensionEditorCommandProxy>>>,
+    formatter_proxy: RwLock<Option<Arc<dyn ExtensionFormatterProxy>>>,
+    code_action_provider_proxy: RwLock<Option<Arc<dyn ExtensionCodeActionProviderProxy>>>,
+    task_source_proxy: RwLock<Option<Arc<dyn ExtensionTaskSourceProxy>>>,
+    web_search_provider_proxy: RwLock<Option<Arc<dyn ExtensionWebSearchProviderProxy>>>,
+    language_model_provider_proxy: RwLock<Option<Arc<dyn ExtensionLanguageModelProviderProxy>>>,
+    panel_proxy: RwLock<Option<Arc<dyn ExtensionPanelProxy>>>,
 }
 
 impl ExtensionHostProxy {
@@ -56,6 +65,13 @@ impl ExtensionHostProxy {
             context_server_proxy: RwLock::default(),
             indexed_docs_provider_proxy: RwLock::default(),
             debug_adapter_provider_proxy: RwLock::default(),
+            editor_command_proxy: RwLock::default(),
+            formatter_proxy: RwLock::default(),
+            code_action_provider_proxy: RwLock::default(),
+            task_source_proxy: RwLock::default(),
+            web_search_provider_proxy: RwLock::default(),
+            language_model_provider_proxy: RwLock::default(),
+            panel_proxy: RwLock::default(),
         }
     }
 
@@ -100,6 +116,46 @@ impl ExtensionHostProxy {
             .write()
             .replace(Arc::new(proxy));
     }
+
+    pub fn register_editor_command_proxy(&self, proxy: impl ExtensionEditorCommandProxy) {
+        self.editor_command_proxy.write().replace(Arc::new(proxy));
+    }
+
+    pub fn register_formatter_proxy(&self, proxy: impl ExtensionFormatterProxy) {
+        self.formatter_proxy.write().replace(Arc::new(proxy));
+    }
+
+    pub fn register_code_action_provider_proxy(
+        &self,
+        proxy: impl ExtensionCodeActionProviderProxy,
+    ) {
+        self.code_action_provider_proxy
+            .write()
+            .replace(Arc::new(proxy));
+    }
+
+    pub fn register_task_source_proxy(&self, proxy: impl ExtensionTaskSourceProxy) {
+        self.task_source_proxy.write().replace(Arc::new(proxy));
+    }
+
+    pub fn register_web_search_provider_proxy(&self, proxy: impl ExtensionWebSearchProviderProxy) {
+        self.web_search_provider_proxy
+            .write()
+            .replace(Arc::new(proxy));
+    }
+
+    pub fn register_language_model_provider_proxy(
+        &self,
+        proxy: impl ExtensionLanguageModelProviderProxy,
+    ) {
+        self.language_model_provider_proxy
+            .write()
+            .replace(Arc::new(proxy));
+    }
+
+    pub fn register_panel_proxy(&self, proxy: impl ExtensionPanelProxy) {
+        self.panel_proxy.write().replace(Arc::new(proxy));
+    }
 }
 
 pub trait ExtensionThemeProxy: Send + Sync + 'static {
@@ -458,3 +514,294 @@ impl ExtensionDebugAdapterProviderProxy for ExtensionHostProxy {
         proxy.unregister_debug_locator(locator_name)
     }
 }
+
+pub trait ExtensionEditorCommandProxy: Send + Sync + 'static {
+    fn register_editor_command(
+        &self,
+        extension: Arc<dyn Extension>,
+        command: EditorCommand,
+        cx: &mut App,
+    );
+
+    fn unregister_editor_command(&self, extension_id: Arc<str>, command_id: Arc<str>, cx: &mut App);
+}
+
+impl ExtensionEditorCommandProxy for ExtensionHostProxy {
+    fn register_editor_command(
+        &self,
+        extension: Arc<dyn Extension>,
+        command: EditorCommand,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.editor_command_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_editor_command(extension, command, cx)
+    }
+
+    fn unregister_editor_command(
+        &self,
+        extension_id: Arc<str>,
+        command_id: Arc<str>,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.editor_command_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_editor_command(extension_id, command_id, cx)
+    }
+}
+
+pub trait ExtensionFormatterProxy: Send + Sync + 'static {
+    fn register_formatter(
+        &self,
+        extension: Arc<dyn Extension>,
+        formatter_id: Arc<str>,
+        languages: Vec<LanguageName>,
+        cx: &mut App,
+    );
+
+    fn unregister_formatter(&self, extension_id: Arc<str>, formatter_id: Arc<str>, cx: &mut App);
+}
+
+impl ExtensionFormatterProxy for ExtensionHostProxy {
+    fn register_formatter(
+        &self,
+        extension: Arc<dyn Extension>,
+        formatter_id: Arc<str>,
+        languages: Vec<LanguageName>,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.formatter_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_formatter(extension, formatter_id, languages, cx)
+    }
+
+    fn unregister_formatter(&self, extension_id: Arc<str>, formatter_id: Arc<str>, cx: &mut App) {
+        let Some(proxy) = self.formatter_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_formatter(extension_id, formatter_id, cx)
+    }
+}
+
+pub trait ExtensionCodeActionProviderProxy: Send + Sync + 'static {
+    fn register_code_action_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider_id: Arc<str>,
+        languages: Vec<LanguageName>,
+        cx: &mut App,
+    );
+
+    fn unregister_code_action_provider(
+        &self,
+        extension_id: Arc<str>,
+        provider_id: Arc<str>,
+        cx: &mut App,
+    );
+}
+
+impl ExtensionCodeActionProviderProxy for ExtensionHostProxy {
+    fn register_code_action_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider_id: Arc<str>,
+        languages: Vec<LanguageName>,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.code_action_provider_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_code_action_provider(extension, provider_id, languages, cx)
+    }
+
+    fn unregister_code_action_provider(
+        &self,
+        extension_id: Arc<str>,
+        provider_id: Arc<str>,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.code_action_provider_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_code_action_provider(extension_id, provider_id, cx)
+    }
+}
+
+pub trait ExtensionTaskSourceProxy: Send + Sync + 'static {
+    fn register_task_source(
+        &self,
+        extension: Arc<dyn Extension>,
+        task_source_id: Arc<str>,
+        cx: &mut App,
+    );
+
+    fn unregister_task_source(
+        &self,
+        extension_id: Arc<str>,
+        task_source_id: Arc<str>,
+        cx: &mut App,
+    );
+}
+
+impl ExtensionTaskSourceProxy for ExtensionHostProxy {
+    fn register_task_source(
+        &self,
+        extension: Arc<dyn Extension>,
+        task_source_id: Arc<str>,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.task_source_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_task_source(extension, task_source_id, cx)
+    }
+
+    fn unregister_task_source(
+        &self,
+        extension_id: Arc<str>,
+        task_source_id: Arc<str>,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.task_source_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_task_source(extension_id, task_source_id, cx)
+    }
+}
+
+pub trait ExtensionWebSearchProviderProxy: Send + Sync + 'static {
+    fn register_web_search_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider_id: Arc<str>,
+        cx: &mut App,
+    );
+
+    fn unregister_web_search_provider(
+        &self,
+        extension_id: Arc<str>,
+        provider_id: Arc<str>,
+        cx: &mut App,
+    );
+}
+
+impl ExtensionWebSearchProviderProxy for ExtensionHostProxy {
+    fn register_web_search_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider_id: Arc<str>,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.web_search_provider_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_web_search_provider(extension, provider_id, cx)
+    }
+
+    fn unregister_web_search_provider(
+        &self,
+        extension_id: Arc<str>,
+        provider_id: Arc<str>,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.web_search_provider_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_web_search_provider(extension_id, provider_id, cx)
+    }
+}
+
+pub trait ExtensionLanguageModelProviderProxy: Send + Sync + 'static {
+    fn register_language_model_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider_id: Arc<str>,
+        provider: LanguageModelProviderManifestEntry,
+        cx: &mut App,
+    );
+
+    fn unregister_language_model_provider(
+        &self,
+        extension_id: Arc<str>,
+        provider_id: Arc<str>,
+        cx: &mut App,
+    );
+}
+
+impl ExtensionLanguageModelProviderProxy for ExtensionHostProxy {
+    fn register_language_model_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider_id: Arc<str>,
+        provider: LanguageModelProviderManifestEntry,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.language_model_provider_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_language_model_provider(extension, provider_id, provider, cx)
+    }
+
+    fn unregister_language_model_provider(
+        &self,
+        extension_id: Arc<str>,
+        provider_id: Arc<str>,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.language_model_provider_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_language_model_provider(extension_id, provider_id, cx)
+    }
+}
+
+pub trait ExtensionPanelProxy: Send + Sync + 'static {
+    fn register_panel(
+        &self,
+        extension: Arc<dyn Extension>,
+        panel_id: Arc<str>,
+        panel: PanelManifestEntry,
+        cx: &mut App,
+    );
+
+    fn unregister_panel(&self, extension_id: Arc<str>, panel_id: Arc<str>, cx: &mut App);
+}
+
+impl ExtensionPanelProxy for ExtensionHostProxy {
+    fn register_panel(
+        &self,
+        extension: Arc<dyn Extension>,
+        panel_id: Arc<str>,
+        panel: PanelManifestEntry,
+        cx: &mut App,
+    ) {
+        let Some(proxy) = self.panel_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_panel(extension, panel_id, panel, cx)
+    }
+
+    fn unregister_panel(&self, extension_id: Arc<str>, panel_id: Arc<str>, cx: &mut App) {
+        let Some(proxy) = self.panel_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_panel(extension_id, panel_id, cx)
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::{Context as _, Result, bail};
 use collections::{BTreeMap, HashMap};
 use fs::Fs;
+use http_client::Url;
 use language::LanguageName;
 use lsp::LanguageServerName;
 use semantic_version::SemanticVersion;
@@ -91,6 +92,20 @@ pub struct ExtensionManifest {
     pub debug_adapters: BTreeMap<Arc<str>, DebugAdapterManifestEntry>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub debug_locators: BTreeMap<Arc<str>, DebugLocatorManifestEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub editor_commands: BTreeMap<Arc<str>, EditorCommandManifestEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub formatters: BTreeMap<Arc<str>, FormatterManifestEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub code_action_providers: BTreeMap<Arc<str>, CodeActionProviderManifestEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub task_sources: BTreeMap<Arc<str>, TaskSourceManifestEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub web_search_providers: BTreeMap<Arc<str>, WebSearchProviderManifestEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub language_model_providers: BTreeMap<Arc<str>, LanguageModelProviderManifestEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub panels: BTreeMap<Arc<str>, PanelManifestEntry>,
 }
 
 impl ExtensionManifest {
@@ -130,6 +145,76 @@ impl ExtensionManifest {
 
         Ok(())
     }
+
+    pub fn allow_http(&self, desired_url: &str) -> Result<()> {
+        let url =
+            Url::parse(desired_url).with_context(|| format!("invalid URL {desired_url:?}"))?;
+        let desired_host = url
+            .host_str()
+            .with_context(|| format!("URL {desired_url:?} has no host"))?;
+
+        let is_allowed = self.capabilities.iter().any(|capability| match capability {
+            ExtensionCapability::NetworkHttp { hosts } => hosts
+                .iter()
+                .any(|host| host_matches_pattern(desired_host, host)),
+            _ => false,
+        });
+
+        if !is_allowed {
+            bail!(
+                "capability for network:http {desired_host} was not listed in the extension manifest",
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn allow_worktree_read(&self) -> Result<()> {
+        if !self
+            .capabilities
+            .iter()
+            .any(|capability| matches!(capability, ExtensionCapability::FsWorktree))
+        {
+            bail!("capability for fs:worktree was not listed in the extension manifest");
+        }
+
+        Ok(())
+    }
+
+    pub fn allow_work_dir(&self) -> Result<()> {
+        if !self
+            .capabilities
+            .iter()
+            .any(|capability| matches!(capability, ExtensionCapability::FsWorkDir))
+        {
+            bail!("capability for fs:work-dir was not listed in the extension manifest");
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether the extension declared read access to the given environment variable.
+    pub fn allow_env_var(&self, desired_var: &str) -> bool {
+        self.capabilities.iter().any(|capability| match capability {
+            ExtensionCapability::EnvRead { vars } => {
+                vars.iter().any(|var| var == "*" || var == desired_var)
+            }
+            _ => false,
+        })
+    }
+
+    /// Returns the paths outside of the extension's work directory that the extension
+    /// declared access to, along with whether that access is read-only.
+    pub fn allowed_paths(&self) -> impl Iterator<Item = (&Path, bool)> {
+        self.capabilities
+            .iter()
+            .filter_map(|capability| match capability {
+                ExtensionCapability::FsPath { path, read_only } => {
+                    Some((path.as_path(), *read_only))
+                }
+                _ => None,
+            })
+    }
 }
 
 /// A capability for an extension.
@@ -144,6 +229,74 @@ pub enum ExtensionCapability {
         /// If the last element is `**`, then any trailing arguments are allowed.
         args: Vec<String>,
     },
+    #[serde(rename = "network:http")]
+    NetworkHttp {
+        /// The hosts the extension may make HTTP requests to. Use `*` to allow any host,
+        /// or a leading `*.` (e.g., `*.github.com`) to allow any subdomain of a host.
+        hosts: Vec<String>,
+    },
+    /// Read-only access to the files in the user's worktrees.
+    #[serde(rename = "fs:worktree")]
+    FsWorktree,
+    /// Access to the extension's own work directory, e.g., for downloading language servers.
+    #[serde(rename = "fs:work-dir")]
+    FsWorkDir,
+    #[serde(rename = "fs:path")]
+    FsPath {
+        /// The absolute path the extension may access.
+        path: PathBuf,
+        /// Whether the extension may only read from the path.
+        #[serde(default)]
+        read_only: bool,
+    },
+    #[serde(rename = "env:read")]
+    EnvRead {
+        /// The environment variables the extension may read. Use `*` to allow any variable.
+        vars: Vec<String>,
+    },
+}
+
+impl fmt::Display for ExtensionCapability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ProcessExec { command, args } => {
+                write!(f, "Run `{command}")?;
+                for arg in args {
+                    write!(f, " {arg}")?;
+                }
+                write!(f, "`")
+            }
+            Self::NetworkHttp { hosts } => {
+                write!(f, "Make HTTP requests to {}", hosts.join(", "))
+            }
+            Self::FsWorktree => write!(f, "Read files in your worktrees"),
+            Self::FsWorkDir => write!(f, "Write to its own work directory"),
+            Self::FsPath { path, read_only } => {
+                if *read_only {
+                    write!(f, "Read files in {}", path.display())
+                } else {
+                    write!(f, "Read and write files in {}", path.display())
+                }
+            }
+            Self::EnvRead { vars } => {
+                write!(f, "Read the environment variables {}", vars.join(", "))
+            }
+        }
+    }
+}
+
+fn host_matches_pattern(host: &str, pattern: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+
+    if let Some(domain) = pattern.strip_prefix("*.") {
+        return host
+            .strip_suffix(domain)
+            .is_some_and(|subdomain| subdomain.ends_with('.'));
+    }
+
+    host.eq_ignore_ascii_case(pattern)
 }
 
 #[derive(Clone, Default, PartialEq, Eq, Debug, Deserialize, Serialize)]
@@ -218,6 +371,44 @@ pub struct DebugAdapterManifestEntry {
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
 pub struct DebugLocatorManifestEntry {}
 
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct EditorCommandManifestEntry {
+    pub title: String,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct FormatterManifestEntry {
+    /// The list of languages this formatter can format.
+    pub languages: Vec<LanguageName>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct CodeActionProviderManifestEntry {
+    /// The list of languages this code action provider offers code actions for.
+    pub languages: Vec<LanguageName>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct TaskSourceManifestEntry {}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct WebSearchProviderManifestEntry {}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct LanguageModelProviderManifestEntry {
+    /// The name of the provider, as shown to the user.
+    pub name: String,
+    /// Whether the user must enter a credential, such as an API key, to use the provider.
+    #[serde(default)]
+    pub requires_credential: bool,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct PanelManifestEntry {
+    /// The title of the panel, as shown to the user.
+    pub title: String,
+}
+
 impl ExtensionManifest {
     pub async fn load(fs: Arc<dyn Fs>, extension_dir: &Path) -> Result<Self> {
         let extension_name = extension_dir
@@ -288,6 +479,13 @@ fn manifest_from_old_manifest(
         capabilities: Vec::new(),
         debug_adapters: Default::default(),
         debug_locators: Default::default(),
+        editor_commands: Default::default(),
+        formatters: Default::default(),
+        code_action_providers: Default::default(),
+        task_sources: Default::default(),
+        web_search_providers: Default::default(),
+        language_model_providers: Default::default(),
+        panels: Default::default(),
     }
 }
 
@@ -317,6 +515,13 @@ mod tests {
             capabilities: vec![],
             debug_adapters: Default::default(),
             debug_locators: Default::default(),
+            editor_commands: Default::default(),
+            formatters: Default::default(),
+            code_action_providers: Default::default(),
+            task_sources: Default::default(),
+            web_search_providers: Default::default(),
+            language_model_providers: Default::default(),
+            panels: Default::default(),
         }
     }
 
@@ -395,4 +600,103 @@ mod tests {
         );
         assert!(manifest.allow_exec("docker", &["ps"]).is_err()); // wrong first arg
     }
+
+    #[test]
+    fn test_allow_http() {
+        let manifest = ExtensionManifest {
+            capabilities: vec![ExtensionCapability::NetworkHttp {
+                hosts: vec!["api.example.com".to_string(), "*.github.com".to_string()],
+            }],
+            ..extension_manifest()
+        };
+
+        assert!(manifest.allow_http("https://api.example.com/v1").is_ok());
+        assert!(manifest.allow_http("https://example.com").is_err());
+        assert!(
+            manifest
+                .allow_http("https://objects.github.com/release.tar.gz")
+                .is_ok()
+        );
+        assert!(manifest.allow_http("https://github.com").is_err()); // not a subdomain
+        assert!(manifest.allow_http("https://notgithub.com").is_err());
+        assert!(manifest.allow_http("not a url").is_err());
+
+        let manifest = ExtensionManifest {
+            capabilities: vec![ExtensionCapability::NetworkHttp {
+                hosts: vec!["*".to_string()],
+            }],
+            ..extension_manifest()
+        };
+        assert!(manifest.allow_http("https://anything.dev").is_ok());
+    }
+
+    #[test]
+    fn test_allow_fs_and_env() {
+        let manifest = ExtensionManifest {
+            capabilities: vec![
+                ExtensionCapability::FsWorkDir,
+                ExtensionCapability::EnvRead {
+                    vars: vec!["PATH".to_string()],
+                },
+            ],
+            ..extension_manifest()
+        };
+
+        assert!(manifest.allow_work_dir().is_ok());
+        assert!(manifest.allow_worktree_read().is_err());
+        assert!(manifest.allow_env_var("PATH"));
+        assert!(!manifest.allow_env_var("HOME"));
+        assert!(extension_manifest().allowed_paths().next().is_none());
+    }
+
+    #[test]
+    fn test_deserialize_capabilities() {
+        let manifest: ExtensionManifest = toml::from_str(
+            r#"
+            id = "test"
+            name = "Test"
+            version = "1.0.0"
+            schema_version = 1
+
+            [[capabilities]]
+            kind = "network:http"
+            hosts = ["api.example.com"]
+
+            [[capabilities]]
+            kind = "fs:worktree"
+
+            [[capabilities]]
+            kind = "fs:path"
+            path = "/opt/tools"
+            read_only = true
+
+            [[capabilities]]
+            kind = "env:read"
+            vars = ["*"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest.capabilities,
+            vec![
+                ExtensionCapability::NetworkHttp {
+                    hosts: vec!["api.example.com".to_string()],
+                },
+                ExtensionCapability::FsWorktree,
+                ExtensionCapability::FsPath {
+                    path: PathBuf::from("/opt/tools"),
+                    read_only: true,
+                },
+                ExtensionCapability::EnvRead {
+                    vars: vec!["*".to_string()],
+                },
+            ]
+        );
+        assert!(manifest.allow_worktree_read().is_ok());
+        assert_eq!(
+            manifest.allowed_paths().collect::<Vec<_>>(),
+            vec![(Path::new("/opt/tools"), true)]
+        );
+    }
 }
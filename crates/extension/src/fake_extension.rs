@@ -0,0 +1,499 @@
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use ::lsp::LanguageServerName;
+use anyhow::{Result, bail};
+use async_trait::async_trait;
+use futures::FutureExt as _;
+use futures::future::BoxFuture;
+use language::LanguageName;
+use parking_lot::Mutex;
+use task::{SpawnInTerminal, ZedDebugConfig};
+
+use crate::*;
+
+type LanguageServerCommandHandler =
+    Box<dyn Fn(LanguageServerName, LanguageName) -> Result<Command> + Send + Sync>;
+type LabelsForCompletionsHandler = Box<
+    dyn Fn(LanguageServerName, Vec<Completion>) -> Result<Vec<Option<CodeLabel>>> + Send + Sync,
+>;
+type RunSlashCommandHandler =
+    Box<dyn Fn(SlashCommand, Vec<String>) -> Result<SlashCommandOutput> + Send + Sync>;
+type FormatBufferHandler =
+    Box<dyn Fn(Arc<str>, LanguageName, String) -> Result<Vec<BufferEdit>> + Send + Sync>;
+type CodeActionsHandler = Box<
+    dyn Fn(Arc<str>, LanguageName, String, Range<usize>) -> Result<Vec<CodeAction>> + Send + Sync,
+>;
+type WorktreeTasksHandler = Box<dyn Fn(Arc<str>) -> Result<WorktreeTasks> + Send + Sync>;
+type TaskVariablesHandler = Box<dyn Fn(Arc<str>, Option<PathBuf>) -> Result<EnvVars> + Send + Sync>;
+type LlmProviderModelsHandler = Box<dyn Fn(Arc<str>) -> Result<Vec<LlmModel>> + Send + Sync>;
+type LlmStreamCompletionHandler = Box<
+    dyn Fn(
+            Arc<str>,
+            Arc<str>,
+            LlmCompletionRequest,
+            Arc<dyn LlmCompletionStreamDelegate>,
+        ) -> Result<()>
+        + Send
+        + Sync,
+>;
+type RenderPanelHandler =
+    Box<dyn Fn(Arc<str>) -> BoxFuture<'static, Result<PanelView>> + Send + Sync>;
+type PanelEventHandler =
+    Box<dyn Fn(Arc<str>, PanelEvent) -> BoxFuture<'static, Result<PanelView>> + Send + Sync>;
+
+/// An extension for tests, whose language servers, slash commands, formatters, code action
+/// providers, task sources, language model providers and panels are implemented by closures
+/// instead of WebAssembly.
+///
+/// Every other part of the extension API fails when it is called.
+pub struct FakeExtension {
+    manifest: Arc<ExtensionManifest>,
+    language_server_command: Mutex<Option<LanguageServerCommandHandler>>,
+    labels_for_completions: Mutex<Option<LabelsForCompletionsHandler>>,
+    run_slash_command: Mutex<Option<RunSlashCommandHandler>>,
+    format_buffer: Mutex<Option<FormatBufferHandler>>,
+    code_actions: Mutex<Option<CodeActionsHandler>>,
+    worktree_tasks: Mutex<Option<WorktreeTasksHandler>>,
+    task_variables: Mutex<Option<TaskVariablesHandler>>,
+    llm_provider_models: Mutex<Option<LlmProviderModelsHandler>>,
+    llm_stream_completion: Mutex<Option<LlmStreamCompletionHandler>>,
+    render_panel: Mutex<Option<RenderPanelHandler>>,
+    handle_panel_event: Mutex<Option<PanelEventHandler>>,
+}
+
+impl FakeExtension {
+    pub fn new(id: &str) -> Arc<Self> {
+        Arc::new(Self {
+            manifest: Arc::new(ExtensionManifest {
+                id: id.into(),
+                name: id.to_string(),
+                version: "1.0.0".into(),
+                schema_version: SchemaVersion(1),
+                description: None,
+                repository: None,
+                authors: Vec::new(),
+                lib: Default::default(),
+                themes: Vec::new(),
+                icon_themes: Vec::new(),
+                languages: Vec::new(),
+                grammars: Default::default(),
+                language_servers: Default::default(),
+                context_servers: Default::default(),
+                slash_commands: Default::default(),
+                indexed_docs_providers: Default::default(),
+                snippets: None,
+                capabilities: Vec::new(),
+                debug_adapters: Default::default(),
+                debug_locators: Default::default(),
+                editor_commands: Default::default(),
+                formatters: Default::default(),
+                code_action_providers: Default::default(),
+                task_sources: Default::default(),
+                web_search_providers: Default::default(),
+                language_model_providers: Default::default(),
+                panels: Default::default(),
+            }),
+            language_server_command: Mutex::default(),
+            labels_for_completions: Mutex::default(),
+            run_slash_command: Mutex::default(),
+            format_buffer: Mutex::default(),
+            code_actions: Mutex::default(),
+            worktree_tasks: Mutex::default(),
+            task_variables: Mutex::default(),
+            llm_provider_models: Mutex::default(),
+            llm_stream_completion: Mutex::default(),
+            render_panel: Mutex::default(),
+            handle_panel_event: Mutex::default(),
+        })
+    }
+
+    /// Handles requests for the command to start a language server, given the language
+    /// server's name and the language it is started for.
+    pub fn on_language_server_command(
+        &self,
+        handler: impl Fn(LanguageServerName, LanguageName) -> Result<Command> + Send + Sync + 'static,
+    ) {
+        *self.language_server_command.lock() = Some(Box::new(handler));
+    }
+
+    /// Handles requests to label a language server's completions.
+    pub fn on_labels_for_completions(
+        &self,
+        handler: impl Fn(LanguageServerName, Vec<Completion>) -> Result<Vec<Option<CodeLabel>>>
+        + Send
+        + Sync
+        + 'static,
+    ) {
+        *self.labels_for_completions.lock() = Some(Box::new(handler));
+    }
+
+    /// Handles requests to run a slash command, given the command and its arguments.
+    pub fn on_run_slash_command(
+        &self,
+        handler: impl Fn(SlashCommand, Vec<String>) -> Result<SlashCommandOutput>
+        + Send
+        + Sync
+        + 'static,
+    ) {
+        *self.run_slash_command.lock() = Some(Box::new(handler));
+    }
+
+    /// Handles requests to format a buffer, given the formatter's ID, the buffer's language
+    /// and its text.
+    pub fn on_format_buffer(
+        &self,
+        handler: impl Fn(Arc<str>, LanguageName, String) -> Result<Vec<BufferEdit>>
+        + Send
+        + Sync
+        + 'static,
+    ) {
+        *self.format_buffer.lock() = Some(Box::new(handler));
+    }
+
+    /// Handles requests for code actions, given the provider's ID, the buffer's language, its
+    /// text and the range that code actions are requested for.
+    pub fn on_code_actions(
+        &self,
+        handler: impl Fn(Arc<str>, LanguageName, String, Range<usize>) -> Result<Vec<CodeAction>>
+        + Send
+        + Sync
+        + 'static,
+    ) {
+        *self.code_actions.lock() = Some(Box::new(handler));
+    }
+
+    /// Handles requests for the tasks of a worktree, given the task source's ID.
+    pub fn on_worktree_tasks(
+        &self,
+        handler: impl Fn(Arc<str>) -> Result<WorktreeTasks> + Send + Sync + 'static,
+    ) {
+        *self.worktree_tasks.lock() = Some(Box::new(handler));
+    }
+
+    /// Handles requests for custom task variables, given the task source's ID and the
+    /// worktree-relative path of the file that tasks are run for.
+    pub fn on_task_variables(
+        &self,
+        handler: impl Fn(Arc<str>, Option<PathBuf>) -> Result<EnvVars> + Send + Sync + 'static,
+    ) {
+        *self.task_variables.lock() = Some(Box::new(handler));
+    }
+
+    /// Handles requests for the models of a language model provider, given the provider's ID.
+    pub fn on_llm_provider_models(
+        &self,
+        handler: impl Fn(Arc<str>) -> Result<Vec<LlmModel>> + Send + Sync + 'static,
+    ) {
+        *self.llm_provider_models.lock() = Some(Box::new(handler));
+    }
+
+    /// Handles requests to stream a completion, given the provider's ID, the model's ID, the
+    /// request and the stream to emit the completion's events to.
+    pub fn on_llm_stream_completion(
+        &self,
+        handler: impl Fn(
+            Arc<str>,
+            Arc<str>,
+            LlmCompletionRequest,
+            Arc<dyn LlmCompletionStreamDelegate>,
+        ) -> Result<()>
+        + Send
+        + Sync
+        + 'static,
+    ) {
+        *self.llm_stream_completion.lock() = Some(Box::new(handler));
+    }
+
+    /// Handles requests to render a panel, given the panel's ID.
+    ///
+    /// The view is returned as a future, so that tests can control when it arrives.
+    pub fn on_render_panel<F>(&self, handler: impl Fn(Arc<str>) -> F + Send + Sync + 'static)
+    where
+        F: Future<Output = Result<PanelView>> + Send + 'static,
+    {
+        *self.render_panel.lock() = Some(Box::new(move |panel_id| handler(panel_id).boxed()));
+    }
+
+    /// Handles the events of a panel, given the panel's ID and the event.
+    ///
+    /// The updated view is returned as a future, so that tests can control when it arrives.
+    pub fn on_handle_panel_event<F>(
+        &self,
+        handler: impl Fn(Arc<str>, PanelEvent) -> F + Send + Sync + 'static,
+    ) where
+        F: Future<Output = Result<PanelView>> + Send + 'static,
+    {
+        *self.handle_panel_event.lock() = Some(Box::new(move |panel_id, event| {
+            handler(panel_id, event).boxed()
+        }));
+    }
+}
+
+#[async_trait]
+impl Extension for FakeExtension {
+    fn manifest(&self) -> Arc<ExtensionManifest> {
+        self.manifest.clone()
+    }
+
+    fn work_dir(&self) -> Arc<Path> {
+        Path::new("/fake-extensions")
+            .join(&*self.manifest.id)
+            .into()
+    }
+
+    async fn language_server_command(
+        &self,
+        language_server_id: LanguageServerName,
+        language_name: LanguageName,
+        _: Arc<dyn WorktreeDelegate>,
+    ) -> Result<Command> {
+        match self.language_server_command.lock().as_ref() {
+            Some(handler) => handler(language_server_id, language_name),
+            None => bail!("fake extension has no language servers"),
+        }
+    }
+
+    async fn language_server_initialization_options(
+        &self,
+        _: LanguageServerName,
+        _: LanguageName,
+        _: Arc<dyn WorktreeDelegate>,
+    ) -> Result<Option<String>> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn language_server_workspace_configuration(
+        &self,
+        _: LanguageServerName,
+        _: Arc<dyn WorktreeDelegate>,
+    ) -> Result<Option<String>> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn language_server_additional_initialization_options(
+        &self,
+        _: LanguageServerName,
+        _: LanguageServerName,
+        _: Arc<dyn WorktreeDelegate>,
+    ) -> Result<Option<String>> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn language_server_additional_workspace_configuration(
+        &self,
+        _: LanguageServerName,
+        _: LanguageServerName,
+        _: Arc<dyn WorktreeDelegate>,
+    ) -> Result<Option<String>> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn labels_for_completions(
+        &self,
+        language_server_id: LanguageServerName,
+        completions: Vec<Completion>,
+    ) -> Result<Vec<Option<CodeLabel>>> {
+        match self.labels_for_completions.lock().as_ref() {
+            Some(handler) => handler(language_server_id, completions),
+            None => bail!("fake extension has no language servers"),
+        }
+    }
+
+    async fn labels_for_symbols(
+        &self,
+        _: LanguageServerName,
+        _: Vec<Symbol>,
+    ) -> Result<Vec<Option<CodeLabel>>> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn complete_slash_command_argument(
+        &self,
+        _: SlashCommand,
+        _: Vec<String>,
+    ) -> Result<Vec<SlashCommandArgumentCompletion>> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn run_slash_command(
+        &self,
+        command: SlashCommand,
+        arguments: Vec<String>,
+        _: Option<Arc<dyn WorktreeDelegate>>,
+    ) -> Result<SlashCommandOutput> {
+        match self.run_slash_command.lock().as_ref() {
+            Some(handler) => handler(command, arguments),
+            None => bail!("fake extension has no slash commands"),
+        }
+    }
+
+    async fn context_server_command(
+        &self,
+        _: Arc<str>,
+        _: Arc<dyn ProjectDelegate>,
+    ) -> Result<Command> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn context_server_configuration(
+        &self,
+        _: Arc<str>,
+        _: Arc<dyn ProjectDelegate>,
+    ) -> Result<Option<ContextServerConfiguration>> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn suggest_docs_packages(&self, _: Arc<str>) -> Result<Vec<String>> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn index_docs(
+        &self,
+        _: Arc<str>,
+        _: Arc<str>,
+        _: Arc<dyn KeyValueStoreDelegate>,
+    ) -> Result<()> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn get_dap_binary(
+        &self,
+        _: Arc<str>,
+        _: DebugTaskDefinition,
+        _: Option<PathBuf>,
+        _: Arc<dyn WorktreeDelegate>,
+    ) -> Result<DebugAdapterBinary> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn dap_request_kind(
+        &self,
+        _: Arc<str>,
+        _: serde_json::Value,
+    ) -> Result<StartDebuggingRequestArgumentsRequest> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn dap_config_to_scenario(&self, _: ZedDebugConfig) -> Result<DebugScenario> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn dap_locator_create_scenario(
+        &self,
+        _: String,
+        _: BuildTaskTemplate,
+        _: String,
+        _: String,
+    ) -> Result<Option<DebugScenario>> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn run_dap_locator(&self, _: String, _: SpawnInTerminal) -> Result<DebugRequest> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn run_editor_command(&self, _: Arc<str>, _: Arc<dyn BufferDelegate>) -> Result<()> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn format_buffer(
+        &self,
+        formatter_id: Arc<str>,
+        language_name: LanguageName,
+        text: String,
+        _: FormattingOptions,
+    ) -> Result<Vec<BufferEdit>> {
+        match self.format_buffer.lock().as_ref() {
+            Some(handler) => handler(formatter_id, language_name, text),
+            None => bail!("fake extension has no formatters"),
+        }
+    }
+
+    async fn code_actions(
+        &self,
+        provider_id: Arc<str>,
+        language_name: LanguageName,
+        text: String,
+        range: Range<usize>,
+        _: Vec<Diagnostic>,
+    ) -> Result<Vec<CodeAction>> {
+        match self.code_actions.lock().as_ref() {
+            Some(handler) => handler(provider_id, language_name, text, range),
+            None => bail!("fake extension has no code action providers"),
+        }
+    }
+
+    async fn worktree_tasks(
+        &self,
+        task_source_id: Arc<str>,
+        _: Arc<dyn WorktreeDelegate>,
+    ) -> Result<WorktreeTasks> {
+        match self.worktree_tasks.lock().as_ref() {
+            Some(handler) => handler(task_source_id),
+            None => bail!("fake extension has no task sources"),
+        }
+    }
+
+    async fn task_variables(
+        &self,
+        task_source_id: Arc<str>,
+        _: Arc<dyn WorktreeDelegate>,
+        path: Option<PathBuf>,
+    ) -> Result<EnvVars> {
+        match self.task_variables.lock().as_ref() {
+            Some(handler) => handler(task_source_id, path),
+            None => bail!("fake extension has no task sources"),
+        }
+    }
+
+    async fn web_search(&self, _: Arc<str>, _: String) -> Result<Vec<WebSearchResult>> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn llm_provider_models(&self, provider_id: Arc<str>) -> Result<Vec<LlmModel>> {
+        match self.llm_provider_models.lock().as_ref() {
+            Some(handler) => handler(provider_id),
+            None => bail!("fake extension has no language model providers"),
+        }
+    }
+
+    async fn llm_count_tokens(
+        &self,
+        _: Arc<str>,
+        _: Arc<str>,
+        _: LlmCompletionRequest,
+    ) -> Result<u64> {
+        bail!("not supported by fake extensions")
+    }
+
+    async fn llm_stream_completion(
+        &self,
+        provider_id: Arc<str>,
+        model_id: Arc<str>,
+        request: LlmCompletionRequest,
+        stream: Arc<dyn LlmCompletionStreamDelegate>,
+    ) -> Result<()> {
+        match self.llm_stream_completion.lock().as_ref() {
+            Some(handler) => handler(provider_id, model_id, request, stream),
+            None => bail!("fake extension has no language model providers"),
+        }
+    }
+
+    async fn render_panel(&self, panel_id: Arc<str>) -> Result<PanelView> {
+        let view = match self.render_panel.lock().as_ref() {
+            Some(handler) => handler(panel_id),
+            None => bail!("fake extension has no panels"),
+        };
+        view.await
+    }
+
+    async fn handle_panel_event(&self, panel_id: Arc<str>, event: PanelEvent) -> Result<PanelView> {
+        let view = match self.handle_panel_event.lock().as_ref() {
+            Some(handler) => handler(panel_id, event),
+            None => bail!("fake extension has no panels"),
+        };
+        view.await
+    }
+}
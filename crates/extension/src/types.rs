@@ -1,16 +1,30 @@
+mod code_action;
 mod context_server;
 mod dap;
+mod editor;
+mod formatting;
+mod llm;
 mod lsp;
+mod panel;
 mod slash_command;
+mod task;
+mod web_search;
 
 use std::ops::Range;
 
 use util::redact::should_redact;
 
+pub use code_action::*;
 pub use context_server::*;
 pub use dap::*;
+pub use editor::*;
+pub use formatting::*;
+pub use llm::*;
 pub use lsp::*;
+pub use panel::*;
 pub use slash_command::*;
+pub use task::*;
+pub use web_search::*;
 
 /// A list of environment variables.
 pub type EnvVars = Vec<(String, String)>;
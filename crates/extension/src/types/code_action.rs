@@ -0,0 +1,38 @@
+use std::ops::Range;
+
+use crate::BufferEdit;
+
+/// The severity of a [`Diagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Information,
+    Hint,
+}
+
+/// A diagnostic reported for a buffer, passed to an extension when requesting code actions.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    /// The byte range in the buffer that the diagnostic applies to.
+    pub range: Range<usize>,
+    /// The severity of the diagnostic.
+    pub severity: DiagnosticSeverity,
+    /// The message of the diagnostic.
+    pub message: String,
+    /// The code of the diagnostic, such as the name of the lint that produced it.
+    pub code: Option<String>,
+    /// The name of the tool that produced the diagnostic.
+    pub source: Option<String>,
+}
+
+/// A code action provided by an extension.
+#[derive(Debug, Clone)]
+pub struct CodeAction {
+    /// The title of the code action, as shown in the code actions menu.
+    pub title: String,
+    /// The kind of the code action, such as `quickfix` or `source.fixAll`.
+    pub kind: Option<String>,
+    /// The edits to apply to the buffer, relative to the text the code action was requested for.
+    pub edits: Vec<BufferEdit>,
+}
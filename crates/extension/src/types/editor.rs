@@ -0,0 +1,56 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use anyhow::{Result, ensure};
+
+/// A command, provided by an extension, that operates on the buffer in the active editor.
+#[derive(Debug, Clone)]
+pub struct EditorCommand {
+    /// The ID of the command, as declared in the extension manifest.
+    pub id: Arc<str>,
+    /// The title of the command, as shown in the command palette.
+    pub title: String,
+}
+
+/// An edit to apply to a buffer.
+#[derive(Debug, Clone)]
+pub struct BufferEdit {
+    /// The byte range in the buffer to replace.
+    pub range: Range<usize>,
+    /// The text to insert in place of the range.
+    pub new_text: String,
+}
+
+/// A selection in an editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    /// The byte range covered by the selection.
+    pub range: Range<usize>,
+    /// Whether the cursor is at the start of the range, rather than the end.
+    pub reversed: bool,
+}
+
+/// Sorts the given edits by their position, and ensures that they can be applied to the given text.
+///
+/// Edits must lie within the text, start and end on character boundaries, and must not overlap.
+pub fn sort_and_validate_buffer_edits(edits: &mut [BufferEdit], text: &str) -> Result<()> {
+    edits.sort_by_key(|edit| edit.range.start);
+    let mut previous_end = 0;
+    for edit in edits.iter() {
+        let range = &edit.range;
+        ensure!(
+            range.start <= range.end && range.end <= text.len(),
+            "edit range {range:?} is out of bounds"
+        );
+        ensure!(
+            text.is_char_boundary(range.start) && text.is_char_boundary(range.end),
+            "edit range {range:?} is not on a character boundary"
+        );
+        ensure!(
+            previous_end <= range.start,
+            "edit range {range:?} overlaps the previous edit"
+        );
+        previous_end = range.end;
+    }
+    Ok(())
+}
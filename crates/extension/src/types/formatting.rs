@@ -0,0 +1,8 @@
+/// Options that control how a buffer is formatted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormattingOptions {
+    /// The number of columns a tab character occupies.
+    pub tab_size: u32,
+    /// Whether to indent using spaces, rather than tabs.
+    pub insert_spaces: bool,
+}
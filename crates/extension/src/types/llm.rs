@@ -0,0 +1,146 @@
+/// A language model offered by an extension's language model provider.
+#[derive(Debug, Clone)]
+pub struct LlmModel {
+    /// The ID of the model, which is passed back to the extension with each request.
+    pub id: String,
+    /// The name of the model shown to the user.
+    pub name: String,
+    /// The maximum number of tokens in a request to the model.
+    pub max_token_count: u64,
+    /// The maximum number of tokens the model may generate in a completion.
+    pub max_output_tokens: Option<u64>,
+    /// The features that the model supports.
+    pub capabilities: LlmModelCapabilities,
+    /// Whether this is the provider's default model.
+    pub is_default: bool,
+    /// Whether this is the provider's default model for quick tasks.
+    pub is_default_fast: bool,
+}
+
+/// The features that a language model supports.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LlmModelCapabilities {
+    pub supports_images: bool,
+    pub supports_tools: bool,
+    pub supports_tool_choice: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LlmRole {
+    User,
+    Assistant,
+    System,
+}
+
+/// An image sent to a language model.
+#[derive(Debug, Clone)]
+pub struct LlmImage {
+    /// The base64-encoded PNG image.
+    pub source: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct LlmThinking {
+    pub text: String,
+    pub signature: Option<String>,
+}
+
+/// A call to a tool made by a language model.
+#[derive(Debug, Clone)]
+pub struct LlmToolUse {
+    pub id: String,
+    pub name: String,
+    /// The input to the tool, as JSON.
+    pub input: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum LlmToolResultContent {
+    Text(String),
+    Image(LlmImage),
+}
+
+#[derive(Debug, Clone)]
+pub struct LlmToolResult {
+    pub tool_use_id: String,
+    pub tool_name: String,
+    pub is_error: bool,
+    pub content: LlmToolResultContent,
+}
+
+#[derive(Debug, Clone)]
+pub enum LlmMessageContent {
+    Text(String),
+    Thinking(LlmThinking),
+    RedactedThinking(String),
+    Image(LlmImage),
+    ToolUse(LlmToolUse),
+    ToolResult(LlmToolResult),
+}
+
+#[derive(Debug, Clone)]
+pub struct LlmMessage {
+    pub role: LlmRole,
+    pub content: Vec<LlmMessageContent>,
+    pub cache: bool,
+}
+
+/// A tool that a language model may use.
+#[derive(Debug, Clone)]
+pub struct LlmTool {
+    pub name: String,
+    pub description: String,
+    /// The JSON schema of the tool's input.
+    pub input_schema: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LlmToolChoice {
+    Auto,
+    Any,
+    None,
+}
+
+/// A request for a completion from an extension's language model.
+#[derive(Debug, Clone)]
+pub struct LlmCompletionRequest {
+    pub messages: Vec<LlmMessage>,
+    pub tools: Vec<LlmTool>,
+    pub tool_choice: Option<LlmToolChoice>,
+    pub stop: Vec<String>,
+    pub temperature: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LlmStopReason {
+    EndTurn,
+    MaxTokens,
+    ToolUse,
+    Refusal,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LlmTokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_input_tokens: u64,
+    pub cache_read_input_tokens: u64,
+}
+
+/// An event in a completion streamed by an extension's language model.
+#[derive(Debug, Clone)]
+pub enum LlmCompletionEvent {
+    Text(String),
+    Thinking(LlmThinking),
+    ToolUse(LlmToolUse),
+    Usage(LlmTokenUsage),
+    Stop(LlmStopReason),
+}
+
+/// Returns the URL under which the credential for an extension's language model provider
+/// is stored.
+pub fn llm_provider_credentials_url(extension_id: &str, provider_id: &str) -> String {
+    format!("zed-extension://{extension_id}/language-model-providers/{provider_id}")
+}
@@ -0,0 +1,181 @@
+use anyhow::{Result, ensure};
+
+/// The contents of a panel provided by an extension.
+///
+/// The elements are stored in a flat list, and refer to their children by their index in
+/// that list. The first element is the root of the view.
+#[derive(Debug, Clone, Default)]
+pub struct PanelView {
+    pub elements: Vec<PanelElement>,
+}
+
+/// An element of a panel provided by an extension.
+#[derive(Debug, Clone)]
+pub enum PanelElement {
+    Column(Vec<u32>),
+    Row(Vec<u32>),
+    Label(PanelLabel),
+    Button(PanelButton),
+    TextInput(PanelTextInput),
+    ListItem(PanelListItem),
+    Divider,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PanelColor {
+    #[default]
+    Default,
+    Muted,
+    Accent,
+    Success,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct PanelLabel {
+    pub text: String,
+    pub color: PanelColor,
+}
+
+#[derive(Debug, Clone)]
+pub struct PanelButton {
+    pub id: String,
+    pub label: String,
+    pub disabled: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct PanelTextInput {
+    pub id: String,
+    pub placeholder: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct PanelListItem {
+    pub id: String,
+    pub label: String,
+    pub detail: Option<String>,
+    pub color: PanelColor,
+    pub selected: bool,
+    /// The indices of the items nested under this item.
+    pub children: Vec<u32>,
+    pub expanded: bool,
+}
+
+/// An interaction with a panel provided by an extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PanelEvent {
+    ButtonClicked(String),
+    ListItemClicked(String),
+    ListItemToggled { id: String, expanded: bool },
+    TextInputSubmitted { id: String, value: String },
+}
+
+impl PanelElement {
+    /// Returns the indices of this element's children.
+    pub fn children(&self) -> &[u32] {
+        match self {
+            PanelElement::Column(children) | PanelElement::Row(children) => children,
+            PanelElement::ListItem(item) => &item.children,
+            PanelElement::Label(_)
+            | PanelElement::Button(_)
+            | PanelElement::TextInput(_)
+            | PanelElement::Divider => &[],
+        }
+    }
+}
+
+impl PanelView {
+    /// Ensures that the view is a tree rooted at its first element.
+    ///
+    /// Every element other than the root must be the child of exactly one element that comes
+    /// before it, and list items may only have other list items as children.
+    pub fn validate(&self) -> Result<()> {
+        let mut has_parent = vec![false; self.elements.len()];
+        for (ix, element) in self.elements.iter().enumerate() {
+            for &child_ix in element.children() {
+                let child_ix = child_ix as usize;
+                ensure!(
+                    ix < child_ix && child_ix < self.elements.len(),
+                    "element {ix} has invalid child {child_ix}"
+                );
+                ensure!(
+                    !has_parent[child_ix],
+                    "element {child_ix} has more than one parent"
+                );
+                has_parent[child_ix] = true;
+                if let PanelElement::ListItem(_) = element {
+                    ensure!(
+                        matches!(self.elements[child_ix], PanelElement::ListItem(_)),
+                        "list item {ix} has a child {child_ix} that is not a list item"
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(text: &str) -> PanelElement {
+        PanelElement::Label(PanelLabel {
+            text: text.into(),
+            color: PanelColor::Default,
+        })
+    }
+
+    fn list_item(id: &str, children: Vec<u32>) -> PanelElement {
+        PanelElement::ListItem(PanelListItem {
+            id: id.into(),
+            label: id.into(),
+            detail: None,
+            color: PanelColor::Default,
+            selected: false,
+            children,
+            expanded: true,
+        })
+    }
+
+    #[test]
+    fn test_validate_panel_view() {
+        let view = PanelView {
+            elements: vec![
+                PanelElement::Column(vec![1, 2]),
+                label("Pods"),
+                list_item("default", vec![3]),
+                list_item("default/web", vec![]),
+            ],
+        };
+        assert!(view.validate().is_ok());
+        assert!(PanelView::default().validate().is_ok());
+
+        let cycle = PanelView {
+            elements: vec![PanelElement::Column(vec![1]), PanelElement::Row(vec![0])],
+        };
+        assert!(cycle.validate().is_err());
+
+        let out_of_bounds = PanelView {
+            elements: vec![PanelElement::Column(vec![1, 5]), label("a")],
+        };
+        assert!(out_of_bounds.validate().is_err());
+
+        let shared_child = PanelView {
+            elements: vec![
+                PanelElement::Column(vec![1, 2]),
+                PanelElement::Row(vec![2]),
+                label("a"),
+            ],
+        };
+        assert!(shared_child.validate().is_err());
+
+        let label_in_list_item = PanelView {
+            elements: vec![list_item("a", vec![1]), label("b")],
+        };
+        assert!(label_in_list_item.validate().is_err());
+    }
+}
@@ -0,0 +1,12 @@
+use task::TaskTemplate;
+
+/// The tasks that an extension's task source provides for a worktree.
+#[derive(Debug, Clone, Default)]
+pub struct WorktreeTasks {
+    /// The task templates to offer in the worktree.
+    pub templates: Vec<TaskTemplate>,
+    /// Glob patterns, relative to the worktree root, matching the files that the tasks are derived from.
+    ///
+    /// The tasks are requested again whenever a matching file changes.
+    pub watched_paths: Vec<String>,
+}
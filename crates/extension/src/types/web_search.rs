@@ -0,0 +1,10 @@
+/// A result returned by an extension's web search provider.
+#[derive(Debug, Clone)]
+pub struct WebSearchResult {
+    /// The title of the page.
+    pub title: String,
+    /// The URL of the page.
+    pub url: String,
+    /// The text of the page that is relevant to the query.
+    pub text: String,
+}
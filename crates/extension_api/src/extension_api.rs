@@ -1,6 +1,7 @@
 //! The Zed Rust Extension API allows you write extensions for [Zed](https://zed.dev/) in Rust.
 
 pub mod http_client;
+pub mod panel;
 pub mod process;
 pub mod settings;
 
@@ -18,6 +19,7 @@ pub use wit::{
     CodeLabel, CodeLabelSpan, CodeLabelSpanLiteral, Command, DownloadedFileType, EnvVars,
     KeyValueStore, LanguageServerInstallationStatus, Project, Range, Worktree, download_file,
     make_file_executable,
+    zed::extension::code_actions::{CodeAction, Diagnostic, DiagnosticSeverity},
     zed::extension::context_server::ContextServerConfiguration,
     zed::extension::dap::{
         AttachRequest, BuildTaskDefinition, BuildTaskDefinitionTemplatePayload, BuildTaskTemplate,
@@ -25,6 +27,8 @@ pub use wit::{
         LaunchRequest, StartDebuggingRequestArguments, StartDebuggingRequestArgumentsRequest,
         TaskTemplate, TcpArguments, TcpArgumentsTemplate, resolve_tcp_template,
     },
+    zed::extension::editor::{Buffer, BufferEdit, Selection},
+    zed::extension::formatting::FormattingOptions,
     zed::extension::github::{
         GithubRelease, GithubReleaseAsset, GithubReleaseOptions, github_release_by_tag_name,
         latest_github_release,
@@ -37,6 +41,8 @@ pub use wit::{
     zed::extension::slash_command::{
         SlashCommand, SlashCommandArgumentCompletion, SlashCommandOutput, SlashCommandOutputSection,
     },
+    zed::extension::tasks::WorktreeTasks,
+    zed::extension::web_search::WebSearchResult,
 };
 
 // Undocumented WIT re-exports.
@@ -54,6 +60,42 @@ pub mod lsp {
     };
 }
 
+/// Constructs for providing language models to Zed.
+pub mod llm {
+    pub use crate::wit::zed::extension::llm_provider::{
+        LlmCompletionEvent, LlmCompletionRequest, LlmCompletionStream, LlmImage, LlmMessage,
+        LlmMessageContent, LlmModel, LlmModelCapabilities, LlmRole, LlmStopReason, LlmThinking,
+        LlmTokenUsage, LlmTool, LlmToolChoice, LlmToolResult, LlmToolResultContent, LlmToolUse,
+        read_credential,
+    };
+
+    /// Estimates the number of tokens in the request from the length of its text,
+    /// assuming four bytes per token.
+    pub fn estimate_token_count(request: &LlmCompletionRequest) -> u64 {
+        let mut len = 0;
+        for message in &request.messages {
+            for content in &message.content {
+                len += match content {
+                    LlmMessageContent::Text(text) | LlmMessageContent::RedactedThinking(text) => {
+                        text.len()
+                    }
+                    LlmMessageContent::Thinking(thinking) => thinking.text.len(),
+                    LlmMessageContent::Image(_) => 0,
+                    LlmMessageContent::ToolUse(tool_use) => tool_use.input.len(),
+                    LlmMessageContent::ToolResult(tool_result) => match &tool_result.content {
+                        LlmToolResultContent::Text(text) => text.len(),
+                        LlmToolResultContent::Image(_) => 0,
+                    },
+                };
+            }
+        }
+        for tool in &request.tools {
+            len += tool.name.len() + tool.description.len() + tool.input_schema.len();
+        }
+        len as u64 / 4
+    }
+}
+
 /// A result returned from a Zed extension.
 pub type Result<T, E = String> = core::result::Result<T, E>;
 
@@ -259,6 +301,135 @@ pub trait Extension: Send + Sync {
     ) -> Result<DebugRequest, String> {
         Err("`run_dap_locator` not implemented".to_string())
     }
+
+    /// Runs the editor command with the given ID against the buffer in the active editor.
+    ///
+    /// Editor commands are declared in the `editor_commands` section of the extension
+    /// manifest, and are listed in the command palette.
+    fn run_editor_command(&mut self, _command_id: String, _buffer: &Buffer) -> Result<(), String> {
+        Err("`run_editor_command` not implemented".to_string())
+    }
+
+    /// Formats the given text, from a buffer in the given language, using the formatter with the given ID.
+    ///
+    /// Formatters are declared in the `formatters` section of the extension manifest.
+    /// The returned edits are applied to the buffer, and their ranges are interpreted
+    /// relative to the given text.
+    fn format_buffer(
+        &mut self,
+        _formatter_id: String,
+        _language_name: String,
+        _text: String,
+        _options: FormattingOptions,
+    ) -> Result<Vec<BufferEdit>, String> {
+        Err("`format_buffer` not implemented".to_string())
+    }
+
+    /// Returns the code actions that the code action provider with the given ID offers
+    /// for a range of a buffer.
+    ///
+    /// Code action providers are declared in the `code_action_providers` section of the
+    /// extension manifest.
+    fn code_actions(
+        &mut self,
+        _provider_id: String,
+        _language_name: String,
+        _text: String,
+        _range: Range,
+        _diagnostics: Vec<Diagnostic>,
+    ) -> Result<Vec<CodeAction>, String> {
+        Err("`code_actions` not implemented".to_string())
+    }
+
+    /// Returns the tasks that the task source with the given ID provides for the worktree.
+    ///
+    /// Task sources are declared in the `task_sources` section of the extension manifest.
+    /// The tasks are requested again whenever a file matching one of the returned
+    /// `watched_paths` changes.
+    fn worktree_tasks(
+        &mut self,
+        _task_source_id: String,
+        _worktree: &Worktree,
+    ) -> Result<WorktreeTasks, String> {
+        Err("`worktree_tasks` not implemented".to_string())
+    }
+
+    /// Returns the values of the custom task variables that the task source with the
+    /// given ID provides, optionally for the worktree-relative path of a file.
+    ///
+    /// A variable named `NAME` can be referenced in tasks as `$ZED_CUSTOM_NAME`.
+    fn task_variables(
+        &mut self,
+        _task_source_id: String,
+        _worktree: &Worktree,
+        _path: Option<String>,
+    ) -> Result<EnvVars, String> {
+        Ok(Vec::new())
+    }
+
+    /// Searches the web for the query using the web search provider with the given ID.
+    ///
+    /// Web search providers are declared in the `web_search_providers` section of the
+    /// extension manifest.
+    fn web_search(
+        &mut self,
+        _provider_id: String,
+        _query: String,
+    ) -> Result<Vec<WebSearchResult>, String> {
+        Err("`web_search` not implemented".to_string())
+    }
+
+    /// Returns the models offered by the language model provider with the given ID.
+    ///
+    /// Language model providers are declared in the `language_model_providers` section of
+    /// the extension manifest.
+    fn llm_provider_models(&mut self, _provider_id: String) -> Result<Vec<llm::LlmModel>, String> {
+        Err("`llm_provider_models` not implemented".to_string())
+    }
+
+    /// Counts the tokens in the request to the given model.
+    ///
+    /// By default, the count is estimated from the length of the request.
+    fn llm_count_tokens(
+        &mut self,
+        _provider_id: String,
+        _model_id: String,
+        request: llm::LlmCompletionRequest,
+    ) -> Result<u64, String> {
+        Ok(llm::estimate_token_count(&request))
+    }
+
+    /// Requests a completion from the given model, sending each of its events to the stream.
+    ///
+    /// This should return once the completion has finished, or as soon as sending an event
+    /// fails because the completion was cancelled.
+    fn llm_stream_completion(
+        &mut self,
+        _provider_id: String,
+        _model_id: String,
+        _request: llm::LlmCompletionRequest,
+        _stream: &llm::LlmCompletionStream,
+    ) -> Result<(), String> {
+        Err("`llm_stream_completion` not implemented".to_string())
+    }
+
+    /// Returns the contents of the panel with the given ID.
+    ///
+    /// Panels are declared in the `panels` section of the extension manifest.
+    fn render_panel(&mut self, _panel_id: String) -> Result<panel::PanelView, String> {
+        Err("`render_panel` not implemented".to_string())
+    }
+
+    /// Handles an interaction with the panel with the given ID, and returns its updated contents.
+    ///
+    /// By default, the panel is rendered again without handling the event.
+    fn handle_panel_event(
+        &mut self,
+        panel_id: String,
+        _event: panel::PanelEvent,
+    ) -> Result<panel::PanelView, String> {
+        self.render_panel(panel_id)
+    }
 }
 
 /// Registers the provided type as a Zed extension.
@@ -300,7 +471,7 @@ mod wit {
 
     wit_bindgen::generate!({
         skip: ["init-extension"],
-        path: "./wit/since_v0.6.0",
+        path: "./wit/since_v0.7.0",
     });
 }
 
@@ -484,6 +655,80 @@ impl wit::Guest for Component {
     ) -> Result<DebugRequest, String> {
         extension().run_dap_locator(locator_name, build_task)
     }
+
+    fn run_editor_command(command_id: String, buffer: &Buffer) -> Result<(), String> {
+        extension().run_editor_command(command_id, buffer)
+    }
+
+    fn format_buffer(
+        formatter_id: String,
+        language_name: String,
+        text: String,
+        options: FormattingOptions,
+    ) -> Result<Vec<BufferEdit>, String> {
+        extension().format_buffer(formatter_id, language_name, text, options)
+    }
+
+    fn code_actions(
+        provider_id: String,
+        language_name: String,
+        text: String,
+        range: Range,
+        diagnostics: Vec<Diagnostic>,
+    ) -> Result<Vec<CodeAction>, String> {
+        extension().code_actions(provider_id, language_name, text, range, diagnostics)
+    }
+
+    fn worktree_tasks(
+        task_source_id: String,
+        worktree: &Worktree,
+    ) -> Result<WorktreeTasks, String> {
+        extension().worktree_tasks(task_source_id, worktree)
+    }
+
+    fn task_variables(
+        task_source_id: String,
+        worktree: &Worktree,
+        path: Option<String>,
+    ) -> Result<EnvVars, String> {
+        extension().task_variables(task_source_id, worktree, path)
+    }
+
+    fn web_search(provider_id: String, query: String) -> Result<Vec<WebSearchResult>, String> {
+        extension().web_search(provider_id, query)
+    }
+
+    fn llm_provider_models(provider_id: String) -> Result<Vec<llm::LlmModel>, String> {
+        extension().llm_provider_models(provider_id)
+    }
+
+    fn llm_count_tokens(
+        provider_id: String,
+        model_id: String,
+        request: llm::LlmCompletionRequest,
+    ) -> Result<u64, String> {
+        extension().llm_count_tokens(provider_id, model_id, request)
+    }
+
+    fn llm_stream_completion(
+        provider_id: String,
+        model_id: String,
+        request: llm::LlmCompletionRequest,
+        stream: &llm::LlmCompletionStream,
+    ) -> Result<(), String> {
+        extension().llm_stream_completion(provider_id, model_id, request, stream)
+    }
+
+    fn render_panel(panel_id: String) -> Result<panel::PanelView, String> {
+        extension().render_panel(panel_id)
+    }
+
+    fn handle_panel_event(
+        panel_id: String,
+        event: panel::PanelEvent,
+    ) -> Result<panel::PanelView, String> {
+        extension().handle_panel_event(panel_id, event)
+    }
 }
 
 /// The ID of a language server.
@@ -0,0 +1,98 @@
+//! Panels that extensions add to Zed's docks.
+
+pub use crate::wit::zed::extension::panel::{
+    PanelButton, PanelColor, PanelElement, PanelEvent, PanelLabel, PanelListItem,
+    PanelListItemToggled, PanelTextInput, PanelTextInputSubmitted, PanelView,
+};
+
+/// An element of a panel, with its children nested inside it.
+///
+/// This is converted into a [`PanelView`], which stores its elements in a flat list.
+#[derive(Debug, Clone)]
+pub enum Element {
+    /// Lays out its children vertically.
+    Column(Vec<Element>),
+    /// Lays out its children horizontally.
+    Row(Vec<Element>),
+    Label(PanelLabel),
+    Button(PanelButton),
+    TextInput(PanelTextInput),
+    /// A list item, with the items nested under it.
+    ///
+    /// The `children` of the [`PanelListItem`] are replaced by the given items.
+    ListItem(PanelListItem, Vec<Element>),
+    Divider,
+}
+
+impl Element {
+    /// Returns a label with the given text.
+    pub fn label(text: impl Into<String>) -> Self {
+        Self::Label(PanelLabel {
+            text: text.into(),
+            color: PanelColor::Default,
+        })
+    }
+
+    /// Returns a button that sends a [`PanelEvent::ButtonClicked`] event with the given ID.
+    pub fn button(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self::Button(PanelButton {
+            id: id.into(),
+            label: label.into(),
+            disabled: false,
+        })
+    }
+
+    /// Returns a list item with the given ID and label, and the given items nested under it.
+    pub fn list_item(
+        id: impl Into<String>,
+        label: impl Into<String>,
+        children: Vec<Element>,
+    ) -> Self {
+        Self::ListItem(
+            PanelListItem {
+                id: id.into(),
+                label: label.into(),
+                detail: None,
+                color: PanelColor::Default,
+                selected: false,
+                children: Vec::new(),
+                expanded: false,
+            },
+            children,
+        )
+    }
+
+    fn push(self, elements: &mut Vec<PanelElement>) -> u32 {
+        let ix = elements.len();
+        elements.push(PanelElement::Divider);
+        let element = match self {
+            Element::Column(children) => PanelElement::Column(push_all(children, elements)),
+            Element::Row(children) => PanelElement::Row(push_all(children, elements)),
+            Element::Label(label) => PanelElement::Label(label),
+            Element::Button(button) => PanelElement::Button(button),
+            Element::TextInput(input) => PanelElement::TextInput(input),
+            Element::ListItem(mut item, children) => {
+                item.children = push_all(children, elements);
+                PanelElement::ListItem(item)
+            }
+            Element::Divider => PanelElement::Divider,
+        };
+        elements[ix] = element;
+        ix as u32
+    }
+}
+
+fn push_all(children: Vec<Element>, elements: &mut Vec<PanelElement>) -> Vec<u32> {
+    children
+        .into_iter()
+        .map(|child| child.push(elements))
+        .collect()
+}
+
+impl From<Element> for PanelView {
+    fn from(root: Element) -> Self {
+        let mut elements = Vec::new();
+        root.push(&mut elements);
+        PanelView { elements }
+    }
+}
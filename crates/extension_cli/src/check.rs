@@ -0,0 +1,347 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use ::fs::Fs;
+use anyhow::{Context as _, Result, bail};
+use extension::extension_builder::{CompileExtensionOptions, ExtensionBuilder};
+use extension::{ExtensionCapability, ExtensionManifest};
+use reqwest_client::ReqwestClient;
+use serde::Serialize;
+use tree_sitter::WasmStore;
+
+use crate::scenarios::ScenarioResult;
+use crate::{test_grammars, test_languages, test_themes};
+
+/// The results of checking, and optionally testing, an extension.
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    pub extension_id: Option<Arc<str>>,
+    pub checks: Vec<CheckResult>,
+    pub scenarios: Vec<ScenarioResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub passed: bool,
+    pub error: Option<String>,
+}
+
+impl Report {
+    /// Records the outcome of a check, returning its value if it passed.
+    pub fn record<T>(&mut self, name: &'static str, result: Result<T>) -> Option<T> {
+        match result {
+            Ok(value) => {
+                self.checks.push(CheckResult {
+                    name,
+                    passed: true,
+                    error: None,
+                });
+                Some(value)
+            }
+            Err(error) => {
+                self.checks.push(CheckResult {
+                    name,
+                    passed: false,
+                    error: Some(format!("{error:#}")),
+                });
+                None
+            }
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+            && self.scenarios.iter().all(|scenario| scenario.passed)
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        if self.passed() { 0 } else { 1 }
+    }
+
+    pub fn print(&self, json: bool) -> Result<()> {
+        if json {
+            println!("{}", serde_json::to_string_pretty(self)?);
+            return Ok(());
+        }
+
+        for check in &self.checks {
+            match &check.error {
+                None => println!("PASS {}", check.name),
+                Some(error) => println!("FAIL {}: {error}", check.name),
+            }
+        }
+
+        for scenario in &self.scenarios {
+            match &scenario.error {
+                None => println!("PASS scenario {:?}", scenario.name),
+                Some(error) => println!("FAIL scenario {:?}: {error}", scenario.name),
+            }
+        }
+
+        let failed_count = self.checks.iter().filter(|check| !check.passed).count()
+            + self
+                .scenarios
+                .iter()
+                .filter(|scenario| !scenario.passed)
+                .count();
+        let total_count = self.checks.len() + self.scenarios.len();
+        println!(
+            "\n{} passed, {failed_count} failed",
+            total_count - failed_count
+        );
+
+        Ok(())
+    }
+}
+
+/// Compiles the extension and validates its manifest and resources.
+///
+/// Returns the compiled extension's manifest if every check passed.
+pub async fn run_checks(
+    extension_path: &Path,
+    scratch_dir: PathBuf,
+    http_client: Arc<ReqwestClient>,
+    fs: Arc<dyn Fs>,
+    report: &mut Report,
+) -> Option<ExtensionManifest> {
+    let manifest = ExtensionManifest::load(fs.clone(), extension_path)
+        .await
+        .and_then(|manifest| {
+            validate_manifest(&manifest)?;
+            Ok(manifest)
+        });
+    let mut manifest = report.record("manifest", manifest)?;
+    report.extension_id = Some(manifest.id.clone());
+
+    log::info!("compiling extension");
+    let builder = ExtensionBuilder::new(http_client, scratch_dir);
+    let compile_result = builder
+        .compile_extension(
+            extension_path,
+            &mut manifest,
+            CompileExtensionOptions { release: false },
+        )
+        .await
+        .context("failed to compile extension");
+    report.record("compile", compile_result)?;
+    report.record("library", validate_library(&manifest));
+
+    let engine = wasmtime::Engine::default();
+    let grammars = WasmStore::new(&engine)
+        .map_err(anyhow::Error::from)
+        .and_then(|mut wasm_store| test_grammars(&manifest, extension_path, &mut wasm_store));
+    if let Some(grammars) = report.record("grammars", grammars) {
+        report.record(
+            "languages",
+            test_languages(&manifest, extension_path, &grammars),
+        );
+    }
+    report.record(
+        "themes",
+        test_themes(&manifest, extension_path, fs.clone()).await,
+    );
+
+    report.passed().then_some(manifest)
+}
+
+fn validate_manifest(manifest: &ExtensionManifest) -> Result<()> {
+    for capability in &manifest.capabilities {
+        match capability {
+            ExtensionCapability::NetworkHttp { hosts } if hosts.is_empty() => {
+                bail!("network:http capability must list at least one host")
+            }
+            ExtensionCapability::FsPath { path, .. } if !path.is_absolute() => {
+                bail!("fs:path capability must use an absolute path, but got {path:?}")
+            }
+            ExtensionCapability::EnvRead { vars } if vars.is_empty() => {
+                bail!("env:read capability must list at least one variable")
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Ensures that an extension providing features implemented in WebAssembly has a
+/// Rust library to implement them.
+fn validate_library(manifest: &ExtensionManifest) -> Result<()> {
+    if manifest.lib.kind.is_some() {
+        return Ok(());
+    }
+
+    let wasm_features = [
+        ("language servers", manifest.language_servers.is_empty()),
+        ("context servers", manifest.context_servers.is_empty()),
+        ("slash commands", manifest.slash_commands.is_empty()),
+        (
+            "indexed docs providers",
+            manifest.indexed_docs_providers.is_empty(),
+        ),
+        ("debug adapters", manifest.debug_adapters.is_empty()),
+        ("debug locators", manifest.debug_locators.is_empty()),
+        ("editor commands", manifest.editor_commands.is_empty()),
+        ("formatters", manifest.formatters.is_empty()),
+        (
+            "code action providers",
+            manifest.code_action_providers.is_empty(),
+        ),
+        ("task sources", manifest.task_sources.is_empty()),
+        (
+            "web search providers",
+            manifest.web_search_providers.is_empty(),
+        ),
+        (
+            "language model providers",
+            manifest.language_model_providers.is_empty(),
+        ),
+        ("panels", manifest.panels.is_empty()),
+    ];
+    for (feature, is_empty) in wasm_features {
+        if !is_empty {
+            bail!(
+                "extension provides {feature}, which require a Rust library, but has no Cargo.toml"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn manifest(extra: &str) -> ExtensionManifest {
+        toml::from_str(&format!(
+            "id = \"test\"\nname = \"Test\"\nversion = \"1.0.0\"\nschema_version = 1\n{extra}"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_manifest() {
+        assert!(validate_manifest(&manifest("")).is_ok());
+        assert!(
+            validate_manifest(&manifest(
+                r#"
+                [[capabilities]]
+                kind = "network:http"
+                hosts = ["api.example.com"]
+
+                [[capabilities]]
+                kind = "fs:path"
+                path = "/etc/hosts"
+                read_only = true
+                "#
+            ))
+            .is_ok()
+        );
+
+        let error = validate_manifest(&manifest(
+            r#"
+            [[capabilities]]
+            kind = "network:http"
+            hosts = []
+            "#,
+        ))
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "network:http capability must list at least one host"
+        );
+
+        let error = validate_manifest(&manifest(
+            r#"
+            [[capabilities]]
+            kind = "fs:path"
+            path = "relative/path"
+            "#,
+        ))
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "fs:path capability must use an absolute path, but got \"relative/path\""
+        );
+
+        let error = validate_manifest(&manifest(
+            r#"
+            [[capabilities]]
+            kind = "env:read"
+            vars = []
+            "#,
+        ))
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "env:read capability must list at least one variable"
+        );
+    }
+
+    #[test]
+    fn test_validate_library() {
+        assert!(validate_library(&manifest("")).is_ok());
+
+        let formatter = r#"
+            [formatters.test-formatter]
+            languages = ["Rust"]
+        "#;
+        let error = validate_library(&manifest(formatter)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "extension provides formatters, which require a Rust library, but has no Cargo.toml"
+        );
+
+        let mut manifest = manifest(formatter);
+        manifest.lib.kind = Some(extension::ExtensionLibraryKind::Rust);
+        assert!(validate_library(&manifest).is_ok());
+    }
+
+    #[test]
+    fn test_report() {
+        let mut report = Report {
+            extension_id: Some("test".into()),
+            ..Default::default()
+        };
+        assert_eq!(report.record("manifest", Ok(1)), Some(1));
+        assert!(report.passed());
+        assert_eq!(report.exit_code(), 0);
+
+        let failure = Err::<(), _>(anyhow::anyhow!("missing file").context("failed to compile"));
+        assert_eq!(report.record("compile", failure), None);
+        report.scenarios.push(ScenarioResult {
+            name: "starts the language server".into(),
+            passed: true,
+            error: None,
+            output: Some(json!({ "command": "gleam" })),
+        });
+        assert!(!report.passed());
+        assert_eq!(report.exit_code(), 1);
+
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({
+                "extension_id": "test",
+                "checks": [
+                    { "name": "manifest", "passed": true, "error": null },
+                    {
+                        "name": "compile",
+                        "passed": false,
+                        "error": "failed to compile: missing file",
+                    },
+                ],
+                "scenarios": [
+                    {
+                        "name": "starts the language server",
+                        "passed": true,
+                        "error": null,
+                        "output": { "command": "gleam" },
+                    },
+                ],
+            })
+        );
+    }
+}
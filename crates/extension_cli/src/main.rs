@@ -1,3 +1,6 @@
+mod check;
+mod scenarios;
+
 use std::collections::{BTreeSet, HashMap};
 use std::env;
 use std::fs;
@@ -7,7 +10,7 @@ use std::sync::Arc;
 
 use ::fs::{CopyOptions, Fs, RealFs, copy_recursive};
 use anyhow::{Context as _, Result, bail};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use extension::ExtensionManifest;
 use extension::extension_builder::{CompileExtensionOptions, ExtensionBuilder};
 use language::LanguageConfig;
@@ -16,17 +19,53 @@ use rpc::ExtensionProvides;
 use tree_sitter::{Language, Query, WasmStore};
 
 #[derive(Parser, Debug)]
-#[command(name = "zed-extension")]
+#[command(
+    name = "zed-extension",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Commands>,
     /// The path to the extension directory
-    #[arg(long)]
-    source_dir: PathBuf,
+    #[arg(long, required = true)]
+    source_dir: Option<PathBuf>,
     /// The output directory to place the packaged extension.
+    #[arg(long, required = true)]
+    output_dir: Option<PathBuf>,
+    /// The path to a directory where build dependencies are downloaded
+    #[arg(long, required = true)]
+    scratch_dir: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Compile the extension and validate its manifest, grammars, languages, and themes.
+    Check(CheckArgs),
+    /// Check the extension, then load it and run the scenarios in a scenarios file against it.
+    Test(TestArgs),
+}
+
+#[derive(clap::Args, Debug)]
+struct CheckArgs {
+    /// The path to the extension directory
     #[arg(long)]
-    output_dir: PathBuf,
+    source_dir: PathBuf,
     /// The path to a directory where build dependencies are downloaded
     #[arg(long)]
     scratch_dir: PathBuf,
+    /// Print the results as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(clap::Args, Debug)]
+struct TestArgs {
+    #[command(flatten)]
+    check: CheckArgs,
+    /// The path to the scenarios file. Defaults to `tests/scenarios.toml` in the extension directory.
+    #[arg(long)]
+    scenarios: Option<PathBuf>,
 }
 
 #[tokio::main]
@@ -34,10 +73,48 @@ async fn main() -> Result<()> {
     env_logger::init();
 
     let args = Args::parse();
-    let fs = Arc::new(RealFs::new(None, gpui::background_executor()));
-    let engine = wasmtime::Engine::default();
-    let mut wasm_store = WasmStore::new(&engine)?;
+    match args.command {
+        Some(Commands::Check(args)) => {
+            let (report, _) = check_extension(&args).await?;
+            report.print(args.json)?;
+            std::process::exit(report.exit_code());
+        }
+        Some(Commands::Test(args)) => {
+            let (mut report, manifest) = check_extension(&args.check).await?;
+            let extension_path = args.check.source_dir.canonicalize()?;
+            let scenarios_path = args
+                .scenarios
+                .unwrap_or_else(|| extension_path.join("tests").join("scenarios.toml"));
+            let scenarios_file = report.record("scenarios file", scenarios::load(&scenarios_path));
+
+            let (Some(manifest), Some(scenarios_file)) = (manifest, scenarios_file) else {
+                report.print(args.check.json)?;
+                std::process::exit(report.exit_code());
+            };
+
+            let work_dir = args.check.scratch_dir.canonicalize()?.join("work");
+            scenarios::run(
+                extension_path,
+                manifest,
+                scenarios_file,
+                work_dir,
+                report,
+                args.check.json,
+            )
+        }
+        None => {
+            package(
+                args.source_dir.context("missing --source-dir")?,
+                args.output_dir.context("missing --output-dir")?,
+                args.scratch_dir.context("missing --scratch-dir")?,
+            )
+            .await
+        }
+    }
+}
 
+async fn check_extension(args: &CheckArgs) -> Result<(check::Report, Option<ExtensionManifest>)> {
+    let fs = Arc::new(RealFs::new(None, gpui::background_executor()));
     let extension_path = args
         .source_dir
         .canonicalize()
@@ -46,10 +123,29 @@ async fn main() -> Result<()> {
         .scratch_dir
         .canonicalize()
         .context("failed to canonicalize scratch_dir")?;
-    let output_dir = if args.output_dir.is_relative() {
-        env::current_dir()?.join(&args.output_dir)
+    let http_client = Arc::new(ReqwestClient::user_agent(&user_agent())?);
+
+    let mut report = check::Report::default();
+    let manifest =
+        check::run_checks(&extension_path, scratch_dir, http_client, fs, &mut report).await;
+    Ok((report, manifest))
+}
+
+async fn package(source_dir: PathBuf, output_dir: PathBuf, scratch_dir: PathBuf) -> Result<()> {
+    let fs = Arc::new(RealFs::new(None, gpui::background_executor()));
+    let engine = wasmtime::Engine::default();
+    let mut wasm_store = WasmStore::new(&engine)?;
+
+    let extension_path = source_dir
+        .canonicalize()
+        .context("failed to canonicalize source_dir")?;
+    let scratch_dir = scratch_dir
+        .canonicalize()
+        .context("failed to canonicalize scratch_dir")?;
+    let output_dir = if output_dir.is_relative() {
+        env::current_dir()?.join(&output_dir)
     } else {
-        args.output_dir
+        output_dir
     };
 
     log::info!("loading extension manifest");
@@ -57,13 +153,7 @@ async fn main() -> Result<()> {
 
     log::info!("compiling extension");
 
-    let user_agent = format!(
-        "Zed Extension CLI/{} ({}; {})",
-        env!("CARGO_PKG_VERSION"),
-        std::env::consts::OS,
-        std::env::consts::ARCH
-    );
-    let http_client = Arc::new(ReqwestClient::user_agent(&user_agent)?);
+    let http_client = Arc::new(ReqwestClient::user_agent(&user_agent())?);
 
     let builder = ExtensionBuilder::new(http_client, scratch_dir);
     builder
@@ -117,6 +207,15 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+fn user_agent() -> String {
+    format!(
+        "Zed Extension CLI/{} ({}; {})",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )
+}
+
 /// Returns the set of features provided by the extension.
 fn extension_provides(manifest: &ExtensionManifest) -> BTreeSet<ExtensionProvides> {
     let mut provides = BTreeSet::default();
@@ -0,0 +1,836 @@
+//! Scripted scenarios that exercise an extension's WebAssembly exports.
+//!
+//! Scenarios are read from a TOML file, which looks like this:
+//!
+//! ```toml
+//! # The fake worktree the scenarios run against. Paths are relative to the scenarios file.
+//! [worktree]
+//! root = "fixtures/project"
+//! env = { PATH = "/usr/bin" }
+//! binaries = { gleam = "/usr/local/bin/gleam" }
+//!
+//! [[scenario]]
+//! name = "starts the language server"
+//! kind = "language-server-command"
+//! language_server = "gleam"
+//! language = "Gleam"
+//! expect = { command = "/usr/local/bin/gleam", args = ["lsp"] }
+//! ```
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use ::fs::RealFs;
+use anyhow::{Context as _, Result, anyhow, bail};
+use async_trait::async_trait;
+use extension::{
+    CodeLabel, CodeLabelSpan, Completion, CompletionKind, CompletionLabelDetails, Extension,
+    ExtensionHostProxy, ExtensionManifest, InsertTextFormat, SlashCommand, WorktreeDelegate,
+};
+use extension_host::wasm_host::{WasmExtension, WasmHost};
+use gpui::AsyncApp;
+use language::LanguageName;
+use lsp::LanguageServerName;
+use node_runtime::{NodeBinaryOptions, NodeRuntime};
+use reqwest_client::ReqwestClient;
+use semantic_version::SemanticVersion;
+use serde::{Deserialize, Serialize};
+
+use crate::check::Report;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenariosFile {
+    #[serde(default)]
+    worktree: FakeWorktreeConfig,
+    #[serde(default, rename = "scenario")]
+    scenarios: Vec<Scenario>,
+    /// The directory containing the scenarios file, which relative paths are resolved against.
+    #[serde(skip)]
+    base_dir: PathBuf,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FakeWorktreeConfig {
+    /// The directory that files are read from. Defaults to the directory containing the
+    /// scenarios file.
+    root: Option<PathBuf>,
+    /// The environment returned by `Worktree::shell_env`.
+    #[serde(default)]
+    env: BTreeMap<String, String>,
+    /// The paths returned by `Worktree::which`, keyed by binary name.
+    #[serde(default)]
+    binaries: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Scenario {
+    name: String,
+    #[serde(flatten)]
+    kind: ScenarioKind,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+enum ScenarioKind {
+    LanguageServerCommand {
+        language_server: String,
+        language: String,
+        #[serde(default)]
+        expect: ExpectedCommand,
+    },
+    SlashCommand {
+        command: String,
+        #[serde(default)]
+        arguments: Vec<String>,
+        #[serde(default)]
+        expect: ExpectedSlashCommandOutput,
+    },
+    LabelsForCompletions {
+        language_server: String,
+        completions: Vec<ScenarioCompletion>,
+        expect: Option<Vec<ExpectedLabel>>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScenarioCompletion {
+    label: String,
+    detail: Option<String>,
+    /// The name of the LSP completion kind, e.g., `function`.
+    kind: Option<String>,
+    label_detail: Option<String>,
+    label_description: Option<String>,
+    #[serde(default)]
+    snippet: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExpectedCommand {
+    command: Option<String>,
+    args: Option<Vec<String>>,
+    env: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExpectedSlashCommandOutput {
+    text: Option<String>,
+    text_contains: Option<String>,
+    section_labels: Option<Vec<String>>,
+}
+
+/// The expected label for a completion. An empty table matches any label, including
+/// no label at all.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExpectedLabel {
+    text: Option<String>,
+    filter_text: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScenarioResult {
+    pub name: String,
+    pub passed: bool,
+    pub error: Option<String>,
+    /// What the extension returned, if the call succeeded.
+    pub output: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct CommandOutput {
+    command: String,
+    args: Vec<String>,
+    env: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize)]
+struct SlashCommandOutput {
+    text: String,
+    sections: Vec<SlashCommandOutputSection>,
+}
+
+#[derive(Debug, Serialize)]
+struct SlashCommandOutputSection {
+    label: String,
+    range: Range<usize>,
+}
+
+#[derive(Debug, Serialize)]
+struct LabelOutput {
+    text: String,
+    filter_text: String,
+    code: String,
+}
+
+pub fn load(path: &Path) -> Result<ScenariosFile> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read scenarios file {path:?}"))?;
+    let mut scenarios: ScenariosFile =
+        toml::from_str(&content).with_context(|| format!("invalid scenarios file {path:?}"))?;
+    scenarios.base_dir = path
+        .parent()
+        .context("scenarios file has no parent directory")?
+        .to_path_buf();
+    Ok(scenarios)
+}
+
+/// Loads the compiled extension into a headless extension host, runs the scenarios
+/// against it, and exits with the outcome.
+pub fn run(
+    extension_path: PathBuf,
+    manifest: ExtensionManifest,
+    scenarios: ScenariosFile,
+    work_dir: PathBuf,
+    mut report: Report,
+    json: bool,
+) -> Result<()> {
+    gpui::Application::headless().run(move |cx| {
+        release_channel::init(SemanticVersion::default(), cx);
+        extension::init(cx);
+
+        let fs = Arc::new(RealFs::new(None, cx.background_executor().clone()));
+        let http_client = Arc::new(
+            ReqwestClient::user_agent(&crate::user_agent()).expect("failed to create HTTP client"),
+        );
+        let (_, node_binary_options) = watch::channel(Some(NodeBinaryOptions {
+            allow_path_lookup: true,
+            allow_binary_download: true,
+            use_paths: None,
+        }));
+        let node_runtime = NodeRuntime::new(http_client.clone(), None, node_binary_options);
+        let wasm_host = WasmHost::new(
+            fs,
+            http_client,
+            node_runtime,
+            ExtensionHostProxy::global(cx),
+            work_dir,
+            cx,
+        );
+
+        cx.spawn(async move |cx| {
+            let manifest = Arc::new(manifest);
+            let extension = load_extension(&extension_path, &manifest, &wasm_host, cx).await;
+            if let Some(extension) = report.record("load", extension) {
+                let worktree = FakeWorktree::new(&scenarios);
+                for scenario in &scenarios.scenarios {
+                    log::info!("running scenario {:?}", scenario.name);
+                    let result =
+                        run_scenario(&extension, &manifest, worktree.clone(), scenario).await;
+                    report.scenarios.push(result);
+                }
+            }
+
+            if let Err(error) = report.print(json) {
+                eprintln!("failed to print report: {error:#}");
+            }
+            std::process::exit(report.exit_code());
+        })
+        .detach();
+    });
+
+    Ok(())
+}
+
+async fn load_extension(
+    extension_path: &Path,
+    manifest: &Arc<ExtensionManifest>,
+    wasm_host: &Arc<WasmHost>,
+    cx: &AsyncApp,
+) -> Result<WasmExtension> {
+    let wasm_bytes = std::fs::read(extension_path.join("extension.wasm"))
+        .context("failed to read extension.wasm")?;
+    wasm_host
+        .load_extension(
+            wasm_bytes,
+            manifest,
+            Default::default(),
+            cx.background_executor().clone(),
+        )
+        .await
+        .context("failed to load extension")
+}
+
+async fn run_scenario(
+    extension: &dyn Extension,
+    manifest: &ExtensionManifest,
+    worktree: Arc<dyn WorktreeDelegate>,
+    scenario: &Scenario,
+) -> ScenarioResult {
+    let result = match &scenario.kind {
+        ScenarioKind::LanguageServerCommand {
+            language_server,
+            language,
+            expect,
+        } => {
+            run_language_server_command(extension, language_server, language, worktree, expect)
+                .await
+        }
+        ScenarioKind::SlashCommand {
+            command,
+            arguments,
+            expect,
+        } => run_slash_command(extension, manifest, command, arguments, worktree, expect).await,
+        ScenarioKind::LabelsForCompletions {
+            language_server,
+            completions,
+            expect,
+        } => {
+            run_labels_for_completions(extension, language_server, completions, expect.as_deref())
+                .await
+        }
+    };
+
+    let (output, error) = match result {
+        Ok((output, Ok(()))) => (Some(output), None),
+        Ok((output, Err(error))) => (Some(output), Some(error)),
+        Err(error) => (None, Some(error)),
+    };
+    ScenarioResult {
+        name: scenario.name.clone(),
+        passed: error.is_none(),
+        error: error.map(|error| format!("{error:#}")),
+        output,
+    }
+}
+
+/// The output of a scenario, along with whether it matched the expectations.
+type ScenarioOutput = (serde_json::Value, Result<()>);
+
+async fn run_language_server_command(
+    extension: &dyn Extension,
+    language_server: &str,
+    language: &str,
+    worktree: Arc<dyn WorktreeDelegate>,
+    expect: &ExpectedCommand,
+) -> Result<ScenarioOutput> {
+    let command = extension
+        .language_server_command(
+            LanguageServerName(language_server.to_string().into()),
+            LanguageName::new(language),
+            worktree,
+        )
+        .await?;
+    let output = CommandOutput {
+        command: command.command,
+        args: command.args,
+        env: command.env.into_iter().collect(),
+    };
+
+    let matches = check_expected("command", &expect.command, &output.command)
+        .and_then(|_| check_expected("args", &expect.args, &output.args))
+        .and_then(|_| check_expected("env", &expect.env, &output.env));
+    Ok((serde_json::to_value(&output)?, matches))
+}
+
+async fn run_slash_command(
+    extension: &dyn Extension,
+    manifest: &ExtensionManifest,
+    command_name: &str,
+    arguments: &[String],
+    worktree: Arc<dyn WorktreeDelegate>,
+    expect: &ExpectedSlashCommandOutput,
+) -> Result<ScenarioOutput> {
+    let entry = manifest
+        .slash_commands
+        .get(command_name)
+        .with_context(|| format!("slash command {command_name:?} is not in the manifest"))?;
+    let command = SlashCommand {
+        name: command_name.to_string(),
+        description: entry.description.clone(),
+        tooltip_text: String::new(),
+        requires_argument: entry.requires_argument,
+    };
+
+    let result = extension
+        .run_slash_command(command, arguments.to_vec(), Some(worktree))
+        .await?;
+    let output = SlashCommandOutput {
+        sections: result
+            .sections
+            .into_iter()
+            .map(|section| SlashCommandOutputSection {
+                label: section.label,
+                range: section.range,
+            })
+            .collect(),
+        text: result.text,
+    };
+
+    let section_labels = output
+        .sections
+        .iter()
+        .map(|section| section.label.clone())
+        .collect::<Vec<_>>();
+    let matches = check_expected("text", &expect.text, &output.text)
+        .and_then(|_| check_expected("section labels", &expect.section_labels, &section_labels))
+        .and_then(|_| match &expect.text_contains {
+            Some(substring) if !output.text.contains(substring.as_str()) => {
+                Err(anyhow!("expected text to contain {substring:?}"))
+            }
+            _ => Ok(()),
+        });
+    Ok((serde_json::to_value(&output)?, matches))
+}
+
+async fn run_labels_for_completions(
+    extension: &dyn Extension,
+    language_server: &str,
+    completions: &[ScenarioCompletion],
+    expect: Option<&[ExpectedLabel]>,
+) -> Result<ScenarioOutput> {
+    let completions = completions
+        .iter()
+        .map(|completion| {
+            Ok(Completion {
+                label: completion.label.clone(),
+                label_details: (completion.label_detail.is_some()
+                    || completion.label_description.is_some())
+                .then(|| CompletionLabelDetails {
+                    detail: completion.label_detail.clone(),
+                    description: completion.label_description.clone(),
+                }),
+                detail: completion.detail.clone(),
+                kind: completion
+                    .kind
+                    .as_deref()
+                    .map(completion_kind_from_name)
+                    .transpose()?,
+                insert_text_format: Some(if completion.snippet {
+                    InsertTextFormat::Snippet
+                } else {
+                    InsertTextFormat::PlainText
+                }),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let labels = extension
+        .labels_for_completions(
+            LanguageServerName(language_server.to_string().into()),
+            completions,
+        )
+        .await?;
+    let output = labels
+        .into_iter()
+        .map(|label| label.map(label_output))
+        .collect::<Vec<_>>();
+
+    let matches = match expect {
+        Some(expect) => check_labels(expect, &output),
+        None => Ok(()),
+    };
+    Ok((serde_json::to_value(&output)?, matches))
+}
+
+fn check_labels(expect: &[ExpectedLabel], labels: &[Option<LabelOutput>]) -> Result<()> {
+    if expect.len() != labels.len() {
+        bail!("expected {} labels, but got {}", expect.len(), labels.len());
+    }
+
+    for (ix, (expected, label)) in expect.iter().zip(labels).enumerate() {
+        match label {
+            Some(label) => {
+                check_expected("text", &expected.text, &label.text)
+                    .and_then(|_| {
+                        check_expected("filter text", &expected.filter_text, &label.filter_text)
+                    })
+                    .with_context(|| format!("label {ix} did not match"))?;
+            }
+            None if expected.text.is_some() || expected.filter_text.is_some() => {
+                bail!("expected a label for completion {ix}, but got none")
+            }
+            None => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn check_expected<T: PartialEq + std::fmt::Debug>(
+    name: &str,
+    expected: &Option<T>,
+    actual: &T,
+) -> Result<()> {
+    match expected {
+        Some(expected) if expected != actual => {
+            bail!("expected {name} to be {expected:?}, but got {actual:?}")
+        }
+        _ => Ok(()),
+    }
+}
+
+fn label_output(label: CodeLabel) -> LabelOutput {
+    let mut text = String::new();
+    for span in &label.spans {
+        match span {
+            CodeLabelSpan::CodeRange(range) => {
+                text.push_str(label.code.get(range.clone()).unwrap_or_default())
+            }
+            CodeLabelSpan::Literal(literal) => text.push_str(&literal.text),
+        }
+    }
+
+    LabelOutput {
+        filter_text: text.get(label.filter_range).unwrap_or_default().to_string(),
+        text,
+        code: label.code,
+    }
+}
+
+fn completion_kind_from_name(name: &str) -> Result<CompletionKind> {
+    Ok(match name {
+        "text" => CompletionKind::Text,
+        "method" => CompletionKind::Method,
+        "function" => CompletionKind::Function,
+        "constructor" => CompletionKind::Constructor,
+        "field" => CompletionKind::Field,
+        "variable" => CompletionKind::Variable,
+        "class" => CompletionKind::Class,
+        "interface" => CompletionKind::Interface,
+        "module" => CompletionKind::Module,
+        "property" => CompletionKind::Property,
+        "unit" => CompletionKind::Unit,
+        "value" => CompletionKind::Value,
+        "enum" => CompletionKind::Enum,
+        "keyword" => CompletionKind::Keyword,
+        "snippet" => CompletionKind::Snippet,
+        "color" => CompletionKind::Color,
+        "file" => CompletionKind::File,
+        "reference" => CompletionKind::Reference,
+        "folder" => CompletionKind::Folder,
+        "enum-member" => CompletionKind::EnumMember,
+        "constant" => CompletionKind::Constant,
+        "struct" => CompletionKind::Struct,
+        "event" => CompletionKind::Event,
+        "operator" => CompletionKind::Operator,
+        "type-parameter" => CompletionKind::TypeParameter,
+        _ => bail!("unknown completion kind {name:?}"),
+    })
+}
+
+/// A worktree backed by a directory on disk, with a configured environment and set of
+/// binaries.
+struct FakeWorktree {
+    root: PathBuf,
+    env: BTreeMap<String, String>,
+    binaries: BTreeMap<String, String>,
+}
+
+impl FakeWorktree {
+    fn new(scenarios: &ScenariosFile) -> Arc<dyn WorktreeDelegate> {
+        let config = &scenarios.worktree;
+        Arc::new(Self {
+            root: match &config.root {
+                Some(root) => scenarios.base_dir.join(root),
+                None => scenarios.base_dir.clone(),
+            },
+            env: config.env.clone(),
+            binaries: config.binaries.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl WorktreeDelegate for FakeWorktree {
+    fn id(&self) -> u64 {
+        0
+    }
+
+    fn root_path(&self) -> String {
+        self.root.to_string_lossy().to_string()
+    }
+
+    async fn read_text_file(&self, path: PathBuf) -> Result<String> {
+        let path = self.root.join(path);
+        std::fs::read_to_string(&path).with_context(|| format!("failed to read {path:?}"))
+    }
+
+    async fn which(&self, binary_name: String) -> Option<String> {
+        self.binaries.get(&binary_name).cloned()
+    }
+
+    async fn shell_env(&self) -> Vec<(String, String)> {
+        self.env
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use extension::{
+        CodeLabelSpanLiteral, Command, FakeExtension, SlashCommandManifestEntry,
+        SlashCommandOutputSection,
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    fn parse(content: &str) -> ScenariosFile {
+        let mut scenarios: ScenariosFile = toml::from_str(content).unwrap();
+        scenarios.base_dir = PathBuf::from("/scenarios");
+        scenarios
+    }
+
+    async fn run_all(
+        extension: &FakeExtension,
+        manifest: &ExtensionManifest,
+        scenarios: &ScenariosFile,
+    ) -> Vec<ScenarioResult> {
+        let worktree = FakeWorktree::new(scenarios);
+        let mut results = Vec::new();
+        for scenario in &scenarios.scenarios {
+            results.push(run_scenario(extension, manifest, worktree.clone(), scenario).await);
+        }
+        results
+    }
+
+    #[test]
+    fn test_parsing_scenarios() {
+        let scenarios = parse(
+            r#"
+            [worktree]
+            root = "fixtures/project"
+            binaries = { gleam = "/usr/local/bin/gleam" }
+
+            [[scenario]]
+            name = "starts the language server"
+            kind = "language-server-command"
+            language_server = "gleam"
+            language = "Gleam"
+            "#,
+        );
+        assert_eq!(scenarios.scenarios.len(), 1);
+        assert_eq!(scenarios.scenarios[0].name, "starts the language server");
+        assert!(matches!(
+            scenarios.scenarios[0].kind,
+            ScenarioKind::LanguageServerCommand { .. }
+        ));
+
+        let worktree = FakeWorktree::new(&scenarios);
+        assert_eq!(
+            PathBuf::from(worktree.root_path()),
+            Path::new("/scenarios/fixtures/project")
+        );
+
+        assert!(toml::from_str::<ScenariosFile>("[worktree]\nunknown = true").is_err());
+        assert!(
+            toml::from_str::<ScenariosFile>(
+                "[[scenario]]\nname = \"unknown\"\nkind = \"unknown-kind\""
+            )
+            .is_err()
+        );
+    }
+
+    #[gpui::test]
+    async fn test_language_server_command_scenarios() {
+        let extension = FakeExtension::new("gleam");
+        extension.on_language_server_command(|language_server, language| {
+            assert_eq!(language_server, LanguageServerName("gleam".into()));
+            assert_eq!(language, LanguageName::new("Gleam"));
+            Ok(Command {
+                command: "/usr/local/bin/gleam".into(),
+                args: vec!["lsp".into()],
+                env: vec![("RUST_LOG".into(), "info".into())],
+            })
+        });
+        let scenarios = parse(
+            r#"
+            [[scenario]]
+            name = "starts the language server"
+            kind = "language-server-command"
+            language_server = "gleam"
+            language = "Gleam"
+            expect = { command = "/usr/local/bin/gleam", args = ["lsp"] }
+
+            [[scenario]]
+            name = "enables verbose logging"
+            kind = "language-server-command"
+            language_server = "gleam"
+            language = "Gleam"
+            expect = { args = ["lsp", "--verbose"] }
+            "#,
+        );
+
+        let results = run_all(&extension, &extension.manifest(), &scenarios).await;
+        assert_eq!(
+            serde_json::to_value(&results).unwrap(),
+            json!([
+                {
+                    "name": "starts the language server",
+                    "passed": true,
+                    "error": null,
+                    "output": {
+                        "command": "/usr/local/bin/gleam",
+                        "args": ["lsp"],
+                        "env": { "RUST_LOG": "info" },
+                    },
+                },
+                {
+                    "name": "enables verbose logging",
+                    "passed": false,
+                    "error": "expected args to be [\"lsp\", \"--verbose\"], but got [\"lsp\"]",
+                    "output": {
+                        "command": "/usr/local/bin/gleam",
+                        "args": ["lsp"],
+                        "env": { "RUST_LOG": "info" },
+                    },
+                },
+            ])
+        );
+    }
+
+    #[gpui::test]
+    async fn test_slash_command_scenarios() {
+        let extension = FakeExtension::new("docs");
+        extension.on_run_slash_command(|command, arguments| {
+            assert_eq!(command.name, "docs");
+            let text = format!("Docs for {}", arguments.join(" "));
+            Ok(extension::SlashCommandOutput {
+                sections: vec![SlashCommandOutputSection {
+                    range: 0..text.len(),
+                    label: "Docs".into(),
+                }],
+                text,
+            })
+        });
+        let mut manifest = (*extension.manifest()).clone();
+        manifest.slash_commands.insert(
+            "docs".into(),
+            SlashCommandManifestEntry {
+                description: "Inserts documentation".into(),
+                requires_argument: true,
+            },
+        );
+        let scenarios = parse(
+            r#"
+            [[scenario]]
+            name = "inserts docs"
+            kind = "slash-command"
+            command = "docs"
+            arguments = ["gpui"]
+            expect = { text_contains = "gpui", section_labels = ["Docs"] }
+
+            [[scenario]]
+            name = "runs an undeclared command"
+            kind = "slash-command"
+            command = "undeclared"
+            "#,
+        );
+
+        let results = run_all(&extension, &manifest, &scenarios).await;
+        assert!(results[0].passed, "{:?}", results[0].error);
+        assert_eq!(
+            results[0].output,
+            Some(json!({
+                "text": "Docs for gpui",
+                "sections": [{ "label": "Docs", "range": { "start": 0, "end": 13 } }],
+            }))
+        );
+        assert!(!results[1].passed);
+        assert_eq!(results[1].output, None);
+        assert_eq!(
+            results[1].error.as_deref(),
+            Some("slash command \"undeclared\" is not in the manifest")
+        );
+    }
+
+    #[gpui::test]
+    async fn test_labels_for_completions_scenarios() {
+        let extension = FakeExtension::new("gleam");
+        extension.on_labels_for_completions(|_, completions| {
+            Ok(completions
+                .into_iter()
+                .map(|completion| {
+                    let detail = completion.detail?;
+                    let code = format!("fn {}{detail}", completion.label);
+                    Some(CodeLabel {
+                        spans: vec![
+                            CodeLabelSpan::CodeRange(3..code.len()),
+                            CodeLabelSpan::Literal(CodeLabelSpanLiteral {
+                                text: " (fn)".into(),
+                                highlight_name: None,
+                            }),
+                        ],
+                        filter_range: 0..completion.label.len(),
+                        code,
+                    })
+                })
+                .collect())
+        });
+        let scenarios = parse(
+            r#"
+            [[scenario]]
+            name = "labels functions"
+            kind = "labels-for-completions"
+            language_server = "gleam"
+            completions = [
+                { label = "main", detail = "()", kind = "function" },
+                { label = "x" },
+            ]
+            expect = [{ text = "main() (fn)", filter_text = "main" }, {}]
+
+            [[scenario]]
+            name = "expects a label for every completion"
+            kind = "labels-for-completions"
+            language_server = "gleam"
+            completions = [{ label = "x" }]
+            expect = [{ text = "x" }]
+
+            [[scenario]]
+            name = "uses an unknown completion kind"
+            kind = "labels-for-completions"
+            language_server = "gleam"
+            completions = [{ label = "x", kind = "unknown" }]
+            "#,
+        );
+
+        let results = run_all(&extension, &extension.manifest(), &scenarios).await;
+        assert!(results[0].passed, "{:?}", results[0].error);
+        assert_eq!(
+            results[0].output,
+            Some(json!([
+                { "text": "main() (fn)", "filter_text": "main", "code": "fn main()" },
+                null,
+            ]))
+        );
+        assert_eq!(
+            results[1].error.as_deref(),
+            Some("expected a label for completion 0, but got none")
+        );
+        assert_eq!(
+            results[2].error.as_deref(),
+            Some("unknown completion kind \"unknown\"")
+        );
+    }
+
+    #[gpui::test]
+    async fn test_failing_extension_calls() {
+        let extension = FakeExtension::new("empty");
+        let scenarios = parse(
+            r#"
+            [[scenario]]
+            name = "starts the language server"
+            kind = "language-server-command"
+            language_server = "gleam"
+            language = "Gleam"
+            "#,
+        );
+
+        let results = run_all(&extension, &extension.manifest(), &scenarios).await;
+        assert!(!results[0].passed);
+        assert_eq!(results[0].output, None);
+        assert_eq!(
+            results[0].error.as_deref(),
+            Some("fake extension has no language servers")
+        );
+    }
+}
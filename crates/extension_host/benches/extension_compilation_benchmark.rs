@@ -37,7 +37,12 @@ fn extension_benchmarks(c: &mut Criterion) {
             |wasm_bytes| {
                 let _extension = cx
                     .executor()
-                    .block(wasm_host.load_extension(wasm_bytes, &manifest, cx.executor()))
+                    .block(wasm_host.load_extension(
+                        wasm_bytes,
+                        &manifest,
+                        Default::default(),
+                        cx.executor(),
+                    ))
                     .unwrap();
             },
             BatchSize::SmallInput,
@@ -140,6 +145,13 @@ fn manifest() -> ExtensionManifest {
         }],
         debug_adapters: Default::default(),
         debug_locators: Default::default(),
+        editor_commands: Default::default(),
+        formatters: Default::default(),
+        code_action_providers: Default::default(),
+        task_sources: Default::default(),
+        web_search_providers: Default::default(),
+        language_model_providers: Default::default(),
+        panels: Default::default(),
     }
 }
 
@@ -14,12 +14,15 @@ use collections::{BTreeMap, BTreeSet, HashMap, HashSet, btree_map};
 pub use extension::ExtensionManifest;
 use extension::extension_builder::{CompileExtensionOptions, ExtensionBuilder};
 use extension::{
-    ExtensionContextServerProxy, ExtensionDebugAdapterProviderProxy, ExtensionEvents,
-    ExtensionGrammarProxy, ExtensionHostProxy, ExtensionIndexedDocsProviderProxy,
-    ExtensionLanguageProxy, ExtensionLanguageServerProxy, ExtensionSlashCommandProxy,
-    ExtensionSnippetProxy, ExtensionThemeProxy,
+    ExtensionCodeActionProviderProxy, ExtensionContextServerProxy,
+    ExtensionDebugAdapterProviderProxy, ExtensionEditorCommandProxy, ExtensionEvents,
+    ExtensionFormatterProxy, ExtensionGrammarProxy, ExtensionHostProxy,
+    ExtensionIndexedDocsProviderProxy, ExtensionLanguageModelProviderProxy, ExtensionLanguageProxy,
+    ExtensionLanguageServerProxy, ExtensionPanelProxy, ExtensionSlashCommandProxy,
+    ExtensionSnippetProxy, ExtensionTaskSourceProxy, ExtensionThemeProxy,
+    ExtensionWebSearchProviderProxy,
 };
-use fs::{Fs, RemoveOptions};
+use fs::{Fs, RemoveOptions, Watcher};
 use futures::{
     AsyncReadExt as _, Future, FutureExt as _, StreamExt as _,
     channel::{
@@ -44,12 +47,13 @@ use release_channel::ReleaseChannel;
 use remote::SshRemoteClient;
 use semantic_version::SemanticVersion;
 use serde::{Deserialize, Serialize};
-use settings::Settings;
+use settings::{Settings, SettingsStore, update_settings_file};
 use std::ops::RangeInclusive;
 use std::str::FromStr;
 use std::{
     cmp::Ordering,
     path::{self, Path, PathBuf},
+    rc::Rc,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -63,10 +67,12 @@ use wasm_host::{
 pub use extension::{
     ExtensionLibraryKind, GrammarManifestEntry, OldExtensionManifest, SchemaVersion,
 };
+use extension_settings::ExtensionCapabilityOverrides;
 pub use extension_settings::ExtensionSettings;
 
 pub const RELOAD_DEBOUNCE_DURATION: Duration = Duration::from_millis(200);
 const FS_WATCH_LATENCY: Duration = Duration::from_millis(100);
+const DEV_EXTENSION_REBUILD_DEBOUNCE_DURATION: Duration = Duration::from_millis(500);
 
 /// The current extension [`SchemaVersion`] supported by Zed.
 const CURRENT_SCHEMA_VERSION: SchemaVersion = SchemaVersion(1);
@@ -118,6 +124,37 @@ pub struct ExtensionStore {
     pub tasks: Vec<Task<()>>,
     pub ssh_clients: HashMap<String, WeakEntity<SshRemoteClient>>,
     pub ssh_registered_tx: UnboundedSender<()>,
+    dev_extension_watchers: HashMap<Arc<str>, Task<()>>,
+    dev_extension_build_errors: HashMap<Arc<str>, String>,
+    /// Dev extensions whose sources changed while they were being rebuilt, and
+    /// whether those changes require compiling them again.
+    pending_dev_extension_rebuilds: HashMap<Arc<str>, bool>,
+    /// Replaces building dev extensions from their sources in tests, which can't run the
+    /// extension builder. It is given whether the extension is compiled.
+    #[cfg(test)]
+    dev_extension_build_override:
+        Option<Box<dyn Fn(bool) -> futures::future::BoxFuture<'static, Result<()>>>>,
+    /// The capability overrides extensions are loaded with, keyed by extension ID.
+    capability_overrides: HashMap<Arc<str>, ExtensionCapabilityOverrides>,
+    capability_prompt: Option<Rc<dyn ExtensionCapabilityPrompt>>,
+    /// The manifests of extensions whose capabilities the user couldn't be asked about yet.
+    /// These extensions are loaded without any capabilities until the user decides.
+    unconfirmed_capabilities: HashMap<Arc<str>, Arc<ExtensionManifest>>,
+    confirm_unconfirmed_capabilities_task: Option<Task<()>>,
+}
+
+/// Asks the user which of the capabilities declared by an extension to grant it.
+pub trait ExtensionCapabilityPrompt: 'static {
+    /// Resolves to the overrides to load the extension with, or `None` if the user can't
+    /// be asked right now.
+    ///
+    /// If the user dismisses the prompt, this should resolve to
+    /// [`ExtensionCapabilityOverrides::deny_all`].
+    fn prompt_for_capabilities(
+        &self,
+        manifest: Arc<ExtensionManifest>,
+        cx: &mut App,
+    ) -> Task<Option<ExtensionCapabilityOverrides>>;
 }
 
 #[derive(Clone, Copy)]
@@ -265,6 +302,17 @@ impl ExtensionStore {
 
             ssh_clients: HashMap::default(),
             ssh_registered_tx: connection_registered_tx,
+            dev_extension_watchers: HashMap::default(),
+            dev_extension_build_errors: HashMap::default(),
+            pending_dev_extension_rebuilds: HashMap::default(),
+            #[cfg(test)]
+            dev_extension_build_override: None,
+            capability_overrides: ExtensionSettings::get_global(cx)
+                .extension_capabilities
+                .clone(),
+            unconfirmed_capabilities: HashMap::default(),
+            confirm_unconfirmed_capabilities_task: None,
+            capability_prompt: None,
         };
 
         // The extensions store maintains an index file, which contains a complete
@@ -300,6 +348,32 @@ impl ExtensionStore {
             }
         }
 
+        // Reload extensions whose capability overrides change, so that the new
+        // overrides take effect.
+        cx.observe_global::<SettingsStore>(move |this, cx| {
+            let new_capability_overrides =
+                &ExtensionSettings::get_global(cx).extension_capabilities;
+            if *new_capability_overrides == this.capability_overrides {
+                return;
+            }
+
+            let changed_extension_ids = new_capability_overrides
+                .keys()
+                .chain(this.capability_overrides.keys())
+                .filter(|extension_id| {
+                    new_capability_overrides.get(*extension_id)
+                        != this.capability_overrides.get(*extension_id)
+                })
+                .cloned()
+                .collect::<HashSet<_>>();
+            this.capability_overrides = new_capability_overrides.clone();
+
+            for extension_id in changed_extension_ids {
+                drop(this.reload(Some(extension_id), cx));
+            }
+        })
+        .detach();
+
         // Immediately load all of the extensions in the initial manifest. If the
         // index needs to be rebuild, then enqueue
         let load_initial_extensions = this.extensions_updated(extension_index, cx);
@@ -433,6 +507,128 @@ impl ExtensionStore {
             .filter_map(|extension| extension.dev.then_some(&extension.manifest))
     }
 
+    /// Returns the error from the most recent build of the given dev extension, if it failed.
+    pub fn dev_extension_build_error(&self, extension_id: &str) -> Option<&str> {
+        self.dev_extension_build_errors
+            .get(extension_id)
+            .map(String::as_str)
+    }
+
+    /// Sets how the user is asked which capabilities to grant an extension before it is
+    /// first loaded.
+    ///
+    /// Without a prompt, extensions are granted the capabilities they declare.
+    pub fn set_capability_prompt(&mut self, prompt: impl ExtensionCapabilityPrompt) {
+        self.capability_prompt = Some(Rc::new(prompt));
+    }
+
+    /// Asks the user which capabilities to grant the extension with the given manifest, before
+    /// it is loaded.
+    ///
+    /// The user isn't asked again if they have already decided on the capabilities the installed
+    /// version of the extension declares. If they can't be asked, the extension is loaded
+    /// without any capabilities until [`Self::confirm_unconfirmed_capabilities`] asks again.
+    fn confirm_capabilities(
+        &mut self,
+        manifest: Arc<ExtensionManifest>,
+        cx: &mut Context<Self>,
+    ) -> Task<()> {
+        let already_decided = !self.unconfirmed_capabilities.contains_key(&manifest.id)
+            && self.capability_overrides.contains_key(&manifest.id)
+            && self
+                .extension_manifest_for_id(&manifest.id)
+                .is_none_or(|installed| installed.capabilities == manifest.capabilities);
+        if manifest.capabilities.is_empty() || already_decided {
+            return Task::ready(());
+        }
+        let Some(prompt) = self.capability_prompt.clone() else {
+            return Task::ready(());
+        };
+
+        let overrides = prompt.prompt_for_capabilities(manifest.clone(), cx);
+        let fs = self.fs.clone();
+        cx.spawn(async move |this, cx| {
+            let overrides = overrides.await;
+            this.update(cx, |this, cx| {
+                let Some(overrides) = overrides else {
+                    this.unconfirmed_capabilities
+                        .insert(manifest.id.clone(), manifest);
+                    return;
+                };
+                this.unconfirmed_capabilities.remove(&manifest.id);
+
+                // The overrides are applied immediately, rather than once the settings
+                // file has been written and reloaded, so that the extension is never
+                // loaded without them.
+                this.capability_overrides
+                    .insert(manifest.id.clone(), overrides.clone());
+                update_settings_file::<ExtensionSettings>(fs, cx, move |settings, _| {
+                    settings
+                        .extension_capabilities
+                        .insert(manifest.id.clone(), overrides);
+                });
+            })
+            .ok();
+        })
+    }
+
+    /// Asks the user about the capabilities of the extensions they couldn't be asked about
+    /// before, such as extensions that were updated while no window was open, and reloads
+    /// each extension once the user has decided.
+    pub fn confirm_unconfirmed_capabilities(&mut self, cx: &mut Context<Self>) {
+        let installed_extensions = &self.extension_index.extensions;
+        self.unconfirmed_capabilities
+            .retain(|extension_id, _| installed_extensions.contains_key(extension_id));
+        if self.unconfirmed_capabilities.is_empty()
+            || self.confirm_unconfirmed_capabilities_task.is_some()
+        {
+            return;
+        }
+
+        let manifests = self
+            .unconfirmed_capabilities
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        self.confirm_unconfirmed_capabilities_task = Some(cx.spawn(async move |this, cx| {
+            // Ask about one extension at a time, so that the prompts don't overlap.
+            for manifest in manifests {
+                let extension_id = manifest.id.clone();
+                let Ok(confirmation) =
+                    this.update(cx, |this, cx| this.confirm_capabilities(manifest, cx))
+                else {
+                    return;
+                };
+                confirmation.await;
+
+                let Ok(reload) = this.update(cx, |this, cx| {
+                    (!this.unconfirmed_capabilities.contains_key(&extension_id))
+                        .then(|| this.reload(Some(extension_id), cx))
+                }) else {
+                    return;
+                };
+                if let Some(reload) = reload {
+                    reload.await;
+                }
+            }
+            this.update(cx, |this, _| {
+                this.confirm_unconfirmed_capabilities_task = None;
+            })
+            .ok();
+        }));
+    }
+
+    /// Returns the capability overrides to load the given extension with.
+    fn capability_overrides_for(&self, extension_id: &str) -> ExtensionCapabilityOverrides {
+        if self.unconfirmed_capabilities.contains_key(extension_id) {
+            return ExtensionCapabilityOverrides::deny_all();
+        }
+        self.capability_overrides
+            .get(extension_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     pub fn extension_manifest_for_id(&self, extension_id: &str) -> Option<&Arc<ExtensionManifest>> {
         self.extension_index
             .extensions
@@ -745,8 +941,14 @@ impl ExtensionStore {
                 }
             }
             let decompressed_bytes = GzipDecoder::new(BufReader::new(tar_gz_bytes.as_slice()));
-            let archive = Archive::new(decompressed_bytes);
-            archive.unpack(extension_dir).await?;
+            futures::pin_mut!(decompressed_bytes);
+            fs.extract_tar_file(&extension_dir, Archive::new(decompressed_bytes))
+                .await?;
+
+            let manifest = ExtensionManifest::load(fs, &extension_dir).await?;
+            this.update(cx, |this, cx| this.confirm_capabilities(Arc::new(manifest), cx))?
+                .await;
+
             this.update( cx, |this, cx| {
                 this.reload(Some(extension_id.clone()), cx)
             })?
@@ -950,6 +1152,12 @@ impl ExtensionStore {
                 util::log_err(error);
             })?;
 
+            let manifest = ExtensionManifest::load(fs.clone(), &extension_source_path).await?;
+            this.update(cx, |this, cx| {
+                this.confirm_capabilities(Arc::new(manifest), cx)
+            })?
+            .await;
+
             let output_path = &extensions_dir.join(extension_id.as_ref());
             if let Some(metadata) = fs.metadata(output_path).await? {
                 if metadata.is_symlink {
@@ -986,43 +1194,212 @@ impl ExtensionStore {
     }
 
     pub fn rebuild_dev_extension(&mut self, extension_id: Arc<str>, cx: &mut Context<Self>) {
+        self.rebuild_and_reload_dev_extension(extension_id, true, cx)
+            .detach_and_log_err(cx)
+    }
+
+    /// Reloads the given dev extension in place, first rebuilding it from its
+    /// sources if `compile` is true.
+    ///
+    /// If the build fails, its error is kept so that it can be shown alongside
+    /// the extension, and the previously loaded version stays in place.
+    ///
+    /// If the extension is already being rebuilt, it is rebuilt once more after
+    /// the current build finishes, so that no changes are missed.
+    fn rebuild_and_reload_dev_extension(
+        &mut self,
+        extension_id: Arc<str>,
+        compile: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
         let path = self.installed_dir.join(extension_id.as_ref());
         let builder = self.builder.clone();
         let fs = self.fs.clone();
 
         match self.outstanding_operations.entry(extension_id.clone()) {
-            btree_map::Entry::Occupied(_) => return,
+            btree_map::Entry::Occupied(e) => {
+                if let ExtensionOperation::Upgrade = e.get() {
+                    *self
+                        .pending_dev_extension_rebuilds
+                        .entry(extension_id)
+                        .or_default() |= compile;
+                }
+                return Task::ready(Ok(()));
+            }
             btree_map::Entry::Vacant(e) => e.insert(ExtensionOperation::Upgrade),
         };
 
         cx.notify();
-        let compile = cx.background_spawn(async move {
+        let build = async move {
             let mut manifest = ExtensionManifest::load(fs, &path).await?;
-            builder
-                .compile_extension(
-                    &path,
-                    &mut manifest,
-                    CompileExtensionOptions { release: true },
-                )
-                .await
-        });
+            if compile {
+                builder
+                    .compile_extension(
+                        &path,
+                        &mut manifest,
+                        CompileExtensionOptions { release: true },
+                    )
+                    .await?;
+            }
+            anyhow::Ok(())
+        };
+        #[cfg(test)]
+        let build = match &self.dev_extension_build_override {
+            Some(build_override) => build_override(compile),
+            None => build.boxed(),
+        };
+        let build = cx.background_spawn(build);
 
         cx.spawn(async move |this, cx| {
-            let result = compile.await;
+            let result = build.await;
 
             this.update(cx, |this, cx| {
                 this.outstanding_operations.remove(&extension_id);
+                match &result {
+                    Ok(()) => {
+                        this.dev_extension_build_errors.remove(&extension_id);
+                    }
+                    Err(error) => {
+                        this.dev_extension_build_errors
+                            .insert(extension_id.clone(), format!("{error:#}"));
+                    }
+                }
+                if let Some(compile) = this.pending_dev_extension_rebuilds.remove(&extension_id) {
+                    this.rebuild_and_reload_dev_extension(extension_id.clone(), compile, cx)
+                        .detach_and_log_err(cx);
+                }
                 cx.notify();
             })?;
+            result?;
+
+            this.update(cx, |this, cx| this.reload(Some(extension_id.clone()), cx))?
+                .await;
+
+            if compile {
+                this.update(cx, |this, cx| {
+                    if let Some(events) = ExtensionEvents::try_global(cx) {
+                        if let Some(manifest) = this.extension_manifest_for_id(&extension_id) {
+                            events.update(cx, |this, cx| {
+                                this.emit(
+                                    extension::Event::DevExtensionRebuilt(manifest.clone()),
+                                    cx,
+                                )
+                            });
+                        }
+                    }
+                })?;
+            }
+
+            Ok(())
+        })
+    }
 
-            if result.is_ok() {
-                this.update(cx, |this, cx| this.reload(Some(extension_id), cx))?
-                    .await;
+    /// Starts watching the sources of any newly-installed dev extensions, and
+    /// stops watching those of dev extensions that have been removed.
+    fn watch_dev_extensions(&mut self, cx: &mut Context<Self>) {
+        let dev_extension_ids = self
+            .extension_index
+            .extensions
+            .iter()
+            .filter(|(_, entry)| entry.dev)
+            .map(|(extension_id, _)| extension_id.clone())
+            .collect::<HashSet<_>>();
+
+        self.dev_extension_watchers
+            .retain(|extension_id, _| dev_extension_ids.contains(extension_id));
+        self.dev_extension_build_errors
+            .retain(|extension_id, _| dev_extension_ids.contains(extension_id));
+        self.pending_dev_extension_rebuilds
+            .retain(|extension_id, _| dev_extension_ids.contains(extension_id));
+
+        for extension_id in dev_extension_ids {
+            if !self.dev_extension_watchers.contains_key(&extension_id) {
+                let watcher = self.watch_dev_extension(extension_id.clone(), cx);
+                self.dev_extension_watchers.insert(extension_id, watcher);
             }
+        }
+    }
+
+    /// Watches the source directory of the given dev extension, and reloads the
+    /// extension whenever its files change, rebuilding it if its Rust sources or
+    /// manifest changed.
+    fn watch_dev_extension(&self, extension_id: Arc<str>, cx: &mut Context<Self>) -> Task<()> {
+        let fs = self.fs.clone();
+        let extension_path = self.installed_dir.join(extension_id.as_ref());
+
+        cx.spawn(async move |this, cx| {
+            let Some(source_path) = fs.canonicalize(&extension_path).await.log_err() else {
+                return;
+            };
 
-            result
+            // Not every platform's file watcher is recursive, so each of the
+            // extension's directories is watched individually.
+            let (events, watcher) = fs.watch(&source_path, FS_WATCH_LATENCY).await;
+            watch_dev_extension_directories(
+                fs.as_ref(),
+                watcher.as_ref(),
+                &source_path,
+                &source_path,
+            )
+            .await;
+
+            let mut events = events.fuse();
+            while let Some(first_events) = events.next().await {
+                let mut changed_paths = first_events
+                    .into_iter()
+                    .map(|event| event.path)
+                    .collect::<BTreeSet<_>>();
+                loop {
+                    let mut debounce_timer = cx
+                        .background_executor()
+                        .timer(DEV_EXTENSION_REBUILD_DEBOUNCE_DURATION)
+                        .fuse();
+                    select_biased! {
+                        more_events = events.next() => {
+                            let Some(more_events) = more_events else { return };
+                            changed_paths.extend(more_events.into_iter().map(|event| event.path));
+                        }
+                        _ = debounce_timer => break,
+                    }
+                }
+
+                let mut compile = false;
+                let mut source_changed = false;
+                for path in &changed_paths {
+                    let Ok(relative_path) = path.strip_prefix(&source_path) else {
+                        continue;
+                    };
+                    if is_dev_extension_build_output(relative_path) {
+                        continue;
+                    }
+
+                    source_changed = true;
+                    compile |= dev_extension_change_requires_rebuild(relative_path);
+                    if fs.is_dir(path).await {
+                        watcher.add(path).log_err();
+                        watch_dev_extension_directories(
+                            fs.as_ref(),
+                            watcher.as_ref(),
+                            &source_path,
+                            path,
+                        )
+                        .await;
+                    }
+                }
+
+                if !source_changed {
+                    continue;
+                }
+
+                log::info!("reloading dev extension {extension_id} after its sources changed");
+                let Ok(reload) = this.update(cx, |this, cx| {
+                    this.rebuild_and_reload_dev_extension(extension_id.clone(), compile, cx)
+                }) else {
+                    return;
+                };
+                reload.await.log_err();
+            }
         })
-        .detach_and_log_err(cx)
     }
 
     /// Updates the set of installed extensions.
@@ -1158,6 +1535,52 @@ impl ExtensionStore {
             for (locator, _) in extension.manifest.debug_locators.iter() {
                 self.proxy.unregister_debug_locator(locator.clone());
             }
+            for command_id in extension.manifest.editor_commands.keys() {
+                self.proxy.unregister_editor_command(
+                    extension.manifest.id.clone(),
+                    command_id.clone(),
+                    cx,
+                );
+            }
+            for formatter_id in extension.manifest.formatters.keys() {
+                self.proxy.unregister_formatter(
+                    extension.manifest.id.clone(),
+                    formatter_id.clone(),
+                    cx,
+                );
+            }
+            for provider_id in extension.manifest.code_action_providers.keys() {
+                self.proxy.unregister_code_action_provider(
+                    extension.manifest.id.clone(),
+                    provider_id.clone(),
+                    cx,
+                );
+            }
+            for task_source_id in extension.manifest.task_sources.keys() {
+                self.proxy.unregister_task_source(
+                    extension.manifest.id.clone(),
+                    task_source_id.clone(),
+                    cx,
+                );
+            }
+            for provider_id in extension.manifest.web_search_providers.keys() {
+                self.proxy.unregister_web_search_provider(
+                    extension.manifest.id.clone(),
+                    provider_id.clone(),
+                    cx,
+                );
+            }
+            for provider_id in extension.manifest.language_model_providers.keys() {
+                self.proxy.unregister_language_model_provider(
+                    extension.manifest.id.clone(),
+                    provider_id.clone(),
+                    cx,
+                );
+            }
+            for panel_id in extension.manifest.panels.keys() {
+                self.proxy
+                    .unregister_panel(extension.manifest.id.clone(), panel_id.clone(), cx);
+            }
         }
 
         self.wasm_extensions
@@ -1255,6 +1678,7 @@ impl ExtensionStore {
             .filter_map(|name| new_index.extensions.get(name).cloned())
             .collect::<Vec<_>>();
         self.extension_index = new_index;
+        self.watch_dev_extensions(cx);
         cx.notify();
         cx.emit(Event::ExtensionsUpdated);
 
@@ -1294,9 +1718,15 @@ impl ExtensionStore {
                 };
 
                 let extension_path = root_dir.join(extension.manifest.id.as_ref());
+                let capability_overrides = this
+                    .read_with(cx, |this, _| {
+                        this.capability_overrides_for(&extension.manifest.id)
+                    })
+                    .unwrap_or_default();
                 let wasm_extension = WasmExtension::load(
                     extension_path,
                     &extension.manifest,
+                    capability_overrides,
                     wasm_host.clone(),
                     &cx,
                 )
@@ -1374,6 +1804,69 @@ impl ExtensionStore {
                         this.proxy
                             .register_debug_locator(extension.clone(), debug_adapter.clone());
                     }
+
+                    for (command_id, command) in &manifest.editor_commands {
+                        this.proxy.register_editor_command(
+                            extension.clone(),
+                            extension::EditorCommand {
+                                id: command_id.clone(),
+                                title: command.title.clone(),
+                            },
+                            cx,
+                        );
+                    }
+
+                    for (formatter_id, formatter) in &manifest.formatters {
+                        this.proxy.register_formatter(
+                            extension.clone(),
+                            formatter_id.clone(),
+                            formatter.languages.clone(),
+                            cx,
+                        );
+                    }
+
+                    for (provider_id, provider) in &manifest.code_action_providers {
+                        this.proxy.register_code_action_provider(
+                            extension.clone(),
+                            provider_id.clone(),
+                            provider.languages.clone(),
+                            cx,
+                        );
+                    }
+
+                    for task_source_id in manifest.task_sources.keys() {
+                        this.proxy.register_task_source(
+                            extension.clone(),
+                            task_source_id.clone(),
+                            cx,
+                        );
+                    }
+
+                    for provider_id in manifest.web_search_providers.keys() {
+                        this.proxy.register_web_search_provider(
+                            extension.clone(),
+                            provider_id.clone(),
+                            cx,
+                        );
+                    }
+
+                    for (provider_id, provider) in &manifest.language_model_providers {
+                        this.proxy.register_language_model_provider(
+                            extension.clone(),
+                            provider_id.clone(),
+                            provider.clone(),
+                            cx,
+                        );
+                    }
+
+                    for (panel_id, panel) in &manifest.panels {
+                        this.proxy.register_panel(
+                            extension.clone(),
+                            panel_id.clone(),
+                            panel.clone(),
+                            cx,
+                        );
+                    }
                 }
 
                 this.wasm_extensions.extend(wasm_extensions);
@@ -1650,10 +2143,13 @@ impl ExtensionStore {
                     if entry.manifest.language_servers.is_empty() {
                         return None;
                     }
+                    // The remote server has no settings of its own for the extension's
+                    // capabilities, so it is sent the ones the user chose here.
                     Some(proto::Extension {
                         id: id.to_string(),
                         version: entry.manifest.version.to_string(),
                         dev: entry.dev,
+                        capability_overrides: Some(this.capability_overrides_for(id).to_proto()),
                     })
                 })
                 .collect()
@@ -1735,6 +2231,60 @@ impl ExtensionStore {
     }
 }
 
+/// Returns whether the given path, relative to a dev extension's directory, is
+/// written by the extension builder, and so shouldn't cause the extension to be
+/// rebuilt when it changes.
+fn is_dev_extension_build_output(path: &Path) -> bool {
+    match path.components().next() {
+        Some(path::Component::Normal(name)) => {
+            matches!(
+                name.to_str(),
+                Some("target" | "grammars" | ".git" | "extension.wasm" | "Cargo.lock")
+            )
+        }
+        _ => true,
+    }
+}
+
+/// Returns whether a change to the given path, relative to a dev extension's
+/// directory, requires the extension to be rebuilt before it is reloaded.
+///
+/// Other files, such as languages and themes, are read when the extension is loaded.
+fn dev_extension_change_requires_rebuild(path: &Path) -> bool {
+    path == Path::new("extension.toml")
+        || path == Path::new("Cargo.toml")
+        || path.starts_with("src")
+}
+
+/// Adds each of the directories nested under `dir` to the given watcher,
+/// skipping the extension builder's outputs.
+async fn watch_dev_extension_directories(
+    fs: &dyn Fs,
+    watcher: &dyn Watcher,
+    source_path: &Path,
+    dir: &Path,
+) {
+    let mut dirs_to_scan = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs_to_scan.pop() {
+        let Ok(mut entries) = fs.read_dir(&dir).await else {
+            continue;
+        };
+        while let Some(entry) = entries.next().await {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            let Ok(relative_path) = entry.strip_prefix(source_path) else {
+                continue;
+            };
+            if is_dev_extension_build_output(relative_path) || !fs.is_dir(&entry).await {
+                continue;
+            }
+            watcher.add(&entry).log_err();
+            dirs_to_scan.push(entry);
+        }
+    }
+}
+
 fn load_plugin_queries(root_path: &Path) -> LanguageQueries {
     let mut result = LanguageQueries::default();
     if let Some(entries) = std::fs::read_dir(root_path).log_err() {
@@ -1,5 +1,7 @@
 use anyhow::Result;
+use client::proto;
 use collections::HashMap;
+use extension::ExtensionCapability;
 use gpui::App;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -18,6 +20,88 @@ pub struct ExtensionSettings {
     pub auto_install_extensions: HashMap<Arc<str>, bool>,
     #[serde(default)]
     pub auto_update_extensions: HashMap<Arc<str>, bool>,
+    /// Overrides for the capabilities granted to individual extensions, keyed by extension ID.
+    ///
+    /// Default: {}
+    #[serde(default)]
+    pub extension_capabilities: HashMap<Arc<str>, ExtensionCapabilityOverrides>,
+}
+
+/// Overrides for the capabilities an extension declares in its manifest.
+///
+/// `true` grants the capability without restriction, `false` denies it entirely,
+/// and leaving it unset uses what the extension declared.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq, JsonSchema)]
+pub struct ExtensionCapabilityOverrides {
+    /// Running processes (`process:exec`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process_exec: Option<bool>,
+    /// Making HTTP requests, including downloading files (`network:http`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_http: Option<bool>,
+    /// Reading files in worktrees (`fs:worktree`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fs_worktree: Option<bool>,
+    /// Writing to the extension's work directory (`fs:work-dir`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fs_work_dir: Option<bool>,
+    /// Accessing the paths the extension declared outside of its work directory (`fs:path`).
+    ///
+    /// Paths must always be declared, so only `false` has an effect.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fs_path: Option<bool>,
+    /// Reading environment variables (`env:read`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env_read: Option<bool>,
+}
+
+impl ExtensionCapabilityOverrides {
+    /// Returns overrides that deny every capability.
+    pub fn deny_all() -> Self {
+        Self {
+            process_exec: Some(false),
+            network_http: Some(false),
+            fs_worktree: Some(false),
+            fs_work_dir: Some(false),
+            fs_path: Some(false),
+            env_read: Some(false),
+        }
+    }
+
+    /// Denies the given capability, along with every other capability of the same kind.
+    pub fn deny(&mut self, capability: &ExtensionCapability) {
+        let overridden = match capability {
+            ExtensionCapability::ProcessExec { .. } => &mut self.process_exec,
+            ExtensionCapability::NetworkHttp { .. } => &mut self.network_http,
+            ExtensionCapability::FsWorktree => &mut self.fs_worktree,
+            ExtensionCapability::FsWorkDir => &mut self.fs_work_dir,
+            ExtensionCapability::FsPath { .. } => &mut self.fs_path,
+            ExtensionCapability::EnvRead { .. } => &mut self.env_read,
+        };
+        *overridden = Some(false);
+    }
+
+    pub fn from_proto(overrides: proto::ExtensionCapabilityOverrides) -> Self {
+        Self {
+            process_exec: overrides.process_exec,
+            network_http: overrides.network_http,
+            fs_worktree: overrides.fs_worktree,
+            fs_work_dir: overrides.fs_work_dir,
+            fs_path: overrides.fs_path,
+            env_read: overrides.env_read,
+        }
+    }
+
+    pub fn to_proto(&self) -> proto::ExtensionCapabilityOverrides {
+        proto::ExtensionCapabilityOverrides {
+            process_exec: self.process_exec,
+            network_http: self.network_http,
+            fs_worktree: self.fs_worktree,
+            fs_work_dir: self.fs_work_dir,
+            fs_path: self.fs_path,
+            env_read: self.env_read,
+        }
+    }
 }
 
 impl ExtensionSettings {
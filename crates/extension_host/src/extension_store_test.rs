@@ -1,14 +1,16 @@
 use crate::{
-    Event, ExtensionIndex, ExtensionIndexEntry, ExtensionIndexLanguageEntry,
+    DEV_EXTENSION_REBUILD_DEBOUNCE_DURATION, Event, ExtensionCapabilityOverrides,
+    ExtensionCapabilityPrompt, ExtensionIndex, ExtensionIndexEntry, ExtensionIndexLanguageEntry,
     ExtensionIndexThemeEntry, ExtensionManifest, ExtensionSettings, ExtensionStore,
     GrammarManifestEntry, RELOAD_DEBOUNCE_DURATION, SchemaVersion,
+    dev_extension_change_requires_rebuild, is_dev_extension_build_output,
 };
 use async_compression::futures::bufread::GzipEncoder;
 use collections::{BTreeMap, HashSet};
-use extension::ExtensionHostProxy;
+use extension::{ExtensionEvents, ExtensionHostProxy};
 use fs::{FakeFs, Fs, RealFs};
-use futures::{AsyncReadExt, StreamExt, io::BufReader};
-use gpui::{AppContext as _, SemanticVersion, TestAppContext};
+use futures::{AsyncReadExt, FutureExt as _, StreamExt, channel::oneshot, io::BufReader};
+use gpui::{App, AppContext as _, SemanticVersion, Task, TestAppContext};
 use http_client::{FakeHttpClient, Response};
 use language::{BinaryStatus, LanguageMatcher, LanguageRegistry};
 use lsp::LanguageServerName;
@@ -164,6 +166,13 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         capabilities: Vec::new(),
                         debug_adapters: Default::default(),
                         debug_locators: Default::default(),
+                        editor_commands: Default::default(),
+                        formatters: Default::default(),
+                        code_action_providers: Default::default(),
+                        task_sources: Default::default(),
+                        web_search_providers: Default::default(),
+                        language_model_providers: Default::default(),
+                        panels: Default::default(),
                     }),
                     dev: false,
                 },
@@ -195,6 +204,13 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         capabilities: Vec::new(),
                         debug_adapters: Default::default(),
                         debug_locators: Default::default(),
+                        editor_commands: Default::default(),
+                        formatters: Default::default(),
+                        code_action_providers: Default::default(),
+                        task_sources: Default::default(),
+                        web_search_providers: Default::default(),
+                        language_model_providers: Default::default(),
+                        panels: Default::default(),
                     }),
                     dev: false,
                 },
@@ -371,6 +387,13 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                 capabilities: Vec::new(),
                 debug_adapters: Default::default(),
                 debug_locators: Default::default(),
+                editor_commands: Default::default(),
+                formatters: Default::default(),
+                code_action_providers: Default::default(),
+                task_sources: Default::default(),
+                web_search_providers: Default::default(),
+                language_model_providers: Default::default(),
+                panels: Default::default(),
             }),
             dev: false,
         },
@@ -837,6 +860,351 @@ async fn test_extension_store_with_test_extension(cx: &mut TestAppContext) {
     assert!(fs.metadata(&expected_server_path).await.unwrap().is_none());
 }
 
+#[test]
+fn test_dev_extension_changes() {
+    for path in [
+        "target/wasm32-wasip2/debug/test_extension.wasm",
+        "grammars/gleam.wasm",
+        "grammars/gleam/src/parser.c",
+        "extension.wasm",
+        "Cargo.lock",
+    ] {
+        assert!(is_dev_extension_build_output(Path::new(path)), "{path}");
+    }
+
+    for path in ["src/lib.rs", "extension.toml", "Cargo.toml"] {
+        assert!(!is_dev_extension_build_output(Path::new(path)), "{path}");
+        assert!(
+            dev_extension_change_requires_rebuild(Path::new(path)),
+            "{path}"
+        );
+    }
+
+    for path in [
+        "languages/gleam/highlights.scm",
+        "languages/gleam/config.toml",
+        "themes/monokai.json",
+        "snippets.json",
+    ] {
+        assert!(!is_dev_extension_build_output(Path::new(path)), "{path}");
+        assert!(
+            !dev_extension_change_requires_rebuild(Path::new(path)),
+            "{path}"
+        );
+    }
+}
+
+#[gpui::test]
+async fn test_rebuilding_dev_extensions_when_sources_change(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree("/the-extension-dir", json!({ "installed": {} }))
+        .await;
+    fs.insert_tree(
+        "/dev-extensions/dev-extension",
+        json!({
+            "extension.toml": r#"
+                id = "dev-extension"
+                name = "Dev Extension"
+                version = "1.0.0"
+                schema_version = 1
+            "#,
+            "src": {
+                "lib.rs": "",
+            },
+            "languages": {
+                "dev-language": {
+                    "config.toml": r#"name = "Dev Language""#,
+                    "highlights.scm": "",
+                },
+            },
+        }),
+    )
+    .await;
+    fs.insert_symlink(
+        "/the-extension-dir/installed/dev-extension",
+        PathBuf::from("/dev-extensions/dev-extension"),
+    )
+    .await;
+
+    let http_client = FakeHttpClient::with_200_response();
+    let store = cx.new(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            Arc::new(ExtensionHostProxy::new()),
+            fs.clone(),
+            http_client.clone(),
+            http_client,
+            None,
+            NodeRuntime::unavailable(),
+            cx,
+        )
+    });
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    store.read_with(cx, |store, _| {
+        assert!(store.installed_extensions()["dev-extension"].dev);
+    });
+
+    // Record each build instead of running the extension builder, and let the test decide
+    // when a build finishes.
+    let builds = Arc::new(Mutex::new(Vec::new()));
+    let build_blocker = Arc::new(Mutex::new(None::<oneshot::Receiver<()>>));
+    store.update(cx, |store, _| {
+        let builds = builds.clone();
+        let build_blocker = build_blocker.clone();
+        store.dev_extension_build_override = Some(Box::new(move |compile| {
+            builds.lock().push(compile);
+            let build_blocker = build_blocker.lock().take();
+            async move {
+                if let Some(build_blocker) = build_blocker {
+                    build_blocker.await.ok();
+                }
+                anyhow::Ok(())
+            }
+            .boxed()
+        }));
+    });
+
+    let rebuilt_events = Arc::new(Mutex::new(0));
+    cx.update(|cx| {
+        let rebuilt_events = rebuilt_events.clone();
+        cx.subscribe(
+            &ExtensionEvents::try_global(cx).unwrap(),
+            move |_, event, _| {
+                if let extension::Event::DevExtensionRebuilt(manifest) = event {
+                    assert_eq!(manifest.id.as_ref(), "dev-extension");
+                    *rebuilt_events.lock() += 1;
+                }
+            },
+        )
+        .detach();
+    });
+    let reloads = Arc::new(Mutex::new(0));
+    cx.update(|cx| {
+        let reloads = reloads.clone();
+        cx.subscribe(&store, move |_, event, _| {
+            if let Event::StartedReloading = event {
+                *reloads.lock() += 1;
+            }
+        })
+        .detach();
+    });
+
+    // Changing the Rust sources compiles the extension before reloading it.
+    fs.insert_file("/dev-extensions/dev-extension/src/lib.rs", b"// 1".to_vec())
+        .await;
+    finish_dev_extension_rebuilds(cx);
+    assert_eq!(*builds.lock(), [true]);
+    assert_eq!(*rebuilt_events.lock(), 1);
+    assert_eq!(*reloads.lock(), 1);
+
+    // Changing a language only reloads the extension.
+    fs.insert_file(
+        "/dev-extensions/dev-extension/languages/dev-language/highlights.scm",
+        b"(identifier) @variable".to_vec(),
+    )
+    .await;
+    finish_dev_extension_rebuilds(cx);
+    assert_eq!(*builds.lock(), [true, false]);
+    assert_eq!(*rebuilt_events.lock(), 1);
+    assert_eq!(*reloads.lock(), 2);
+
+    // Changes made while the extension is being built cause one more build once it finishes.
+    let (finish_build, build_finished) = oneshot::channel();
+    *build_blocker.lock() = Some(build_finished);
+    fs.insert_file("/dev-extensions/dev-extension/src/lib.rs", b"// 2".to_vec())
+        .await;
+    cx.executor()
+        .advance_clock(DEV_EXTENSION_REBUILD_DEBOUNCE_DURATION);
+    assert_eq!(*builds.lock(), [true, false, true]);
+
+    fs.insert_file("/dev-extensions/dev-extension/src/lib.rs", b"// 3".to_vec())
+        .await;
+    fs.insert_file("/dev-extensions/dev-extension/Cargo.toml", b"".to_vec())
+        .await;
+    cx.executor()
+        .advance_clock(DEV_EXTENSION_REBUILD_DEBOUNCE_DURATION);
+    assert_eq!(*builds.lock(), [true, false, true]);
+
+    finish_build.send(()).unwrap();
+    finish_dev_extension_rebuilds(cx);
+    assert_eq!(*builds.lock(), [true, false, true, true]);
+    assert_eq!(*rebuilt_events.lock(), 3);
+    store.read_with(cx, |store, _| {
+        assert!(store.outstanding_operations().is_empty());
+        assert_eq!(store.dev_extension_build_error("dev-extension"), None);
+    });
+}
+
+/// Advances the clock until the dev extension watcher has handled every change to the
+/// sources, waiting for each rebuild to be debounced, built and reloaded in turn.
+fn finish_dev_extension_rebuilds(cx: &mut TestAppContext) {
+    for _ in 0..5 {
+        cx.executor()
+            .advance_clock(DEV_EXTENSION_REBUILD_DEBOUNCE_DURATION + RELOAD_DEBOUNCE_DURATION);
+    }
+}
+
+#[gpui::test]
+async fn test_capability_prompt_on_install(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    struct FakeCapabilityPrompt {
+        prompted_extensions: Arc<Mutex<Vec<Arc<str>>>>,
+        response: Arc<Mutex<Option<ExtensionCapabilityOverrides>>>,
+    }
+
+    impl ExtensionCapabilityPrompt for FakeCapabilityPrompt {
+        fn prompt_for_capabilities(
+            &self,
+            manifest: Arc<ExtensionManifest>,
+            _: &mut App,
+        ) -> Task<Option<ExtensionCapabilityOverrides>> {
+            self.prompted_extensions.lock().push(manifest.id.clone());
+            Task::ready(self.response.lock().clone())
+        }
+    }
+
+    let fs = FakeFs::new(cx.executor());
+    fs.create_dir(Path::new("/the-extension-dir/installed"))
+        .await
+        .unwrap();
+    fs.create_dir(paths::config_dir()).await.unwrap();
+
+    let extension_toml = Arc::new(Mutex::new(String::new()));
+    let set_extension_toml = |version: &str, capabilities: &str| {
+        *extension_toml.lock() = format!(
+            r#"
+                id = "exec-extension"
+                name = "Exec Extension"
+                version = "{version}"
+                schema_version = 1
+                capabilities = [{capabilities}]
+            "#
+        );
+    };
+    let http_client = FakeHttpClient::create({
+        let extension_toml = extension_toml.clone();
+        move |_| {
+            let extension_toml = extension_toml.lock().clone();
+            async move {
+                let mut bytes = Vec::<u8>::new();
+                let mut archive = async_tar::Builder::new(&mut bytes);
+                let mut header = async_tar::Header::new_gnu();
+                header.set_size(extension_toml.len() as u64);
+                archive
+                    .append_data(&mut header, "extension.toml", extension_toml.as_bytes())
+                    .await
+                    .unwrap();
+                archive.into_inner().await.unwrap();
+                let mut gzipped_bytes = Vec::new();
+                let mut encoder = GzipEncoder::new(BufReader::new(bytes.as_slice()));
+                encoder.read_to_end(&mut gzipped_bytes).await.unwrap();
+                Ok(Response::new(gzipped_bytes.into()))
+            }
+        }
+    });
+
+    let proxy = Arc::new(ExtensionHostProxy::new());
+    let store = cx.new(|cx| {
+        ExtensionStore::new(
+            PathBuf::from("/the-extension-dir"),
+            None,
+            proxy,
+            fs.clone(),
+            http_client.clone(),
+            http_client,
+            None,
+            NodeRuntime::unavailable(),
+            cx,
+        )
+    });
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+
+    let prompted_extensions = Arc::new(Mutex::new(Vec::new()));
+    let response = Arc::new(Mutex::new(None));
+    store.update(cx, |store, _| {
+        store.set_capability_prompt(FakeCapabilityPrompt {
+            prompted_extensions: prompted_extensions.clone(),
+            response: response.clone(),
+        })
+    });
+
+    // When the user can't be asked, the extension is installed without any capabilities.
+    let exec_capability = r#"{ kind = "process:exec", command = "echo", args = ["**"] }"#;
+    set_extension_toml("1.0.0", exec_capability);
+    store.update(cx, |store, cx| {
+        store.install_extension("exec-extension".into(), "1.0.0".into(), cx)
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert_eq!(
+        *prompted_extensions.lock(),
+        [Arc::<str>::from("exec-extension")]
+    );
+    store.read_with(cx, |store, _| {
+        assert!(store.installed_extensions().contains_key("exec-extension"));
+        assert_eq!(
+            store.capability_overrides_for("exec-extension"),
+            ExtensionCapabilityOverrides::deny_all()
+        );
+    });
+    assert!(!fs.is_file(paths::settings_file()).await);
+
+    // Once the user can be asked, their decision is applied and persisted.
+    let overrides = ExtensionCapabilityOverrides {
+        process_exec: Some(true),
+        ..Default::default()
+    };
+    *response.lock() = Some(overrides.clone());
+    store.update(cx, |store, cx| store.confirm_unconfirmed_capabilities(cx));
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert_eq!(prompted_extensions.lock().len(), 2);
+    store.read_with(cx, |store, _| {
+        assert_eq!(store.capability_overrides_for("exec-extension"), overrides);
+    });
+    let settings = fs.load(paths::settings_file()).await.unwrap();
+    assert!(settings.contains("extension_capabilities"), "{settings}");
+
+    // Upgrading to a version that declares the same capabilities doesn't ask again.
+    set_extension_toml("1.1.0", exec_capability);
+    store.update(cx, |store, cx| {
+        store
+            .upgrade_extension("exec-extension".into(), "1.1.0".into(), cx)
+            .detach_and_log_err(cx)
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert_eq!(prompted_extensions.lock().len(), 2);
+
+    // Upgrading to a version that declares new capabilities asks again.
+    set_extension_toml(
+        "1.2.0",
+        &format!(r#"{exec_capability}, {{ kind = "network:http", hosts = ["example.com"] }}"#),
+    );
+    store.update(cx, |store, cx| {
+        store
+            .upgrade_extension("exec-extension".into(), "1.2.0".into(), cx)
+            .detach_and_log_err(cx)
+    });
+    cx.executor().run_until_parked();
+    cx.executor().advance_clock(RELOAD_DEBOUNCE_DURATION);
+    assert_eq!(prompted_extensions.lock().len(), 3);
+    store.read_with(cx, |store, _| {
+        assert_eq!(
+            store
+                .extension_manifest_for_id("exec-extension")
+                .unwrap()
+                .version
+                .as_ref(),
+            "1.2.0"
+        );
+    });
+}
+
 fn init_test(cx: &mut TestAppContext) {
     cx.update(|cx| {
         let store = SettingsStore::test(cx);
@@ -14,13 +14,41 @@ use language::{LanguageConfig, LanguageName, LanguageQueries, LoadedLanguage};
 use lsp::LanguageServerName;
 use node_runtime::NodeRuntime;
 
-use crate::wasm_host::{WasmExtension, WasmHost};
+use crate::{
+    extension_settings::ExtensionCapabilityOverrides,
+    wasm_host::{WasmExtension, WasmHost},
+};
 
 #[derive(Clone, Debug)]
 pub struct ExtensionVersion {
     pub id: String,
     pub version: String,
     pub dev: bool,
+    /// The capability overrides from the settings of the client the extension was synced from.
+    pub capability_overrides: ExtensionCapabilityOverrides,
+}
+
+impl ExtensionVersion {
+    fn from_proto(extension: proto::Extension) -> Self {
+        Self {
+            id: extension.id,
+            version: extension.version,
+            dev: extension.dev,
+            capability_overrides: extension
+                .capability_overrides
+                .map(ExtensionCapabilityOverrides::from_proto)
+                .unwrap_or_default(),
+        }
+    }
+
+    fn to_proto(&self) -> proto::Extension {
+        proto::Extension {
+            id: self.id.clone(),
+            version: self.version.clone(),
+            dev: self.dev,
+            capability_overrides: Some(self.capability_overrides.to_proto()),
+        }
+    }
 }
 
 pub struct HeadlessExtensionStore {
@@ -29,6 +57,7 @@ pub struct HeadlessExtensionStore {
     pub proxy: Arc<ExtensionHostProxy>,
     pub wasm_host: Arc<WasmHost>,
     pub loaded_extensions: HashMap<Arc<str>, Arc<str>>,
+    pub loaded_capability_overrides: HashMap<Arc<str>, ExtensionCapabilityOverrides>,
     pub loaded_languages: HashMap<Arc<str>, Vec<LanguageName>>,
     pub loaded_language_servers: HashMap<Arc<str>, Vec<(LanguageServerName, LanguageName)>>,
 }
@@ -55,6 +84,7 @@ impl HeadlessExtensionStore {
             extension_dir,
             proxy: extension_host_proxy,
             loaded_extensions: Default::default(),
+            loaded_capability_overrides: Default::default(),
             loaded_languages: Default::default(),
             loaded_language_servers: Default::default(),
         })
@@ -81,6 +111,10 @@ impl HeadlessExtensionStore {
                 self.loaded_extensions
                     .get(e.id.as_str())
                     .is_none_or(|loaded| loaded.as_ref() != e.version.as_str())
+                    || self
+                        .loaded_capability_overrides
+                        .get(e.id.as_str())
+                        .is_none_or(|loaded| *loaded != e.capability_overrides)
             })
             .collect();
 
@@ -112,10 +146,16 @@ impl HeadlessExtensionStore {
         cx: &mut AsyncApp,
     ) -> Result<()> {
         let (fs, wasm_host, extension_dir) = this.update(cx, |this, _cx| {
+            // The extension may be reloaded with different capability overrides.
+            this.unload_extension(&extension.id.clone().into());
             this.loaded_extensions.insert(
                 extension.id.clone().into(),
                 extension.version.clone().into(),
             );
+            this.loaded_capability_overrides.insert(
+                extension.id.clone().into(),
+                extension.capability_overrides.clone(),
+            );
             (
                 this.fs.clone(),
                 this.wasm_host.clone(),
@@ -169,8 +209,16 @@ impl HeadlessExtensionStore {
             return Ok(());
         }
 
-        let wasm_extension: Arc<dyn Extension> =
-            Arc::new(WasmExtension::load(extension_dir, &manifest, wasm_host.clone(), &cx).await?);
+        let wasm_extension: Arc<dyn Extension> = Arc::new(
+            WasmExtension::load(
+                extension_dir,
+                &manifest,
+                extension.capability_overrides,
+                wasm_host.clone(),
+                &cx,
+            )
+            .await?,
+        );
 
         for (language_server_id, language_server_config) in &manifest.language_servers {
             for language in language_server_config.languages() {
@@ -196,7 +244,26 @@ impl HeadlessExtensionStore {
         extension_id: &Arc<str>,
         cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
+        self.unload_extension(extension_id);
+
+        let path = self.extension_dir.join(&extension_id.to_string());
+        let fs = self.fs.clone();
+        cx.spawn(async move |_, _| {
+            fs.remove_dir(
+                &path,
+                RemoveOptions {
+                    recursive: true,
+                    ignore_if_not_exists: true,
+                },
+            )
+            .await
+        })
+    }
+
+    /// Removes the languages and language servers of a loaded extension, leaving its files in place.
+    fn unload_extension(&mut self, extension_id: &Arc<str>) {
         self.loaded_extensions.remove(extension_id);
+        self.loaded_capability_overrides.remove(extension_id);
 
         let languages_to_remove = self
             .loaded_languages
@@ -212,19 +279,6 @@ impl HeadlessExtensionStore {
             self.proxy
                 .remove_language_server(&language, &language_server_name);
         }
-
-        let path = self.extension_dir.join(&extension_id.to_string());
-        let fs = self.fs.clone();
-        cx.spawn(async move |_, _| {
-            fs.remove_dir(
-                &path,
-                RemoveOptions {
-                    recursive: true,
-                    ignore_if_not_exists: true,
-                },
-            )
-            .await
-        })
     }
 
     pub fn install_extension(
@@ -256,16 +310,11 @@ impl HeadlessExtensionStore {
         envelope: TypedEnvelope<proto::SyncExtensions>,
         mut cx: AsyncApp,
     ) -> Result<proto::SyncExtensionsResponse> {
-        let requested_extensions =
-            envelope
-                .payload
-                .extensions
-                .into_iter()
-                .map(|p| ExtensionVersion {
-                    id: p.id,
-                    version: p.version,
-                    dev: p.dev,
-                });
+        let requested_extensions = envelope
+            .payload
+            .extensions
+            .into_iter()
+            .map(ExtensionVersion::from_proto);
         let missing_extensions = extension_store
             .update(&mut cx, |extension_store, cx| {
                 extension_store.sync_extensions(requested_extensions.collect(), cx)
@@ -274,12 +323,8 @@ impl HeadlessExtensionStore {
 
         Ok(proto::SyncExtensionsResponse {
             missing_extensions: missing_extensions
-                .into_iter()
-                .map(|e| proto::Extension {
-                    id: e.id,
-                    version: e.version,
-                    dev: e.dev,
-                })
+                .iter()
+                .map(ExtensionVersion::to_proto)
                 .collect(),
             tmp_dir: paths::remote_extensions_uploads_dir()
                 .to_string_lossy()
@@ -300,11 +345,7 @@ impl HeadlessExtensionStore {
         extensions
             .update(&mut cx, |extensions, cx| {
                 extensions.install_extension(
-                    ExtensionVersion {
-                        id: extension.id,
-                        version: extension.version,
-                        dev: extension.dev,
-                    },
+                    ExtensionVersion::from_proto(extension),
                     PathBuf::from(envelope.payload.tmp_dir),
                     cx,
                 )
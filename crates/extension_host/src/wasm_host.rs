@@ -1,13 +1,19 @@
+mod capability_granter;
 pub mod wit;
 
 use crate::ExtensionManifest;
+use crate::extension_settings::ExtensionCapabilityOverrides;
 use anyhow::{Context as _, Result, anyhow, bail};
 use async_trait::async_trait;
+use capability_granter::CapabilityGranter;
 use dap::{DebugRequest, StartDebuggingRequestArgumentsRequest};
 use extension::{
-    CodeLabel, Command, Completion, ContextServerConfiguration, DebugAdapterBinary,
-    DebugTaskDefinition, ExtensionHostProxy, KeyValueStoreDelegate, ProjectDelegate, SlashCommand,
-    SlashCommandArgumentCompletion, SlashCommandOutput, Symbol, WorktreeDelegate,
+    BufferDelegate, BufferEdit, CodeAction, CodeLabel, Command, Completion,
+    ContextServerConfiguration, DebugAdapterBinary, DebugTaskDefinition, Diagnostic, EnvVars,
+    ExtensionHostProxy, FormattingOptions, KeyValueStoreDelegate, LlmCompletionRequest,
+    LlmCompletionStreamDelegate, LlmModel, PanelEvent, PanelView, ProjectDelegate, SlashCommand,
+    SlashCommandArgumentCompletion, SlashCommandOutput, Symbol, WebSearchResult, WorktreeDelegate,
+    WorktreeTasks,
 };
 use fs::{Fs, normalize_path};
 use futures::future::LocalBoxFuture;
@@ -20,7 +26,11 @@ use futures::{
     future::BoxFuture,
 };
 use gpui::{App, AsyncApp, BackgroundExecutor, Task, Timer};
-use http_client::HttpClient;
+use http_client::http::header;
+use http_client::{
+    AsyncBody, HttpClient, HttpRequestExt as _, Method, RedirectPolicy, Request, Response,
+    StatusCode, Url,
+};
 use language::LanguageName;
 use lsp::LanguageServerName;
 use moka::sync::Cache;
@@ -31,6 +41,7 @@ use std::borrow::Cow;
 use std::sync::{LazyLock, OnceLock};
 use std::time::Duration;
 use std::{
+    ops::Range,
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -473,10 +484,255 @@ impl extension::Extension for WasmExtension {
         })
         .await
     }
+
+    async fn run_editor_command(
+        &self,
+        command_id: Arc<str>,
+        buffer: Arc<dyn BufferDelegate>,
+    ) -> Result<()> {
+        self.call(|extension, store| {
+            async move {
+                let buffer_resource = store.data_mut().table().push(buffer)?;
+                extension
+                    .call_run_editor_command(store, command_id.as_ref(), buffer_resource)
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?;
+
+                anyhow::Ok(())
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn format_buffer(
+        &self,
+        formatter_id: Arc<str>,
+        language_name: LanguageName,
+        text: String,
+        options: FormattingOptions,
+    ) -> Result<Vec<BufferEdit>> {
+        self.call(|extension, store| {
+            async move {
+                let edits = extension
+                    .call_format_buffer(
+                        store,
+                        formatter_id.as_ref(),
+                        &language_name,
+                        &text,
+                        options.into(),
+                    )
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?;
+
+                Ok(edits.into_iter().map(Into::into).collect())
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn code_actions(
+        &self,
+        provider_id: Arc<str>,
+        language_name: LanguageName,
+        text: String,
+        range: Range<usize>,
+        diagnostics: Vec<Diagnostic>,
+    ) -> Result<Vec<CodeAction>> {
+        self.call(|extension, store| {
+            async move {
+                let diagnostics = diagnostics.into_iter().map(Into::into).collect::<Vec<_>>();
+                let code_actions = extension
+                    .call_code_actions(
+                        store,
+                        provider_id.as_ref(),
+                        &language_name,
+                        &text,
+                        range.into(),
+                        &diagnostics,
+                    )
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?;
+
+                Ok(code_actions.into_iter().map(Into::into).collect())
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn worktree_tasks(
+        &self,
+        task_source_id: Arc<str>,
+        worktree: Arc<dyn WorktreeDelegate>,
+    ) -> Result<WorktreeTasks> {
+        self.call(|extension, store| {
+            async move {
+                let resource = store.data_mut().table().push(worktree)?;
+                let tasks = extension
+                    .call_worktree_tasks(store, task_source_id.as_ref(), resource)
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?;
+
+                Ok(tasks.into())
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn task_variables(
+        &self,
+        task_source_id: Arc<str>,
+        worktree: Arc<dyn WorktreeDelegate>,
+        path: Option<PathBuf>,
+    ) -> Result<EnvVars> {
+        self.call(|extension, store| {
+            async move {
+                let resource = store.data_mut().table().push(worktree)?;
+                let path = path.map(|path| path.to_string_lossy().into_owned());
+                let variables = extension
+                    .call_task_variables(store, task_source_id.as_ref(), resource, path.as_deref())
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?;
+
+                Ok(variables)
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn web_search(
+        &self,
+        provider_id: Arc<str>,
+        query: String,
+    ) -> Result<Vec<WebSearchResult>> {
+        self.call(|extension, store| {
+            async move {
+                let results = extension
+                    .call_web_search(store, provider_id.as_ref(), &query)
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?;
+
+                Ok(results.into_iter().map(Into::into).collect())
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn llm_provider_models(&self, provider_id: Arc<str>) -> Result<Vec<LlmModel>> {
+        self.call(|extension, store| {
+            async move {
+                let models = extension
+                    .call_llm_provider_models(store, provider_id.as_ref())
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?;
+
+                Ok(models.into_iter().map(Into::into).collect())
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn llm_count_tokens(
+        &self,
+        provider_id: Arc<str>,
+        model_id: Arc<str>,
+        request: LlmCompletionRequest,
+    ) -> Result<u64> {
+        self.call(|extension, store| {
+            async move {
+                let token_count = extension
+                    .call_llm_count_tokens(
+                        store,
+                        provider_id.as_ref(),
+                        model_id.as_ref(),
+                        &request.into(),
+                    )
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?;
+
+                Ok(token_count)
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn llm_stream_completion(
+        &self,
+        provider_id: Arc<str>,
+        model_id: Arc<str>,
+        request: LlmCompletionRequest,
+        stream: Arc<dyn LlmCompletionStreamDelegate>,
+    ) -> Result<()> {
+        self.call(|extension, store| {
+            async move {
+                let stream_resource = store.data_mut().table().push(stream)?;
+                extension
+                    .call_llm_stream_completion(
+                        store,
+                        provider_id.as_ref(),
+                        model_id.as_ref(),
+                        &request.into(),
+                        stream_resource,
+                    )
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?;
+
+                anyhow::Ok(())
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn render_panel(&self, panel_id: Arc<str>) -> Result<PanelView> {
+        self.call(|extension, store| {
+            async move {
+                let view: PanelView = extension
+                    .call_render_panel(store, panel_id.as_ref())
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?
+                    .into();
+                view.validate()?;
+
+                Ok(view)
+            }
+            .boxed()
+        })
+        .await
+    }
+
+    async fn handle_panel_event(&self, panel_id: Arc<str>, event: PanelEvent) -> Result<PanelView> {
+        self.call(|extension, store| {
+            async move {
+                let view: PanelView = extension
+                    .call_handle_panel_event(store, panel_id.as_ref(), &event.into())
+                    .await?
+                    .map_err(|err| store.data().extension_error(err))?
+                    .into();
+                view.validate()?;
+
+                Ok(view)
+            }
+            .boxed()
+        })
+        .await
+    }
 }
 
+/// The maximum number of redirects followed for an extension's HTTP request, when it asks
+/// for all of them to be followed.
+const MAX_HTTP_REDIRECTS: u32 = 20;
+
 pub struct WasmState {
     manifest: Arc<ExtensionManifest>,
+    capability_granter: CapabilityGranter,
     pub table: ResourceTable,
     ctx: wasi::WasiCtx,
     pub host: Arc<WasmHost>,
@@ -574,6 +830,7 @@ impl WasmHost {
         self: &Arc<Self>,
         wasm_bytes: Vec<u8>,
         manifest: &Arc<ExtensionManifest>,
+        capability_overrides: ExtensionCapabilityOverrides,
         executor: BackgroundExecutor,
     ) -> Task<Result<WasmExtension>> {
         let this = self.clone();
@@ -584,11 +841,15 @@ impl WasmHost {
             let component = Component::from_binary(&this.engine, &wasm_bytes)
                 .context("failed to compile wasm component")?;
 
+            let capability_granter =
+                CapabilityGranter::new(manifest.clone(), zed_api_version, capability_overrides);
+
             let mut store = wasmtime::Store::new(
                 &this.engine,
                 WasmState {
-                    ctx: this.build_wasi_ctx(&manifest).await?,
+                    ctx: this.build_wasi_ctx(&manifest, &capability_granter).await?,
                     manifest: manifest.clone(),
+                    capability_granter,
                     table: ResourceTable::new(),
                     host: this.clone(),
                 },
@@ -629,7 +890,11 @@ impl WasmHost {
         })
     }
 
-    async fn build_wasi_ctx(&self, manifest: &Arc<ExtensionManifest>) -> Result<wasi::WasiCtx> {
+    async fn build_wasi_ctx(
+        &self,
+        manifest: &Arc<ExtensionManifest>,
+        capability_granter: &CapabilityGranter,
+    ) -> Result<wasi::WasiCtx> {
         let extension_work_dir = self.work_dir.join(manifest.id.as_ref());
         self.fs
             .create_dir(&extension_work_dir)
@@ -639,15 +904,37 @@ impl WasmHost {
         let file_perms = wasi::FilePerms::all();
         let dir_perms = wasi::DirPerms::all();
 
-        Ok(wasi::WasiCtxBuilder::new()
-            .inherit_stdio()
-            .preopened_dir(&extension_work_dir, ".", dir_perms, file_perms)?
-            .preopened_dir(
-                &extension_work_dir,
-                extension_work_dir.to_string_lossy(),
-                dir_perms,
-                file_perms,
-            )?
+        let mut builder = wasi::WasiCtxBuilder::new();
+        builder.inherit_stdio();
+
+        if capability_granter.grant_work_dir().is_ok() {
+            builder
+                .preopened_dir(&extension_work_dir, ".", dir_perms, file_perms)?
+                .preopened_dir(
+                    &extension_work_dir,
+                    extension_work_dir.to_string_lossy(),
+                    dir_perms,
+                    file_perms,
+                )?;
+        }
+
+        for (path, read_only) in capability_granter.granted_paths() {
+            let (dir_perms, file_perms) = if read_only {
+                (wasi::DirPerms::READ, wasi::FilePerms::READ)
+            } else {
+                (dir_perms, file_perms)
+            };
+            if let Err(error) =
+                builder.preopened_dir(path, path.to_string_lossy(), dir_perms, file_perms)
+            {
+                log::warn!(
+                    "failed to grant extension {} access to {path:?}: {error:#}",
+                    manifest.id
+                );
+            }
+        }
+
+        Ok(builder
             .env("PWD", extension_work_dir.to_string_lossy())
             .env("RUST_BACKTRACE", "full")
             .build())
@@ -711,6 +998,7 @@ impl WasmExtension {
     pub async fn load(
         extension_dir: PathBuf,
         manifest: &Arc<ExtensionManifest>,
+        capability_overrides: ExtensionCapabilityOverrides,
         wasm_host: Arc<WasmHost>,
         cx: &AsyncApp,
     ) -> Result<Self> {
@@ -728,7 +1016,12 @@ impl WasmExtension {
             .context("failed to read wasm")?;
 
         wasm_host
-            .load_extension(wasm_bytes, manifest, cx.background_executor().clone())
+            .load_extension(
+                wasm_bytes,
+                manifest,
+                capability_overrides,
+                cx.background_executor().clone(),
+            )
             .await
             .with_context(|| format!("failed to load wasm extension {}", manifest.id))
     }
@@ -780,6 +1073,16 @@ impl WasmState {
         self.host.work_dir.join(self.manifest.id.as_ref())
     }
 
+    /// Sends an HTTP request on behalf of the extension.
+    async fn send_http_request(&self, request: Request<Vec<u8>>) -> Result<Response<AsyncBody>> {
+        send_http_request(
+            self.host.http_client.as_ref(),
+            &self.capability_granter,
+            request,
+        )
+        .await
+    }
+
     fn extension_error(&self, message: String) -> anyhow::Error {
         anyhow!(
             "from extension \"{}\" version {}: {}",
@@ -800,6 +1103,69 @@ impl wasi::WasiView for WasmState {
     }
 }
 
+/// Sends an HTTP request on behalf of an extension.
+///
+/// Redirects are followed here, rather than by the HTTP client, so that every URL the
+/// request is redirected to is checked against the extension's `network:http` capability.
+async fn send_http_request(
+    http_client: &dyn HttpClient,
+    capability_granter: &CapabilityGranter,
+    request: Request<Vec<u8>>,
+) -> Result<Response<AsyncBody>> {
+    let redirect_limit = match request.extensions().get::<RedirectPolicy>() {
+        None | Some(RedirectPolicy::NoFollow) => None,
+        Some(RedirectPolicy::FollowLimit(limit)) => Some(*limit),
+        Some(RedirectPolicy::FollowAll) => Some(MAX_HTTP_REDIRECTS),
+    };
+    let (parts, mut body) = request.into_parts();
+    let mut method = parts.method;
+    let mut headers = parts.headers;
+    let mut url = Url::parse(&parts.uri.to_string())?;
+    let mut redirect_count = 0;
+
+    loop {
+        capability_granter.grant_http(url.as_str())?;
+
+        let mut request = Request::builder()
+            .method(method.clone())
+            .uri(url.as_str())
+            .follow_redirects(RedirectPolicy::NoFollow)
+            .body(AsyncBody::from(body.clone()))?;
+        *request.headers_mut() = headers.clone();
+        let response = http_client.send(request).await?;
+
+        let status = response.status();
+        let Some(redirect_limit) = redirect_limit.filter(|_| status.is_redirection()) else {
+            return Ok(response);
+        };
+        let Some(location) = response.headers().get(header::LOCATION) else {
+            return Ok(response);
+        };
+        anyhow::ensure!(
+            redirect_count < redirect_limit,
+            "too many redirects when requesting {url}"
+        );
+        redirect_count += 1;
+
+        let location = url
+            .join(location.to_str()?)
+            .context("invalid redirect location")?;
+        if location.host_str() != url.host_str() {
+            headers.remove(header::AUTHORIZATION);
+            headers.remove(header::PROXY_AUTHORIZATION);
+            headers.remove(header::COOKIE);
+        }
+        if status == StatusCode::SEE_OTHER
+            || (method == Method::POST
+                && matches!(status, StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND))
+        {
+            method = Method::GET;
+            body = Vec::new();
+        }
+        url = location;
+    }
+}
+
 /// Wrapper around a mini-moka bounded cache for storing incremental compilation artifacts.
 /// Since wasm modules have many similar elements, this can save us a lot of work at the
 /// cost of a small memory footprint. However, we don't want this to be unbounded, so we use
@@ -832,3 +1198,105 @@ impl CacheStore for IncrementalCompilationCache {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_client::FakeHttpClient;
+    use parking_lot::Mutex;
+
+    #[gpui::test]
+    async fn test_http_redirects_are_checked_against_capabilities() {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let http_client = FakeHttpClient::create({
+            let requests = requests.clone();
+            move |request| {
+                let requests = requests.clone();
+                async move {
+                    let url = request.uri().to_string();
+                    let has_authorization = request.headers().contains_key(header::AUTHORIZATION);
+                    requests.lock().push((url.clone(), has_authorization));
+                    let location = match url.as_str() {
+                        "https://example.com/allowed" => "https://downloads.example.com/file",
+                        "https://example.com/denied" => "https://elsewhere.test/file",
+                        _ => return Ok(Response::builder().body(Default::default())?),
+                    };
+                    Ok(Response::builder()
+                        .status(StatusCode::FOUND)
+                        .header(header::LOCATION, location)
+                        .body(Default::default())?)
+                }
+            }
+        });
+        let manifest = toml::from_str::<ExtensionManifest>(
+            r#"
+                id = "test"
+                name = "Test"
+                version = "1.0.0"
+                schema_version = 1
+                capabilities = [
+                    { kind = "network:http", hosts = ["example.com", "*.example.com"] },
+                ]
+            "#,
+        )
+        .unwrap();
+        let capability_granter = CapabilityGranter::new(
+            Arc::new(manifest),
+            SemanticVersion::new(0, 7, 0),
+            ExtensionCapabilityOverrides::default(),
+        );
+        let request = |url: &str, redirect_policy: RedirectPolicy| {
+            Request::builder()
+                .uri(url)
+                .header(header::AUTHORIZATION, "Bearer secret")
+                .follow_redirects(redirect_policy)
+                .body(Vec::new())
+                .unwrap()
+        };
+
+        // Credentials aren't sent to the other hosts requests are redirected to.
+        let response = send_http_request(
+            http_client.as_ref(),
+            &capability_granter,
+            request("https://example.com/allowed", RedirectPolicy::FollowAll),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            requests.lock().drain(..).collect::<Vec<_>>(),
+            [
+                ("https://example.com/allowed".to_string(), true),
+                ("https://downloads.example.com/file".to_string(), false),
+            ]
+        );
+
+        // Redirects to hosts the extension didn't declare are never requested.
+        let error = send_http_request(
+            http_client.as_ref(),
+            &capability_granter,
+            request("https://example.com/denied", RedirectPolicy::FollowAll),
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            error.to_string().contains("network:http elsewhere.test"),
+            "{error}"
+        );
+        assert_eq!(
+            requests.lock().drain(..).collect::<Vec<_>>(),
+            [("https://example.com/denied".to_string(), true)]
+        );
+
+        // Without following redirects, the redirect itself is returned.
+        let response = send_http_request(
+            http_client.as_ref(),
+            &capability_granter,
+            request("https://example.com/denied", RedirectPolicy::NoFollow),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::FOUND);
+        assert_eq!(requests.lock().len(), 1);
+    }
+}
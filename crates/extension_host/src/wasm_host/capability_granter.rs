@@ -0,0 +1,209 @@
+use crate::ExtensionManifest;
+use crate::extension_settings::ExtensionCapabilityOverrides;
+use anyhow::{Result, bail};
+use semantic_version::SemanticVersion;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Extensions built against this version of the extension API, or later, only get
+/// network, filesystem, and environment access for the capabilities they declare.
+///
+/// Extensions built against older versions predate these capabilities, so they keep
+/// their unrestricted access unless it has been denied in the settings.
+const DECLARED_CAPABILITIES_MIN_VERSION: SemanticVersion = SemanticVersion::new(0, 7, 0);
+
+/// Decides which capabilities an extension is granted, based on its manifest and the
+/// overrides in the user's settings.
+#[derive(Debug, Clone)]
+pub struct CapabilityGranter {
+    manifest: Arc<ExtensionManifest>,
+    overrides: ExtensionCapabilityOverrides,
+    requires_declared_capabilities: bool,
+}
+
+impl CapabilityGranter {
+    pub fn new(
+        manifest: Arc<ExtensionManifest>,
+        zed_api_version: SemanticVersion,
+        overrides: ExtensionCapabilityOverrides,
+    ) -> Self {
+        Self {
+            manifest,
+            overrides,
+            requires_declared_capabilities: zed_api_version >= DECLARED_CAPABILITIES_MIN_VERSION,
+        }
+    }
+
+    pub fn grant_exec(
+        &self,
+        desired_command: &str,
+        desired_args: &[impl AsRef<str> + std::fmt::Debug],
+    ) -> Result<()> {
+        // Process execution has always required a declared capability, regardless of
+        // the extension API version.
+        match self.overrides.process_exec {
+            Some(true) => Ok(()),
+            Some(false) => self.denied("process:exec"),
+            None => self.manifest.allow_exec(desired_command, desired_args),
+        }
+    }
+
+    /// Checks whether the extension may make an HTTP request to the given URL.
+    ///
+    /// This only covers the URL itself, so it must be checked again for every URL the
+    /// request is redirected to.
+    pub fn grant_http(&self, desired_url: &str) -> Result<()> {
+        self.grant(self.overrides.network_http, "network:http", || {
+            self.manifest.allow_http(desired_url)
+        })
+    }
+
+    pub fn grant_worktree_read(&self) -> Result<()> {
+        self.grant(self.overrides.fs_worktree, "fs:worktree", || {
+            self.manifest.allow_worktree_read()
+        })
+    }
+
+    pub fn grant_work_dir(&self) -> Result<()> {
+        self.grant(self.overrides.fs_work_dir, "fs:work-dir", || {
+            self.manifest.allow_work_dir()
+        })
+    }
+
+    pub fn grant_env_var(&self, desired_var: &str) -> bool {
+        self.grant(self.overrides.env_read, "env:read", || {
+            if self.manifest.allow_env_var(desired_var) {
+                Ok(())
+            } else {
+                bail!(
+                    "capability for env:read {desired_var} was not listed in the extension manifest"
+                )
+            }
+        })
+        .is_ok()
+    }
+
+    /// Returns the paths declared with `fs:path` that the extension may access, along
+    /// with whether that access is read-only.
+    pub fn granted_paths(&self) -> impl Iterator<Item = (&Path, bool)> {
+        let granted = self.overrides.fs_path != Some(false);
+        self.manifest.allowed_paths().filter(move |_| granted)
+    }
+
+    fn grant(
+        &self,
+        overridden: Option<bool>,
+        capability: &str,
+        check_manifest: impl FnOnce() -> Result<()>,
+    ) -> Result<()> {
+        match overridden {
+            Some(true) => Ok(()),
+            Some(false) => self.denied(capability),
+            None if !self.requires_declared_capabilities => Ok(()),
+            None => check_manifest(),
+        }
+    }
+
+    fn denied(&self, capability: &str) -> Result<()> {
+        bail!(
+            "capability for {capability} was denied for extension {} in the settings",
+            self.manifest.id
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capability_granter(
+        zed_api_version: SemanticVersion,
+        overrides: ExtensionCapabilityOverrides,
+    ) -> CapabilityGranter {
+        let manifest = toml::from_str::<ExtensionManifest>(
+            r#"
+                id = "test"
+                name = "Test"
+                version = "1.0.0"
+                schema_version = 1
+                capabilities = [
+                    { kind = "process:exec", command = "echo", args = ["**"] },
+                    { kind = "network:http", hosts = ["example.com"] },
+                    { kind = "env:read", vars = ["HOME"] },
+                    { kind = "fs:path", path = "/opt/cache", read_only = true },
+                ]
+            "#,
+        )
+        .unwrap();
+        CapabilityGranter::new(Arc::new(manifest), zed_api_version, overrides)
+    }
+
+    #[test]
+    fn test_granting_declared_capabilities() {
+        let granter = capability_granter(
+            DECLARED_CAPABILITIES_MIN_VERSION,
+            ExtensionCapabilityOverrides::default(),
+        );
+        assert!(granter.grant_exec("echo", &["hello"]).is_ok());
+        assert!(granter.grant_exec("rm", &["-rf", "/"]).is_err());
+        assert!(granter.grant_http("https://example.com/file").is_ok());
+        assert!(granter.grant_http("https://elsewhere.test/file").is_err());
+        assert!(granter.grant_worktree_read().is_err());
+        assert!(granter.grant_work_dir().is_err());
+        assert!(granter.grant_env_var("HOME"));
+        assert!(!granter.grant_env_var("PATH"));
+        assert_eq!(
+            granter.granted_paths().collect::<Vec<_>>(),
+            [(Path::new("/opt/cache"), true)]
+        );
+    }
+
+    #[test]
+    fn test_overrides_take_precedence_over_manifest() {
+        let granter = capability_granter(
+            DECLARED_CAPABILITIES_MIN_VERSION,
+            ExtensionCapabilityOverrides {
+                process_exec: Some(false),
+                network_http: Some(true),
+                fs_worktree: Some(true),
+                fs_work_dir: None,
+                fs_path: Some(false),
+                env_read: Some(false),
+            },
+        );
+        assert!(granter.grant_exec("echo", &["hello"]).is_err());
+        assert!(granter.grant_http("https://elsewhere.test/file").is_ok());
+        assert!(granter.grant_worktree_read().is_ok());
+        assert!(granter.grant_work_dir().is_err());
+        assert!(!granter.grant_env_var("HOME"));
+        assert_eq!(granter.granted_paths().count(), 0);
+    }
+
+    #[test]
+    fn test_granting_capabilities_to_older_extensions() {
+        // Extensions built before capabilities had to be declared keep unrestricted access,
+        // except for running processes, which has always required a declared capability.
+        let granter = capability_granter(
+            SemanticVersion::new(0, 6, 0),
+            ExtensionCapabilityOverrides::default(),
+        );
+        assert!(granter.grant_http("https://elsewhere.test/file").is_ok());
+        assert!(granter.grant_worktree_read().is_ok());
+        assert!(granter.grant_work_dir().is_ok());
+        assert!(granter.grant_env_var("PATH"));
+        assert!(granter.grant_exec("rm", &["-rf", "/"]).is_err());
+
+        // Their access can still be denied in the settings.
+        let granter = capability_granter(
+            SemanticVersion::new(0, 6, 0),
+            ExtensionCapabilityOverrides {
+                network_http: Some(false),
+                env_read: Some(false),
+                ..Default::default()
+            },
+        );
+        assert!(granter.grant_http("https://example.com/file").is_err());
+        assert!(!granter.grant_env_var("HOME"));
+        assert!(granter.grant_worktree_read().is_ok());
+    }
+}
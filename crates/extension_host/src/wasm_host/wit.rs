@@ -7,20 +7,24 @@ mod since_v0_3_0;
 mod since_v0_4_0;
 mod since_v0_5_0;
 mod since_v0_6_0;
+mod since_v0_7_0;
 use dap::DebugRequest;
-use extension::{DebugTaskDefinition, KeyValueStoreDelegate, WorktreeDelegate};
+use extension::{
+    BufferDelegate, DebugTaskDefinition, KeyValueStoreDelegate, LlmCompletionStreamDelegate,
+    WorktreeDelegate,
+};
 use gpui::BackgroundExecutor;
 use language::LanguageName;
 use lsp::LanguageServerName;
 use release_channel::ReleaseChannel;
 use task::{DebugScenario, SpawnInTerminal, TaskTemplate, ZedDebugConfig};
 
-use crate::wasm_host::wit::since_v0_6_0::dap::StartDebuggingRequestArgumentsRequest;
+use crate::wasm_host::wit::since_v0_7_0::dap::StartDebuggingRequestArgumentsRequest;
 
 use super::{WasmState, wasm_engine};
 use anyhow::{Context as _, Result, anyhow};
 use semantic_version::SemanticVersion;
-use since_v0_6_0 as latest;
+use since_v0_7_0 as latest;
 use std::{ops::RangeInclusive, path::PathBuf, sync::Arc};
 use wasmtime::{
     Store,
@@ -65,11 +69,9 @@ pub fn is_supported_wasm_api_version(
 #[inline(always)]
 pub fn wasm_api_version_range(release_channel: ReleaseChannel) -> RangeInclusive<SemanticVersion> {
     // Note: The release channel can be used to stage a new version of the extension API.
-    let _ = release_channel;
-
     let max_version = match release_channel {
         ReleaseChannel::Dev | ReleaseChannel::Nightly => latest::MAX_VERSION,
-        ReleaseChannel::Stable | ReleaseChannel::Preview => latest::MAX_VERSION,
+        ReleaseChannel::Stable | ReleaseChannel::Preview => since_v0_6_0::MAX_VERSION,
     };
 
     since_v0_0_1::MIN_VERSION..=max_version
@@ -98,6 +100,7 @@ pub fn authorize_access_to_unreleased_wasm_api_version(
 }
 
 pub enum Extension {
+    V0_7_0(since_v0_7_0::Extension),
     V0_6_0(since_v0_6_0::Extension),
     V0_5_0(since_v0_5_0::Extension),
     V0_4_0(since_v0_4_0::Extension),
@@ -118,13 +121,22 @@ impl Extension {
         component: &Component,
     ) -> Result<Self> {
         // Note: The release channel can be used to stage a new version of the extension API.
-        let _ = release_channel;
-
         if version >= latest::MIN_VERSION {
+            authorize_access_to_unreleased_wasm_api_version(release_channel)?;
+
             let extension =
                 latest::Extension::instantiate_async(store, component, latest::linker(executor))
                     .await
                     .context("failed to instantiate wasm extension")?;
+            Ok(Self::V0_7_0(extension))
+        } else if version >= since_v0_6_0::MIN_VERSION {
+            let extension = since_v0_6_0::Extension::instantiate_async(
+                store,
+                component,
+                since_v0_6_0::linker(executor),
+            )
+            .await
+            .context("failed to instantiate wasm extension")?;
             Ok(Self::V0_6_0(extension))
         } else if version >= since_v0_5_0::MIN_VERSION {
             let extension = since_v0_5_0::Extension::instantiate_async(
@@ -203,6 +215,7 @@ impl Extension {
 
     pub async fn call_init_extension(&self, store: &mut Store<WasmState>) -> Result<()> {
         match self {
+            Extension::V0_7_0(ext) => ext.call_init_extension(store).await,
             Extension::V0_6_0(ext) => ext.call_init_extension(store).await,
             Extension::V0_5_0(ext) => ext.call_init_extension(store).await,
             Extension::V0_4_0(ext) => ext.call_init_extension(store).await,
@@ -223,6 +236,10 @@ impl Extension {
         resource: Resource<Arc<dyn WorktreeDelegate>>,
     ) -> Result<Result<Command, String>> {
         match self {
+            Extension::V0_7_0(ext) => {
+                ext.call_language_server_command(store, &language_server_id.0, resource)
+                    .await
+            }
             Extension::V0_6_0(ext) => {
                 ext.call_language_server_command(store, &language_server_id.0, resource)
                     .await
@@ -285,6 +302,14 @@ impl Extension {
         resource: Resource<Arc<dyn WorktreeDelegate>>,
     ) -> Result<Result<Option<String>, String>> {
         match self {
+            Extension::V0_7_0(ext) => {
+                ext.call_language_server_initialization_options(
+                    store,
+                    &language_server_id.0,
+                    resource,
+                )
+                .await
+            }
             Extension::V0_6_0(ext) => {
                 ext.call_language_server_initialization_options(
                     store,
@@ -374,6 +399,14 @@ impl Extension {
         resource: Resource<Arc<dyn WorktreeDelegate>>,
     ) -> Result<Result<Option<String>, String>> {
         match self {
+            Extension::V0_7_0(ext) => {
+                ext.call_language_server_workspace_configuration(
+                    store,
+                    &language_server_id.0,
+                    resource,
+                )
+                .await
+            }
             Extension::V0_6_0(ext) => {
                 ext.call_language_server_workspace_configuration(
                     store,
@@ -442,6 +475,15 @@ impl Extension {
         resource: Resource<Arc<dyn WorktreeDelegate>>,
     ) -> Result<Result<Option<String>, String>> {
         match self {
+            Extension::V0_7_0(ext) => {
+                ext.call_language_server_additional_initialization_options(
+                    store,
+                    &language_server_id.0,
+                    &target_language_server_id.0,
+                    resource,
+                )
+                .await
+            }
             Extension::V0_6_0(ext) => {
                 ext.call_language_server_additional_initialization_options(
                     store,
@@ -486,6 +528,15 @@ impl Extension {
         resource: Resource<Arc<dyn WorktreeDelegate>>,
     ) -> Result<Result<Option<String>, String>> {
         match self {
+            Extension::V0_7_0(ext) => {
+                ext.call_language_server_additional_workspace_configuration(
+                    store,
+                    &language_server_id.0,
+                    &target_language_server_id.0,
+                    resource,
+                )
+                .await
+            }
             Extension::V0_6_0(ext) => {
                 ext.call_language_server_additional_workspace_configuration(
                     store,
@@ -529,10 +580,19 @@ impl Extension {
         completions: Vec<latest::Completion>,
     ) -> Result<Result<Vec<Option<CodeLabel>>, String>> {
         match self {
-            Extension::V0_6_0(ext) => {
+            Extension::V0_7_0(ext) => {
                 ext.call_labels_for_completions(store, &language_server_id.0, &completions)
                     .await
             }
+            Extension::V0_6_0(ext) => Ok(ext
+                .call_labels_for_completions(store, &language_server_id.0, &completions)
+                .await?
+                .map(|labels| {
+                    labels
+                        .into_iter()
+                        .map(|label| label.map(Into::into))
+                        .collect()
+                })),
             Extension::V0_5_0(ext) => Ok(ext
                 .call_labels_for_completions(
                     store,
@@ -622,10 +682,19 @@ impl Extension {
         symbols: Vec<latest::Symbol>,
     ) -> Result<Result<Vec<Option<CodeLabel>>, String>> {
         match self {
-            Extension::V0_6_0(ext) => {
+            Extension::V0_7_0(ext) => {
                 ext.call_labels_for_symbols(store, &language_server_id.0, &symbols)
                     .await
             }
+            Extension::V0_6_0(ext) => Ok(ext
+                .call_labels_for_symbols(store, &language_server_id.0, &symbols)
+                .await?
+                .map(|labels| {
+                    labels
+                        .into_iter()
+                        .map(|label| label.map(Into::into))
+                        .collect()
+                })),
             Extension::V0_5_0(ext) => Ok(ext
                 .call_labels_for_symbols(
                     store,
@@ -715,6 +784,10 @@ impl Extension {
         arguments: &[String],
     ) -> Result<Result<Vec<SlashCommandArgumentCompletion>, String>> {
         match self {
+            Extension::V0_7_0(ext) => {
+                ext.call_complete_slash_command_argument(store, command, arguments)
+                    .await
+            }
             Extension::V0_6_0(ext) => {
                 ext.call_complete_slash_command_argument(store, command, arguments)
                     .await
@@ -753,6 +826,10 @@ impl Extension {
         resource: Option<Resource<Arc<dyn WorktreeDelegate>>>,
     ) -> Result<Result<SlashCommandOutput, String>> {
         match self {
+            Extension::V0_7_0(ext) => {
+                ext.call_run_slash_command(store, command, arguments, resource)
+                    .await
+            }
             Extension::V0_6_0(ext) => {
                 ext.call_run_slash_command(store, command, arguments, resource)
                     .await
@@ -790,6 +867,10 @@ impl Extension {
         project: Resource<ExtensionProject>,
     ) -> Result<Result<Command, String>> {
         match self {
+            Extension::V0_7_0(ext) => {
+                ext.call_context_server_command(store, &context_server_id, project)
+                    .await
+            }
             Extension::V0_6_0(ext) => {
                 ext.call_context_server_command(store, &context_server_id, project)
                     .await
@@ -826,6 +907,10 @@ impl Extension {
         project: Resource<ExtensionProject>,
     ) -> Result<Result<Option<ContextServerConfiguration>, String>> {
         match self {
+            Extension::V0_7_0(ext) => {
+                ext.call_context_server_configuration(store, &context_server_id, project)
+                    .await
+            }
             Extension::V0_6_0(ext) => {
                 ext.call_context_server_configuration(store, &context_server_id, project)
                     .await
@@ -852,6 +937,7 @@ impl Extension {
         provider: &str,
     ) -> Result<Result<Vec<String>, String>> {
         match self {
+            Extension::V0_7_0(ext) => ext.call_suggest_docs_packages(store, provider).await,
             Extension::V0_6_0(ext) => ext.call_suggest_docs_packages(store, provider).await,
             Extension::V0_5_0(ext) => ext.call_suggest_docs_packages(store, provider).await,
             Extension::V0_4_0(ext) => ext.call_suggest_docs_packages(store, provider).await,
@@ -872,6 +958,10 @@ impl Extension {
         kv_store: Resource<Arc<dyn KeyValueStoreDelegate>>,
     ) -> Result<Result<(), String>> {
         match self {
+            Extension::V0_7_0(ext) => {
+                ext.call_index_docs(store, provider, package_name, kv_store)
+                    .await
+            }
             Extension::V0_6_0(ext) => {
                 ext.call_index_docs(store, provider, package_name, kv_store)
                     .await
@@ -910,6 +1000,20 @@ impl Extension {
         resource: Resource<Arc<dyn WorktreeDelegate>>,
     ) -> Result<Result<DebugAdapterBinary, String>> {
         match self {
+            Extension::V0_7_0(ext) => {
+                let dap_binary = ext
+                    .call_get_dap_binary(
+                        store,
+                        &adapter_name,
+                        &task.try_into()?,
+                        user_installed_path.as_ref().and_then(|p| p.to_str()),
+                        resource,
+                    )
+                    .await?
+                    .map_err(|e| anyhow!("{e:?}"))?;
+
+                Ok(Ok(dap_binary))
+            }
             Extension::V0_6_0(ext) => {
                 let dap_binary = ext
                     .call_get_dap_binary(
@@ -934,6 +1038,16 @@ impl Extension {
         config: serde_json::Value,
     ) -> Result<Result<StartDebuggingRequestArgumentsRequest, String>> {
         match self {
+            Extension::V0_7_0(ext) => {
+                let config =
+                    serde_json::to_string(&config).context("Adapter config is not a valid JSON")?;
+                let dap_binary = ext
+                    .call_dap_request_kind(store, &adapter_name, &config)
+                    .await?
+                    .map_err(|e| anyhow!("{e:?}"))?;
+
+                Ok(Ok(dap_binary))
+            }
             Extension::V0_6_0(ext) => {
                 let config =
                     serde_json::to_string(&config).context("Adapter config is not a valid JSON")?;
@@ -953,8 +1067,17 @@ impl Extension {
         config: ZedDebugConfig,
     ) -> Result<Result<DebugScenario, String>> {
         match self {
+            Extension::V0_7_0(ext) => {
+                let config = config.try_into()?;
+                let dap_binary = ext
+                    .call_dap_config_to_scenario(store, &config)
+                    .await?
+                    .map_err(|e| anyhow!("{e:?}"))?;
+
+                Ok(Ok(dap_binary.try_into()?))
+            }
             Extension::V0_6_0(ext) => {
-                let config = config.into();
+                let config = config.try_into()?;
                 let dap_binary = ext
                     .call_dap_config_to_scenario(store, &config)
                     .await?
@@ -974,6 +1097,20 @@ impl Extension {
         debug_adapter_name: String,
     ) -> Result<Option<DebugScenario>> {
         match self {
+            Extension::V0_7_0(ext) => {
+                let build_config_template = build_config_template.into();
+                let dap_binary = ext
+                    .call_dap_locator_create_scenario(
+                        store,
+                        &locator_name,
+                        &build_config_template,
+                        &resolved_label,
+                        &debug_adapter_name,
+                    )
+                    .await?;
+
+                Ok(dap_binary.map(TryInto::try_into).transpose()?)
+            }
             Extension::V0_6_0(ext) => {
                 let build_config_template = build_config_template.into();
                 let dap_binary = ext
@@ -998,6 +1135,15 @@ impl Extension {
         resolved_build_task: SpawnInTerminal,
     ) -> Result<Result<DebugRequest, String>> {
         match self {
+            Extension::V0_7_0(ext) => {
+                let build_config_template = resolved_build_task.into();
+                let dap_request = ext
+                    .call_run_dap_locator(store, &locator_name, &build_config_template)
+                    .await?
+                    .map_err(|e| anyhow!("{e:?}"))?;
+
+                Ok(Ok(dap_request.into()))
+            }
             Extension::V0_6_0(ext) => {
                 let build_config_template = resolved_build_task.into();
                 let dap_request = ext
@@ -1010,6 +1156,170 @@ impl Extension {
             _ => anyhow::bail!("`dap_locator_create_scenario` not available prior to v0.6.0"),
         }
     }
+
+    pub async fn call_run_editor_command(
+        &self,
+        store: &mut Store<WasmState>,
+        command_id: &str,
+        buffer: Resource<Arc<dyn BufferDelegate>>,
+    ) -> Result<Result<(), String>> {
+        match self {
+            Extension::V0_7_0(ext) => ext.call_run_editor_command(store, command_id, buffer).await,
+            _ => anyhow::bail!("`run_editor_command` not available prior to v0.7.0"),
+        }
+    }
+
+    pub async fn call_format_buffer(
+        &self,
+        store: &mut Store<WasmState>,
+        formatter_id: &str,
+        language_name: &LanguageName,
+        text: &str,
+        options: latest::formatting::FormattingOptions,
+    ) -> Result<Result<Vec<latest::editor::BufferEdit>, String>> {
+        match self {
+            Extension::V0_7_0(ext) => {
+                ext.call_format_buffer(store, formatter_id, language_name.as_ref(), text, &options)
+                    .await
+            }
+            _ => anyhow::bail!("`format_buffer` not available prior to v0.7.0"),
+        }
+    }
+
+    pub async fn call_code_actions(
+        &self,
+        store: &mut Store<WasmState>,
+        provider_id: &str,
+        language_name: &LanguageName,
+        text: &str,
+        range: latest::Range,
+        diagnostics: &[latest::code_actions::Diagnostic],
+    ) -> Result<Result<Vec<latest::code_actions::CodeAction>, String>> {
+        match self {
+            Extension::V0_7_0(ext) => {
+                ext.call_code_actions(
+                    store,
+                    provider_id,
+                    language_name.as_ref(),
+                    text,
+                    &range,
+                    diagnostics,
+                )
+                .await
+            }
+            _ => anyhow::bail!("`code_actions` not available prior to v0.7.0"),
+        }
+    }
+
+    pub async fn call_worktree_tasks(
+        &self,
+        store: &mut Store<WasmState>,
+        task_source_id: &str,
+        worktree: Resource<Arc<dyn WorktreeDelegate>>,
+    ) -> Result<Result<latest::tasks::WorktreeTasks, String>> {
+        match self {
+            Extension::V0_7_0(ext) => {
+                ext.call_worktree_tasks(store, task_source_id, worktree)
+                    .await
+            }
+            _ => anyhow::bail!("`worktree_tasks` not available prior to v0.7.0"),
+        }
+    }
+
+    pub async fn call_task_variables(
+        &self,
+        store: &mut Store<WasmState>,
+        task_source_id: &str,
+        worktree: Resource<Arc<dyn WorktreeDelegate>>,
+        path: Option<&str>,
+    ) -> Result<Result<Vec<(String, String)>, String>> {
+        match self {
+            Extension::V0_7_0(ext) => {
+                ext.call_task_variables(store, task_source_id, worktree, path)
+                    .await
+            }
+            _ => anyhow::bail!("`task_variables` not available prior to v0.7.0"),
+        }
+    }
+
+    pub async fn call_web_search(
+        &self,
+        store: &mut Store<WasmState>,
+        provider_id: &str,
+        query: &str,
+    ) -> Result<Result<Vec<latest::web_search::WebSearchResult>, String>> {
+        match self {
+            Extension::V0_7_0(ext) => ext.call_web_search(store, provider_id, query).await,
+            _ => anyhow::bail!("`web_search` not available prior to v0.7.0"),
+        }
+    }
+
+    pub async fn call_llm_provider_models(
+        &self,
+        store: &mut Store<WasmState>,
+        provider_id: &str,
+    ) -> Result<Result<Vec<latest::llm_provider::LlmModel>, String>> {
+        match self {
+            Extension::V0_7_0(ext) => ext.call_llm_provider_models(store, provider_id).await,
+            _ => anyhow::bail!("`llm_provider_models` not available prior to v0.7.0"),
+        }
+    }
+
+    pub async fn call_llm_count_tokens(
+        &self,
+        store: &mut Store<WasmState>,
+        provider_id: &str,
+        model_id: &str,
+        request: &latest::llm_provider::LlmCompletionRequest,
+    ) -> Result<Result<u64, String>> {
+        match self {
+            Extension::V0_7_0(ext) => {
+                ext.call_llm_count_tokens(store, provider_id, model_id, request)
+                    .await
+            }
+            _ => anyhow::bail!("`llm_count_tokens` not available prior to v0.7.0"),
+        }
+    }
+
+    pub async fn call_llm_stream_completion(
+        &self,
+        store: &mut Store<WasmState>,
+        provider_id: &str,
+        model_id: &str,
+        request: &latest::llm_provider::LlmCompletionRequest,
+        stream: Resource<Arc<dyn LlmCompletionStreamDelegate>>,
+    ) -> Result<Result<(), String>> {
+        match self {
+            Extension::V0_7_0(ext) => {
+                ext.call_llm_stream_completion(store, provider_id, model_id, request, stream)
+                    .await
+            }
+            _ => anyhow::bail!("`llm_stream_completion` not available prior to v0.7.0"),
+        }
+    }
+
+    pub async fn call_render_panel(
+        &self,
+        store: &mut Store<WasmState>,
+        panel_id: &str,
+    ) -> Result<Result<latest::panel::PanelView, String>> {
+        match self {
+            Extension::V0_7_0(ext) => ext.call_render_panel(store, panel_id).await,
+            _ => anyhow::bail!("`render_panel` not available prior to v0.7.0"),
+        }
+    }
+
+    pub async fn call_handle_panel_event(
+        &self,
+        store: &mut Store<WasmState>,
+        panel_id: &str,
+        event: &latest::panel::PanelEvent,
+    ) -> Result<Result<latest::panel::PanelView, String>> {
+        match self {
+            Extension::V0_7_0(ext) => ext.call_handle_panel_event(store, panel_id, event).await,
+            _ => anyhow::bail!("`handle_panel_event` not available prior to v0.7.0"),
+        }
+    }
 }
 
 trait ToWasmtimeResult<T> {
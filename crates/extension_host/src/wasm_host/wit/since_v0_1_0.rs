@@ -295,8 +295,9 @@ impl http_client::Host for WasmState {
     ) -> wasmtime::Result<Result<http_client::HttpResponse, String>> {
         maybe!(async {
             let url = &request.url;
+            self.capability_granter.grant_http(url)?;
             let request = convert_request(&request)?;
-            let mut response = self.host.http_client.send(request).await?;
+            let mut response = self.send_http_request(request).await?;
 
             if response.status().is_client_error() || response.status().is_server_error() {
                 bail!("failed to fetch '{url}': status code {}", response.status())
@@ -311,10 +312,13 @@ impl http_client::Host for WasmState {
         &mut self,
         request: http_client::HttpRequest,
     ) -> wasmtime::Result<Result<Resource<ExtensionHttpResponseStream>, String>> {
+        if let Err(error) = self.capability_granter.grant_http(&request.url) {
+            return Ok(Err(error.to_string()));
+        }
+
         let request = convert_request(&request)?;
-        let response = self.host.http_client.send(request);
         maybe!(async {
-            let response = response.await?;
+            let response = self.send_http_request(request).await?;
             let stream = Arc::new(Mutex::new(response));
             let resource = self.table.push(stream)?;
             Ok(resource)
@@ -366,7 +370,7 @@ impl From<http_client::HttpMethod> for ::http_client::Method {
 
 fn convert_request(
     extension_request: &http_client::HttpRequest,
-) -> anyhow::Result<::http_client::Request<AsyncBody>> {
+) -> anyhow::Result<::http_client::Request<Vec<u8>>> {
     let mut request = ::http_client::Request::builder()
         .method(::http_client::Method::from(extension_request.method))
         .uri(&extension_request.url)
@@ -380,11 +384,7 @@ fn convert_request(
     for (key, value) in &extension_request.headers {
         request = request.header(key, value);
     }
-    let body = extension_request
-        .body
-        .clone()
-        .map(AsyncBody::from)
-        .unwrap_or_default();
+    let body = extension_request.body.clone().unwrap_or_default();
     request.body(body).map_err(anyhow::Error::from)
 }
 
@@ -495,6 +495,9 @@ impl ExtensionImports for WasmState {
         file_type: DownloadedFileType,
     ) -> wasmtime::Result<Result<(), String>> {
         maybe!(async {
+            self.capability_granter.grant_work_dir()?;
+            self.capability_granter.grant_http(&url)?;
+
             let path = PathBuf::from(path);
             let extension_work_dir = self.host.work_dir.join(self.manifest.id.as_ref());
 
@@ -504,10 +507,11 @@ impl ExtensionImports for WasmState {
                 .host
                 .writeable_path_from_extension(&self.manifest.id, &path)?;
 
+            let request = ::http_client::Request::get(&url)
+                .follow_redirects(::http_client::RedirectPolicy::FollowAll)
+                .body(Vec::new())?;
             let mut response = self
-                .host
-                .http_client
-                .get(&url, Default::default(), true)
+                .send_http_request(request)
                 .await
                 .context("downloading release")?;
 
@@ -557,6 +561,10 @@ impl ExtensionImports for WasmState {
     }
 
     async fn make_file_executable(&mut self, path: String) -> wasmtime::Result<Result<(), String>> {
+        if let Err(error) = self.capability_granter.grant_work_dir() {
+            return Ok(Err(error.to_string()));
+        }
+
         let path = self
             .host
             .writeable_path_from_extension(&self.manifest.id, Path::new(&path))?;
@@ -0,0 +1,1603 @@
+use crate::wasm_host::wit::since_v0_7_0::{
+    dap::{
+        AttachRequest, BuildTaskDefinition, BuildTaskDefinitionTemplatePayload, LaunchRequest,
+        StartDebuggingRequestArguments, TcpArguments, TcpArgumentsTemplate,
+    },
+    slash_command::SlashCommandOutputSection,
+};
+use crate::wasm_host::wit::{CompletionKind, CompletionLabelDetails, InsertTextFormat, SymbolKind};
+use crate::wasm_host::{WasmState, wit::ToWasmtimeResult};
+use ::http_client::{AsyncBody, HttpRequestExt};
+use ::settings::{Settings, WorktreeId};
+use anyhow::{Context as _, Result, bail};
+use async_compression::futures::bufread::GzipDecoder;
+use async_tar::Archive;
+use async_trait::async_trait;
+use credentials_provider::CredentialsProvider;
+use extension::{
+    BufferDelegate, ExtensionLanguageServerProxy, KeyValueStoreDelegate,
+    LlmCompletionStreamDelegate, ProjectDelegate, WorktreeDelegate,
+};
+use futures::{AsyncReadExt, lock::Mutex};
+use futures::{FutureExt as _, io::BufReader};
+use gpui::{BackgroundExecutor, SharedString};
+use language::{BinaryStatus, LanguageName, language_settings::AllLanguageSettings};
+use project::project_settings::ProjectSettings;
+use semantic_version::SemanticVersion;
+use std::{
+    env,
+    net::Ipv4Addr,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, OnceLock},
+};
+use task::{SpawnInTerminal, ZedDebugConfig};
+use util::{archive::extract_zip, fs::make_file_executable, maybe};
+use wasmtime::component::{Linker, Resource};
+
+pub const MIN_VERSION: SemanticVersion = SemanticVersion::new(0, 7, 0);
+pub const MAX_VERSION: SemanticVersion = SemanticVersion::new(0, 7, 0);
+
+wasmtime::component::bindgen!({
+    async: true,
+    trappable_imports: true,
+    path: "../extension_api/wit/since_v0.7.0",
+    with: {
+         "worktree": ExtensionWorktree,
+         "project": ExtensionProject,
+         "key-value-store": ExtensionKeyValueStore,
+         "zed:extension/http-client/http-response-stream": ExtensionHttpResponseStream,
+         "zed:extension/editor/buffer": ExtensionBuffer,
+         "zed:extension/llm-provider/llm-completion-stream": ExtensionLlmCompletionStream
+    },
+});
+
+pub use self::zed::extension::*;
+
+mod settings {
+    include!(concat!(env!("OUT_DIR"), "/since_v0.7.0/settings.rs"));
+}
+
+pub type ExtensionWorktree = Arc<dyn WorktreeDelegate>;
+pub type ExtensionProject = Arc<dyn ProjectDelegate>;
+pub type ExtensionKeyValueStore = Arc<dyn KeyValueStoreDelegate>;
+pub type ExtensionHttpResponseStream = Arc<Mutex<::http_client::Response<AsyncBody>>>;
+pub type ExtensionBuffer = Arc<dyn BufferDelegate>;
+pub type ExtensionLlmCompletionStream = Arc<dyn LlmCompletionStreamDelegate>;
+
+pub fn linker(executor: &BackgroundExecutor) -> &'static Linker<WasmState> {
+    static LINKER: OnceLock<Linker<WasmState>> = OnceLock::new();
+    LINKER.get_or_init(|| super::new_linker(executor, Extension::add_to_linker))
+}
+
+impl From<Range> for std::ops::Range<usize> {
+    fn from(range: Range) -> Self {
+        let start = range.start as usize;
+        let end = range.end as usize;
+        start..end
+    }
+}
+
+impl From<std::ops::Range<usize>> for Range {
+    fn from(range: std::ops::Range<usize>) -> Self {
+        Self {
+            start: range.start as u32,
+            end: range.end as u32,
+        }
+    }
+}
+
+impl From<Command> for extension::Command {
+    fn from(value: Command) -> Self {
+        Self {
+            command: value.command,
+            args: value.args,
+            env: value.env,
+        }
+    }
+}
+
+impl From<StartDebuggingRequestArgumentsRequest>
+    for extension::StartDebuggingRequestArgumentsRequest
+{
+    fn from(value: StartDebuggingRequestArgumentsRequest) -> Self {
+        match value {
+            StartDebuggingRequestArgumentsRequest::Launch => Self::Launch,
+            StartDebuggingRequestArgumentsRequest::Attach => Self::Attach,
+        }
+    }
+}
+impl TryFrom<StartDebuggingRequestArguments> for extension::StartDebuggingRequestArguments {
+    type Error = anyhow::Error;
+
+    fn try_from(value: StartDebuggingRequestArguments) -> Result<Self, Self::Error> {
+        Ok(Self {
+            configuration: serde_json::from_str(&value.configuration)?,
+            request: value.request.into(),
+        })
+    }
+}
+impl From<TcpArguments> for extension::TcpArguments {
+    fn from(value: TcpArguments) -> Self {
+        Self {
+            host: value.host.into(),
+            port: value.port,
+            timeout: value.timeout,
+        }
+    }
+}
+
+impl From<extension::TcpArgumentsTemplate> for TcpArgumentsTemplate {
+    fn from(value: extension::TcpArgumentsTemplate) -> Self {
+        Self {
+            host: value.host.map(Ipv4Addr::to_bits),
+            port: value.port,
+            timeout: value.timeout,
+        }
+    }
+}
+
+impl From<TcpArgumentsTemplate> for extension::TcpArgumentsTemplate {
+    fn from(value: TcpArgumentsTemplate) -> Self {
+        Self {
+            host: value.host.map(Ipv4Addr::from_bits),
+            port: value.port,
+            timeout: value.timeout,
+        }
+    }
+}
+
+impl TryFrom<extension::DebugTaskDefinition> for DebugTaskDefinition {
+    type Error = anyhow::Error;
+    fn try_from(value: extension::DebugTaskDefinition) -> Result<Self, Self::Error> {
+        Ok(Self {
+            label: value.label.to_string(),
+            adapter: value.adapter.to_string(),
+            config: value.config.to_string(),
+            tcp_connection: value.tcp_connection.map(Into::into),
+        })
+    }
+}
+
+impl TryFrom<task::DebugRequest> for DebugRequest {
+    type Error = anyhow::Error;
+
+    fn try_from(value: task::DebugRequest) -> Result<Self, Self::Error> {
+        match value {
+            task::DebugRequest::Launch(launch_request) => Ok(Self::Launch(launch_request.into())),
+            task::DebugRequest::Attach(attach_request) => Ok(Self::Attach(attach_request.into())),
+            task::DebugRequest::CoreDump(_) => {
+                anyhow::bail!("Core dumps are not supported by extension debug adapters")
+            }
+        }
+    }
+}
+
+impl From<DebugRequest> for task::DebugRequest {
+    fn from(value: DebugRequest) -> Self {
+        match value {
+            DebugRequest::Launch(launch_request) => Self::Launch(launch_request.into()),
+            DebugRequest::Attach(attach_request) => Self::Attach(attach_request.into()),
+        }
+    }
+}
+
+impl From<task::LaunchRequest> for LaunchRequest {
+    fn from(value: task::LaunchRequest) -> Self {
+        Self {
+            program: value.program,
+            cwd: value.cwd.map(|p| p.to_string_lossy().into_owned()),
+            args: value.args,
+            envs: value.env.into_iter().collect(),
+        }
+    }
+}
+
+impl From<task::AttachRequest> for AttachRequest {
+    fn from(value: task::AttachRequest) -> Self {
+        Self {
+            process_id: value.process_id,
+        }
+    }
+}
+
+impl From<LaunchRequest> for task::LaunchRequest {
+    fn from(value: LaunchRequest) -> Self {
+        Self {
+            program: value.program,
+            cwd: value.cwd.map(|p| p.into()),
+            args: value.args,
+            env: value.envs.into_iter().collect(),
+        }
+    }
+}
+impl From<AttachRequest> for task::AttachRequest {
+    fn from(value: AttachRequest) -> Self {
+        Self {
+            process_id: value.process_id,
+        }
+    }
+}
+
+impl TryFrom<ZedDebugConfig> for DebugConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(value: ZedDebugConfig) -> Result<Self, Self::Error> {
+        Ok(Self {
+            label: value.label.into(),
+            adapter: value.adapter.into(),
+            request: value.request.try_into()?,
+            stop_on_entry: value.stop_on_entry,
+        })
+    }
+}
+impl TryFrom<DebugAdapterBinary> for extension::DebugAdapterBinary {
+    type Error = anyhow::Error;
+    fn try_from(value: DebugAdapterBinary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            command: value.command,
+            arguments: value.arguments,
+            envs: value.envs.into_iter().collect(),
+            cwd: value.cwd.map(|s| s.into()),
+            connection: value.connection.map(Into::into),
+            request_args: value.request_args.try_into()?,
+        })
+    }
+}
+
+impl From<BuildTaskDefinition> for extension::BuildTaskDefinition {
+    fn from(value: BuildTaskDefinition) -> Self {
+        match value {
+            BuildTaskDefinition::ByName(name) => Self::ByName(name.into()),
+            BuildTaskDefinition::Template(build_task_template) => Self::Template {
+                task_template: build_task_template.template.into(),
+                locator_name: build_task_template.locator_name.map(SharedString::from),
+            },
+        }
+    }
+}
+
+impl From<extension::BuildTaskDefinition> for BuildTaskDefinition {
+    fn from(value: extension::BuildTaskDefinition) -> Self {
+        match value {
+            extension::BuildTaskDefinition::ByName(name) => Self::ByName(name.into()),
+            extension::BuildTaskDefinition::Template {
+                task_template,
+                locator_name,
+            } => Self::Template(BuildTaskDefinitionTemplatePayload {
+                template: task_template.into(),
+                locator_name: locator_name.map(String::from),
+            }),
+        }
+    }
+}
+impl From<BuildTaskTemplate> for extension::BuildTaskTemplate {
+    fn from(value: BuildTaskTemplate) -> Self {
+        Self {
+            label: value.label,
+            command: value.command,
+            args: value.args,
+            env: value.env.into_iter().collect(),
+            cwd: value.cwd,
+            ..Default::default()
+        }
+    }
+}
+impl From<extension::BuildTaskTemplate> for BuildTaskTemplate {
+    fn from(value: extension::BuildTaskTemplate) -> Self {
+        Self {
+            label: value.label,
+            command: value.command,
+            args: value.args,
+            env: value.env.into_iter().collect(),
+            cwd: value.cwd,
+        }
+    }
+}
+
+impl TryFrom<DebugScenario> for extension::DebugScenario {
+    type Error = anyhow::Error;
+
+    fn try_from(value: DebugScenario) -> std::result::Result<Self, Self::Error> {
+        Ok(Self {
+            adapter: value.adapter.into(),
+            label: value.label.into(),
+            build: value.build.map(Into::into),
+            config: serde_json::Value::from_str(&value.config)?,
+            tcp_connection: value.tcp_connection.map(Into::into),
+        })
+    }
+}
+
+impl From<extension::DebugScenario> for DebugScenario {
+    fn from(value: extension::DebugScenario) -> Self {
+        Self {
+            adapter: value.adapter.into(),
+            label: value.label.into(),
+            build: value.build.map(Into::into),
+            config: value.config.to_string(),
+            tcp_connection: value.tcp_connection.map(Into::into),
+        }
+    }
+}
+
+impl From<SpawnInTerminal> for ResolvedTask {
+    fn from(value: SpawnInTerminal) -> Self {
+        Self {
+            label: value.label,
+            command: value.command,
+            args: value.args,
+            env: value.env.into_iter().collect(),
+            cwd: value.cwd.map(|s| s.to_string_lossy().into_owned()),
+        }
+    }
+}
+
+impl From<CodeLabel> for extension::CodeLabel {
+    fn from(value: CodeLabel) -> Self {
+        Self {
+            code: value.code,
+            spans: value.spans.into_iter().map(Into::into).collect(),
+            filter_range: value.filter_range.into(),
+        }
+    }
+}
+
+impl From<CodeLabelSpan> for extension::CodeLabelSpan {
+    fn from(value: CodeLabelSpan) -> Self {
+        match value {
+            CodeLabelSpan::CodeRange(range) => Self::CodeRange(range.into()),
+            CodeLabelSpan::Literal(literal) => Self::Literal(literal.into()),
+        }
+    }
+}
+
+impl From<CodeLabelSpanLiteral> for extension::CodeLabelSpanLiteral {
+    fn from(value: CodeLabelSpanLiteral) -> Self {
+        Self {
+            text: value.text,
+            highlight_name: value.highlight_name,
+        }
+    }
+}
+
+impl From<extension::Completion> for Completion {
+    fn from(value: extension::Completion) -> Self {
+        Self {
+            label: value.label,
+            label_details: value.label_details.map(Into::into),
+            detail: value.detail,
+            kind: value.kind.map(Into::into),
+            insert_text_format: value.insert_text_format.map(Into::into),
+        }
+    }
+}
+
+impl From<extension::CompletionLabelDetails> for CompletionLabelDetails {
+    fn from(value: extension::CompletionLabelDetails) -> Self {
+        Self {
+            detail: value.detail,
+            description: value.description,
+        }
+    }
+}
+
+impl From<extension::CompletionKind> for CompletionKind {
+    fn from(value: extension::CompletionKind) -> Self {
+        match value {
+            extension::CompletionKind::Text => Self::Text,
+            extension::CompletionKind::Method => Self::Method,
+            extension::CompletionKind::Function => Self::Function,
+            extension::CompletionKind::Constructor => Self::Constructor,
+            extension::CompletionKind::Field => Self::Field,
+            extension::CompletionKind::Variable => Self::Variable,
+            extension::CompletionKind::Class => Self::Class,
+            extension::CompletionKind::Interface => Self::Interface,
+            extension::CompletionKind::Module => Self::Module,
+            extension::CompletionKind::Property => Self::Property,
+            extension::CompletionKind::Unit => Self::Unit,
+            extension::CompletionKind::Value => Self::Value,
+            extension::CompletionKind::Enum => Self::Enum,
+            extension::CompletionKind::Keyword => Self::Keyword,
+            extension::CompletionKind::Snippet => Self::Snippet,
+            extension::CompletionKind::Color => Self::Color,
+            extension::CompletionKind::File => Self::File,
+            extension::CompletionKind::Reference => Self::Reference,
+            extension::CompletionKind::Folder => Self::Folder,
+            extension::CompletionKind::EnumMember => Self::EnumMember,
+            extension::CompletionKind::Constant => Self::Constant,
+            extension::CompletionKind::Struct => Self::Struct,
+            extension::CompletionKind::Event => Self::Event,
+            extension::CompletionKind::Operator => Self::Operator,
+            extension::CompletionKind::TypeParameter => Self::TypeParameter,
+            extension::CompletionKind::Other(value) => Self::Other(value),
+        }
+    }
+}
+
+impl From<extension::InsertTextFormat> for InsertTextFormat {
+    fn from(value: extension::InsertTextFormat) -> Self {
+        match value {
+            extension::InsertTextFormat::PlainText => Self::PlainText,
+            extension::InsertTextFormat::Snippet => Self::Snippet,
+            extension::InsertTextFormat::Other(value) => Self::Other(value),
+        }
+    }
+}
+
+impl From<extension::Symbol> for Symbol {
+    fn from(value: extension::Symbol) -> Self {
+        Self {
+            kind: value.kind.into(),
+            name: value.name,
+        }
+    }
+}
+
+impl From<extension::SymbolKind> for SymbolKind {
+    fn from(value: extension::SymbolKind) -> Self {
+        match value {
+            extension::SymbolKind::File => Self::File,
+            extension::SymbolKind::Module => Self::Module,
+            extension::SymbolKind::Namespace => Self::Namespace,
+            extension::SymbolKind::Package => Self::Package,
+            extension::SymbolKind::Class => Self::Class,
+            extension::SymbolKind::Method => Self::Method,
+            extension::SymbolKind::Property => Self::Property,
+            extension::SymbolKind::Field => Self::Field,
+            extension::SymbolKind::Constructor => Self::Constructor,
+            extension::SymbolKind::Enum => Self::Enum,
+            extension::SymbolKind::Interface => Self::Interface,
+            extension::SymbolKind::Function => Self::Function,
+            extension::SymbolKind::Variable => Self::Variable,
+            extension::SymbolKind::Constant => Self::Constant,
+            extension::SymbolKind::String => Self::String,
+            extension::SymbolKind::Number => Self::Number,
+            extension::SymbolKind::Boolean => Self::Boolean,
+            extension::SymbolKind::Array => Self::Array,
+            extension::SymbolKind::Object => Self::Object,
+            extension::SymbolKind::Key => Self::Key,
+            extension::SymbolKind::Null => Self::Null,
+            extension::SymbolKind::EnumMember => Self::EnumMember,
+            extension::SymbolKind::Struct => Self::Struct,
+            extension::SymbolKind::Event => Self::Event,
+            extension::SymbolKind::Operator => Self::Operator,
+            extension::SymbolKind::TypeParameter => Self::TypeParameter,
+            extension::SymbolKind::Other(value) => Self::Other(value),
+        }
+    }
+}
+
+impl From<extension::SlashCommand> for SlashCommand {
+    fn from(value: extension::SlashCommand) -> Self {
+        Self {
+            name: value.name,
+            description: value.description,
+            tooltip_text: value.tooltip_text,
+            requires_argument: value.requires_argument,
+        }
+    }
+}
+
+impl From<SlashCommandOutput> for extension::SlashCommandOutput {
+    fn from(value: SlashCommandOutput) -> Self {
+        Self {
+            text: value.text,
+            sections: value.sections.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<SlashCommandOutputSection> for extension::SlashCommandOutputSection {
+    fn from(value: SlashCommandOutputSection) -> Self {
+        Self {
+            range: value.range.start as usize..value.range.end as usize,
+            label: value.label,
+        }
+    }
+}
+
+impl From<SlashCommandArgumentCompletion> for extension::SlashCommandArgumentCompletion {
+    fn from(value: SlashCommandArgumentCompletion) -> Self {
+        Self {
+            label: value.label,
+            new_text: value.new_text,
+            run_command: value.run_command,
+        }
+    }
+}
+
+impl TryFrom<ContextServerConfiguration> for extension::ContextServerConfiguration {
+    type Error = anyhow::Error;
+
+    fn try_from(value: ContextServerConfiguration) -> Result<Self, Self::Error> {
+        let settings_schema: serde_json::Value = serde_json::from_str(&value.settings_schema)
+            .context("Failed to parse settings_schema")?;
+
+        Ok(Self {
+            installation_instructions: value.installation_instructions,
+            default_settings: value.default_settings,
+            settings_schema,
+        })
+    }
+}
+
+impl HostKeyValueStore for WasmState {
+    async fn insert(
+        &mut self,
+        kv_store: Resource<ExtensionKeyValueStore>,
+        key: String,
+        value: String,
+    ) -> wasmtime::Result<Result<(), String>> {
+        let kv_store = self.table.get(&kv_store)?;
+        kv_store.insert(key, value).await.to_wasmtime_result()
+    }
+
+    async fn drop(&mut self, _worktree: Resource<ExtensionKeyValueStore>) -> Result<()> {
+        // We only ever hand out borrows of key-value stores.
+        Ok(())
+    }
+}
+
+impl HostProject for WasmState {
+    async fn worktree_ids(
+        &mut self,
+        project: Resource<ExtensionProject>,
+    ) -> wasmtime::Result<Vec<u64>> {
+        let project = self.table.get(&project)?;
+        Ok(project.worktree_ids())
+    }
+
+    async fn drop(&mut self, _project: Resource<Project>) -> Result<()> {
+        // We only ever hand out borrows of projects.
+        Ok(())
+    }
+}
+
+impl HostWorktree for WasmState {
+    async fn id(&mut self, delegate: Resource<Arc<dyn WorktreeDelegate>>) -> wasmtime::Result<u64> {
+        let delegate = self.table.get(&delegate)?;
+        Ok(delegate.id())
+    }
+
+    async fn root_path(
+        &mut self,
+        delegate: Resource<Arc<dyn WorktreeDelegate>>,
+    ) -> wasmtime::Result<String> {
+        let delegate = self.table.get(&delegate)?;
+        Ok(delegate.root_path())
+    }
+
+    async fn read_text_file(
+        &mut self,
+        delegate: Resource<Arc<dyn WorktreeDelegate>>,
+        path: String,
+    ) -> wasmtime::Result<Result<String, String>> {
+        if let Err(error) = self.capability_granter.grant_worktree_read() {
+            return Ok(Err(error.to_string()));
+        }
+
+        let delegate = self.table.get(&delegate)?;
+        Ok(delegate
+            .read_text_file(path.into())
+            .await
+            .map_err(|error| error.to_string()))
+    }
+
+    async fn shell_env(
+        &mut self,
+        delegate: Resource<Arc<dyn WorktreeDelegate>>,
+    ) -> wasmtime::Result<EnvVars> {
+        let delegate = self.table.get(&delegate)?;
+        Ok(delegate
+            .shell_env()
+            .await
+            .into_iter()
+            .filter(|(name, _)| self.capability_granter.grant_env_var(name))
+            .collect())
+    }
+
+    async fn which(
+        &mut self,
+        delegate: Resource<Arc<dyn WorktreeDelegate>>,
+        binary_name: String,
+    ) -> wasmtime::Result<Option<String>> {
+        let delegate = self.table.get(&delegate)?;
+        Ok(delegate.which(binary_name).await)
+    }
+
+    async fn drop(&mut self, _worktree: Resource<Worktree>) -> Result<()> {
+        // We only ever hand out borrows of worktrees.
+        Ok(())
+    }
+}
+
+impl common::Host for WasmState {}
+
+impl http_client::Host for WasmState {
+    async fn fetch(
+        &mut self,
+        request: http_client::HttpRequest,
+    ) -> wasmtime::Result<Result<http_client::HttpResponse, String>> {
+        maybe!(async {
+            let url = &request.url;
+            self.capability_granter.grant_http(url)?;
+            let request = convert_request(&request)?;
+            let mut response = self.send_http_request(request).await?;
+
+            if response.status().is_client_error() || response.status().is_server_error() {
+                bail!("failed to fetch '{url}': status code {}", response.status())
+            }
+            convert_response(&mut response).await
+        })
+        .await
+        .to_wasmtime_result()
+    }
+
+    async fn fetch_stream(
+        &mut self,
+        request: http_client::HttpRequest,
+    ) -> wasmtime::Result<Result<Resource<ExtensionHttpResponseStream>, String>> {
+        if let Err(error) = self.capability_granter.grant_http(&request.url) {
+            return Ok(Err(error.to_string()));
+        }
+
+        let request = convert_request(&request)?;
+        maybe!(async {
+            let response = self.send_http_request(request).await?;
+            let stream = Arc::new(Mutex::new(response));
+            let resource = self.table.push(stream)?;
+            Ok(resource)
+        })
+        .await
+        .to_wasmtime_result()
+    }
+}
+
+impl http_client::HostHttpResponseStream for WasmState {
+    async fn next_chunk(
+        &mut self,
+        resource: Resource<ExtensionHttpResponseStream>,
+    ) -> wasmtime::Result<Result<Option<Vec<u8>>, String>> {
+        let stream = self.table.get(&resource)?.clone();
+        maybe!(async move {
+            let mut response = stream.lock().await;
+            let mut buffer = vec![0; 8192]; // 8KB buffer
+            let bytes_read = response.body_mut().read(&mut buffer).await?;
+            if bytes_read == 0 {
+                Ok(None)
+            } else {
+                buffer.truncate(bytes_read);
+                Ok(Some(buffer))
+            }
+        })
+        .await
+        .to_wasmtime_result()
+    }
+
+    async fn drop(&mut self, _resource: Resource<ExtensionHttpResponseStream>) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl From<http_client::HttpMethod> for ::http_client::Method {
+    fn from(value: http_client::HttpMethod) -> Self {
+        match value {
+            http_client::HttpMethod::Get => Self::GET,
+            http_client::HttpMethod::Post => Self::POST,
+            http_client::HttpMethod::Put => Self::PUT,
+            http_client::HttpMethod::Delete => Self::DELETE,
+            http_client::HttpMethod::Head => Self::HEAD,
+            http_client::HttpMethod::Options => Self::OPTIONS,
+            http_client::HttpMethod::Patch => Self::PATCH,
+        }
+    }
+}
+
+fn convert_request(
+    extension_request: &http_client::HttpRequest,
+) -> anyhow::Result<::http_client::Request<Vec<u8>>> {
+    let mut request = ::http_client::Request::builder()
+        .method(::http_client::Method::from(extension_request.method))
+        .uri(&extension_request.url)
+        .follow_redirects(match extension_request.redirect_policy {
+            http_client::RedirectPolicy::NoFollow => ::http_client::RedirectPolicy::NoFollow,
+            http_client::RedirectPolicy::FollowLimit(limit) => {
+                ::http_client::RedirectPolicy::FollowLimit(limit)
+            }
+            http_client::RedirectPolicy::FollowAll => ::http_client::RedirectPolicy::FollowAll,
+        });
+    for (key, value) in &extension_request.headers {
+        request = request.header(key, value);
+    }
+    let body = extension_request.body.clone().unwrap_or_default();
+    request.body(body).map_err(anyhow::Error::from)
+}
+
+async fn convert_response(
+    response: &mut ::http_client::Response<AsyncBody>,
+) -> anyhow::Result<http_client::HttpResponse> {
+    let mut extension_response = http_client::HttpResponse {
+        body: Vec::new(),
+        headers: Vec::new(),
+    };
+
+    for (key, value) in response.headers() {
+        extension_response
+            .headers
+            .push((key.to_string(), value.to_str().unwrap_or("").to_string()));
+    }
+
+    response
+        .body_mut()
+        .read_to_end(&mut extension_response.body)
+        .await?;
+
+    Ok(extension_response)
+}
+
+impl nodejs::Host for WasmState {
+    async fn node_binary_path(&mut self) -> wasmtime::Result<Result<String, String>> {
+        self.host
+            .node_runtime
+            .binary_path()
+            .await
+            .map(|path| path.to_string_lossy().to_string())
+            .to_wasmtime_result()
+    }
+
+    async fn npm_package_latest_version(
+        &mut self,
+        package_name: String,
+    ) -> wasmtime::Result<Result<String, String>> {
+        self.host
+            .node_runtime
+            .npm_package_latest_version(&package_name)
+            .await
+            .to_wasmtime_result()
+    }
+
+    async fn npm_package_installed_version(
+        &mut self,
+        package_name: String,
+    ) -> wasmtime::Result<Result<Option<String>, String>> {
+        self.host
+            .node_runtime
+            .npm_package_installed_version(&self.work_dir(), &package_name)
+            .await
+            .to_wasmtime_result()
+    }
+
+    async fn npm_install_package(
+        &mut self,
+        package_name: String,
+        version: String,
+    ) -> wasmtime::Result<Result<(), String>> {
+        if let Err(error) = self.capability_granter.grant_work_dir() {
+            return Ok(Err(error.to_string()));
+        }
+
+        self.host
+            .node_runtime
+            .npm_install_packages(&self.work_dir(), &[(&package_name, &version)])
+            .await
+            .to_wasmtime_result()
+    }
+}
+
+#[async_trait]
+impl lsp::Host for WasmState {}
+
+impl From<::http_client::github::GithubRelease> for github::GithubRelease {
+    fn from(value: ::http_client::github::GithubRelease) -> Self {
+        Self {
+            version: value.tag_name,
+            assets: value.assets.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<::http_client::github::GithubReleaseAsset> for github::GithubReleaseAsset {
+    fn from(value: ::http_client::github::GithubReleaseAsset) -> Self {
+        Self {
+            name: value.name,
+            download_url: value.browser_download_url,
+        }
+    }
+}
+
+impl github::Host for WasmState {
+    async fn latest_github_release(
+        &mut self,
+        repo: String,
+        options: github::GithubReleaseOptions,
+    ) -> wasmtime::Result<Result<github::GithubRelease, String>> {
+        maybe!(async {
+            self.capability_granter
+                .grant_http(&format!("https://api.github.com/repos/{repo}/releases"))?;
+            let release = ::http_client::github::latest_github_release(
+                &repo,
+                options.require_assets,
+                options.pre_release,
+                self.host.http_client.clone(),
+            )
+            .await?;
+            Ok(release.into())
+        })
+        .await
+        .to_wasmtime_result()
+    }
+
+    async fn github_release_by_tag_name(
+        &mut self,
+        repo: String,
+        tag: String,
+    ) -> wasmtime::Result<Result<github::GithubRelease, String>> {
+        maybe!(async {
+            self.capability_granter.grant_http(&format!(
+                "https://api.github.com/repos/{repo}/releases/tags/{tag}"
+            ))?;
+            let release = ::http_client::github::get_release_by_tag_name(
+                &repo,
+                &tag,
+                self.host.http_client.clone(),
+            )
+            .await?;
+            Ok(release.into())
+        })
+        .await
+        .to_wasmtime_result()
+    }
+}
+
+impl platform::Host for WasmState {
+    async fn current_platform(&mut self) -> Result<(platform::Os, platform::Architecture)> {
+        Ok((
+            match env::consts::OS {
+                "macos" => platform::Os::Mac,
+                "linux" => platform::Os::Linux,
+                "windows" => platform::Os::Windows,
+                _ => panic!("unsupported os"),
+            },
+            match env::consts::ARCH {
+                "aarch64" => platform::Architecture::Aarch64,
+                "x86" => platform::Architecture::X86,
+                "x86_64" => platform::Architecture::X8664,
+                _ => panic!("unsupported architecture"),
+            },
+        ))
+    }
+}
+
+impl From<std::process::Output> for process::Output {
+    fn from(output: std::process::Output) -> Self {
+        Self {
+            status: output.status.code(),
+            stdout: output.stdout,
+            stderr: output.stderr,
+        }
+    }
+}
+
+impl process::Host for WasmState {
+    async fn run_command(
+        &mut self,
+        command: process::Command,
+    ) -> wasmtime::Result<Result<process::Output, String>> {
+        maybe!(async {
+            self.capability_granter
+                .grant_exec(&command.command, &command.args)?;
+
+            let output = util::command::new_smol_command(command.command.as_str())
+                .args(&command.args)
+                .envs(command.env)
+                .output()
+                .await?;
+
+            Ok(output.into())
+        })
+        .await
+        .to_wasmtime_result()
+    }
+}
+
+#[async_trait]
+impl slash_command::Host for WasmState {}
+
+#[async_trait]
+impl context_server::Host for WasmState {}
+
+impl From<editor::BufferEdit> for extension::BufferEdit {
+    fn from(value: editor::BufferEdit) -> Self {
+        Self {
+            range: value.range.into(),
+            new_text: value.new_text,
+        }
+    }
+}
+
+impl From<editor::Selection> for extension::Selection {
+    fn from(value: editor::Selection) -> Self {
+        Self {
+            range: value.range.into(),
+            reversed: value.reversed,
+        }
+    }
+}
+
+impl From<extension::Selection> for editor::Selection {
+    fn from(value: extension::Selection) -> Self {
+        Self {
+            range: value.range.into(),
+            reversed: value.reversed,
+        }
+    }
+}
+
+impl editor::Host for WasmState {}
+
+impl editor::HostBuffer for WasmState {
+    async fn text(&mut self, buffer: Resource<ExtensionBuffer>) -> wasmtime::Result<String> {
+        let buffer = self.table.get(&buffer)?;
+        Ok(buffer.text())
+    }
+
+    async fn language_name(
+        &mut self,
+        buffer: Resource<ExtensionBuffer>,
+    ) -> wasmtime::Result<Option<String>> {
+        let buffer = self.table.get(&buffer)?;
+        Ok(buffer.language_name())
+    }
+
+    async fn path(
+        &mut self,
+        buffer: Resource<ExtensionBuffer>,
+    ) -> wasmtime::Result<Option<String>> {
+        let buffer = self.table.get(&buffer)?;
+        Ok(buffer.path())
+    }
+
+    async fn version(&mut self, buffer: Resource<ExtensionBuffer>) -> wasmtime::Result<u64> {
+        let buffer = self.table.get(&buffer)?;
+        Ok(buffer.version())
+    }
+
+    async fn apply_edits(
+        &mut self,
+        buffer: Resource<ExtensionBuffer>,
+        edits: Vec<editor::BufferEdit>,
+    ) -> wasmtime::Result<Result<(), String>> {
+        let buffer = self.table.get(&buffer)?.clone();
+        let edits = edits.into_iter().map(Into::into).collect();
+        self.on_main_thread(move |cx| {
+            async move { cx.update(|cx| buffer.apply_edits(edits, cx))? }.boxed_local()
+        })
+        .await
+        .to_wasmtime_result()
+    }
+
+    async fn selections(
+        &mut self,
+        buffer: Resource<ExtensionBuffer>,
+    ) -> wasmtime::Result<Vec<editor::Selection>> {
+        let buffer = self.table.get(&buffer)?;
+        Ok(buffer.selections().into_iter().map(Into::into).collect())
+    }
+
+    async fn set_selections(
+        &mut self,
+        buffer: Resource<ExtensionBuffer>,
+        selections: Vec<editor::Selection>,
+    ) -> wasmtime::Result<Result<(), String>> {
+        let buffer = self.table.get(&buffer)?.clone();
+        let selections = selections.into_iter().map(Into::into).collect();
+        self.on_main_thread(move |cx| {
+            async move { cx.update(|cx| buffer.set_selections(selections, cx))? }.boxed_local()
+        })
+        .await
+        .to_wasmtime_result()
+    }
+
+    async fn drop(&mut self, _buffer: Resource<ExtensionBuffer>) -> Result<()> {
+        // We only ever hand out borrows of buffers.
+        Ok(())
+    }
+}
+
+impl From<extension::FormattingOptions> for formatting::FormattingOptions {
+    fn from(value: extension::FormattingOptions) -> Self {
+        Self {
+            tab_size: value.tab_size,
+            insert_spaces: value.insert_spaces,
+        }
+    }
+}
+
+impl formatting::Host for WasmState {}
+
+impl From<extension::DiagnosticSeverity> for code_actions::DiagnosticSeverity {
+    fn from(value: extension::DiagnosticSeverity) -> Self {
+        match value {
+            extension::DiagnosticSeverity::Error => Self::Error,
+            extension::DiagnosticSeverity::Warning => Self::Warning,
+            extension::DiagnosticSeverity::Information => Self::Information,
+            extension::DiagnosticSeverity::Hint => Self::Hint,
+        }
+    }
+}
+
+impl From<extension::Diagnostic> for code_actions::Diagnostic {
+    fn from(value: extension::Diagnostic) -> Self {
+        Self {
+            range: value.range.into(),
+            severity: value.severity.into(),
+            message: value.message,
+            code: value.code,
+            source: value.source,
+        }
+    }
+}
+
+impl From<code_actions::CodeAction> for extension::CodeAction {
+    fn from(value: code_actions::CodeAction) -> Self {
+        Self {
+            title: value.title,
+            kind: value.kind,
+            edits: value.edits.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl code_actions::Host for WasmState {}
+
+impl From<tasks::WorktreeTasks> for extension::WorktreeTasks {
+    fn from(value: tasks::WorktreeTasks) -> Self {
+        Self {
+            templates: value.templates.into_iter().map(Into::into).collect(),
+            watched_paths: value.watched_paths,
+        }
+    }
+}
+
+impl tasks::Host for WasmState {}
+
+impl From<web_search::WebSearchResult> for extension::WebSearchResult {
+    fn from(value: web_search::WebSearchResult) -> Self {
+        Self {
+            title: value.title,
+            url: value.url,
+            text: value.text,
+        }
+    }
+}
+
+impl web_search::Host for WasmState {}
+
+impl From<llm_provider::LlmModel> for extension::LlmModel {
+    fn from(value: llm_provider::LlmModel) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            max_token_count: value.max_token_count,
+            max_output_tokens: value.max_output_tokens,
+            capabilities: value.capabilities.into(),
+            is_default: value.is_default,
+            is_default_fast: value.is_default_fast,
+        }
+    }
+}
+
+impl From<llm_provider::LlmModelCapabilities> for extension::LlmModelCapabilities {
+    fn from(value: llm_provider::LlmModelCapabilities) -> Self {
+        Self {
+            supports_images: value.supports_images,
+            supports_tools: value.supports_tools,
+            supports_tool_choice: value.supports_tool_choice,
+        }
+    }
+}
+
+impl From<extension::LlmCompletionRequest> for llm_provider::LlmCompletionRequest {
+    fn from(value: extension::LlmCompletionRequest) -> Self {
+        Self {
+            messages: value.messages.into_iter().map(Into::into).collect(),
+            tools: value.tools.into_iter().map(Into::into).collect(),
+            tool_choice: value.tool_choice.map(Into::into),
+            stop: value.stop,
+            temperature: value.temperature,
+        }
+    }
+}
+
+impl From<extension::LlmMessage> for llm_provider::LlmMessage {
+    fn from(value: extension::LlmMessage) -> Self {
+        Self {
+            role: value.role.into(),
+            content: value.content.into_iter().map(Into::into).collect(),
+            cache: value.cache,
+        }
+    }
+}
+
+impl From<extension::LlmRole> for llm_provider::LlmRole {
+    fn from(value: extension::LlmRole) -> Self {
+        match value {
+            extension::LlmRole::User => Self::User,
+            extension::LlmRole::Assistant => Self::Assistant,
+            extension::LlmRole::System => Self::System,
+        }
+    }
+}
+
+impl From<extension::LlmMessageContent> for llm_provider::LlmMessageContent {
+    fn from(value: extension::LlmMessageContent) -> Self {
+        match value {
+            extension::LlmMessageContent::Text(text) => Self::Text(text),
+            extension::LlmMessageContent::Thinking(thinking) => Self::Thinking(thinking.into()),
+            extension::LlmMessageContent::RedactedThinking(data) => Self::RedactedThinking(data),
+            extension::LlmMessageContent::Image(image) => Self::Image(image.into()),
+            extension::LlmMessageContent::ToolUse(tool_use) => Self::ToolUse(tool_use.into()),
+            extension::LlmMessageContent::ToolResult(tool_result) => {
+                Self::ToolResult(tool_result.into())
+            }
+        }
+    }
+}
+
+impl From<extension::LlmImage> for llm_provider::LlmImage {
+    fn from(value: extension::LlmImage) -> Self {
+        Self {
+            source: value.source,
+            width: value.width,
+            height: value.height,
+        }
+    }
+}
+
+impl From<extension::LlmThinking> for llm_provider::LlmThinking {
+    fn from(value: extension::LlmThinking) -> Self {
+        Self {
+            text: value.text,
+            signature: value.signature,
+        }
+    }
+}
+
+impl From<llm_provider::LlmThinking> for extension::LlmThinking {
+    fn from(value: llm_provider::LlmThinking) -> Self {
+        Self {
+            text: value.text,
+            signature: value.signature,
+        }
+    }
+}
+
+impl From<extension::LlmToolUse> for llm_provider::LlmToolUse {
+    fn from(value: extension::LlmToolUse) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            input: value.input,
+        }
+    }
+}
+
+impl From<llm_provider::LlmToolUse> for extension::LlmToolUse {
+    fn from(value: llm_provider::LlmToolUse) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            input: value.input,
+        }
+    }
+}
+
+impl From<extension::LlmToolResult> for llm_provider::LlmToolResult {
+    fn from(value: extension::LlmToolResult) -> Self {
+        Self {
+            tool_use_id: value.tool_use_id,
+            tool_name: value.tool_name,
+            is_error: value.is_error,
+            content: match value.content {
+                extension::LlmToolResultContent::Text(text) => {
+                    llm_provider::LlmToolResultContent::Text(text)
+                }
+                extension::LlmToolResultContent::Image(image) => {
+                    llm_provider::LlmToolResultContent::Image(image.into())
+                }
+            },
+        }
+    }
+}
+
+impl From<extension::LlmTool> for llm_provider::LlmTool {
+    fn from(value: extension::LlmTool) -> Self {
+        Self {
+            name: value.name,
+            description: value.description,
+            input_schema: value.input_schema,
+        }
+    }
+}
+
+impl From<extension::LlmToolChoice> for llm_provider::LlmToolChoice {
+    fn from(value: extension::LlmToolChoice) -> Self {
+        match value {
+            extension::LlmToolChoice::Auto => Self::Auto,
+            extension::LlmToolChoice::Any => Self::Any,
+            extension::LlmToolChoice::None => Self::None,
+        }
+    }
+}
+
+impl From<llm_provider::LlmCompletionEvent> for extension::LlmCompletionEvent {
+    fn from(value: llm_provider::LlmCompletionEvent) -> Self {
+        match value {
+            llm_provider::LlmCompletionEvent::Text(text) => Self::Text(text),
+            llm_provider::LlmCompletionEvent::Thinking(thinking) => Self::Thinking(thinking.into()),
+            llm_provider::LlmCompletionEvent::ToolUse(tool_use) => Self::ToolUse(tool_use.into()),
+            llm_provider::LlmCompletionEvent::Usage(usage) => {
+                Self::Usage(extension::LlmTokenUsage {
+                    input_tokens: usage.input_tokens,
+                    output_tokens: usage.output_tokens,
+                    cache_creation_input_tokens: usage.cache_creation_input_tokens,
+                    cache_read_input_tokens: usage.cache_read_input_tokens,
+                })
+            }
+            llm_provider::LlmCompletionEvent::Stop(reason) => Self::Stop(match reason {
+                llm_provider::LlmStopReason::EndTurn => extension::LlmStopReason::EndTurn,
+                llm_provider::LlmStopReason::MaxTokens => extension::LlmStopReason::MaxTokens,
+                llm_provider::LlmStopReason::ToolUse => extension::LlmStopReason::ToolUse,
+                llm_provider::LlmStopReason::Refusal => extension::LlmStopReason::Refusal,
+            }),
+        }
+    }
+}
+
+impl llm_provider::Host for WasmState {
+    async fn read_credential(
+        &mut self,
+        provider_id: String,
+    ) -> wasmtime::Result<Result<Option<String>, String>> {
+        maybe!(async {
+            if !self
+                .manifest
+                .language_model_providers
+                .contains_key(provider_id.as_str())
+            {
+                bail!("extension does not provide the language model provider {provider_id:?}");
+            }
+
+            let url = extension::llm_provider_credentials_url(&self.manifest.id, &provider_id);
+            self.on_main_thread(move |cx| {
+                async move {
+                    let credentials_provider =
+                        cx.update(|cx| <dyn CredentialsProvider>::global(cx))?;
+                    let Some((_, credential)) =
+                        credentials_provider.read_credentials(&url, cx).await?
+                    else {
+                        return Ok(None);
+                    };
+                    Ok(Some(
+                        String::from_utf8(credential).context("invalid credential")?,
+                    ))
+                }
+                .boxed_local()
+            })
+            .await
+        })
+        .await
+        .to_wasmtime_result()
+    }
+}
+
+impl llm_provider::HostLlmCompletionStream for WasmState {
+    async fn emit(
+        &mut self,
+        stream: Resource<ExtensionLlmCompletionStream>,
+        event: llm_provider::LlmCompletionEvent,
+    ) -> wasmtime::Result<Result<(), String>> {
+        let stream = self.table.get(&stream)?;
+        Ok(stream.emit(event.into()).map_err(|error| error.to_string()))
+    }
+
+    async fn drop(&mut self, _stream: Resource<ExtensionLlmCompletionStream>) -> Result<()> {
+        // We only ever hand out borrows of completion streams.
+        Ok(())
+    }
+}
+
+impl From<panel::PanelView> for extension::PanelView {
+    fn from(value: panel::PanelView) -> Self {
+        Self {
+            elements: value.elements.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<panel::PanelElement> for extension::PanelElement {
+    fn from(value: panel::PanelElement) -> Self {
+        match value {
+            panel::PanelElement::Column(children) => Self::Column(children),
+            panel::PanelElement::Row(children) => Self::Row(children),
+            panel::PanelElement::Label(label) => Self::Label(extension::PanelLabel {
+                text: label.text,
+                color: label.color.into(),
+            }),
+            panel::PanelElement::Button(button) => Self::Button(extension::PanelButton {
+                id: button.id,
+                label: button.label,
+                disabled: button.disabled,
+            }),
+            panel::PanelElement::TextInput(input) => Self::TextInput(extension::PanelTextInput {
+                id: input.id,
+                placeholder: input.placeholder,
+                value: input.value,
+            }),
+            panel::PanelElement::ListItem(item) => Self::ListItem(extension::PanelListItem {
+                id: item.id,
+                label: item.label,
+                detail: item.detail,
+                color: item.color.into(),
+                selected: item.selected,
+                children: item.children,
+                expanded: item.expanded,
+            }),
+            panel::PanelElement::Divider => Self::Divider,
+        }
+    }
+}
+
+impl From<panel::PanelColor> for extension::PanelColor {
+    fn from(value: panel::PanelColor) -> Self {
+        match value {
+            panel::PanelColor::Default => Self::Default,
+            panel::PanelColor::Muted => Self::Muted,
+            panel::PanelColor::Accent => Self::Accent,
+            panel::PanelColor::Success => Self::Success,
+            panel::PanelColor::Warning => Self::Warning,
+            panel::PanelColor::Error => Self::Error,
+        }
+    }
+}
+
+impl From<extension::PanelEvent> for panel::PanelEvent {
+    fn from(value: extension::PanelEvent) -> Self {
+        match value {
+            extension::PanelEvent::ButtonClicked(id) => Self::ButtonClicked(id),
+            extension::PanelEvent::ListItemClicked(id) => Self::ListItemClicked(id),
+            extension::PanelEvent::ListItemToggled { id, expanded } => {
+                Self::ListItemToggled(panel::PanelListItemToggled { id, expanded })
+            }
+            extension::PanelEvent::TextInputSubmitted { id, value } => {
+                Self::TextInputSubmitted(panel::PanelTextInputSubmitted { id, value })
+            }
+        }
+    }
+}
+
+impl panel::Host for WasmState {}
+
+impl dap::Host for WasmState {
+    async fn resolve_tcp_template(
+        &mut self,
+        template: TcpArgumentsTemplate,
+    ) -> wasmtime::Result<Result<TcpArguments, String>> {
+        maybe!(async {
+            let (host, port, timeout) =
+                ::dap::configure_tcp_connection(task::TcpArgumentsTemplate {
+                    port: template.port,
+                    host: template.host.map(Ipv4Addr::from_bits),
+                    timeout: template.timeout,
+                })
+                .await?;
+            Ok(TcpArguments {
+                port,
+                host: host.to_bits(),
+                timeout,
+            })
+        })
+        .await
+        .to_wasmtime_result()
+    }
+}
+
+impl ExtensionImports for WasmState {
+    async fn get_settings(
+        &mut self,
+        location: Option<self::SettingsLocation>,
+        category: String,
+        key: Option<String>,
+    ) -> wasmtime::Result<Result<String, String>> {
+        self.on_main_thread(|cx| {
+            async move {
+                let location = location
+                    .as_ref()
+                    .map(|location| ::settings::SettingsLocation {
+                        worktree_id: WorktreeId::from_proto(location.worktree_id),
+                        path: Path::new(&location.path),
+                    });
+
+                cx.update(|cx| match category.as_str() {
+                    "language" => {
+                        let key = key.map(|k| LanguageName::new(&k));
+                        let settings = AllLanguageSettings::get(location, cx).language(
+                            location,
+                            key.as_ref(),
+                            cx,
+                        );
+                        Ok(serde_json::to_string(&settings::LanguageSettings {
+                            tab_size: settings.tab_size,
+                        })?)
+                    }
+                    "lsp" => {
+                        let settings = key
+                            .and_then(|key| {
+                                ProjectSettings::get(location, cx)
+                                    .lsp
+                                    .get(&::lsp::LanguageServerName::from_proto(key))
+                            })
+                            .cloned()
+                            .unwrap_or_default();
+                        Ok(serde_json::to_string(&settings::LspSettings {
+                            binary: settings.binary.map(|binary| settings::CommandSettings {
+                                path: binary.path,
+                                arguments: binary.arguments,
+                                env: binary.env,
+                            }),
+                            settings: settings.settings,
+                            initialization_options: settings.initialization_options,
+                        })?)
+                    }
+                    "context_servers" => {
+                        let settings = key
+                            .and_then(|key| {
+                                ProjectSettings::get(location, cx)
+                                    .context_servers
+                                    .get(key.as_str())
+                            })
+                            .cloned()
+                            .unwrap_or_else(|| {
+                                project::project_settings::ContextServerSettings::default_extension(
+                                )
+                            });
+
+                        match settings {
+                            project::project_settings::ContextServerSettings::Custom {
+                                enabled: _,
+                                command,
+                            } => Ok(serde_json::to_string(&settings::ContextServerSettings {
+                                command: Some(settings::CommandSettings {
+                                    path: Some(command.path),
+                                    arguments: Some(command.args),
+                                    env: command.env.map(|env| env.into_iter().collect()),
+                                }),
+                                settings: None,
+                            })?),
+                            project::project_settings::ContextServerSettings::Extension {
+                                enabled: _,
+                                settings,
+                            } => Ok(serde_json::to_string(&settings::ContextServerSettings {
+                                command: None,
+                                settings: Some(settings),
+                            })?),
+                        }
+                    }
+                    _ => {
+                        bail!("Unknown settings category: {}", category);
+                    }
+                })
+            }
+            .boxed_local()
+        })
+        .await?
+        .to_wasmtime_result()
+    }
+
+    async fn set_language_server_installation_status(
+        &mut self,
+        server_name: String,
+        status: LanguageServerInstallationStatus,
+    ) -> wasmtime::Result<()> {
+        let status = match status {
+            LanguageServerInstallationStatus::CheckingForUpdate => BinaryStatus::CheckingForUpdate,
+            LanguageServerInstallationStatus::Downloading => BinaryStatus::Downloading,
+            LanguageServerInstallationStatus::None => BinaryStatus::None,
+            LanguageServerInstallationStatus::Failed(error) => BinaryStatus::Failed { error },
+        };
+
+        self.host
+            .proxy
+            .update_language_server_status(::lsp::LanguageServerName(server_name.into()), status);
+
+        Ok(())
+    }
+
+    async fn download_file(
+        &mut self,
+        url: String,
+        path: String,
+        file_type: DownloadedFileType,
+    ) -> wasmtime::Result<Result<(), String>> {
+        maybe!(async {
+            self.capability_granter.grant_work_dir()?;
+            self.capability_granter.grant_http(&url)?;
+
+            let path = PathBuf::from(path);
+            let extension_work_dir = self.host.work_dir.join(self.manifest.id.as_ref());
+
+            self.host.fs.create_dir(&extension_work_dir).await?;
+
+            let destination_path = self
+                .host
+                .writeable_path_from_extension(&self.manifest.id, &path)?;
+
+            let request = ::http_client::Request::get(&url)
+                .follow_redirects(::http_client::RedirectPolicy::FollowAll)
+                .body(Vec::new())?;
+            let mut response = self
+                .send_http_request(request)
+                .await
+                .context("downloading release")?;
+
+            anyhow::ensure!(
+                response.status().is_success(),
+                "download failed with status {}",
+                response.status().to_string()
+            );
+            let body = BufReader::new(response.body_mut());
+
+            match file_type {
+                DownloadedFileType::Uncompressed => {
+                    futures::pin_mut!(body);
+                    self.host
+                        .fs
+                        .create_file_with(&destination_path, body)
+                        .await?;
+                }
+                DownloadedFileType::Gzip => {
+                    let body = GzipDecoder::new(body);
+                    futures::pin_mut!(body);
+                    self.host
+                        .fs
+                        .create_file_with(&destination_path, body)
+                        .await?;
+                }
+                DownloadedFileType::GzipTar => {
+                    let body = GzipDecoder::new(body);
+                    futures::pin_mut!(body);
+                    self.host
+                        .fs
+                        .extract_tar_file(&destination_path, Archive::new(body))
+                        .await?;
+                }
+                DownloadedFileType::Zip => {
+                    futures::pin_mut!(body);
+                    extract_zip(&destination_path, body)
+                        .await
+                        .with_context(|| format!("unzipping {path:?} archive"))?;
+                }
+            }
+
+            Ok(())
+        })
+        .await
+        .to_wasmtime_result()
+    }
+
+    async fn make_file_executable(&mut self, path: String) -> wasmtime::Result<Result<(), String>> {
+        if let Err(error) = self.capability_granter.grant_work_dir() {
+            return Ok(Err(error.to_string()));
+        }
+
+        let path = self
+            .host
+            .writeable_path_from_extension(&self.manifest.id, Path::new(&path))?;
+
+        make_file_executable(&path)
+            .await
+            .with_context(|| format!("setting permissions for path {path:?}"))
+            .to_wasmtime_result()
+    }
+}
@@ -0,0 +1,129 @@
+use std::mem;
+use std::sync::Arc;
+
+use anyhow::Result;
+use extension::{ExtensionCapability, ExtensionManifest};
+use extension_host::ExtensionCapabilityPrompt;
+use extension_host::ExtensionStore;
+use extension_host::extension_settings::ExtensionCapabilityOverrides;
+use gpui::{AnyWindowHandle, App, AsyncApp, PromptLevel, Task};
+use workspace::Workspace;
+
+pub(crate) fn init(cx: &mut App) {
+    let Some(extension_store) = ExtensionStore::try_global(cx) else {
+        return;
+    };
+    extension_store.update(cx, |extension_store, _| {
+        extension_store.set_capability_prompt(WindowCapabilityPrompt);
+    });
+
+    // Extensions may have been installed or updated while there was no window to ask about
+    // their capabilities in, so ask once there is one.
+    cx.observe_new(|_: &mut Workspace, _, cx| {
+        cx.defer(|cx| {
+            if let Some(extension_store) = ExtensionStore::try_global(cx) {
+                extension_store.update(cx, |extension_store, cx| {
+                    extension_store.confirm_unconfirmed_capabilities(cx);
+                });
+            }
+        });
+    })
+    .detach();
+}
+
+/// Asks for an extension's capabilities in the active window, or in any workspace window if
+/// none is active.
+///
+/// Without any window, the user isn't asked until one is opened.
+struct WindowCapabilityPrompt;
+
+impl ExtensionCapabilityPrompt for WindowCapabilityPrompt {
+    fn prompt_for_capabilities(
+        &self,
+        manifest: Arc<ExtensionManifest>,
+        cx: &mut App,
+    ) -> Task<Option<ExtensionCapabilityOverrides>> {
+        let window = cx.active_window().or_else(|| {
+            cx.windows()
+                .into_iter()
+                .find(|window| window.downcast::<Workspace>().is_some())
+        });
+        let Some(window) = window else {
+            return Task::ready(None);
+        };
+
+        // If the window is closed before the user has decided, they are asked again later.
+        cx.spawn(async move |cx| prompt_for_capabilities(&manifest, window, cx).await.ok())
+    }
+}
+
+/// Shows the user the capabilities an extension declares, and lets them allow or deny all of
+/// them, or decide on each kind of capability in turn.
+///
+/// Dismissing any of the prompts denies every capability.
+async fn prompt_for_capabilities(
+    manifest: &ExtensionManifest,
+    window: AnyWindowHandle,
+    cx: &mut AsyncApp,
+) -> Result<ExtensionCapabilityOverrides> {
+    let message = format!("{} requests the following capabilities:", manifest.name);
+    let detail = describe_capabilities(manifest.capabilities.iter());
+    let answer = window
+        .update(cx, |_, window, cx| {
+            window.prompt(
+                PromptLevel::Info,
+                &message,
+                Some(&detail),
+                &["Allow All", "Choose…", "Deny All"],
+                cx,
+            )
+        })?
+        .await?;
+    match answer {
+        0 => return Ok(ExtensionCapabilityOverrides::default()),
+        1 => {}
+        _ => return Ok(ExtensionCapabilityOverrides::deny_all()),
+    }
+
+    // Capabilities are granted or denied by kind, so ask about each kind once.
+    let mut kinds = Vec::<Vec<&ExtensionCapability>>::new();
+    for capability in &manifest.capabilities {
+        match kinds
+            .iter_mut()
+            .find(|kind| mem::discriminant(kind[0]) == mem::discriminant(capability))
+        {
+            Some(kind) => kind.push(capability),
+            None => kinds.push(vec![capability]),
+        }
+    }
+
+    let mut overrides = ExtensionCapabilityOverrides::default();
+    for kind in kinds {
+        let message = format!("Allow {} to:", manifest.name);
+        let detail = describe_capabilities(kind.iter().copied());
+        let answer = window
+            .update(cx, |_, window, cx| {
+                window.prompt(
+                    PromptLevel::Info,
+                    &message,
+                    Some(&detail),
+                    &["Allow", "Deny"],
+                    cx,
+                )
+            })?
+            .await?;
+        if answer != 0 {
+            overrides.deny(kind[0]);
+        }
+    }
+    Ok(overrides)
+}
+
+fn describe_capabilities<'a>(
+    capabilities: impl Iterator<Item = &'a ExtensionCapability>,
+) -> String {
+    capabilities
+        .map(|capability| format!("• {capability}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
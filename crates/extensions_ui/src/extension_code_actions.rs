@@ -0,0 +1,391 @@
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use editor::{CodeActionProvider, Editor, ExcerptId};
+use extension::{
+    Extension, ExtensionCodeActionProviderProxy, ExtensionHostProxy, sort_and_validate_buffer_edits,
+};
+use futures::future::join_all;
+use gpui::{App, Entity, Global, Task, Window};
+use language::{
+    Anchor, Buffer, BufferId, BufferSnapshot, LanguageName, LanguageServerId, ToOffset as _,
+};
+use project::{CodeAction, LspAction, ProjectTransaction};
+use util::ResultExt as _;
+
+const EXTENSION_CODE_ACTION_PROVIDER_ID: &str = "extensions";
+
+pub(crate) fn init(cx: &mut App) {
+    let proxy = ExtensionHostProxy::default_global(cx);
+    proxy.register_code_action_provider_proxy(CodeActionProviderRegistryProxy);
+
+    cx.observe_new(|editor: &mut Editor, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+        if !editor.mode().is_full() {
+            return;
+        }
+
+        editor.add_code_action_provider(
+            Rc::new(ExtensionCodeActionProvider::default()),
+            window,
+            cx,
+        );
+    })
+    .detach();
+}
+
+/// The code action providers that have been registered by extensions, in the order in which
+/// they were registered.
+#[derive(Default)]
+struct GlobalExtensionCodeActionProviders {
+    providers: Vec<RegisteredCodeActionProvider>,
+}
+
+impl Global for GlobalExtensionCodeActionProviders {}
+
+#[derive(Clone)]
+struct RegisteredCodeActionProvider {
+    id: Arc<str>,
+    extension: Arc<dyn Extension>,
+    languages: Vec<LanguageName>,
+}
+
+impl RegisteredCodeActionProvider {
+    fn is(&self, extension_id: &str, provider_id: &str) -> bool {
+        self.extension.manifest().id.as_ref() == extension_id && self.id.as_ref() == provider_id
+    }
+}
+
+struct CodeActionProviderRegistryProxy;
+
+impl ExtensionCodeActionProviderProxy for CodeActionProviderRegistryProxy {
+    fn register_code_action_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider_id: Arc<str>,
+        languages: Vec<LanguageName>,
+        cx: &mut App,
+    ) {
+        let extension_id = extension.manifest().id.clone();
+        let providers = &mut cx
+            .default_global::<GlobalExtensionCodeActionProviders>()
+            .providers;
+        providers.retain(|provider| !provider.is(&extension_id, &provider_id));
+        providers.push(RegisteredCodeActionProvider {
+            id: provider_id,
+            extension,
+            languages,
+        });
+    }
+
+    fn unregister_code_action_provider(
+        &self,
+        extension_id: Arc<str>,
+        provider_id: Arc<str>,
+        cx: &mut App,
+    ) {
+        cx.default_global::<GlobalExtensionCodeActionProviders>()
+            .providers
+            .retain(|provider| !provider.is(&extension_id, &provider_id));
+    }
+}
+
+/// Offers the code actions of every extension code action provider that supports the
+/// language of a buffer.
+#[derive(Default)]
+struct ExtensionCodeActionProvider {
+    pending_actions: Rc<RefCell<PendingCodeActions>>,
+}
+
+/// The edits of the code actions that were most recently offered for each buffer,
+/// keyed by the ID stored in each code action's `data`.
+#[derive(Default)]
+struct PendingCodeActions {
+    next_id: u64,
+    by_buffer: HashMap<BufferId, HashMap<u64, Vec<(Range<Anchor>, Arc<str>)>>>,
+}
+
+impl CodeActionProvider for ExtensionCodeActionProvider {
+    fn id(&self) -> Arc<str> {
+        EXTENSION_CODE_ACTION_PROVIDER_ID.into()
+    }
+
+    fn code_actions(
+        &self,
+        buffer: &Entity<Buffer>,
+        range: Range<Anchor>,
+        _window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Vec<CodeAction>>> {
+        let snapshot = buffer.read(cx).snapshot();
+        let Some(language_name) = snapshot.language().map(|language| language.name()) else {
+            return Task::ready(Ok(Vec::new()));
+        };
+        let providers = cx
+            .try_global::<GlobalExtensionCodeActionProviders>()
+            .map(|registered| {
+                registered
+                    .providers
+                    .iter()
+                    .filter(|provider| provider.languages.contains(&language_name))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if providers.is_empty() {
+            return Task::ready(Ok(Vec::new()));
+        }
+
+        let range = range.start.to_offset(&snapshot)..range.end.to_offset(&snapshot);
+        let diagnostics = snapshot
+            .diagnostics_in_range::<_, usize>(range.clone(), false)
+            .map(|entry| extension::Diagnostic {
+                range: entry.range,
+                severity: diagnostic_severity(entry.diagnostic.severity),
+                message: entry.diagnostic.message,
+                code: entry.diagnostic.code.map(|code| match code {
+                    lsp::NumberOrString::Number(code) => code.to_string(),
+                    lsp::NumberOrString::String(code) => code,
+                }),
+                source: entry.diagnostic.source,
+            })
+            .collect::<Vec<_>>();
+        let text = snapshot.text();
+        let pending_actions = self.pending_actions.clone();
+
+        cx.spawn(async move |_| {
+            let responses = join_all(providers.iter().map(|provider| {
+                provider.extension.code_actions(
+                    provider.id.clone(),
+                    language_name.clone(),
+                    text.clone(),
+                    range.clone(),
+                    diagnostics.clone(),
+                )
+            }))
+            .await;
+
+            let pending_actions = &mut *pending_actions.borrow_mut();
+            let actions_for_buffer = pending_actions
+                .by_buffer
+                .entry(snapshot.remote_id())
+                .or_default();
+            actions_for_buffer.clear();
+
+            let mut code_actions = Vec::new();
+            for (provider, response) in providers.iter().zip(responses) {
+                let Some(actions) = response
+                    .with_context(|| {
+                        format!(
+                            "failed to get code actions from extension {}",
+                            provider.extension.manifest().id
+                        )
+                    })
+                    .log_err()
+                else {
+                    continue;
+                };
+
+                for action in actions {
+                    let Some(edits) = resolve_edits(action.edits, &text, &snapshot)
+                        .with_context(|| format!("invalid edits in code action {}", action.title))
+                        .log_err()
+                    else {
+                        continue;
+                    };
+
+                    let id = pending_actions.next_id;
+                    pending_actions.next_id += 1;
+                    actions_for_buffer.insert(id, edits);
+                    code_actions.push(CodeAction {
+                        server_id: LanguageServerId(0),
+                        range: snapshot.anchor_before(range.start)
+                            ..snapshot.anchor_after(range.end),
+                        lsp_action: LspAction::Action(Box::new(lsp::CodeAction {
+                            title: action.title,
+                            kind: action.kind.map(lsp::CodeActionKind::from),
+                            data: Some(id.into()),
+                            ..Default::default()
+                        })),
+                        resolved: true,
+                    });
+                }
+            }
+
+            Ok(code_actions)
+        })
+    }
+
+    fn apply_code_action(
+        &self,
+        buffer_handle: Entity<Buffer>,
+        action: CodeAction,
+        _excerpt_id: ExcerptId,
+        push_to_history: bool,
+        _window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<ProjectTransaction>> {
+        let id = match &action.lsp_action {
+            LspAction::Action(action) => action.data.as_ref().and_then(|data| data.as_u64()),
+            LspAction::Command(_) | LspAction::CodeLens(_) => None,
+        };
+        let buffer_id = buffer_handle.read(cx).remote_id();
+        let edits = id.and_then(|id| {
+            self.pending_actions
+                .borrow_mut()
+                .by_buffer
+                .get_mut(&buffer_id)?
+                .remove(&id)
+        });
+        let Some(edits) = edits else {
+            return Task::ready(Err(anyhow::anyhow!(
+                "code action {} is no longer available",
+                action.lsp_action.title()
+            )));
+        };
+
+        let transaction = buffer_handle.update(cx, |buffer, cx| {
+            buffer.finalize_last_transaction();
+            buffer.start_transaction();
+            buffer.edit(edits, None, cx);
+            let transaction_id = buffer.end_transaction(cx)?;
+            if push_to_history {
+                buffer.finalize_last_transaction();
+                buffer.get_transaction(transaction_id).cloned()
+            } else {
+                buffer.forget_transaction(transaction_id)
+            }
+        });
+
+        let mut project_transaction = ProjectTransaction::default();
+        if let Some(transaction) = transaction {
+            project_transaction.0.insert(buffer_handle, transaction);
+        }
+        Task::ready(Ok(project_transaction))
+    }
+}
+
+/// Converts the edits of a code action, which are relative to the given text, into anchors in
+/// the buffer, so that they remain valid if the buffer is edited before the action is applied.
+fn resolve_edits(
+    mut edits: Vec<extension::BufferEdit>,
+    text: &str,
+    snapshot: &BufferSnapshot,
+) -> Result<Vec<(Range<Anchor>, Arc<str>)>> {
+    sort_and_validate_buffer_edits(&mut edits, text)?;
+    Ok(edits
+        .into_iter()
+        .map(|edit| {
+            let range =
+                snapshot.anchor_after(edit.range.start)..snapshot.anchor_before(edit.range.end);
+            (range, edit.new_text.into())
+        })
+        .collect())
+}
+
+fn diagnostic_severity(severity: lsp::DiagnosticSeverity) -> extension::DiagnosticSeverity {
+    match severity {
+        lsp::DiagnosticSeverity::ERROR => extension::DiagnosticSeverity::Error,
+        lsp::DiagnosticSeverity::WARNING => extension::DiagnosticSeverity::Warning,
+        lsp::DiagnosticSeverity::INFORMATION => extension::DiagnosticSeverity::Information,
+        _ => extension::DiagnosticSeverity::Hint,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use extension::{BufferEdit, FakeExtension};
+    use gpui::TestAppContext;
+    use language::{Language, LanguageConfig};
+
+    use super::*;
+    use crate::tests::init_test;
+
+    #[gpui::test]
+    async fn test_extension_code_actions(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let extension = FakeExtension::new("test-extension");
+        extension.on_code_actions(|provider_id, language_name, text, range| {
+            assert_eq!(provider_id.as_ref(), "fixes");
+            assert_eq!(language_name.as_ref(), "Rust");
+            assert_eq!(&text[range], "let x");
+            Ok(vec![extension::CodeAction {
+                title: "Rename x to y".into(),
+                kind: Some("quickfix".into()),
+                edits: vec![BufferEdit {
+                    range: 4..5,
+                    new_text: "y".into(),
+                }],
+            }])
+        });
+        cx.update(|cx| {
+            CodeActionProviderRegistryProxy.register_code_action_provider(
+                extension.clone(),
+                "fixes".into(),
+                vec!["Rust".into()],
+                cx,
+            )
+        });
+
+        let language = Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                ..Default::default()
+            },
+            None,
+        ));
+        let buffer = cx.new(|cx| Buffer::local("let x = 1;", cx).with_language(language, cx));
+        let (_, cx) =
+            cx.add_window_view(|window, cx| Editor::for_buffer(buffer.clone(), None, window, cx));
+        let range = buffer.read_with(cx, |buffer, _| {
+            buffer.anchor_before(0)..buffer.anchor_after(5)
+        });
+
+        let provider = ExtensionCodeActionProvider::default();
+        let actions = cx
+            .update(|window, cx| provider.code_actions(&buffer, range.clone(), window, cx))
+            .await
+            .unwrap();
+        assert_eq!(
+            actions
+                .iter()
+                .map(|action| action.lsp_action.title())
+                .collect::<Vec<_>>(),
+            ["Rename x to y"]
+        );
+
+        // The action's edits still apply after the buffer is edited elsewhere.
+        buffer.update(cx, |buffer, cx| buffer.edit([(10..10, "\n")], None, cx));
+        let action = actions.into_iter().next().unwrap();
+        cx.update(|window, cx| {
+            provider.apply_code_action(buffer.clone(), action, ExcerptId::min(), true, window, cx)
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            buffer.read_with(cx, |buffer, _| buffer.text()),
+            "let y = 1;\n"
+        );
+
+        // Unregistered providers no longer offer code actions.
+        cx.update(|_, cx| {
+            CodeActionProviderRegistryProxy.unregister_code_action_provider(
+                "test-extension".into(),
+                "fixes".into(),
+                cx,
+            )
+        });
+        let actions = cx
+            .update(|window, cx| provider.code_actions(&buffer, range, window, cx))
+            .await
+            .unwrap();
+        assert!(actions.is_empty());
+    }
+}
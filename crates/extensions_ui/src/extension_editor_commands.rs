@@ -0,0 +1,408 @@
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use command_palette_hooks::CommandPaletteCommands;
+use editor::{Editor, SelectionEffects};
+use extension::{
+    BufferDelegate, BufferEdit, EditorCommand, Extension, ExtensionEditorCommandProxy,
+    ExtensionHostProxy, Selection, sort_and_validate_buffer_edits,
+};
+use gpui::{Action, AnyWindowHandle, App, Entity, Global, SharedString, Task, WeakEntity, Window};
+use language::{Buffer, BufferSnapshot};
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use workspace::notifications::NotifyTaskExt as _;
+
+/// Runs an editor command provided by an extension against the buffer in the focused editor.
+#[derive(Clone, PartialEq, Deserialize, JsonSchema, Action)]
+#[action(namespace = extensions)]
+#[serde(deny_unknown_fields)]
+pub struct RunEditorCommand {
+    /// The ID of the extension that provides the command.
+    pub extension_id: String,
+    /// The ID of the command, as declared in the extension's manifest.
+    pub command_id: String,
+}
+
+pub(crate) fn init(cx: &mut App) {
+    let proxy = ExtensionHostProxy::default_global(cx);
+    proxy.register_editor_command_proxy(EditorCommandRegistryProxy);
+
+    cx.observe_new(|editor: &mut Editor, _window, cx| {
+        if !editor.mode().is_full() {
+            return;
+        }
+
+        let editor_handle = cx.entity().downgrade();
+        editor
+            .register_action(move |action: &RunEditorCommand, window, cx| {
+                run_editor_command(&editor_handle, action, window, cx)
+            })
+            .detach();
+    })
+    .detach();
+}
+
+/// The editor commands that have been registered by extensions, keyed by extension ID and command ID.
+#[derive(Default)]
+struct GlobalExtensionEditorCommands {
+    commands: HashMap<(Arc<str>, Arc<str>), RegisteredEditorCommand>,
+}
+
+impl Global for GlobalExtensionEditorCommands {}
+
+struct RegisteredEditorCommand {
+    extension: Arc<dyn Extension>,
+    /// The name under which the command is listed in the command palette.
+    name: SharedString,
+}
+
+struct EditorCommandRegistryProxy;
+
+impl ExtensionEditorCommandProxy for EditorCommandRegistryProxy {
+    fn register_editor_command(
+        &self,
+        extension: Arc<dyn Extension>,
+        command: EditorCommand,
+        cx: &mut App,
+    ) {
+        let manifest = extension.manifest();
+        let name = SharedString::from(format!("{}: {}", manifest.name, command.title));
+        let action = RunEditorCommand {
+            extension_id: manifest.id.to_string(),
+            command_id: command.id.to_string(),
+        };
+        CommandPaletteCommands::update_global(cx, |commands, _| {
+            commands.register(name.clone(), action.boxed_clone());
+        });
+
+        let previous = cx
+            .default_global::<GlobalExtensionEditorCommands>()
+            .commands
+            .insert(
+                (manifest.id.clone(), command.id),
+                RegisteredEditorCommand { extension, name },
+            );
+        if let Some(previous) = previous {
+            unregister_palette_command(&previous, cx);
+        }
+    }
+
+    fn unregister_editor_command(
+        &self,
+        extension_id: Arc<str>,
+        command_id: Arc<str>,
+        cx: &mut App,
+    ) {
+        let Some(registered) = cx
+            .default_global::<GlobalExtensionEditorCommands>()
+            .commands
+            .remove(&(extension_id, command_id))
+        else {
+            return;
+        };
+
+        unregister_palette_command(&registered, cx);
+    }
+}
+
+fn unregister_palette_command(registered: &RegisteredEditorCommand, cx: &mut App) {
+    // Another registration may have taken over this name in the meantime.
+    let name_in_use = cx
+        .global::<GlobalExtensionEditorCommands>()
+        .commands
+        .values()
+        .any(|command| command.name == registered.name);
+    if !name_in_use {
+        CommandPaletteCommands::update_global(cx, |commands, _| {
+            commands.unregister(&registered.name);
+        });
+    }
+}
+
+fn run_editor_command(
+    editor: &WeakEntity<Editor>,
+    action: &RunEditorCommand,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let task = match start_editor_command(editor, action, window, cx) {
+        Ok(task) => task,
+        Err(error) => Task::ready(Err(error)),
+    };
+    task.detach_and_notify_err(window, cx);
+}
+
+fn start_editor_command(
+    editor: &WeakEntity<Editor>,
+    action: &RunEditorCommand,
+    window: &mut Window,
+    cx: &mut App,
+) -> Result<Task<Result<()>>> {
+    let editor = editor.upgrade().context("editor was released")?;
+    let command_id: Arc<str> = action.command_id.as_str().into();
+    let extension = cx
+        .try_global::<GlobalExtensionEditorCommands>()
+        .and_then(|registered| {
+            registered
+                .commands
+                .get(&(action.extension_id.as_str().into(), command_id.clone()))
+        })
+        .map(|registered| registered.extension.clone())
+        .with_context(|| {
+            format!(
+                "extension {} does not provide an editor command named {}",
+                action.extension_id, action.command_id
+            )
+        })?;
+
+    let buffer = Arc::new(EditorBufferDelegate::new(editor, window, cx)?);
+    Ok(cx.background_spawn(async move { extension.run_editor_command(command_id, buffer).await }))
+}
+
+/// Exposes the buffer in an editor to an extension.
+///
+/// The buffer's contents are captured when the command starts, and refreshed whenever
+/// the extension makes changes, so that an extension always sees the effect of its own edits.
+struct EditorBufferDelegate {
+    window: AnyWindowHandle,
+    editor: WeakEntity<Editor>,
+    buffer: WeakEntity<Buffer>,
+    state: Mutex<BufferState>,
+}
+
+struct BufferState {
+    text: String,
+    language_name: Option<String>,
+    path: Option<String>,
+    version: u64,
+    selections: Vec<Selection>,
+}
+
+impl EditorBufferDelegate {
+    fn new(editor: Entity<Editor>, window: &Window, cx: &mut App) -> Result<Self> {
+        let buffer = editor
+            .read(cx)
+            .buffer()
+            .read(cx)
+            .as_singleton()
+            .context("extension editor commands can only be run in single-buffer editors")?;
+        let state = BufferState::read(&editor, &buffer, cx);
+        Ok(Self {
+            window: window.window_handle(),
+            editor: editor.downgrade(),
+            buffer: buffer.downgrade(),
+            state: Mutex::new(state),
+        })
+    }
+
+    fn entities(&self) -> Result<(Entity<Editor>, Entity<Buffer>)> {
+        let editor = self.editor.upgrade().context("editor was closed")?;
+        let buffer = self.buffer.upgrade().context("buffer was closed")?;
+        Ok((editor, buffer))
+    }
+}
+
+impl BufferState {
+    fn read(editor: &Entity<Editor>, buffer: &Entity<Buffer>, cx: &mut App) -> Self {
+        let selections = editor.update(cx, |editor, cx| editor.selections.all::<usize>(cx));
+        let snapshot = buffer.read(cx).snapshot();
+        Self {
+            text: snapshot.text(),
+            language_name: snapshot
+                .language()
+                .map(|language| language.name().0.to_string()),
+            path: snapshot
+                .file()
+                .map(|file| file.path().to_string_lossy().into_owned()),
+            version: buffer_version(&snapshot),
+            selections: selections
+                .into_iter()
+                .map(|selection| Selection {
+                    range: selection.start..selection.end,
+                    reversed: selection.reversed,
+                })
+                .collect(),
+        }
+    }
+
+    /// Ensures that the offsets the extension computed against this state still apply to the buffer.
+    fn ensure_current(&self, buffer: &Entity<Buffer>, cx: &App) -> Result<()> {
+        anyhow::ensure!(
+            buffer_version(&buffer.read(cx).snapshot()) == self.version,
+            "buffer has changed since it was read"
+        );
+        Ok(())
+    }
+
+    fn ensure_valid_range(&self, range: &std::ops::Range<usize>) -> Result<()> {
+        anyhow::ensure!(
+            range.start <= range.end && range.end <= self.text.len(),
+            "range {range:?} is out of bounds"
+        );
+        anyhow::ensure!(
+            self.text.is_char_boundary(range.start) && self.text.is_char_boundary(range.end),
+            "range {range:?} is not on a character boundary"
+        );
+        Ok(())
+    }
+}
+
+/// Returns a number that increases every time the buffer is edited, by any replica.
+fn buffer_version(snapshot: &BufferSnapshot) -> u64 {
+    snapshot
+        .version()
+        .iter()
+        .map(|timestamp| timestamp.value as u64)
+        .sum()
+}
+
+impl BufferDelegate for EditorBufferDelegate {
+    fn text(&self) -> String {
+        self.state.lock().text.clone()
+    }
+
+    fn language_name(&self) -> Option<String> {
+        self.state.lock().language_name.clone()
+    }
+
+    fn path(&self) -> Option<String> {
+        self.state.lock().path.clone()
+    }
+
+    fn version(&self) -> u64 {
+        self.state.lock().version
+    }
+
+    fn selections(&self) -> Vec<Selection> {
+        self.state.lock().selections.clone()
+    }
+
+    fn apply_edits(&self, mut edits: Vec<BufferEdit>, cx: &mut App) -> Result<()> {
+        let (editor, buffer) = self.entities()?;
+        let mut state = self.state.lock();
+        state.ensure_current(&buffer, cx)?;
+
+        sort_and_validate_buffer_edits(&mut edits, &state.text)?;
+
+        editor.update(cx, |editor, cx| {
+            anyhow::ensure!(!editor.read_only(cx), "editor is read-only");
+            editor.edit(
+                edits.into_iter().map(|edit| (edit.range, edit.new_text)),
+                cx,
+            );
+            anyhow::Ok(())
+        })?;
+
+        *state = BufferState::read(&editor, &buffer, cx);
+        Ok(())
+    }
+
+    fn set_selections(&self, selections: Vec<Selection>, cx: &mut App) -> Result<()> {
+        let (editor, buffer) = self.entities()?;
+        let mut state = self.state.lock();
+        state.ensure_current(&buffer, cx)?;
+        anyhow::ensure!(!selections.is_empty(), "at least one selection is required");
+        for selection in &selections {
+            state.ensure_valid_range(&selection.range)?;
+        }
+
+        self.window.update(cx, |_, window, cx| {
+            editor.update(cx, |editor, cx| {
+                editor.change_selections(SelectionEffects::default(), window, cx, |s| {
+                    s.select_ranges(selections.into_iter().map(|selection| {
+                        if selection.reversed {
+                            selection.range.end..selection.range.start
+                        } else {
+                            selection.range
+                        }
+                    }))
+                });
+            })
+        })?;
+
+        *state = BufferState::read(&editor, &buffer, cx);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::TestAppContext;
+
+    use super::*;
+    use crate::tests::init_test;
+
+    #[gpui::test]
+    async fn test_apply_edits(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let buffer = cx.new(|cx| Buffer::local("one two three", cx));
+        let (editor, cx) =
+            cx.add_window_view(|window, cx| Editor::for_buffer(buffer.clone(), None, window, cx));
+        let delegate = cx
+            .update(|window, cx| EditorBufferDelegate::new(editor.clone(), window, cx))
+            .unwrap();
+
+        // Overlapping edits are rejected without changing the buffer.
+        let result = cx.update(|_, cx| {
+            delegate.apply_edits(
+                vec![
+                    BufferEdit {
+                        range: 0..3,
+                        new_text: "1".into(),
+                    },
+                    BufferEdit {
+                        range: 2..7,
+                        new_text: "2".into(),
+                    },
+                ],
+                cx,
+            )
+        });
+        assert!(result.is_err());
+        assert_eq!(
+            buffer.read_with(cx, |buffer, _| buffer.text()),
+            "one two three"
+        );
+
+        // Valid edits are applied, and the extension sees their effect.
+        cx.update(|_, cx| {
+            delegate.apply_edits(
+                vec![
+                    BufferEdit {
+                        range: 4..7,
+                        new_text: "2".into(),
+                    },
+                    BufferEdit {
+                        range: 0..3,
+                        new_text: "1".into(),
+                    },
+                ],
+                cx,
+            )
+        })
+        .unwrap();
+        assert_eq!(buffer.read_with(cx, |buffer, _| buffer.text()), "1 2 three");
+        assert_eq!(delegate.text(), "1 2 three");
+
+        // Edits computed against a buffer that has since changed are rejected.
+        buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "0 ")], None, cx));
+        let result = cx.update(|_, cx| {
+            delegate.apply_edits(
+                vec![BufferEdit {
+                    range: 0..1,
+                    new_text: "one".into(),
+                }],
+                cx,
+            )
+        });
+        assert!(result.is_err());
+        assert_eq!(
+            buffer.read_with(cx, |buffer, _| buffer.text()),
+            "0 1 2 three"
+        );
+    }
+}
@@ -0,0 +1,726 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use collections::{BTreeMap, HashMap};
+use editor::Editor;
+use extension::{
+    Extension, ExtensionHostProxy, ExtensionPanelProxy, PanelColor, PanelElement, PanelEvent,
+    PanelListItem, PanelManifestEntry, PanelView,
+};
+use gpui::{Entity, EventEmitter, FocusHandle, Focusable, Global, Render, Subscription, actions};
+use ui::{Divider, ListItem, ListItemSpacing, prelude::*};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent as DockPanelEvent},
+};
+
+actions!(extension_panel, [ToggleFocus]);
+
+const DEFAULT_WIDTH: Pixels = px(320.);
+
+pub(crate) fn init(cx: &mut App) {
+    let proxy = ExtensionHostProxy::default_global(cx);
+    proxy.register_panel_proxy(PanelRegistryProxy);
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<ExtensionPanel>(window, cx);
+        });
+    })
+    .detach();
+}
+
+/// Identifies a panel by the ID of the extension that provides it and the ID of the panel.
+type PanelKey = (Arc<str>, Arc<str>);
+
+/// The panels that have been registered by extensions.
+#[derive(Default)]
+struct ExtensionPanelRegistry {
+    panels: BTreeMap<PanelKey, RegisteredPanel>,
+}
+
+struct RegisteredPanel {
+    extension: Arc<dyn Extension>,
+    title: SharedString,
+}
+
+struct GlobalExtensionPanelRegistry(Entity<ExtensionPanelRegistry>);
+
+impl Global for GlobalExtensionPanelRegistry {}
+
+impl ExtensionPanelRegistry {
+    fn global(cx: &mut App) -> Entity<Self> {
+        if let Some(registry) = cx.try_global::<GlobalExtensionPanelRegistry>() {
+            return registry.0.clone();
+        }
+
+        let registry = cx.new(|_| Self::default());
+        cx.set_global(GlobalExtensionPanelRegistry(registry.clone()));
+        registry
+    }
+}
+
+struct PanelRegistryProxy;
+
+impl ExtensionPanelProxy for PanelRegistryProxy {
+    fn register_panel(
+        &self,
+        extension: Arc<dyn Extension>,
+        panel_id: Arc<str>,
+        panel: PanelManifestEntry,
+        cx: &mut App,
+    ) {
+        let key = (extension.manifest().id.clone(), panel_id);
+        ExtensionPanelRegistry::global(cx).update(cx, |registry, cx| {
+            registry.panels.insert(
+                key,
+                RegisteredPanel {
+                    extension,
+                    title: panel.title.into(),
+                },
+            );
+            cx.notify();
+        });
+    }
+
+    fn unregister_panel(&self, extension_id: Arc<str>, panel_id: Arc<str>, cx: &mut App) {
+        ExtensionPanelRegistry::global(cx).update(cx, |registry, cx| {
+            if registry.panels.remove(&(extension_id, panel_id)).is_some() {
+                cx.notify();
+            }
+        });
+    }
+}
+
+/// The dock panel that shows the panels provided by extensions, one at a time.
+pub struct ExtensionPanel {
+    registry: Entity<ExtensionPanelRegistry>,
+    views: HashMap<PanelKey, Entity<ExtensionPanelView>>,
+    active_panel: Option<PanelKey>,
+    focus_handle: FocusHandle,
+    position: DockPosition,
+    width: Option<Pixels>,
+    _registry_subscription: Subscription,
+}
+
+impl ExtensionPanel {
+    pub fn new(window: &mut Window, cx: &mut App) -> Entity<Self> {
+        let registry = ExtensionPanelRegistry::global(cx);
+        cx.new(|cx| {
+            let registry_subscription = cx.observe_in(&registry, window, |this, _, window, cx| {
+                this.sync_views(window, cx);
+            });
+            let mut this = Self {
+                registry,
+                views: HashMap::default(),
+                active_panel: None,
+                focus_handle: cx.focus_handle(),
+                position: DockPosition::Right,
+                width: None,
+                _registry_subscription: registry_subscription,
+            };
+            this.sync_views(window, cx);
+            this
+        })
+    }
+
+    /// Drops the views of panels that are no longer registered, or whose extension has been
+    /// reloaded, and ensures that the active panel has a view.
+    fn sync_views(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let registry = self.registry.read(cx);
+        self.views.retain(|key, view| {
+            registry.panels.get(key).is_some_and(|registered| {
+                Arc::ptr_eq(&registered.extension, &view.read(cx).extension)
+            })
+        });
+
+        if !self
+            .active_panel
+            .as_ref()
+            .is_some_and(|key| registry.panels.contains_key(key))
+        {
+            self.active_panel = registry.panels.keys().next().cloned();
+        }
+
+        if let Some(key) = self.active_panel.clone() {
+            self.activate_panel(key, window, cx);
+        }
+        cx.notify();
+    }
+
+    fn activate_panel(&mut self, key: PanelKey, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(registered) = self.registry.read(cx).panels.get(&key) else {
+            return;
+        };
+
+        if !self.views.contains_key(&key) {
+            let extension = registered.extension.clone();
+            let panel_id = key.1.clone();
+            let view = cx.new(|cx| ExtensionPanelView::new(extension, panel_id, window, cx));
+            self.views.insert(key.clone(), view);
+        }
+        self.active_panel = Some(key);
+        cx.notify();
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let registry = self.registry.read(cx);
+        h_flex()
+            .flex_wrap()
+            .gap_1()
+            .px_2()
+            .py_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .children(
+                registry
+                    .panels
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, (key, registered))| {
+                        let is_active = self.active_panel.as_ref() == Some(key);
+                        let key = key.clone();
+                        Button::new(("extension-panel-tab", ix), registered.title.clone())
+                            .label_size(LabelSize::Small)
+                            .toggle_state(is_active)
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.activate_panel(key.clone(), window, cx);
+                            }))
+                    }),
+            )
+    }
+}
+
+impl Focusable for ExtensionPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<DockPanelEvent> for ExtensionPanel {}
+
+impl Render for ExtensionPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let active_view = self
+            .active_panel
+            .as_ref()
+            .and_then(|key| self.views.get(key))
+            .cloned();
+
+        v_flex()
+            .key_context("ExtensionPanel")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().panel_background)
+            .child(self.render_header(cx))
+            .map(|this| match active_view {
+                Some(view) => this.child(view),
+                None => this.child(
+                    div()
+                        .p_2()
+                        .child(Label::new("No extension panels installed.").color(Color::Muted)),
+                ),
+            })
+    }
+}
+
+impl Panel for ExtensionPanel {
+    fn persistent_name() -> &'static str {
+        "ExtensionPanel"
+    }
+
+    fn position(&self, _: &Window, _: &App) -> DockPosition {
+        self.position
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        self.position = position;
+        cx.notify();
+    }
+
+    fn size(&self, _: &Window, _: &App) -> Pixels {
+        self.width.unwrap_or(DEFAULT_WIDTH)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        cx.notify();
+    }
+
+    fn set_active(&mut self, active: bool, window: &mut Window, cx: &mut Context<Self>) {
+        if !active {
+            return;
+        }
+
+        // Show up-to-date contents whenever the panel is opened.
+        if let Some(view) = self
+            .active_panel
+            .as_ref()
+            .and_then(|key| self.views.get(key))
+        {
+            view.update(cx, |view, cx| view.refresh(window, cx));
+        }
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        (!self.registry.read(cx).panels.is_empty()).then_some(IconName::Blocks)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Extension Panels")
+    }
+
+    fn toggle_action(&self) -> Box<dyn gpui::Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        10
+    }
+}
+
+/// The contents of a single panel provided by an extension.
+struct ExtensionPanelView {
+    extension: Arc<dyn Extension>,
+    panel_id: Arc<str>,
+    view: Option<PanelView>,
+    error: Option<SharedString>,
+    text_inputs: HashMap<String, TextInputState>,
+    /// Incremented whenever the view is requested from the extension, so that responses to
+    /// earlier requests can be ignored.
+    view_generation: usize,
+}
+
+struct TextInputState {
+    editor: Entity<Editor>,
+    /// The value of the input in the last view returned by the extension.
+    value: String,
+}
+
+impl ExtensionPanelView {
+    fn new(
+        extension: Arc<dyn Extension>,
+        panel_id: Arc<str>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut this = Self {
+            extension,
+            panel_id,
+            view: None,
+            error: None,
+            text_inputs: HashMap::default(),
+            view_generation: 0,
+        };
+        this.refresh(window, cx);
+        this
+    }
+
+    fn refresh(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let extension = self.extension.clone();
+        let panel_id = self.panel_id.clone();
+        self.request_view(
+            async move { extension.render_panel(panel_id).await },
+            window,
+            cx,
+        );
+    }
+
+    fn send_event(&mut self, event: PanelEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let extension = self.extension.clone();
+        let panel_id = self.panel_id.clone();
+        self.request_view(
+            async move { extension.handle_panel_event(panel_id, event).await },
+            window,
+            cx,
+        );
+    }
+
+    /// Shows the view the extension responds with, unless the view has been requested again
+    /// in the meantime.
+    ///
+    /// Earlier requests still run to completion, so that no events are lost, but their
+    /// responses are dropped so that a slow response can't replace a newer view.
+    fn request_view(
+        &mut self,
+        view: impl Future<Output = Result<PanelView>> + Send + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.view_generation += 1;
+        let generation = self.view_generation;
+        let task = cx.background_spawn(view);
+        cx.spawn_in(window, async move |this, cx| {
+            let view = task.await;
+            this.update_in(cx, |this, window, cx| {
+                if this.view_generation == generation {
+                    this.set_view(view, window, cx);
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn set_view(&mut self, view: Result<PanelView>, window: &mut Window, cx: &mut Context<Self>) {
+        match view {
+            Ok(view) => {
+                self.sync_text_inputs(&view, window, cx);
+                self.view = Some(view);
+                self.error = None;
+            }
+            Err(error) => {
+                log::error!(
+                    "failed to render extension panel {}: {error:#}",
+                    self.panel_id
+                );
+                self.error = Some(format!("{error:#}").into());
+            }
+        }
+        cx.notify();
+    }
+
+    /// Keeps an editor for each text input in the view, so that their contents survive
+    /// updates to the view.
+    fn sync_text_inputs(&mut self, view: &PanelView, window: &mut Window, cx: &mut Context<Self>) {
+        let mut text_inputs = HashMap::default();
+        for element in &view.elements {
+            let PanelElement::TextInput(input) = element else {
+                continue;
+            };
+
+            let state = match self.text_inputs.remove(&input.id) {
+                Some(mut state) => {
+                    state.editor.update(cx, |editor, cx| {
+                        editor.set_placeholder_text(input.placeholder.as_str(), cx);
+                        // Only overwrite what the user has typed when the extension changes the value.
+                        if state.value != input.value {
+                            editor.set_text(input.value.as_str(), window, cx);
+                        }
+                    });
+                    state.value = input.value.clone();
+                    state
+                }
+                None => TextInputState {
+                    editor: cx.new(|cx| {
+                        let mut editor = Editor::single_line(window, cx);
+                        editor.set_placeholder_text(input.placeholder.as_str(), cx);
+                        editor.set_text(input.value.as_str(), window, cx);
+                        editor
+                    }),
+                    value: input.value.clone(),
+                },
+            };
+            text_inputs.insert(input.id.clone(), state);
+        }
+        self.text_inputs = text_inputs;
+    }
+
+    fn submit_text_input(&mut self, id: String, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(state) = self.text_inputs.get(&id) else {
+            return;
+        };
+
+        let value = state.editor.read(cx).text(cx);
+        self.send_event(PanelEvent::TextInputSubmitted { id, value }, window, cx);
+    }
+
+    /// Renders the element at the given index, adding it to `children`.
+    ///
+    /// List items are rendered as siblings of the items nested under them, indented by their depth.
+    fn render_element(
+        &self,
+        view: &PanelView,
+        ix: usize,
+        depth: usize,
+        children: &mut Vec<AnyElement>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(element) = view.elements.get(ix) else {
+            return;
+        };
+
+        match element {
+            PanelElement::Column(child_ixs) => {
+                let column_children = self.render_children(view, child_ixs, depth, cx);
+                children.push(
+                    v_flex()
+                        .gap_1()
+                        .children(column_children)
+                        .into_any_element(),
+                );
+            }
+            PanelElement::Row(child_ixs) => {
+                let row_children = self.render_children(view, child_ixs, depth, cx);
+                children.push(
+                    h_flex()
+                        .flex_wrap()
+                        .gap_1()
+                        .children(row_children)
+                        .into_any_element(),
+                );
+            }
+            PanelElement::Label(label) => {
+                children.push(
+                    Label::new(label.text.clone())
+                        .color(panel_color(label.color))
+                        .into_any_element(),
+                );
+            }
+            PanelElement::Button(button) => {
+                let id = button.id.clone();
+                children.push(
+                    Button::new(("extension-panel-button", ix), button.label.clone())
+                        .disabled(button.disabled)
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.send_event(PanelEvent::ButtonClicked(id.clone()), window, cx);
+                        }))
+                        .into_any_element(),
+                );
+            }
+            PanelElement::TextInput(input) => {
+                let Some(state) = self.text_inputs.get(&input.id) else {
+                    return;
+                };
+
+                let id = input.id.clone();
+                children.push(
+                    div()
+                        .w_full()
+                        .px_2()
+                        .py_1()
+                        .rounded_sm()
+                        .border_1()
+                        .border_color(cx.theme().colors().border)
+                        .bg(cx.theme().colors().editor_background)
+                        .on_action(cx.listener(move |this, _: &menu::Confirm, window, cx| {
+                            this.submit_text_input(id.clone(), window, cx);
+                        }))
+                        .child(state.editor.clone())
+                        .into_any_element(),
+                );
+            }
+            PanelElement::ListItem(item) => {
+                children.push(self.render_list_item(item, ix, depth, cx));
+                if item.expanded {
+                    for &child_ix in &item.children {
+                        self.render_element(view, child_ix as usize, depth + 1, children, cx);
+                    }
+                }
+            }
+            PanelElement::Divider => {
+                children.push(Divider::horizontal().into_any_element());
+            }
+        }
+    }
+
+    fn render_children(
+        &self,
+        view: &PanelView,
+        child_ixs: &[u32],
+        depth: usize,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        let mut children = Vec::with_capacity(child_ixs.len());
+        for &child_ix in child_ixs {
+            self.render_element(view, child_ix as usize, depth, &mut children, cx);
+        }
+        children
+    }
+
+    fn render_list_item(
+        &self,
+        item: &PanelListItem,
+        ix: usize,
+        depth: usize,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let has_children = !item.children.is_empty();
+        let expanded = item.expanded;
+        let id = item.id.clone();
+        ListItem::new(("extension-panel-list-item", ix))
+            .spacing(ListItemSpacing::Sparse)
+            .indent_level(depth)
+            .toggle_state(item.selected)
+            .toggle(has_children.then_some(expanded))
+            .on_toggle(cx.listener({
+                let id = id.clone();
+                move |this, _, window, cx| {
+                    let event = PanelEvent::ListItemToggled {
+                        id: id.clone(),
+                        expanded: !expanded,
+                    };
+                    this.send_event(event, window, cx);
+                }
+            }))
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.send_event(PanelEvent::ListItemClicked(id.clone()), window, cx);
+            }))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Label::new(item.label.clone()).color(panel_color(item.color)))
+                    .children(item.detail.clone().map(|detail| {
+                        Label::new(detail)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                    })),
+            )
+            .into_any_element()
+    }
+}
+
+impl Render for ExtensionPanelView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let mut children = Vec::new();
+        if let Some(view) = self.view.as_ref() {
+            self.render_element(view, 0, 0, &mut children, cx);
+        }
+
+        v_flex()
+            .id("extension-panel-view")
+            .size_full()
+            .p_2()
+            .gap_2()
+            .overflow_y_scroll()
+            .children(
+                self.error
+                    .clone()
+                    .map(|error| Label::new(error).color(Color::Error)),
+            )
+            .when(self.view.is_none() && self.error.is_none(), |this| {
+                this.child(Label::new("Loading…").color(Color::Muted))
+            })
+            .children(children)
+    }
+}
+
+fn panel_color(color: PanelColor) -> Color {
+    match color {
+        PanelColor::Default => Color::Default,
+        PanelColor::Muted => Color::Muted,
+        PanelColor::Accent => Color::Accent,
+        PanelColor::Success => Color::Success,
+        PanelColor::Warning => Color::Warning,
+        PanelColor::Error => Color::Error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use extension::{FakeExtension, PanelLabel, PanelTextInput};
+    use futures::channel::oneshot;
+    use gpui::TestAppContext;
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::tests::init_test;
+
+    fn panel_view(label: &str, query: &str) -> PanelView {
+        PanelView {
+            elements: vec![
+                PanelElement::Column(vec![1, 2]),
+                PanelElement::Label(PanelLabel {
+                    text: label.into(),
+                    color: PanelColor::Default,
+                }),
+                PanelElement::TextInput(PanelTextInput {
+                    id: "query".into(),
+                    placeholder: "Search".into(),
+                    value: query.into(),
+                }),
+            ],
+        }
+    }
+
+    fn label_text(view: &ExtensionPanelView) -> Option<&str> {
+        view.view
+            .as_ref()?
+            .elements
+            .iter()
+            .find_map(|element| match element {
+                PanelElement::Label(label) => Some(label.text.as_str()),
+                _ => None,
+            })
+    }
+
+    #[gpui::test]
+    async fn test_extension_panel_view_updates(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| theme::init(theme::LoadThemes::JustBase, cx));
+
+        let extension = FakeExtension::new("test-extension");
+        extension.on_render_panel(|panel_id| async move {
+            assert_eq!(panel_id.as_ref(), "search");
+            anyhow::Ok(panel_view("Initial", ""))
+        });
+        let received_events = Arc::new(Mutex::new(Vec::new()));
+        let slow_response = Arc::new(Mutex::new(None::<oneshot::Receiver<PanelView>>));
+        extension.on_handle_panel_event({
+            let received_events = received_events.clone();
+            let slow_response = slow_response.clone();
+            move |_, event| {
+                let PanelEvent::ButtonClicked(button_id) = event else {
+                    panic!("only buttons are clicked in this test");
+                };
+                received_events.lock().push(button_id.clone());
+                let slow_response = slow_response.lock().take();
+                async move {
+                    anyhow::Ok(match button_id.as_str() {
+                        "slow" => slow_response.unwrap().await.unwrap(),
+                        "reset" => panel_view("Reset", "default"),
+                        _ => panel_view(&button_id, ""),
+                    })
+                }
+            }
+        });
+
+        let (view, cx) = cx.add_window_view(|window, cx| {
+            ExtensionPanelView::new(extension.clone(), "search".into(), window, cx)
+        });
+        cx.run_until_parked();
+        view.read_with(cx, |view, _| assert_eq!(label_text(view), Some("Initial")));
+
+        let query_editor = view.read_with(cx, |view, _| view.text_inputs["query"].editor.clone());
+        query_editor.update_in(cx, |editor, window, cx| editor.set_text("rust", window, cx));
+
+        // A slow response to an earlier event doesn't replace the view of a later one.
+        let (slow_response_tx, slow_response_rx) = oneshot::channel();
+        *slow_response.lock() = Some(slow_response_rx);
+        view.update_in(cx, |view, window, cx| {
+            view.send_event(PanelEvent::ButtonClicked("slow".into()), window, cx)
+        });
+        cx.run_until_parked();
+        view.update_in(cx, |view, window, cx| {
+            view.send_event(PanelEvent::ButtonClicked("fast".into()), window, cx)
+        });
+        cx.run_until_parked();
+        view.read_with(cx, |view, _| assert_eq!(label_text(view), Some("fast")));
+
+        slow_response_tx.send(panel_view("slow", "")).unwrap();
+        cx.run_until_parked();
+        view.read_with(cx, |view, _| assert_eq!(label_text(view), Some("fast")));
+        assert_eq!(received_events.lock().as_slice(), ["slow", "fast"]);
+
+        // What the user typed survives views that don't change the input's value.
+        view.read_with(cx, |view, cx| {
+            let query_state = &view.text_inputs["query"];
+            assert_eq!(query_state.editor, query_editor);
+            assert_eq!(query_editor.read(cx).text(cx), "rust");
+        });
+
+        // Views that change the input's value replace what the user typed.
+        view.update_in(cx, |view, window, cx| {
+            view.send_event(PanelEvent::ButtonClicked("reset".into()), window, cx)
+        });
+        cx.run_until_parked();
+        view.read_with(cx, |view, cx| {
+            assert_eq!(label_text(view), Some("Reset"));
+            assert_eq!(query_editor.read(cx).text(cx), "default");
+        });
+    }
+}
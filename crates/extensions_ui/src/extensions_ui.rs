@@ -1,6 +1,12 @@
 mod components;
+mod extension_capabilities;
+mod extension_code_actions;
+mod extension_editor_commands;
+mod extension_panels;
 mod extension_suggest;
 mod extension_version_selector;
+#[cfg(test)]
+mod tests;
 
 use std::sync::OnceLock;
 use std::time::Duration;
@@ -38,9 +44,16 @@ use crate::extension_version_selector::{
     ExtensionVersionSelector, ExtensionVersionSelectorDelegate,
 };
 
+pub use crate::extension_panels::ExtensionPanel;
+
 actions!(zed, [InstallDevExtension]);
 
 pub fn init(cx: &mut App) {
+    extension_capabilities::init(cx);
+    extension_code_actions::init(cx);
+    extension_editor_commands::init(cx);
+    extension_panels::init(cx);
+
     cx.observe_new(move |workspace: &mut Workspace, window, cx| {
         let Some(window) = window else {
             return;
@@ -541,6 +554,11 @@ impl ExtensionsPage {
 
         let can_configure = !extension.context_servers.is_empty();
 
+        let build_error = ExtensionStore::global(cx)
+            .read(cx)
+            .dev_extension_build_error(&extension.id)
+            .map(SharedString::new);
+
         ExtensionCard::new()
             .child(
                 h_flex()
@@ -641,12 +659,36 @@ impl ExtensionsPage {
                 h_flex()
                     .gap_2()
                     .justify_between()
-                    .children(extension.description.as_ref().map(|description| {
-                        Label::new(description.clone())
-                            .size(LabelSize::Small)
-                            .color(Color::Default)
-                            .truncate()
-                    }))
+                    .map(|this| match build_error {
+                        // The card has a fixed height, so only the first line of the error is
+                        // shown, with the rest in its tooltip.
+                        Some(build_error) => this.child(
+                            h_flex()
+                                .id(SharedString::from(format!("build-error-{}", extension.id)))
+                                .gap_1()
+                                .overflow_x_hidden()
+                                .child(
+                                    Icon::new(IconName::XCircle)
+                                        .size(IconSize::Small)
+                                        .color(Color::Error),
+                                )
+                                .child(
+                                    Label::new(
+                                        build_error.lines().next().unwrap_or_default().to_string(),
+                                    )
+                                    .size(LabelSize::Small)
+                                    .color(Color::Error)
+                                    .truncate(),
+                                )
+                                .tooltip(Tooltip::text(build_error)),
+                        ),
+                        None => this.children(extension.description.as_ref().map(|description| {
+                            Label::new(description.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Default)
+                                .truncate()
+                        })),
+                    })
                     .children(repository_url.map(|repository_url| {
                         IconButton::new(
                             SharedString::from(format!("repository-{}", extension.id)),
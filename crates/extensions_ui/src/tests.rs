@@ -0,0 +1,15 @@
+use gpui::TestAppContext;
+use project::Project;
+use settings::SettingsStore;
+
+pub(crate) fn init_test(cx: &mut TestAppContext) {
+    cx.update(|cx| {
+        let settings_store = SettingsStore::test(cx);
+        cx.set_global(settings_store);
+        language::init(cx);
+        Project::init_settings(cx);
+        workspace::init_settings(cx);
+        editor::init_settings(cx);
+        theme::ThemeSettings::register(cx)
+    });
+}
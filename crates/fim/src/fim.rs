@@ -0,0 +1,133 @@
+mod fim_completion_provider;
+mod fim_context;
+mod fim_template;
+
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result};
+use http_client::HttpClient;
+use language::language_settings::{FimApi, FimSettings};
+
+pub use fim_completion_provider::*;
+pub use fim_context::*;
+pub use fim_template::*;
+
+/// The default URL of an OpenAI-compatible server, which is where llama.cpp's server
+/// listens by default.
+pub const DEFAULT_OPEN_AI_COMPATIBLE_API_URL: &str = "http://localhost:8080/v1";
+pub const DEFAULT_MAX_TOKENS: u32 = 128;
+const TEMPERATURE: f32 = 0.2;
+
+/// The text that a fill-in-the-middle completion is requested for.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FimPrompt {
+    /// The path of the file being edited, relative to its worktree.
+    pub path: Option<String>,
+    /// The text before the cursor.
+    pub prefix: String,
+    /// The text after the cursor.
+    pub suffix: String,
+    /// Snippets from other files that are relevant to the text being completed.
+    pub snippets: Vec<Snippet>,
+    /// The prefix of a line comment in the language being edited.
+    pub line_comment_prefix: Option<Arc<str>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    pub path: String,
+    pub text: String,
+}
+
+/// Requests a fill-in-the-middle completion for the prompt from the server configured in
+/// the settings.
+pub async fn request_completion(
+    http_client: Arc<dyn HttpClient>,
+    settings: &FimSettings,
+    prompt: &FimPrompt,
+) -> Result<String> {
+    let model = settings
+        .model
+        .clone()
+        .context("no model configured for fill-in-the-middle completions")?;
+    let template = FimTemplate::from_setting(settings.template.as_deref(), &model);
+    let rendered = template.render(prompt);
+    let stop = template.stop_sequences();
+    let max_tokens = settings.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS);
+
+    let completion = match settings.api {
+        FimApi::Ollama => {
+            let api_url = settings
+                .api_url
+                .as_deref()
+                .unwrap_or(ollama::OLLAMA_API_URL);
+            let request = ollama::GenerateRequest {
+                model,
+                prompt: rendered.prompt,
+                raw: rendered.suffix.is_none(),
+                suffix: rendered.suffix,
+                stream: false,
+                keep_alive: Default::default(),
+                options: Some(ollama::ChatOptions {
+                    num_predict: Some(max_tokens as isize),
+                    stop: (!stop.is_empty()).then_some(stop),
+                    temperature: Some(TEMPERATURE),
+                    ..Default::default()
+                }),
+            };
+            ollama::generate(http_client.as_ref(), api_url, request)
+                .await?
+                .response
+        }
+        FimApi::OpenAiCompatible => {
+            let api_url = settings
+                .api_url
+                .as_deref()
+                .unwrap_or(DEFAULT_OPEN_AI_COMPATIBLE_API_URL);
+            let request = open_ai::CompletionRequest {
+                model,
+                prompt: rendered.prompt,
+                suffix: rendered.suffix,
+                max_tokens,
+                temperature: TEMPERATURE,
+                stop,
+                stream: false,
+            };
+            open_ai::complete(http_client.as_ref(), api_url, None, request)
+                .await?
+                .choices
+                .into_iter()
+                .next()
+                .map(|choice| choice.text)
+                .unwrap_or_default()
+        }
+    };
+
+    Ok(clean_completion(&completion, &prompt.suffix))
+}
+
+/// Removes the text at the end of the completion that the model repeated from the
+/// start of the suffix, such as a closing bracket.
+fn clean_completion(completion: &str, suffix: &str) -> String {
+    let completion = completion.trim_end();
+    let suffix_line = suffix.lines().next().unwrap_or_default().trim();
+    if !suffix_line.is_empty() {
+        if let Some(completion) = completion.strip_suffix(suffix_line) {
+            return completion.to_string();
+        }
+    }
+    completion.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_completion() {
+        assert_eq!(clean_completion("a + b\n\n", "\n}\n"), "a + b");
+        assert_eq!(clean_completion("x, y)", ");\n"), "x, y)");
+        assert_eq!(clean_completion("x, y);", ");\n"), "x, y");
+        assert_eq!(clean_completion("   ", ""), "");
+    }
+}
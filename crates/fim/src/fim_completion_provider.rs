@@ -0,0 +1,411 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use gpui::{App, AppContext as _, Context, Entity, EntityId, Task};
+use http_client::HttpClient;
+use inline_completion::{Direction, EditPredictionProvider, InlineCompletion};
+use language::{Anchor, Buffer, language_settings::all_language_settings};
+use project::Project;
+use text::ToOffset;
+
+use crate::{ContextBuffer, build_prompt, request_completion};
+
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// An edit prediction provider that requests fill-in-the-middle completions from a
+/// model served locally by Ollama or an OpenAI-compatible server.
+pub struct FimCompletionProvider {
+    http_client: Arc<dyn HttpClient>,
+    buffer_id: Option<EntityId>,
+    completion: Option<CurrentCompletion>,
+    pending_refresh: Option<Task<Result<()>>>,
+}
+
+struct CurrentCompletion {
+    /// The position of the cursor when the completion was requested.
+    position: Anchor,
+    text: String,
+}
+
+impl FimCompletionProvider {
+    pub fn new(http_client: Arc<dyn HttpClient>) -> Self {
+        Self {
+            http_client,
+            buffer_id: None,
+            completion: None,
+            pending_refresh: None,
+        }
+    }
+}
+
+impl EditPredictionProvider for FimCompletionProvider {
+    fn name() -> &'static str {
+        "fim"
+    }
+
+    fn display_name() -> &'static str {
+        "Fill-in-the-Middle"
+    }
+
+    fn show_completions_in_menu() -> bool {
+        false
+    }
+
+    fn is_enabled(&self, _buffer: &Entity<Buffer>, _cursor_position: Anchor, cx: &App) -> bool {
+        all_language_settings(None, cx)
+            .edit_predictions
+            .fim
+            .model
+            .is_some()
+    }
+
+    fn is_refreshing(&self) -> bool {
+        self.pending_refresh.is_some()
+    }
+
+    fn refresh(
+        &mut self,
+        project: Option<Entity<Project>>,
+        buffer: Entity<Buffer>,
+        cursor_position: Anchor,
+        debounce: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let settings = &all_language_settings(None, cx).edit_predictions;
+        let fim_settings = settings.fim.clone();
+        let debounce_timeout = fim_settings
+            .debounce_ms
+            .map_or(DEFAULT_DEBOUNCE, Duration::from_millis);
+
+        let snapshot = buffer.read(cx).snapshot();
+        let path = buffer
+            .read(cx)
+            .file()
+            .map(|file| file.path().to_string_lossy().into_owned());
+        let context_buffers = project
+            .map(|project| {
+                project
+                    .read(cx)
+                    .opened_buffers(cx)
+                    .into_iter()
+                    .filter(|other| other.entity_id() != buffer.entity_id())
+                    .filter_map(|other| {
+                        let other = other.read(cx);
+                        let file = other.file()?;
+                        if !settings.enabled_for_file(file, cx) {
+                            return None;
+                        }
+                        Some(ContextBuffer {
+                            path: file.path().to_string_lossy().into_owned(),
+                            snapshot: other.snapshot(),
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let http_client = self.http_client.clone();
+        // Replacing the pending refresh drops its task, which cancels the request that
+        // was in flight for an earlier cursor position.
+        self.pending_refresh = Some(cx.spawn(async move |this, cx| {
+            if debounce {
+                cx.background_executor().timer(debounce_timeout).await;
+            }
+
+            let cursor_offset = cursor_position.to_offset(&snapshot);
+            let prompt = cx
+                .background_spawn(async move {
+                    build_prompt(&snapshot, path, cursor_offset, &context_buffers)
+                })
+                .await;
+
+            let completion = request_completion(http_client, &fim_settings, &prompt).await;
+
+            this.update(cx, |this, cx| {
+                this.pending_refresh = None;
+                match completion {
+                    Ok(text) if !text.trim().is_empty() => {
+                        this.buffer_id = Some(buffer.entity_id());
+                        this.completion = Some(CurrentCompletion {
+                            position: cursor_position,
+                            text,
+                        });
+                    }
+                    Ok(_) => this.completion = None,
+                    Err(error) => {
+                        log::error!("failed to request fill-in-the-middle completion: {error:?}");
+                        this.completion = None;
+                    }
+                }
+                cx.notify();
+            })
+        }));
+    }
+
+    fn cycle(
+        &mut self,
+        _buffer: Entity<Buffer>,
+        _cursor_position: Anchor,
+        _direction: Direction,
+        _cx: &mut Context<Self>,
+    ) {
+    }
+
+    fn accept(&mut self, _cx: &mut Context<Self>) {
+        self.pending_refresh = None;
+        self.completion = None;
+    }
+
+    fn discard(&mut self, _cx: &mut Context<Self>) {
+        self.pending_refresh = None;
+        self.completion = None;
+    }
+
+    fn suggest(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cursor_position: Anchor,
+        cx: &mut Context<Self>,
+    ) -> Option<InlineCompletion> {
+        if self.buffer_id != Some(buffer.entity_id()) {
+            return None;
+        }
+        let completion = self.completion.as_ref()?;
+
+        // Keep suggesting the rest of the completion while the user types it.
+        let snapshot = buffer.read(cx).snapshot();
+        let start = completion.position.to_offset(&snapshot);
+        let end = cursor_position.to_offset(&snapshot);
+        if end < start {
+            return None;
+        }
+        let typed = snapshot.text_for_range(start..end).collect::<String>();
+        let remaining = completion.text.strip_prefix(&typed)?;
+        if remaining.trim().is_empty() {
+            return None;
+        }
+
+        let position = snapshot.anchor_after(end);
+        Some(InlineCompletion {
+            id: None,
+            edits: vec![(position..position, remaining.to_string())],
+            edit_preview: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::AsyncReadExt as _;
+    use gpui::{AppContext as _, TestAppContext};
+    use http_client::{FakeHttpClient, Response};
+    use language::{
+        Point,
+        language_settings::{
+            AllLanguageSettings, EditPredictionSettingsContent, FimApi, FimSettingsContent,
+        },
+    };
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    #[gpui::test]
+    async fn test_ollama_completion(cx: &mut TestAppContext) {
+        init_test(
+            FimSettingsContent {
+                model: Some("qwen2.5-coder:1.5b".into()),
+                ..Default::default()
+            },
+            cx,
+        );
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let http_client = FakeHttpClient::create({
+            let requests = requests.clone();
+            move |request| {
+                let requests = requests.clone();
+                async move {
+                    let uri = request.uri().to_string();
+                    let mut body = String::new();
+                    request.into_body().read_to_string(&mut body).await?;
+                    requests.lock().unwrap().push((uri, body));
+                    Ok(Response::builder()
+                        .status(200)
+                        .body(
+                            json!({ "response": "a + b\n", "done": true })
+                                .to_string()
+                                .into(),
+                        )
+                        .unwrap())
+                }
+            }
+        });
+        let provider = cx.new(|_| FimCompletionProvider::new(http_client));
+        let buffer = cx.new(|cx| Buffer::local("fn add(a: i32, b: i32) -> i32 {\n    \n}\n", cx));
+        let cursor = buffer.read_with(cx, |buffer, _| buffer.anchor_before(Point::new(1, 4)));
+
+        provider.update(cx, |provider, cx| {
+            provider.refresh(None, buffer.clone(), cursor, false, cx)
+        });
+        cx.run_until_parked();
+
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 1);
+        let (uri, body) = &requests[0];
+        assert_eq!(uri, "http://localhost:11434/api/generate");
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(body["model"], "qwen2.5-coder:1.5b");
+        assert_eq!(body["raw"], true);
+        assert_eq!(
+            body["prompt"],
+            "<|fim_prefix|>fn add(a: i32, b: i32) -> i32 {\n    <|fim_suffix|>\n}\n<|fim_middle|>"
+        );
+
+        let completion = provider
+            .update(cx, |provider, cx| provider.suggest(&buffer, cursor, cx))
+            .unwrap();
+        assert_eq!(completion.edits[0].1, "a + b");
+
+        // Typing the start of the completion keeps suggesting the rest of it.
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit([(Point::new(1, 4)..Point::new(1, 4), "a ")], None, cx)
+        });
+        let cursor = buffer.read_with(cx, |buffer, _| buffer.anchor_before(Point::new(1, 6)));
+        let completion = provider
+            .update(cx, |provider, cx| provider.suggest(&buffer, cursor, cx))
+            .unwrap();
+        assert_eq!(completion.edits[0].1, "+ b");
+
+        // Typing something else discards it.
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit([(Point::new(1, 6)..Point::new(1, 6), "-")], None, cx)
+        });
+        let cursor = buffer.read_with(cx, |buffer, _| buffer.anchor_before(Point::new(1, 7)));
+        assert!(
+            provider
+                .update(cx, |provider, cx| provider.suggest(&buffer, cursor, cx))
+                .is_none()
+        );
+    }
+
+    #[gpui::test]
+    async fn test_open_ai_compatible_completion(cx: &mut TestAppContext) {
+        init_test(
+            FimSettingsContent {
+                api: Some(FimApi::OpenAiCompatible),
+                model: Some("my-model".into()),
+                ..Default::default()
+            },
+            cx,
+        );
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let http_client = FakeHttpClient::create({
+            let requests = requests.clone();
+            move |request| {
+                let requests = requests.clone();
+                async move {
+                    let uri = request.uri().to_string();
+                    let mut body = String::new();
+                    request.into_body().read_to_string(&mut body).await?;
+                    requests.lock().unwrap().push((uri, body));
+                    Ok(Response::builder()
+                        .status(200)
+                        .body(
+                            json!({ "choices": [{ "text": "world\");", "finish_reason": "stop" }] })
+                                .to_string()
+                                .into(),
+                        )
+                        .unwrap())
+                }
+            }
+        });
+        let provider = cx.new(|_| FimCompletionProvider::new(http_client));
+        let buffer = cx.new(|cx| Buffer::local("print(\"hello \");\n", cx));
+        let cursor = buffer.read_with(cx, |buffer, _| buffer.anchor_before(13));
+
+        provider.update(cx, |provider, cx| {
+            provider.refresh(None, buffer.clone(), cursor, false, cx)
+        });
+        cx.run_until_parked();
+
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 1);
+        let (uri, body) = &requests[0];
+        assert_eq!(uri, "http://localhost:8080/v1/completions");
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(body["prompt"], "print(\"hello ");
+        assert_eq!(body["suffix"], "\");\n");
+
+        // The closing quote and parenthesis that the model repeated from the suffix are
+        // removed.
+        let completion = provider
+            .update(cx, |provider, cx| provider.suggest(&buffer, cursor, cx))
+            .unwrap();
+        assert_eq!(completion.edits[0].1, "world");
+    }
+
+    #[gpui::test]
+    async fn test_refresh_is_debounced(cx: &mut TestAppContext) {
+        init_test(
+            FimSettingsContent {
+                model: Some("codellama:7b-code".into()),
+                debounce_ms: Some(100),
+                ..Default::default()
+            },
+            cx,
+        );
+
+        let request_count = Arc::new(AtomicUsize::new(0));
+        let http_client = FakeHttpClient::create({
+            let request_count = request_count.clone();
+            move |_| {
+                request_count.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    Ok(Response::builder()
+                        .status(200)
+                        .body(json!({ "response": "x", "done": true }).to_string().into())
+                        .unwrap())
+                }
+            }
+        });
+        let provider = cx.new(|_| FimCompletionProvider::new(http_client));
+        let buffer = cx.new(|cx| Buffer::local("let x = \n", cx));
+
+        for offset in [6, 7, 8] {
+            let cursor = buffer.read_with(cx, |buffer, _| buffer.anchor_before(offset));
+            provider.update(cx, |provider, cx| {
+                provider.refresh(None, buffer.clone(), cursor, true, cx)
+            });
+            cx.executor().advance_clock(Duration::from_millis(50));
+            cx.run_until_parked();
+        }
+        assert_eq!(request_count.load(Ordering::SeqCst), 0);
+        assert!(provider.read_with(cx, |provider, _| provider.is_refreshing()));
+
+        cx.executor().advance_clock(Duration::from_millis(100));
+        cx.run_until_parked();
+        assert_eq!(request_count.load(Ordering::SeqCst), 1);
+        assert!(!provider.read_with(cx, |provider, _| provider.is_refreshing()));
+    }
+
+    fn init_test(fim_settings: FimSettingsContent, cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings::<AllLanguageSettings>(cx, |settings| {
+                    settings.edit_predictions = Some(EditPredictionSettingsContent {
+                        fim: fim_settings,
+                        ..Default::default()
+                    });
+                });
+            });
+        });
+    }
+}
@@ -0,0 +1,236 @@
+use std::ops::Range;
+
+use collections::HashSet;
+use language::{BufferSnapshot, Point};
+use text::Bias;
+
+use crate::{FimPrompt, Snippet};
+
+/// The maximum length of the text before the cursor to include in the prompt.
+const MAX_PREFIX_LEN: usize = 6000;
+/// The maximum length of the text after the cursor to include in the prompt.
+const MAX_SUFFIX_LEN: usize = 2000;
+/// The maximum combined length of the snippets from other buffers.
+const MAX_SNIPPETS_LEN: usize = 2000;
+const MAX_SNIPPETS: usize = 3;
+/// The number of lines in each snippet taken from another buffer.
+const SNIPPET_LINES: u32 = 20;
+/// The number of lines before the cursor that snippets are compared against.
+const QUERY_LINES: u32 = 20;
+/// Buffers longer than this aren't searched for snippets.
+const MAX_CONTEXT_BUFFER_LEN: usize = 512 * 1024;
+
+/// Another open buffer that snippets may be taken from.
+pub struct ContextBuffer {
+    pub path: String,
+    pub snapshot: BufferSnapshot,
+}
+
+/// Builds the prompt for a completion at the cursor, from the text surrounding the cursor
+/// and the snippets of other buffers that are most similar to the code being written.
+pub fn build_prompt(
+    snapshot: &BufferSnapshot,
+    path: Option<String>,
+    cursor_offset: usize,
+    context_buffers: &[ContextBuffer],
+) -> FimPrompt {
+    let cursor_point = snapshot.offset_to_point(cursor_offset);
+    let query_start =
+        snapshot.point_to_offset(Point::new(cursor_point.row.saturating_sub(QUERY_LINES), 0));
+    let query = snapshot
+        .text_for_range(query_start..cursor_offset)
+        .collect::<String>();
+
+    FimPrompt {
+        path,
+        prefix: snapshot
+            .text_for_range(prefix_range(snapshot, cursor_offset))
+            .collect(),
+        suffix: snapshot
+            .text_for_range(suffix_range(snapshot, cursor_offset))
+            .collect(),
+        snippets: similar_snippets(&query, context_buffers),
+        line_comment_prefix: snapshot
+            .language_scope_at(cursor_offset)
+            .and_then(|scope| scope.line_comment_prefixes().first().cloned()),
+    }
+}
+
+/// Returns the range of whole lines before the cursor that fit in the prefix.
+fn prefix_range(snapshot: &BufferSnapshot, cursor_offset: usize) -> Range<usize> {
+    let mut start = snapshot.clip_offset(cursor_offset.saturating_sub(MAX_PREFIX_LEN), Bias::Right);
+    if start > 0 {
+        let point = snapshot.offset_to_point(start);
+        if point.column > 0 {
+            start = snapshot
+                .point_to_offset(Point::new(point.row + 1, 0))
+                .min(cursor_offset);
+        }
+    }
+    start..cursor_offset
+}
+
+/// Returns the range of whole lines after the cursor that fit in the suffix.
+fn suffix_range(snapshot: &BufferSnapshot, cursor_offset: usize) -> Range<usize> {
+    let mut end = snapshot.clip_offset(
+        (cursor_offset + MAX_SUFFIX_LEN).min(snapshot.len()),
+        Bias::Left,
+    );
+    if end < snapshot.len() {
+        let point = snapshot.offset_to_point(end);
+        end = snapshot
+            .point_to_offset(Point::new(point.row, 0))
+            .max(cursor_offset);
+    }
+    cursor_offset..end
+}
+
+/// Returns the snippets of the context buffers that share the most identifiers with the
+/// query, taking at most one snippet from each buffer.
+fn similar_snippets(query: &str, context_buffers: &[ContextBuffer]) -> Vec<Snippet> {
+    let query_words = words(query);
+    if query_words.is_empty() {
+        return Vec::new();
+    }
+
+    let mut candidates = Vec::new();
+    for buffer in context_buffers {
+        if buffer.snapshot.len() > MAX_CONTEXT_BUFFER_LEN {
+            continue;
+        }
+
+        let snapshot = &buffer.snapshot;
+        let max_point = snapshot.max_point();
+        let mut best_window: Option<(f32, String)> = None;
+        let mut row = 0;
+        loop {
+            let end = if row + SNIPPET_LINES > max_point.row {
+                max_point
+            } else {
+                Point::new(row + SNIPPET_LINES, 0)
+            };
+            let text = snapshot
+                .text_for_range(Point::new(row, 0)..end)
+                .collect::<String>();
+            let score = similarity(&query_words, &words(&text));
+            if best_window
+                .as_ref()
+                .map_or(true, |(best_score, _)| score > *best_score)
+            {
+                best_window = Some((score, text));
+            }
+
+            if end == max_point {
+                break;
+            }
+            row += SNIPPET_LINES / 2;
+        }
+
+        if let Some((score, text)) = best_window {
+            if score > 0. && !text.trim().is_empty() {
+                candidates.push((score, buffer.path.clone(), text));
+            }
+        }
+    }
+
+    candidates.sort_by(|(a, _, _), (b, _, _)| b.total_cmp(a));
+
+    let mut snippets = Vec::new();
+    let mut len = 0;
+    for (_, path, text) in candidates {
+        if snippets.len() == MAX_SNIPPETS {
+            break;
+        }
+        if len + text.len() > MAX_SNIPPETS_LEN {
+            continue;
+        }
+        len += text.len();
+        snippets.push(Snippet {
+            path,
+            text: text.trim_end().to_string(),
+        });
+    }
+    snippets
+}
+
+fn words(text: &str) -> HashSet<&str> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// The Jaccard similarity of two sets of words.
+fn similarity(a: &HashSet<&str>, b: &HashSet<&str>) -> f32 {
+    let intersection = a.intersection(b).count();
+    let union = a.len() + b.len() - intersection;
+    if union == 0 {
+        0.
+    } else {
+        intersection as f32 / union as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{AppContext as _, TestAppContext};
+    use indoc::indoc;
+    use language::Buffer;
+
+    #[gpui::test]
+    fn test_build_prompt(cx: &mut TestAppContext) {
+        let snapshot = |text: &str, cx: &mut TestAppContext| {
+            cx.new(|cx| Buffer::local(text, cx))
+                .read_with(cx, |buffer, _| buffer.snapshot())
+        };
+
+        let text = indoc! {"
+            fn main() {
+                let total = add(1, 2);
+            }
+        "};
+        let cursor_offset = text.find("add").unwrap();
+        let current = snapshot(text, cx);
+        let context_buffers = vec![
+            ContextBuffer {
+                path: "src/math.rs".into(),
+                snapshot: snapshot("pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n", cx),
+            },
+            ContextBuffer {
+                path: "README.md".into(),
+                snapshot: snapshot("# Unrelated\n", cx),
+            },
+        ];
+
+        let prompt = build_prompt(
+            &current,
+            Some("src/main.rs".into()),
+            cursor_offset,
+            &context_buffers,
+        );
+        assert_eq!(prompt.prefix, "fn main() {\n    let total = ");
+        assert_eq!(prompt.suffix, "add(1, 2);\n}\n");
+        assert_eq!(
+            prompt.snippets,
+            vec![Snippet {
+                path: "src/math.rs".into(),
+                text: "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}".into(),
+            }]
+        );
+    }
+
+    #[gpui::test]
+    fn test_prefix_and_suffix_are_whole_lines(cx: &mut TestAppContext) {
+        let line = "x".repeat(99);
+        let text = format!("{line}\n").repeat(200);
+        let snapshot = cx
+            .new(|cx| Buffer::local(text.clone(), cx))
+            .read_with(cx, |buffer, _| buffer.snapshot());
+
+        let cursor_offset = text.len() / 2 + 50;
+        let prefix = prefix_range(&snapshot, cursor_offset);
+        let suffix = suffix_range(&snapshot, cursor_offset);
+        assert_eq!(prefix, 4100..cursor_offset);
+        assert_eq!(suffix, cursor_offset..12000);
+    }
+}
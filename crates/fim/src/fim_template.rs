@@ -0,0 +1,272 @@
+use crate::FimPrompt;
+
+/// The format of the prompt for a fill-in-the-middle model.
+///
+/// Models are trained with their own special tokens for marking the prefix, suffix, and
+/// middle of a completion, so the prompt has to match the model it's sent to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FimTemplate {
+    CodeLlama,
+    CodeGemma,
+    Codestral,
+    DeepSeekCoder,
+    QwenCoder,
+    StarCoder,
+    /// Sends the prefix and suffix separately, and lets the server apply the model's
+    /// own template.
+    Native,
+    /// A template with `{prefix}` and `{suffix}` placeholders.
+    Custom(String),
+}
+
+/// A prompt rendered with a [`FimTemplate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedPrompt {
+    pub prompt: String,
+    /// The suffix, if the server is responsible for applying the template.
+    pub suffix: Option<String>,
+}
+
+impl FimTemplate {
+    /// Returns the template with the given name, or a custom template if the setting
+    /// contains placeholders. Without a setting, the template is inferred from the model.
+    pub fn from_setting(template: Option<&str>, model: &str) -> Self {
+        match template {
+            Some(template) if template.contains("{prefix}") => Self::Custom(template.to_string()),
+            Some(name) => Self::from_name(name).unwrap_or_else(|| {
+                log::warn!("unknown FIM template {name:?}, inferring it from the model instead");
+                Self::infer(model)
+            }),
+            None => Self::infer(model),
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "code_llama" => Self::CodeLlama,
+            "codegemma" => Self::CodeGemma,
+            "codestral" => Self::Codestral,
+            "deepseek_coder" => Self::DeepSeekCoder,
+            "qwen_coder" => Self::QwenCoder,
+            "starcoder" => Self::StarCoder,
+            "native" => Self::Native,
+            _ => return None,
+        })
+    }
+
+    /// Infers the template from the name of the model, falling back to letting the
+    /// server apply the model's template.
+    pub fn infer(model: &str) -> Self {
+        let model = model.to_lowercase();
+        if model.contains("codellama") || model.contains("code-llama") {
+            Self::CodeLlama
+        } else if model.contains("codegemma") {
+            Self::CodeGemma
+        } else if model.contains("codestral") {
+            Self::Codestral
+        } else if model.contains("deepseek") {
+            Self::DeepSeekCoder
+        } else if model.contains("qwen") {
+            Self::QwenCoder
+        } else if model.contains("starcoder") {
+            Self::StarCoder
+        } else {
+            Self::Native
+        }
+    }
+
+    pub fn stop_sequences(&self) -> Vec<String> {
+        let stop: &[&str] = match self {
+            Self::CodeLlama => &["<EOT>", "<PRE>", "<SUF>", "<MID>"],
+            Self::CodeGemma => &[
+                "<|file_separator|>",
+                "<|fim_prefix|>",
+                "<|fim_suffix|>",
+                "<|fim_middle|>",
+            ],
+            Self::Codestral => &["[PREFIX]", "[SUFFIX]"],
+            Self::DeepSeekCoder => &[
+                "<｜end▁of▁sentence｜>",
+                "<｜fim▁begin｜>",
+                "<｜fim▁hole｜>",
+                "<｜fim▁end｜>",
+            ],
+            Self::QwenCoder => &[
+                "<|endoftext|>",
+                "<|file_sep|>",
+                "<|fim_prefix|>",
+                "<|fim_suffix|>",
+                "<|fim_middle|>",
+                "<|fim_pad|>",
+            ],
+            Self::StarCoder => &[
+                "<|endoftext|>",
+                "<file_sep>",
+                "<fim_prefix>",
+                "<fim_suffix>",
+                "<fim_middle>",
+            ],
+            Self::Native | Self::Custom(_) => &[],
+        };
+        stop.iter().map(|stop| stop.to_string()).collect()
+    }
+
+    /// The token that models trained on whole repositories use to separate files.
+    fn file_separator(&self) -> Option<&'static str> {
+        match self {
+            Self::QwenCoder => Some("<|file_sep|>"),
+            Self::StarCoder => Some("<file_sep>"),
+            _ => None,
+        }
+    }
+
+    pub fn render(&self, prompt: &FimPrompt) -> RenderedPrompt {
+        let context = self.render_context(prompt);
+        let prefix = &prompt.prefix;
+        let suffix = &prompt.suffix;
+
+        let prompt = match self {
+            Self::CodeLlama => format!("{context}<PRE> {prefix} <SUF>{suffix} <MID>"),
+            Self::CodeGemma => {
+                format!("{context}<|fim_prefix|>{prefix}<|fim_suffix|>{suffix}<|fim_middle|>")
+            }
+            Self::Codestral => format!("[SUFFIX]{suffix}[PREFIX]{context}{prefix}"),
+            Self::DeepSeekCoder => {
+                format!("{context}<｜fim▁begin｜>{prefix}<｜fim▁hole｜>{suffix}<｜fim▁end｜>")
+            }
+            Self::QwenCoder => {
+                format!("{context}<|fim_prefix|>{prefix}<|fim_suffix|>{suffix}<|fim_middle|>")
+            }
+            Self::StarCoder => {
+                format!("{context}<fim_prefix>{prefix}<fim_suffix>{suffix}<fim_middle>")
+            }
+            Self::Native => {
+                return RenderedPrompt {
+                    prompt: format!("{context}{prefix}"),
+                    suffix: Some(suffix.clone()),
+                };
+            }
+            Self::Custom(template) => template
+                .replace("{prefix}", &format!("{context}{prefix}"))
+                .replace("{suffix}", suffix),
+        };
+
+        RenderedPrompt {
+            prompt,
+            suffix: None,
+        }
+    }
+
+    /// Renders the snippets from other files that precede the prefix.
+    ///
+    /// Models trained on whole repositories get each snippet as a separate file. Other
+    /// models get them as comments, which is only possible when the language has line
+    /// comments.
+    fn render_context(&self, prompt: &FimPrompt) -> String {
+        let mut context = String::new();
+        if let Some(file_separator) = self.file_separator() {
+            for snippet in &prompt.snippets {
+                context.push_str(file_separator);
+                context.push_str(&snippet.path);
+                context.push('\n');
+                context.push_str(&snippet.text);
+                context.push('\n');
+            }
+            if !context.is_empty() {
+                if let Some(path) = &prompt.path {
+                    context.push_str(file_separator);
+                    context.push_str(path);
+                    context.push('\n');
+                }
+            }
+        } else if let Some(comment_prefix) = &prompt.line_comment_prefix {
+            let comment_prefix = comment_prefix.trim_end();
+            for snippet in &prompt.snippets {
+                context.push_str(&format!("{comment_prefix} Path: {}\n", snippet.path));
+                for line in snippet.text.lines() {
+                    context.push_str(comment_prefix);
+                    if !line.is_empty() {
+                        context.push(' ');
+                        context.push_str(line);
+                    }
+                    context.push('\n');
+                }
+                context.push('\n');
+            }
+        }
+        context
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Snippet;
+
+    #[test]
+    fn test_infer_template() {
+        assert_eq!(
+            FimTemplate::infer("qwen2.5-coder:1.5b"),
+            FimTemplate::QwenCoder
+        );
+        assert_eq!(
+            FimTemplate::infer("deepseek-coder-v2:lite"),
+            FimTemplate::DeepSeekCoder
+        );
+        assert_eq!(
+            FimTemplate::infer("codellama:7b-code"),
+            FimTemplate::CodeLlama
+        );
+        assert_eq!(FimTemplate::infer("starcoder2:3b"), FimTemplate::StarCoder);
+        assert_eq!(FimTemplate::infer("my-model"), FimTemplate::Native);
+
+        assert_eq!(
+            FimTemplate::from_setting(Some("codestral"), "qwen2.5-coder"),
+            FimTemplate::Codestral
+        );
+        assert_eq!(
+            FimTemplate::from_setting(Some("<a>{prefix}<b>{suffix}<c>"), "qwen2.5-coder"),
+            FimTemplate::Custom("<a>{prefix}<b>{suffix}<c>".into())
+        );
+    }
+
+    #[test]
+    fn test_render_template() {
+        let prompt = FimPrompt {
+            path: Some("src/main.rs".into()),
+            prefix: "fn main() {\n    ".into(),
+            suffix: "\n}\n".into(),
+            snippets: vec![Snippet {
+                path: "src/lib.rs".into(),
+                text: "pub fn greet() {}".into(),
+            }],
+            line_comment_prefix: Some("// ".into()),
+        };
+
+        assert_eq!(
+            FimTemplate::QwenCoder.render(&prompt).prompt,
+            "<|file_sep|>src/lib.rs\npub fn greet() {}\n<|file_sep|>src/main.rs\n\
+             <|fim_prefix|>fn main() {\n    <|fim_suffix|>\n}\n<|fim_middle|>"
+        );
+        assert_eq!(
+            FimTemplate::CodeLlama.render(&prompt).prompt,
+            "// Path: src/lib.rs\n// pub fn greet() {}\n\n<PRE> fn main() {\n     <SUF>\n}\n <MID>"
+        );
+        assert_eq!(
+            FimTemplate::Native.render(&prompt),
+            RenderedPrompt {
+                prompt: "// Path: src/lib.rs\n// pub fn greet() {}\n\nfn main() {\n    ".into(),
+                suffix: Some("\n}\n".into()),
+            }
+        );
+        assert_eq!(
+            FimTemplate::Custom("<a>{prefix}<b>{suffix}<c>".into())
+                .render(&FimPrompt {
+                    line_comment_prefix: None,
+                    ..prompt
+                })
+                .prompt,
+            "<a>fn main() {\n    <b>\n}\n<c>"
+        );
+    }
+}
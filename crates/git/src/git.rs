@@ -86,6 +86,14 @@ impl Oid {
         self.0.as_bytes()
     }
 
+    /// Returns the [`Oid`] that git assigns to a blob with the given contents,
+    /// without writing it to any repository.
+    pub fn for_blob(contents: &[u8]) -> Result<Self> {
+        let oid = libgit::Oid::hash_object(libgit::ObjectType::Blob, contents)
+            .context("failed to hash blob")?;
+        Ok(Self(oid))
+    }
+
     pub(crate) fn is_zero(&self) -> bool {
         self.0.is_zero()
     }
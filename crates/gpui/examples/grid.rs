@@ -0,0 +1,75 @@
+use gpui::{
+    App, Application, Bounds, Context, Window, WindowBounds, WindowOptions, div, fr, minmax,
+    prelude::*, px, rgb, size,
+};
+
+struct KeybindingTable {
+    rows: Vec<(&'static str, &'static str, &'static str)>,
+}
+
+impl Render for KeybindingTable {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        let cell = |text: &'static str| div().px_2().py_1().bg(rgb(0x303030)).child(text);
+
+        div()
+            .size_full()
+            .p_4()
+            .bg(rgb(0x202020))
+            .text_color(rgb(0xffffff))
+            .child(
+                div()
+                    .grid()
+                    .grid_template_columns([minmax(px(120.), fr(1.)), fr(2.), px(140.).into()])
+                    .gap_1()
+                    .child(
+                        div()
+                            .col_span_full()
+                            .px_2()
+                            .py_1()
+                            .bg(rgb(0x0050a0))
+                            .child("Keybindings"),
+                    )
+                    .children(self.rows.iter().flat_map(|&(action, keystroke, context)| {
+                        [cell(action), cell(keystroke), cell(context)]
+                    })),
+            )
+            .child(
+                div()
+                    .mt_4()
+                    .grid()
+                    .grid_cols(4)
+                    .gap_2()
+                    .children((0..6).map(|ix| {
+                        div()
+                            .h_12()
+                            .bg(rgb(0x505050))
+                            .when(ix == 0, |this| this.col_span(2).row_span(2))
+                            .child(format!("Item {ix}"))
+                    })),
+            )
+    }
+}
+
+fn main() {
+    Application::new().run(|cx: &mut App| {
+        let bounds = Bounds::centered(None, size(px(600.), px(400.0)), cx);
+        cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                ..Default::default()
+            },
+            |_, cx| {
+                cx.new(|_| KeybindingTable {
+                    rows: vec![
+                        ("editor::Copy", "ctrl-c", "Editor"),
+                        ("editor::Paste", "ctrl-v", "Editor"),
+                        ("workspace::Save", "ctrl-s", "Workspace"),
+                        ("pane::CloseActiveItem", "ctrl-w", "Pane"),
+                    ],
+                })
+            },
+        )
+        .unwrap();
+        cx.activate(true);
+    });
+}
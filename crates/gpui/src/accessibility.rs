@@ -0,0 +1,684 @@
+//! Accessibility support for GPUI.
+//!
+//! Elements describe themselves to assistive technologies by recording
+//! [`AccessibilityNode`]s while they paint, see [`Window::with_accessibility_node`].
+//! After each frame, the recorded nodes are assembled into an [`AccessibilityTree`],
+//! which platforms bridge to their native accessibility APIs (AT-SPI on Linux) and
+//! which tests can print with [`TestAppContext::dump_accessibility_tree`].
+//!
+//! [`Window::with_accessibility_node`]: crate::Window::with_accessibility_node
+//! [`TestAppContext::dump_accessibility_tree`]: crate::TestAppContext::dump_accessibility_tree
+
+use crate::{Bounds, FocusHandle, FocusId, Pixels, SharedString};
+use collections::{FxHashMap, FxHashSet};
+use smallvec::SmallVec;
+use std::{
+    fmt,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+/// The kind of user interface object an accessibility node represents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AccessibilityRole {
+    /// The root of a window's accessibility tree.
+    Window,
+    /// A container grouping related elements.
+    Group,
+    /// A clickable element that performs an action.
+    Button,
+    /// An element that can be checked and unchecked.
+    Checkbox,
+    /// An element that navigates somewhere when activated.
+    Link,
+    /// A container of [`AccessibilityRole::ListItem`]s.
+    List,
+    /// An item in a [`AccessibilityRole::List`].
+    ListItem,
+    /// A container of [`AccessibilityRole::MenuItem`]s.
+    Menu,
+    /// An item in a [`AccessibilityRole::Menu`].
+    MenuItem,
+    /// A container of [`AccessibilityRole::Tab`]s.
+    TabList,
+    /// A tab in a [`AccessibilityRole::TabList`].
+    Tab,
+    /// A window-like element that interrupts the user, such as a modal.
+    Dialog,
+    /// A popup describing another element.
+    Tooltip,
+    /// A heading for a section of content.
+    Heading,
+    /// An image or icon.
+    Image,
+    /// Non-editable text.
+    StaticText,
+    /// A single-line editable text field.
+    TextInput,
+    /// A multi-line editable text area, such as a code editor.
+    MultilineTextInput,
+}
+
+impl AccessibilityRole {
+    /// The name used for this role when printing an [`AccessibilityTree`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccessibilityRole::Window => "window",
+            AccessibilityRole::Group => "group",
+            AccessibilityRole::Button => "button",
+            AccessibilityRole::Checkbox => "checkbox",
+            AccessibilityRole::Link => "link",
+            AccessibilityRole::List => "list",
+            AccessibilityRole::ListItem => "list_item",
+            AccessibilityRole::Menu => "menu",
+            AccessibilityRole::MenuItem => "menu_item",
+            AccessibilityRole::TabList => "tab_list",
+            AccessibilityRole::Tab => "tab",
+            AccessibilityRole::Dialog => "dialog",
+            AccessibilityRole::Tooltip => "tooltip",
+            AccessibilityRole::Heading => "heading",
+            AccessibilityRole::Image => "image",
+            AccessibilityRole::StaticText => "static_text",
+            AccessibilityRole::TextInput => "text_input",
+            AccessibilityRole::MultilineTextInput => "multiline_text_input",
+        }
+    }
+
+    /// Whether nodes with this role are named after the text they contain
+    /// when they weren't given an explicit name.
+    fn is_named_from_contents(&self) -> bool {
+        matches!(
+            self,
+            AccessibilityRole::Button
+                | AccessibilityRole::Checkbox
+                | AccessibilityRole::Link
+                | AccessibilityRole::ListItem
+                | AccessibilityRole::MenuItem
+                | AccessibilityRole::Tab
+                | AccessibilityRole::Heading
+                | AccessibilityRole::Tooltip
+        )
+    }
+}
+
+/// An action assistive technologies can perform on an accessibility node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AccessibilityAction {
+    /// Activate the node, as if it was clicked with the mouse.
+    Click,
+    /// Move keyboard focus to the node.
+    Focus,
+}
+
+impl AccessibilityAction {
+    /// The name used for this action when printing an [`AccessibilityTree`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccessibilityAction::Click => "click",
+            AccessibilityAction::Focus => "focus",
+        }
+    }
+}
+
+/// A request from an assistive technology to perform an action on a node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccessibilityActionRequest {
+    /// The node to perform the action on.
+    pub node: AccessibilityNodeId,
+    /// The action to perform.
+    pub action: AccessibilityAction,
+}
+
+/// The accessibility properties assigned to an interactive element, see
+/// [`InteractiveElement::role`](crate::InteractiveElement::role).
+#[derive(Clone, Debug, Default)]
+pub struct AccessibilityProperties {
+    /// The role of the element. Defaults to [`AccessibilityRole::Button`] for
+    /// clickable elements and [`AccessibilityRole::Group`] otherwise.
+    pub role: Option<AccessibilityRole>,
+    /// The name announced for the element.
+    pub label: Option<SharedString>,
+    /// The current value of the element.
+    pub value: Option<SharedString>,
+    /// Whether the element is checked, for elements that can be toggled.
+    pub checked: Option<bool>,
+}
+
+/// A node recorded into the accessibility tree while painting an element.
+#[derive(Clone, Debug)]
+pub struct AccessibilityNode {
+    pub(crate) role: AccessibilityRole,
+    pub(crate) name: Option<SharedString>,
+    pub(crate) value: Option<SharedString>,
+    pub(crate) checked: Option<bool>,
+    pub(crate) bounds: Option<Bounds<Pixels>>,
+    pub(crate) focus_id: Option<FocusId>,
+    pub(crate) actions: SmallVec<[AccessibilityAction; 2]>,
+    pub(crate) stable_id: Option<u64>,
+}
+
+impl AccessibilityNode {
+    /// Creates a node with the given role.
+    pub fn new(role: AccessibilityRole) -> Self {
+        Self {
+            role,
+            name: None,
+            value: None,
+            checked: None,
+            bounds: None,
+            focus_id: None,
+            actions: SmallVec::new(),
+            stable_id: None,
+        }
+    }
+
+    /// Creates a node from the properties assigned to an element.
+    pub fn from_properties(properties: &AccessibilityProperties) -> Self {
+        let mut node = Self::new(properties.role.unwrap_or(AccessibilityRole::Group));
+        node.name = properties.label.clone();
+        node.value = properties.value.clone();
+        node.checked = properties.checked;
+        node
+    }
+
+    /// Sets the name announced for this node. When no name is set, buttons,
+    /// list items and similar nodes are named after the text they contain.
+    pub fn name(mut self, name: impl Into<SharedString>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the current value of this node, such as the text of an input.
+    pub fn value(mut self, value: impl Into<SharedString>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Sets whether this node is checked.
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+
+    /// Sets the window-relative bounds of this node. Nodes without bounds
+    /// cover the union of their children.
+    pub fn bounds(mut self, bounds: Bounds<Pixels>) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Marks this node as focusable through the given handle.
+    pub fn focus_handle(mut self, focus_handle: &FocusHandle) -> Self {
+        self.focus_id = Some(focus_handle.id);
+        self.action(AccessibilityAction::Focus)
+    }
+
+    /// Adds an action that can be performed on this node.
+    pub fn action(mut self, action: AccessibilityAction) -> Self {
+        if !self.actions.contains(&action) {
+            self.actions.push(action);
+        }
+        self
+    }
+
+    /// Derives this node's [`AccessibilityNodeId`] from the given value, such as a
+    /// [`GlobalElementId`](crate::GlobalElementId), so it stays the same across frames even if the
+    /// surrounding tree changes.
+    pub fn id(mut self, id: impl Hash) -> Self {
+        let mut hasher = DefaultHasher::new();
+        id.hash(&mut hasher);
+        self.stable_id = Some(hasher.finish());
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum AccessibilityOperation {
+    Push(AccessibilityNode),
+    Pop,
+}
+
+/// Identifies a node in an [`AccessibilityTree`] across frames.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AccessibilityNodeId(pub u64);
+
+impl AccessibilityNodeId {
+    /// The id of the root node of every tree.
+    pub const ROOT: AccessibilityNodeId = AccessibilityNodeId(0);
+}
+
+/// A node in an [`AccessibilityTree`].
+#[derive(Clone, Debug)]
+pub struct AccessibilityTreeNode {
+    /// The id of this node, stable across frames.
+    pub id: AccessibilityNodeId,
+    /// The role of this node.
+    pub role: AccessibilityRole,
+    /// The name announced for this node.
+    pub name: Option<SharedString>,
+    /// The current value of this node.
+    pub value: Option<SharedString>,
+    /// Whether this node is checked, for nodes that can be toggled.
+    pub checked: Option<bool>,
+    /// The window-relative bounds of this node.
+    pub bounds: Bounds<Pixels>,
+    /// Whether this node currently has keyboard focus.
+    pub focused: bool,
+    /// The actions that can be performed on this node.
+    pub actions: SmallVec<[AccessibilityAction; 2]>,
+    /// The index of this node's parent in [`AccessibilityTree::nodes`].
+    pub parent: Option<usize>,
+    /// The indices of this node's children in [`AccessibilityTree::nodes`].
+    pub children: Vec<usize>,
+    pub(crate) focus_id: Option<FocusId>,
+}
+
+impl AccessibilityTreeNode {
+    /// Whether keyboard focus can be moved to this node.
+    pub fn focusable(&self) -> bool {
+        self.focus_id.is_some()
+    }
+}
+
+/// The accessibility tree of a window, built from the nodes recorded while painting a frame.
+#[derive(Clone, Debug)]
+pub struct AccessibilityTree {
+    nodes: Vec<AccessibilityTreeNode>,
+    indices_by_id: FxHashMap<AccessibilityNodeId, usize>,
+    focused: Option<usize>,
+}
+
+impl AccessibilityTree {
+    pub(crate) fn build(
+        operations: &[AccessibilityOperation],
+        title: Option<SharedString>,
+        viewport: Bounds<Pixels>,
+        focus: Option<FocusId>,
+    ) -> Self {
+        let root = AccessibilityTreeNode {
+            id: AccessibilityNodeId::ROOT,
+            role: AccessibilityRole::Window,
+            name: title,
+            value: None,
+            checked: None,
+            bounds: viewport,
+            focused: false,
+            actions: SmallVec::new(),
+            parent: None,
+            children: Vec::new(),
+            focus_id: None,
+        };
+        let mut nodes = vec![root];
+        let mut has_bounds = vec![true];
+        let mut used_ids = FxHashSet::default();
+        used_ids.insert(AccessibilityNodeId::ROOT);
+        let mut stack = vec![0];
+
+        for operation in operations {
+            match operation {
+                AccessibilityOperation::Push(node) => {
+                    let parent = *stack.last().unwrap();
+                    let id = node
+                        .stable_id
+                        .map(AccessibilityNodeId)
+                        .filter(|id| used_ids.insert(*id))
+                        .unwrap_or_else(|| {
+                            // Nodes without an element id, or whose element was painted more than
+                            // once, are identified by their position among their siblings instead.
+                            let mut hasher = DefaultHasher::new();
+                            nodes[parent].id.hash(&mut hasher);
+                            nodes[parent].children.len().hash(&mut hasher);
+                            node.role.hash(&mut hasher);
+                            loop {
+                                let id = AccessibilityNodeId(hasher.finish());
+                                if used_ids.insert(id) {
+                                    break id;
+                                }
+                                id.hash(&mut hasher);
+                            }
+                        });
+
+                    let ix = nodes.len();
+                    nodes[parent].children.push(ix);
+                    nodes.push(AccessibilityTreeNode {
+                        id,
+                        role: node.role,
+                        name: node.name.clone(),
+                        value: node.value.clone(),
+                        checked: node.checked,
+                        bounds: node.bounds.unwrap_or_default(),
+                        focused: false,
+                        actions: node.actions.clone(),
+                        parent: Some(parent),
+                        children: Vec::new(),
+                        focus_id: node.focus_id,
+                    });
+                    has_bounds.push(node.bounds.is_some());
+                    stack.push(ix);
+                }
+                AccessibilityOperation::Pop => {
+                    if stack.len() > 1 {
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        // Children always come after their parents, so walking backwards sizes
+        // every node before its parent needs it.
+        for ix in (1..nodes.len()).rev() {
+            if !has_bounds[ix] {
+                let mut children = nodes[ix].children.iter();
+                if let Some(first) = children.next() {
+                    nodes[ix].bounds = children.fold(nodes[*first].bounds, |bounds, child| {
+                        bounds.union(&nodes[*child].bounds)
+                    });
+                }
+            }
+        }
+
+        let mut tree = Self {
+            indices_by_id: nodes
+                .iter()
+                .enumerate()
+                .map(|(ix, node)| (node.id, ix))
+                .collect(),
+            nodes,
+            focused: None,
+        };
+
+        for ix in 0..tree.nodes.len() {
+            if tree.nodes[ix].name.is_none() && tree.nodes[ix].role.is_named_from_contents() {
+                let contents = tree.text_contents(ix);
+                if !contents.is_empty() {
+                    tree.nodes[ix].name = Some(contents.into());
+                }
+            }
+        }
+
+        if let Some(focus) = focus {
+            tree.focused = tree
+                .nodes
+                .iter()
+                .rposition(|node| node.focus_id == Some(focus));
+            if let Some(focused) = tree.focused {
+                tree.nodes[focused].focused = true;
+            }
+        }
+
+        tree
+    }
+
+    /// The root node, which represents the window itself.
+    pub fn root(&self) -> &AccessibilityTreeNode {
+        &self.nodes[0]
+    }
+
+    /// All nodes in the tree, in paint order. Parents always precede their children.
+    pub fn nodes(&self) -> &[AccessibilityTreeNode] {
+        &self.nodes
+    }
+
+    /// Returns the node with the given id.
+    pub fn node(&self, id: AccessibilityNodeId) -> Option<&AccessibilityTreeNode> {
+        self.indices_by_id.get(&id).map(|ix| &self.nodes[*ix])
+    }
+
+    /// Returns the children of the given node.
+    pub fn children<'a>(
+        &'a self,
+        node: &'a AccessibilityTreeNode,
+    ) -> impl Iterator<Item = &'a AccessibilityTreeNode> + 'a {
+        node.children.iter().map(|ix| &self.nodes[*ix])
+    }
+
+    /// Returns the parent of the given node.
+    pub fn parent(&self, node: &AccessibilityTreeNode) -> Option<&AccessibilityTreeNode> {
+        node.parent.map(|ix| &self.nodes[ix])
+    }
+
+    /// Returns the node that currently has keyboard focus, if any.
+    pub fn focused(&self) -> Option<&AccessibilityTreeNode> {
+        self.focused.map(|ix| &self.nodes[ix])
+    }
+
+    /// Returns the first node, in paint order, with the given role and name.
+    pub fn find(&self, role: AccessibilityRole, name: &str) -> Option<&AccessibilityTreeNode> {
+        self.nodes
+            .iter()
+            .find(|node| node.role == role && node.name.as_deref() == Some(name))
+    }
+
+    fn text_contents(&self, ix: usize) -> String {
+        let mut contents = String::new();
+        let mut stack = vec![ix];
+        while let Some(ix) = stack.pop() {
+            let node = &self.nodes[ix];
+            if node.role == AccessibilityRole::StaticText {
+                if let Some(name) = node.name.as_ref() {
+                    if !contents.is_empty() {
+                        contents.push(' ');
+                    }
+                    contents.push_str(name.trim());
+                }
+            }
+            stack.extend(node.children.iter().rev());
+        }
+        contents
+    }
+
+    fn fmt_node(&self, ix: usize, depth: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let node = &self.nodes[ix];
+        write!(
+            f,
+            "{:indent$}{}",
+            "",
+            node.role.as_str(),
+            indent = depth * 2
+        )?;
+        if let Some(name) = node.name.as_ref() {
+            write!(f, " {:?}", name.as_ref())?;
+        }
+        if let Some(value) = node.value.as_ref() {
+            write!(f, " value={:?}", value.as_ref())?;
+        }
+        match node.checked {
+            Some(true) => write!(f, " checked")?,
+            Some(false) => write!(f, " unchecked")?,
+            None => {}
+        }
+        if node.focused {
+            write!(f, " focused")?;
+        } else if node.focusable() {
+            write!(f, " focusable")?;
+        }
+        if !node.actions.is_empty() {
+            let actions = node.actions.iter().map(|action| action.as_str());
+            write!(f, " actions={}", actions.collect::<Vec<_>>().join(","))?;
+        }
+        writeln!(f)?;
+
+        for child in &node.children {
+            self.fmt_node(*child, depth + 1, f)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AccessibilityTree {
+    /// Prints the tree as indented text, one node per line, for use in test assertions.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_node(0, 0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        self as gpui, Context, FocusHandle, InteractiveElement, IntoElement, ParentElement, Render,
+        ScrollStrategy, StatefulInteractiveElement, Styled, TestAppContext,
+        UniformListScrollHandle, Window, div, px, uniform_list,
+    };
+
+    struct Form {
+        saves: usize,
+        name_input: FocusHandle,
+    }
+
+    impl Render for Form {
+        fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+            div()
+                .size_full()
+                .child(
+                    div()
+                        .id("save")
+                        .on_click(cx.listener(|this, _, _, _| this.saves += 1))
+                        .child("Save"),
+                )
+                .child(
+                    div()
+                        .id("name")
+                        .track_focus(&self.name_input)
+                        .role(AccessibilityRole::TextInput)
+                        .aria_label("Name")
+                        .aria_value("Ada")
+                        .h(px(20.)),
+                )
+                .child(
+                    div()
+                        .role(AccessibilityRole::Checkbox)
+                        .aria_label("Wrap lines")
+                        .aria_checked(true)
+                        .h(px(20.)),
+                )
+                .child(
+                    uniform_list("items", 2, |range, _, _| {
+                        range.map(|ix| div().child(format!("Item {ix}"))).collect()
+                    })
+                    .h(px(100.)),
+                )
+        }
+    }
+
+    #[gpui::test]
+    fn test_accessibility_tree_dump(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_, cx| Form {
+            saves: 0,
+            name_input: cx.focus_handle(),
+        });
+
+        assert_eq!(
+            cx.dump_accessibility_tree(),
+            concat!(
+                "window\n",
+                "  button \"Save\" actions=click\n",
+                "    static_text \"Save\"\n",
+                "  text_input \"Name\" value=\"Ada\" focusable actions=focus\n",
+                "  checkbox \"Wrap lines\" checked\n",
+                "  list\n",
+                "    list_item \"Item 0\"\n",
+                "      static_text \"Item 0\"\n",
+                "    list_item \"Item 1\"\n",
+                "      static_text \"Item 1\"\n",
+            )
+        );
+    }
+
+    #[gpui::test]
+    fn test_accessibility_actions(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_, cx| Form {
+            saves: 0,
+            name_input: cx.focus_handle(),
+        });
+
+        let tree = cx.accessibility_tree();
+        let save = tree.find(AccessibilityRole::Button, "Save").unwrap().id;
+        let name = tree.find(AccessibilityRole::TextInput, "Name").unwrap().id;
+        assert!(tree.focused().is_none());
+
+        cx.perform_accessibility_action(save, AccessibilityAction::Click);
+        assert_eq!(view.read_with(cx, |view, _| view.saves), 1);
+
+        cx.perform_accessibility_action(name, AccessibilityAction::Focus);
+        let tree = cx.accessibility_tree();
+        assert_eq!(tree.focused().map(|node| node.id), Some(name));
+        assert_eq!(
+            tree.find(AccessibilityRole::Button, "Save").unwrap().id,
+            save,
+            "node ids should be stable across frames"
+        );
+    }
+
+    struct ItemList {
+        scroll_handle: UniformListScrollHandle,
+    }
+
+    impl Render for ItemList {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            uniform_list("items", 100, |range, _, _| {
+                range
+                    .map(|ix| div().h(px(20.)).child(format!("Item {ix}")))
+                    .collect()
+            })
+            .track_scroll(self.scroll_handle.clone())
+            .h(px(100.))
+        }
+    }
+
+    #[gpui::test]
+    fn test_uniform_list_accessibility_nodes(cx: &mut TestAppContext) {
+        let scroll_handle = UniformListScrollHandle::new();
+        let (_, cx) = cx.add_window_view(|_, _| ItemList {
+            scroll_handle: scroll_handle.clone(),
+        });
+
+        let tree = cx.update(|window, cx| {
+            window.draw(cx).clear();
+            window.accessibility_tree()
+        });
+        assert_eq!(
+            tree.nodes().len(),
+            1,
+            "no nodes should be recorded until accessibility is enabled"
+        );
+
+        let list_items = |tree: &AccessibilityTree| {
+            tree.nodes()
+                .iter()
+                .filter(|node| node.role == AccessibilityRole::ListItem)
+                .map(|node| (node.id, node.name.clone().unwrap_or_default()))
+                .collect::<Vec<_>>()
+        };
+
+        let top_items = list_items(&cx.accessibility_tree());
+        assert_eq!(
+            top_items
+                .iter()
+                .map(|(_, name)| name.as_ref())
+                .collect::<Vec<_>>(),
+            ["Item 0", "Item 1", "Item 2", "Item 3", "Item 4"],
+            "only the visible items should be in the tree"
+        );
+
+        scroll_handle.scroll_to_item(50, ScrollStrategy::Top);
+        cx.update(|window, _| window.refresh());
+        let scrolled_items = list_items(&cx.accessibility_tree());
+        assert_eq!(
+            scrolled_items
+                .iter()
+                .map(|(_, name)| name.as_ref())
+                .collect::<Vec<_>>(),
+            ["Item 50", "Item 51", "Item 52", "Item 53", "Item 54"]
+        );
+        assert!(
+            scrolled_items
+                .iter()
+                .all(|(id, _)| top_items.iter().all(|(top_id, _)| top_id != id)),
+            "list items should be identified by their index, not their position"
+        );
+
+        scroll_handle.scroll_to_item(0, ScrollStrategy::Top);
+        cx.update(|window, _| window.refresh());
+        assert_eq!(list_items(&cx.accessibility_tree()), top_items);
+    }
+}
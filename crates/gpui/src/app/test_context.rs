@@ -1,15 +1,19 @@
 use crate::{
-    Action, AnyView, AnyWindowHandle, App, AppCell, AppContext, AsyncApp, AvailableSpace,
-    BackgroundExecutor, BorrowAppContext, Bounds, Capslock, ClipboardItem, DrawPhase, Drawable,
-    Element, Empty, EventEmitter, ForegroundExecutor, Global, InputEvent, Keystroke, Modifiers,
-    ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels,
-    Platform, Point, Render, Result, Size, Task, TestDispatcher, TestPlatform,
-    TestScreenCaptureSource, TestWindow, TextSystem, VisualContext, Window, WindowBounds,
-    WindowHandle, WindowOptions,
+    AccessibilityAction, AccessibilityNodeId, AccessibilityTree, Action, AnyView, AnyWindowHandle,
+    App, AppCell, AppContext, AsyncApp, AvailableSpace, BackgroundExecutor, BorrowAppContext,
+    Bounds, Capslock, ClipboardItem, DrawPhase, Drawable, Element, Empty, EventEmitter,
+    ForegroundExecutor, Global, InputEvent, Keystroke, Modifiers, ModifiersChangedEvent,
+    MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Platform, Point, Render,
+    Result, ScreenshotTolerance, Size, Task, TestDispatcher, TestPlatform, TestScreenCaptureSource,
+    TestWindow, TextSystem, VisualContext, Window, WindowBounds, WindowHandle, WindowOptions,
+    assert_screenshot_matches,
 };
 use anyhow::{anyhow, bail};
 use futures::{Stream, StreamExt, channel::oneshot};
-use std::{cell::RefCell, future::Future, ops::Deref, rc::Rc, sync::Arc, time::Duration};
+use image::RgbaImage;
+use std::{
+    cell::RefCell, future::Future, mem, ops::Deref, path::Path, rc::Rc, sync::Arc, time::Duration,
+};
 
 /// A TestAppContext is provided to tests created with `#[gpui::test]`, it provides
 /// an implementation of `Context` with additional methods that are useful in tests.
@@ -448,6 +452,42 @@ impl TestAppContext {
             .clone()
     }
 
+    /// Renders the given window's current frame on the CPU, drawing it first if it's stale.
+    ///
+    /// The image is sized in device pixels, so it's scaled by the test window's scale factor.
+    pub fn capture_window(&mut self, window: AnyWindowHandle) -> RgbaImage {
+        let test_window = self.test_window(window);
+        self.update_window(window, |_, window, cx| {
+            if window.invalidator.is_dirty() {
+                window.draw(cx).clear();
+            }
+            test_window.rasterize(&window.rendered_frame.scene)
+        })
+        .unwrap()
+    }
+
+    /// Returns the accessibility tree of the given window's current frame, drawing it first if it's stale.
+    ///
+    /// The first call enables accessibility for the window, as a screen reader connecting would.
+    pub fn accessibility_tree(&mut self, window: AnyWindowHandle) -> AccessibilityTree {
+        let test_window = self.test_window(window);
+        self.update_window(window, |_, window, cx| {
+            if !mem::replace(&mut test_window.0.lock().accessibility_enabled, true) {
+                window.refresh();
+            }
+            if window.invalidator.is_dirty() {
+                window.draw(cx).clear();
+            }
+            window.accessibility_tree()
+        })
+        .unwrap()
+    }
+
+    /// Prints the accessibility tree of the given window as indented text, for use in assertions.
+    pub fn dump_accessibility_tree(&mut self, window: AnyWindowHandle) -> String {
+        self.accessibility_tree(window).to_string()
+    }
+
     /// Returns a stream of notifications whenever the Entity is updated.
     pub fn notifications<T: 'static>(
         &mut self,
@@ -695,6 +735,47 @@ impl VisualTestContext {
         self.cx.test_window(self.window).0.lock().title.clone()
     }
 
+    /// Renders the window's current frame on the CPU. See [`TestAppContext::capture_window`].
+    pub fn capture_screenshot(&mut self) -> RgbaImage {
+        self.run_until_parked();
+        self.cx.capture_window(self.window)
+    }
+
+    /// Captures the window and compares it against the PNG at `golden_path`.
+    /// See [`assert_screenshot_matches`](crate::assert_screenshot_matches).
+    #[track_caller]
+    pub fn assert_screenshot(
+        &mut self,
+        golden_path: impl AsRef<Path>,
+        tolerance: ScreenshotTolerance,
+    ) {
+        let screenshot = self.capture_screenshot();
+        assert_screenshot_matches(&screenshot, golden_path, tolerance);
+    }
+
+    /// Returns the window's accessibility tree. See [`TestAppContext::accessibility_tree`].
+    pub fn accessibility_tree(&mut self) -> AccessibilityTree {
+        self.run_until_parked();
+        self.cx.accessibility_tree(self.window)
+    }
+
+    /// Prints the window's accessibility tree. See [`TestAppContext::dump_accessibility_tree`].
+    pub fn dump_accessibility_tree(&mut self) -> String {
+        self.run_until_parked();
+        self.cx.dump_accessibility_tree(self.window)
+    }
+
+    /// Performs an action on a node of the window's accessibility tree, as a screen reader would.
+    /// Automatically runs until parked.
+    pub fn perform_accessibility_action(
+        &mut self,
+        node: AccessibilityNodeId,
+        action: AccessibilityAction,
+    ) {
+        self.update(|window, cx| window.perform_accessibility_action(node, action, cx));
+        self.run_until_parked();
+    }
+
     /// Simulate a sequence of keystrokes `cx.simulate_keystrokes("cmd-p escape")`
     /// Automatically runs until parked.
     pub fn simulate_keystrokes(&mut self, keystrokes: &str) {
@@ -16,11 +16,12 @@
 //! constructed by combining these two systems into an all-in-one element.
 
 use crate::{
-    Action, AnyDrag, AnyElement, AnyTooltip, AnyView, App, Bounds, ClickEvent, DispatchPhase,
-    Element, ElementId, Entity, FocusHandle, Global, GlobalElementId, Hitbox, HitboxBehavior,
-    HitboxId, InspectorElementId, IntoElement, IsZero, KeyContext, KeyDownEvent, KeyUpEvent,
-    LayoutId, ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
-    Overflow, ParentElement, Pixels, Point, Render, ScrollWheelEvent, SharedString, Size, Style,
+    AccessibilityAction, AccessibilityNode, AccessibilityProperties, AccessibilityRole, Action,
+    AnyDrag, AnyElement, AnyTooltip, AnyView, App, Bounds, ClickEvent, DispatchPhase, Element,
+    ElementId, Entity, FocusHandle, Global, GlobalElementId, Hitbox, HitboxBehavior, HitboxId,
+    InspectorElementId, IntoElement, IsZero, KeyContext, KeyDownEvent, KeyUpEvent, LayoutId,
+    ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Overflow,
+    ParentElement, Pixels, Point, Render, ScrollWheelEvent, SharedString, Size, Style,
     StyleRefinement, Styled, Task, TooltipId, Visibility, Window, WindowControlArea, point, px,
     size,
 };
@@ -619,6 +620,33 @@ pub trait InteractiveElement: Sized {
         self
     }
 
+    /// Set the role this element is exposed with in the accessibility tree. Elements
+    /// with click listeners are exposed as buttons by default.
+    fn role(mut self, role: AccessibilityRole) -> Self {
+        self.interactivity().accessibility_properties().role = Some(role);
+        self
+    }
+
+    /// Set the name assistive technologies announce for this element, instead of
+    /// one computed from the text it contains.
+    fn aria_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.interactivity().accessibility_properties().label = Some(label.into());
+        self
+    }
+
+    /// Set the current value assistive technologies report for this element,
+    /// such as the contents of an input or the position of a slider.
+    fn aria_value(mut self, value: impl Into<SharedString>) -> Self {
+        self.interactivity().accessibility_properties().value = Some(value.into());
+        self
+    }
+
+    /// Set whether assistive technologies report this element as checked.
+    fn aria_checked(mut self, checked: bool) -> Self {
+        self.interactivity().accessibility_properties().checked = Some(checked);
+        self
+    }
+
     /// Set the keymap context for this element. This will be used to determine
     /// which action to dispatch from the keymap.
     fn key_context<C, E>(mut self, key_context: C) -> Self
@@ -1462,6 +1490,7 @@ pub struct Interactivity {
     pub(crate) tooltip_builder: Option<TooltipBuilder>,
     pub(crate) window_control: Option<WindowControlArea>,
     pub(crate) hitbox_behavior: HitboxBehavior,
+    pub(crate) accessibility: Option<Box<AccessibilityProperties>>,
 
     #[cfg(any(feature = "inspector", debug_assertions))]
     pub(crate) source_location: Option<&'static core::panic::Location<'static>>,
@@ -1729,63 +1758,71 @@ impl Interactivity {
                     return ((), element_state);
                 }
 
-                window.with_element_opacity(style.opacity, |window| {
-                    style.paint(bounds, window, cx, |window: &mut Window, cx: &mut App| {
-                        window.with_text_style(style.text_style().cloned(), |window| {
-                            window.with_content_mask(
-                                style.overflow_mask(bounds, window.rem_size()),
-                                |window| {
-                                    if let Some(hitbox) = hitbox {
-                                        #[cfg(debug_assertions)]
-                                        self.paint_debug_info(
-                                            global_id, hitbox, &style, window, cx,
-                                        );
-
-                                        if let Some(drag) = cx.active_drag.as_ref() {
-                                            if let Some(mouse_cursor) = drag.cursor_style {
-                                                window.set_window_cursor_style(mouse_cursor);
+                let accessibility_node = window
+                    .accessibility_enabled()
+                    .then(|| self.accessibility_node(global_id, bounds))
+                    .flatten();
+                window.with_accessibility_node(accessibility_node, |window| {
+                    window.with_element_opacity(style.opacity, |window| {
+                        style.paint(bounds, window, cx, |window: &mut Window, cx: &mut App| {
+                            window.with_text_style(style.text_style().cloned(), |window| {
+                                window.with_content_mask(
+                                    style.overflow_mask(bounds, window.rem_size()),
+                                    |window| {
+                                        if let Some(hitbox) = hitbox {
+                                            #[cfg(debug_assertions)]
+                                            self.paint_debug_info(
+                                                global_id, hitbox, &style, window, cx,
+                                            );
+
+                                            if let Some(drag) = cx.active_drag.as_ref() {
+                                                if let Some(mouse_cursor) = drag.cursor_style {
+                                                    window.set_window_cursor_style(mouse_cursor);
+                                                }
+                                            } else {
+                                                if let Some(mouse_cursor) = style.mouse_cursor {
+                                                    window.set_cursor_style(mouse_cursor, hitbox);
+                                                }
                                             }
-                                        } else {
-                                            if let Some(mouse_cursor) = style.mouse_cursor {
-                                                window.set_cursor_style(mouse_cursor, hitbox);
+
+                                            if let Some(group) = self.group.clone() {
+                                                GroupHitboxes::push(group, hitbox.id, cx);
                                             }
-                                        }
 
-                                        if let Some(group) = self.group.clone() {
-                                            GroupHitboxes::push(group, hitbox.id, cx);
-                                        }
+                                            if let Some(area) = self.window_control {
+                                                window.insert_window_control_hitbox(
+                                                    area,
+                                                    hitbox.clone(),
+                                                );
+                                            }
 
-                                        if let Some(area) = self.window_control {
-                                            window
-                                                .insert_window_control_hitbox(area, hitbox.clone());
+                                            self.paint_mouse_listeners(
+                                                hitbox,
+                                                element_state.as_mut(),
+                                                window,
+                                                cx,
+                                            );
+                                            self.paint_scroll_listener(hitbox, &style, window, cx);
                                         }
 
-                                        self.paint_mouse_listeners(
-                                            hitbox,
-                                            element_state.as_mut(),
-                                            window,
-                                            cx,
-                                        );
-                                        self.paint_scroll_listener(hitbox, &style, window, cx);
-                                    }
-
-                                    self.paint_keyboard_listeners(window, cx);
-                                    f(&style, window, cx);
+                                        self.paint_keyboard_listeners(window, cx);
+                                        f(&style, window, cx);
 
-                                    if let Some(_hitbox) = hitbox {
-                                        #[cfg(any(feature = "inspector", debug_assertions))]
-                                        window.insert_inspector_hitbox(
-                                            _hitbox.id,
-                                            _inspector_id,
-                                            cx,
-                                        );
+                                        if let Some(_hitbox) = hitbox {
+                                            #[cfg(any(feature = "inspector", debug_assertions))]
+                                            window.insert_inspector_hitbox(
+                                                _hitbox.id,
+                                                _inspector_id,
+                                                cx,
+                                            );
 
-                                        if let Some(group) = self.group.as_ref() {
-                                            GroupHitboxes::pop(group, cx);
+                                            if let Some(group) = self.group.as_ref() {
+                                                GroupHitboxes::pop(group, cx);
+                                            }
                                         }
-                                    }
-                                },
-                            );
+                                    },
+                                );
+                            });
                         });
                     });
                 });
@@ -1795,6 +1832,43 @@ impl Interactivity {
         );
     }
 
+    pub(crate) fn accessibility_properties(&mut self) -> &mut AccessibilityProperties {
+        self.accessibility.get_or_insert_default()
+    }
+
+    /// Elements are only added to the accessibility tree when they were given accessibility
+    /// properties or can be clicked, so that layout-only divs don't clutter it.
+    fn accessibility_node(
+        &self,
+        global_id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+    ) -> Option<AccessibilityNode> {
+        let clickable = !self.click_listeners.is_empty();
+        let mut node = match self.accessibility.as_deref() {
+            Some(properties) => {
+                let mut node = AccessibilityNode::from_properties(properties);
+                if properties.role.is_none() && clickable {
+                    node.role = AccessibilityRole::Button;
+                }
+                node
+            }
+            None if clickable => AccessibilityNode::new(AccessibilityRole::Button),
+            None => return None,
+        };
+
+        node = node.bounds(bounds);
+        if let Some(global_id) = global_id {
+            node = node.id(global_id);
+        }
+        if let Some(focus_handle) = self.tracked_focus_handle.as_ref() {
+            node = node.focus_handle(focus_handle);
+        }
+        if clickable {
+            node = node.action(AccessibilityAction::Click);
+        }
+        Some(node)
+    }
+
     #[cfg(debug_assertions)]
     fn paint_debug_info(
         &self,
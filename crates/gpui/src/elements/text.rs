@@ -1,9 +1,9 @@
 use crate::{
-    ActiveTooltip, AnyView, App, Bounds, DispatchPhase, Element, ElementId, GlobalElementId,
-    HighlightStyle, Hitbox, HitboxBehavior, InspectorElementId, IntoElement, LayoutId,
-    MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Point, SharedString, Size, TextOverflow,
-    TextRun, TextStyle, TooltipId, WhiteSpace, Window, WrappedLine, WrappedLineLayout,
-    register_tooltip_mouse_handlers, set_tooltip_on_window,
+    AccessibilityNode, AccessibilityRole, ActiveTooltip, AnyView, App, Bounds, DispatchPhase,
+    Element, ElementId, GlobalElementId, HighlightStyle, Hitbox, HitboxBehavior,
+    InspectorElementId, IntoElement, LayoutId, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
+    Pixels, Point, SharedString, Size, TextOverflow, TextRun, TextStyle, TooltipId, WhiteSpace,
+    Window, WrappedLine, WrappedLineLayout, register_tooltip_mouse_handlers, set_tooltip_on_window,
 };
 use anyhow::Context as _;
 use smallvec::SmallVec;
@@ -62,7 +62,7 @@ impl Element for &'static str {
         window: &mut Window,
         cx: &mut App,
     ) {
-        text_layout.paint(self, window, cx)
+        text_layout.paint(&SharedString::new_static(self), window, cx)
     }
 }
 
@@ -128,7 +128,7 @@ impl Element for SharedString {
         window: &mut Window,
         cx: &mut App,
     ) {
-        text_layout.paint(self.as_ref(), window, cx)
+        text_layout.paint(self, window, cx)
     }
 }
 
@@ -431,7 +431,7 @@ impl TextLayout {
         element_state.bounds = Some(bounds);
     }
 
-    fn paint(&self, text: &str, window: &mut Window, cx: &mut App) {
+    fn paint(&self, text: &SharedString, window: &mut Window, cx: &mut App) {
         let element_state = self.0.borrow();
         let element_state = element_state
             .as_ref()
@@ -442,6 +442,14 @@ impl TextLayout {
             .with_context(|| format!("prepaint has not been performed on {text}"))
             .unwrap();
 
+        if window.accessibility_enabled() {
+            window.insert_accessibility_node(
+                AccessibilityNode::new(AccessibilityRole::StaticText)
+                    .name(text.clone())
+                    .bounds(bounds),
+            );
+        }
+
         let line_height = element_state.line_height;
         let mut line_origin = bounds.origin;
         let text_style = window.text_style();
@@ -596,10 +604,8 @@ impl TextLayout {
         let mut lines = Vec::new();
         for wrapped in self.0.borrow().as_ref().unwrap().lines.iter() {
             let mut seen = 0;
-            for boundary in wrapped.layout.wrap_boundaries.iter() {
-                let index = wrapped.layout.unwrapped_layout.runs[boundary.run_ix].glyphs
-                    [boundary.glyph_ix]
-                    .index;
+            for boundary_ix in 0..wrapped.layout.wrap_boundaries.len() {
+                let index = wrapped.layout.wrap_boundary_index(boundary_ix);
 
                 lines.push(wrapped.text[seen..index].to_string());
                 seen = index;
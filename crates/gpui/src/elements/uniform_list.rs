@@ -5,10 +5,10 @@
 //! elements with uniform height.
 
 use crate::{
-    AnyElement, App, AvailableSpace, Bounds, ContentMask, Element, ElementId, GlobalElementId,
-    Hitbox, InspectorElementId, InteractiveElement, Interactivity, IntoElement, IsZero, LayoutId,
-    ListSizingBehavior, Overflow, Pixels, ScrollHandle, Size, StyleRefinement, Styled, Window,
-    point, size,
+    AccessibilityNode, AccessibilityProperties, AccessibilityRole, AnyElement, App, AvailableSpace,
+    Bounds, ContentMask, Element, ElementId, GlobalElementId, Hitbox, InspectorElementId,
+    InteractiveElement, Interactivity, IntoElement, IsZero, LayoutId, ListSizingBehavior, Overflow,
+    Pixels, ScrollHandle, Size, StyleRefinement, Styled, Window, point, size,
 };
 use smallvec::SmallVec;
 use std::{cell::RefCell, cmp, ops::Range, rc::Rc};
@@ -46,6 +46,10 @@ where
         interactivity: Interactivity {
             element_id: Some(id),
             base_style: Box::new(base_style),
+            accessibility: Some(Box::new(AccessibilityProperties {
+                role: Some(AccessibilityRole::List),
+                ..Default::default()
+            })),
             ..Interactivity::new()
         },
         scroll_handle: None,
@@ -71,6 +75,7 @@ pub struct UniformList {
 /// Frame state used by the [UniformList].
 pub struct UniformListFrameState {
     items: SmallVec<[AnyElement; 32]>,
+    item_accessibility_nodes: SmallVec<[AccessibilityNode; 32]>,
     decorations: SmallVec<[AnyElement; 1]>,
 }
 
@@ -211,6 +216,7 @@ impl Element for UniformList {
             layout_id,
             UniformListFrameState {
                 items: SmallVec::new(),
+                item_accessibility_nodes: SmallVec::new(),
                 decorations: SmallVec::new(),
             },
         )
@@ -391,6 +397,22 @@ impl Element for UniformList {
                             item.layout_as_root(available_space, window, cx);
                             item.prepaint_at(item_origin, window, cx);
                             frame_state.items.push(item);
+
+                            if window.accessibility_enabled() {
+                                let item_ix = if y_flipped {
+                                    self.item_count - 1 - ix
+                                } else {
+                                    ix
+                                };
+                                frame_state.item_accessibility_nodes.push(
+                                    AccessibilityNode::new(AccessibilityRole::ListItem)
+                                        .id((global_id, item_ix))
+                                        .bounds(Bounds::new(
+                                            item_origin,
+                                            size(available_width, item_height),
+                                        )),
+                                );
+                            }
                         }
 
                         let bounds = Bounds::new(
@@ -448,8 +470,11 @@ impl Element for UniformList {
             window,
             cx,
             |_, window, cx| {
+                let mut accessibility_nodes = request_layout.item_accessibility_nodes.drain(..);
                 for item in &mut request_layout.items {
-                    item.paint(window, cx);
+                    window.with_accessibility_node(accessibility_nodes.next(), |window| {
+                        item.paint(window, cx)
+                    });
                 }
                 for decoration in &mut request_layout.decorations {
                     decoration.paint(window, cx);
@@ -0,0 +1,924 @@
+//! Exposes GPUI's accessibility trees to assistive technologies through AT-SPI.
+//!
+//! AT-SPI runs on its own D-Bus bus, whose address is looked up on the session bus.
+//! Every node of every window's tree is registered as an object on that bus, and the
+//! application's root object is embedded into the AT-SPI registry, which is how screen
+//! readers like Orca discover it. The bus is served from the background executor; action
+//! requests coming back from assistive technologies are forwarded to the main thread.
+
+use crate::{
+    AccessibilityAction, AccessibilityActionRequest, AccessibilityNodeId, AccessibilityRole,
+    AccessibilityTree, AccessibilityTreeNode, BackgroundExecutor, ForegroundExecutor, Pixels,
+    Point, Size, point, px,
+};
+use anyhow::{Context as _, Result};
+use futures::{StreamExt as _, channel::mpsc};
+use parking_lot::Mutex;
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, HashMap, HashSet},
+    rc::Rc,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+use util::ResultExt as _;
+use zbus::{
+    interface,
+    object_server::ObjectServer,
+    zvariant::{ObjectPath, OwnedObjectPath, Value},
+};
+
+const ROOT_PATH: &str = "/org/a11y/atspi/accessible/root";
+const NULL_PATH: &str = "/org/a11y/atspi/null";
+const REGISTRY_BUS_NAME: &str = "org.a11y.atspi.Registry";
+
+/// A reference to an accessible object: the bus name that serves it and its path.
+type ObjectRef = (String, OwnedObjectPath);
+
+type ActionHandler = Rc<RefCell<Box<dyn FnMut(AccessibilityActionRequest)>>>;
+
+#[derive(Default)]
+struct BridgeState {
+    bus_name: String,
+    desktop: Option<ObjectRef>,
+    application_id: i32,
+    windows: BTreeMap<u64, Arc<AccessibilityTree>>,
+}
+
+impl BridgeState {
+    fn object_ref(&self, path: OwnedObjectPath) -> ObjectRef {
+        (self.bus_name.clone(), path)
+    }
+
+    fn root_ref(&self) -> ObjectRef {
+        self.object_ref(owned_path(ROOT_PATH))
+    }
+
+    fn node_ref(&self, window: u64, node: &AccessibilityTreeNode) -> ObjectRef {
+        self.object_ref(node_path(window, node.id))
+    }
+}
+
+enum BridgeEvent {
+    TreeUpdated {
+        window: u64,
+        focused: Option<AccessibilityNodeId>,
+    },
+    WindowRemoved(u64),
+}
+
+struct BridgeInner {
+    state: Arc<Mutex<BridgeState>>,
+    events: mpsc::UnboundedSender<BridgeEvent>,
+    connected: Arc<AtomicBool>,
+    action_handlers: RefCell<HashMap<u64, ActionHandler>>,
+    next_window_id: Cell<u64>,
+}
+
+/// The process-wide connection to the AT-SPI bus, shared by all windows.
+#[derive(Clone)]
+pub(crate) struct AccessibilityBridge(Rc<BridgeInner>);
+
+impl AccessibilityBridge {
+    /// Connects to the AT-SPI bus in the background, unless disabled through `NO_AT_BRIDGE`
+    /// like in GTK applications.
+    pub(crate) fn new(
+        background_executor: &BackgroundExecutor,
+        foreground_executor: &ForegroundExecutor,
+    ) -> Option<Self> {
+        if std::env::var_os("NO_AT_BRIDGE").is_some_and(|value| value == "1") {
+            return None;
+        }
+
+        let state = Arc::new(Mutex::new(BridgeState::default()));
+        let connected = Arc::new(AtomicBool::new(false));
+        let (events_tx, events_rx) = mpsc::unbounded();
+        let (actions_tx, mut actions_rx) = mpsc::unbounded();
+
+        background_executor
+            .spawn({
+                let state = state.clone();
+                let connected = connected.clone();
+                async move {
+                    serve(state, connected, events_rx, actions_tx)
+                        .await
+                        .context("serving the AT-SPI accessibility bus")
+                        .log_err();
+                }
+            })
+            .detach();
+
+        let this = Self(Rc::new(BridgeInner {
+            state,
+            events: events_tx,
+            connected,
+            action_handlers: RefCell::default(),
+            next_window_id: Cell::new(1),
+        }));
+
+        let bridge = Rc::downgrade(&this.0);
+        foreground_executor
+            .spawn(async move {
+                while let Some((window, request)) = actions_rx.next().await {
+                    let Some(bridge) = bridge.upgrade() else {
+                        break;
+                    };
+                    let handler = bridge.action_handlers.borrow().get(&window).cloned();
+                    if let Some(handler) = handler {
+                        (handler.borrow_mut())(request);
+                    }
+                }
+            })
+            .detach();
+
+        Some(this)
+    }
+
+    pub(crate) fn add_window(&self) -> AccessibilityWindow {
+        let id = self.0.next_window_id.get();
+        self.0.next_window_id.set(id + 1);
+        AccessibilityWindow {
+            id,
+            bridge: self.0.clone(),
+        }
+    }
+}
+
+/// A window's handle to the [`AccessibilityBridge`]. Dropping it removes the window's
+/// tree from the bus.
+pub(crate) struct AccessibilityWindow {
+    id: u64,
+    bridge: Rc<BridgeInner>,
+}
+
+impl AccessibilityWindow {
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.bridge.connected.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn update_tree(&self, tree: Arc<AccessibilityTree>) {
+        let focused = tree.focused().map(|node| node.id);
+        let previous_focused = self
+            .bridge
+            .state
+            .lock()
+            .windows
+            .insert(self.id, tree)
+            .and_then(|previous| previous.focused().map(|node| node.id));
+
+        self.bridge
+            .events
+            .unbounded_send(BridgeEvent::TreeUpdated {
+                window: self.id,
+                focused: focused.filter(|focused| Some(*focused) != previous_focused),
+            })
+            .ok();
+    }
+
+    pub(crate) fn on_action(&self, callback: Box<dyn FnMut(AccessibilityActionRequest)>) {
+        self.bridge
+            .action_handlers
+            .borrow_mut()
+            .insert(self.id, Rc::new(RefCell::new(callback)));
+    }
+}
+
+impl Drop for AccessibilityWindow {
+    fn drop(&mut self) {
+        self.bridge.action_handlers.borrow_mut().remove(&self.id);
+        self.bridge.state.lock().windows.remove(&self.id);
+        self.bridge
+            .events
+            .unbounded_send(BridgeEvent::WindowRemoved(self.id))
+            .ok();
+    }
+}
+
+async fn serve(
+    state: Arc<Mutex<BridgeState>>,
+    connected: Arc<AtomicBool>,
+    mut events: mpsc::UnboundedReceiver<BridgeEvent>,
+    actions: mpsc::UnboundedSender<(u64, AccessibilityActionRequest)>,
+) -> Result<()> {
+    let session = zbus::Connection::session().await?;
+    let status =
+        zbus::Proxy::new(&session, "org.a11y.Bus", "/org/a11y/bus", "org.a11y.Status").await?;
+    if !status.get_property::<bool>("IsEnabled").await? {
+        log::info!("accessibility is disabled, not connecting to the AT-SPI bus");
+        return Ok(());
+    }
+
+    let a11y_bus =
+        zbus::Proxy::new(&session, "org.a11y.Bus", "/org/a11y/bus", "org.a11y.Bus").await?;
+    let address: String = a11y_bus.call("GetAddress", &()).await?;
+    let connection = zbus::connection::Builder::address(address.as_str())?
+        .build()
+        .await?;
+    let bus_name = connection
+        .unique_name()
+        .context("AT-SPI connection has no unique name")?
+        .to_string();
+    state.lock().bus_name = bus_name.clone();
+
+    let object_server = connection.object_server();
+    object_server
+        .at(
+            ROOT_PATH,
+            RootAccessible {
+                state: state.clone(),
+            },
+        )
+        .await?;
+    object_server
+        .at(
+            ROOT_PATH,
+            RootApplication {
+                state: state.clone(),
+            },
+        )
+        .await?;
+
+    let registry = zbus::Proxy::new(
+        &connection,
+        REGISTRY_BUS_NAME,
+        ROOT_PATH,
+        "org.a11y.atspi.Socket",
+    )
+    .await?;
+    let desktop: ObjectRef = registry
+        .call(
+            "Embed",
+            &((bus_name.as_str(), ObjectPath::try_from(ROOT_PATH)?),),
+        )
+        .await?;
+    state.lock().desktop = Some(desktop);
+    connected.store(true, Ordering::Release);
+
+    let mut registered: HashMap<u64, HashSet<AccessibilityNodeId>> = HashMap::new();
+    while let Some(event) = events.next().await {
+        // Trees are updated every frame, so only register the latest one of each window.
+        let mut updated_windows = BTreeMap::new();
+        let mut removed_windows = Vec::new();
+        let mut pending = Some(event);
+        while let Some(event) = pending.take().or_else(|| events.try_next().ok().flatten()) {
+            match event {
+                BridgeEvent::TreeUpdated { window, focused } => {
+                    let entry = updated_windows.entry(window).or_insert(None);
+                    if focused.is_some() {
+                        *entry = focused;
+                    }
+                }
+                BridgeEvent::WindowRemoved(window) => {
+                    updated_windows.remove(&window);
+                    removed_windows.push(window);
+                }
+            }
+        }
+
+        for window in removed_windows {
+            for node in registered.remove(&window).unwrap_or_default() {
+                unregister_node(object_server, window, node).await;
+            }
+        }
+
+        for (window, focused) in updated_windows {
+            let Some(tree) = state.lock().windows.get(&window).cloned() else {
+                continue;
+            };
+            let registered = registered.entry(window).or_default();
+            let current = tree
+                .nodes()
+                .iter()
+                .map(|node| node.id)
+                .collect::<HashSet<_>>();
+
+            for node in registered.difference(&current) {
+                unregister_node(object_server, window, *node).await;
+            }
+            for node in tree.nodes() {
+                if !registered.contains(&node.id) {
+                    register_node(object_server, &state, &actions, window, node)
+                        .await
+                        .log_err();
+                }
+            }
+            *registered = current;
+
+            if let Some(focused) = focused {
+                connection
+                    .emit_signal(
+                        None::<&str>,
+                        node_path(window, focused),
+                        "org.a11y.atspi.Event.Object",
+                        "StateChanged",
+                        &(
+                            "focused",
+                            1i32,
+                            0i32,
+                            Value::from(0i32),
+                            HashMap::<&str, Value>::new(),
+                        ),
+                    )
+                    .await
+                    .log_err();
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn register_node(
+    object_server: &ObjectServer,
+    state: &Arc<Mutex<BridgeState>>,
+    actions: &mpsc::UnboundedSender<(u64, AccessibilityActionRequest)>,
+    window: u64,
+    node: &AccessibilityTreeNode,
+) -> Result<()> {
+    let object = NodeObject {
+        window,
+        id: node.id,
+        state: state.clone(),
+        actions: actions.clone(),
+    };
+    let path = node_path(window, node.id);
+    object_server
+        .at(path.clone(), NodeAccessible(object.clone()))
+        .await?;
+    object_server
+        .at(path.clone(), NodeComponent(object.clone()))
+        .await?;
+    object_server
+        .at(path.clone(), NodeAction(object.clone()))
+        .await?;
+    if is_text_input(node.role) {
+        object_server.at(path, NodeText(object)).await?;
+    }
+    Ok(())
+}
+
+async fn unregister_node(object_server: &ObjectServer, window: u64, node: AccessibilityNodeId) {
+    let path = node_path(window, node);
+    object_server
+        .remove::<NodeAccessible, _>(path.clone())
+        .await
+        .ok();
+    object_server
+        .remove::<NodeComponent, _>(path.clone())
+        .await
+        .ok();
+    object_server
+        .remove::<NodeAction, _>(path.clone())
+        .await
+        .ok();
+    object_server.remove::<NodeText, _>(path).await.ok();
+}
+
+fn node_path(window: u64, node: AccessibilityNodeId) -> OwnedObjectPath {
+    owned_path(&format!(
+        "/org/a11y/atspi/accessible/w{window}_{:016x}",
+        node.0
+    ))
+}
+
+fn owned_path(path: &str) -> OwnedObjectPath {
+    OwnedObjectPath::try_from(path.to_string()).expect("invalid object path")
+}
+
+fn null_ref() -> ObjectRef {
+    (String::new(), owned_path(NULL_PATH))
+}
+
+fn is_text_input(role: AccessibilityRole) -> bool {
+    matches!(
+        role,
+        AccessibilityRole::TextInput | AccessibilityRole::MultilineTextInput
+    )
+}
+
+// See `AtspiRole` in at-spi2-core's atspi-constants.h.
+fn atspi_role(role: AccessibilityRole) -> u32 {
+    match role {
+        AccessibilityRole::Window => 23,
+        AccessibilityRole::Group => 39,
+        AccessibilityRole::Button => 43,
+        AccessibilityRole::Checkbox => 7,
+        AccessibilityRole::Link => 88,
+        AccessibilityRole::List => 31,
+        AccessibilityRole::ListItem => 32,
+        AccessibilityRole::Menu => 33,
+        AccessibilityRole::MenuItem => 35,
+        AccessibilityRole::TabList => 38,
+        AccessibilityRole::Tab => 37,
+        AccessibilityRole::Dialog => 16,
+        AccessibilityRole::Tooltip => 64,
+        AccessibilityRole::Heading => 83,
+        AccessibilityRole::Image => 27,
+        AccessibilityRole::StaticText => 29,
+        AccessibilityRole::TextInput => 79,
+        AccessibilityRole::MultilineTextInput => 61,
+    }
+}
+
+const ATSPI_ROLE_APPLICATION: u32 = 75;
+
+// See `AtspiStateType` in at-spi2-core's atspi-constants.h.
+const STATE_ACTIVE: u32 = 1;
+const STATE_CHECKED: u32 = 4;
+const STATE_EDITABLE: u32 = 7;
+const STATE_ENABLED: u32 = 8;
+const STATE_FOCUSABLE: u32 = 11;
+const STATE_FOCUSED: u32 = 12;
+const STATE_MULTI_LINE: u32 = 17;
+const STATE_SENSITIVE: u32 = 24;
+const STATE_SHOWING: u32 = 25;
+const STATE_SINGLE_LINE: u32 = 26;
+const STATE_VISIBLE: u32 = 30;
+
+fn state_set(states: impl IntoIterator<Item = u32>) -> Vec<u32> {
+    let mut set = vec![0u32; 2];
+    for state in states {
+        set[(state / 32) as usize] |= 1 << (state % 32);
+    }
+    set
+}
+
+fn to_atspi_point(point: Point<Pixels>) -> (i32, i32) {
+    (point.x.0.round() as i32, point.y.0.round() as i32)
+}
+
+fn to_atspi_size(size: Size<Pixels>) -> (i32, i32) {
+    (size.width.0.round() as i32, size.height.0.round() as i32)
+}
+
+struct RootAccessible {
+    state: Arc<Mutex<BridgeState>>,
+}
+
+#[interface(name = "org.a11y.atspi.Accessible")]
+impl RootAccessible {
+    #[zbus(property)]
+    fn name(&self) -> String {
+        std::env::current_exe()
+            .ok()
+            .and_then(|path| Some(path.file_stem()?.to_string_lossy().into_owned()))
+            .unwrap_or_default()
+    }
+
+    #[zbus(property)]
+    fn description(&self) -> String {
+        String::new()
+    }
+
+    #[zbus(property)]
+    fn parent(&self) -> ObjectRef {
+        self.state.lock().desktop.clone().unwrap_or_else(null_ref)
+    }
+
+    #[zbus(property)]
+    fn child_count(&self) -> i32 {
+        self.state.lock().windows.len() as i32
+    }
+
+    #[zbus(property)]
+    fn locale(&self) -> String {
+        String::new()
+    }
+
+    #[zbus(property)]
+    fn accessible_id(&self) -> String {
+        String::new()
+    }
+
+    fn get_child_at_index(&self, index: i32) -> ObjectRef {
+        self.get_children()
+            .into_iter()
+            .nth(index.max(0) as usize)
+            .unwrap_or_else(null_ref)
+    }
+
+    fn get_children(&self) -> Vec<ObjectRef> {
+        let state = self.state.lock();
+        state
+            .windows
+            .iter()
+            .map(|(window, tree)| state.node_ref(*window, tree.root()))
+            .collect()
+    }
+
+    fn get_index_in_parent(&self) -> i32 {
+        -1
+    }
+
+    fn get_relation_set(&self) -> Vec<(u32, Vec<ObjectRef>)> {
+        Vec::new()
+    }
+
+    fn get_role(&self) -> u32 {
+        ATSPI_ROLE_APPLICATION
+    }
+
+    fn get_role_name(&self) -> String {
+        "application".into()
+    }
+
+    fn get_localized_role_name(&self) -> String {
+        "application".into()
+    }
+
+    fn get_state(&self) -> Vec<u32> {
+        state_set([])
+    }
+
+    fn get_attributes(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    fn get_application(&self) -> ObjectRef {
+        self.state.lock().root_ref()
+    }
+
+    fn get_interfaces(&self) -> Vec<String> {
+        vec![
+            "org.a11y.atspi.Accessible".into(),
+            "org.a11y.atspi.Application".into(),
+        ]
+    }
+}
+
+struct RootApplication {
+    state: Arc<Mutex<BridgeState>>,
+}
+
+#[interface(name = "org.a11y.atspi.Application")]
+impl RootApplication {
+    #[zbus(property)]
+    fn toolkit_name(&self) -> String {
+        "GPUI".into()
+    }
+
+    #[zbus(property)]
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").into()
+    }
+
+    #[zbus(property)]
+    fn atspi_version(&self) -> String {
+        "2.1".into()
+    }
+
+    #[zbus(property)]
+    fn id(&self) -> i32 {
+        self.state.lock().application_id
+    }
+
+    #[zbus(property)]
+    fn set_id(&mut self, id: i32) {
+        self.state.lock().application_id = id;
+    }
+
+    fn get_locale(&self, _locale_type: u32) -> String {
+        String::new()
+    }
+}
+
+#[derive(Clone)]
+struct NodeObject {
+    window: u64,
+    id: AccessibilityNodeId,
+    state: Arc<Mutex<BridgeState>>,
+    actions: mpsc::UnboundedSender<(u64, AccessibilityActionRequest)>,
+}
+
+impl NodeObject {
+    fn read<R: Default>(
+        &self,
+        f: impl FnOnce(&BridgeState, &AccessibilityTree, &AccessibilityTreeNode) -> R,
+    ) -> R {
+        let state = self.state.lock();
+        state
+            .windows
+            .get(&self.window)
+            .and_then(|tree| Some(f(&state, tree, tree.node(self.id)?)))
+            .unwrap_or_default()
+    }
+
+    fn request(&self, action: AccessibilityAction) -> bool {
+        let supported = self.read(|_, _, node| node.actions.contains(&action));
+        supported
+            && self
+                .actions
+                .unbounded_send((
+                    self.window,
+                    AccessibilityActionRequest {
+                        node: self.id,
+                        action,
+                    },
+                ))
+                .is_ok()
+    }
+
+    fn node_at_point(&self, position: Point<Pixels>) -> ObjectRef {
+        self.read(|state, tree, node| {
+            if !node.bounds.contains(&position) {
+                return None;
+            }
+            let mut node = node;
+            while let Some(child) = tree
+                .children(node)
+                .filter(|child| child.bounds.contains(&position))
+                .last()
+            {
+                node = child;
+            }
+            Some(state.node_ref(self.window, node))
+        })
+        .unwrap_or_else(null_ref)
+    }
+}
+
+struct NodeAccessible(NodeObject);
+
+#[interface(name = "org.a11y.atspi.Accessible")]
+impl NodeAccessible {
+    #[zbus(property)]
+    fn name(&self) -> String {
+        self.0
+            .read(|_, _, node| node.name.as_ref().map(|name| name.to_string()))
+            .unwrap_or_default()
+    }
+
+    #[zbus(property)]
+    fn description(&self) -> String {
+        String::new()
+    }
+
+    #[zbus(property)]
+    fn parent(&self) -> ObjectRef {
+        self.0
+            .read(|state, tree, node| {
+                Some(match tree.parent(node) {
+                    Some(parent) => state.node_ref(self.0.window, parent),
+                    None => state.root_ref(),
+                })
+            })
+            .unwrap_or_else(null_ref)
+    }
+
+    #[zbus(property)]
+    fn child_count(&self) -> i32 {
+        self.0.read(|_, _, node| node.children.len() as i32)
+    }
+
+    #[zbus(property)]
+    fn locale(&self) -> String {
+        String::new()
+    }
+
+    #[zbus(property)]
+    fn accessible_id(&self) -> String {
+        format!("{:016x}", self.0.id.0)
+    }
+
+    fn get_child_at_index(&self, index: i32) -> ObjectRef {
+        self.get_children()
+            .into_iter()
+            .nth(index.max(0) as usize)
+            .unwrap_or_else(null_ref)
+    }
+
+    fn get_children(&self) -> Vec<ObjectRef> {
+        self.0.read(|state, tree, node| {
+            tree.children(node)
+                .map(|child| state.node_ref(self.0.window, child))
+                .collect()
+        })
+    }
+
+    fn get_index_in_parent(&self) -> i32 {
+        self.0
+            .read(|state, tree, node| match tree.parent(node) {
+                Some(parent) => tree
+                    .children(parent)
+                    .position(|sibling| sibling.id == node.id)
+                    .map(|ix| ix as i32),
+                None => state
+                    .windows
+                    .keys()
+                    .position(|window| *window == self.0.window)
+                    .map(|ix| ix as i32),
+            })
+            .unwrap_or(-1)
+    }
+
+    fn get_relation_set(&self) -> Vec<(u32, Vec<ObjectRef>)> {
+        Vec::new()
+    }
+
+    fn get_role(&self) -> u32 {
+        self.0.read(|_, _, node| atspi_role(node.role))
+    }
+
+    fn get_role_name(&self) -> String {
+        self.0
+            .read(|_, _, node| node.role.as_str().replace('_', " "))
+    }
+
+    fn get_localized_role_name(&self) -> String {
+        self.get_role_name()
+    }
+
+    fn get_state(&self) -> Vec<u32> {
+        self.0.read(|_, _, node| {
+            let mut states = vec![STATE_ENABLED, STATE_SENSITIVE, STATE_VISIBLE, STATE_SHOWING];
+            if node.role == AccessibilityRole::Window {
+                states.push(STATE_ACTIVE);
+            }
+            if node.focusable() {
+                states.push(STATE_FOCUSABLE);
+            }
+            if node.focused {
+                states.push(STATE_FOCUSED);
+            }
+            if node.checked == Some(true) {
+                states.push(STATE_CHECKED);
+            }
+            match node.role {
+                AccessibilityRole::TextInput => {
+                    states.extend([STATE_EDITABLE, STATE_SINGLE_LINE]);
+                }
+                AccessibilityRole::MultilineTextInput => {
+                    states.extend([STATE_EDITABLE, STATE_MULTI_LINE]);
+                }
+                _ => {}
+            }
+            state_set(states)
+        })
+    }
+
+    fn get_attributes(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    fn get_application(&self) -> ObjectRef {
+        self.0.state.lock().root_ref()
+    }
+
+    fn get_interfaces(&self) -> Vec<String> {
+        let mut interfaces = vec![
+            "org.a11y.atspi.Accessible".to_string(),
+            "org.a11y.atspi.Component".to_string(),
+            "org.a11y.atspi.Action".to_string(),
+        ];
+        if self.0.read(|_, _, node| is_text_input(node.role)) {
+            interfaces.push("org.a11y.atspi.Text".to_string());
+        }
+        interfaces
+    }
+}
+
+/// Extents are reported relative to the window for every coordinate type, since
+/// Wayland doesn't let clients know where their windows are on screen.
+struct NodeComponent(NodeObject);
+
+#[interface(name = "org.a11y.atspi.Component")]
+impl NodeComponent {
+    fn contains(&self, x: i32, y: i32, _coord_type: u32) -> bool {
+        self.0
+            .read(|_, _, node| node.bounds.contains(&point(px(x as f32), px(y as f32))))
+    }
+
+    fn get_accessible_at_point(&self, x: i32, y: i32, _coord_type: u32) -> ObjectRef {
+        self.0.node_at_point(point(px(x as f32), px(y as f32)))
+    }
+
+    fn get_extents(&self, _coord_type: u32) -> (i32, i32, i32, i32) {
+        self.0.read(|_, _, node| {
+            let (x, y) = to_atspi_point(node.bounds.origin);
+            let (width, height) = to_atspi_size(node.bounds.size);
+            (x, y, width, height)
+        })
+    }
+
+    fn get_position(&self, _coord_type: u32) -> (i32, i32) {
+        self.0.read(|_, _, node| to_atspi_point(node.bounds.origin))
+    }
+
+    fn get_size(&self) -> (i32, i32) {
+        self.0.read(|_, _, node| to_atspi_size(node.bounds.size))
+    }
+
+    fn get_layer(&self) -> u32 {
+        // ATSPI_LAYER_WIDGET
+        3
+    }
+
+    #[zbus(name = "GetMDIZOrder")]
+    fn get_mdi_z_order(&self) -> i16 {
+        0
+    }
+
+    fn grab_focus(&self) -> bool {
+        self.0.request(AccessibilityAction::Focus)
+    }
+
+    fn get_alpha(&self) -> f64 {
+        1.
+    }
+}
+
+struct NodeAction(NodeObject);
+
+#[interface(name = "org.a11y.atspi.Action")]
+impl NodeAction {
+    #[zbus(property)]
+    fn n_actions(&self) -> i32 {
+        self.0.read(|_, _, node| node.actions.len() as i32)
+    }
+
+    fn get_description(&self, _index: i32) -> String {
+        String::new()
+    }
+
+    fn get_name(&self, index: i32) -> String {
+        self.action(index)
+            .map(|action| action.as_str().to_string())
+            .unwrap_or_default()
+    }
+
+    fn get_localized_name(&self, index: i32) -> String {
+        self.get_name(index)
+    }
+
+    fn get_key_binding(&self, _index: i32) -> String {
+        String::new()
+    }
+
+    fn get_actions(&self) -> Vec<(String, String, String)> {
+        self.0.read(|_, _, node| {
+            node.actions
+                .iter()
+                .map(|action| {
+                    let name = action.as_str().to_string();
+                    (name.clone(), name, String::new())
+                })
+                .collect()
+        })
+    }
+
+    fn do_action(&self, index: i32) -> bool {
+        self.action(index)
+            .is_some_and(|action| self.0.request(action))
+    }
+}
+
+impl NodeAction {
+    fn action(&self, index: i32) -> Option<AccessibilityAction> {
+        self.0.read(|_, _, node| {
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| node.actions.get(index).copied())
+        })
+    }
+}
+
+/// A read-only view of a text input's value. Editing goes through the window's input handler.
+struct NodeText(NodeObject);
+
+#[interface(name = "org.a11y.atspi.Text")]
+impl NodeText {
+    #[zbus(property)]
+    fn character_count(&self) -> i32 {
+        self.value().chars().count() as i32
+    }
+
+    #[zbus(property)]
+    fn caret_offset(&self) -> i32 {
+        0
+    }
+
+    fn get_text(&self, start_offset: i32, end_offset: i32) -> String {
+        let value = self.value();
+        let start = start_offset.max(0) as usize;
+        let end = if end_offset < 0 {
+            usize::MAX
+        } else {
+            end_offset as usize
+        };
+        value
+            .chars()
+            .skip(start)
+            .take(end.saturating_sub(start))
+            .collect()
+    }
+}
+
+impl NodeText {
+    fn value(&self) -> String {
+        self.0
+            .read(|_, _, node| node.value.as_ref().map(|value| value.to_string()))
+            .unwrap_or_default()
+    }
+}
@@ -93,6 +93,8 @@ pub(crate) struct LinuxCommon {
     pub(crate) callbacks: PlatformHandlers,
     pub(crate) signal: LoopSignal,
     pub(crate) menus: Vec<OwnedMenu>,
+    #[cfg(any(feature = "wayland", feature = "x11"))]
+    pub(crate) accessibility: Option<crate::AccessibilityBridge>,
 }
 
 impl LinuxCommon {
@@ -109,16 +111,23 @@ impl LinuxCommon {
         let dispatcher = Arc::new(LinuxDispatcher::new(main_sender.clone()));
 
         let background_executor = BackgroundExecutor::new(dispatcher.clone());
+        let foreground_executor = ForegroundExecutor::new(dispatcher.clone());
+
+        #[cfg(any(feature = "wayland", feature = "x11"))]
+        let accessibility =
+            crate::AccessibilityBridge::new(&background_executor, &foreground_executor);
 
         let common = LinuxCommon {
             background_executor,
-            foreground_executor: ForegroundExecutor::new(dispatcher.clone()),
+            foreground_executor,
             text_system,
             appearance: WindowAppearance::Light,
             auto_hide_scrollbars: false,
             callbacks,
             signal,
             menus: Vec::new(),
+            #[cfg(any(feature = "wayland", feature = "x11"))]
+            accessibility,
         };
 
         (common, main_receiver)
@@ -455,6 +455,7 @@ impl CosmicTextSystemState {
             descent: layout.max_descent.into(),
             runs,
             len: text.len(),
+            direction_runs: Vec::new(),
         }
     }
 }
@@ -691,6 +691,11 @@ impl LinuxClient for WaylandClient {
     ) -> anyhow::Result<Box<dyn PlatformWindow>> {
         let mut state = self.0.borrow_mut();
 
+        let accessibility = state
+            .common
+            .accessibility
+            .as_ref()
+            .map(|accessibility| accessibility.add_window());
         let (window, surface_id) = WaylandWindow::new(
             handle,
             state.globals.clone(),
@@ -698,6 +703,7 @@ impl LinuxClient for WaylandClient {
             WaylandClientStatePtr(Rc::downgrade(&self.0)),
             params,
             state.common.appearance,
+            accessibility,
         )?;
         state.windows.insert(surface_id, window.0.clone());
 
@@ -23,11 +23,11 @@ use wayland_protocols_plasma::blur::client::org_kde_kwin_blur;
 
 use crate::scene::Scene;
 use crate::{
-    AnyWindowHandle, Bounds, Decorations, Globals, GpuSpecs, Modifiers, Output, Pixels,
-    PlatformDisplay, PlatformInput, Point, PromptButton, PromptLevel, RequestFrameOptions,
-    ResizeEdge, ScaledPixels, Size, Tiling, WaylandClientStatePtr, WindowAppearance,
-    WindowBackgroundAppearance, WindowBounds, WindowControlArea, WindowControls, WindowDecorations,
-    WindowParams, px, size,
+    AccessibilityActionRequest, AccessibilityTree, AccessibilityWindow, AnyWindowHandle, Bounds,
+    Decorations, Globals, GpuSpecs, Modifiers, Output, Pixels, PlatformDisplay, PlatformInput,
+    Point, PromptButton, PromptLevel, RequestFrameOptions, ResizeEdge, ScaledPixels, Size, Tiling,
+    WaylandClientStatePtr, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
+    WindowControlArea, WindowControls, WindowDecorations, WindowParams, px, size,
 };
 use crate::{
     Capslock,
@@ -110,6 +110,7 @@ pub struct WaylandWindowState {
     in_progress_window_controls: Option<WindowControls>,
     window_controls: WindowControls,
     inset: Option<Pixels>,
+    accessibility: Option<AccessibilityWindow>,
 }
 
 #[derive(Clone)]
@@ -131,6 +132,7 @@ impl WaylandWindowState {
         globals: Globals,
         gpu_context: &BladeContext,
         options: WindowParams,
+        accessibility: Option<AccessibilityWindow>,
     ) -> anyhow::Result<Self> {
         let renderer = {
             let raw_window = RawWindow {
@@ -184,6 +186,7 @@ impl WaylandWindowState {
             in_progress_window_controls: None,
             window_controls: WindowControls::default(),
             inset: None,
+            accessibility,
         })
     }
 
@@ -267,6 +270,7 @@ impl WaylandWindow {
         client: WaylandClientStatePtr,
         params: WindowParams,
         appearance: WindowAppearance,
+        accessibility: Option<AccessibilityWindow>,
     ) -> anyhow::Result<(Self, ObjectId)> {
         let surface = globals.compositor.create_surface(&globals.qh, ());
         let xdg_surface = globals
@@ -308,6 +312,7 @@ impl WaylandWindow {
                 globals,
                 gpu_context,
                 params,
+                accessibility,
             )?)),
             callbacks: Rc::new(RefCell::new(Callbacks::default())),
         });
@@ -1074,6 +1079,25 @@ impl PlatformWindow for WaylandWindow {
     fn gpu_specs(&self) -> Option<GpuSpecs> {
         self.borrow().renderer.gpu_specs().into()
     }
+
+    fn accessibility_enabled(&self) -> bool {
+        self.borrow()
+            .accessibility
+            .as_ref()
+            .is_some_and(|accessibility| accessibility.is_enabled())
+    }
+
+    fn update_accessibility_tree(&self, tree: Arc<AccessibilityTree>) {
+        if let Some(accessibility) = self.borrow().accessibility.as_ref() {
+            accessibility.update_tree(tree);
+        }
+    }
+
+    fn on_accessibility_action(&self, callback: Box<dyn FnMut(AccessibilityActionRequest)>) {
+        if let Some(accessibility) = self.borrow().accessibility.as_ref() {
+            accessibility.on_action(callback);
+        }
+    }
 }
 
 fn update_window(mut state: RefMut<WaylandWindowState>) {
@@ -1513,6 +1513,11 @@ impl LinuxClient for X11Client {
             &state.atoms,
             state.scale_factor,
             state.common.appearance,
+            state
+                .common
+                .accessibility
+                .as_ref()
+                .map(|accessibility| accessibility.add_window()),
         )?;
         check_reply(
             || "Failed to set XdndAware property",
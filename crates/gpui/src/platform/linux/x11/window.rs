@@ -3,10 +3,11 @@ use x11rb::connection::RequestConnection;
 
 use crate::platform::blade::{BladeContext, BladeRenderer, BladeSurfaceConfig};
 use crate::{
-    AnyWindowHandle, Bounds, Decorations, DevicePixels, ForegroundExecutor, GpuSpecs, Modifiers,
-    Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow,
-    Point, PromptButton, PromptLevel, RequestFrameOptions, ResizeEdge, ScaledPixels, Scene, Size,
-    Tiling, WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowControlArea,
+    AccessibilityActionRequest, AccessibilityTree, AccessibilityWindow, AnyWindowHandle, Bounds,
+    Decorations, DevicePixels, ForegroundExecutor, GpuSpecs, Modifiers, Pixels, PlatformAtlas,
+    PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow, Point, PromptButton,
+    PromptLevel, RequestFrameOptions, ResizeEdge, ScaledPixels, Scene, Size, Tiling,
+    WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowControlArea,
     WindowDecorations, WindowKind, WindowParams, X11ClientStatePtr, px, size,
 };
 
@@ -271,6 +272,7 @@ pub struct X11WindowState {
     edge_constraints: Option<EdgeConstraints>,
     pub handle: AnyWindowHandle,
     last_insets: [u32; 4],
+    accessibility: Option<AccessibilityWindow>,
 }
 
 impl X11WindowState {
@@ -656,6 +658,7 @@ impl X11WindowState {
                 edge_constraints: None,
                 counter_id: sync_request_counter,
                 last_sync_counter: None,
+                accessibility: None,
             })
         });
 
@@ -737,6 +740,7 @@ impl X11Window {
         atoms: &XcbAtoms,
         scale_factor: f32,
         appearance: WindowAppearance,
+        accessibility: Option<AccessibilityWindow>,
     ) -> anyhow::Result<Self> {
         let ptr = X11WindowStatePtr {
             state: Rc::new(RefCell::new(X11WindowState::new(
@@ -758,7 +762,8 @@ impl X11Window {
             x_window,
         };
 
-        let state = ptr.state.borrow_mut();
+        let mut state = ptr.state.borrow_mut();
+        state.accessibility = accessibility;
         ptr.set_wm_properties(state)?;
 
         Ok(Self(ptr))
@@ -1629,4 +1634,25 @@ impl PlatformWindow for X11Window {
     fn gpu_specs(&self) -> Option<GpuSpecs> {
         self.0.state.borrow().renderer.gpu_specs().into()
     }
+
+    fn accessibility_enabled(&self) -> bool {
+        self.0
+            .state
+            .borrow()
+            .accessibility
+            .as_ref()
+            .is_some_and(|accessibility| accessibility.is_enabled())
+    }
+
+    fn update_accessibility_tree(&self, tree: Arc<AccessibilityTree>) {
+        if let Some(accessibility) = self.0.state.borrow().accessibility.as_ref() {
+            accessibility.update_tree(tree);
+        }
+    }
+
+    fn on_accessibility_action(&self, callback: Box<dyn FnMut(AccessibilityActionRequest)>) {
+        if let Some(accessibility) = self.0.state.borrow().accessibility.as_ref() {
+            accessibility.on_action(callback);
+        }
+    }
 }
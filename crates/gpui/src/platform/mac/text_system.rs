@@ -511,6 +511,7 @@ impl MacTextSystemState {
             ascent: typographic_bounds.ascent.into(),
             descent: typographic_bounds.descent.into(),
             len: text.len(),
+            direction_runs: Vec::new(),
         }
     }
 }
@@ -1,11 +1,14 @@
 mod dispatcher;
 mod display;
 mod platform;
+mod rasterizer;
+mod text_system;
 mod window;
 
 pub use dispatcher::*;
 pub(crate) use display::*;
 pub(crate) use platform::*;
+pub(crate) use text_system::*;
 pub(crate) use window::*;
 
 pub use platform::TestScreenCaptureSource;
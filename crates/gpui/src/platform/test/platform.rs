@@ -1,8 +1,8 @@
 use crate::{
     AnyWindowHandle, BackgroundExecutor, ClipboardItem, CursorStyle, DevicePixels,
-    ForegroundExecutor, Keymap, NoopTextSystem, Platform, PlatformDisplay, PlatformKeyboardLayout,
+    ForegroundExecutor, Keymap, Platform, PlatformDisplay, PlatformKeyboardLayout,
     PlatformTextSystem, PromptButton, ScreenCaptureFrame, ScreenCaptureSource, ScreenCaptureStream,
-    Size, Task, TestDisplay, TestWindow, WindowAppearance, WindowParams, size,
+    Size, Task, TestDisplay, TestTextSystem, TestWindow, WindowAppearance, WindowParams, size,
 };
 use anyhow::Result;
 use collections::VecDeque;
@@ -91,7 +91,7 @@ impl TestPlatform {
             )
         };
 
-        let text_system = Arc::new(NoopTextSystem);
+        let text_system = Arc::new(TestTextSystem::new());
 
         Rc::new_cyclic(|weak| TestPlatform {
             background_executor: executor,
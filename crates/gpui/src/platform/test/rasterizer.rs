@@ -0,0 +1,1083 @@
+//! A CPU port of the blade shaders, used by the test platform to turn a
+//! [`Scene`] into pixels without a GPU.
+//!
+//! Blending happens in linear space with premultiplied alpha, and the result
+//! is encoded as sRGB, which mirrors what the GPU renderers do when presenting
+//! to an sRGB swapchain.
+
+use crate::{
+    AtlasTile, Background, BackgroundTag, BorderStyle, Bounds, ColorSpace, Corners, DevicePixels,
+    Hsla, MonochromeSprite, Path, PolychromeSprite, PrimitiveBatch, Quad, Rgba, ScaledPixels,
+    Scene, Shadow, Size, TestAtlas, Underline,
+};
+use image::RgbaImage;
+use std::f32::consts::PI;
+
+const ANTIALIAS_THRESHOLD: f32 = 0.5;
+const GRAYSCALE_FACTORS: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+type Color = [f32; 4];
+
+/// Renders `scene` into an image of the given size, reading sprite data out of `atlas`.
+pub(crate) fn rasterize_scene(
+    scene: &Scene,
+    atlas: &TestAtlas,
+    size: Size<DevicePixels>,
+) -> RgbaImage {
+    let mut canvas = Canvas::new(size.width.0.max(0) as u32, size.height.0.max(0) as u32);
+    for batch in scene.batches() {
+        match batch {
+            PrimitiveBatch::Shadows(shadows) => {
+                for shadow in shadows {
+                    canvas.draw_shadow(shadow);
+                }
+            }
+            PrimitiveBatch::Quads(quads) => {
+                for quad in quads {
+                    canvas.draw_quad(quad);
+                }
+            }
+            PrimitiveBatch::Paths(paths) => {
+                for path in paths {
+                    canvas.draw_path(path);
+                }
+            }
+            PrimitiveBatch::Underlines(underlines) => {
+                for underline in underlines {
+                    canvas.draw_underline(underline);
+                }
+            }
+            PrimitiveBatch::MonochromeSprites { sprites, .. } => {
+                for sprite in sprites {
+                    canvas.draw_monochrome_sprite(sprite, atlas);
+                }
+            }
+            PrimitiveBatch::PolychromeSprites { sprites, .. } => {
+                for sprite in sprites {
+                    canvas.draw_polychrome_sprite(sprite, atlas);
+                }
+            }
+            // Surfaces are only produced by screen capture and video playback,
+            // which aren't available on the test platform.
+            PrimitiveBatch::Surfaces(_) => {}
+        }
+    }
+    canvas.into_image()
+}
+
+/// A range of pixels whose centers fall within some bounds.
+struct PixelRange {
+    x: std::ops::Range<u32>,
+    y: std::ops::Range<u32>,
+}
+
+impl PixelRange {
+    fn pixels(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.y
+            .clone()
+            .flat_map(move |y| self.x.clone().map(move |x| (x, y)))
+    }
+}
+
+struct Canvas {
+    width: u32,
+    height: u32,
+    /// Linear, premultiplied colors.
+    pixels: Vec<Color>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0.; 4]; (width * height) as usize],
+        }
+    }
+
+    fn into_image(self) -> RgbaImage {
+        let mut image = RgbaImage::new(self.width, self.height);
+        for (pixel, color) in image.pixels_mut().zip(self.pixels) {
+            let alpha = color[3].clamp(0., 1.);
+            if alpha > 0. {
+                let unpremultiply = |value: f32| to_u8(linear_to_srgb(value / alpha));
+                pixel.0 = [
+                    unpremultiply(color[0]),
+                    unpremultiply(color[1]),
+                    unpremultiply(color[2]),
+                    to_u8(alpha),
+                ];
+            }
+        }
+        image
+    }
+
+    /// Returns the pixels whose centers are inside of both `bounds` and `clip`.
+    fn pixel_range(&self, bounds: Bounds<f32>, clip: Bounds<f32>) -> PixelRange {
+        let left = bounds.origin.x.max(clip.origin.x);
+        let top = bounds.origin.y.max(clip.origin.y);
+        let right = (bounds.origin.x + bounds.size.width).min(clip.origin.x + clip.size.width);
+        let bottom = (bounds.origin.y + bounds.size.height).min(clip.origin.y + clip.size.height);
+        let first_pixel =
+            |edge: f32, limit: u32| (edge - 0.5).ceil().clamp(0., limit as f32) as u32;
+        PixelRange {
+            x: first_pixel(left, self.width)..first_pixel(right, self.width),
+            y: first_pixel(top, self.height)..first_pixel(bottom, self.height),
+        }
+    }
+
+    /// Composites a straight-alpha linear `color` over the pixel, scaling its
+    /// alpha by `coverage`.
+    fn blend(&mut self, x: u32, y: u32, color: Color, coverage: f32) {
+        let alpha = (color[3] * coverage).clamp(0., 1.);
+        if alpha <= 0. || !alpha.is_finite() {
+            return;
+        }
+        let pixel = &mut self.pixels[(y * self.width + x) as usize];
+        for (channel, source) in pixel.iter_mut().zip(color).take(3) {
+            *channel = source * alpha + *channel * (1. - alpha);
+        }
+        pixel[3] = alpha + pixel[3] * (1. - alpha);
+    }
+
+    fn draw_quad(&mut self, quad: &Quad) {
+        let bounds = to_f32_bounds(&quad.bounds);
+        let corner_radii = to_f32_corners(&quad.corner_radii);
+        let border_widths = [
+            quad.border_widths.top.0,
+            quad.border_widths.right.0,
+            quad.border_widths.bottom.0,
+            quad.border_widths.left.0,
+        ];
+        let gradient = GradientColors::new(&quad.background);
+        let border_color = hsla_to_linear(quad.border_color);
+        let unrounded = corner_radii.iter().all(|radius| *radius == 0.);
+        let has_border = border_widths.iter().any(|width| *width != 0.);
+
+        let range = self.pixel_range(bounds, to_f32_bounds(&quad.content_mask.bounds));
+        for (x, y) in range.pixels() {
+            let position = [x as f32 + 0.5, y as f32 + 0.5];
+            let background = gradient.color_at(&quad.background, position, bounds);
+            if !has_border && unrounded {
+                self.blend(x, y, background, 1.);
+                continue;
+            }
+
+            let half_size = [bounds.size.width / 2., bounds.size.height / 2.];
+            let point = [position[0] - bounds.origin.x, position[1] - bounds.origin.y];
+            let center_to_point = [point[0] - half_size[0], point[1] - half_size[1]];
+            let corner_radius = pick_corner_radius(center_to_point, corner_radii);
+            let border = [
+                if center_to_point[0] < 0. {
+                    border_widths[3]
+                } else {
+                    border_widths[1]
+                },
+                if center_to_point[1] < 0. {
+                    border_widths[0]
+                } else {
+                    border_widths[2]
+                },
+            ];
+            let reduced_border = border.map(|width| {
+                if width == 0. {
+                    -ANTIALIAS_THRESHOLD
+                } else {
+                    width
+                }
+            });
+            let corner_to_point = [
+                center_to_point[0].abs() - half_size[0],
+                center_to_point[1].abs() - half_size[1],
+            ];
+            let corner_center_to_point = corner_to_point.map(|value| value + corner_radius);
+            let is_near_rounded_corner =
+                corner_center_to_point[0] >= 0. && corner_center_to_point[1] >= 0.;
+            let straight_border_inner_corner_to_point = [
+                corner_to_point[0] + reduced_border[0],
+                corner_to_point[1] + reduced_border[1],
+            ];
+            let is_beyond_inner_straight_border = straight_border_inner_corner_to_point[0] > 0.
+                || straight_border_inner_corner_to_point[1] > 0.;
+            let is_within_inner_straight_border = straight_border_inner_corner_to_point[0]
+                < -ANTIALIAS_THRESHOLD
+                && straight_border_inner_corner_to_point[1] < -ANTIALIAS_THRESHOLD;
+            if is_within_inner_straight_border && !is_near_rounded_corner {
+                self.blend(x, y, background, 1.);
+                continue;
+            }
+
+            let outer_sdf = quad_sdf_impl(corner_center_to_point, corner_radius);
+            let inner_sdf = if corner_center_to_point[0] <= 0. || corner_center_to_point[1] <= 0. {
+                -straight_border_inner_corner_to_point[0]
+                    .max(straight_border_inner_corner_to_point[1])
+            } else if is_beyond_inner_straight_border {
+                -1.
+            } else if reduced_border[0] == reduced_border[1] {
+                -(outer_sdf + reduced_border[0])
+            } else {
+                let ellipse_radii = reduced_border.map(|width| (corner_radius - width).max(0.));
+                quarter_ellipse_sdf(corner_center_to_point, ellipse_radii)
+            };
+            let border_sdf = inner_sdf.max(outer_sdf);
+
+            let mut color = background;
+            if border_sdf < ANTIALIAS_THRESHOLD {
+                let mut border_color = border_color;
+                if quad.border_style == BorderStyle::Dashed {
+                    border_color[3] *= dash_alpha_for_quad(
+                        point,
+                        center_to_point,
+                        corner_center_to_point,
+                        corner_radius,
+                        border,
+                        bounds.size,
+                        corner_radii,
+                        border_widths,
+                        unrounded,
+                        is_near_rounded_corner,
+                    );
+                }
+                let blended_border = over(background, border_color);
+                color = mix(
+                    background,
+                    blended_border,
+                    saturate(ANTIALIAS_THRESHOLD - inner_sdf),
+                );
+            }
+            self.blend(x, y, color, saturate(ANTIALIAS_THRESHOLD - outer_sdf));
+        }
+    }
+
+    fn draw_shadow(&mut self, shadow: &Shadow) {
+        let blur_radius = shadow.blur_radius.0;
+        let shadow_bounds = to_f32_bounds(&shadow.bounds);
+        let corner_radii = to_f32_corners(&shadow.corner_radii);
+        let color = hsla_to_linear(shadow.color);
+        let margin = 3. * blur_radius;
+        let mut bounds = shadow_bounds;
+        bounds.origin.x -= margin;
+        bounds.origin.y -= margin;
+        bounds.size.width += 2. * margin;
+        bounds.size.height += 2. * margin;
+
+        let half_size = [
+            shadow_bounds.size.width / 2.,
+            shadow_bounds.size.height / 2.,
+        ];
+        let center = [
+            shadow_bounds.origin.x + half_size[0],
+            shadow_bounds.origin.y + half_size[1],
+        ];
+        let range = self.pixel_range(bounds, to_f32_bounds(&shadow.content_mask.bounds));
+        for (x, y) in range.pixels() {
+            let center_to_point = [x as f32 + 0.5 - center[0], y as f32 + 0.5 - center[1]];
+            let corner_radius = pick_corner_radius(center_to_point, corner_radii);
+
+            // A blur radius of zero degenerates into a plain rounded rectangle.
+            if blur_radius <= 0. {
+                let corner_to_point = [
+                    center_to_point[0].abs() - half_size[0],
+                    center_to_point[1].abs() - half_size[1],
+                ];
+                let distance =
+                    quad_sdf_impl(corner_to_point.map(|v| v + corner_radius), corner_radius);
+                self.blend(x, y, color, saturate(ANTIALIAS_THRESHOLD - distance));
+                continue;
+            }
+
+            let low = center_to_point[1] - half_size[1];
+            let high = center_to_point[1] + half_size[1];
+            let start = (-3. * blur_radius).clamp(low, high);
+            let end = (3. * blur_radius).clamp(low, high);
+            let step = (end - start) / 4.;
+            let mut sample_y = start + step * 0.5;
+            let mut alpha = 0.;
+            for _ in 0..4 {
+                let blur = blur_along_x(
+                    center_to_point[0],
+                    center_to_point[1] - sample_y,
+                    blur_radius,
+                    corner_radius,
+                    half_size,
+                );
+                alpha += blur * gaussian(sample_y, blur_radius) * step;
+                sample_y += step;
+            }
+            self.blend(x, y, color, alpha);
+        }
+    }
+
+    fn draw_path(&mut self, path: &Path<ScaledPixels>) {
+        let bounds = to_f32_bounds(&path.bounds);
+        let range = self.pixel_range(bounds, to_f32_bounds(&path.content_mask.bounds));
+        if range.x.is_empty() || range.y.is_empty() {
+            return;
+        }
+
+        // Accumulate coverage the same way the GPU does, so that overlapping
+        // triangles cancel out according to the even-odd rule.
+        let columns = range.x.len();
+        let mut coverage = vec![0f32; columns * range.y.len()];
+        for triangle in path.vertices.chunks_exact(3) {
+            let xy = [0, 1, 2].map(|ix| {
+                let position = &triangle[ix].xy_position;
+                [position.x.0, position.y.0]
+            });
+            let st = [0, 1, 2].map(|ix| {
+                let position = &triangle[ix].st_position;
+                [position.x, position.y]
+            });
+            let area = edge_function(xy[0], xy[1], xy[2]);
+            if area == 0. {
+                continue;
+            }
+
+            // Partial derivatives of the barycentric weights, which give the
+            // screen-space gradient of the s and t coordinates.
+            let weight_derivatives = [
+                [(xy[1][1] - xy[2][1]) / area, (xy[2][0] - xy[1][0]) / area],
+                [(xy[2][1] - xy[0][1]) / area, (xy[0][0] - xy[2][0]) / area],
+                [(xy[0][1] - xy[1][1]) / area, (xy[1][0] - xy[0][0]) / area],
+            ];
+            let derivative = |component: usize| {
+                [0, 1].map(|axis| {
+                    weight_derivatives
+                        .iter()
+                        .zip(&st)
+                        .map(|(weights, st)| weights[axis] * st[component])
+                        .sum::<f32>()
+                })
+            };
+            let ds = derivative(0);
+            let dt = derivative(1);
+
+            for (row, y) in range.y.clone().enumerate() {
+                for (column, x) in range.x.clone().enumerate() {
+                    let position = [x as f32 + 0.5, y as f32 + 0.5];
+                    let weights = [
+                        edge_function(xy[1], xy[2], position) / area,
+                        edge_function(xy[2], xy[0], position) / area,
+                        edge_function(xy[0], xy[1], position) / area,
+                    ];
+                    if weights.iter().any(|weight| *weight < 0.) {
+                        continue;
+                    }
+                    let s = weights[0] * st[0][0] + weights[1] * st[1][0] + weights[2] * st[2][0];
+                    let t = weights[0] * st[0][1] + weights[1] * st[1][1] + weights[2] * st[2][1];
+                    let gradient = [2. * s * ds[0] - dt[0], 2. * s * ds[1] - dt[1]];
+                    let f = s * s - t;
+                    let gradient_length = gradient[0].hypot(gradient[1]);
+                    let sample = if gradient_length == 0. {
+                        if f <= 0. { 1. } else { 0. }
+                    } else {
+                        saturate(ANTIALIAS_THRESHOLD - f / gradient_length)
+                    };
+                    coverage[row * columns + column] += sample;
+                }
+            }
+        }
+
+        let gradient = GradientColors::new(&path.color);
+        for (row, y) in range.y.clone().enumerate() {
+            for (column, x) in range.x.clone().enumerate() {
+                let sample = coverage[row * columns + column];
+                let mask = 1. - (1. - sample % 2.).abs();
+                if mask > 0. {
+                    let position = [x as f32 + 0.5, y as f32 + 0.5];
+                    let color = gradient.color_at(&path.color, position, bounds);
+                    self.blend(x, y, color, mask);
+                }
+            }
+        }
+    }
+
+    fn draw_underline(&mut self, underline: &Underline) {
+        let bounds = to_f32_bounds(&underline.bounds);
+        let color = hsla_to_linear(underline.color);
+        let thickness = underline.thickness.0;
+        let range = self.pixel_range(bounds, to_f32_bounds(&underline.content_mask.bounds));
+        for (x, y) in range.pixels() {
+            if !underline.wavy {
+                self.blend(x, y, color, 1.);
+                continue;
+            }
+
+            let half_thickness = thickness * 0.5;
+            let st = [
+                (x as f32 + 0.5 - bounds.origin.x) / bounds.size.height,
+                (y as f32 + 0.5 - bounds.origin.y) / bounds.size.height - 0.5,
+            ];
+            let frequency = PI * 3. * thickness / 3.;
+            let amplitude = 1. / (4. * thickness);
+            let sine = (st[0] * frequency).sin() * amplitude;
+            let d_sine = (st[0] * frequency).cos() * amplitude * frequency;
+            let distance = (st[1] - sine) / (1. + d_sine * d_sine).sqrt();
+            let distance_in_pixels = distance * bounds.size.height;
+            let distance_from_top_border = distance_in_pixels - half_thickness;
+            let distance_from_bottom_border = distance_in_pixels + half_thickness;
+            let alpha =
+                saturate(0.5 - (-distance_from_bottom_border).max(distance_from_top_border));
+            self.blend(x, y, color, alpha);
+        }
+    }
+
+    fn draw_monochrome_sprite(&mut self, sprite: &MonochromeSprite, atlas: &TestAtlas) {
+        let Some((tile_size, bytes)) = atlas.tile_data(&sprite.tile) else {
+            return;
+        };
+        let bounds = to_f32_bounds(&sprite.bounds);
+        let clip = to_f32_bounds(&sprite.content_mask.bounds);
+        let color = hsla_to_linear(sprite.color);
+        let [[a, b], [c, d]] = sprite.transformation.rotation_scale;
+        let [tx, ty] = sprite.transformation.translation;
+        let determinant = a * d - b * c;
+        if determinant == 0. {
+            return;
+        }
+
+        // Walk the pixels covered by the transformed sprite and map each of
+        // them back into the untransformed sprite bounds to sample the tile.
+        let corners = [
+            [bounds.origin.x, bounds.origin.y],
+            [bounds.origin.x + bounds.size.width, bounds.origin.y],
+            [bounds.origin.x, bounds.origin.y + bounds.size.height],
+            [
+                bounds.origin.x + bounds.size.width,
+                bounds.origin.y + bounds.size.height,
+            ],
+        ]
+        .map(|[x, y]| [a * x + b * y + tx, c * x + d * y + ty]);
+        let min_x = corners.iter().map(|p| p[0]).fold(f32::INFINITY, f32::min);
+        let max_x = corners
+            .iter()
+            .map(|p| p[0])
+            .fold(f32::NEG_INFINITY, f32::max);
+        let min_y = corners.iter().map(|p| p[1]).fold(f32::INFINITY, f32::min);
+        let max_y = corners
+            .iter()
+            .map(|p| p[1])
+            .fold(f32::NEG_INFINITY, f32::max);
+        let transformed_bounds = Bounds {
+            origin: crate::point(min_x, min_y),
+            size: crate::size(max_x - min_x, max_y - min_y),
+        };
+        let range = self.pixel_range(transformed_bounds, transformed_bounds);
+        for (x, y) in range.pixels() {
+            let qx = x as f32 + 0.5 - tx;
+            let qy = y as f32 + 0.5 - ty;
+            let position = [
+                (d * qx - b * qy) / determinant,
+                (a * qy - c * qx) / determinant,
+            ];
+            if !contains(&bounds, position) || !contains(&clip, position) {
+                continue;
+            }
+            let Some(texel) = sample_tile(&sprite.tile, tile_size, bounds, position) else {
+                continue;
+            };
+            let alpha = bytes.get(texel).copied().unwrap_or(0) as f32 / 255.;
+            self.blend(x, y, color, alpha);
+        }
+    }
+
+    fn draw_polychrome_sprite(&mut self, sprite: &PolychromeSprite, atlas: &TestAtlas) {
+        let Some((tile_size, bytes)) = atlas.tile_data(&sprite.tile) else {
+            return;
+        };
+        let bounds = to_f32_bounds(&sprite.bounds);
+        let corner_radii = to_f32_corners(&sprite.corner_radii);
+        let range = self.pixel_range(bounds, to_f32_bounds(&sprite.content_mask.bounds));
+        for (x, y) in range.pixels() {
+            let position = [x as f32 + 0.5, y as f32 + 0.5];
+            let Some(texel) = sample_tile(&sprite.tile, tile_size, bounds, position) else {
+                continue;
+            };
+            let Some(bgra) = bytes.get(texel * 4..texel * 4 + 4) else {
+                continue;
+            };
+            let mut color = [
+                srgb_to_linear(bgra[2] as f32 / 255.),
+                srgb_to_linear(bgra[1] as f32 / 255.),
+                srgb_to_linear(bgra[0] as f32 / 255.),
+                bgra[3] as f32 / 255.,
+            ];
+            if sprite.grayscale {
+                let grayscale = color[0] * GRAYSCALE_FACTORS[0]
+                    + color[1] * GRAYSCALE_FACTORS[1]
+                    + color[2] * GRAYSCALE_FACTORS[2];
+                color = [grayscale, grayscale, grayscale, color[3]];
+            }
+            let distance = quad_sdf(position, bounds, corner_radii);
+            self.blend(x, y, color, sprite.opacity * saturate(0.5 - distance));
+        }
+    }
+}
+
+/// The colors of a background, converted once per primitive rather than per pixel.
+struct GradientColors {
+    solid: Color,
+    color0: Color,
+    color1: Color,
+}
+
+impl GradientColors {
+    fn new(background: &Background) -> Self {
+        let mut colors = Self {
+            solid: [0.; 4],
+            color0: [0.; 4],
+            color1: [0.; 4],
+        };
+        match background.tag {
+            BackgroundTag::Solid | BackgroundTag::PatternSlash => {
+                colors.solid = hsla_to_linear(background.solid);
+            }
+            BackgroundTag::LinearGradient => {
+                let color0 = hsla_to_linear(background.colors[0].color);
+                let color1 = hsla_to_linear(background.colors[1].color);
+                match background.color_space {
+                    ColorSpace::Srgb => {
+                        colors.color0 = linear_to_srgba(color0);
+                        colors.color1 = linear_to_srgba(color1);
+                    }
+                    ColorSpace::Oklab => {
+                        colors.color0 = linear_srgb_to_oklab(color0);
+                        colors.color1 = linear_srgb_to_oklab(color1);
+                    }
+                }
+            }
+        }
+        colors
+    }
+
+    fn color_at(&self, background: &Background, position: [f32; 2], bounds: Bounds<f32>) -> Color {
+        match background.tag {
+            BackgroundTag::Solid => self.solid,
+            BackgroundTag::LinearGradient => {
+                // -90 degrees to match the CSS gradient angle.
+                let angle = background.gradient_angle_or_pattern_height;
+                let radians = (angle % 360. - 90.) * PI / 180.;
+                let mut direction = [radians.cos(), radians.sin()];
+                if bounds.size.width > bounds.size.height {
+                    direction[1] *= bounds.size.height / bounds.size.width;
+                } else {
+                    direction[0] *= bounds.size.width / bounds.size.height;
+                }
+
+                let half_size = [bounds.size.width / 2., bounds.size.height / 2.];
+                let center_to_point = [
+                    position[0] - bounds.origin.x - half_size[0],
+                    position[1] - bounds.origin.y - half_size[1],
+                ];
+                let mut t = (center_to_point[0] * direction[0] + center_to_point[1] * direction[1])
+                    / direction[0].hypot(direction[1]);
+                if direction[0].abs() > direction[1].abs() {
+                    t = (t + half_size[0]) / bounds.size.width;
+                } else {
+                    t = (t + half_size[1]) / bounds.size.height;
+                }
+
+                let stop0 = background.colors[0].percentage;
+                let stop1 = background.colors[1].percentage;
+                let t = saturate((t - stop0) / (stop1 - stop0));
+                let color = mix(self.color0, self.color1, t);
+                match background.color_space {
+                    ColorSpace::Srgb => srgba_to_linear(color),
+                    ColorSpace::Oklab => oklab_to_linear_srgb(color),
+                }
+            }
+            BackgroundTag::PatternSlash => {
+                let height = background.gradient_angle_or_pattern_height;
+                let pattern_width = (height / 65535.) / 255.;
+                let pattern_interval = (height % 65535.) / 255.;
+                let pattern_height = pattern_width + pattern_interval;
+                let stripe_angle = PI / 4.;
+                let pattern_period = pattern_height * stripe_angle.sin();
+                let relative_position =
+                    [position[0] - bounds.origin.x, position[1] - bounds.origin.y];
+                let rotated_x = stripe_angle.cos() * relative_position[0]
+                    + stripe_angle.sin() * relative_position[1];
+                let pattern = rotated_x % pattern_period;
+                let distance = pattern.min(pattern_period - pattern)
+                    - pattern_period * (pattern_width / pattern_height) / 2.;
+                let mut color = self.solid;
+                color[3] *= saturate(0.5 - distance);
+                color
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn dash_alpha_for_quad(
+    point: [f32; 2],
+    center_to_point: [f32; 2],
+    corner_center_to_point: [f32; 2],
+    corner_radius: f32,
+    border: [f32; 2],
+    size: Size<f32>,
+    corner_radii: [f32; 4],
+    border_widths: [f32; 4],
+    unrounded: bool,
+    is_near_rounded_corner: bool,
+) -> f32 {
+    // Dash pattern: (2 * border width) dash, (1 * border width) gap
+    let dash_length_per_width = 2.;
+    let dash_gap_per_width = 1.;
+    let dash_period_per_width = dash_length_per_width + dash_gap_per_width;
+    let dv_numerator = 1. / dash_period_per_width;
+
+    let t;
+    let mut max_t;
+    let dash_velocity;
+    if unrounded {
+        // Dashes are laid out separately on each straight side, so that each
+        // side starts and ends with a dash.
+        let is_horizontal = corner_center_to_point[0] < corner_center_to_point[1];
+        let border_width = if is_horizontal { border[1] } else { border[0] };
+        dash_velocity = dv_numerator / border_width;
+        t = if is_horizontal { point[0] } else { point[1] } * dash_velocity;
+        max_t = if is_horizontal {
+            size.width
+        } else {
+            size.height
+        } * dash_velocity;
+    } else {
+        // Dashes are laid out clockwise around the whole perimeter.
+        let [r_tl, r_tr, r_br, r_bl] = corner_radii;
+        let [w_t, w_r, w_b, w_l] = border_widths;
+        let side_velocity = |width: f32| {
+            if width <= 0. {
+                0.
+            } else {
+                dv_numerator / width
+            }
+        };
+        let dv_t = side_velocity(w_t);
+        let dv_r = side_velocity(w_r);
+        let dv_b = side_velocity(w_b);
+        let dv_l = side_velocity(w_l);
+
+        let s_t = (size.width - r_tl - r_tr) * dv_t;
+        let s_r = (size.height - r_tr - r_br) * dv_r;
+        let s_b = (size.width - r_br - r_bl) * dv_b;
+        let s_l = (size.height - r_bl - r_tl) * dv_l;
+
+        let corner_dv_tr = corner_dash_velocity(dv_t, dv_r);
+        let corner_dv_br = corner_dash_velocity(dv_b, dv_r);
+        let corner_dv_bl = corner_dash_velocity(dv_b, dv_l);
+        let corner_dv_tl = corner_dash_velocity(dv_t, dv_l);
+
+        let c_tr = r_tr * (PI / 2.) * corner_dv_tr;
+        let c_br = r_br * (PI / 2.) * corner_dv_br;
+        let c_bl = r_bl * (PI / 2.) * corner_dv_bl;
+        let c_tl = r_tl * (PI / 2.) * corner_dv_tl;
+
+        let upto_tr = s_t;
+        let upto_r = upto_tr + c_tr;
+        let upto_br = upto_r + s_r;
+        let upto_b = upto_br + c_br;
+        let upto_bl = upto_b + s_b;
+        let upto_l = upto_bl + c_bl;
+        let upto_tl = upto_l + s_l;
+        max_t = upto_tl + c_tl;
+
+        if is_near_rounded_corner {
+            let radians = corner_center_to_point[1].atan2(corner_center_to_point[0]);
+            let corner_t = radians * corner_radius;
+            if center_to_point[0] >= 0. {
+                if center_to_point[1] < 0. {
+                    dash_velocity = corner_dv_tr;
+                    t = upto_r - corner_t * dash_velocity;
+                } else {
+                    dash_velocity = corner_dv_br;
+                    t = upto_br + corner_t * dash_velocity;
+                }
+            } else if center_to_point[1] >= 0. {
+                dash_velocity = corner_dv_bl;
+                t = upto_l - corner_t * dash_velocity;
+            } else {
+                dash_velocity = corner_dv_tl;
+                t = upto_tl + corner_t * dash_velocity;
+            }
+        } else {
+            let is_horizontal = corner_center_to_point[0] < corner_center_to_point[1];
+            if is_horizontal {
+                if center_to_point[1] < 0. {
+                    dash_velocity = dv_t;
+                    t = (point[0] - r_tl) * dash_velocity;
+                } else {
+                    dash_velocity = dv_b;
+                    t = upto_bl - (point[0] - r_bl) * dash_velocity;
+                }
+            } else if center_to_point[0] < 0. {
+                dash_velocity = dv_l;
+                t = upto_tl - (point[1] - r_tl) * dash_velocity;
+            } else {
+                dash_velocity = dv_r;
+                t = upto_r + (point[1] - r_tr) * dash_velocity;
+            }
+        }
+    }
+
+    let dash_length = dash_length_per_width / dash_period_per_width;
+    if unrounded {
+        max_t -= dash_length;
+    }
+    if max_t >= 1. {
+        let dash_count = max_t.floor();
+        let dash_period = max_t / dash_count;
+        dash_alpha(t, dash_period, dash_length, dash_velocity)
+    } else if unrounded {
+        let dash_gap = max_t - dash_length;
+        if dash_gap > 0. {
+            dash_alpha(t, dash_length + dash_gap, dash_length, dash_velocity)
+        } else {
+            1.
+        }
+    } else {
+        1.
+    }
+}
+
+fn corner_dash_velocity(dv1: f32, dv2: f32) -> f32 {
+    if dv1 == 0. {
+        dv2
+    } else if dv2 == 0. {
+        dv1
+    } else {
+        dv1.min(dv2)
+    }
+}
+
+fn dash_alpha(t: f32, period: f32, length: f32, dash_velocity: f32) -> f32 {
+    let half_period = period / 2.;
+    let half_length = length / 2.;
+    let centered = (t + half_period - half_length) % period - half_period;
+    let signed_distance = centered.abs() - half_length;
+    saturate(ANTIALIAS_THRESHOLD - signed_distance / dash_velocity)
+}
+
+fn quarter_ellipse_sdf(point: [f32; 2], radii: [f32; 2]) -> f32 {
+    let circle = [point[0] / radii[0], point[1] / radii[1]];
+    let unit_circle_sdf = circle[0].hypot(circle[1]) - 1.;
+    unit_circle_sdf * (radii[0] + radii[1]) * -0.5
+}
+
+/// Corner radii in `[top_left, top_right, bottom_right, bottom_left]` order.
+fn to_f32_corners(corners: &Corners<ScaledPixels>) -> [f32; 4] {
+    [
+        corners.top_left.0,
+        corners.top_right.0,
+        corners.bottom_right.0,
+        corners.bottom_left.0,
+    ]
+}
+
+fn to_f32_bounds(bounds: &Bounds<ScaledPixels>) -> Bounds<f32> {
+    Bounds {
+        origin: crate::point(bounds.origin.x.0, bounds.origin.y.0),
+        size: crate::size(bounds.size.width.0, bounds.size.height.0),
+    }
+}
+
+fn contains(bounds: &Bounds<f32>, point: [f32; 2]) -> bool {
+    point[0] >= bounds.origin.x
+        && point[0] < bounds.origin.x + bounds.size.width
+        && point[1] >= bounds.origin.y
+        && point[1] < bounds.origin.y + bounds.size.height
+}
+
+/// Returns the index of the texel nearest to `position` within the sprite `bounds`.
+fn sample_tile(
+    tile: &AtlasTile,
+    tile_size: Size<DevicePixels>,
+    bounds: Bounds<f32>,
+    position: [f32; 2],
+) -> Option<usize> {
+    let width = tile.bounds.size.width.0;
+    let height = tile.bounds.size.height.0;
+    if width <= 0 || height <= 0 || bounds.size.width <= 0. || bounds.size.height <= 0. {
+        return None;
+    }
+    let u = (position[0] - bounds.origin.x) / bounds.size.width;
+    let v = (position[1] - bounds.origin.y) / bounds.size.height;
+    let column = ((u * width as f32) as i32).clamp(0, width - 1) + tile.bounds.origin.x.0;
+    let row = ((v * height as f32) as i32).clamp(0, height - 1) + tile.bounds.origin.y.0;
+    Some((row * tile_size.width.0 + column) as usize)
+}
+
+fn pick_corner_radius(center_to_point: [f32; 2], radii: [f32; 4]) -> f32 {
+    let [top_left, top_right, bottom_right, bottom_left] = radii;
+    if center_to_point[0] < 0. {
+        if center_to_point[1] < 0. {
+            top_left
+        } else {
+            bottom_left
+        }
+    } else if center_to_point[1] < 0. {
+        top_right
+    } else {
+        bottom_right
+    }
+}
+
+fn quad_sdf(point: [f32; 2], bounds: Bounds<f32>, corner_radii: [f32; 4]) -> f32 {
+    let half_size = [bounds.size.width / 2., bounds.size.height / 2.];
+    let center_to_point = [
+        point[0] - bounds.origin.x - half_size[0],
+        point[1] - bounds.origin.y - half_size[1],
+    ];
+    let corner_radius = pick_corner_radius(center_to_point, corner_radii);
+    let corner_center_to_point = [
+        center_to_point[0].abs() - half_size[0] + corner_radius,
+        center_to_point[1].abs() - half_size[1] + corner_radius,
+    ];
+    quad_sdf_impl(corner_center_to_point, corner_radius)
+}
+
+fn quad_sdf_impl(corner_center_to_point: [f32; 2], corner_radius: f32) -> f32 {
+    if corner_radius == 0. {
+        corner_center_to_point[0].max(corner_center_to_point[1])
+    } else {
+        let outside = corner_center_to_point[0]
+            .max(0.)
+            .hypot(corner_center_to_point[1].max(0.));
+        let inside = corner_center_to_point[0]
+            .max(corner_center_to_point[1])
+            .min(0.);
+        outside + inside - corner_radius
+    }
+}
+
+fn gaussian(x: f32, sigma: f32) -> f32 {
+    (-(x * x) / (2. * sigma * sigma)).exp() / ((2. * PI).sqrt() * sigma)
+}
+
+fn erf(value: f32) -> f32 {
+    let sign = value.signum();
+    let a = value.abs();
+    let r1 = 1. + (0.278393 + (0.230389 + (0.000972 + 0.078108 * a) * a) * a) * a;
+    let r2 = r1 * r1;
+    sign - sign / (r2 * r2)
+}
+
+fn blur_along_x(x: f32, y: f32, sigma: f32, corner: f32, half_size: [f32; 2]) -> f32 {
+    let delta = (half_size[1] - corner - y.abs()).min(0.);
+    let curved = half_size[0] - corner + (corner * corner - delta * delta).max(0.).sqrt();
+    let scale = 0.5f32.sqrt() / sigma;
+    let low = 0.5 + 0.5 * erf((x - curved) * scale);
+    let high = 0.5 + 0.5 * erf((x + curved) * scale);
+    high - low
+}
+
+fn edge_function(a: [f32; 2], b: [f32; 2], point: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (point[1] - a[1]) - (b[1] - a[1]) * (point[0] - a[0])
+}
+
+fn saturate(value: f32) -> f32 {
+    value.clamp(0., 1.)
+}
+
+fn mix(a: Color, b: Color, t: f32) -> Color {
+    [0, 1, 2, 3].map(|ix| a[ix] + (b[ix] - a[ix]) * t)
+}
+
+fn over(below: Color, above: Color) -> Color {
+    let alpha = above[3] + below[3] * (1. - above[3]);
+    if alpha == 0. {
+        return [0.; 4];
+    }
+    let channel =
+        |ix: usize| (above[ix] * above[3] + below[ix] * below[3] * (1. - above[3])) / alpha;
+    [channel(0), channel(1), channel(2), alpha]
+}
+
+fn to_u8(value: f32) -> u8 {
+    (value.clamp(0., 1.) * 255.).round() as u8
+}
+
+fn hsla_to_linear(hsla: Hsla) -> Color {
+    let rgba = Rgba::from(hsla);
+    [
+        srgb_to_linear(rgba.r),
+        srgb_to_linear(rgba.g),
+        srgb_to_linear(rgba.b),
+        rgba.a,
+    ]
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value < 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value < 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1. / 2.4) - 0.055
+    }
+}
+
+fn linear_to_srgba(color: Color) -> Color {
+    [
+        linear_to_srgb(color[0]),
+        linear_to_srgb(color[1]),
+        linear_to_srgb(color[2]),
+        color[3],
+    ]
+}
+
+fn srgba_to_linear(color: Color) -> Color {
+    [
+        srgb_to_linear(color[0]),
+        srgb_to_linear(color[1]),
+        srgb_to_linear(color[2]),
+        color[3],
+    ]
+}
+
+fn linear_srgb_to_oklab(color: Color) -> Color {
+    let l = 0.4122214708 * color[0] + 0.5363325363 * color[1] + 0.0514459929 * color[2];
+    let m = 0.2119034982 * color[0] + 0.6806995451 * color[1] + 0.1073969566 * color[2];
+    let s = 0.0883024619 * color[0] + 0.2817188376 * color[1] + 0.6299787005 * color[2];
+
+    let l = l.cbrt();
+    let m = m.cbrt();
+    let s = s.cbrt();
+
+    [
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        color[3],
+    ]
+}
+
+fn oklab_to_linear_srgb(color: Color) -> Color {
+    let l = color[0] + 0.3963377774 * color[1] + 0.2158037573 * color[2];
+    let m = color[0] - 0.1055613458 * color[1] - 0.0638541728 * color[2];
+    let s = color[0] - 0.0894841775 * color[1] - 1.2914855480 * color[2];
+
+    let l = l * l * l;
+    let m = m * m * m;
+    let s = s * s * s;
+
+    [
+        4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+        -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+        -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+        color[3],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as gpui, Context, IntoElement, ParentElement, Render, ScreenshotTolerance, Styled,
+        TestAppContext, Window, black, compare_screenshots, div, px, red, rgb, size, white,
+    };
+    use util::test::TempTree;
+
+    struct Swatch {
+        square_color: gpui::Hsla,
+        label: Option<&'static str>,
+    }
+
+    impl Render for Swatch {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            div()
+                .size_full()
+                .bg(white())
+                .text_color(black())
+                .children(self.label)
+                .child(
+                    div()
+                        .absolute()
+                        .top(px(10.))
+                        .left(px(10.))
+                        .size(px(10.))
+                        .bg(self.square_color),
+                )
+        }
+    }
+
+    #[gpui::test]
+    fn test_capture_window_renders_quads(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_, _| Swatch {
+            square_color: red(),
+            label: None,
+        });
+        cx.simulate_resize(size(px(40.), px(30.)));
+
+        // The test window has a scale factor of 2.
+        let screenshot = cx.capture_screenshot();
+        assert_eq!(screenshot.dimensions(), (80, 60));
+        assert_eq!(screenshot.get_pixel(5, 5).0, [0xFF, 0xFF, 0xFF, 0xFF]);
+        assert_eq!(screenshot.get_pixel(20, 20).0, [0xFF, 0, 0, 0xFF]);
+        assert_eq!(screenshot.get_pixel(39, 39).0, [0xFF, 0, 0, 0xFF]);
+        assert_eq!(screenshot.get_pixel(40, 40).0, [0xFF, 0xFF, 0xFF, 0xFF]);
+    }
+
+    #[gpui::test]
+    fn test_capture_window_renders_text(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_, _| Swatch {
+            square_color: white(),
+            label: Some("gpui"),
+        });
+        cx.simulate_resize(size(px(100.), px(40.)));
+
+        let screenshot = cx.capture_screenshot();
+        assert!(
+            screenshot.pixels().any(|pixel| pixel.0 == [0, 0, 0, 0xFF]),
+            "expected the label's glyphs to be painted"
+        );
+    }
+
+    #[gpui::test]
+    fn test_screenshot_golden_comparison(cx: &mut TestAppContext) {
+        let goldens = TempTree::new(serde_json::json!({}));
+        let golden_path = goldens.path().join("square.png");
+
+        let (view, cx) = cx.add_window_view(|_, _| Swatch {
+            square_color: red(),
+            label: None,
+        });
+        cx.simulate_resize(size(px(40.), px(30.)));
+        let golden = cx.capture_screenshot();
+        golden.save(&golden_path).unwrap();
+        cx.assert_screenshot(&golden_path, ScreenshotTolerance::exact());
+
+        view.update(cx, |view, cx| {
+            view.square_color = rgb(0xfe0000).into();
+            cx.notify();
+        });
+        let slightly_off = cx.capture_screenshot();
+        assert!(compare_screenshots(&golden, &slightly_off, ScreenshotTolerance::exact()).is_err());
+        assert!(
+            compare_screenshots(&golden, &slightly_off, ScreenshotTolerance::default()).is_ok()
+        );
+
+        view.update(cx, |view, cx| {
+            view.square_color = black();
+            cx.notify();
+        });
+        let different = cx.capture_screenshot();
+        assert!(compare_screenshots(&golden, &different, ScreenshotTolerance::default()).is_err());
+        assert!(
+            compare_screenshots(
+                &golden,
+                &different,
+                ScreenshotTolerance {
+                    max_channel_difference: 0,
+                    max_differing_pixels: 0.1,
+                }
+            )
+            .is_ok()
+        );
+    }
+}
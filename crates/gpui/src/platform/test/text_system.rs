@@ -0,0 +1,85 @@
+use std::borrow::Cow;
+
+use anyhow::Result;
+
+use crate::{
+    Bounds, DevicePixels, Font, FontId, FontMetrics, FontRun, GlyphId, LineLayout, NoopTextSystem,
+    Pixels, PlatformTextSystem, RenderGlyphParams, Size, point, size,
+};
+
+/// Lays out text like [`NoopTextSystem`], but rasterizes glyphs as solid boxes matching their
+/// typographic bounds, which keeps text visible (and deterministic) in screenshots of test
+/// windows.
+pub(crate) struct TestTextSystem(NoopTextSystem);
+
+impl TestTextSystem {
+    pub fn new() -> Self {
+        Self(NoopTextSystem::new())
+    }
+}
+
+impl PlatformTextSystem for TestTextSystem {
+    fn add_fonts(&self, fonts: Vec<Cow<'static, [u8]>>) -> Result<()> {
+        self.0.add_fonts(fonts)
+    }
+
+    fn all_font_names(&self) -> Vec<String> {
+        self.0.all_font_names()
+    }
+
+    fn font_id(&self, descriptor: &Font) -> Result<FontId> {
+        self.0.font_id(descriptor)
+    }
+
+    fn font_metrics(&self, font_id: FontId) -> FontMetrics {
+        self.0.font_metrics(font_id)
+    }
+
+    fn typographic_bounds(&self, font_id: FontId, glyph_id: GlyphId) -> Result<Bounds<f32>> {
+        self.0.typographic_bounds(font_id, glyph_id)
+    }
+
+    fn advance(&self, font_id: FontId, glyph_id: GlyphId) -> Result<Size<f32>> {
+        self.0.advance(font_id, glyph_id)
+    }
+
+    fn glyph_for_char(&self, font_id: FontId, ch: char) -> Option<GlyphId> {
+        self.0.glyph_for_char(font_id, ch)
+    }
+
+    fn glyph_raster_bounds(&self, params: &RenderGlyphParams) -> Result<Bounds<DevicePixels>> {
+        let glyph_bounds = self.typographic_bounds(params.font_id, params.glyph_id)?;
+        let scale = params.font_size.0 * params.scale_factor
+            / self.font_metrics(params.font_id).units_per_em as f32;
+        let device_pixels = |units: f32| DevicePixels((units * scale).round() as i32);
+        Ok(Bounds {
+            origin: point(
+                device_pixels(glyph_bounds.origin.x),
+                device_pixels(-(glyph_bounds.origin.y + glyph_bounds.size.height)),
+            ),
+            size: size(
+                device_pixels(glyph_bounds.size.width),
+                device_pixels(glyph_bounds.size.height),
+            ),
+        })
+    }
+
+    fn rasterize_glyph(
+        &self,
+        params: &RenderGlyphParams,
+        raster_bounds: Bounds<DevicePixels>,
+    ) -> Result<(Size<DevicePixels>, Vec<u8>)> {
+        let pixel_count =
+            (raster_bounds.size.width.0.max(0) * raster_bounds.size.height.0.max(0)) as usize;
+        let bytes = if params.is_emoji {
+            [0x80, 0x80, 0x80, 0xFF].repeat(pixel_count)
+        } else {
+            vec![0xFF; pixel_count]
+        };
+        Ok((raster_bounds.size, bytes))
+    }
+
+    fn layout_line(&self, text: &str, font_size: Pixels, runs: &[FontRun]) -> LineLayout {
+        self.0.layout_line(text, font_size, runs)
+    }
+}
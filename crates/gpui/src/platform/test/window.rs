@@ -1,10 +1,13 @@
+use super::rasterizer::rasterize_scene;
 use crate::{
-    AnyWindowHandle, AtlasKey, AtlasTextureId, AtlasTile, Bounds, DispatchEventResult, GpuSpecs,
-    Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow,
-    Point, PromptButton, RequestFrameOptions, ScaledPixels, Size, TestPlatform, TileId,
-    WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowControlArea, WindowParams,
+    AnyWindowHandle, AtlasKey, AtlasTextureId, AtlasTile, Bounds, DevicePixels,
+    DispatchEventResult, GpuSpecs, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput,
+    PlatformInputHandler, PlatformWindow, Point, PromptButton, RequestFrameOptions, ScaledPixels,
+    Scene, Size, TestPlatform, TileId, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
+    WindowControlArea, WindowParams,
 };
 use collections::HashMap;
+use image::RgbaImage;
 use parking_lot::Mutex;
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use std::{
@@ -19,7 +22,7 @@ pub(crate) struct TestWindowState {
     pub(crate) title: Option<String>,
     pub(crate) edited: bool,
     platform: Weak<TestPlatform>,
-    sprite_atlas: Arc<dyn PlatformAtlas>,
+    sprite_atlas: Arc<TestAtlas>,
     pub(crate) should_close_handler: Option<Box<dyn FnMut() -> bool>>,
     hit_test_window_control_callback: Option<Box<dyn FnMut() -> Option<WindowControlArea>>>,
     input_callback: Option<Box<dyn FnMut(PlatformInput) -> DispatchEventResult>>,
@@ -29,6 +32,7 @@ pub(crate) struct TestWindowState {
     moved_callback: Option<Box<dyn FnMut()>>,
     input_handler: Option<PlatformInputHandler>,
     is_fullscreen: bool,
+    pub(crate) accessibility_enabled: bool,
 }
 
 #[derive(Clone)]
@@ -74,6 +78,7 @@ impl TestWindow {
             moved_callback: None,
             input_handler: None,
             is_fullscreen: false,
+            accessibility_enabled: false,
         })))
     }
 
@@ -109,6 +114,20 @@ impl TestWindow {
         self.0.lock().input_callback = Some(callback);
         !result.propagate
     }
+
+    /// Renders the given scene on the CPU at this window's size and scale factor.
+    pub(crate) fn rasterize(&self, scene: &Scene) -> RgbaImage {
+        let scale_factor = self.scale_factor();
+        let (size, atlas) = {
+            let lock = self.0.lock();
+            (lock.bounds.size, lock.sprite_atlas.clone())
+        };
+        let device_size = Size {
+            width: DevicePixels((size.width.0 * scale_factor).round() as i32),
+            height: DevicePixels((size.height.0 * scale_factor).round() as i32),
+        };
+        rasterize_scene(scene, &atlas, device_size)
+    }
 }
 
 impl PlatformWindow for TestWindow {
@@ -294,11 +313,18 @@ impl PlatformWindow for TestWindow {
     fn gpu_specs(&self) -> Option<GpuSpecs> {
         None
     }
+
+    fn accessibility_enabled(&self) -> bool {
+        self.0.lock().accessibility_enabled
+    }
 }
 
 pub(crate) struct TestAtlasState {
     next_id: u32,
     tiles: HashMap<AtlasKey, AtlasTile>,
+    /// The rasterized contents of each tile, kept around so that scenes can
+    /// be rendered on the CPU.
+    tile_data: HashMap<TileId, (Size<DevicePixels>, Arc<[u8]>)>,
 }
 
 pub(crate) struct TestAtlas(Mutex<TestAtlasState>);
@@ -308,8 +334,14 @@ impl TestAtlas {
         TestAtlas(Mutex::new(TestAtlasState {
             next_id: 0,
             tiles: HashMap::default(),
+            tile_data: HashMap::default(),
         }))
     }
+
+    /// Returns the size and bytes that were uploaded for the given tile.
+    pub(crate) fn tile_data(&self, tile: &AtlasTile) -> Option<(Size<DevicePixels>, Arc<[u8]>)> {
+        self.0.lock().tile_data.get(&tile.tile_id).cloned()
+    }
 }
 
 impl PlatformAtlas for TestAtlas {
@@ -326,7 +358,7 @@ impl PlatformAtlas for TestAtlas {
         }
         drop(state);
 
-        let Some((size, _)) = build()? else {
+        let Some((size, bytes)) = build()? else {
             return Ok(None);
         };
 
@@ -341,7 +373,7 @@ impl PlatformAtlas for TestAtlas {
             crate::AtlasTile {
                 texture_id: AtlasTextureId {
                     index: texture_id,
-                    kind: crate::AtlasTextureKind::Path,
+                    kind: key.texture_kind(),
                 },
                 tile_id: TileId(tile_id),
                 padding: 0,
@@ -351,12 +383,17 @@ impl PlatformAtlas for TestAtlas {
                 },
             },
         );
+        state
+            .tile_data
+            .insert(TileId(tile_id), (size, Arc::from(bytes.into_owned())));
 
         Ok(Some(state.tiles[key].clone()))
     }
 
     fn remove(&self, key: &AtlasKey) {
         let mut state = self.0.lock();
-        state.tiles.remove(key);
+        if let Some(tile) = state.tiles.remove(key) {
+            state.tile_data.remove(&tile.tile_id);
+        }
     }
 }
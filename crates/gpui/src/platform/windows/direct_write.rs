@@ -615,6 +615,7 @@ impl DirectWriteState {
                 descent,
                 runs,
                 len: text.len(),
+                direction_runs: Vec::new(),
             })
         }
     }
@@ -8,8 +8,8 @@ use crate::{
     AbsoluteLength, App, Background, BackgroundTag, BorderStyle, Bounds, ContentMask, Corners,
     CornersRefinement, CursorStyle, DefiniteLength, DevicePixels, Edges, EdgesRefinement, Font,
     FontFallbacks, FontFeatures, FontStyle, FontWeight, Hsla, Length, Pixels, Point,
-    PointRefinement, Rgba, SharedString, Size, SizeRefinement, Styled, TextRun, Window, black, phi,
-    point, quad, rems, size,
+    PointRefinement, Rems, Rgba, SharedString, Size, SizeRefinement, Styled, TextRun, Window,
+    black, phi, point, quad, rems, size,
 };
 use collections::HashSet;
 use refineable::Refineable;
@@ -219,7 +219,7 @@ pub struct Style {
     pub align_content: Option<AlignContent>,
     /// How should contained within this item be aligned in the main/inline axis
     pub justify_content: Option<JustifyContent>,
-    /// How large should the gaps between items in a flex container be?
+    /// How large should the gaps between items in a flex or grid container be?
     #[refineable]
     pub gap: Size<DefiniteLength>,
 
@@ -235,6 +235,22 @@ pub struct Style {
     /// The relative rate at which this item shrinks when it is contracting to fit into space, 1.0 is the default value, and this value must be positive.
     pub flex_shrink: f32,
 
+    // Grid properties
+    /// The sizes of the grid's explicit columns
+    pub grid_template_columns: Vec<GridTemplateTrack>,
+    /// The sizes of the grid's explicit rows
+    pub grid_template_rows: Vec<GridTemplateTrack>,
+    /// The sizes of columns created implicitly to fit items placed outside of the explicit grid
+    pub grid_auto_columns: Vec<TrackSize>,
+    /// The sizes of rows created implicitly to fit items placed outside of the explicit grid
+    pub grid_auto_rows: Vec<TrackSize>,
+    /// How items that aren't explicitly placed are flowed into the grid
+    pub grid_auto_flow: GridAutoFlow,
+    /// Which columns of its parent grid this item occupies
+    pub grid_column: GridLines,
+    /// Which rows of its parent grid this item occupies
+    pub grid_row: GridLines,
+
     /// The fill color of this element
     pub background: Option<Fill>,
 
@@ -749,6 +765,14 @@ impl Default for Style {
             flex_grow: 0.0,
             flex_shrink: 1.0,
             flex_basis: Length::Auto,
+            // Grid
+            grid_template_columns: Vec::new(),
+            grid_template_rows: Vec::new(),
+            grid_auto_columns: Vec::new(),
+            grid_auto_rows: Vec::new(),
+            grid_auto_flow: GridAutoFlow::Row,
+            grid_column: GridLines::default(),
+            grid_row: GridLines::default(),
             background: None,
             border_color: None,
             border_style: BorderStyle::default(),
@@ -1145,6 +1169,301 @@ pub enum FlexDirection {
     ColumnReverse,
 }
 
+/// The size of a single grid track, similar to a `minmax()` value in CSS
+/// `grid-template-columns` and `grid-template-rows`.
+///
+/// Usually constructed with [`fr`], [`minmax`], or from a length such as `px(120.)` or `auto()`.
+///
+/// [Specification](https://www.w3.org/TR/css-grid-1/#track-sizing)
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TrackSize {
+    /// The smallest size this track can shrink to
+    pub min: MinTrackSize,
+    /// The largest size this track can grow to
+    pub max: MaxTrackSize,
+}
+
+impl TrackSize {
+    /// A track sized to the largest minimum content contribution of its items.
+    pub const fn min_content() -> Self {
+        Self {
+            min: MinTrackSize::MinContent,
+            max: MaxTrackSize::MinContent,
+        }
+    }
+
+    /// A track sized to the largest maximum content contribution of its items.
+    pub const fn max_content() -> Self {
+        Self {
+            min: MinTrackSize::MaxContent,
+            max: MaxTrackSize::MaxContent,
+        }
+    }
+
+    /// A track sized to its content, but no larger than `limit`.
+    pub fn fit_content(limit: impl Into<DefiniteLength>) -> Self {
+        Self {
+            min: MinTrackSize::Auto,
+            max: MaxTrackSize::FitContent(limit.into()),
+        }
+    }
+}
+
+impl From<Length> for TrackSize {
+    fn from(length: Length) -> Self {
+        Self {
+            min: length.into(),
+            max: length.into(),
+        }
+    }
+}
+
+impl From<DefiniteLength> for TrackSize {
+    fn from(length: DefiniteLength) -> Self {
+        Length::Definite(length).into()
+    }
+}
+
+impl From<Pixels> for TrackSize {
+    fn from(pixels: Pixels) -> Self {
+        Length::from(pixels).into()
+    }
+}
+
+impl From<Rems> for TrackSize {
+    fn from(rems: Rems) -> Self {
+        Length::from(rems).into()
+    }
+}
+
+/// The lower bound of a [`TrackSize`].
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+// Copy of taffy::style type of the same name, to derive JsonSchema.
+pub enum MinTrackSize {
+    /// The track can't shrink below the given length
+    Fixed(DefiniteLength),
+    /// The track can't shrink below the largest minimum content contribution of its items
+    MinContent,
+    /// The track can't shrink below the largest maximum content contribution of its items
+    MaxContent,
+    /// The track can't shrink below the largest minimum size of its items
+    Auto,
+}
+
+impl From<Length> for MinTrackSize {
+    fn from(length: Length) -> Self {
+        match length {
+            Length::Definite(length) => Self::Fixed(length),
+            Length::Auto => Self::Auto,
+        }
+    }
+}
+
+impl From<DefiniteLength> for MinTrackSize {
+    fn from(length: DefiniteLength) -> Self {
+        Self::Fixed(length)
+    }
+}
+
+impl From<Pixels> for MinTrackSize {
+    fn from(pixels: Pixels) -> Self {
+        Self::Fixed(pixels.into())
+    }
+}
+
+impl From<Rems> for MinTrackSize {
+    fn from(rems: Rems) -> Self {
+        Self::Fixed(rems.into())
+    }
+}
+
+impl From<TrackSize> for MinTrackSize {
+    fn from(track: TrackSize) -> Self {
+        track.min
+    }
+}
+
+/// The upper bound of a [`TrackSize`].
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+// Copy of taffy::style type of the same name, to derive JsonSchema.
+pub enum MaxTrackSize {
+    /// The track can't grow beyond the given length
+    Fixed(DefiniteLength),
+    /// The track can't grow beyond the largest minimum content contribution of its items
+    MinContent,
+    /// The track can't grow beyond the largest maximum content contribution of its items
+    MaxContent,
+    /// The track grows to fit its content, but no larger than the given length
+    FitContent(DefiniteLength),
+    /// The track can grow to the largest maximum content contribution of its items,
+    /// and may be stretched further by `align-content`/`justify-content`
+    Auto,
+    /// The track takes this share of the space left over once all other tracks are sized
+    Fraction(f32),
+}
+
+impl From<Length> for MaxTrackSize {
+    fn from(length: Length) -> Self {
+        match length {
+            Length::Definite(length) => Self::Fixed(length),
+            Length::Auto => Self::Auto,
+        }
+    }
+}
+
+impl From<DefiniteLength> for MaxTrackSize {
+    fn from(length: DefiniteLength) -> Self {
+        Self::Fixed(length)
+    }
+}
+
+impl From<Pixels> for MaxTrackSize {
+    fn from(pixels: Pixels) -> Self {
+        Self::Fixed(pixels.into())
+    }
+}
+
+impl From<Rems> for MaxTrackSize {
+    fn from(rems: Rems) -> Self {
+        Self::Fixed(rems.into())
+    }
+}
+
+impl From<TrackSize> for MaxTrackSize {
+    fn from(track: TrackSize) -> Self {
+        track.max
+    }
+}
+
+/// A flexible track taking `fraction` shares of the grid's free space, like `1fr` in CSS.
+pub fn fr(fraction: f32) -> TrackSize {
+    TrackSize {
+        min: MinTrackSize::Auto,
+        max: MaxTrackSize::Fraction(fraction),
+    }
+}
+
+/// A track no smaller than `min` and no larger than `max`, like `minmax()` in CSS.
+pub fn minmax(min: impl Into<MinTrackSize>, max: impl Into<MaxTrackSize>) -> TrackSize {
+    TrackSize {
+        min: min.into(),
+        max: max.into(),
+    }
+}
+
+/// Repeats the given tracks, like `repeat()` in CSS.
+pub fn repeat(
+    count: impl Into<GridRepetition>,
+    tracks: impl IntoIterator<Item = impl Into<TrackSize>>,
+) -> GridTemplateTrack {
+    GridTemplateTrack::Repeat(count.into(), tracks.into_iter().map(Into::into).collect())
+}
+
+/// An entry in [`Style::grid_template_columns`] or [`Style::grid_template_rows`].
+///
+/// [Specification](https://www.w3.org/TR/css-grid-1/#track-sizing)
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+// Copy of taffy::style::TrackSizingFunction, to derive JsonSchema.
+pub enum GridTemplateTrack {
+    /// A single track
+    Single(TrackSize),
+    /// A list of tracks repeated a number of times
+    Repeat(GridRepetition, Vec<TrackSize>),
+}
+
+impl From<TrackSize> for GridTemplateTrack {
+    fn from(track: TrackSize) -> Self {
+        Self::Single(track)
+    }
+}
+
+impl From<Length> for GridTemplateTrack {
+    fn from(length: Length) -> Self {
+        Self::Single(length.into())
+    }
+}
+
+impl From<DefiniteLength> for GridTemplateTrack {
+    fn from(length: DefiniteLength) -> Self {
+        Self::Single(length.into())
+    }
+}
+
+impl From<Pixels> for GridTemplateTrack {
+    fn from(pixels: Pixels) -> Self {
+        Self::Single(pixels.into())
+    }
+}
+
+impl From<Rems> for GridTemplateTrack {
+    fn from(rems: Rems) -> Self {
+        Self::Single(rems.into())
+    }
+}
+
+/// How many times the tracks of a [`GridTemplateTrack::Repeat`] are repeated.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema)]
+// Copy of taffy::style::GridTrackRepetition, to derive JsonSchema.
+pub enum GridRepetition {
+    /// Repeat as many times as fits in the container, like `auto-fill` in CSS
+    AutoFill,
+    /// Repeat as many times as fits in the container, collapsing empty tracks, like `auto-fit` in CSS
+    AutoFit,
+    /// Repeat the given number of times
+    Count(u16),
+}
+
+impl From<u16> for GridRepetition {
+    fn from(count: u16) -> Self {
+        Self::Count(count)
+    }
+}
+
+/// Controls how items that aren't explicitly placed are flowed into the grid.
+///
+/// Defaults to [`GridAutoFlow::Row`]
+///
+/// [Specification](https://www.w3.org/TR/css-grid-1/#grid-auto-flow-property)
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize, JsonSchema)]
+// Copy of taffy::style type of the same name, to derive JsonSchema.
+pub enum GridAutoFlow {
+    /// Items are placed by filling each row in turn, adding new rows as necessary
+    #[default]
+    Row,
+    /// Items are placed by filling each column in turn, adding new columns as necessary
+    Column,
+    /// Like [`GridAutoFlow::Row`], but backfilling holes left by earlier items
+    RowDense,
+    /// Like [`GridAutoFlow::Column`], but backfilling holes left by earlier items
+    ColumnDense,
+}
+
+/// Where an item starts or ends along one axis of the grid.
+///
+/// [Specification](https://www.w3.org/TR/css-grid-1/#line-placement)
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize, JsonSchema)]
+// Copy of taffy::style type of the same name, to derive JsonSchema.
+pub enum GridPlacement {
+    /// Placed automatically by the grid's [`GridAutoFlow`]
+    #[default]
+    Auto,
+    /// Placed at the given line. Lines are 1-based, and negative values count back from the
+    /// last line of the explicit grid.
+    Line(i16),
+    /// Spans the given number of tracks
+    Span(u16),
+}
+
+/// The lines an item is placed between along one axis of the grid, like `grid-column` or
+/// `grid-row` in CSS.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct GridLines {
+    /// The line the item starts at
+    pub start: GridPlacement,
+    /// The line the item ends at
+    pub end: GridPlacement,
+}
+
 /// How children overflowing their container should affect layout
 ///
 /// In CSS the primary effect of this property is to control whether contents of a parent container that overflow that container should
@@ -1263,6 +1582,46 @@ impl From<FlexDirection> for taffy::style::FlexDirection {
     }
 }
 
+impl From<GridAutoFlow> for taffy::style::GridAutoFlow {
+    fn from(value: GridAutoFlow) -> Self {
+        match value {
+            GridAutoFlow::Row => Self::Row,
+            GridAutoFlow::Column => Self::Column,
+            GridAutoFlow::RowDense => Self::RowDense,
+            GridAutoFlow::ColumnDense => Self::ColumnDense,
+        }
+    }
+}
+
+impl From<GridPlacement> for taffy::style::GridPlacement {
+    fn from(value: GridPlacement) -> Self {
+        match value {
+            GridPlacement::Auto => Self::Auto,
+            GridPlacement::Line(line) => Self::Line(line.into()),
+            GridPlacement::Span(span) => Self::Span(span),
+        }
+    }
+}
+
+impl From<GridLines> for taffy::geometry::Line<taffy::style::GridPlacement> {
+    fn from(value: GridLines) -> Self {
+        Self {
+            start: value.start.into(),
+            end: value.end.into(),
+        }
+    }
+}
+
+impl From<GridRepetition> for taffy::style::GridTrackRepetition {
+    fn from(value: GridRepetition) -> Self {
+        match value {
+            GridRepetition::AutoFill => Self::AutoFill,
+            GridRepetition::AutoFit => Self::AutoFit,
+            GridRepetition::Count(count) => Self::Count(count),
+        }
+    }
+}
+
 impl From<Overflow> for taffy::style::Overflow {
     fn from(value: Overflow) -> Self {
         match value {
@@ -1,8 +1,10 @@
 use crate::{
     self as gpui, AbsoluteLength, AlignContent, AlignItems, BorderStyle, CursorStyle,
-    DefiniteLength, Display, Fill, FlexDirection, FlexWrap, Font, FontStyle, FontWeight, Hsla,
-    JustifyContent, Length, SharedString, StrikethroughStyle, StyleRefinement, TextAlign,
-    TextOverflow, TextStyleRefinement, UnderlineStyle, WhiteSpace, px, relative, rems,
+    DefiniteLength, Display, Fill, FlexDirection, FlexWrap, Font, FontStyle, FontWeight,
+    GridAutoFlow, GridLines, GridPlacement, GridTemplateTrack, Hsla, JustifyContent, Length,
+    SharedString, StrikethroughStyle, StyleRefinement, TextAlign, TextOverflow,
+    TextStyleRefinement, TrackSize, UnderlineStyle, WhiteSpace, fr, minmax, px, relative, rems,
+    repeat,
 };
 pub use gpui_macros::{
     border_style_methods, box_shadow_style_methods, cursor_style_methods, margin_style_methods,
@@ -46,6 +48,13 @@ pub trait Styled: Sized {
         self
     }
 
+    /// Sets the display type of the element to `grid`.
+    /// [Docs](https://tailwindcss.com/docs/display)
+    fn grid(mut self) -> Self {
+        self.style().display = Some(Display::Grid);
+        self
+    }
+
     /// Sets the whitespace of the element to `normal`.
     /// [Docs](https://tailwindcss.com/docs/whitespace#normal)
     fn whitespace_normal(mut self) -> Self {
@@ -354,6 +363,144 @@ pub trait Styled: Sized {
         self
     }
 
+    /// Sets the grid to have the given number of equally sized columns.
+    /// [Docs](https://tailwindcss.com/docs/grid-template-columns)
+    fn grid_cols(mut self, columns: u16) -> Self {
+        self.style().grid_template_columns = Some(vec![repeat(columns, [minmax(px(0.), fr(1.))])]);
+        self
+    }
+
+    /// Sets the grid to have the given number of equally sized rows.
+    /// [Docs](https://tailwindcss.com/docs/grid-template-rows)
+    fn grid_rows(mut self, rows: u16) -> Self {
+        self.style().grid_template_rows = Some(vec![repeat(rows, [minmax(px(0.), fr(1.))])]);
+        self
+    }
+
+    /// Sets the sizes of the grid's columns, e.g. `[px(120.).into(), fr(1.)]`.
+    /// [Docs](https://developer.mozilla.org/en-US/docs/Web/CSS/grid-template-columns)
+    fn grid_template_columns(
+        mut self,
+        columns: impl IntoIterator<Item = impl Into<GridTemplateTrack>>,
+    ) -> Self {
+        self.style().grid_template_columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the sizes of the grid's rows.
+    /// [Docs](https://developer.mozilla.org/en-US/docs/Web/CSS/grid-template-rows)
+    fn grid_template_rows(
+        mut self,
+        rows: impl IntoIterator<Item = impl Into<GridTemplateTrack>>,
+    ) -> Self {
+        self.style().grid_template_rows = Some(rows.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the sizes of implicitly created grid columns.
+    /// [Docs](https://tailwindcss.com/docs/grid-auto-columns)
+    fn grid_auto_columns(
+        mut self,
+        columns: impl IntoIterator<Item = impl Into<TrackSize>>,
+    ) -> Self {
+        self.style().grid_auto_columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the sizes of implicitly created grid rows.
+    /// [Docs](https://tailwindcss.com/docs/grid-auto-rows)
+    fn grid_auto_rows(mut self, rows: impl IntoIterator<Item = impl Into<TrackSize>>) -> Self {
+        self.style().grid_auto_rows = Some(rows.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the grid to place items by filling each column in turn.
+    /// [Docs](https://tailwindcss.com/docs/grid-auto-flow)
+    fn grid_flow_col(mut self) -> Self {
+        self.style().grid_auto_flow = Some(GridAutoFlow::Column);
+        self
+    }
+
+    /// Sets the grid to place items by filling each row in turn, backfilling any holes.
+    /// [Docs](https://tailwindcss.com/docs/grid-auto-flow)
+    fn grid_flow_row_dense(mut self) -> Self {
+        self.style().grid_auto_flow = Some(GridAutoFlow::RowDense);
+        self
+    }
+
+    /// Sets the grid to place items by filling each column in turn, backfilling any holes.
+    /// [Docs](https://tailwindcss.com/docs/grid-auto-flow)
+    fn grid_flow_col_dense(mut self) -> Self {
+        self.style().grid_auto_flow = Some(GridAutoFlow::ColumnDense);
+        self
+    }
+
+    /// Sets the element to span the given number of columns of its parent grid.
+    /// [Docs](https://tailwindcss.com/docs/grid-column)
+    fn col_span(mut self, columns: u16) -> Self {
+        let grid_column = self.style().grid_column.get_or_insert_default();
+        grid_column.start = GridPlacement::Span(columns);
+        grid_column.end = GridPlacement::Auto;
+        self
+    }
+
+    /// Sets the element to span all columns of its parent grid.
+    /// [Docs](https://tailwindcss.com/docs/grid-column)
+    fn col_span_full(mut self) -> Self {
+        self.style().grid_column = Some(GridLines {
+            start: GridPlacement::Line(1),
+            end: GridPlacement::Line(-1),
+        });
+        self
+    }
+
+    /// Sets the column line the element starts at in its parent grid. Lines are 1-based.
+    /// [Docs](https://tailwindcss.com/docs/grid-column)
+    fn col_start(mut self, line: i16) -> Self {
+        self.style().grid_column.get_or_insert_default().start = GridPlacement::Line(line);
+        self
+    }
+
+    /// Sets the column line the element ends at in its parent grid. Lines are 1-based.
+    /// [Docs](https://tailwindcss.com/docs/grid-column)
+    fn col_end(mut self, line: i16) -> Self {
+        self.style().grid_column.get_or_insert_default().end = GridPlacement::Line(line);
+        self
+    }
+
+    /// Sets the element to span the given number of rows of its parent grid.
+    /// [Docs](https://tailwindcss.com/docs/grid-row)
+    fn row_span(mut self, rows: u16) -> Self {
+        let grid_row = self.style().grid_row.get_or_insert_default();
+        grid_row.start = GridPlacement::Span(rows);
+        grid_row.end = GridPlacement::Auto;
+        self
+    }
+
+    /// Sets the element to span all rows of its parent grid.
+    /// [Docs](https://tailwindcss.com/docs/grid-row)
+    fn row_span_full(mut self) -> Self {
+        self.style().grid_row = Some(GridLines {
+            start: GridPlacement::Line(1),
+            end: GridPlacement::Line(-1),
+        });
+        self
+    }
+
+    /// Sets the row line the element starts at in its parent grid. Lines are 1-based.
+    /// [Docs](https://tailwindcss.com/docs/grid-row)
+    fn row_start(mut self, line: i16) -> Self {
+        self.style().grid_row.get_or_insert_default().start = GridPlacement::Line(line);
+        self
+    }
+
+    /// Sets the row line the element ends at in its parent grid. Lines are 1-based.
+    /// [Docs](https://tailwindcss.com/docs/grid-row)
+    fn row_end(mut self, line: i16) -> Self {
+        self.style().grid_row.get_or_insert_default().end = GridPlacement::Line(line);
+        self
+    }
+
     /// Sets the background color of the element.
     fn bg<F>(mut self, fill: F) -> Self
     where
@@ -1,5 +1,6 @@
 use crate::{
-    AbsoluteLength, App, Bounds, DefiniteLength, Edges, Length, Pixels, Point, Size, Style, Window,
+    AbsoluteLength, App, Bounds, DefiniteLength, Edges, GridTemplateTrack, Length, MaxTrackSize,
+    MinTrackSize, Pixels, Point, Size, Style, TrackSize, Window,
 };
 use collections::{FxHashMap, FxHashSet};
 use smallvec::SmallVec;
@@ -274,7 +275,80 @@ impl ToTaffy<taffy::style::Style> for Style {
             flex_basis: self.flex_basis.to_taffy(rem_size),
             flex_grow: self.flex_grow,
             flex_shrink: self.flex_shrink,
-            ..Default::default() // Ignore grid properties for now
+            grid_template_columns: self.grid_template_columns.to_taffy(rem_size),
+            grid_template_rows: self.grid_template_rows.to_taffy(rem_size),
+            grid_auto_columns: self.grid_auto_columns.to_taffy(rem_size),
+            grid_auto_rows: self.grid_auto_rows.to_taffy(rem_size),
+            grid_auto_flow: self.grid_auto_flow.into(),
+            grid_column: self.grid_column.into(),
+            grid_row: self.grid_row.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl<T, U> ToTaffy<Vec<U>> for Vec<T>
+where
+    T: ToTaffy<U>,
+{
+    fn to_taffy(&self, rem_size: Pixels) -> Vec<U> {
+        self.iter().map(|item| item.to_taffy(rem_size)).collect()
+    }
+}
+
+impl ToTaffy<taffy::style::TrackSizingFunction> for GridTemplateTrack {
+    fn to_taffy(&self, rem_size: Pixels) -> taffy::style::TrackSizingFunction {
+        match self {
+            GridTemplateTrack::Single(track) => {
+                taffy::style::TrackSizingFunction::Single(track.to_taffy(rem_size))
+            }
+            GridTemplateTrack::Repeat(repetition, tracks) => {
+                taffy::style::TrackSizingFunction::Repeat(
+                    (*repetition).into(),
+                    tracks.to_taffy(rem_size),
+                )
+            }
+        }
+    }
+}
+
+impl ToTaffy<taffy::style::NonRepeatedTrackSizingFunction> for TrackSize {
+    fn to_taffy(&self, rem_size: Pixels) -> taffy::style::NonRepeatedTrackSizingFunction {
+        taffy::geometry::MinMax {
+            min: self.min.to_taffy(rem_size),
+            max: self.max.to_taffy(rem_size),
+        }
+    }
+}
+
+impl ToTaffy<taffy::style::MinTrackSizingFunction> for MinTrackSize {
+    fn to_taffy(&self, rem_size: Pixels) -> taffy::style::MinTrackSizingFunction {
+        match self {
+            MinTrackSize::Fixed(length) => {
+                taffy::style::MinTrackSizingFunction::Fixed(length.to_taffy(rem_size))
+            }
+            MinTrackSize::MinContent => taffy::style::MinTrackSizingFunction::MinContent,
+            MinTrackSize::MaxContent => taffy::style::MinTrackSizingFunction::MaxContent,
+            MinTrackSize::Auto => taffy::style::MinTrackSizingFunction::Auto,
+        }
+    }
+}
+
+impl ToTaffy<taffy::style::MaxTrackSizingFunction> for MaxTrackSize {
+    fn to_taffy(&self, rem_size: Pixels) -> taffy::style::MaxTrackSizingFunction {
+        match self {
+            MaxTrackSize::Fixed(length) => {
+                taffy::style::MaxTrackSizingFunction::Fixed(length.to_taffy(rem_size))
+            }
+            MaxTrackSize::MinContent => taffy::style::MaxTrackSizingFunction::MinContent,
+            MaxTrackSize::MaxContent => taffy::style::MaxTrackSizingFunction::MaxContent,
+            MaxTrackSize::FitContent(length) => {
+                taffy::style::MaxTrackSizingFunction::FitContent(length.to_taffy(rem_size))
+            }
+            MaxTrackSize::Auto => taffy::style::MaxTrackSizingFunction::Auto,
+            MaxTrackSize::Fraction(fraction) => {
+                taffy::style::MaxTrackSizingFunction::Fraction(*fraction)
+            }
         }
     }
 }
@@ -501,3 +575,64 @@ impl From<Size<Pixels>> for Size<AvailableSpace> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as gpui, StyleRefinement, Styled, TestAppContext, fr, point, px, size};
+    use refineable::Refineable as _;
+
+    #[gpui::test]
+    fn test_grid_layout(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        cx.update(|window, cx| {
+            let rem_size = px(16.);
+            let mut engine = TaffyLayoutEngine::new();
+            let mut leaf = |refinement: StyleRefinement| {
+                engine.request_layout(Style::default().refined(refinement), rem_size, &[])
+            };
+
+            let header = leaf(StyleRefinement::default().col_span_full().h(px(20.)));
+            let cells = [
+                leaf(StyleRefinement::default().h(px(30.))),
+                leaf(StyleRefinement::default().h(px(30.))),
+                leaf(StyleRefinement::default().h(px(30.))),
+            ];
+            let footer = leaf(
+                StyleRefinement::default()
+                    .col_start(2)
+                    .col_end(4)
+                    .h(px(30.)),
+            );
+
+            let grid = StyleRefinement::default()
+                .grid()
+                .grid_template_columns([px(100.).into(), fr(1.), fr(3.)])
+                .gap_x(px(10.))
+                .w(px(400.));
+            let root = engine.request_layout(
+                Style::default().refined(grid),
+                rem_size,
+                &[header, cells[0], cells[1], cells[2], footer],
+            );
+            engine.compute_layout(root, size(px(400.), px(400.)).into(), window, cx);
+
+            assert_eq!(
+                engine.layout_bounds(header),
+                Bounds::new(point(px(0.), px(0.)), size(px(400.), px(20.)))
+            );
+            assert_eq!(
+                cells.map(|cell| engine.layout_bounds(cell)),
+                [
+                    Bounds::new(point(px(0.), px(20.)), size(px(100.), px(30.))),
+                    Bounds::new(point(px(110.), px(20.)), size(px(70.), px(30.))),
+                    Bounds::new(point(px(190.), px(20.)), size(px(210.), px(30.))),
+                ]
+            );
+            assert_eq!(
+                engine.layout_bounds(footer),
+                Bounds::new(point(px(110.), px(50.)), size(px(290.), px(30.)))
+            );
+        });
+    }
+}
@@ -26,12 +26,15 @@
 //! }
 //! ```
 use crate::{Entity, Subscription, TestAppContext, TestDispatcher};
+use anyhow::{Context as _, Result, bail};
 use futures::StreamExt as _;
+use image::RgbaImage;
 use rand::prelude::*;
 use smol::channel;
 use std::{
     env,
     panic::{self, RefUnwindSafe},
+    path::{Path, PathBuf},
     pin::Pin,
 };
 
@@ -156,3 +159,158 @@ pub fn observe<T: 'static>(entity: &Entity<T>, cx: &mut TestAppContext) -> Obser
 
     Observation { rx, _subscription }
 }
+
+/// When this environment variable is set, [`assert_screenshot_matches`] overwrites
+/// golden images with the captured screenshot instead of comparing against them.
+pub const UPDATE_SCREENSHOTS_ENV_VAR: &str = "UPDATE_SCREENSHOTS";
+
+/// How far a screenshot may drift from its golden image and still be considered a match.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScreenshotTolerance {
+    /// The largest difference in any one channel for which two pixels are still considered equal.
+    pub max_channel_difference: u8,
+    /// The fraction of pixels, between 0.0 and 1.0, that are allowed to differ.
+    pub max_differing_pixels: f32,
+}
+
+impl ScreenshotTolerance {
+    /// Requires every pixel to match exactly.
+    pub fn exact() -> Self {
+        Self {
+            max_channel_difference: 0,
+            max_differing_pixels: 0.,
+        }
+    }
+}
+
+impl Default for ScreenshotTolerance {
+    fn default() -> Self {
+        Self {
+            max_channel_difference: 2,
+            max_differing_pixels: 0.,
+        }
+    }
+}
+
+/// Compares two images, returning an error describing the difference if they
+/// don't match within the given tolerance.
+pub fn compare_screenshots(
+    expected: &RgbaImage,
+    actual: &RgbaImage,
+    tolerance: ScreenshotTolerance,
+) -> Result<()> {
+    if expected.dimensions() != actual.dimensions() {
+        bail!(
+            "expected a {}x{} image, but got {}x{}",
+            expected.width(),
+            expected.height(),
+            actual.width(),
+            actual.height()
+        );
+    }
+
+    let mut differing_pixels = 0;
+    let mut largest_difference = 0;
+    for (expected, actual) in expected.pixels().zip(actual.pixels()) {
+        let difference = channel_difference(expected.0, actual.0);
+        if difference > tolerance.max_channel_difference {
+            differing_pixels += 1;
+            largest_difference = largest_difference.max(difference);
+        }
+    }
+
+    let total_pixels = (expected.width() * expected.height()).max(1);
+    let differing_fraction = differing_pixels as f32 / total_pixels as f32;
+    if differing_fraction > tolerance.max_differing_pixels {
+        bail!(
+            "{differing_pixels} of {total_pixels} pixels differ ({:.2}%), by up to {largest_difference} per channel",
+            differing_fraction * 100.
+        );
+    }
+    Ok(())
+}
+
+/// Compares `actual` against the PNG at `golden_path`, panicking if they don't match.
+///
+/// On a mismatch, the captured image and a diff highlighting the differing pixels
+/// are written next to the golden image as `<name>.actual.png` and `<name>.diff.png`.
+/// Set [`UPDATE_SCREENSHOTS_ENV_VAR`] to write the captured image as the new golden.
+#[track_caller]
+pub fn assert_screenshot_matches(
+    actual: &RgbaImage,
+    golden_path: impl AsRef<Path>,
+    tolerance: ScreenshotTolerance,
+) {
+    let golden_path = golden_path.as_ref();
+    if env::var_os(UPDATE_SCREENSHOTS_ENV_VAR).is_some() {
+        save_screenshot(actual, golden_path)
+            .with_context(|| format!("writing golden image {golden_path:?}"))
+            .unwrap();
+        return;
+    }
+
+    let expected = match image::open(golden_path) {
+        Ok(expected) => expected.into_rgba8(),
+        Err(error) => panic!(
+            "failed to load golden image {golden_path:?}: {error}. \
+            Rerun with {UPDATE_SCREENSHOTS_ENV_VAR}=1 to create it."
+        ),
+    };
+
+    if let Err(error) = compare_screenshots(&expected, actual, tolerance) {
+        let actual_path = sibling_path(golden_path, "actual");
+        let diff_path = sibling_path(golden_path, "diff");
+        save_screenshot(actual, &actual_path).ok();
+        if expected.dimensions() == actual.dimensions() {
+            save_screenshot(&diff_image(&expected, actual, tolerance), &diff_path).ok();
+        }
+        panic!(
+            "screenshot does not match {golden_path:?}: {error}. \
+            The captured image was written to {actual_path:?}. \
+            Rerun with {UPDATE_SCREENSHOTS_ENV_VAR}=1 to accept it."
+        );
+    }
+}
+
+fn channel_difference(expected: [u8; 4], actual: [u8; 4]) -> u8 {
+    expected
+        .iter()
+        .zip(actual.iter())
+        .map(|(expected, actual)| expected.abs_diff(*actual))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Dims the actual image and paints differing pixels in solid red.
+fn diff_image(
+    expected: &RgbaImage,
+    actual: &RgbaImage,
+    tolerance: ScreenshotTolerance,
+) -> RgbaImage {
+    let mut diff = actual.clone();
+    for (diff, expected) in diff.pixels_mut().zip(expected.pixels()) {
+        if channel_difference(expected.0, diff.0) > tolerance.max_channel_difference {
+            diff.0 = [0xFF, 0, 0, 0xFF];
+        } else {
+            let [r, g, b, _] = diff.0;
+            diff.0 = [r / 4, g / 4, b / 4, 0xFF];
+        }
+    }
+    diff
+}
+
+fn sibling_path(golden_path: &Path, suffix: &str) -> PathBuf {
+    let stem = golden_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    golden_path.with_file_name(format!("{stem}.{suffix}.png"))
+}
+
+fn save_screenshot(image: &RgbaImage, path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    image.save_with_format(path, image::ImageFormat::Png)?;
+    Ok(())
+}
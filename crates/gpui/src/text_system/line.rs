@@ -5,7 +5,7 @@ use crate::{
 };
 use derive_more::{Deref, DerefMut};
 use smallvec::SmallVec;
-use std::sync::Arc;
+use std::{ops::Range, sync::Arc};
 
 /// Set the text decoration for a run of text.
 #[derive(Debug, Clone)]
@@ -55,6 +55,7 @@ impl ShapedLine {
             descent: layout.descent,
             runs: layout.runs.clone(),
             len,
+            direction_runs: layout.direction_runs.clone(),
         });
         self
     }
@@ -206,9 +207,9 @@ fn paint_line(
     window.paint_layer(line_bounds, |window| {
         let padding_top = (line_height - layout.ascent - layout.descent) / 2.;
         let baseline_offset = point(px(0.), padding_top + layout.ascent);
-        let mut decoration_runs = decoration_runs.iter();
         let mut wraps = wrap_boundaries.iter().peekable();
-        let mut run_end = 0;
+        let mut decoration_run_range = 0..0;
+        let mut decoration_run_hint = (0, 0);
         let mut color = black();
         let mut current_underline: Option<(Point<Pixels>, UnderlineStyle)> = None;
         let mut current_strikethrough: Option<(Point<Pixels>, StrikethroughStyle)> = None;
@@ -279,19 +280,15 @@ fn paint_line(
 
                 let mut finished_underline: Option<(Point<Pixels>, UnderlineStyle)> = None;
                 let mut finished_strikethrough: Option<(Point<Pixels>, StrikethroughStyle)> = None;
-                if glyph.index >= run_end {
-                    let mut style_run = decoration_runs.next();
-
-                    // ignore style runs that apply to a partial glyph
-                    while let Some(run) = style_run {
-                        if glyph.index < run_end + (run.len as usize) {
-                            break;
-                        }
-                        run_end += run.len as usize;
-                        style_run = decoration_runs.next();
-                    }
+                if !decoration_run_range.contains(&glyph.index) {
+                    let style_run = decoration_run_for_index(
+                        decoration_runs,
+                        glyph.index,
+                        &mut decoration_run_hint,
+                    );
 
-                    if let Some(style_run) = style_run {
+                    if let Some((style_run, style_run_range)) = style_run {
+                        decoration_run_range = style_run_range;
                         if let Some((_, underline_style)) = &mut current_underline {
                             if style_run.underline.as_ref() != Some(underline_style) {
                                 finished_underline = current_underline.take();
@@ -329,10 +326,9 @@ fn paint_line(
                             ));
                         }
 
-                        run_end += style_run.len as usize;
                         color = style_run.color;
                     } else {
-                        run_end = layout.len;
+                        decoration_run_range = decoration_run_hint.1..usize::MAX;
                         finished_underline = current_underline.take();
                         finished_strikethrough = current_strikethrough.take();
                     }
@@ -441,9 +437,9 @@ fn paint_line_background(
         ),
     );
     window.paint_layer(line_bounds, |window| {
-        let mut decoration_runs = decoration_runs.iter();
         let mut wraps = wrap_boundaries.iter().peekable();
-        let mut run_end = 0;
+        let mut decoration_run_range = 0..0;
+        let mut decoration_run_hint = (0, 0);
         let mut current_background: Option<(Point<Pixels>, Hsla)> = None;
         let text_system = cx.text_system().clone();
         let mut glyph_origin = point(
@@ -496,19 +492,15 @@ fn paint_line_background(
                 prev_glyph_position = glyph.position;
 
                 let mut finished_background: Option<(Point<Pixels>, Hsla)> = None;
-                if glyph.index >= run_end {
-                    let mut style_run = decoration_runs.next();
-
-                    // ignore style runs that apply to a partial glyph
-                    while let Some(run) = style_run {
-                        if glyph.index < run_end + (run.len as usize) {
-                            break;
-                        }
-                        run_end += run.len as usize;
-                        style_run = decoration_runs.next();
-                    }
+                if !decoration_run_range.contains(&glyph.index) {
+                    let style_run = decoration_run_for_index(
+                        decoration_runs,
+                        glyph.index,
+                        &mut decoration_run_hint,
+                    );
 
-                    if let Some(style_run) = style_run {
+                    if let Some((style_run, style_run_range)) = style_run {
+                        decoration_run_range = style_run_range;
                         if let Some((_, background_color)) = &mut current_background {
                             if style_run.background_color.as_ref() != Some(background_color) {
                                 finished_background = current_background.take();
@@ -520,9 +512,8 @@ fn paint_line_background(
                                 run_background,
                             ));
                         }
-                        run_end += style_run.len as usize;
                     } else {
-                        run_end = layout.len;
+                        decoration_run_range = decoration_run_hint.1..usize::MAX;
                         finished_background = current_background.take();
                     }
                 }
@@ -567,6 +558,34 @@ fn paint_line_background(
     })
 }
 
+/// Finds the decoration run containing the byte at `index`, along with the range it covers.
+///
+/// Glyphs are mostly visited in increasing index order, so the search resumes from `hint`, the
+/// index and start of the last run found. It restarts from the beginning when right-to-left
+/// text moves backwards through the line. Runs that apply to a partial glyph are skipped.
+fn decoration_run_for_index<'a>(
+    decoration_runs: &'a [DecorationRun],
+    index: usize,
+    hint: &mut (usize, usize),
+) -> Option<(&'a DecorationRun, Range<usize>)> {
+    if index < hint.1 {
+        *hint = (0, 0);
+    }
+
+    let (mut run_ix, mut run_start) = *hint;
+    while let Some(run) = decoration_runs.get(run_ix) {
+        let run_end = run_start + run.len as usize;
+        if index < run_end {
+            *hint = (run_ix, run_start);
+            return Some((run, run_start..run_end));
+        }
+        run_ix += 1;
+        run_start = run_end;
+    }
+    *hint = (run_ix, run_start);
+    None
+}
+
 fn aligned_origin_x(
     origin: Point<Pixels>,
     align_width: Pixels,
@@ -8,6 +8,7 @@ use std::{
     ops::Range,
     sync::Arc,
 };
+use unicode_bidi::{BidiClass, BidiInfo, Level};
 
 use super::LineWrapper;
 
@@ -26,6 +27,26 @@ pub struct LineLayout {
     pub runs: Vec<ShapedRun>,
     /// The length of the line in utf-8 bytes
     pub len: usize,
+    /// The line's runs of uniform text direction, in visual order.
+    ///
+    /// This is empty for lines that are entirely left-to-right, in which case glyph indices
+    /// increase along with their x position.
+    pub direction_runs: Vec<DirectionRun>,
+}
+
+/// A run of text laid out in a single direction, as determined by the Unicode Bidirectional
+/// Algorithm.
+#[derive(Debug, Clone)]
+pub struct DirectionRun {
+    /// The range of the line's text in this run, in utf-8 bytes
+    pub range: Range<usize>,
+    /// The horizontal extent of this run in the line
+    pub x: Range<Pixels>,
+    /// Whether this run is laid out right-to-left. Its glyphs are still ordered by x position,
+    /// so their indices decrease.
+    pub rtl: bool,
+    /// The indices of the [`LineLayout::runs`] this run was shaped into
+    pub shaped_runs: Range<usize>,
 }
 
 /// A run of text that has been shaped .
@@ -73,6 +94,14 @@ impl LineLayout {
     /// closest_index_for_x returns the character boundary closest to the given x coordinate
     /// (e.g. to handle aligning up/down arrow keys)
     pub fn closest_index_for_x(&self, x: Pixels) -> usize {
+        if !self.direction_runs.is_empty() {
+            return self
+                .caret_stops()
+                .into_iter()
+                .min_by(|(a, _), (b, _)| (*a - x).abs().0.total_cmp(&(*b - x).abs().0))
+                .map_or(0, |(_, index)| index);
+        }
+
         let mut prev_index = 0;
         let mut prev_x = px(0.);
 
@@ -103,6 +132,12 @@ impl LineLayout {
 
     /// The x position of the character at the given index
     pub fn x_for_index(&self, index: usize) -> Pixels {
+        if !self.direction_runs.is_empty() {
+            return self
+                .direction_run_for_index(index)
+                .map_or(self.width, |run| self.x_for_index_in_run(run, index));
+        }
+
         for run in &self.runs {
             for glyph in &run.glyphs {
                 if glyph.index >= index {
@@ -115,6 +150,16 @@ impl LineLayout {
 
     /// The corresponding Font at the given index
     pub fn font_id_for_index(&self, index: usize) -> Option<FontId> {
+        if !self.direction_runs.is_empty() {
+            return self
+                .runs
+                .iter()
+                .flat_map(|run| run.glyphs.iter().map(|glyph| (run.font_id, glyph.index)))
+                .filter(|(_, glyph_index)| *glyph_index <= index)
+                .max_by_key(|(_, glyph_index)| *glyph_index)
+                .map(|(font_id, _)| font_id);
+        }
+
         for run in &self.runs {
             for glyph in &run.glyphs {
                 if glyph.index >= index {
@@ -125,6 +170,153 @@ impl LineLayout {
         None
     }
 
+    /// Whether any of this line is laid out right-to-left.
+    pub fn has_rtl(&self) -> bool {
+        self.direction_runs.iter().any(|run| run.rtl)
+    }
+
+    /// The horizontal extents covered by the given range of the line's text, in visual order.
+    ///
+    /// A range spanning a change of direction is split into several extents, which is what
+    /// selections need to paint.
+    pub fn x_ranges_for_range(&self, range: Range<usize>) -> SmallVec<[Range<Pixels>; 1]> {
+        let mut x_ranges = SmallVec::<[Range<Pixels>; 1]>::new();
+        if range.is_empty() {
+            return x_ranges;
+        }
+        if self.direction_runs.is_empty() {
+            x_ranges.push(self.x_for_index(range.start)..self.x_for_index(range.end));
+            return x_ranges;
+        }
+
+        for run in &self.direction_runs {
+            let start = range.start.max(run.range.start);
+            let end = range.end.min(run.range.end);
+            if start >= end {
+                continue;
+            }
+
+            let start_x = self.x_for_index_in_run(run, start);
+            let end_x = self.x_for_index_in_run(run, end);
+            let x_range = if run.rtl {
+                end_x..start_x
+            } else {
+                start_x..end_x
+            };
+            match x_ranges.last_mut() {
+                Some(last) if last.end == x_range.start => last.end = x_range.end,
+                _ => x_ranges.push(x_range),
+            }
+        }
+        x_ranges
+    }
+
+    /// The index of the caret position visually to the left of the one at `index`, or `None`
+    /// if it's already at the left edge of the line.
+    ///
+    /// This differs from the previous character boundary wherever the line contains
+    /// right-to-left text.
+    pub fn visual_index_left_of(&self, index: usize) -> Option<usize> {
+        let x = self.x_for_index(index);
+        self.caret_stops()
+            .into_iter()
+            .rev()
+            .find(|(stop_x, _)| *stop_x < x)
+            .map(|(_, index)| index)
+    }
+
+    /// The index of the caret position visually to the right of the one at `index`, or `None`
+    /// if it's already at the right edge of the line.
+    ///
+    /// This differs from the next character boundary wherever the line contains
+    /// right-to-left text.
+    pub fn visual_index_right_of(&self, index: usize) -> Option<usize> {
+        let x = self.x_for_index(index);
+        self.caret_stops()
+            .into_iter()
+            .find(|(stop_x, _)| *stop_x > x)
+            .map(|(_, index)| index)
+    }
+
+    /// The positions a caret can be placed at in this line, in visual order, paired with the
+    /// index they correspond to. Each index's position matches [`Self::x_for_index`].
+    fn caret_stops(&self) -> Vec<(Pixels, usize)> {
+        let mut stops = Vec::new();
+        if self.direction_runs.is_empty() {
+            for glyph in self.runs.iter().flat_map(|run| run.glyphs.iter()) {
+                if stops
+                    .last()
+                    .map_or(true, |(_, index)| *index != glyph.index)
+                {
+                    stops.push((glyph.position.x, glyph.index));
+                }
+            }
+            stops.push((self.width, self.len));
+            return stops;
+        }
+
+        for run in &self.direction_runs {
+            let mut glyphs = self.runs[run.shaped_runs.clone()]
+                .iter()
+                .flat_map(|run| run.glyphs.iter())
+                .peekable();
+            if run.rtl && run.range.end == self.len {
+                stops.push((run.x.start, run.range.end));
+            }
+            let mut prev_index = None;
+            while let Some(glyph) = glyphs.next() {
+                if run.rtl {
+                    // The leading edge of a right-to-left character is on its right, so use the
+                    // rightmost glyph of each cluster.
+                    let next = glyphs.peek();
+                    if next.map_or(false, |next| next.index == glyph.index) {
+                        continue;
+                    }
+                    stops.push((next.map_or(run.x.end, |next| next.position.x), glyph.index));
+                } else if prev_index != Some(glyph.index) {
+                    stops.push((glyph.position.x, glyph.index));
+                }
+                prev_index = Some(glyph.index);
+            }
+            if !run.rtl && run.range.end == self.len {
+                stops.push((run.x.end, run.range.end));
+            }
+        }
+        stops
+    }
+
+    fn direction_run_for_index(&self, index: usize) -> Option<&DirectionRun> {
+        self.direction_runs
+            .iter()
+            .find(|run| run.range.contains(&index))
+            .or_else(|| {
+                self.direction_runs
+                    .iter()
+                    .find(|run| run.range.end == index)
+            })
+    }
+
+    fn x_for_index_in_run(&self, run: &DirectionRun, index: usize) -> Pixels {
+        let mut glyphs = self.runs[run.shaped_runs.clone()]
+            .iter()
+            .flat_map(|run| run.glyphs.iter());
+        if run.rtl {
+            // Glyphs are ordered by x, so walk them from the right to visit increasing indices.
+            let mut right_edge = run.x.end;
+            for glyph in glyphs.rev() {
+                if glyph.index >= index {
+                    return right_edge;
+                }
+                right_edge = glyph.position.x;
+            }
+            run.x.start
+        } else {
+            glyphs
+                .find(|glyph| glyph.index >= index)
+                .map_or(run.x.end, |glyph| glyph.position.x)
+        }
+    }
+
     fn compute_wrap_boundaries(
         &self,
         text: &str,
@@ -211,6 +403,9 @@ impl LineLayout {
 #[derive(Default, Debug)]
 pub struct WrappedLineLayout {
     /// The line layout, pre-wrapping.
+    ///
+    /// If the line contains right-to-left text, each wrapped line is reordered on its own, and
+    /// they are laid out one after another.
     pub unwrapped_layout: Arc<LineLayout>,
 
     /// The boundaries at which the line was wrapped
@@ -277,6 +472,45 @@ impl WrappedLineLayout {
         &self.unwrapped_layout.runs
     }
 
+    /// The index in the text at which the line following the given wrap boundary starts.
+    ///
+    /// This is the index of the boundary's glyph, unless the line contains right-to-left text
+    /// and was reordered, in which case its glyphs aren't in logical order.
+    pub fn wrap_boundary_index(&self, boundary_ix: usize) -> usize {
+        let boundary = self.wrap_boundaries[boundary_ix];
+        if self.unwrapped_layout.direction_runs.is_empty() {
+            return self.unwrapped_layout.runs[boundary.run_ix].glyphs[boundary.glyph_ix].index;
+        }
+
+        let next_boundary = self.wrap_boundaries.get(boundary_ix + 1).copied();
+        self.unwrapped_layout
+            .runs
+            .iter()
+            .enumerate()
+            .flat_map(|(run_ix, run)| {
+                run.glyphs
+                    .iter()
+                    .enumerate()
+                    .map(move |(glyph_ix, glyph)| (WrapBoundary { run_ix, glyph_ix }, glyph.index))
+            })
+            .skip_while(|(position, _)| *position < boundary)
+            .take_while(|(position, _)| next_boundary.map_or(true, |next| *position < next))
+            .map(|(_, index)| index)
+            .min()
+            .unwrap_or(self.unwrapped_layout.len)
+    }
+
+    /// The x position in the unwrapped layout at which the given wrapped line starts.
+    fn wrapped_line_start_x(&self, wrapped_line_ix: usize) -> Pixels {
+        if wrapped_line_ix == 0 {
+            return Pixels::ZERO;
+        }
+        let boundary = self.wrap_boundaries[wrapped_line_ix - 1];
+        self.unwrapped_layout.runs[boundary.run_ix].glyphs[boundary.glyph_ix]
+            .position
+            .x
+    }
+
     /// The index corresponding to a given position in this layout for the given line height.
     ///
     /// See also [`Self::closest_index_for_position`].
@@ -312,13 +546,11 @@ impl WrappedLineLayout {
         let wrapped_line_start_index;
         let wrapped_line_start_x;
         if wrapped_line_ix > 0 {
-            let Some(line_start_boundary) = self.wrap_boundaries.get(wrapped_line_ix - 1) else {
+            if wrapped_line_ix > self.wrap_boundaries.len() {
                 return Err(0);
-            };
-            let run = &self.unwrapped_layout.runs[line_start_boundary.run_ix];
-            let glyph = &run.glyphs[line_start_boundary.glyph_ix];
-            wrapped_line_start_index = glyph.index;
-            wrapped_line_start_x = glyph.position.x;
+            }
+            wrapped_line_start_index = self.wrap_boundary_index(wrapped_line_ix - 1);
+            wrapped_line_start_x = self.wrapped_line_start_x(wrapped_line_ix);
         } else {
             wrapped_line_start_index = 0;
             wrapped_line_start_x = Pixels::ZERO;
@@ -327,12 +559,8 @@ impl WrappedLineLayout {
         let wrapped_line_end_index;
         let wrapped_line_end_x;
         if wrapped_line_ix < self.wrap_boundaries.len() {
-            let next_wrap_boundary_ix = wrapped_line_ix;
-            let next_wrap_boundary = self.wrap_boundaries[next_wrap_boundary_ix];
-            let run = &self.unwrapped_layout.runs[next_wrap_boundary.run_ix];
-            let glyph = &run.glyphs[next_wrap_boundary.glyph_ix];
-            wrapped_line_end_index = glyph.index;
-            wrapped_line_end_x = glyph.position.x;
+            wrapped_line_end_index = self.wrap_boundary_index(wrapped_line_ix);
+            wrapped_line_end_x = self.wrapped_line_start_x(wrapped_line_ix + 1);
         } else {
             wrapped_line_end_index = self.unwrapped_layout.len;
             wrapped_line_end_x = self.unwrapped_layout.width;
@@ -361,25 +589,23 @@ impl WrappedLineLayout {
     /// Returns the pixel position for the given byte index.
     pub fn position_for_index(&self, index: usize, line_height: Pixels) -> Option<Point<Pixels>> {
         let mut line_start_ix = 0;
-        let mut line_end_indices = self
-            .wrap_boundaries
-            .iter()
-            .map(|wrap_boundary| {
-                let run = &self.unwrapped_layout.runs[wrap_boundary.run_ix];
-                let glyph = &run.glyphs[wrap_boundary.glyph_ix];
-                glyph.index
-            })
+        let mut line_end_indices = (0..self.wrap_boundaries.len())
+            .map(|boundary_ix| self.wrap_boundary_index(boundary_ix))
             .chain([self.len()])
             .enumerate();
+        // The end of a reordered line isn't necessarily at its right edge, so an index where the
+        // line wraps is placed at the start of the next line instead.
+        let reordered = self.unwrapped_layout.has_rtl();
         for (ix, line_end_ix) in line_end_indices {
             let line_y = ix as f32 * line_height;
+            let wraps_after = reordered && ix < self.wrap_boundaries.len();
             if index < line_start_ix {
                 break;
-            } else if index > line_end_ix {
+            } else if index > line_end_ix || (index == line_end_ix && wraps_after) {
                 line_start_ix = line_end_ix;
                 continue;
             } else {
-                let line_start_x = self.unwrapped_layout.x_for_index(line_start_ix);
+                let line_start_x = self.wrapped_line_start_x(ix);
                 let x = self.unwrapped_layout.x_for_index(index) - line_start_x;
                 return Some(point(x, line_y));
             }
@@ -500,11 +726,24 @@ impl LineLayoutCache {
         } else {
             drop(current_frame);
             let text = SharedString::from(text);
-            let unwrapped_layout = self.layout_line::<&SharedString>(&text, font_size, runs);
-            let wrap_boundaries = if let Some(wrap_width) = wrap_width {
-                unwrapped_layout.compute_wrap_boundaries(text.as_ref(), wrap_width, max_lines)
-            } else {
-                SmallVec::new()
+            let mut unwrapped_layout = self.layout_line::<&SharedString>(&text, font_size, runs);
+            let wrap_boundaries = match wrap_width {
+                Some(wrap_width) if unwrapped_layout.has_rtl() => {
+                    let (wrapped_layout, wrap_boundaries) = layout_wrapped_bidi_line(
+                        self.platform_text_system.as_ref(),
+                        &unwrapped_layout,
+                        text.as_ref(),
+                        runs,
+                        wrap_width,
+                        max_lines,
+                    );
+                    unwrapped_layout = Arc::new(wrapped_layout);
+                    wrap_boundaries
+                }
+                Some(wrap_width) => {
+                    unwrapped_layout.compute_wrap_boundaries(text.as_ref(), wrap_width, max_lines)
+                }
+                None => SmallVec::new(),
             };
             let layout = Arc::new(WrappedLineLayout {
                 unwrapped_layout,
@@ -557,10 +796,12 @@ impl LineLayoutCache {
             layout
         } else {
             let text = SharedString::from(text);
-            let layout = Arc::new(
-                self.platform_text_system
-                    .layout_line(&text, font_size, runs),
-            );
+            let layout = Arc::new(layout_bidi_line(
+                self.platform_text_system.as_ref(),
+                &text,
+                font_size,
+                runs,
+            ));
             let key = Arc::new(CacheKey {
                 text,
                 font_size,
@@ -574,6 +815,240 @@ impl LineLayoutCache {
     }
 }
 
+/// Lays out a line, reordering it with the Unicode Bidirectional Algorithm if it contains any
+/// right-to-left text.
+///
+/// Lines are always given a left-to-right base direction, so that code and mixed-language prose
+/// stay anchored to the left edge. Each run of uniform direction is shaped separately by the
+/// platform, and its glyphs are placed in visual order.
+fn layout_bidi_line(
+    text_system: &dyn PlatformTextSystem,
+    text: &str,
+    font_size: Pixels,
+    runs: &[FontRun],
+) -> LineLayout {
+    if !text.chars().any(is_rtl_char) {
+        return text_system.layout_line(text, font_size, runs);
+    }
+
+    let bidi_info = BidiInfo::new(text, Some(Level::ltr()));
+    let mut layout = LineLayout {
+        font_size,
+        len: text.len(),
+        ..Default::default()
+    };
+    push_visual_runs(&mut layout, text_system, &bidi_info, runs, 0..text.len());
+    layout
+}
+
+/// Wraps a line that contains right-to-left text.
+///
+/// The line is broken where it would be when read in logical order, and each wrapped line is
+/// then reordered on its own, so that the right-to-left text on it reads from its right edge.
+/// The wrapped lines are laid out one after another, as if they were a single line.
+fn layout_wrapped_bidi_line(
+    text_system: &dyn PlatformTextSystem,
+    unwrapped_layout: &LineLayout,
+    text: &str,
+    runs: &[FontRun],
+    wrap_width: Pixels,
+    max_lines: Option<usize>,
+) -> (LineLayout, SmallVec<[WrapBoundary; 1]>) {
+    let logical_layout = logical_line_layout(unwrapped_layout);
+    let wrap_indices = logical_layout
+        .compute_wrap_boundaries(text, wrap_width, max_lines)
+        .into_iter()
+        .map(|boundary| logical_layout.runs[boundary.run_ix].glyphs[boundary.glyph_ix].index);
+
+    let bidi_info = BidiInfo::new(text, Some(Level::ltr()));
+    let mut layout = LineLayout {
+        font_size: unwrapped_layout.font_size,
+        len: text.len(),
+        ..Default::default()
+    };
+    let mut wrap_boundaries = SmallVec::new();
+    let mut line_start = 0;
+    for line_end in wrap_indices.chain([text.len()]) {
+        let runs_start = layout.runs.len();
+        push_visual_runs(
+            &mut layout,
+            text_system,
+            &bidi_info,
+            runs,
+            line_start..line_end,
+        );
+        if line_start > 0 && layout.runs.len() > runs_start {
+            wrap_boundaries.push(WrapBoundary {
+                run_ix: runs_start,
+                glyph_ix: 0,
+            });
+        }
+        line_start = line_end;
+    }
+    (layout, wrap_boundaries)
+}
+
+/// Returns the glyphs of a line laid out by [`layout_bidi_line`] in logical order, each keeping
+/// its advance, so that the line can be wrapped the way it is read.
+fn logical_line_layout(layout: &LineLayout) -> LineLayout {
+    let mut glyphs = Vec::new();
+    for direction_run in &layout.direction_runs {
+        let run_glyphs = layout.runs[direction_run.shaped_runs.clone()]
+            .iter()
+            .flat_map(|run| run.glyphs.iter().map(move |glyph| (run.font_id, glyph)))
+            .collect::<Vec<_>>();
+        for (ix, (font_id, glyph)) in run_glyphs.iter().enumerate() {
+            let next_x = run_glyphs
+                .get(ix + 1)
+                .map_or(direction_run.x.end, |(_, glyph)| glyph.position.x);
+            glyphs.push((*font_id, (*glyph).clone(), next_x - glyph.position.x));
+        }
+    }
+    glyphs.sort_by_key(|(_, glyph, _)| glyph.index);
+
+    let mut logical_layout = LineLayout {
+        font_size: layout.font_size,
+        width: layout.width,
+        ascent: layout.ascent,
+        descent: layout.descent,
+        len: layout.len,
+        ..Default::default()
+    };
+    let mut x = px(0.);
+    for (font_id, mut glyph, advance) in glyphs {
+        glyph.position.x = x;
+        x += advance;
+        match logical_layout.runs.last_mut() {
+            Some(run) if run.font_id == font_id => run.glyphs.push(glyph),
+            _ => logical_layout.runs.push(ShapedRun {
+                font_id,
+                glyphs: vec![glyph],
+            }),
+        }
+    }
+    logical_layout
+}
+
+/// Appends the given range of a line's text to `layout`, with its runs of uniform direction in
+/// visual order.
+fn push_visual_runs(
+    layout: &mut LineLayout,
+    text_system: &dyn PlatformTextSystem,
+    bidi_info: &BidiInfo,
+    runs: &[FontRun],
+    range: Range<usize>,
+) {
+    for paragraph in &bidi_info.paragraphs {
+        let line = range.start.max(paragraph.range.start)..range.end.min(paragraph.range.end);
+        if line.is_empty() {
+            continue;
+        }
+
+        let (levels, level_runs) = bidi_info.visual_runs(paragraph, line);
+        for range in level_runs {
+            if range.is_empty() {
+                continue;
+            }
+            let rtl = levels[range.start].is_rtl();
+            push_direction_run(layout, text_system, bidi_info.text, runs, range, rtl);
+        }
+    }
+}
+
+/// Shapes a run of text of uniform direction and appends it to `layout`, placing its glyphs in
+/// visual order.
+fn push_direction_run(
+    layout: &mut LineLayout,
+    text_system: &dyn PlatformTextSystem,
+    text: &str,
+    runs: &[FontRun],
+    range: Range<usize>,
+    rtl: bool,
+) {
+    let run_layout = text_system.layout_line(
+        &text[range.clone()],
+        layout.font_size,
+        &font_runs_in_range(runs, range.clone()),
+    );
+    let origin_x = layout.width;
+    layout.ascent = layout.ascent.max(run_layout.ascent);
+    layout.descent = layout.descent.max(run_layout.descent);
+
+    let mut glyphs = run_layout
+        .runs
+        .into_iter()
+        .flat_map(|run| {
+            let font_id = run.font_id;
+            run.glyphs.into_iter().map(move |glyph| (font_id, glyph))
+        })
+        .collect::<Vec<_>>();
+    glyphs.sort_by(|(_, a), (_, b)| a.position.x.0.total_cmp(&b.position.x.0));
+
+    // Some shapers leave right-to-left glyphs in logical order, so mirror them into place.
+    let logical_order = glyphs
+        .first()
+        .zip(glyphs.last())
+        .map_or(false, |((_, first), (_, last))| first.index < last.index);
+    if rtl && logical_order {
+        let mut next_x = run_layout.width;
+        for (_, glyph) in glyphs.iter_mut().rev() {
+            let x = glyph.position.x;
+            glyph.position.x = run_layout.width - next_x;
+            next_x = x;
+        }
+        glyphs.reverse();
+    }
+
+    let shaped_runs_start = layout.runs.len();
+    for (font_id, mut glyph) in glyphs {
+        glyph.position.x += origin_x;
+        glyph.index += range.start;
+        match layout.runs[shaped_runs_start..].last_mut() {
+            Some(run) if run.font_id == font_id => run.glyphs.push(glyph),
+            _ => layout.runs.push(ShapedRun {
+                font_id,
+                glyphs: vec![glyph],
+            }),
+        }
+    }
+
+    layout.width += run_layout.width;
+    layout.direction_runs.push(DirectionRun {
+        range,
+        x: origin_x..layout.width,
+        rtl,
+        shaped_runs: shaped_runs_start..layout.runs.len(),
+    });
+}
+
+/// Whether the given character is laid out right-to-left, such as a Hebrew or Arabic letter.
+pub fn is_rtl_char(ch: char) -> bool {
+    // Every strong right-to-left character lives above the Hebrew block's start.
+    ch >= '\u{590}'
+        && matches!(
+            unicode_bidi::bidi_class(ch),
+            BidiClass::R | BidiClass::AL | BidiClass::RLE | BidiClass::RLO | BidiClass::RLI
+        )
+}
+
+fn font_runs_in_range(runs: &[FontRun], range: Range<usize>) -> SmallVec<[FontRun; 1]> {
+    let mut run_start = 0;
+    let mut result = SmallVec::new();
+    for run in runs {
+        let run_end = run_start + run.len;
+        let start = run_start.max(range.start);
+        let end = run_end.min(range.end);
+        if start < end {
+            result.push(FontRun {
+                len: end - start,
+                font_id: run.font_id,
+            });
+        }
+        run_start = run_end;
+    }
+    result
+}
+
 /// A run of text with a single font.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct FontRun {
@@ -649,3 +1124,151 @@ impl AsCacheKeyRef for CacheKeyRef<'_> {
         *self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoopTextSystem;
+
+    fn layout(text: &str) -> LineLayout {
+        let runs = [FontRun {
+            len: text.len(),
+            font_id: FontId(0),
+        }];
+        // The noop text system lays out every character 6px wide at this size.
+        layout_bidi_line(&NoopTextSystem::new(), text, px(10.), &runs)
+    }
+
+    fn glyph_indices(layout: &LineLayout) -> Vec<usize> {
+        layout
+            .runs
+            .iter()
+            .flat_map(|run| run.glyphs.iter().map(|glyph| glyph.index))
+            .collect()
+    }
+
+    #[test]
+    fn test_left_to_right_line() {
+        let layout = layout("abc");
+        assert!(layout.direction_runs.is_empty());
+        assert!(!layout.has_rtl());
+        assert_eq!(glyph_indices(&layout), [0, 1, 2]);
+        assert_eq!(layout.x_for_index(2), px(12.));
+        assert_eq!(layout.visual_index_left_of(2), Some(1));
+        assert_eq!(layout.visual_index_right_of(3), None);
+    }
+
+    #[test]
+    fn test_bidi_reordering() {
+        // Hebrew letters are two bytes each: א is at 3 and ב is at 5.
+        let layout = layout("ab אב cd");
+        assert!(layout.has_rtl());
+        assert_eq!(
+            layout
+                .direction_runs
+                .iter()
+                .map(|run| (run.range.clone(), run.rtl))
+                .collect::<Vec<_>>(),
+            [(0..3, false), (3..7, true), (7..10, false)]
+        );
+        assert_eq!(glyph_indices(&layout), [0, 1, 2, 5, 3, 7, 8, 9]);
+        assert_eq!(layout.width, px(48.));
+
+        // The caret before א sits on its right edge.
+        assert_eq!(layout.x_for_index(3), px(30.));
+        assert_eq!(layout.x_for_index(5), px(24.));
+        assert_eq!(layout.x_for_index(7), px(30.));
+        assert_eq!(layout.x_for_index(10), px(48.));
+        assert_eq!(layout.index_for_x(px(19.)), Some(5));
+        assert_eq!(layout.closest_index_for_x(px(25.)), 5);
+        assert_eq!(layout.font_id_for_index(4), Some(FontId(0)));
+    }
+
+    #[test]
+    fn test_bidi_visual_movement() {
+        let layout = layout("ab אב cd");
+
+        let mut index = 0;
+        let mut rightward = vec![index];
+        while let Some(next) = layout.visual_index_right_of(index) {
+            index = next;
+            rightward.push(index);
+        }
+        assert_eq!(rightward, [0, 1, 2, 5, 3, 8, 9, 10]);
+
+        let mut leftward = vec![index];
+        while let Some(next) = layout.visual_index_left_of(index) {
+            index = next;
+            leftward.push(index);
+        }
+        assert_eq!(leftward, [10, 9, 8, 7, 5, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_wrapped_bidi_line() {
+        let cache = LineLayoutCache::new(Arc::new(NoopTextSystem::new()));
+        // Hebrew letters are two bytes each: א is at 3, ב at 5, ג at 7 and ד at 9.
+        let text = "ab אבגד";
+        let runs = [FontRun {
+            len: text.len(),
+            font_id: FontId(0),
+        }];
+        let layout = cache.layout_wrapped_line(text, px(10.), &runs, Some(px(30.)), None);
+
+        // The line wraps after the first two letters it is read with, rather than the first two
+        // that are displayed, and each line is reordered on its own.
+        assert_eq!(
+            layout.wrap_boundaries(),
+            [WrapBoundary {
+                run_ix: 2,
+                glyph_ix: 0
+            }]
+        );
+        assert_eq!(
+            glyph_indices(&layout.unwrapped_layout),
+            [0, 1, 2, 5, 3, 9, 7]
+        );
+        assert_eq!(layout.wrap_boundary_index(0), 7);
+
+        let line_height = px(20.);
+        assert_eq!(
+            layout.position_for_index(3, line_height),
+            Some(point(px(30.), px(0.)))
+        );
+        assert_eq!(
+            layout.position_for_index(5, line_height),
+            Some(point(px(24.), px(0.)))
+        );
+        assert_eq!(
+            layout.position_for_index(7, line_height),
+            Some(point(px(12.), px(20.)))
+        );
+        assert_eq!(
+            layout.position_for_index(9, line_height),
+            Some(point(px(6.), px(20.)))
+        );
+        assert_eq!(
+            layout.position_for_index(11, line_height),
+            Some(point(px(0.), px(20.)))
+        );
+    }
+
+    #[test]
+    fn test_bidi_selection_ranges() {
+        let layout = layout("ab אב cd");
+        assert_eq!(
+            layout.x_ranges_for_range(0..2).as_slice(),
+            [px(0.)..px(12.)]
+        );
+        // Selecting "b א" covers the end of the first run and the right edge of the second.
+        assert_eq!(
+            layout.x_ranges_for_range(1..5).as_slice(),
+            [px(6.)..px(18.), px(24.)..px(30.)]
+        );
+        // Selecting across the whole right-to-left run joins up with the runs around it.
+        assert_eq!(
+            layout.x_ranges_for_range(2..8).as_slice(),
+            [px(12.)..px(36.)]
+        );
+    }
+}
@@ -1,21 +1,23 @@
 #[cfg(any(feature = "inspector", debug_assertions))]
 use crate::Inspector;
 use crate::{
-    Action, AnyDrag, AnyElement, AnyImageCache, AnyTooltip, AnyView, App, AppContext, Arena, Asset,
-    AsyncWindowContext, AvailableSpace, Background, BorderStyle, Bounds, BoxShadow, Capslock,
-    Context, Corners, CursorStyle, Decorations, DevicePixels, DispatchActionListener,
-    DispatchNodeId, DispatchTree, DisplayId, Edges, Effect, Entity, EntityId, EventEmitter,
-    FileDropEvent, FontId, Global, GlobalElementId, GlyphId, GpuSpecs, Hsla, InputHandler, IsZero,
-    KeyBinding, KeyContext, KeyDownEvent, KeyEvent, Keystroke, KeystrokeEvent, LayoutId,
-    LineLayoutIndex, Modifiers, ModifiersChangedEvent, MonochromeSprite, MouseButton, MouseEvent,
-    MouseMoveEvent, MouseUpEvent, Path, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput,
-    PlatformInputHandler, PlatformWindow, Point, PolychromeSprite, PromptButton, PromptLevel, Quad,
-    Render, RenderGlyphParams, RenderImage, RenderImageParams, RenderSvgParams, Replay, ResizeEdge,
-    SMOOTH_SVG_SCALE_FACTOR, SUBPIXEL_VARIANTS, ScaledPixels, Scene, Shadow, SharedString, Size,
-    StrikethroughStyle, Style, SubscriberSet, Subscription, TaffyLayoutEngine, Task, TextStyle,
-    TextStyleRefinement, TransformationMatrix, Underline, UnderlineStyle, WindowAppearance,
-    WindowBackgroundAppearance, WindowBounds, WindowControls, WindowDecorations, WindowOptions,
-    WindowParams, WindowTextSystem, point, prelude::*, px, rems, size, transparent_black,
+    AccessibilityAction, AccessibilityNode, AccessibilityNodeId, AccessibilityOperation,
+    AccessibilityTree, Action, AnyDrag, AnyElement, AnyImageCache, AnyTooltip, AnyView, App,
+    AppContext, Arena, Asset, AsyncWindowContext, AvailableSpace, Background, BorderStyle, Bounds,
+    BoxShadow, Capslock, Context, Corners, CursorStyle, Decorations, DevicePixels,
+    DispatchActionListener, DispatchNodeId, DispatchTree, DisplayId, Edges, Effect, Entity,
+    EntityId, EventEmitter, FileDropEvent, FontId, Global, GlobalElementId, GlyphId, GpuSpecs,
+    Hsla, InputHandler, IsZero, KeyBinding, KeyContext, KeyDownEvent, KeyEvent, Keystroke,
+    KeystrokeEvent, LayoutId, LineLayoutIndex, Modifiers, ModifiersChangedEvent, MonochromeSprite,
+    MouseButton, MouseDownEvent, MouseEvent, MouseMoveEvent, MouseUpEvent, Path, Pixels,
+    PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow, Point,
+    PolychromeSprite, PromptButton, PromptLevel, Quad, Render, RenderGlyphParams, RenderImage,
+    RenderImageParams, RenderSvgParams, Replay, ResizeEdge, SMOOTH_SVG_SCALE_FACTOR,
+    SUBPIXEL_VARIANTS, ScaledPixels, Scene, Shadow, SharedString, Size, StrikethroughStyle, Style,
+    SubscriberSet, Subscription, TaffyLayoutEngine, Task, TextStyle, TextStyleRefinement,
+    TransformationMatrix, Underline, UnderlineStyle, WindowAppearance, WindowBackgroundAppearance,
+    WindowBounds, WindowControls, WindowDecorations, WindowOptions, WindowParams, WindowTextSystem,
+    point, prelude::*, px, rems, size, transparent_black,
 };
 use anyhow::{Context as _, Result, anyhow};
 use collections::{FxHashMap, FxHashSet};
@@ -636,6 +638,7 @@ pub(crate) struct Frame {
     pub(crate) input_handlers: Vec<Option<PlatformInputHandler>>,
     pub(crate) tooltip_requests: Vec<Option<TooltipRequest>>,
     pub(crate) cursor_styles: Vec<CursorStyleRequest>,
+    pub(crate) accessibility_operations: Vec<AccessibilityOperation>,
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) debug_bounds: FxHashMap<String, Bounds<Pixels>>,
     #[cfg(any(feature = "inspector", debug_assertions))]
@@ -660,6 +663,7 @@ pub(crate) struct PaintIndex {
     mouse_listeners_index: usize,
     input_handlers_index: usize,
     cursor_styles_index: usize,
+    accessibility_operations_index: usize,
     accessed_element_states_index: usize,
     line_layout_index: LineLayoutIndex,
 }
@@ -680,6 +684,7 @@ impl Frame {
             input_handlers: Vec::new(),
             tooltip_requests: Vec::new(),
             cursor_styles: Vec::new(),
+            accessibility_operations: Vec::new(),
 
             #[cfg(any(test, feature = "test-support"))]
             debug_bounds: FxHashMap::default(),
@@ -701,6 +706,7 @@ impl Frame {
         self.input_handlers.clear();
         self.tooltip_requests.clear();
         self.cursor_styles.clear();
+        self.accessibility_operations.clear();
         self.hitboxes.clear();
         self.window_control_hitboxes.clear();
         self.deferred_draws.clear();
@@ -818,6 +824,7 @@ pub struct Window {
     pub(crate) needs_present: Rc<Cell<bool>>,
     pub(crate) last_input_timestamp: Rc<Cell<Instant>>,
     pub(crate) refreshing: bool,
+    accessibility_enabled: bool,
     pub(crate) activation_observers: SubscriberSet<(), AnyObserver>,
     pub(crate) focus: Option<FocusId>,
     focus_enabled: bool,
@@ -826,6 +833,7 @@ pub struct Window {
     pub(crate) pending_input_observers: SubscriberSet<(), AnyObserver>,
     prompt: Option<RenderablePromptHandle>,
     pub(crate) client_inset: Option<Pixels>,
+    title: Option<SharedString>,
     #[cfg(any(feature = "inspector", debug_assertions))]
     inspector: Option<Entity<Inspector>>,
 }
@@ -903,6 +911,9 @@ impl Window {
         let bounds = window_bounds
             .map(|bounds| bounds.get_bounds())
             .unwrap_or_else(|| default_bounds(display_id, cx));
+        let title = titlebar
+            .as_ref()
+            .and_then(|titlebar| titlebar.title.clone());
         let mut platform_window = cx.platform.open_window(
             handle,
             WindowParams {
@@ -1061,6 +1072,16 @@ impl Window {
                     .unwrap_or(DispatchEventResult::default())
             })
         });
+        platform_window.on_accessibility_action({
+            let mut cx = cx.to_async();
+            Box::new(move |request| {
+                handle
+                    .update(&mut cx, |_, window, cx| {
+                        window.perform_accessibility_action(request.node, request.action, cx)
+                    })
+                    .log_err();
+            })
+        });
         platform_window.on_hit_test_window_control({
             let mut cx = cx.to_async();
             Box::new(move || {
@@ -1127,6 +1148,7 @@ impl Window {
             needs_present,
             last_input_timestamp,
             refreshing: false,
+            accessibility_enabled: false,
             activation_observers: SubscriberSet::new(),
             focus: None,
             focus_enabled: true,
@@ -1135,6 +1157,7 @@ impl Window {
             pending_input_observers: SubscriberSet::new(),
             prompt: None,
             client_inset: None,
+            title,
             image_cache_stack: Vec::new(),
             #[cfg(any(feature = "inspector", debug_assertions))]
             inspector: None,
@@ -1610,6 +1633,7 @@ impl Window {
 
     /// Updates the window's title at the platform level.
     pub fn set_window_title(&mut self, title: &str) {
+        self.title = Some(SharedString::from(title.to_string()));
         self.platform_window.set_title(title);
     }
 
@@ -1751,6 +1775,13 @@ impl Window {
         self.invalidator.set_dirty(false);
         self.requested_autoscroll = None;
 
+        let accessibility_enabled = self.platform_window.accessibility_enabled();
+        if accessibility_enabled && !self.accessibility_enabled {
+            // Views cached while accessibility was off recorded no nodes, so paint them again.
+            self.refreshing = true;
+        }
+        self.accessibility_enabled = accessibility_enabled;
+
         // Restore the previously-used input handler.
         if let Some(input_handler) = self.platform_window.take_input_handler() {
             self.rendered_frame.input_handlers.push(Some(input_handler));
@@ -1803,6 +1834,11 @@ impl Window {
                 .retain(&(), |listener| listener(&event, self, cx));
         }
 
+        if self.accessibility_enabled {
+            self.platform_window
+                .update_accessibility_tree(Arc::new(self.accessibility_tree()));
+        }
+
         debug_assert!(self.rendered_entity_stack.is_empty());
         self.record_entities_accessed(cx);
         self.reset_cursor_style(cx);
@@ -2117,6 +2153,7 @@ impl Window {
             mouse_listeners_index: self.next_frame.mouse_listeners.len(),
             input_handlers_index: self.next_frame.input_handlers.len(),
             cursor_styles_index: self.next_frame.cursor_styles.len(),
+            accessibility_operations_index: self.next_frame.accessibility_operations.len(),
             accessed_element_states_index: self.next_frame.accessed_element_states.len(),
             line_layout_index: self.text_system.layout_index(),
         }
@@ -2129,6 +2166,12 @@ impl Window {
                 .iter()
                 .cloned(),
         );
+        self.next_frame.accessibility_operations.extend(
+            self.rendered_frame.accessibility_operations[range.start.accessibility_operations_index
+                ..range.end.accessibility_operations_index]
+                .iter()
+                .cloned(),
+        );
         self.next_frame.input_handlers.extend(
             self.rendered_frame.input_handlers
                 [range.start.input_handlers_index..range.end.input_handlers_index]
@@ -2196,6 +2239,103 @@ impl Window {
         })
     }
 
+    /// Returns whether an assistive technology is reading this window's accessibility tree.
+    /// Elements should only build their accessibility nodes when this returns true.
+    pub fn accessibility_enabled(&self) -> bool {
+        self.accessibility_enabled
+    }
+
+    /// Records an accessibility node for the element being painted, and invokes the given
+    /// function with it as the parent of any nodes recorded by that function. If `node` is
+    /// `None`, the function's nodes are added to the current parent instead. This method
+    /// should only be called during the paint phase of element drawing.
+    pub fn with_accessibility_node<R>(
+        &mut self,
+        node: Option<AccessibilityNode>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.invalidator.debug_assert_paint();
+        if let Some(node) = node.filter(|_| self.accessibility_enabled) {
+            self.next_frame
+                .accessibility_operations
+                .push(AccessibilityOperation::Push(node));
+            let result = f(self);
+            self.next_frame
+                .accessibility_operations
+                .push(AccessibilityOperation::Pop);
+            result
+        } else {
+            f(self)
+        }
+    }
+
+    /// Records an accessibility node without children, such as a run of text. This method
+    /// should only be called during the paint phase of element drawing.
+    pub fn insert_accessibility_node(&mut self, node: AccessibilityNode) {
+        self.with_accessibility_node(Some(node), |_| {});
+    }
+
+    /// Builds the accessibility tree of the most recently rendered frame.
+    pub fn accessibility_tree(&self) -> AccessibilityTree {
+        AccessibilityTree::build(
+            &self.rendered_frame.accessibility_operations,
+            self.title.clone(),
+            Bounds::new(Point::default(), self.viewport_size),
+            self.rendered_frame.focus,
+        )
+    }
+
+    /// Performs an action on a node of the most recently rendered accessibility tree,
+    /// as requested by an assistive technology.
+    pub fn perform_accessibility_action(
+        &mut self,
+        node: AccessibilityNodeId,
+        action: AccessibilityAction,
+        cx: &mut App,
+    ) {
+        let tree = self.accessibility_tree();
+        let Some(node) = tree.node(node) else {
+            return;
+        };
+        if !node.actions.contains(&action) {
+            return;
+        }
+
+        match action {
+            AccessibilityAction::Click => {
+                let position = node.bounds.center();
+                let modifiers = Modifiers::default();
+                self.dispatch_event(
+                    PlatformInput::MouseDown(MouseDownEvent {
+                        button: MouseButton::Left,
+                        position,
+                        modifiers,
+                        click_count: 1,
+                        first_mouse: false,
+                    }),
+                    cx,
+                );
+                self.dispatch_event(
+                    PlatformInput::MouseUp(MouseUpEvent {
+                        button: MouseButton::Left,
+                        position,
+                        modifiers,
+                        click_count: 1,
+                    }),
+                    cx,
+                );
+            }
+            AccessibilityAction::Focus => {
+                if let Some(focus_handle) = node
+                    .focus_id
+                    .and_then(|id| FocusHandle::for_id(id, &cx.focus_handles))
+                {
+                    self.focus(&focus_handle);
+                }
+            }
+        }
+    }
+
     /// Sets a tooltip to be rendered for the upcoming frame. This method should only be called
     /// during the paint phase of element drawing.
     pub fn set_tooltip(&mut self, tooltip: AnyTooltip) -> TooltipId {
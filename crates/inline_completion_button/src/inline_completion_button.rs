@@ -224,6 +224,36 @@ impl Render for InlineCompletionButton {
                 );
             }
 
+            EditPredictionProvider::Fim => {
+                let icon = match all_language_settings.edit_predictions.fim.api {
+                    language_settings::FimApi::Ollama => IconName::AiOllama,
+                    language_settings::FimApi::OpenAiCompatible => IconName::Ai,
+                };
+                let tooltip_text = if all_language_settings.edit_predictions.fim.model.is_some() {
+                    "Fill-in-the-Middle"
+                } else {
+                    "Fill-in-the-Middle: No model configured"
+                };
+                let this = cx.entity().clone();
+
+                div().child(
+                    PopoverMenu::new("fim")
+                        .menu(move |window, cx| {
+                            Some(
+                                this.update(cx, |this, cx| this.build_fim_context_menu(window, cx)),
+                            )
+                        })
+                        .anchor(Corner::BottomRight)
+                        .trigger_with_tooltip(
+                            IconButton::new("fim-icon", icon),
+                            move |window, cx| {
+                                Tooltip::for_action(tooltip_text, &ToggleMenu, window, cx)
+                            },
+                        )
+                        .with_handle(self.popover_menu_handle.clone()),
+                )
+            }
+
             EditPredictionProvider::Zed => {
                 let enabled = self.editor_enabled.unwrap_or(true);
 
@@ -690,6 +720,16 @@ impl InlineCompletionButton {
         })
     }
 
+    fn build_fim_context_menu(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<ContextMenu> {
+        ContextMenu::build(window, cx, |menu, window, cx| {
+            self.build_language_settings_menu(menu, window, cx)
+        })
+    }
+
     fn build_zeta_context_menu(
         &self,
         window: &mut Window,
@@ -213,6 +213,8 @@ pub enum EditPredictionProvider {
     Copilot,
     Supermaven,
     Zed,
+    /// Fill-in-the-middle completions from a local Ollama or OpenAI-compatible server.
+    Fim,
 }
 
 impl EditPredictionProvider {
@@ -221,7 +223,8 @@ impl EditPredictionProvider {
             EditPredictionProvider::Zed => true,
             EditPredictionProvider::None
             | EditPredictionProvider::Copilot
-            | EditPredictionProvider::Supermaven => false,
+            | EditPredictionProvider::Supermaven
+            | EditPredictionProvider::Fim => false,
         }
     }
 }
@@ -240,6 +243,8 @@ pub struct EditPredictionSettings {
     pub mode: EditPredictionsMode,
     /// Settings specific to GitHub Copilot.
     pub copilot: CopilotSettings,
+    /// Settings specific to the fill-in-the-middle provider.
+    pub fim: FimSettings,
     /// Whether edit predictions are enabled in the assistant panel.
     /// This setting has no effect if globally disabled.
     pub enabled_in_text_threads: bool,
@@ -289,6 +294,57 @@ pub struct CopilotSettings {
     pub enterprise_uri: Option<String>,
 }
 
+/// The API that the fill-in-the-middle provider requests completions from.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FimApi {
+    /// Ollama's `/api/generate` endpoint.
+    #[default]
+    Ollama,
+    /// The `/completions` endpoint of an OpenAI-compatible server, such as llama.cpp,
+    /// vLLM, or LM Studio.
+    OpenAiCompatible,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct FimSettings {
+    /// The API to request completions from.
+    pub api: FimApi,
+    /// The URL of the server.
+    pub api_url: Option<String>,
+    /// The model to request completions from.
+    pub model: Option<String>,
+    /// The name of the prompt template to use, or a custom template.
+    pub template: Option<String>,
+    /// The maximum number of tokens to generate.
+    pub max_tokens: Option<u32>,
+    /// How long to wait after the last edit before requesting a completion.
+    pub debounce_ms: Option<u64>,
+}
+
+impl FimSettings {
+    fn merge(&mut self, content: &FimSettingsContent) {
+        if let Some(api) = content.api {
+            self.api = api;
+        }
+        if let Some(api_url) = &content.api_url {
+            self.api_url = Some(api_url.clone());
+        }
+        if let Some(model) = &content.model {
+            self.model = Some(model.clone());
+        }
+        if let Some(template) = &content.template {
+            self.template = Some(template.clone());
+        }
+        if let Some(max_tokens) = content.max_tokens {
+            self.max_tokens = Some(max_tokens);
+        }
+        if let Some(debounce_ms) = content.debounce_ms {
+            self.debounce_ms = Some(debounce_ms);
+        }
+    }
+}
+
 /// The settings for all languages.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct AllLanguageSettingsContent {
@@ -617,6 +673,9 @@ pub struct EditPredictionSettingsContent {
     /// Settings specific to GitHub Copilot.
     #[serde(default)]
     pub copilot: CopilotSettingsContent,
+    /// Settings specific to the fill-in-the-middle provider.
+    #[serde(default)]
+    pub fim: FimSettingsContent,
     /// Whether edit predictions are enabled in the assistant prompt editor.
     /// This has no effect if globally disabled.
     #[serde(default = "default_true")]
@@ -642,6 +701,43 @@ pub struct CopilotSettingsContent {
     pub enterprise_uri: Option<String>,
 }
 
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct FimSettingsContent {
+    /// The API to request completions from.
+    ///
+    /// Default: ollama
+    #[serde(default)]
+    pub api: Option<FimApi>,
+    /// The URL of the server.
+    ///
+    /// Default: "http://localhost:11434" for Ollama, "http://localhost:8080/v1" otherwise
+    #[serde(default)]
+    pub api_url: Option<String>,
+    /// The model to request completions from.
+    ///
+    /// Default: none
+    #[serde(default)]
+    pub model: Option<String>,
+    /// The prompt template to use: one of "code_llama", "codegemma", "codestral",
+    /// "deepseek_coder", "qwen_coder", "starcoder", or "native" to let the server apply
+    /// the model's own template. A custom template may be given instead, with `{prefix}`
+    /// and `{suffix}` placeholders.
+    ///
+    /// Default: inferred from the model name
+    #[serde(default)]
+    pub template: Option<String>,
+    /// The maximum number of tokens to generate.
+    ///
+    /// Default: 128
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+    /// How long to wait after the last edit before requesting a completion, in milliseconds.
+    ///
+    /// Default: 150
+    #[serde(default)]
+    pub debounce_ms: Option<u64>,
+}
+
 /// The settings for enabling/disabling features.
 #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -693,7 +789,7 @@ impl JsonSchema for FormatOnSave {
                 },
                 {
                     "type": "string",
-                    "enum": ["on", "off", "prettier", "language_server"]
+                    "enum": ["on", "off", "prettier", "language_server", "extension"]
                 },
                 formatter_schema
             ]
@@ -739,6 +835,8 @@ impl<'de> Deserialize<'de> for FormatOnSave {
                     Ok(Self::Value::List(vec![Formatter::LanguageServer {
                         name: None,
                     }]))
+                } else if v == "extension" {
+                    Ok(Self::Value::List(vec![Formatter::Extension { name: None }]))
                 } else {
                     let ret: Result<Vec<Formatter>, _> =
                         Deserialize::deserialize(v.into_deserializer());
@@ -813,7 +911,7 @@ impl JsonSchema for SelectedFormatter {
                 },
                 {
                     "type": "string",
-                    "enum": ["auto", "prettier", "language_server"]
+                    "enum": ["auto", "prettier", "language_server", "extension"]
                 },
                 formatter_schema
             ]
@@ -856,6 +954,8 @@ impl<'de> Deserialize<'de> for SelectedFormatter {
                     Ok(Self::Value::List(vec![Formatter::LanguageServer {
                         name: None,
                     }]))
+                } else if v == "extension" {
+                    Ok(Self::Value::List(vec![Formatter::Extension { name: None }]))
                 } else {
                     let ret: Result<Vec<Formatter>, _> =
                         Deserialize::deserialize(v.into_deserializer());
@@ -900,6 +1000,8 @@ pub enum Formatter {
     },
     /// Files should be formatted using code actions executed by language servers.
     CodeActions(HashMap<String, bool>),
+    /// Format code using a formatter provided by an extension.
+    Extension { name: Option<String> },
 }
 
 /// The settings for indent guides.
@@ -1226,6 +1328,11 @@ impl settings::Settings for AllLanguageSettings {
             })
             .unwrap_or_default();
 
+        let mut fim_settings = FimSettings::default();
+        if let Some(edit_predictions) = default_value.edit_predictions.as_ref() {
+            fim_settings.merge(&edit_predictions.fim);
+        }
+
         let mut enabled_in_text_threads = default_value
             .edit_predictions
             .as_ref()
@@ -1256,6 +1363,7 @@ impl settings::Settings for AllLanguageSettings {
             if let Some(edit_predictions) = user_settings.edit_predictions.as_ref() {
                 edit_predictions_mode = edit_predictions.mode;
                 enabled_in_text_threads = edit_predictions.enabled_in_text_threads;
+                fim_settings.merge(&edit_predictions.fim);
 
                 if let Some(disabled_globs) = edit_predictions.disabled_globs.as_ref() {
                     completion_globs.extend(disabled_globs.iter());
@@ -1342,6 +1450,7 @@ impl settings::Settings for AllLanguageSettings {
                     .collect(),
                 mode: edit_predictions_mode,
                 copilot: copilot_settings,
+                fim: fim_settings,
                 enabled_in_text_threads,
             },
             defaults,
@@ -1634,6 +1743,22 @@ mod tests {
                 Formatter::Prettier
             ]))
         );
+        let raw = "{\"formatter\": \"extension\"}";
+        let settings: LanguageSettingsContent = serde_json::from_str(raw).unwrap();
+        assert_eq!(
+            settings.formatter,
+            Some(SelectedFormatter::List(vec![Formatter::Extension {
+                name: None
+            }]))
+        );
+        let raw = "{\"formatter\": {\"extension\": {\"name\": \"sql-formatter\"}}}";
+        let settings: LanguageSettingsContent = serde_json::from_str(raw).unwrap();
+        assert_eq!(
+            settings.formatter,
+            Some(SelectedFormatter::List(vec![Formatter::Extension {
+                name: Some("sql-formatter".into())
+            }]))
+        );
     }
 
     #[test]
@@ -0,0 +1,1049 @@
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use credentials_provider::CredentialsProvider;
+use editor::{Editor, EditorElement, EditorStyle};
+use extension::{
+    Extension, ExtensionHostProxy, ExtensionLanguageModelProviderProxy,
+    LanguageModelProviderManifestEntry, LlmCompletionEvent, LlmCompletionRequest,
+    LlmCompletionStreamDelegate, LlmImage, LlmMessage, LlmMessageContent, LlmModel, LlmRole,
+    LlmStopReason, LlmThinking, LlmTool, LlmToolChoice, LlmToolResult, LlmToolResultContent,
+    LlmToolUse,
+};
+use futures::{FutureExt, StreamExt, channel::mpsc, future::BoxFuture, stream::BoxStream};
+use gpui::{AnyView, AppContext as _, AsyncApp, Entity, FontStyle, Task, TextStyle, WhiteSpace};
+use language_model::{
+    AuthenticateError, LanguageModel, LanguageModelCompletionError, LanguageModelCompletionEvent,
+    LanguageModelId, LanguageModelImage, LanguageModelName, LanguageModelProvider,
+    LanguageModelProviderId, LanguageModelProviderName, LanguageModelProviderState,
+    LanguageModelRegistry, LanguageModelRequest, LanguageModelToolChoice,
+    LanguageModelToolResultContent, LanguageModelToolUse, MessageContent, RateLimiter, Role,
+    StopReason, TokenUsage,
+};
+use settings::Settings;
+use theme::ThemeSettings;
+use ui::{Icon, IconName, prelude::*};
+use util::ResultExt;
+
+pub fn init(cx: &mut App) {
+    let proxy = ExtensionHostProxy::default_global(cx);
+    proxy.register_language_model_provider_proxy(LanguageModelRegistryProxy);
+}
+
+struct LanguageModelRegistryProxy;
+
+impl ExtensionLanguageModelProviderProxy for LanguageModelRegistryProxy {
+    fn register_language_model_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider_id: Arc<str>,
+        provider: LanguageModelProviderManifestEntry,
+        cx: &mut App,
+    ) {
+        let provider = ExtensionLanguageModelProvider::new(extension, provider_id, provider, cx);
+        let authenticate = provider.authenticate(cx);
+        LanguageModelRegistry::global(cx).update(cx, |registry, cx| {
+            registry.register_provider(provider, cx);
+        });
+
+        cx.spawn(async move |_| {
+            if let Err(error) = authenticate.await {
+                if !matches!(error, AuthenticateError::CredentialsNotFound) {
+                    log::error!(
+                        "failed to authenticate extension language model provider: {error}"
+                    );
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn unregister_language_model_provider(
+        &self,
+        extension_id: Arc<str>,
+        provider_id: Arc<str>,
+        cx: &mut App,
+    ) {
+        LanguageModelRegistry::global(cx).update(cx, |registry, cx| {
+            registry.unregister_provider(extension_provider_id(&extension_id, &provider_id), cx);
+        });
+    }
+}
+
+/// Returns the ID under which a provider from an extension is registered. Extensions choose
+/// their provider IDs independently, so the ID includes the extension's ID.
+fn extension_provider_id(extension_id: &str, provider_id: &str) -> LanguageModelProviderId {
+    LanguageModelProviderId(format!("extension/{extension_id}/{provider_id}").into())
+}
+
+/// A language model provider implemented by an extension.
+pub struct ExtensionLanguageModelProvider {
+    id: LanguageModelProviderId,
+    extension: Arc<dyn Extension>,
+    /// The ID of the provider within the extension.
+    provider_id: Arc<str>,
+    name: LanguageModelProviderName,
+    state: Entity<State>,
+}
+
+pub struct State {
+    extension: Arc<dyn Extension>,
+    provider_id: Arc<str>,
+    requires_credential: bool,
+    has_credential: bool,
+    models: Vec<LlmModel>,
+    fetch_models_task: Option<Task<Result<()>>>,
+}
+
+impl State {
+    fn credentials_url(&self) -> String {
+        extension::llm_provider_credentials_url(&self.extension.manifest().id, &self.provider_id)
+    }
+
+    fn is_authenticated(&self) -> bool {
+        !self.requires_credential || self.has_credential
+    }
+
+    fn fetch_models(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let extension = self.extension.clone();
+        let provider_id = self.provider_id.clone();
+        cx.spawn(async move |this, cx| {
+            let models = extension.llm_provider_models(provider_id).await?;
+            this.update(cx, |this, cx| {
+                this.models = models;
+                cx.notify();
+            })
+        })
+    }
+
+    fn restart_fetch_models_task(&mut self, cx: &mut Context<Self>) {
+        let task = self.fetch_models(cx);
+        self.fetch_models_task.replace(task);
+    }
+
+    fn set_credential(&mut self, credential: String, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let credentials_provider = <dyn CredentialsProvider>::global(cx);
+        let credentials_url = self.credentials_url();
+        cx.spawn(async move |this, cx| {
+            credentials_provider
+                .write_credentials(&credentials_url, "Bearer", credential.as_bytes(), &cx)
+                .await?;
+            this.update(cx, |this, cx| {
+                this.has_credential = true;
+                this.restart_fetch_models_task(cx);
+                cx.notify();
+            })
+        })
+    }
+
+    fn reset_credential(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let credentials_provider = <dyn CredentialsProvider>::global(cx);
+        let credentials_url = self.credentials_url();
+        cx.spawn(async move |this, cx| {
+            credentials_provider
+                .delete_credentials(&credentials_url, &cx)
+                .await
+                .log_err();
+            this.update(cx, |this, cx| {
+                this.has_credential = false;
+                this.models.clear();
+                cx.notify();
+            })
+        })
+    }
+
+    fn authenticate(&mut self, cx: &mut Context<Self>) -> Task<Result<(), AuthenticateError>> {
+        if self.is_authenticated() && !self.models.is_empty() {
+            return Task::ready(Ok(()));
+        }
+
+        let requires_credential = self.requires_credential && !self.has_credential;
+        let credentials_provider = <dyn CredentialsProvider>::global(cx);
+        let credentials_url = self.credentials_url();
+        cx.spawn(async move |this, cx| {
+            if requires_credential {
+                credentials_provider
+                    .read_credentials(&credentials_url, &cx)
+                    .await?
+                    .ok_or(AuthenticateError::CredentialsNotFound)?;
+                this.update(cx, |this, cx| {
+                    this.has_credential = true;
+                    cx.notify();
+                })?;
+            }
+
+            let fetch_models = this.update(cx, |this, cx| this.fetch_models(cx))?;
+            fetch_models.await?;
+            Ok(())
+        })
+    }
+}
+
+impl ExtensionLanguageModelProvider {
+    pub fn new(
+        extension: Arc<dyn Extension>,
+        provider_id: Arc<str>,
+        provider: LanguageModelProviderManifestEntry,
+        cx: &mut App,
+    ) -> Self {
+        let state = cx.new(|_| State {
+            extension: extension.clone(),
+            provider_id: provider_id.clone(),
+            requires_credential: provider.requires_credential,
+            has_credential: false,
+            models: Vec::new(),
+            fetch_models_task: None,
+        });
+
+        Self {
+            id: extension_provider_id(&extension.manifest().id, &provider_id),
+            extension,
+            provider_id,
+            name: LanguageModelProviderName(provider.name.into()),
+            state,
+        }
+    }
+
+    fn create_language_model(&self, model: LlmModel) -> Arc<dyn LanguageModel> {
+        Arc::new(ExtensionLanguageModel {
+            id: LanguageModelId::from(model.id.clone()),
+            model,
+            extension: self.extension.clone(),
+            provider: self.id.clone(),
+            provider_id: self.provider_id.clone(),
+            provider_name: self.name.clone(),
+            request_limiter: RateLimiter::new(4),
+        }) as Arc<dyn LanguageModel>
+    }
+}
+
+impl LanguageModelProviderState for ExtensionLanguageModelProvider {
+    type ObservableEntity = State;
+
+    fn observable_entity(&self) -> Option<Entity<Self::ObservableEntity>> {
+        Some(self.state.clone())
+    }
+}
+
+impl LanguageModelProvider for ExtensionLanguageModelProvider {
+    fn id(&self) -> LanguageModelProviderId {
+        self.id.clone()
+    }
+
+    fn name(&self) -> LanguageModelProviderName {
+        self.name.clone()
+    }
+
+    fn default_model(&self, cx: &App) -> Option<Arc<dyn LanguageModel>> {
+        let models = &self.state.read(cx).models;
+        models
+            .iter()
+            .find(|model| model.is_default)
+            .or_else(|| models.first())
+            .map(|model| self.create_language_model(model.clone()))
+    }
+
+    fn default_fast_model(&self, cx: &App) -> Option<Arc<dyn LanguageModel>> {
+        self.state
+            .read(cx)
+            .models
+            .iter()
+            .find(|model| model.is_default_fast)
+            .map(|model| self.create_language_model(model.clone()))
+    }
+
+    fn provided_models(&self, cx: &App) -> Vec<Arc<dyn LanguageModel>> {
+        self.state
+            .read(cx)
+            .models
+            .iter()
+            .map(|model| self.create_language_model(model.clone()))
+            .collect()
+    }
+
+    fn is_authenticated(&self, cx: &App) -> bool {
+        self.state.read(cx).is_authenticated()
+    }
+
+    fn authenticate(&self, cx: &mut App) -> Task<Result<(), AuthenticateError>> {
+        self.state.update(cx, |state, cx| state.authenticate(cx))
+    }
+
+    fn configuration_view(&self, window: &mut Window, cx: &mut App) -> AnyView {
+        let extension_name = self.extension.manifest().name.clone();
+        cx.new(|cx| {
+            ConfigurationView::new(
+                self.state.clone(),
+                self.name.0.clone(),
+                extension_name,
+                window,
+                cx,
+            )
+        })
+        .into()
+    }
+
+    fn reset_credentials(&self, cx: &mut App) -> Task<Result<()>> {
+        self.state
+            .update(cx, |state, cx| state.reset_credential(cx))
+    }
+}
+
+pub struct ExtensionLanguageModel {
+    id: LanguageModelId,
+    model: LlmModel,
+    extension: Arc<dyn Extension>,
+    provider: LanguageModelProviderId,
+    /// The ID of the model's provider within the extension.
+    provider_id: Arc<str>,
+    provider_name: LanguageModelProviderName,
+    request_limiter: RateLimiter,
+}
+
+impl LanguageModel for ExtensionLanguageModel {
+    fn id(&self) -> LanguageModelId {
+        self.id.clone()
+    }
+
+    fn name(&self) -> LanguageModelName {
+        LanguageModelName::from(self.model.name.clone())
+    }
+
+    fn provider_id(&self) -> LanguageModelProviderId {
+        self.provider.clone()
+    }
+
+    fn provider_name(&self) -> LanguageModelProviderName {
+        self.provider_name.clone()
+    }
+
+    fn supports_tools(&self) -> bool {
+        self.model.capabilities.supports_tools
+    }
+
+    fn supports_tool_choice(&self, choice: LanguageModelToolChoice) -> bool {
+        match choice {
+            LanguageModelToolChoice::Auto => self.model.capabilities.supports_tools,
+            LanguageModelToolChoice::Any | LanguageModelToolChoice::None => {
+                self.model.capabilities.supports_tool_choice
+            }
+        }
+    }
+
+    fn supports_images(&self) -> bool {
+        self.model.capabilities.supports_images
+    }
+
+    fn telemetry_id(&self) -> String {
+        format!("{}/{}", self.provider.0, self.model.id)
+    }
+
+    fn max_token_count(&self) -> u64 {
+        self.model.max_token_count
+    }
+
+    fn max_output_tokens(&self) -> Option<u64> {
+        self.model.max_output_tokens
+    }
+
+    fn count_tokens(
+        &self,
+        request: LanguageModelRequest,
+        _cx: &App,
+    ) -> BoxFuture<'static, Result<u64>> {
+        let extension = self.extension.clone();
+        let provider_id = self.provider_id.clone();
+        let model_id: Arc<str> = self.model.id.as_str().into();
+        let request = into_extension_request(request);
+        async move {
+            extension
+                .llm_count_tokens(provider_id, model_id, request)
+                .await
+        }
+        .boxed()
+    }
+
+    fn stream_completion(
+        &self,
+        request: LanguageModelRequest,
+        cx: &AsyncApp,
+    ) -> BoxFuture<
+        'static,
+        Result<
+            BoxStream<'static, Result<LanguageModelCompletionEvent, LanguageModelCompletionError>>,
+            LanguageModelCompletionError,
+        >,
+    > {
+        let extension = self.extension.clone();
+        let provider_id = self.provider_id.clone();
+        let model_id: Arc<str> = self.model.id.as_str().into();
+        let request = into_extension_request(request);
+        let executor = cx.background_executor().clone();
+        let future = self.request_limiter.stream(async move {
+            let (tx, rx) = mpsc::unbounded();
+            executor
+                .spawn(async move {
+                    let stream = Arc::new(ExtensionCompletionStream(tx.clone()));
+                    if let Err(error) = extension
+                        .llm_stream_completion(provider_id, model_id, request, stream)
+                        .await
+                    {
+                        tx.unbounded_send(Err(LanguageModelCompletionError::Other(error)))
+                            .ok();
+                    }
+                    // The extension may keep its handle to the stream, so close the channel
+                    // for every sender to end the stream.
+                    tx.close_channel();
+                })
+                .detach();
+            Ok(rx)
+        });
+        async move { Ok(future.await?.boxed()) }.boxed()
+    }
+}
+
+type CompletionEventResult = Result<LanguageModelCompletionEvent, LanguageModelCompletionError>;
+
+/// Forwards the events that an extension emits to the consumer of the completion.
+struct ExtensionCompletionStream(mpsc::UnboundedSender<CompletionEventResult>);
+
+impl LlmCompletionStreamDelegate for ExtensionCompletionStream {
+    fn emit(&self, event: LlmCompletionEvent) -> Result<()> {
+        self.0
+            .unbounded_send(into_completion_event(event))
+            .map_err(|_| anyhow!("completion was cancelled"))
+    }
+}
+
+fn into_completion_event(event: LlmCompletionEvent) -> CompletionEventResult {
+    Ok(match event {
+        LlmCompletionEvent::Text(text) => LanguageModelCompletionEvent::Text(text),
+        LlmCompletionEvent::Thinking(thinking) => LanguageModelCompletionEvent::Thinking {
+            text: thinking.text,
+            signature: thinking.signature,
+        },
+        LlmCompletionEvent::ToolUse(tool_use) => match serde_json::from_str(&tool_use.input) {
+            Ok(input) => LanguageModelCompletionEvent::ToolUse(LanguageModelToolUse {
+                id: tool_use.id.into(),
+                name: tool_use.name.into(),
+                is_input_complete: true,
+                input,
+                raw_input: tool_use.input,
+            }),
+            Err(error) => {
+                return Err(LanguageModelCompletionError::BadInputJson {
+                    id: tool_use.id.into(),
+                    tool_name: tool_use.name.into(),
+                    raw_input: tool_use.input.into(),
+                    json_parse_error: error.to_string(),
+                });
+            }
+        },
+        LlmCompletionEvent::Usage(usage) => LanguageModelCompletionEvent::UsageUpdate(TokenUsage {
+            input_tokens: usage.input_tokens,
+            output_tokens: usage.output_tokens,
+            cache_creation_input_tokens: usage.cache_creation_input_tokens,
+            cache_read_input_tokens: usage.cache_read_input_tokens,
+        }),
+        LlmCompletionEvent::Stop(reason) => LanguageModelCompletionEvent::Stop(match reason {
+            LlmStopReason::EndTurn => StopReason::EndTurn,
+            LlmStopReason::MaxTokens => StopReason::MaxTokens,
+            LlmStopReason::ToolUse => StopReason::ToolUse,
+            LlmStopReason::Refusal => StopReason::Refusal,
+        }),
+    })
+}
+
+fn into_extension_request(request: LanguageModelRequest) -> LlmCompletionRequest {
+    LlmCompletionRequest {
+        messages: request
+            .messages
+            .into_iter()
+            .map(|message| LlmMessage {
+                role: match message.role {
+                    Role::User => LlmRole::User,
+                    Role::Assistant => LlmRole::Assistant,
+                    Role::System => LlmRole::System,
+                },
+                content: message
+                    .content
+                    .into_iter()
+                    .map(into_extension_message_content)
+                    .collect(),
+                cache: message.cache,
+            })
+            .collect(),
+        tools: request
+            .tools
+            .into_iter()
+            .map(|tool| LlmTool {
+                name: tool.name,
+                description: tool.description,
+                input_schema: tool.input_schema.to_string(),
+            })
+            .collect(),
+        tool_choice: request.tool_choice.map(|choice| match choice {
+            LanguageModelToolChoice::Auto => LlmToolChoice::Auto,
+            LanguageModelToolChoice::Any => LlmToolChoice::Any,
+            LanguageModelToolChoice::None => LlmToolChoice::None,
+        }),
+        stop: request.stop,
+        temperature: request.temperature,
+    }
+}
+
+fn into_extension_message_content(content: MessageContent) -> LlmMessageContent {
+    match content {
+        MessageContent::Text(text) => LlmMessageContent::Text(text),
+        MessageContent::Thinking { text, signature } => {
+            LlmMessageContent::Thinking(LlmThinking { text, signature })
+        }
+        MessageContent::RedactedThinking(data) => LlmMessageContent::RedactedThinking(data),
+        MessageContent::Image(image) => LlmMessageContent::Image(into_extension_image(image)),
+        MessageContent::ToolUse(tool_use) => LlmMessageContent::ToolUse(LlmToolUse {
+            id: tool_use.id.to_string(),
+            name: tool_use.name.to_string(),
+            input: tool_use.input.to_string(),
+        }),
+        MessageContent::ToolResult(tool_result) => LlmMessageContent::ToolResult(LlmToolResult {
+            tool_use_id: tool_result.tool_use_id.to_string(),
+            tool_name: tool_result.tool_name.to_string(),
+            is_error: tool_result.is_error,
+            content: match tool_result.content {
+                LanguageModelToolResultContent::Text(text) => {
+                    LlmToolResultContent::Text(text.to_string())
+                }
+                LanguageModelToolResultContent::Image(image) => {
+                    LlmToolResultContent::Image(into_extension_image(image))
+                }
+            },
+        }),
+    }
+}
+
+fn into_extension_image(image: LanguageModelImage) -> LlmImage {
+    LlmImage {
+        source: image.source.to_string(),
+        width: image.size.width.0 as u32,
+        height: image.size.height.0 as u32,
+    }
+}
+
+struct ConfigurationView {
+    credential_editor: Entity<Editor>,
+    state: Entity<State>,
+    provider_name: SharedString,
+    extension_name: String,
+    load_credentials_task: Option<Task<()>>,
+}
+
+impl ConfigurationView {
+    fn new(
+        state: Entity<State>,
+        provider_name: SharedString,
+        extension_name: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let credential_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("API key", cx);
+            editor
+        });
+
+        cx.observe(&state, |_, _, cx| {
+            cx.notify();
+        })
+        .detach();
+
+        let load_credentials_task = Some(cx.spawn({
+            let state = state.clone();
+            async move |this, cx| {
+                if let Some(task) = state
+                    .update(cx, |state, cx| state.authenticate(cx))
+                    .log_err()
+                {
+                    let _ = task.await;
+                }
+
+                this.update(cx, |this, cx| {
+                    this.load_credentials_task = None;
+                    cx.notify();
+                })
+                .log_err();
+            }
+        }));
+
+        Self {
+            credential_editor,
+            state,
+            provider_name,
+            extension_name,
+            load_credentials_task,
+        }
+    }
+
+    fn save_credential(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let credential = self.credential_editor.read(cx).text(cx);
+        if credential.is_empty() {
+            return;
+        }
+
+        let state = self.state.clone();
+        cx.spawn(async move |_, cx| {
+            state
+                .update(cx, |state, cx| state.set_credential(credential, cx))?
+                .await
+        })
+        .detach_and_log_err(cx);
+
+        cx.notify();
+    }
+
+    fn reset_credential(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.credential_editor
+            .update(cx, |editor, cx| editor.set_text("", window, cx));
+
+        let state = self.state.clone();
+        cx.spawn(async move |_, cx| {
+            state
+                .update(cx, |state, cx| state.reset_credential(cx))?
+                .await
+        })
+        .detach_and_log_err(cx);
+
+        cx.notify();
+    }
+
+    fn render_credential_editor(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let settings = ThemeSettings::get_global(cx);
+        let text_style = TextStyle {
+            color: cx.theme().colors().text,
+            font_family: settings.ui_font.family.clone(),
+            font_features: settings.ui_font.features.clone(),
+            font_fallbacks: settings.ui_font.fallbacks.clone(),
+            font_size: rems(0.875).into(),
+            font_weight: settings.ui_font.weight,
+            font_style: FontStyle::Normal,
+            line_height: relative(1.3),
+            background_color: None,
+            underline: None,
+            strikethrough: None,
+            white_space: WhiteSpace::Normal,
+            ..Default::default()
+        };
+        EditorElement::new(
+            &self.credential_editor,
+            EditorStyle {
+                background: cx.theme().colors().editor_background,
+                local_player: cx.theme().players().local(),
+                text: text_style,
+                ..Default::default()
+            },
+        )
+    }
+}
+
+impl Render for ConfigurationView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = self.state.read(cx);
+        let description = format!(
+            "{} is provided by the {} extension.",
+            self.provider_name, self.extension_name
+        );
+
+        if self.load_credentials_task.is_some() {
+            div().child(Label::new("Loading credentials...")).into_any()
+        } else if !state.requires_credential {
+            v_flex()
+                .size_full()
+                .child(Label::new(description))
+                .into_any()
+        } else if !state.has_credential {
+            v_flex()
+                .size_full()
+                .on_action(cx.listener(Self::save_credential))
+                .child(Label::new(description))
+                .child(Label::new(format!(
+                    "To use {} in Zed, paste your API key below and hit enter.",
+                    self.provider_name
+                )))
+                .child(
+                    h_flex()
+                        .w_full()
+                        .my_2()
+                        .px_2()
+                        .py_1()
+                        .bg(cx.theme().colors().editor_background)
+                        .border_1()
+                        .border_color(cx.theme().colors().border)
+                        .rounded_sm()
+                        .child(self.render_credential_editor(cx)),
+                )
+                .into_any()
+        } else {
+            h_flex()
+                .mt_1()
+                .p_1()
+                .justify_between()
+                .rounded_md()
+                .border_1()
+                .border_color(cx.theme().colors().border)
+                .bg(cx.theme().colors().background)
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(Icon::new(IconName::Check).color(Color::Success))
+                        .child(Label::new("API key configured")),
+                )
+                .child(
+                    Button::new("reset-key", "Reset Key")
+                        .label_size(LabelSize::Small)
+                        .icon(Some(IconName::Trash))
+                        .icon_size(IconSize::Small)
+                        .icon_position(IconPosition::Start)
+                        .on_click(
+                            cx.listener(|this, _, window, cx| this.reset_credential(window, cx)),
+                        ),
+                )
+                .into_any()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use credentials_provider::FakeCredentialsProvider;
+    use extension::{FakeExtension, LlmModelCapabilities, LlmTokenUsage};
+    use gpui::TestAppContext;
+    use language_model::LanguageModelRequestMessage;
+    use std::sync::Mutex;
+
+    fn init_test(cx: &mut TestAppContext) -> Arc<FakeCredentialsProvider> {
+        let credentials_provider = Arc::new(FakeCredentialsProvider::default());
+        cx.update(|cx| {
+            language_model::init_settings(cx);
+            <dyn CredentialsProvider>::set_global(credentials_provider.clone(), cx);
+        });
+        credentials_provider
+    }
+
+    fn fake_extension(id: &str) -> Arc<FakeExtension> {
+        let extension = FakeExtension::new(id);
+        extension.on_llm_provider_models(|provider_id| {
+            Ok(vec![LlmModel {
+                id: format!("{provider_id}-model"),
+                name: "Model".into(),
+                max_token_count: 100_000,
+                max_output_tokens: None,
+                capabilities: LlmModelCapabilities {
+                    supports_tools: true,
+                    ..Default::default()
+                },
+                is_default: true,
+                is_default_fast: false,
+            }])
+        });
+        extension
+    }
+
+    fn manifest_entry(requires_credential: bool) -> LanguageModelProviderManifestEntry {
+        LanguageModelProviderManifestEntry {
+            name: "Gateway".into(),
+            requires_credential,
+        }
+    }
+
+    fn registered_provider_ids(cx: &mut TestAppContext) -> Vec<LanguageModelProviderId> {
+        cx.update(|cx| {
+            LanguageModelRegistry::read_global(cx)
+                .providers()
+                .iter()
+                .map(|provider| provider.id())
+                .collect()
+        })
+    }
+
+    fn user_request(text: &str) -> LanguageModelRequest {
+        LanguageModelRequest {
+            messages: vec![LanguageModelRequestMessage {
+                role: Role::User,
+                content: vec![MessageContent::Text(text.into())],
+                cache: false,
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[gpui::test]
+    async fn test_provider_ids_are_namespaced_by_extension(cx: &mut TestAppContext) {
+        init_test(cx);
+        let first_extension = fake_extension("first-extension");
+        let second_extension = fake_extension("second-extension");
+
+        cx.update(|cx| {
+            for extension in [first_extension.clone(), second_extension.clone()] {
+                LanguageModelRegistryProxy.register_language_model_provider(
+                    extension,
+                    "gateway".into(),
+                    manifest_entry(false),
+                    cx,
+                );
+            }
+        });
+        cx.run_until_parked();
+
+        let first_id = LanguageModelProviderId("extension/first-extension/gateway".into());
+        let second_id = LanguageModelProviderId("extension/second-extension/gateway".into());
+        let provider_ids = registered_provider_ids(cx);
+        assert!(provider_ids.contains(&first_id), "{provider_ids:?}");
+        assert!(provider_ids.contains(&second_id), "{provider_ids:?}");
+        cx.update(|cx| {
+            let provider = LanguageModelRegistry::read_global(cx)
+                .provider(&second_id)
+                .unwrap();
+            let model = provider.default_model(cx).unwrap();
+            assert_eq!(model.provider_id(), second_id);
+        });
+
+        cx.update(|cx| {
+            LanguageModelRegistryProxy.unregister_language_model_provider(
+                "first-extension".into(),
+                "gateway".into(),
+                cx,
+            )
+        });
+        let provider_ids = registered_provider_ids(cx);
+        assert!(!provider_ids.contains(&first_id), "{provider_ids:?}");
+        assert!(provider_ids.contains(&second_id), "{provider_ids:?}");
+    }
+
+    #[gpui::test]
+    async fn test_reading_and_writing_credentials(cx: &mut TestAppContext) {
+        let credentials_provider = init_test(cx);
+        let extension = fake_extension("my-extension");
+        let credentials_url = extension::llm_provider_credentials_url("my-extension", "gateway");
+        let provider = cx.update(|cx| {
+            ExtensionLanguageModelProvider::new(
+                extension.clone(),
+                "gateway".into(),
+                manifest_entry(true),
+                cx,
+            )
+        });
+
+        // Without a credential, the provider doesn't offer any models.
+        let result = cx.update(|cx| provider.authenticate(cx)).await;
+        assert!(
+            matches!(result, Err(AuthenticateError::CredentialsNotFound)),
+            "{result:?}"
+        );
+        cx.update(|cx| {
+            assert!(!provider.is_authenticated(cx));
+            assert!(provider.provided_models(cx).is_empty());
+        });
+
+        provider
+            .state
+            .update(cx, |state, cx| state.set_credential("secret".into(), cx))
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(
+            credentials_provider.credentials(&credentials_url),
+            Some(("Bearer".into(), b"secret".to_vec()))
+        );
+        cx.update(|cx| {
+            assert!(provider.is_authenticated(cx));
+            assert_eq!(provider.provided_models(cx).len(), 1);
+        });
+
+        // A provider that is loaded later reads the stored credential.
+        let reloaded_provider = cx.update(|cx| {
+            ExtensionLanguageModelProvider::new(
+                extension.clone(),
+                "gateway".into(),
+                manifest_entry(true),
+                cx,
+            )
+        });
+        cx.update(|cx| reloaded_provider.authenticate(cx))
+            .await
+            .unwrap();
+        cx.update(|cx| {
+            assert!(reloaded_provider.is_authenticated(cx));
+            assert_eq!(reloaded_provider.provided_models(cx).len(), 1);
+        });
+
+        cx.update(|cx| provider.reset_credentials(cx))
+            .await
+            .unwrap();
+        assert_eq!(credentials_provider.credentials(&credentials_url), None);
+        cx.update(|cx| {
+            assert!(!provider.is_authenticated(cx));
+            assert!(provider.provided_models(cx).is_empty());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_streaming_completions(cx: &mut TestAppContext) {
+        init_test(cx);
+        let extension = fake_extension("my-extension");
+        let retained_stream = Arc::new(Mutex::new(None));
+        extension.on_llm_stream_completion({
+            let retained_stream = retained_stream.clone();
+            move |provider_id, model_id, request, stream| {
+                assert_eq!(provider_id.as_ref(), "gateway");
+                assert_eq!(model_id.as_ref(), "gateway-model");
+                assert!(matches!(
+                    request.messages[0].content.as_slice(),
+                    [LlmMessageContent::Text(text)] if text == "Read main.rs"
+                ));
+
+                stream.emit(LlmCompletionEvent::Text("Reading it.".into()))?;
+                stream.emit(LlmCompletionEvent::ToolUse(LlmToolUse {
+                    id: "call-1".into(),
+                    name: "read_file".into(),
+                    input: r#"{"path":"src/main.rs"}"#.into(),
+                }))?;
+                stream.emit(LlmCompletionEvent::Usage(LlmTokenUsage {
+                    input_tokens: 10,
+                    output_tokens: 5,
+                    ..Default::default()
+                }))?;
+                stream.emit(LlmCompletionEvent::Stop(LlmStopReason::ToolUse))?;
+
+                // Extensions may keep the stream after the completion has finished.
+                *retained_stream.lock().unwrap() = Some(stream);
+                Ok(())
+            }
+        });
+        let provider = cx.update(|cx| {
+            ExtensionLanguageModelProvider::new(
+                extension,
+                "gateway".into(),
+                manifest_entry(false),
+                cx,
+            )
+        });
+        cx.update(|cx| provider.authenticate(cx)).await.unwrap();
+        let model = cx.update(|cx| provider.default_model(cx)).unwrap();
+
+        let events = model
+            .stream_completion(user_request("Read main.rs"), &cx.to_async())
+            .await
+            .unwrap()
+            .map(|event| event.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            events,
+            [
+                LanguageModelCompletionEvent::Text("Reading it.".into()),
+                LanguageModelCompletionEvent::ToolUse(LanguageModelToolUse {
+                    id: "call-1".into(),
+                    name: "read_file".into(),
+                    raw_input: r#"{"path":"src/main.rs"}"#.into(),
+                    input: serde_json::json!({ "path": "src/main.rs" }),
+                    is_input_complete: true,
+                }),
+                LanguageModelCompletionEvent::UsageUpdate(TokenUsage {
+                    input_tokens: 10,
+                    output_tokens: 5,
+                    cache_creation_input_tokens: 0,
+                    cache_read_input_tokens: 0,
+                }),
+                LanguageModelCompletionEvent::Stop(StopReason::ToolUse),
+            ]
+        );
+
+        // Once the completion has ended, the extension can no longer emit events to it.
+        let stream = retained_stream.lock().unwrap().take().unwrap();
+        assert!(
+            stream
+                .emit(LlmCompletionEvent::Text("Late".into()))
+                .is_err()
+        );
+    }
+
+    #[gpui::test]
+    async fn test_streaming_completion_errors(cx: &mut TestAppContext) {
+        init_test(cx);
+        let extension = fake_extension("my-extension");
+        extension.on_llm_stream_completion(|_, _, _, stream| {
+            stream.emit(LlmCompletionEvent::Text("Reading it.".into()))?;
+            stream.emit(LlmCompletionEvent::ToolUse(LlmToolUse {
+                id: "call-1".into(),
+                name: "read_file".into(),
+                input: r#"{"path":"#.into(),
+            }))?;
+            Err(anyhow!("connection reset"))
+        });
+        let provider = cx.update(|cx| {
+            ExtensionLanguageModelProvider::new(
+                extension,
+                "gateway".into(),
+                manifest_entry(false),
+                cx,
+            )
+        });
+        cx.update(|cx| provider.authenticate(cx)).await.unwrap();
+        let model = cx.update(|cx| provider.default_model(cx)).unwrap();
+
+        let events = model
+            .stream_completion(user_request("Read main.rs"), &cx.to_async())
+            .await
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(events.len(), 3, "{events:?}");
+        assert_eq!(
+            events[0].as_ref().unwrap(),
+            &LanguageModelCompletionEvent::Text("Reading it.".into())
+        );
+        assert!(
+            matches!(
+                &events[1],
+                Err(LanguageModelCompletionError::BadInputJson { tool_name, .. })
+                    if tool_name.as_ref() == "read_file"
+            ),
+            "{events:?}"
+        );
+        assert!(
+            matches!(
+                &events[2],
+                Err(LanguageModelCompletionError::Other(error))
+                    if error.to_string() == "connection reset"
+            ),
+            "{events:?}"
+        );
+    }
+
+    #[test]
+    fn test_into_completion_event() {
+        let event = into_completion_event(LlmCompletionEvent::ToolUse(LlmToolUse {
+            id: "call-1".into(),
+            name: "read_file".into(),
+            input: r#"{"path":"src/main.rs"}"#.into(),
+        }))
+        .unwrap();
+        let LanguageModelCompletionEvent::ToolUse(tool_use) = event else {
+            panic!("expected a tool use, got {event:?}");
+        };
+        assert_eq!(tool_use.id.to_string(), "call-1");
+        assert_eq!(tool_use.name.as_ref(), "read_file");
+        assert_eq!(tool_use.input, serde_json::json!({ "path": "src/main.rs" }));
+
+        let error = into_completion_event(LlmCompletionEvent::ToolUse(LlmToolUse {
+            id: "call-2".into(),
+            name: "read_file".into(),
+            input: "{".into(),
+        }))
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            LanguageModelCompletionError::BadInputJson { .. }
+        ));
+    }
+}
@@ -5,6 +5,7 @@ use gpui::{App, Context, Entity};
 use language_model::LanguageModelRegistry;
 use provider::deepseek::DeepSeekLanguageModelProvider;
 
+mod extension_language_models;
 pub mod provider;
 mod settings;
 pub mod ui;
@@ -28,6 +29,7 @@ pub fn init(user_store: Entity<UserStore>, client: Arc<Client>, cx: &mut App) {
     registry.update(cx, |registry, cx| {
         register_language_model_providers(registry, user_store, client, cx);
     });
+    extension_language_models::init(cx);
 }
 
 fn register_language_model_providers(
@@ -172,6 +172,28 @@ pub struct ChatOptions {
     pub top_p: Option<f32>,
 }
 
+/// A request to Ollama's `/api/generate` endpoint.
+#[derive(Serialize, Debug)]
+pub struct GenerateRequest {
+    pub model: String,
+    pub prompt: String,
+    /// The text after the completion, for models that support fill-in-the-middle.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
+    /// Whether to send the prompt without applying the model's template.
+    pub raw: bool,
+    pub stream: bool,
+    pub keep_alive: KeepAlive,
+    pub options: Option<ChatOptions>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GenerateResponse {
+    pub response: String,
+    #[allow(unused)]
+    pub done: bool,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct ChatResponseDelta {
     #[allow(unused)]
@@ -271,6 +293,38 @@ pub async fn complete(
     }
 }
 
+pub async fn generate(
+    client: &dyn HttpClient,
+    api_url: &str,
+    request: GenerateRequest,
+) -> Result<GenerateResponse> {
+    let uri = format!("{api_url}/api/generate");
+    let request_builder = HttpRequest::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+
+    let serialized_request = serde_json::to_string(&request)?;
+    let request = request_builder.body(AsyncBody::from(serialized_request))?;
+
+    let mut response = client.send(request).await?;
+
+    let mut body = Vec::new();
+    response.body_mut().read_to_end(&mut body).await?;
+
+    if response.status().is_success() {
+        let response: GenerateResponse = serde_json::from_slice(&body)?;
+        Ok(response)
+    } else {
+        let body_str = std::str::from_utf8(&body)?;
+        anyhow::bail!(
+            "Failed to connect to Ollama API: {} {}",
+            response.status(),
+            body_str
+        );
+    }
+}
+
 pub async fn stream_chat_completion(
     client: &dyn HttpClient,
     api_url: &str,
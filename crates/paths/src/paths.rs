@@ -415,6 +415,12 @@ pub fn local_debug_file_relative_path() -> &'static Path {
     Path::new(".zed/debug.json")
 }
 
+/// Returns the relative path to a `breakpoints.json` file within a project.
+/// .zed/breakpoints.json
+pub fn local_breakpoints_file_relative_path() -> &'static Path {
+    Path::new(".zed/breakpoints.json")
+}
+
 /// Returns the relative path to a `.vscode/launch.json` file within a project.
 pub fn local_vscode_launch_file_relative_path() -> &'static Path {
     Path::new(".vscode/launch.json")
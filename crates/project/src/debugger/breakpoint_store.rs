@@ -13,7 +13,14 @@ use rpc::{
     AnyProtoClient, TypedEnvelope,
     proto::{self},
 };
-use std::{hash::Hash, ops::Range, path::Path, sync::Arc, u32};
+use serde::{Deserialize, Serialize};
+use std::{
+    hash::Hash,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+    u32,
+};
 use text::{Point, PointUtf16};
 use util::maybe;
 
@@ -158,6 +165,10 @@ pub struct BreakpointStore {
     breakpoints: BTreeMap<Arc<Path>, BreakpointsInFile>,
     downstream_client: Option<(AnyProtoClient, u64)>,
     active_stack_frame: Option<ActiveStackFrame>,
+    /// The git branch whose breakpoints are currently loaded, when breakpoints are kept per branch.
+    active_branch: Option<Arc<str>>,
+    /// Breakpoints of the other branches, restored when switching back to them.
+    branch_breakpoints: BTreeMap<Arc<str>, BTreeMap<Arc<Path>, Vec<SourceBreakpoint>>>,
     // E.g ssh
     mode: BreakpointStoreMode,
 }
@@ -176,6 +187,8 @@ impl BreakpointStore {
             }),
             downstream_client: None,
             active_stack_frame: Default::default(),
+            active_branch: None,
+            branch_breakpoints: Default::default(),
         }
     }
 
@@ -188,6 +201,8 @@ impl BreakpointStore {
             }),
             downstream_client: None,
             active_stack_frame: Default::default(),
+            active_branch: None,
+            branch_breakpoints: Default::default(),
         }
     }
 
@@ -535,6 +550,25 @@ impl BreakpointStore {
                     }
                 }
             }
+            BreakpointEditAction::EditGroup(group) => {
+                let group = (!group.is_empty()).then_some(group);
+                let found_bp = breakpoint_set.breakpoints.iter_mut().find_map(|other| {
+                    if breakpoint.position == *other.position() {
+                        Some(&mut other.bp.bp)
+                    } else {
+                        None
+                    }
+                });
+
+                if let Some(found_bp) = found_bp {
+                    found_bp.group = group;
+                } else if group.is_some() {
+                    breakpoint.bp.group = group;
+                    breakpoint_set
+                        .breakpoints
+                        .push(StatefulBreakpoint::new(breakpoint.clone()));
+                }
+            }
         }
 
         if breakpoint_set.breakpoints.is_empty() {
@@ -724,6 +758,7 @@ impl BreakpointStore {
                             message: bp.bp.message.clone(),
                             condition: bp.bp.condition.clone(),
                             hit_condition: bp.bp.hit_condition.clone(),
+                            group: bp.bp.group.clone(),
                         }
                     })
                     .collect()
@@ -763,6 +798,7 @@ impl BreakpointStore {
                                 state: breakpoint.bp.state,
                                 hit_condition: breakpoint.bp.hit_condition.clone(),
                                 condition: breakpoint.bp.condition.clone(),
+                                group: breakpoint.bp.group.clone(),
                             }
                         })
                         .collect(),
@@ -826,6 +862,7 @@ impl BreakpointStore {
                                     state: bp.state,
                                     condition: bp.condition,
                                     hit_condition: bp.hit_condition,
+                                    group: bp.group,
                                 },
                             }))
                     }
@@ -856,6 +893,168 @@ impl BreakpointStore {
         }
     }
 
+    /// Returns the names of all breakpoint groups, along with whether any of their breakpoints are enabled.
+    pub fn breakpoint_groups(&self) -> BTreeMap<Arc<str>, BreakpointState> {
+        let mut groups = BTreeMap::<Arc<str>, BreakpointState>::default();
+        for breakpoint in self
+            .breakpoints
+            .values()
+            .flat_map(|breakpoints_in_file| &breakpoints_in_file.breakpoints)
+        {
+            let breakpoint = &breakpoint.bp.bp;
+            if let Some(group) = &breakpoint.group {
+                let state = groups
+                    .entry(group.clone())
+                    .or_insert(BreakpointState::Disabled);
+                if breakpoint.is_enabled() {
+                    *state = BreakpointState::Enabled;
+                }
+            }
+        }
+        groups
+    }
+
+    /// Enables or disables all breakpoints in a group.
+    pub fn set_group_state(&mut self, group: &str, state: BreakpointState, cx: &mut Context<Self>) {
+        let to_invert = self
+            .breakpoints
+            .values()
+            .flat_map(|breakpoints_in_file| {
+                breakpoints_in_file
+                    .breakpoints
+                    .iter()
+                    .filter(|breakpoint| {
+                        breakpoint.bp.bp.group.as_deref() == Some(group)
+                            && breakpoint.bp.bp.state != state
+                    })
+                    .map(|breakpoint| (breakpoints_in_file.buffer.clone(), breakpoint.bp.clone()))
+            })
+            .collect::<Vec<_>>();
+
+        for (buffer, breakpoint) in to_invert {
+            self.toggle_breakpoint(buffer, breakpoint, BreakpointEditAction::InvertState, cx);
+        }
+    }
+
+    /// Converts the breakpoints of files within `root` into a breakpoints file, with paths relative to `root`.
+    pub fn export_breakpoints(&self, root: &Path, cx: &App) -> BreakpointsFile {
+        BreakpointsFile::from_source_breakpoints(root, &self.all_source_breakpoints(cx))
+    }
+
+    /// Adds the breakpoints of a breakpoints file whose paths are relative to `root`,
+    /// replacing existing breakpoints on the same lines.
+    pub fn import_breakpoints(
+        &mut self,
+        root: &Path,
+        file: BreakpointsFile,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let mut breakpoints = self.all_source_breakpoints(cx);
+        for (path, imported) in file.into_source_breakpoints(root) {
+            let existing = breakpoints.entry(path).or_default();
+            existing.retain(|breakpoint| {
+                !imported
+                    .iter()
+                    .any(|imported| imported.row == breakpoint.row)
+            });
+            existing.extend(imported);
+        }
+        self.replace_breakpoints(breakpoints, cx)
+    }
+
+    /// Switches to the breakpoints of another git branch.
+    ///
+    /// The current breakpoints are kept aside for the previous branch. The first branch
+    /// reported keeps whatever breakpoints are already loaded, unless they were restored
+    /// for another branch.
+    pub fn set_active_branch(
+        &mut self,
+        branch: Arc<str>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if self.active_branch.as_ref() == Some(&branch) {
+            return Task::ready(Ok(()));
+        }
+        let Some(previous_branch) = self.active_branch.replace(branch.clone()) else {
+            return Task::ready(Ok(()));
+        };
+
+        log::debug!("Switching breakpoints from branch {previous_branch} to {branch}");
+        let previous_breakpoints = self.all_source_breakpoints(cx);
+        self.branch_breakpoints
+            .insert(previous_branch, previous_breakpoints);
+        let breakpoints = self.branch_breakpoints.remove(&branch).unwrap_or_default();
+        self.clear_breakpoints(cx);
+        self.replace_breakpoints(breakpoints, cx)
+    }
+
+    /// Returns the git branch whose breakpoints are currently loaded.
+    pub fn active_branch(&self) -> Option<&Arc<str>> {
+        self.active_branch.as_ref()
+    }
+
+    /// Returns the breakpoints kept aside for the git branches that aren't checked out.
+    pub fn branch_breakpoints(
+        &self,
+    ) -> &BTreeMap<Arc<str>, BTreeMap<Arc<Path>, Vec<SourceBreakpoint>>> {
+        &self.branch_breakpoints
+    }
+
+    /// Restores the breakpoints of every git branch, e.g. from the workspace database.
+    ///
+    /// `breakpoints` belong to `branch`, the branch that was checked out when they were saved.
+    /// If another branch has been reported since, they are kept aside and that branch's
+    /// breakpoints are loaded instead.
+    pub fn with_serialized_branch_breakpoints(
+        &mut self,
+        branch: Option<Arc<str>>,
+        breakpoints: BTreeMap<Arc<Path>, Vec<SourceBreakpoint>>,
+        branch_breakpoints: BTreeMap<Arc<str>, BTreeMap<Arc<Path>, Vec<SourceBreakpoint>>>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.branch_breakpoints = branch_breakpoints;
+        let Some(branch) = branch else {
+            return self.with_serialized_breakpoints(breakpoints, cx);
+        };
+        match self.active_branch.clone() {
+            Some(active_branch) if active_branch != branch => {
+                log::debug!("Restoring breakpoints of branch {branch} while on {active_branch}");
+                self.branch_breakpoints.insert(branch, breakpoints);
+                let breakpoints = self
+                    .branch_breakpoints
+                    .remove(&active_branch)
+                    .unwrap_or_default();
+                self.with_serialized_breakpoints(breakpoints, cx)
+            }
+            Some(_) => self.with_serialized_breakpoints(breakpoints, cx),
+            None => {
+                self.active_branch = Some(branch);
+                self.with_serialized_breakpoints(breakpoints, cx)
+            }
+        }
+    }
+
+    fn replace_breakpoints(
+        &mut self,
+        breakpoints: BTreeMap<Arc<Path>, Vec<SourceBreakpoint>>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let paths = breakpoints.keys().cloned().collect::<Vec<_>>();
+        let task = self.with_serialized_breakpoints(breakpoints, cx);
+        cx.spawn(async move |this, cx| {
+            task.await?;
+            this.update(cx, |this, cx| {
+                this.broadcast();
+                for path in paths {
+                    cx.emit(BreakpointStoreEvent::BreakpointsUpdated(
+                        path,
+                        BreakpointUpdatedReason::Toggled,
+                    ));
+                }
+            })
+        })
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) fn breakpoint_paths(&self) -> Vec<Arc<Path>> {
         self.breakpoints.keys().cloned().collect()
@@ -886,6 +1085,8 @@ pub enum BreakpointEditAction {
     EditLogMessage(BreakpointMessage),
     EditCondition(BreakpointMessage),
     EditHitCondition(BreakpointMessage),
+    /// Moves the breakpoint into the named group, or out of its group when the name is empty.
+    EditGroup(BreakpointMessage),
 }
 
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
@@ -921,6 +1122,8 @@ pub struct Breakpoint {
     pub hit_condition: Option<Arc<str>>,
     pub condition: Option<BreakpointMessage>,
     pub state: BreakpointState,
+    /// Name of the group this breakpoint belongs to. Breakpoints in a group are enabled and disabled together.
+    pub group: Option<BreakpointMessage>,
 }
 
 impl Breakpoint {
@@ -930,6 +1133,7 @@ impl Breakpoint {
             hit_condition: None,
             condition: None,
             message: None,
+            group: None,
         }
     }
 
@@ -939,6 +1143,7 @@ impl Breakpoint {
             condition: None,
             hit_condition: Some(hit_condition.into()),
             message: None,
+            group: None,
         }
     }
 
@@ -948,6 +1153,7 @@ impl Breakpoint {
             hit_condition: None,
             condition: None,
             message: Some(log_message.into()),
+            group: None,
         }
     }
 
@@ -969,6 +1175,7 @@ impl Breakpoint {
                 .hit_condition
                 .as_ref()
                 .map(|s| String::from(s.as_ref())),
+            group: self.group.as_ref().map(|s| String::from(s.as_ref())),
             session_state: session_states
                 .iter()
                 .map(|(session_id, state)| {
@@ -993,6 +1200,7 @@ impl Breakpoint {
             message: breakpoint.message.map(Into::into),
             condition: breakpoint.condition.map(Into::into),
             hit_condition: breakpoint.hit_condition.map(Into::into),
+            group: breakpoint.group.map(Into::into),
         })
     }
 
@@ -1016,6 +1224,7 @@ pub struct SourceBreakpoint {
     pub condition: Option<Arc<str>>,
     pub hit_condition: Option<Arc<str>>,
     pub state: BreakpointState,
+    pub group: Option<Arc<str>>,
 }
 
 impl From<SourceBreakpoint> for dap::SourceBreakpoint {
@@ -1034,3 +1243,84 @@ impl From<SourceBreakpoint> for dap::SourceBreakpoint {
         }
     }
 }
+
+/// A set of breakpoints stored in a project's `.zed/breakpoints.json`.
+///
+/// Unlike the breakpoints saved with a workspace, paths are relative to the worktree root, so the file can be checked in and shared.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BreakpointsFile {
+    pub breakpoints: Vec<BreakpointsFileEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BreakpointsFileEntry {
+    pub path: PathBuf,
+    /// One-based line of the breakpoint.
+    pub line: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hit_condition: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+}
+
+impl BreakpointsFile {
+    pub fn from_source_breakpoints(
+        root: &Path,
+        breakpoints: &BTreeMap<Arc<Path>, Vec<SourceBreakpoint>>,
+    ) -> Self {
+        let mut breakpoints = breakpoints
+            .iter()
+            .filter_map(|(path, breakpoints)| Some((path.strip_prefix(root).ok()?, breakpoints)))
+            .flat_map(|(path, breakpoints)| {
+                breakpoints.iter().map(|breakpoint| BreakpointsFileEntry {
+                    path: path.to_path_buf(),
+                    line: breakpoint.row + 1,
+                    log_message: breakpoint.message.as_deref().map(ToOwned::to_owned),
+                    condition: breakpoint.condition.as_deref().map(ToOwned::to_owned),
+                    hit_condition: breakpoint.hit_condition.as_deref().map(ToOwned::to_owned),
+                    group: breakpoint.group.as_deref().map(ToOwned::to_owned),
+                    disabled: breakpoint.state.is_disabled(),
+                })
+            })
+            .collect::<Vec<_>>();
+        breakpoints.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+        Self { breakpoints }
+    }
+
+    pub fn into_source_breakpoints(
+        self,
+        root: &Path,
+    ) -> BTreeMap<Arc<Path>, Vec<SourceBreakpoint>> {
+        let mut breakpoints = BTreeMap::<Arc<Path>, Vec<SourceBreakpoint>>::default();
+        for entry in self.breakpoints {
+            if entry.line == 0 {
+                log::error!("Skipping breakpoint at line 0 of {:?}", entry.path);
+                continue;
+            }
+            let path: Arc<Path> = root.join(&entry.path).into();
+            breakpoints
+                .entry(path.clone())
+                .or_default()
+                .push(SourceBreakpoint {
+                    row: entry.line - 1,
+                    path,
+                    message: entry.log_message.map(Into::into),
+                    condition: entry.condition.map(Into::into),
+                    hit_condition: entry.hit_condition.map(Into::into),
+                    state: if entry.disabled {
+                        BreakpointState::Disabled
+                    } else {
+                        BreakpointState::Enabled
+                    },
+                    group: entry.group.map(Into::into),
+                });
+        }
+        breakpoints
+    }
+}
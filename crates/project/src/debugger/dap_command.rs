@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 use anyhow::{Context as _, Ok, Result};
 use dap::{
@@ -1774,3 +1774,176 @@ impl DapCommand for LocationsCommand {
         })
     }
 }
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(crate) struct GotoTargetsCommand {
+    pub path: Arc<Path>,
+    /// One-based line in `path` to look up goto targets for.
+    pub line: u64,
+    pub column: Option<u64>,
+}
+
+impl LocalDapCommand for GotoTargetsCommand {
+    type Response = Vec<dap::GotoTarget>;
+    type DapRequest = dap::requests::GotoTargets;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_goto_targets_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::GotoTargetsArguments {
+            source: super::session::client_source(&self.path),
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.targets)
+    }
+}
+
+impl DapCommand for GotoTargetsCommand {
+    type ProtoRequest = proto::DapGotoTargetsRequest;
+    type ProtoResponse = proto::DapGotoTargetsResponse;
+
+    fn client_id_from_proto(request: &Self::ProtoRequest) -> SessionId {
+        SessionId::from_proto(request.client_id)
+    }
+
+    fn from_proto(request: &Self::ProtoRequest) -> Self {
+        Self {
+            path: Path::new(&request.path).into(),
+            line: request.line,
+            column: request.column,
+        }
+    }
+
+    fn to_proto(
+        &self,
+        debug_client_id: SessionId,
+        upstream_project_id: u64,
+    ) -> proto::DapGotoTargetsRequest {
+        proto::DapGotoTargetsRequest {
+            project_id: upstream_project_id,
+            client_id: debug_client_id.to_proto(),
+            path: self.path.to_string_lossy().to_string(),
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn response_to_proto(
+        debug_client_id: SessionId,
+        message: Self::Response,
+    ) -> Self::ProtoResponse {
+        proto::DapGotoTargetsResponse {
+            client_id: debug_client_id.to_proto(),
+            targets: message
+                .into_iter()
+                .map(|target| proto::DapGotoTarget {
+                    id: target.id,
+                    label: target.label,
+                    line: target.line,
+                    column: target.column,
+                    end_line: target.end_line,
+                    end_column: target.end_column,
+                    instruction_pointer_reference: target.instruction_pointer_reference,
+                })
+                .collect(),
+        }
+    }
+
+    fn response_from_proto(&self, message: Self::ProtoResponse) -> Result<Self::Response> {
+        Ok(message
+            .targets
+            .into_iter()
+            .map(|target| dap::GotoTarget {
+                id: target.id,
+                label: target.label,
+                line: target.line,
+                column: target.column,
+                end_line: target.end_line,
+                end_column: target.end_column,
+                instruction_pointer_reference: target.instruction_pointer_reference,
+            })
+            .collect())
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(crate) struct GotoCommand {
+    pub thread_id: u64,
+    pub target_id: u64,
+}
+
+impl LocalDapCommand for GotoCommand {
+    type Response = <dap::requests::Goto as dap::requests::Request>::Response;
+    type DapRequest = dap::requests::Goto;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_goto_targets_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::GotoArguments {
+            thread_id: self.thread_id,
+            target_id: self.target_id,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        _message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(())
+    }
+}
+
+impl DapCommand for GotoCommand {
+    type ProtoRequest = proto::DapGotoRequest;
+    type ProtoResponse = proto::Ack;
+
+    fn client_id_from_proto(request: &Self::ProtoRequest) -> SessionId {
+        SessionId::from_proto(request.client_id)
+    }
+
+    fn from_proto(request: &Self::ProtoRequest) -> Self {
+        Self {
+            thread_id: request.thread_id,
+            target_id: request.target_id,
+        }
+    }
+
+    fn to_proto(
+        &self,
+        debug_client_id: SessionId,
+        upstream_project_id: u64,
+    ) -> proto::DapGotoRequest {
+        proto::DapGotoRequest {
+            project_id: upstream_project_id,
+            client_id: debug_client_id.to_proto(),
+            thread_id: self.thread_id,
+            target_id: self.target_id,
+        }
+    }
+
+    fn response_to_proto(
+        _debug_client_id: SessionId,
+        _message: Self::Response,
+    ) -> Self::ProtoResponse {
+        proto::Ack {}
+    }
+
+    fn response_from_proto(&self, _message: Self::ProtoResponse) -> Result<Self::Response> {
+        Ok(())
+    }
+}
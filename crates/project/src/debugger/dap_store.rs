@@ -2,7 +2,7 @@ use super::{
     breakpoint_store::BreakpointStore,
     dap_command::EvaluateCommand,
     locators,
-    session::{self, Session, SessionStateEvent},
+    session::{self, Session, SessionEvent, SessionStateEvent},
 };
 use crate::{
     InlayHint, InlayHintLabel, ProjectEnvironment, ResolveState,
@@ -21,8 +21,11 @@ use dap::{
     },
     client::SessionId,
     inline_value::VariableLookupKind,
-    messages::Message,
-    requests::{Completions, Evaluate},
+    messages::{Events, Message, Response},
+    requests::{
+        Completions, Evaluate, LoadedSources, Modules, Request, Scopes, Source as SourceRequest,
+        StackTrace, Threads, Variables,
+    },
 };
 use fs::Fs;
 use futures::{
@@ -30,7 +33,10 @@ use futures::{
     channel::mpsc::{self, UnboundedSender},
     future::{Shared, join_all},
 };
-use gpui::{App, AppContext, AsyncApp, Context, Entity, EventEmitter, SharedString, Task};
+use gpui::{
+    App, AppContext, AsyncApp, BackgroundExecutor, Context, Entity, EventEmitter, SharedString,
+    Task,
+};
 use http_client::HttpClient;
 use language::{Buffer, LanguageToolchainStore, language_settings::InlayHintKind};
 use node_runtime::NodeRuntime;
@@ -53,11 +59,25 @@ use task::{DebugScenario, SpawnInTerminal, TaskContext, TaskTemplate};
 use util::ResultExt as _;
 use worktree::Worktree;
 
+/// The requests guests of a shared project may send to the host's debug sessions, none of
+/// which change the state of the session.
+const GUEST_DEBUG_REQUESTS: &[&str] = &[
+    Threads::COMMAND,
+    StackTrace::COMMAND,
+    Scopes::COMMAND,
+    Variables::COMMAND,
+    SourceRequest::COMMAND,
+    Modules::COMMAND,
+    LoadedSources::COMMAND,
+];
+
 #[derive(Debug)]
 pub enum DapStoreEvent {
     DebugClientStarted(SessionId),
     DebugSessionInitialized(SessionId),
     DebugClientShutdown(SessionId),
+    /// The host of the project shared one of its debug sessions with us.
+    DebugSessionShared(SessionId),
     DebugClientEvent {
         session_id: SessionId,
         message: Message,
@@ -69,7 +89,7 @@ pub enum DapStoreEvent {
 enum DapStoreMode {
     Local(LocalDapStore),
     Ssh(SshDapStore),
-    Collab,
+    Collab(CollabDapStore),
 }
 
 pub struct LocalDapStore {
@@ -86,6 +106,13 @@ pub struct SshDapStore {
     upstream_project_id: u64,
 }
 
+pub struct CollabDapStore {
+    upstream_client: AnyProtoClient,
+    upstream_project_id: u64,
+    /// Passes the host's responses and events on to the debug sessions it shared.
+    remote_sessions: HashMap<SessionId, UnboundedSender<Message>>,
+}
+
 pub struct DapStore {
     mode: DapStoreMode,
     downstream_client: Option<(AnyProtoClient, u64)>,
@@ -110,6 +137,10 @@ impl DapStore {
         client.add_entity_request_handler(Self::handle_run_debug_locator);
         client.add_entity_request_handler(Self::handle_get_debug_adapter_binary);
         client.add_entity_message_handler(Self::handle_log_to_debug_console);
+        client.add_entity_message_handler(Self::handle_share_debug_session);
+        client.add_entity_message_handler(Self::handle_unshare_debug_session);
+        client.add_entity_message_handler(Self::handle_debug_session_event);
+        client.add_entity_request_handler(Self::handle_debug_session_request);
     }
 
     #[expect(clippy::too_many_arguments)]
@@ -151,13 +182,19 @@ impl DapStore {
     }
 
     pub fn new_collab(
-        _project_id: u64,
-        _upstream_client: AnyProtoClient,
+        project_id: u64,
+        upstream_client: AnyProtoClient,
         breakpoint_store: Entity<BreakpointStore>,
         worktree_store: Entity<WorktreeStore>,
         cx: &mut Context<Self>,
     ) -> Self {
-        Self::new(DapStoreMode::Collab, breakpoint_store, worktree_store, cx)
+        let mode = DapStoreMode::Collab(CollabDapStore {
+            upstream_client,
+            upstream_project_id: project_id,
+            remote_sessions: HashMap::default(),
+        });
+
+        Self::new(mode, breakpoint_store, worktree_store, cx)
     }
 
     fn new(
@@ -281,7 +318,7 @@ impl DapStore {
                     })
                 })
             }
-            DapStoreMode::Collab => {
+            DapStoreMode::Collab(_) => {
                 Task::ready(Err(anyhow!("Debugging is not yet supported via collab")))
             }
         }
@@ -351,7 +388,7 @@ impl DapStore {
                     DebugRequest::from_proto(response)
                 })
             }
-            DapStoreMode::Collab => {
+            DapStoreMode::Collab(_) => {
                 Task::ready(Err(anyhow!("Debugging is not yet supported via collab")))
             }
         }
@@ -389,10 +426,29 @@ impl DapStore {
             task_context,
             cx,
         );
+        self.insert_session(session_id, session.clone(), cx);
 
+        session
+    }
+
+    fn insert_session(
+        &mut self,
+        session_id: SessionId,
+        session: Entity<Session>,
+        cx: &mut Context<Self>,
+    ) {
+        session.update(cx, |session, _| {
+            session.set_downstream_client(self.downstream_client.clone())
+        });
         self.sessions.insert(session_id, session.clone());
         cx.notify();
 
+        cx.subscribe(&session, |this, session, event: &SessionEvent, cx| {
+            if let SessionEvent::CapabilitiesLoaded = event {
+                this.share_session(&session, cx);
+            }
+        })
+        .detach();
         cx.subscribe(&session, {
             move |this: &mut DapStore, _, event: &SessionStateEvent, cx| match event {
                 SessionStateEvent::Shutdown => {
@@ -405,8 +461,27 @@ impl DapStore {
             }
         })
         .detach();
+    }
 
-        session
+    /// Shows guests of the shared project a session, or updates the capabilities they see.
+    fn share_session(&self, session: &Entity<Session>, cx: &App) {
+        let Some((client, project_id)) = &self.downstream_client else {
+            return;
+        };
+        let session = session.read(cx);
+        if session.is_terminated() {
+            return;
+        }
+        if let Some(message) = session.to_share_proto(*project_id) {
+            client.send(message).log_err();
+        }
+    }
+
+    /// Shares all sessions again, for guests that joined after they were shared.
+    pub(crate) fn resend_shared_sessions(&self, cx: &App) {
+        for session in self.sessions.values() {
+            self.share_session(session, cx);
+        }
     }
 
     pub fn boot_session(
@@ -417,7 +492,13 @@ impl DapStore {
         cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
         let dap_store = cx.weak_entity();
-        let console = session.update(cx, |session, cx| session.console_output(cx));
+        let is_core_dump = DapRegistry::global(cx)
+            .adapter(&definition.adapter)
+            .is_some_and(|adapter| adapter.is_core_dump(&definition.config));
+        let console = session.update(cx, |session, cx| {
+            session.set_read_only(is_core_dump, cx);
+            session.console_output(cx)
+        });
         let session_id = session.read(cx).session_id();
 
         cx.spawn({
@@ -731,6 +812,17 @@ impl DapStore {
 
         let shutdown_task = session.update(cx, |this, cx| this.shutdown(cx));
 
+        if let Some((client, project_id)) = &self.downstream_client {
+            client
+                .send(proto::UnshareDebugSession {
+                    project_id: *project_id,
+                    session_id: session_id.to_proto(),
+                })
+                .log_err();
+        }
+        if let DapStoreMode::Collab(collab) = &mut self.mode {
+            collab.remote_sessions.remove(&session_id);
+        }
         cx.emit(DapStoreEvent::DebugClientShutdown(session_id));
 
         cx.background_spawn(async move {
@@ -752,13 +844,22 @@ impl DapStore {
         &mut self,
         project_id: u64,
         downstream_client: AnyProtoClient,
-        _: &mut Context<Self>,
+        cx: &mut Context<Self>,
     ) {
         self.downstream_client = Some((downstream_client.clone(), project_id));
+        for session in self.sessions.values() {
+            session.update(cx, |session, _| {
+                session.set_downstream_client(self.downstream_client.clone())
+            });
+        }
+        self.resend_shared_sessions(cx);
     }
 
     pub fn unshared(&mut self, cx: &mut Context<Self>) {
         self.downstream_client.take();
+        for session in self.sessions.values() {
+            session.update(cx, |session, _| session.set_downstream_client(None));
+        }
 
         cx.notify();
     }
@@ -853,6 +954,201 @@ impl DapStore {
             })
         })
     }
+
+    async fn handle_share_debug_session(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::ShareDebugSession>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let payload = envelope.payload;
+        let session_id = SessionId::from_proto(payload.session_id);
+        let capabilities: Capabilities = serde_json::from_str(&payload.capabilities)?;
+
+        let Some((messages_tx, boot)) = this.update(&mut cx, |this, cx| {
+            if let Some(session) = this.sessions.get(&session_id) {
+                session.update(cx, |session, cx| {
+                    session.capabilities = capabilities;
+                    cx.emit(SessionEvent::CapabilitiesLoaded);
+                    cx.notify();
+                });
+                return Ok(None);
+            }
+            let DapStoreMode::Collab(collab) = &mut this.mode else {
+                anyhow::bail!("debug sessions can only be shared with guests");
+            };
+
+            let (requests_tx, requests_rx) = mpsc::unbounded();
+            let (messages_tx, messages_rx) = mpsc::unbounded();
+            collab
+                .remote_sessions
+                .insert(session_id, messages_tx.clone());
+            cx.background_spawn(Self::forward_guest_requests(
+                collab.upstream_client.clone(),
+                collab.upstream_project_id,
+                session_id,
+                requests_rx,
+                messages_tx.clone(),
+                cx.background_executor().clone(),
+            ))
+            .detach();
+
+            let session = Session::new(
+                this.breakpoint_store.clone(),
+                session_id,
+                None,
+                payload.label.into(),
+                DebugAdapterName(payload.adapter.into()),
+                TaskContext::default(),
+                cx,
+            );
+            this.insert_session(session_id, session.clone(), cx);
+            let boot = session.update(cx, |session, cx| {
+                session.boot_remote(capabilities, requests_tx, messages_rx, cx)
+            });
+            anyhow::Ok(Some((messages_tx, boot)))
+        })??
+        else {
+            return Ok(());
+        };
+
+        boot.await?;
+        // Guests only see the events sent after they joined, so tell the session which thread
+        // the host is stopped on.
+        if let Some(thread_id) = payload.stopped_thread_id {
+            messages_tx
+                .unbounded_send(Message::Event(Box::new(Events::Stopped(
+                    dap::StoppedEvent {
+                        reason: dap::StoppedEventReason::Pause,
+                        description: None,
+                        thread_id: Some(thread_id),
+                        preserve_focus_hint: None,
+                        text: None,
+                        all_threads_stopped: None,
+                        hit_breakpoint_ids: None,
+                    },
+                ))))
+                .ok();
+        }
+        this.update(&mut cx, |_, cx| {
+            cx.emit(DapStoreEvent::DebugSessionShared(session_id))
+        })
+    }
+
+    /// Sends the requests of a debug session shared with us to the host, and passes its
+    /// responses back to the session.
+    async fn forward_guest_requests(
+        upstream_client: AnyProtoClient,
+        project_id: u64,
+        session_id: SessionId,
+        mut requests_rx: mpsc::UnboundedReceiver<dap::messages::Request>,
+        messages_tx: UnboundedSender<Message>,
+        executor: BackgroundExecutor,
+    ) {
+        while let Some(request) = requests_rx.next().await {
+            let response = upstream_client.request(proto::DebugSessionRequest {
+                project_id,
+                session_id: session_id.to_proto(),
+                command: request.command.clone(),
+                arguments: request.arguments.map(|arguments| arguments.to_string()),
+            });
+            let messages_tx = messages_tx.clone();
+            executor
+                .spawn(async move {
+                    let body = response.await.and_then(|response| {
+                        response
+                            .body
+                            .map(|body| serde_json::from_str(&body))
+                            .transpose()
+                            .map_err(Into::into)
+                    });
+                    let response = match body {
+                        Ok(body) => Response {
+                            seq: request.seq,
+                            request_seq: request.seq,
+                            success: true,
+                            command: request.command,
+                            body,
+                            message: None,
+                        },
+                        Err(error) => Response {
+                            seq: request.seq,
+                            request_seq: request.seq,
+                            success: false,
+                            command: request.command,
+                            body: None,
+                            message: Some(error.to_string()),
+                        },
+                    };
+                    messages_tx.unbounded_send(Message::Response(response)).ok();
+                })
+                .detach();
+        }
+    }
+
+    async fn handle_unshare_debug_session(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::UnshareDebugSession>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let session_id = SessionId::from_proto(envelope.payload.session_id);
+        this.update(&mut cx, |this, cx| {
+            if this.sessions.contains_key(&session_id) {
+                this.shutdown_session(session_id, cx).detach_and_log_err(cx);
+            }
+        })
+    }
+
+    async fn handle_debug_session_event(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::DebugSessionEvent>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let session_id = SessionId::from_proto(envelope.payload.session_id);
+        let event: Events = serde_json::from_str(&envelope.payload.event)?;
+        this.update(&mut cx, |this, _| {
+            if let DapStoreMode::Collab(collab) = &this.mode {
+                if let Some(messages_tx) = collab.remote_sessions.get(&session_id) {
+                    messages_tx
+                        .unbounded_send(Message::Event(Box::new(event)))
+                        .ok();
+                }
+            }
+        })
+    }
+
+    async fn handle_debug_session_request(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::DebugSessionRequest>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::DebugSessionResponse> {
+        let payload = envelope.payload;
+        anyhow::ensure!(
+            GUEST_DEBUG_REQUESTS.contains(&payload.command.as_str()),
+            "guests can't send `{}` requests to a debug session",
+            payload.command
+        );
+        let session_id = SessionId::from_proto(payload.session_id);
+        let client = this
+            .read_with(&cx, |this, cx| {
+                this.session_by_id(session_id)
+                    .and_then(|session| session.read(cx).adapter_client())
+            })?
+            .with_context(|| format!("no running debug session with id {}", session_id.0))?;
+        let arguments = payload
+            .arguments
+            .map(|arguments| serde_json::from_str(&arguments))
+            .transpose()?;
+
+        let response = client.request_raw(&payload.command, arguments).await?;
+        anyhow::ensure!(
+            response.success,
+            "Request failed: {}",
+            response.message.unwrap_or_default()
+        );
+        Ok(proto::DebugSessionResponse {
+            body: response.body.map(|body| body.to_string()),
+        })
+    }
 }
 
 #[derive(Clone)]
@@ -5,17 +5,18 @@ use super::breakpoint_store::{
 };
 use super::dap_command::{
     self, Attach, ConfigurationDone, ContinueCommand, DapCommand, DisconnectCommand,
-    EvaluateCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand,
-    ModulesCommand, NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand,
-    ScopesCommand, SetExceptionBreakpoints, SetVariableValueCommand, StackTraceCommand,
-    StepBackCommand, StepCommand, StepInCommand, StepOutCommand, TerminateCommand,
-    TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+    EvaluateCommand, GotoCommand, GotoTargetsCommand, Initialize, Launch, LoadedSourcesCommand,
+    LocalDapCommand, LocationsCommand, ModulesCommand, NextCommand, PauseCommand, RestartCommand,
+    RestartStackFrameCommand, ScopesCommand, SetExceptionBreakpoints, SetVariableValueCommand,
+    StackTraceCommand, StepBackCommand, StepCommand, StepInCommand, StepOutCommand,
+    TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
 };
 use super::dap_store::DapStore;
 use anyhow::{Context as _, Result, anyhow};
 use collections::{HashMap, HashSet, IndexMap};
 use dap::adapters::{DebugAdapterBinary, DebugAdapterName};
 use dap::messages::Response;
+use dap::recording::DapRecording;
 use dap::requests::{Request, RunInTerminal, StartDebugging};
 use dap::{
     Capabilities, ContinueArguments, EvaluateArgumentsContext, Module, Source, StackFrameId,
@@ -37,7 +38,7 @@ use gpui::{
     Task, WeakEntity,
 };
 
-use rpc::ErrorExt;
+use rpc::{AnyProtoClient, ErrorExt, proto};
 use serde_json::Value;
 use smol::stream::StreamExt;
 use std::any::TypeId;
@@ -151,7 +152,7 @@ pub struct RunningMode {
     messages_tx: UnboundedSender<Message>,
 }
 
-fn client_source(abs_path: &Path) -> dap::Source {
+pub(super) fn client_source(abs_path: &Path) -> dap::Source {
     dap::Source {
         name: abs_path
             .file_name()
@@ -166,12 +167,28 @@ fn client_source(abs_path: &Path) -> dap::Source {
     }
 }
 
+/// The binary of a session that doesn't run a debug adapter itself.
+fn empty_binary() -> DebugAdapterBinary {
+    DebugAdapterBinary {
+        command: None,
+        arguments: Vec::new(),
+        envs: Default::default(),
+        cwd: None,
+        connection: None,
+        request_args: StartDebuggingRequestArguments {
+            configuration: Value::Object(Default::default()),
+            request: StartDebuggingRequestArgumentsRequest::Launch,
+        },
+    }
+}
+
 impl RunningMode {
     async fn new(
         session_id: SessionId,
         parent_session: Option<Entity<Session>>,
         worktree: WeakEntity<Worktree>,
         binary: DebugAdapterBinary,
+        recording: Option<Arc<DapRecording>>,
         messages_tx: futures::channel::mpsc::UnboundedSender<Message>,
         cx: &mut AsyncApp,
     ) -> Result<Self> {
@@ -182,7 +199,16 @@ impl RunningMode {
             }
         });
 
-        let client = if let Some(client) = parent_session
+        let client = if let Some(recording) = recording {
+            DebugAdapterClient::start_replay(
+                session_id,
+                binary.clone(),
+                &recording,
+                message_handler,
+                cx,
+            )
+            .await?
+        } else if let Some(client) = parent_session
             .and_then(|session| cx.update(|cx| session.read(cx).adapter_client()).ok())
             .flatten()
         {
@@ -205,6 +231,42 @@ impl RunningMode {
         })
     }
 
+    async fn new_remote(
+        session_id: SessionId,
+        requests_tx: mpsc::UnboundedSender<dap::messages::Request>,
+        messages_rx: mpsc::UnboundedReceiver<Message>,
+        messages_tx: UnboundedSender<Message>,
+        cx: &mut AsyncApp,
+    ) -> Result<Self> {
+        let binary = empty_binary();
+        let message_handler = Box::new({
+            let messages_tx = messages_tx.clone();
+            move |message| {
+                messages_tx.unbounded_send(message).ok();
+            }
+        });
+        let client = DebugAdapterClient::start_remote(
+            session_id,
+            binary.clone(),
+            requests_tx,
+            messages_rx,
+            message_handler,
+            cx,
+        )
+        .await?;
+
+        Ok(Self {
+            client: Arc::new(client),
+            worktree: WeakEntity::new_invalid(),
+            tmp_breakpoint: None,
+            binary,
+            executor: cx.background_executor().clone(),
+            is_started: true,
+            has_ever_stopped: false,
+            messages_tx,
+        })
+    }
+
     pub(crate) fn worktree(&self) -> &WeakEntity<Worktree> {
         &self.worktree
     }
@@ -576,6 +638,14 @@ impl ThreadStates {
         self.known_thread_states.clear();
     }
 
+    fn last_stopped_thread(&self) -> Option<ThreadId> {
+        self.known_thread_states
+            .iter()
+            .rev()
+            .find(|(_, status)| **status == ThreadStatus::Stopped)
+            .map(|(thread_id, _)| *thread_id)
+    }
+
     fn stop_thread(&mut self, thread_id: ThreadId) {
         self.known_thread_states
             .insert(thread_id, ThreadStatus::Stopped);
@@ -646,6 +716,9 @@ pub struct Session {
     requests: HashMap<TypeId, HashMap<RequestSlot, Shared<Task<Option<()>>>>>,
     pub(crate) breakpoint_store: Entity<BreakpointStore>,
     ignore_breakpoints: bool,
+    read_only: bool,
+    recording: Option<Arc<DapRecording>>,
+    downstream_client: Option<(AnyProtoClient, u64)>,
     exception_breakpoints: BTreeMap<String, (ExceptionBreakpointsFilter, IsEnabled)>,
     background_tasks: Vec<Task<()>>,
     task_context: TaskContext,
@@ -812,6 +885,9 @@ impl Session {
                 locations: Default::default(),
                 is_session_terminated: false,
                 ignore_breakpoints: false,
+                read_only: false,
+                recording: None,
+                downstream_client: None,
                 breakpoint_store,
                 exception_breakpoints: Default::default(),
                 label,
@@ -854,6 +930,7 @@ impl Session {
                         }
                     } else {
                         let Ok(_) = this.update(cx, |session, cx| {
+                            session.send_event_downstream(&event);
                             session.handle_dap_event(event, cx);
                         }) else {
                             break;
@@ -877,6 +954,7 @@ impl Session {
         self.background_tasks = background_tasks;
         let id = self.id;
         let parent_session = self.parent_session.clone();
+        let recording = self.recording.clone();
 
         cx.spawn(async move |this, cx| {
             let mode = RunningMode::new(
@@ -884,6 +962,7 @@ impl Session {
                 parent_session,
                 worktree.downgrade(),
                 binary.clone(),
+                recording,
                 message_tx,
                 cx,
             )
@@ -919,6 +998,64 @@ impl Session {
         })
     }
 
+    /// Boots a read-only session that replays a recording instead of running a
+    /// debug adapter, for reviewing a session after it has ended.
+    pub fn boot_replay(
+        &mut self,
+        recording: Arc<DapRecording>,
+        worktree: Entity<Worktree>,
+        dap_store: WeakEntity<DapStore>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.recording = Some(recording);
+        self.set_read_only(true, cx);
+        self.boot(empty_binary(), worktree, dap_store, cx)
+    }
+
+    /// Boots a read-only session that mirrors a debug session running on the host of a
+    /// shared project. Requests are sent to `requests_tx` for the host to answer, and the
+    /// host's responses and the adapter's events are read from `messages_rx`.
+    pub(super) fn boot_remote(
+        &mut self,
+        capabilities: Capabilities,
+        requests_tx: mpsc::UnboundedSender<dap::messages::Request>,
+        messages_rx: mpsc::UnboundedReceiver<Message>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let (message_tx, mut message_rx) = futures::channel::mpsc::unbounded();
+        self.background_tasks = vec![cx.spawn(async move |this: WeakEntity<Session>, cx| {
+            while let Some(message) = message_rx.next().await {
+                if let Message::Event(event) = message {
+                    let Ok(_) = this.update(cx, |session, cx| {
+                        session.handle_dap_event(event, cx);
+                    }) else {
+                        break;
+                    };
+                }
+            }
+        })];
+        self.capabilities = capabilities;
+        self.ignore_breakpoints = true;
+        self.set_read_only(true, cx);
+        let id = self.id;
+
+        cx.spawn(async move |this, cx| {
+            let mode =
+                RunningMode::new_remote(id, requests_tx, messages_rx, message_tx, cx).await?;
+            this.update(cx, |this, cx| {
+                this.mode = Mode::Running(mode);
+                cx.emit(SessionStateEvent::Running);
+                cx.emit(SessionEvent::CapabilitiesLoaded);
+                cx.notify();
+            })
+        })
+    }
+
+    /// The recording this session replays, if it was booted with [`Self::boot_replay`].
+    pub fn recording(&self) -> Option<&Arc<DapRecording>> {
+        self.recording.as_ref()
+    }
+
     pub fn session_id(&self) -> SessionId {
         self.id
     }
@@ -995,10 +1132,58 @@ impl Session {
         self.label.clone()
     }
 
+    /// Whether this session inspects a core dump, replays a recording or mirrors a
+    /// collaborator's session, instead of debugging a live process of its own.
+    ///
+    /// Threads of a read-only session can be browsed, but not resumed, stepped or modified.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub(super) fn set_read_only(&mut self, read_only: bool, cx: &mut Context<Self>) {
+        self.read_only = read_only;
+        cx.notify();
+    }
+
     pub fn is_terminated(&self) -> bool {
         self.is_session_terminated
     }
 
+    pub(super) fn set_downstream_client(
+        &mut self,
+        downstream_client: Option<(AnyProtoClient, u64)>,
+    ) {
+        self.downstream_client = downstream_client;
+    }
+
+    /// The message that shows guests of a shared project this session.
+    pub(super) fn to_share_proto(&self, project_id: u64) -> Option<proto::ShareDebugSession> {
+        Some(proto::ShareDebugSession {
+            project_id,
+            session_id: self.id.to_proto(),
+            label: self.label.to_string(),
+            adapter: self.adapter.to_string(),
+            capabilities: serde_json::to_string(&self.capabilities).log_err()?,
+            stopped_thread_id: self.thread_states.last_stopped_thread().map(|id| id.0),
+        })
+    }
+
+    fn send_event_downstream(&self, event: &Events) {
+        let Some((client, project_id)) = &self.downstream_client else {
+            return;
+        };
+        let Some(event) = serde_json::to_string(event).log_err() else {
+            return;
+        };
+        client
+            .send(proto::DebugSessionEvent {
+                project_id: *project_id,
+                session_id: self.id.to_proto(),
+                event,
+            })
+            .log_err();
+    }
+
     pub fn console_output(&mut self, cx: &mut Context<Self>) -> mpsc::UnboundedSender<String> {
         let (tx, mut rx) = mpsc::unbounded();
 
@@ -1262,6 +1447,9 @@ impl Session {
         active_thread_id: ThreadId,
         cx: &mut Context<Self>,
     ) {
+        if self.read_only {
+            return;
+        }
         match &mut self.mode {
             Mode::Running(local_mode) => {
                 if !matches!(
@@ -1291,6 +1479,64 @@ impl Session {
         }
     }
 
+    /// Whether the adapter can move execution to another line, see [`Self::jump_to_position`].
+    pub fn supports_goto_targets(&self) -> bool {
+        !self.read_only && GotoTargetsCommand::is_supported(&self.capabilities)
+    }
+
+    /// Moves a stopped thread to `row` in `path` without executing the code in between.
+    ///
+    /// The adapter is asked for the goto targets of that line and the thread jumps
+    /// to the first one. Adapters report the new location with a stopped event.
+    pub fn jump_to_position(
+        &mut self,
+        path: Arc<Path>,
+        row: u32,
+        thread_id: ThreadId,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.supports_goto_targets()
+            || !matches!(
+                self.thread_states.thread_state(thread_id),
+                Some(ThreadStatus::Stopped)
+            )
+        {
+            return;
+        }
+
+        let targets = self.request(
+            GotoTargetsCommand {
+                path,
+                line: row as u64 + 1,
+                column: None,
+            },
+            |_, result, _| result.log_err(),
+            cx,
+        );
+
+        cx.spawn(async move |this, cx| {
+            let Some(target) = targets.await.and_then(|targets| targets.into_iter().next()) else {
+                log::warn!("Debug adapter returned no goto targets for row {row}");
+                return;
+            };
+
+            this.update(cx, |this, cx| {
+                this.thread_states.process_step(thread_id);
+                this.request(
+                    GotoCommand {
+                        thread_id: thread_id.0,
+                        target_id: target.id,
+                    },
+                    Self::on_step_response::<GotoCommand>(thread_id),
+                    cx,
+                )
+                .detach();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
     pub fn has_new_output(&self, last_update: OutputToken) -> bool {
         self.output_token.0.checked_sub(last_update.0).unwrap_or(0) != 0
     }
@@ -1811,6 +2057,9 @@ impl Session {
     }
 
     pub fn pause_thread(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        if self.read_only {
+            return;
+        }
         self.request(
             PauseCommand {
                 thread_id: thread_id.0,
@@ -1822,6 +2071,9 @@ impl Session {
     }
 
     pub fn restart_stack_frame(&mut self, stack_frame_id: u64, cx: &mut Context<Self>) {
+        if self.read_only {
+            return;
+        }
         self.request(
             RestartStackFrameCommand { stack_frame_id },
             Self::empty_response,
@@ -1902,6 +2154,9 @@ impl Session {
     }
 
     pub fn continue_thread(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        if self.read_only {
+            return;
+        }
         self.thread_states.continue_thread(thread_id);
         self.request(
             ContinueCommand {
@@ -1932,6 +2187,9 @@ impl Session {
         granularity: SteppingGranularity,
         cx: &mut Context<Self>,
     ) {
+        if self.read_only {
+            return;
+        }
         let supports_single_thread_execution_requests =
             self.capabilities.supports_single_thread_execution_requests;
         let supports_stepping_granularity = self
@@ -1962,6 +2220,9 @@ impl Session {
         granularity: SteppingGranularity,
         cx: &mut Context<Self>,
     ) {
+        if self.read_only {
+            return;
+        }
         let supports_single_thread_execution_requests =
             self.capabilities.supports_single_thread_execution_requests;
         let supports_stepping_granularity = self
@@ -1992,6 +2253,9 @@ impl Session {
         granularity: SteppingGranularity,
         cx: &mut Context<Self>,
     ) {
+        if self.read_only {
+            return;
+        }
         let supports_single_thread_execution_requests =
             self.capabilities.supports_single_thread_execution_requests;
         let supports_stepping_granularity = self
@@ -2022,6 +2286,9 @@ impl Session {
         granularity: SteppingGranularity,
         cx: &mut Context<Self>,
     ) {
+        if self.read_only {
+            return;
+        }
         let supports_single_thread_execution_requests =
             self.capabilities.supports_single_thread_execution_requests;
         let supports_stepping_granularity = self
@@ -2279,7 +2546,7 @@ impl Session {
         value: String,
         cx: &mut Context<Self>,
     ) {
-        if self.capabilities.supports_set_variable.unwrap_or_default() {
+        if !self.read_only && self.capabilities.supports_set_variable.unwrap_or_default() {
             self.request(
                 SetVariableValueCommand {
                     name,
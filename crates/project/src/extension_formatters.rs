@@ -0,0 +1,268 @@
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result};
+use extension::{
+    Extension, ExtensionFormatterProxy, ExtensionHostProxy, FormattingOptions,
+    sort_and_validate_buffer_edits,
+};
+use gpui::{App, AsyncApp, Entity, Global};
+use language::{Buffer, Diff, LanguageName};
+
+pub(crate) fn init(cx: &mut App) {
+    let proxy = ExtensionHostProxy::default_global(cx);
+    proxy.register_formatter_proxy(ExtensionFormatterRegistryProxy);
+}
+
+/// A formatter provided by an extension.
+#[derive(Clone)]
+pub(crate) struct ExtensionFormatter {
+    id: Arc<str>,
+    extension: Arc<dyn Extension>,
+    languages: Vec<LanguageName>,
+}
+
+/// The formatters that have been registered by extensions, in the order in which they were registered.
+#[derive(Default)]
+pub(crate) struct ExtensionFormatters {
+    formatters: Vec<ExtensionFormatter>,
+}
+
+impl Global for ExtensionFormatters {}
+
+impl ExtensionFormatters {
+    /// Returns the extension formatter for the given language.
+    ///
+    /// If a name is given, only the formatter with that ID is considered.
+    /// Otherwise, the first formatter that supports the language is returned.
+    pub(crate) fn formatter_for_language(
+        name: Option<&str>,
+        language: &LanguageName,
+        cx: &App,
+    ) -> Option<ExtensionFormatter> {
+        cx.try_global::<Self>()?
+            .formatters
+            .iter()
+            .find(|formatter| {
+                name.is_none_or(|name| formatter.id.as_ref() == name)
+                    && formatter.languages.contains(language)
+            })
+            .cloned()
+    }
+}
+
+impl ExtensionFormatter {
+    pub(crate) fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn is(&self, extension_id: &str, formatter_id: &str) -> bool {
+        self.extension.manifest().id.as_ref() == extension_id && self.id.as_ref() == formatter_id
+    }
+
+    /// Formats the given buffer, returning `None` if the formatter made no changes.
+    pub(crate) async fn format(
+        &self,
+        buffer: &Entity<Buffer>,
+        language: LanguageName,
+        options: FormattingOptions,
+        cx: &mut AsyncApp,
+    ) -> Result<Option<Diff>> {
+        let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot())?;
+        let text = snapshot.text();
+        let mut edits = self
+            .extension
+            .format_buffer(self.id.clone(), language, text.clone(), options)
+            .await?;
+        if edits.is_empty() {
+            return Ok(None);
+        }
+
+        sort_and_validate_buffer_edits(&mut edits, &text)
+            .context("formatter returned invalid edits")?;
+
+        Ok(Some(Diff {
+            base_version: snapshot.version().clone(),
+            line_ending: snapshot.line_ending(),
+            edits: edits
+                .into_iter()
+                .map(|edit| (edit.range, edit.new_text.into()))
+                .collect(),
+        }))
+    }
+}
+
+struct ExtensionFormatterRegistryProxy;
+
+impl ExtensionFormatterProxy for ExtensionFormatterRegistryProxy {
+    fn register_formatter(
+        &self,
+        extension: Arc<dyn Extension>,
+        formatter_id: Arc<str>,
+        languages: Vec<LanguageName>,
+        cx: &mut App,
+    ) {
+        let extension_id = extension.manifest().id.clone();
+        let formatters = &mut cx.default_global::<ExtensionFormatters>().formatters;
+        formatters.retain(|formatter| !formatter.is(&extension_id, &formatter_id));
+        formatters.push(ExtensionFormatter {
+            id: formatter_id,
+            extension,
+            languages,
+        });
+    }
+
+    fn unregister_formatter(&self, extension_id: Arc<str>, formatter_id: Arc<str>, cx: &mut App) {
+        cx.default_global::<ExtensionFormatters>()
+            .formatters
+            .retain(|formatter| !formatter.is(&extension_id, &formatter_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use collections::HashSet;
+    use extension::{BufferEdit, FakeExtension};
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use language::{Language, LanguageConfig, LanguageMatcher};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    use super::*;
+    use crate::{
+        Project,
+        lsp_store::{FormatTrigger, LspFormatTarget},
+    };
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+    }
+
+    fn formatter_id(name: Option<&str>, language: &str, cx: &App) -> Option<String> {
+        ExtensionFormatters::formatter_for_language(name, &language.into(), cx)
+            .map(|formatter| formatter.id().to_string())
+    }
+
+    #[gpui::test]
+    fn test_registering_formatters(cx: &mut App) {
+        let proxy = ExtensionFormatterRegistryProxy;
+        proxy.register_formatter(
+            FakeExtension::new("first"),
+            "first-formatter".into(),
+            vec!["Rust".into()],
+            cx,
+        );
+        proxy.register_formatter(
+            FakeExtension::new("second"),
+            "second-formatter".into(),
+            vec!["Rust".into(), "TOML".into()],
+            cx,
+        );
+
+        assert_eq!(
+            formatter_id(None, "Rust", cx).as_deref(),
+            Some("first-formatter")
+        );
+        assert_eq!(
+            formatter_id(Some("second-formatter"), "Rust", cx).as_deref(),
+            Some("second-formatter")
+        );
+        assert_eq!(
+            formatter_id(None, "TOML", cx).as_deref(),
+            Some("second-formatter")
+        );
+        assert_eq!(formatter_id(Some("first-formatter"), "TOML", cx), None);
+        assert_eq!(formatter_id(None, "JSON", cx), None);
+
+        // Formatters are only unregistered by the extension that registered them.
+        proxy.unregister_formatter("second".into(), "first-formatter".into(), cx);
+        assert_eq!(
+            formatter_id(None, "Rust", cx).as_deref(),
+            Some("first-formatter")
+        );
+
+        proxy.unregister_formatter("first".into(), "first-formatter".into(), cx);
+        assert_eq!(
+            formatter_id(None, "Rust", cx).as_deref(),
+            Some("second-formatter")
+        );
+
+        proxy.unregister_formatter("second".into(), "second-formatter".into(), cx);
+        assert_eq!(formatter_id(None, "Rust", cx), None);
+        assert_eq!(formatter_id(None, "TOML", cx), None);
+    }
+
+    #[gpui::test]
+    async fn test_formatting_with_extension_formatter(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ "main.rs": "fn main( ) {}\n" }))
+            .await;
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        project.read_with(cx, |project, _| {
+            project.languages().add(Arc::new(Language::new(
+                LanguageConfig {
+                    name: "Rust".into(),
+                    matcher: LanguageMatcher {
+                        path_suffixes: vec!["rs".to_string()],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                None,
+            )))
+        });
+
+        let extension = FakeExtension::new("rust-formatter");
+        extension.on_format_buffer(|formatter_id, language_name, text| {
+            assert_eq!(formatter_id.as_ref(), "rustfmt");
+            assert_eq!(language_name.as_ref(), "Rust");
+            assert_eq!(text, "fn main( ) {}\n");
+            Ok(vec![BufferEdit {
+                range: 8..9,
+                new_text: String::new(),
+            }])
+        });
+        cx.update(|cx| {
+            ExtensionFormatterRegistryProxy.register_formatter(
+                extension,
+                "rustfmt".into(),
+                vec!["Rust".into()],
+                cx,
+            )
+        });
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/dir/main.rs"), cx)
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        // Without any language servers, the extension formatter is used by default.
+        project
+            .update(cx, |project, cx| {
+                project.format(
+                    HashSet::from_iter([buffer.clone()]),
+                    LspFormatTarget::Buffers,
+                    true,
+                    FormatTrigger::Manual,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            buffer.read_with(cx, |buffer, _| buffer.text()),
+            "fn main() {}\n"
+        );
+    }
+}
@@ -0,0 +1,597 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use collections::HashMap;
+use extension::{
+    EnvVars, Extension, ExtensionHostProxy, ExtensionTaskSourceProxy, WorktreeDelegate,
+};
+use fs::Fs;
+use futures::future::join_all;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use gpui::{App, AppContext as _, Context, Entity, Global, Subscription, Task};
+use task::{TaskVariables, VariableName};
+use util::ResultExt as _;
+use worktree::{Worktree, WorktreeId};
+
+use crate::{
+    Inventory, ProjectEnvironment, TaskSourceKind,
+    worktree_store::{WorktreeStore, WorktreeStoreEvent},
+};
+
+pub(crate) fn init(cx: &mut App) {
+    let proxy = ExtensionHostProxy::default_global(cx);
+    proxy.register_task_source_proxy(ExtensionTaskSourceRegistryProxy);
+}
+
+/// A task source provided by an extension.
+#[derive(Clone)]
+pub(crate) struct ExtensionTaskSource {
+    id: Arc<str>,
+    extension: Arc<dyn Extension>,
+}
+
+impl ExtensionTaskSource {
+    fn is(&self, extension_id: &str, task_source_id: &str) -> bool {
+        self.extension.manifest().id.as_ref() == extension_id && self.id.as_ref() == task_source_id
+    }
+
+    fn source_kind(&self, worktree_id: WorktreeId) -> TaskSourceKind {
+        TaskSourceKind::Extension {
+            worktree: worktree_id,
+            extension_id: self.extension.manifest().id.clone().into(),
+            task_source: self.id.clone().into(),
+        }
+    }
+}
+
+struct GlobalExtensionTaskSources(Entity<ExtensionTaskSources>);
+
+impl Global for GlobalExtensionTaskSources {}
+
+/// The task sources that have been registered by extensions, in the order in which they were registered.
+#[derive(Default)]
+pub(crate) struct ExtensionTaskSources {
+    sources: Vec<ExtensionTaskSource>,
+}
+
+impl ExtensionTaskSources {
+    /// Returns the global [`ExtensionTaskSources`].
+    ///
+    /// Inserts an empty [`ExtensionTaskSources`] if one does not yet exist.
+    pub(crate) fn default_global(cx: &mut App) -> Entity<Self> {
+        if !cx.has_global::<GlobalExtensionTaskSources>() {
+            let sources = cx.new(|_| Self::default());
+            cx.set_global(GlobalExtensionTaskSources(sources));
+        }
+        cx.global::<GlobalExtensionTaskSources>().0.clone()
+    }
+
+    /// Returns the values of the custom task variables that every task source provides for a
+    /// file in the given worktree.
+    pub(crate) fn task_variables(
+        worktree: &Entity<Worktree>,
+        path: Option<Arc<Path>>,
+        fs: Arc<dyn Fs>,
+        shell_env: HashMap<String, String>,
+        cx: &App,
+    ) -> Task<TaskVariables> {
+        let sources = cx
+            .try_global::<GlobalExtensionTaskSources>()
+            .map(|global| global.0.read(cx).sources.clone())
+            .unwrap_or_default();
+        if sources.is_empty() {
+            return Task::ready(TaskVariables::default());
+        }
+
+        let delegate: Arc<dyn WorktreeDelegate> = Arc::new(TaskSourceWorktreeDelegate {
+            worktree: worktree.read(cx).snapshot(),
+            fs,
+            shell_env,
+        });
+        cx.background_spawn(async move {
+            let responses = join_all(sources.iter().map(|source| {
+                source.extension.task_variables(
+                    source.id.clone(),
+                    delegate.clone(),
+                    path.as_ref().map(|path| path.to_path_buf()),
+                )
+            }))
+            .await;
+
+            let mut task_variables = TaskVariables::default();
+            for (source, response) in sources.iter().zip(responses) {
+                let Some(variables) = response
+                    .with_context(|| {
+                        format!(
+                            "failed to get task variables from extension {}",
+                            source.extension.manifest().id
+                        )
+                    })
+                    .log_err()
+                else {
+                    continue;
+                };
+                for (name, value) in variables {
+                    task_variables.insert(VariableName::Custom(name.into()), value);
+                }
+            }
+            task_variables
+        })
+    }
+}
+
+struct ExtensionTaskSourceRegistryProxy;
+
+impl ExtensionTaskSourceProxy for ExtensionTaskSourceRegistryProxy {
+    fn register_task_source(
+        &self,
+        extension: Arc<dyn Extension>,
+        task_source_id: Arc<str>,
+        cx: &mut App,
+    ) {
+        let extension_id = extension.manifest().id.clone();
+        ExtensionTaskSources::default_global(cx).update(cx, |registry, cx| {
+            registry
+                .sources
+                .retain(|source| !source.is(&extension_id, &task_source_id));
+            registry.sources.push(ExtensionTaskSource {
+                id: task_source_id,
+                extension,
+            });
+            cx.notify();
+        });
+    }
+
+    fn unregister_task_source(
+        &self,
+        extension_id: Arc<str>,
+        task_source_id: Arc<str>,
+        cx: &mut App,
+    ) {
+        ExtensionTaskSources::default_global(cx).update(cx, |registry, cx| {
+            registry
+                .sources
+                .retain(|source| !source.is(&extension_id, &task_source_id));
+            cx.notify();
+        });
+    }
+}
+
+/// Keeps the tasks that extension task sources provide for the worktrees of a local project
+/// up to date in the project's [`Inventory`].
+///
+/// The tasks of a worktree are requested again whenever one of the files that the task
+/// source watches in it changes.
+pub(crate) struct ExtensionWorktreeTasks {
+    inventory: Entity<Inventory>,
+    worktree_store: Entity<WorktreeStore>,
+    environment: Entity<ProjectEnvironment>,
+    fs: Arc<dyn Fs>,
+    sources: Vec<ExtensionTaskSource>,
+    watched_paths: HashMap<TaskSourceKind, GlobSet>,
+    refresh_tasks: HashMap<TaskSourceKind, Task<()>>,
+    _subscriptions: [Subscription; 2],
+}
+
+impl ExtensionWorktreeTasks {
+    pub(crate) fn new(
+        inventory: Entity<Inventory>,
+        worktree_store: Entity<WorktreeStore>,
+        environment: Entity<ProjectEnvironment>,
+        fs: Arc<dyn Fs>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let registry = ExtensionTaskSources::default_global(cx);
+        let subscriptions = [
+            cx.observe(&registry, |this, registry, cx| {
+                this.sources_changed(registry, cx);
+            }),
+            cx.subscribe(&worktree_store, |this, _, event, cx| {
+                this.on_worktree_store_event(event, cx);
+            }),
+        ];
+        let mut this = Self {
+            inventory,
+            worktree_store,
+            environment,
+            fs,
+            sources: Vec::new(),
+            watched_paths: HashMap::default(),
+            refresh_tasks: HashMap::default(),
+            _subscriptions: subscriptions,
+        };
+        this.sources_changed(registry, cx);
+        this
+    }
+
+    fn sources_changed(&mut self, registry: Entity<ExtensionTaskSources>, cx: &mut Context<Self>) {
+        self.sources = registry.read(cx).sources.clone();
+
+        let sources = &self.sources;
+        let is_registered = |kind: &TaskSourceKind| {
+            let TaskSourceKind::Extension {
+                extension_id,
+                task_source,
+                ..
+            } = kind
+            else {
+                return false;
+            };
+            sources
+                .iter()
+                .any(|source| source.is(extension_id, task_source))
+        };
+        self.watched_paths.retain(|kind, _| is_registered(kind));
+        self.refresh_tasks.retain(|kind, _| is_registered(kind));
+        self.inventory.update(cx, |inventory, _| {
+            inventory.retain_extension_tasks(is_registered);
+        });
+
+        // An extension may have been reloaded, so request the tasks of every source again.
+        let worktrees = self.worktree_store.read(cx).worktrees().collect::<Vec<_>>();
+        for worktree in worktrees {
+            for source in self.sources.clone() {
+                self.refresh(&worktree, source, cx);
+            }
+        }
+    }
+
+    fn on_worktree_store_event(&mut self, event: &WorktreeStoreEvent, cx: &mut Context<Self>) {
+        match event {
+            WorktreeStoreEvent::WorktreeAdded(worktree) => {
+                for source in self.sources.clone() {
+                    self.refresh(worktree, source, cx);
+                }
+            }
+            WorktreeStoreEvent::WorktreeRemoved(_, worktree_id) => {
+                let is_other_worktree = |kind: &TaskSourceKind| match kind {
+                    TaskSourceKind::Extension { worktree, .. } => worktree != worktree_id,
+                    _ => true,
+                };
+                self.watched_paths.retain(|kind, _| is_other_worktree(kind));
+                self.refresh_tasks.retain(|kind, _| is_other_worktree(kind));
+                self.inventory.update(cx, |inventory, _| {
+                    inventory.retain_extension_tasks(is_other_worktree);
+                });
+            }
+            WorktreeStoreEvent::WorktreeUpdatedEntries(worktree_id, changes) => {
+                let Some(worktree) = self
+                    .worktree_store
+                    .read(cx)
+                    .worktree_for_id(*worktree_id, cx)
+                else {
+                    return;
+                };
+                let sources_to_refresh = self
+                    .sources
+                    .iter()
+                    .filter(|source| {
+                        self.watched_paths
+                            .get(&source.source_kind(*worktree_id))
+                            .is_some_and(|watched_paths| {
+                                changes
+                                    .iter()
+                                    .any(|(path, _, _)| watched_paths.is_match(path))
+                            })
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>();
+                for source in sources_to_refresh {
+                    self.refresh(&worktree, source, cx);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn refresh(
+        &mut self,
+        worktree: &Entity<Worktree>,
+        source: ExtensionTaskSource,
+        cx: &mut Context<Self>,
+    ) {
+        let (worktree_id, scan_complete) = {
+            let worktree = worktree.read(cx);
+            let Some(local_worktree) = worktree.as_local() else {
+                return;
+            };
+            if !worktree.is_visible() || worktree.root_dir().is_none() {
+                return;
+            }
+            (worktree.id(), local_worktree.scan_complete())
+        };
+        let task_source_kind = source.source_kind(worktree_id);
+        let shell_env = self.environment.update(cx, |environment, cx| {
+            environment.get_worktree_environment(worktree.clone(), cx)
+        });
+        let worktree = worktree.clone();
+        let fs = self.fs.clone();
+
+        let refresh_task = cx.spawn({
+            let task_source_kind = task_source_kind.clone();
+            async move |this, cx| {
+                // Task sources usually read files of the worktree, so wait until it has been scanned.
+                scan_complete.await;
+                let shell_env = shell_env.await.unwrap_or_default();
+                let Ok(worktree_snapshot) =
+                    worktree.read_with(cx, |worktree, _| worktree.snapshot())
+                else {
+                    return;
+                };
+                let delegate = Arc::new(TaskSourceWorktreeDelegate {
+                    worktree: worktree_snapshot,
+                    fs,
+                    shell_env,
+                });
+                let Some(tasks) = source
+                    .extension
+                    .worktree_tasks(source.id.clone(), delegate)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "failed to get tasks from extension {}",
+                            source.extension.manifest().id
+                        )
+                    })
+                    .log_err()
+                else {
+                    return;
+                };
+
+                let mut watched_paths = GlobSetBuilder::new();
+                for pattern in &tasks.watched_paths {
+                    if let Some(glob) = Glob::new(pattern)
+                        .with_context(|| format!("invalid watched path glob {pattern:?}"))
+                        .log_err()
+                    {
+                        watched_paths.add(glob);
+                    }
+                }
+                let Some(watched_paths) = watched_paths.build().log_err() else {
+                    return;
+                };
+
+                this.update(cx, |this, cx| {
+                    this.watched_paths
+                        .insert(task_source_kind.clone(), watched_paths);
+                    this.inventory.update(cx, |inventory, _| {
+                        inventory.update_extension_tasks(task_source_kind, tasks.templates);
+                    });
+                })
+                .ok();
+            }
+        });
+        self.refresh_tasks.insert(task_source_kind, refresh_task);
+    }
+}
+
+/// A [`WorktreeDelegate`] for calling extension task sources with a local worktree.
+struct TaskSourceWorktreeDelegate {
+    worktree: worktree::Snapshot,
+    fs: Arc<dyn Fs>,
+    shell_env: HashMap<String, String>,
+}
+
+#[async_trait]
+impl WorktreeDelegate for TaskSourceWorktreeDelegate {
+    fn id(&self) -> u64 {
+        self.worktree.id().to_proto()
+    }
+
+    fn root_path(&self) -> String {
+        self.worktree.abs_path().to_string_lossy().to_string()
+    }
+
+    async fn read_text_file(&self, path: PathBuf) -> Result<String> {
+        let entry = self
+            .worktree
+            .entry_for_path(&path)
+            .with_context(|| format!("no worktree entry for path {path:?}"))?;
+        let abs_path = self
+            .worktree
+            .absolutize(&entry.path)
+            .with_context(|| format!("cannot absolutize path {path:?}"))?;
+
+        self.fs.load(&abs_path).await
+    }
+
+    async fn which(&self, binary_name: String) -> Option<String> {
+        let shell_path = self.shell_env.get("PATH");
+        which::which_in(binary_name, shell_path, self.worktree.abs_path())
+            .ok()
+            .map(|path| path.to_string_lossy().to_string())
+    }
+
+    async fn shell_env(&self) -> EnvVars {
+        self.shell_env.clone().into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+
+    use extension::{FakeExtension, WorktreeTasks};
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use language::Location;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use task::TaskTemplate;
+    use text::Anchor;
+    use util::path;
+
+    use super::*;
+    use crate::Project;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+    }
+
+    async fn task_labels(
+        project: &Entity<Project>,
+        worktree_id: WorktreeId,
+        cx: &mut TestAppContext,
+    ) -> Vec<String> {
+        let inventory = project.read_with(cx, |project, cx| {
+            project
+                .task_store()
+                .read(cx)
+                .task_inventory()
+                .cloned()
+                .unwrap()
+        });
+        inventory
+            .update(cx, |inventory, cx| {
+                inventory.list_tasks(None, None, Some(worktree_id), cx)
+            })
+            .await
+            .into_iter()
+            .map(|(_, template)| template.label)
+            .collect()
+    }
+
+    #[gpui::test]
+    async fn test_refreshing_tasks_when_watched_files_change(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "targets.txt": "build\n",
+                "src": { "main.rs": "fn main() {}\n" },
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let worktree_id = project.read_with(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+
+        let requests = Arc::new(AtomicUsize::new(0));
+        let extension = FakeExtension::new("targets");
+        extension.on_worktree_tasks({
+            let requests = requests.clone();
+            move |task_source_id| {
+                assert_eq!(task_source_id.as_ref(), "targets");
+                let request = requests.fetch_add(1, SeqCst) + 1;
+                Ok(WorktreeTasks {
+                    templates: vec![TaskTemplate {
+                        label: format!("build {request}"),
+                        command: "make".to_string(),
+                        ..TaskTemplate::default()
+                    }],
+                    watched_paths: vec!["targets.txt".to_string()],
+                })
+            }
+        });
+        cx.update(|cx| {
+            ExtensionTaskSourceRegistryProxy.register_task_source(extension, "targets".into(), cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(requests.load(SeqCst), 1);
+        assert_eq!(task_labels(&project, worktree_id, cx).await, ["build 1"]);
+
+        fs.insert_file(path!("/dir/targets.txt"), b"build\ntest\n".to_vec())
+            .await;
+        cx.run_until_parked();
+        assert_eq!(requests.load(SeqCst), 2);
+        assert_eq!(task_labels(&project, worktree_id, cx).await, ["build 2"]);
+
+        // Changes to files that the task source doesn't watch don't request the tasks again.
+        fs.insert_file(path!("/dir/src/main.rs"), b"fn main() { }\n".to_vec())
+            .await;
+        cx.run_until_parked();
+        assert_eq!(requests.load(SeqCst), 2);
+
+        cx.update(|cx| {
+            ExtensionTaskSourceRegistryProxy.unregister_task_source(
+                "targets".into(),
+                "targets".into(),
+                cx,
+            )
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            task_labels(&project, worktree_id, cx).await,
+            Vec::<String>::new()
+        );
+    }
+
+    #[gpui::test]
+    async fn test_merging_extension_task_variables(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({ "src": { "main.rs": "fn main() {}\n" } }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+        let extension = FakeExtension::new("targets");
+        extension.on_worktree_tasks(|_| Ok(WorktreeTasks::default()));
+        extension.on_task_variables(|task_source_id, path| {
+            assert_eq!(task_source_id.as_ref(), "targets");
+            assert_eq!(path, Some(PathBuf::from("src/main.rs")));
+            Ok(vec![
+                ("TARGET".to_string(), "//src:main".to_string()),
+                ("PROFILE".to_string(), "release".to_string()),
+            ])
+        });
+        cx.update(|cx| {
+            ExtensionTaskSourceRegistryProxy.register_task_source(extension, "targets".into(), cx)
+        });
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/dir/src/main.rs"), cx)
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        let captured_variables = TaskVariables::from_iter([(
+            VariableName::Custom("TARGET".into()),
+            "//src:captured".to_string(),
+        )]);
+        let task_context = project
+            .update(cx, |project, cx| {
+                project.task_store().update(cx, |task_store, cx| {
+                    task_store.task_context_for_location(
+                        captured_variables,
+                        Location {
+                            buffer,
+                            range: Anchor::MIN..Anchor::MIN,
+                        },
+                        cx,
+                    )
+                })
+            })
+            .await
+            .unwrap();
+
+        // Variables captured from the buffer take precedence over the ones from extensions.
+        assert_eq!(
+            task_context
+                .task_variables
+                .get(&VariableName::Custom("TARGET".into())),
+            Some("//src:captured")
+        );
+        assert_eq!(
+            task_context
+                .task_variables
+                .get(&VariableName::Custom("PROFILE".into())),
+            Some("release")
+        );
+    }
+}
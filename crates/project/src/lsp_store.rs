@@ -8,6 +8,7 @@ use crate::{
     ProjectPath, ProjectTransaction, PulledDiagnostics, ResolveState, Symbol, ToolchainStore,
     buffer_store::{BufferStore, BufferStoreEvent},
     environment::ProjectEnvironment,
+    extension_formatters::ExtensionFormatters,
     lsp_command::{self, *},
     lsp_store,
     manifest_tree::{
@@ -1327,20 +1328,21 @@ impl LocalLspStore {
         logger: zlog::Logger,
         cx: &mut AsyncApp,
     ) -> Result<()> {
-        let (adapters_and_servers, settings) = lsp_store.update(cx, |lsp_store, cx| {
-            buffer.handle.update(cx, |buffer, cx| {
-                let adapters_and_servers = lsp_store
-                    .as_local()
-                    .unwrap()
-                    .language_servers_for_buffer(buffer, cx)
-                    .map(|(adapter, lsp)| (adapter.clone(), lsp.clone()))
-                    .collect::<Vec<_>>();
-                let settings =
-                    language_settings(buffer.language().map(|l| l.name()), buffer.file(), cx)
-                        .into_owned();
-                (adapters_and_servers, settings)
-            })
-        })?;
+        let (adapters_and_servers, language_name, settings) =
+            lsp_store.update(cx, |lsp_store, cx| {
+                buffer.handle.update(cx, |buffer, cx| {
+                    let adapters_and_servers = lsp_store
+                        .as_local()
+                        .unwrap()
+                        .language_servers_for_buffer(buffer, cx)
+                        .map(|(adapter, lsp)| (adapter.clone(), lsp.clone()))
+                        .collect::<Vec<_>>();
+                    let language_name = buffer.language().map(|l| l.name());
+                    let settings =
+                        language_settings(language_name.clone(), buffer.file(), cx).into_owned();
+                    (adapters_and_servers, language_name, settings)
+                })
+            })?;
 
         /// Apply edits to the buffer that will become part of the formatting transaction.
         /// Fails if the buffer has been edited since the start of that transaction.
@@ -1403,6 +1405,16 @@ impl LocalLspStore {
             }
         }
 
+        // When there are no language servers for the buffer, an extension formatter
+        // for its language takes the place of the primary language server.
+        let use_extension_formatter_for_auto = adapters_and_servers.is_empty()
+            && language_name.as_ref().is_some_and(|language_name| {
+                cx.update(|cx| {
+                    ExtensionFormatters::formatter_for_language(None, language_name, cx).is_some()
+                })
+                .unwrap_or(false)
+            });
+
         let formatters = match (trigger, &settings.format_on_save) {
             (FormatTrigger::Save, FormatOnSave::Off) => &[],
             (FormatTrigger::Save, FormatOnSave::List(formatters)) => formatters.as_slice(),
@@ -1412,6 +1424,9 @@ impl LocalLspStore {
                         if settings.prettier.allowed {
                             zlog::trace!(logger => "Formatter set to auto: defaulting to prettier");
                             std::slice::from_ref(&Formatter::Prettier)
+                        } else if use_extension_formatter_for_auto {
+                            zlog::trace!(logger => "Formatter set to auto: no language servers, defaulting to extension formatter");
+                            std::slice::from_ref(&Formatter::Extension { name: None })
                         } else {
                             zlog::trace!(logger => "Formatter set to auto: defaulting to primary language server");
                             std::slice::from_ref(&Formatter::LanguageServer { name: None })
@@ -1480,6 +1495,57 @@ impl LocalLspStore {
                         },
                     )?;
                 }
+                Formatter::Extension { name } => {
+                    let logger = zlog::scoped!(logger => "extension");
+                    zlog::trace!(logger => "formatting");
+                    let _timer = zlog::time!(logger => "Formatting buffer via extension");
+
+                    let Some(language_name) = language_name.clone() else {
+                        zlog::warn!(logger => "Cannot format buffer without a language using extensions. Skipping");
+                        continue;
+                    };
+                    let formatter = cx.update(|cx| {
+                        ExtensionFormatters::formatter_for_language(
+                            name.as_deref(),
+                            &language_name,
+                            cx,
+                        )
+                    })?;
+                    let Some(formatter) = formatter else {
+                        log::debug!(
+                            "No extension formatter found for language '{}'. Skipping",
+                            language_name
+                        );
+                        continue;
+                    };
+
+                    let options = extension::FormattingOptions {
+                        tab_size: settings.tab_size.get(),
+                        insert_spaces: !settings.hard_tabs,
+                    };
+                    let diff = formatter
+                        .format(&buffer.handle, language_name, options, cx)
+                        .await
+                        .with_context(|| {
+                            format!(
+                                "Failed to format buffer via extension formatter: {}",
+                                formatter.id()
+                            )
+                        })?;
+                    let Some(diff) = diff else {
+                        zlog::trace!(logger => "No changes");
+                        continue;
+                    };
+
+                    extend_formatting_transaction(
+                        buffer,
+                        formatting_transaction_id,
+                        cx,
+                        |buffer, cx| {
+                            buffer.apply_diff(diff, cx);
+                        },
+                    )?;
+                }
                 Formatter::LanguageServer { name } => {
                     let logger = zlog::scoped!(logger => "language-server");
                     zlog::trace!(logger => "formatting");
@@ -3751,6 +3817,11 @@ impl LspStore {
                 Self::reload_zed_json_schemas_on_extensions_changed,
             )
             .detach();
+            cx.subscribe(
+                extension_events,
+                Self::restart_language_servers_on_dev_extension_rebuilt,
+            )
+            .detach();
         } else {
             log::debug!("No extension events global found. Skipping JSON schema auto-reload setup");
         }
@@ -4021,6 +4092,31 @@ impl LspStore {
         Ok(())
     }
 
+    /// Restarts the language servers provided by a dev extension after it is
+    /// rebuilt, so that they use the extension's new code.
+    fn restart_language_servers_on_dev_extension_rebuilt(
+        &mut self,
+        _: Entity<extension::ExtensionEvents>,
+        evt: &extension::Event,
+        cx: &mut Context<Self>,
+    ) {
+        let extension::Event::DevExtensionRebuilt(manifest) = evt else {
+            return;
+        };
+        if self.as_local().is_none() || manifest.language_servers.is_empty() {
+            return;
+        }
+
+        let servers_to_restart = manifest
+            .language_servers
+            .keys()
+            .cloned()
+            .map(LanguageServerSelector::Name)
+            .collect::<HashSet<_>>();
+        let buffers = self.buffer_store.read(cx).buffers().collect();
+        self.restart_language_servers_for_buffers(buffers, servers_to_restart, cx);
+    }
+
     pub fn reload_zed_json_schemas_on_extensions_changed(
         &mut self,
         _: Entity<extension::ExtensionEvents>,
@@ -4030,7 +4126,8 @@ impl LspStore {
         match evt {
             extension::Event::ExtensionInstalled(_)
             | extension::Event::ExtensionUninstalled(_)
-            | extension::Event::ConfigureExtensionRequested(_) => return,
+            | extension::Event::ConfigureExtensionRequested(_)
+            | extension::Event::DevExtensionRebuilt(_) => return,
             extension::Event::ExtensionsInstalledChanged => {}
         }
         if self.as_local().is_none() {
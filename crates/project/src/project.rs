@@ -4,6 +4,8 @@ pub mod connection_manager;
 pub mod context_server_store;
 pub mod debounced_delay;
 pub mod debugger;
+mod extension_formatters;
+mod extension_task_sources;
 pub mod git_store;
 pub mod image_store;
 pub mod lsp_command;
@@ -27,11 +29,14 @@ use buffer_diff::BufferDiff;
 use context_server_store::ContextServerStore;
 pub use environment::{EnvironmentErrorMessage, ProjectEnvironmentEvent};
 use git::repository::get_git_committer;
-use git_store::{Repository, RepositoryId};
+use git_store::{GitStoreEvent, Repository, RepositoryEvent, RepositoryId};
 pub mod search_history;
 mod yarn;
 
-use dap::inline_value::{InlineValueLocation, VariableLookupKind, VariableScope};
+use dap::{
+    debugger_settings::DebuggerSettings,
+    inline_value::{InlineValueLocation, VariableLookupKind, VariableScope},
+};
 
 use crate::git_store::GitStore;
 pub use git_store::{
@@ -321,6 +326,9 @@ pub enum Event {
     SnippetEdit(BufferId, Vec<(lsp::Range, Snippet)>),
     ExpandedAllForEntry(WorktreeId, ProjectEntryId),
     AgentLocationChanged,
+    SharedTerminalsChanged,
+    /// The host of this remote project started sharing the terminal with the given id.
+    RemoteTerminalOpened(u64),
 }
 
 pub struct AgentLocationChanged;
@@ -941,6 +949,7 @@ impl Project {
     pub fn init_settings(cx: &mut App) {
         WorktreeSettings::register(cx);
         ProjectSettings::register(cx);
+        DebuggerSettings::register(cx);
     }
 
     pub fn init(client: &Arc<Client>, cx: &mut App) {
@@ -962,6 +971,10 @@ impl Project {
         client.add_entity_request_handler(Self::handle_open_buffer_by_path);
         client.add_entity_request_handler(Self::handle_open_new_buffer);
         client.add_entity_message_handler(Self::handle_create_buffer_for_peer);
+        client.add_entity_message_handler(Self::handle_share_terminal);
+        client.add_entity_message_handler(Self::handle_update_shared_terminal);
+        client.add_entity_message_handler(Self::handle_unshare_terminal);
+        client.add_entity_request_handler(Self::handle_shared_terminal_input);
 
         WorktreeStore::init(&client);
         BufferStore::init(&client);
@@ -973,6 +986,8 @@ impl Project {
         DapStore::init(&client, cx);
         BreakpointStore::init(&client);
         context_server_store::init(cx);
+        extension_formatters::init(cx);
+        extension_task_sources::init(cx);
     }
 
     pub fn local(
@@ -1089,6 +1104,7 @@ impl Project {
                     cx,
                 )
             });
+            cx.subscribe(&git_store, Self::on_git_store_event).detach();
 
             cx.subscribe(&lsp_store, Self::on_lsp_store_event).detach();
 
@@ -1119,9 +1135,7 @@ impl Project {
 
                 buffers_needing_diff: Default::default(),
                 git_diff_debouncer: DebouncedDelay::new(),
-                terminals: Terminals {
-                    local_handles: Vec::new(),
-                },
+                terminals: Terminals::default(),
                 node: Some(node),
                 search_history: Self::new_search_history(),
                 environment,
@@ -1283,9 +1297,7 @@ impl Project {
                 ssh_client: Some(ssh.clone()),
                 buffers_needing_diff: Default::default(),
                 git_diff_debouncer: DebouncedDelay::new(),
-                terminals: Terminals {
-                    local_handles: Vec::new(),
-                },
+                terminals: Terminals::default(),
                 node: Some(node),
                 search_history: Self::new_search_history(),
                 environment,
@@ -1535,9 +1547,7 @@ impl Project {
                 git_store: git_store.clone(),
                 buffers_needing_diff: Default::default(),
                 git_diff_debouncer: DebouncedDelay::new(),
-                terminals: Terminals {
-                    local_handles: Vec::new(),
-                },
+                terminals: Terminals::default(),
                 node: None,
                 search_history: Self::new_search_history(),
                 search_included_history: Self::new_search_history(),
@@ -2245,6 +2255,8 @@ impl Project {
                 git_store.shared(remote_id, self.client.clone().into(), cx)
             });
         }
+        self.dap_store.read(cx).resend_shared_sessions(cx);
+        self.resend_shared_terminals();
         cx.emit(Event::Reshared);
         Ok(())
     }
@@ -2315,6 +2327,7 @@ impl Project {
             self.git_store.update(cx, |git_store, cx| {
                 git_store.unshared(cx);
             });
+            self.forget_shared_terminals();
 
             self.client
                 .send(proto::UnshareProject {
@@ -2370,6 +2383,7 @@ impl Project {
             });
             self.lsp_store
                 .update(cx, |lsp_store, _cx| lsp_store.disconnected_from_host());
+            self.forget_remote_terminals();
         }
     }
 
@@ -2839,6 +2853,38 @@ impl Project {
         }
     }
 
+    fn on_git_store_event(
+        &mut self,
+        git_store: Entity<GitStore>,
+        event: &GitStoreEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            GitStoreEvent::ActiveRepositoryChanged(_)
+            | GitStoreEvent::RepositoryUpdated(_, RepositoryEvent::Updated { .. }, true) => {
+                if !DebuggerSettings::get_global(cx).breakpoints_per_branch {
+                    return;
+                }
+                let Some(branch) = git_store
+                    .read(cx)
+                    .active_repository()
+                    .and_then(|repository| {
+                        let branch = repository.read(cx).branch.as_ref()?;
+                        Some(Arc::<str>::from(branch.name()))
+                    })
+                else {
+                    return;
+                };
+                self.breakpoint_store
+                    .update(cx, |breakpoint_store, cx| {
+                        breakpoint_store.set_active_branch(branch, cx)
+                    })
+                    .detach_and_log_err(cx);
+            }
+            _ => {}
+        }
+    }
+
     fn on_lsp_store_event(
         &mut self,
         _: Entity<LspStore>,
@@ -4430,6 +4476,8 @@ impl Project {
                 buffer_store.forget_shared_buffers_for(&collaborator.peer_id);
             });
             this.breakpoint_store.read(cx).broadcast();
+            this.dap_store.read(cx).resend_shared_sessions(cx);
+            this.resend_shared_terminals();
             cx.emit(Event::CollaboratorJoined(collaborator.peer_id));
             this.collaborators
                 .insert(collaborator.peer_id, collaborator);
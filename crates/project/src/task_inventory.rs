@@ -3,7 +3,7 @@
 use std::{
     borrow::Cow,
     cmp::{self, Reverse},
-    collections::hash_map,
+    collections::{BTreeMap, hash_map},
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -38,6 +38,7 @@ pub struct Inventory {
     last_scheduled_scenarios: VecDeque<DebugScenario>,
     templates_from_settings: InventoryFor<TaskTemplate>,
     scenarios_from_settings: InventoryFor<DebugScenario>,
+    templates_from_extensions: BTreeMap<TaskSourceKind, Vec<TaskTemplate>>,
 }
 
 impl std::fmt::Debug for Inventory {
@@ -47,6 +48,7 @@ impl std::fmt::Debug for Inventory {
             .field("last_scheduled_scenarios", &self.last_scheduled_scenarios)
             .field("templates_from_settings", &self.templates_from_settings)
             .field("scenarios_from_settings", &self.scenarios_from_settings)
+            .field("templates_from_extensions", &self.templates_from_extensions)
             .finish()
     }
 }
@@ -148,6 +150,12 @@ pub enum TaskSourceKind {
         language_name: SharedString,
         server: LanguageServerId,
     },
+    /// Tasks that an extension's task source provides for a worktree.
+    Extension {
+        worktree: WorktreeId,
+        extension_id: SharedString,
+        task_source: SharedString,
+    },
 }
 
 /// A collection of task contexts, derived from the current state of the workspace.
@@ -230,6 +238,11 @@ impl TaskSourceKind {
                 server,
                 language_name,
             } => format!("lsp_{language_name}_{server}"),
+            Self::Extension {
+                worktree,
+                extension_id,
+                task_source,
+            } => format!("extension_{extension_id}_{task_source}_{worktree}"),
         }
     }
 }
@@ -242,6 +255,7 @@ impl Inventory {
             last_scheduled_scenarios: VecDeque::default(),
             templates_from_settings: InventoryFor::default(),
             scenarios_from_settings: InventoryFor::default(),
+            templates_from_extensions: BTreeMap::default(),
         })
     }
 
@@ -367,7 +381,10 @@ impl Inventory {
         let fs = self.fs.clone();
         let mut worktree_tasks = worktree
             .into_iter()
-            .flat_map(|worktree| self.worktree_templates_from_settings(worktree))
+            .flat_map(|worktree| {
+                self.worktree_templates_from_settings(worktree)
+                    .chain(self.worktree_templates_from_extensions(worktree))
+            })
             .collect::<Vec<_>>();
         let task_source_kind = language.as_ref().map(|language| TaskSourceKind::Language {
             name: language.name().into(),
@@ -470,7 +487,10 @@ impl Inventory {
             });
         let worktree_tasks = worktree
             .into_iter()
-            .flat_map(|worktree| self.worktree_templates_from_settings(worktree))
+            .flat_map(|worktree| {
+                self.worktree_templates_from_settings(worktree)
+                    .chain(self.worktree_templates_from_extensions(worktree))
+            })
             .collect::<Vec<_>>();
         let task_contexts = task_contexts.clone();
         cx.background_spawn(async move {
@@ -493,7 +513,9 @@ impl Inventory {
             let new_resolved_tasks = worktree_tasks
                 .flat_map(|(kind, task)| {
                     let id_base = kind.to_id_base();
-                    if let TaskSourceKind::Worktree { id, .. } = &kind {
+                    if let TaskSourceKind::Worktree { id, .. }
+                    | TaskSourceKind::Extension { worktree: id, .. } = &kind
+                    {
                         None.or_else(|| {
                             let (_, _, item_context) =
                                 task_contexts.active_item_context.as_ref().filter(
@@ -509,16 +531,12 @@ impl Inventory {
                             task.resolve_task(&id_base, worktree_context)
                         })
                         .or_else(|| {
-                            if let TaskSourceKind::Worktree { id, .. } = &kind {
-                                let worktree_context = task_contexts
-                                    .other_worktree_contexts
-                                    .iter()
-                                    .find(|(worktree_id, _)| worktree_id == id)
-                                    .map(|(_, context)| context)?;
-                                task.resolve_task(&id_base, worktree_context)
-                            } else {
-                                None
-                            }
+                            let worktree_context = task_contexts
+                                .other_worktree_contexts
+                                .iter()
+                                .find(|(worktree_id, _)| worktree_id == id)
+                                .map(|(_, context)| context)?;
+                            task.resolve_task(&id_base, worktree_context)
                         })
                     } else {
                         None.or_else(|| {
@@ -616,6 +634,53 @@ impl Inventory {
         self.templates_from_settings.worktree_scenarios(worktree)
     }
 
+    fn worktree_templates_from_extensions(
+        &self,
+        worktree: WorktreeId,
+    ) -> impl '_ + Iterator<Item = (TaskSourceKind, TaskTemplate)> {
+        self.templates_from_extensions
+            .iter()
+            .filter(move |(kind, _)| {
+                matches!(kind, TaskSourceKind::Extension { worktree: id, .. } if *id == worktree)
+            })
+            .flat_map(|(kind, templates)| {
+                templates
+                    .iter()
+                    .map(|template| (kind.clone(), template.clone()))
+            })
+    }
+
+    /// Replaces the task templates provided by an extension's task source for a worktree.
+    ///
+    /// Previously scheduled tasks of the source are forgotten, unless the source still provides a template with the same label.
+    pub(crate) fn update_extension_tasks(
+        &mut self,
+        task_source_kind: TaskSourceKind,
+        templates: Vec<TaskTemplate>,
+    ) {
+        debug_assert!(matches!(task_source_kind, TaskSourceKind::Extension { .. }));
+        let labels = templates
+            .iter()
+            .map(|template| template.label.as_str())
+            .collect::<HashSet<_>>();
+        self.last_scheduled_tasks.retain(|(kind, task)| {
+            kind != &task_source_kind || labels.contains(task.original_task().label.as_str())
+        });
+        if templates.is_empty() {
+            self.templates_from_extensions.remove(&task_source_kind);
+        } else {
+            self.templates_from_extensions
+                .insert(task_source_kind, templates);
+        }
+    }
+
+    /// Removes the task templates, and previously scheduled tasks, of the extension task sources that do not match the predicate.
+    pub(crate) fn retain_extension_tasks(&mut self, mut f: impl FnMut(&TaskSourceKind) -> bool) {
+        self.templates_from_extensions.retain(|kind, _| f(kind));
+        self.last_scheduled_tasks
+            .retain(|(kind, _)| !matches!(kind, TaskSourceKind::Extension { .. }) || f(kind));
+    }
+
     /// Updates in-memory task metadata from the JSON string given.
     /// Will fail if the JSON is not a valid array of objects, but will continue if any object will not parse into a [`TaskTemplate`].
     ///
@@ -810,7 +875,8 @@ fn task_source_kind_preference(kind: &TaskSourceKind) -> u32 {
         TaskSourceKind::Language { .. } => 1,
         TaskSourceKind::UserInput => 2,
         TaskSourceKind::Worktree { .. } => 3,
-        TaskSourceKind::AbsPath { .. } => 4,
+        TaskSourceKind::Extension { .. } => 4,
+        TaskSourceKind::AbsPath { .. } => 5,
     }
 }
 
@@ -1531,6 +1597,67 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_extension_tasks(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let inventory = cx.update(|cx| Inventory::new(fs, cx));
+        let worktree_1 = WorktreeId::from_usize(1);
+        let worktree_2 = WorktreeId::from_usize(2);
+        let extension_source = |worktree| TaskSourceKind::Extension {
+            worktree,
+            extension_id: "bazel".into(),
+            task_source: "bazel-targets".into(),
+        };
+        let template = |label: &str| TaskTemplate {
+            label: label.to_string(),
+            command: "bazel".to_string(),
+            args: vec!["run".to_string(), label.to_string()],
+            ..TaskTemplate::default()
+        };
+
+        inventory.update(cx, |inventory, _| {
+            inventory.update_extension_tasks(
+                extension_source(worktree_1),
+                vec![template("//app:server"), template("//app:cli")],
+            );
+        });
+        assert_eq!(
+            list_tasks(&inventory, Some(worktree_1), cx).await,
+            vec![
+                (extension_source(worktree_1), "//app:server".to_string()),
+                (extension_source(worktree_1), "//app:cli".to_string()),
+            ],
+        );
+        assert_eq!(
+            list_tasks(&inventory, Some(worktree_2), cx).await,
+            Vec::new(),
+            "Extension tasks should only be listed for the worktree they were provided for"
+        );
+
+        register_worktree_task_used(&inventory, worktree_1, "//app:server", cx).await;
+        register_worktree_task_used(&inventory, worktree_1, "//app:cli", cx).await;
+        inventory.update(cx, |inventory, _| {
+            inventory.update_extension_tasks(
+                extension_source(worktree_1),
+                vec![template("//app:server")],
+            );
+        });
+        assert_eq!(
+            resolved_task_names(&inventory, Some(worktree_1), cx).await,
+            vec!["//app:server"],
+            "Previously used tasks that the source no longer provides should be forgotten"
+        );
+
+        inventory.update(cx, |inventory, _| {
+            inventory.retain_extension_tasks(|kind| kind != &extension_source(worktree_1));
+        });
+        assert_eq!(
+            resolved_task_names(&inventory, Some(worktree_1), cx).await,
+            Vec::<String>::new(),
+        );
+    }
+
     fn init_test(_cx: &mut TestAppContext) {
         zlog::init_test();
         TaskStore::init(None);
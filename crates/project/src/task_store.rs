@@ -6,7 +6,7 @@ use std::{
 use anyhow::Context as _;
 use collections::HashMap;
 use fs::Fs;
-use gpui::{App, AsyncApp, Context, Entity, EventEmitter, Task, WeakEntity};
+use gpui::{App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, Task, WeakEntity};
 use language::{
     ContextLocation, ContextProvider as _, LanguageToolchainStore, Location,
     proto::{deserialize_anchor, serialize_anchor},
@@ -18,7 +18,9 @@ use text::{BufferId, OffsetRangeExt};
 use util::ResultExt;
 
 use crate::{
-    BasicContextProvider, Inventory, ProjectEnvironment, buffer_store::BufferStore,
+    BasicContextProvider, Inventory, ProjectEnvironment,
+    buffer_store::BufferStore,
+    extension_task_sources::{ExtensionTaskSources, ExtensionWorktreeTasks},
     worktree_store::WorktreeStore,
 };
 
@@ -40,6 +42,7 @@ enum StoreMode {
     Local {
         downstream_client: Option<(AnyProtoClient, u64)>,
         environment: Entity<ProjectEnvironment>,
+        _extension_tasks: Entity<ExtensionWorktreeTasks>,
     },
     Remote {
         upstream_client: AnyProtoClient,
@@ -166,12 +169,23 @@ impl TaskStore {
         environment: Entity<ProjectEnvironment>,
         cx: &mut Context<Self>,
     ) -> Self {
+        let task_inventory = Inventory::new(fs.clone(), cx);
+        let extension_tasks = cx.new(|cx| {
+            ExtensionWorktreeTasks::new(
+                task_inventory.clone(),
+                worktree_store.clone(),
+                environment.clone(),
+                fs,
+                cx,
+            )
+        });
         Self::Functional(StoreState {
             mode: StoreMode::Local {
                 downstream_client: None,
                 environment,
+                _extension_tasks: extension_tasks,
             },
-            task_inventory: Inventory::new(fs, cx),
+            task_inventory,
             buffer_store,
             toolchain_store,
             worktree_store,
@@ -310,10 +324,14 @@ fn local_task_context_for_location(
     location: Location,
     cx: &App,
 ) -> Task<Option<TaskContext>> {
-    let worktree_id = location.buffer.read(cx).file().map(|f| f.worktree_id(cx));
-    let worktree_abs_path = worktree_id
-        .and_then(|worktree_id| worktree_store.read(cx).worktree_for_id(worktree_id, cx))
+    let file = location.buffer.read(cx).file();
+    let worktree = file
+        .map(|f| f.worktree_id(cx))
+        .and_then(|worktree_id| worktree_store.read(cx).worktree_for_id(worktree_id, cx));
+    let worktree_abs_path = worktree
+        .as_ref()
         .and_then(|worktree| worktree.read(cx).root_dir());
+    let path = file.map(|f| f.path().clone());
     let fs = worktree_store.read(cx).fs();
 
     cx.spawn(async move |cx| {
@@ -328,7 +346,7 @@ fn local_task_context_for_location(
             .update(|cx| {
                 combine_task_variables(
                     captured_variables,
-                    fs,
+                    fs.clone(),
                     worktree_store.clone(),
                     location,
                     project_env.clone(),
@@ -340,6 +358,26 @@ fn local_task_context_for_location(
             .ok()?
             .await
             .log_err()?;
+        if let Some((worktree, fs)) = worktree.zip(fs) {
+            let extension_variables = cx
+                .update(|cx| {
+                    ExtensionTaskSources::task_variables(
+                        &worktree,
+                        path,
+                        fs,
+                        project_env.clone().unwrap_or_default(),
+                        cx,
+                    )
+                })
+                .ok()?
+                .await;
+            // Variables captured from the buffer take precedence over the ones provided by extensions.
+            for (name, value) in extension_variables {
+                if task_variables.get(&name).is_none() {
+                    task_variables.insert(name, value);
+                }
+            }
+        }
         // Remove all custom entries starting with _, as they're not intended for use by the end user.
         task_variables.sweep();
 
@@ -1,9 +1,14 @@
-use crate::{Project, ProjectPath};
+use crate::{Event, Project, ProjectClientState, ProjectPath};
 use anyhow::{Context as _, Result};
 use collections::HashMap;
-use gpui::{AnyWindowHandle, App, AppContext as _, Context, Entity, Task, WeakEntity};
+use futures::{StreamExt as _, channel::mpsc};
+use gpui::{
+    AnyWindowHandle, App, AppContext as _, AsyncApp, Context, Entity, Subscription, Task,
+    WeakEntity,
+};
 use itertools::Itertools;
 use language::LanguageName;
+use rpc::{TypedEnvelope, proto};
 use settings::{Settings, SettingsLocation};
 use smol::channel::bounded;
 use std::{
@@ -11,16 +16,45 @@ use std::{
     env::{self},
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 use task::{DEFAULT_REMOTE_SHELL, Shell, ShellBuilder, SpawnInTerminal};
 use terminal::{
-    TaskState, TaskStatus, Terminal, TerminalBuilder,
+    ScreenSnapshot, TaskState, TaskStatus, Terminal, TerminalBuilder,
     terminal_settings::{self, TerminalSettings, VenvSettings},
 };
 use util::ResultExt;
 
+/// How long a shared terminal batches output before sending its screen to guests.
+pub const SHARED_TERMINAL_UPDATE_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Default)]
 pub struct Terminals {
     pub(crate) local_handles: Vec<WeakEntity<terminal::Terminal>>,
+    /// Terminals that we share with the guests of this project.
+    shared: HashMap<u64, SharedTerminal>,
+    /// Terminals that the host of this project shares with us.
+    remote: HashMap<u64, RemoteTerminal>,
+    next_shared_terminal_id: u64,
+}
+
+struct SharedTerminal {
+    terminal: WeakEntity<Terminal>,
+    read_write: bool,
+    title: String,
+    /// The screen as guests last saw it, which updates are diffed against.
+    screen: ScreenSnapshot,
+    pending_update: Option<Task<()>>,
+    _subscriptions: [Subscription; 2],
+}
+
+/// A terminal that the host of a remote project shares with its guests.
+pub struct RemoteTerminal {
+    /// A display-only terminal that mirrors the host's screen.
+    pub terminal: Entity<Terminal>,
+    /// Whether the host accepts input from guests that are allowed to edit the project.
+    pub read_write: bool,
+    _forward_input: Task<Result<()>>,
 }
 
 /// Terminals are opened either for the users shell, or to run a task.
@@ -520,6 +554,329 @@ impl Project {
     pub fn local_terminal_handles(&self) -> &Vec<WeakEntity<terminal::Terminal>> {
         &self.terminals.local_handles
     }
+
+    /// Shares a local terminal with the guests of this project, returning the id it is shared under.
+    ///
+    /// Guests see the terminal's screen. When `read_write` is true, guests that are allowed to
+    /// edit the project can also type into it. Sharing a terminal that is already shared changes
+    /// whether guests can type into it.
+    pub fn share_terminal(
+        &mut self,
+        terminal: Entity<Terminal>,
+        read_write: bool,
+        cx: &mut Context<Self>,
+    ) -> Result<u64> {
+        let ProjectClientState::Shared { remote_id } = self.client_state else {
+            anyhow::bail!("only the host of a shared project can share terminals");
+        };
+        if let Some(terminal_id) = self.shared_terminal_id(&terminal) {
+            let shared = self
+                .terminals
+                .shared
+                .get_mut(&terminal_id)
+                .context("terminal is not shared")?;
+            if shared.read_write != read_write {
+                shared.read_write = read_write;
+                self.client.send(proto::ShareTerminal {
+                    project_id: remote_id,
+                    terminal_id,
+                    title: shared.title.clone(),
+                    read_write,
+                    screen: shared.screen.to_bytes(),
+                })?;
+                cx.emit(Event::SharedTerminalsChanged);
+            }
+            return Ok(terminal_id);
+        }
+
+        let terminal_id = self.terminals.next_shared_terminal_id;
+        self.terminals.next_shared_terminal_id += 1;
+
+        let (title, screen) = terminal.read_with(cx, |terminal, _| {
+            (terminal.title(false), terminal.screen_snapshot())
+        });
+        self.client.send(proto::ShareTerminal {
+            project_id: remote_id,
+            terminal_id,
+            title: title.clone(),
+            read_write,
+            screen: screen.to_bytes(),
+        })?;
+
+        let subscriptions = [
+            cx.subscribe(&terminal, move |this, _, event, cx| match event {
+                terminal::Event::Wakeup
+                | terminal::Event::TitleChanged
+                | terminal::Event::BreadcrumbsChanged => {
+                    this.schedule_shared_terminal_update(terminal_id, cx)
+                }
+                terminal::Event::CloseTerminal => {
+                    this.unshare_terminal(terminal_id, cx).log_err();
+                }
+                _ => {}
+            }),
+            cx.observe_release(&terminal, move |this, _, cx| {
+                this.unshare_terminal(terminal_id, cx).log_err();
+            }),
+        ];
+        self.terminals.shared.insert(
+            terminal_id,
+            SharedTerminal {
+                terminal: terminal.downgrade(),
+                read_write,
+                title,
+                screen,
+                pending_update: None,
+                _subscriptions: subscriptions,
+            },
+        );
+        cx.emit(Event::SharedTerminalsChanged);
+        Ok(terminal_id)
+    }
+
+    /// Stops sharing a terminal previously shared with [`Project::share_terminal`].
+    pub fn unshare_terminal(&mut self, terminal_id: u64, cx: &mut Context<Self>) -> Result<()> {
+        self.terminals
+            .shared
+            .remove(&terminal_id)
+            .context("terminal is not shared")?;
+        if let ProjectClientState::Shared { remote_id } = self.client_state {
+            self.client.send(proto::UnshareTerminal {
+                project_id: remote_id,
+                terminal_id,
+            })?;
+        }
+        cx.emit(Event::SharedTerminalsChanged);
+        Ok(())
+    }
+
+    /// Returns the id under which a local terminal is shared with guests, if it is shared.
+    pub fn shared_terminal_id(&self, terminal: &Entity<Terminal>) -> Option<u64> {
+        self.terminals
+            .shared
+            .iter()
+            .find(|(_, shared)| &shared.terminal == terminal)
+            .map(|(terminal_id, _)| *terminal_id)
+    }
+
+    /// Terminals that the host of this remote project shares with us, by id.
+    pub fn remote_terminals(&self) -> &HashMap<u64, RemoteTerminal> {
+        &self.terminals.remote
+    }
+
+    /// Sends every shared terminal to guests again, e.g. when a guest joins the project.
+    pub(crate) fn resend_shared_terminals(&mut self) {
+        let ProjectClientState::Shared { remote_id } = self.client_state else {
+            return;
+        };
+        for (terminal_id, shared) in &self.terminals.shared {
+            self.client
+                .send(proto::ShareTerminal {
+                    project_id: remote_id,
+                    terminal_id: *terminal_id,
+                    title: shared.title.clone(),
+                    read_write: shared.read_write,
+                    screen: shared.screen.to_bytes(),
+                })
+                .log_err();
+        }
+    }
+
+    pub(crate) fn forget_shared_terminals(&mut self) {
+        self.terminals.shared.clear();
+    }
+
+    pub(crate) fn forget_remote_terminals(&mut self) {
+        self.terminals.remote.clear();
+    }
+
+    fn schedule_shared_terminal_update(&mut self, terminal_id: u64, cx: &mut Context<Self>) {
+        let Some(shared) = self.terminals.shared.get_mut(&terminal_id) else {
+            return;
+        };
+        if shared.pending_update.is_some() {
+            return;
+        }
+        shared.pending_update = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(SHARED_TERMINAL_UPDATE_INTERVAL)
+                .await;
+            this.update(cx, |this, cx| {
+                this.send_shared_terminal_update(terminal_id, cx)
+            })
+            .ok();
+        }));
+    }
+
+    fn send_shared_terminal_update(&mut self, terminal_id: u64, cx: &mut Context<Self>) {
+        let ProjectClientState::Shared { remote_id } = self.client_state else {
+            return;
+        };
+        let Some(shared) = self.terminals.shared.get_mut(&terminal_id) else {
+            return;
+        };
+        shared.pending_update = None;
+        let Some(terminal) = shared.terminal.upgrade() else {
+            return;
+        };
+
+        let terminal = terminal.read(cx);
+        let title = terminal.title(false);
+        let screen = terminal.screen_snapshot();
+        let screen_diff = screen.diff(&shared.screen);
+        if title == shared.title && screen_diff.is_none() {
+            return;
+        }
+        shared.title = title.clone();
+        shared.screen = screen;
+        self.client
+            .send(proto::UpdateSharedTerminal {
+                project_id: remote_id,
+                terminal_id,
+                title,
+                screen_diff: screen_diff.unwrap_or_default(),
+            })
+            .log_err();
+    }
+
+    fn open_remote_terminal(
+        &mut self,
+        terminal_id: u64,
+        title: String,
+        read_write: bool,
+        cx: &mut Context<Self>,
+    ) -> Entity<Terminal> {
+        let (input_tx, mut input_rx) = mpsc::unbounded();
+        let settings = TerminalSettings::get_global(cx);
+        let builder = TerminalBuilder::new_display_only(
+            Some(title.into()),
+            settings.cursor_shape.unwrap_or_default(),
+            settings.alternate_scroll,
+            settings.max_scroll_history_lines,
+            input_tx,
+        );
+        let terminal = cx.new(|cx| builder.subscribe(cx));
+
+        let forward_input = cx.spawn(async move |this, cx| {
+            while let Some(input) = input_rx.next().await {
+                let request = this.update(cx, |this, cx| {
+                    let remote_id = this.remote_id()?;
+                    let remote = this.terminals.remote.get(&terminal_id)?;
+                    if !remote.read_write || this.is_read_only(cx) {
+                        return None;
+                    }
+                    Some(this.client.request(proto::SharedTerminalInput {
+                        project_id: remote_id,
+                        terminal_id,
+                        input,
+                    }))
+                })?;
+                if let Some(request) = request {
+                    request.await.log_err();
+                }
+            }
+            anyhow::Ok(())
+        });
+
+        self.terminals.remote.insert(
+            terminal_id,
+            RemoteTerminal {
+                terminal: terminal.clone(),
+                read_write,
+                _forward_input: forward_input,
+            },
+        );
+        terminal
+    }
+
+    pub(crate) async fn handle_share_terminal(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::ShareTerminal>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let message = envelope.payload;
+        this.update(&mut cx, |this, cx| {
+            let (terminal, opened) = match this.terminals.remote.get_mut(&message.terminal_id) {
+                Some(remote) => {
+                    remote.read_write = message.read_write;
+                    (remote.terminal.clone(), false)
+                }
+                None => {
+                    let terminal = this.open_remote_terminal(
+                        message.terminal_id,
+                        message.title.clone(),
+                        message.read_write,
+                        cx,
+                    );
+                    (terminal, true)
+                }
+            };
+            terminal.update(cx, |terminal, cx| {
+                terminal.set_title_override(Some(message.title.into()), cx);
+                terminal.write_output(&message.screen, cx);
+            });
+            if opened {
+                cx.emit(Event::RemoteTerminalOpened(message.terminal_id));
+            }
+            cx.emit(Event::SharedTerminalsChanged);
+        })
+    }
+
+    pub(crate) async fn handle_update_shared_terminal(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::UpdateSharedTerminal>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let message = envelope.payload;
+        this.update(&mut cx, |this, cx| {
+            let remote = this
+                .terminals
+                .remote
+                .get(&message.terminal_id)
+                .context("received an update for an unknown terminal")?;
+            remote.terminal.update(cx, |terminal, cx| {
+                terminal.set_title_override(Some(message.title.into()), cx);
+                terminal.write_output(&message.screen_diff, cx);
+            });
+            Ok(())
+        })?
+    }
+
+    pub(crate) async fn handle_unshare_terminal(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::UnshareTerminal>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, cx| {
+            if this
+                .terminals
+                .remote
+                .remove(&envelope.payload.terminal_id)
+                .is_some()
+            {
+                cx.emit(Event::SharedTerminalsChanged);
+            }
+        })
+    }
+
+    pub(crate) async fn handle_shared_terminal_input(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::SharedTerminalInput>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let message = envelope.payload;
+        this.update(&mut cx, |this, cx| {
+            let shared = this
+                .terminals
+                .shared
+                .get(&message.terminal_id)
+                .context("terminal is not shared")?;
+            anyhow::ensure!(shared.read_write, "terminal is shared read-only");
+            let terminal = shared.terminal.upgrade().context("terminal was closed")?;
+            terminal.update(cx, |terminal, _| terminal.input(message.input));
+            Ok(proto::Ack {})
+        })?
+    }
 }
 
 pub fn wrap_for_ssh(
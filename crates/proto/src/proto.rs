@@ -56,6 +56,7 @@ messages!(
     (CreateContext, Foreground),
     (CreateContextResponse, Foreground),
     (CreateProjectEntry, Foreground),
+    (CreateReviewThread, Foreground),
     (CreateRoom, Foreground),
     (CreateRoomResponse, Foreground),
     (DeclineCall, Foreground),
@@ -78,6 +79,10 @@ messages!(
     (FormatBuffers, Foreground),
     (FormatBuffersResponse, Foreground),
     (FuzzySearchUsers, Foreground),
+    (GetChannelBufferRevisionText, Foreground),
+    (GetChannelBufferRevisionTextResponse, Foreground),
+    (GetChannelBufferRevisions, Foreground),
+    (GetChannelBufferRevisionsResponse, Foreground),
     (GetChannelMembers, Foreground),
     (GetChannelMembersResponse, Foreground),
     (GetChannelMessages, Background),
@@ -111,6 +116,8 @@ messages!(
     (GetProjectSymbolsResponse, Background),
     (GetReferences, Background),
     (GetReferencesResponse, Background),
+    (GetReviewThreads, Foreground),
+    (GetReviewThreadsResponse, Foreground),
     (GetSignatureHelp, Background),
     (GetSignatureHelpResponse, Background),
     (GetSupermavenApiKey, Background),
@@ -176,6 +183,7 @@ messages!(
     (LspExtClearFlycheck, Background),
     (MarkNotificationRead, Foreground),
     (MoveChannel, Foreground),
+    (MoveReviewThread, Foreground),
     (ReorderChannel, Foreground),
     (MultiLspQuery, Background),
     (MultiLspQueryResponse, Background),
@@ -217,6 +225,8 @@ messages!(
     (RenameChannelResponse, Foreground),
     (RenameProjectEntry, Foreground),
     (RequestContact, Foreground),
+    (ReplyToReviewThread, Foreground),
+    (ResolveReviewThread, Foreground),
     (ResolveCompletionDocumentation, Background),
     (ResolveCompletionDocumentationResponse, Background),
     (ResolveInlayHint, Background),
@@ -231,9 +241,11 @@ messages!(
     (RespondToChannelInvite, Foreground),
     (RespondToContactRequest, Foreground),
     (RestartLanguageServers, Foreground),
+    (ReviewThreadResponse, Foreground),
     (StopLanguageServers, Background),
     (RoomUpdated, Foreground),
     (SaveBuffer, Foreground),
+    (SearchChannelMessages, Background),
     (SendChannelMessage, Background),
     (SendChannelMessageResponse, Background),
     (SetChannelMemberRole, Foreground),
@@ -241,6 +253,8 @@ messages!(
     (SetRoomParticipantRole, Foreground),
     (ShareProject, Foreground),
     (ShareProjectResponse, Foreground),
+    (ShareTerminal, Foreground),
+    (SharedTerminalInput, Foreground),
     (ShowContacts, Foreground),
     (ShutdownRemoteServer, Foreground),
     (Stage, Background),
@@ -260,6 +274,7 @@ messages!(
     (Toast, Background),
     (Unfollow, Foreground),
     (UnshareProject, Foreground),
+    (UnshareTerminal, Foreground),
     (Unstage, Background),
     (UpdateBuffer, Foreground),
     (UpdateBufferFile, Foreground),
@@ -279,12 +294,14 @@ messages!(
     (UpdateParticipantLocation, Foreground),
     (UpdateProject, Foreground),
     (UpdateProjectCollaborator, Foreground),
+    (UpdateReviewThread, Foreground),
     (UpdateUserChannels, Foreground),
     (UpdateUserPlan, Foreground),
     (UpdateWorktree, Foreground),
     (UpdateWorktreeSettings, Foreground),
     (UpdateRepository, Foreground),
     (RemoveRepository, Foreground),
+    (UpdateSharedTerminal, Foreground),
     (UsersResponse, Foreground),
     (GitReset, Background),
     (GitCheckoutFiles, Background),
@@ -311,6 +328,11 @@ messages!(
     (RunDebugLocators, Background),
     (DebugRequest, Background),
     (LogToDebugConsole, Background),
+    (ShareDebugSession, Background),
+    (UnshareDebugSession, Background),
+    (DebugSessionRequest, Background),
+    (DebugSessionResponse, Background),
+    (DebugSessionEvent, Background),
     (GetDocumentDiagnostics, Background),
     (GetDocumentDiagnosticsResponse, Background),
     (PullWorkspaceDiagnostics, Background)
@@ -330,6 +352,7 @@ request_messages!(
     (CreateChannel, CreateChannelResponse),
     (CreateProjectEntry, ProjectEntryResponse),
     (CreateRoom, CreateRoomResponse),
+    (CreateReviewThread, ReviewThreadResponse),
     (DeclineCall, Ack),
     (DeleteChannel, Ack),
     (DeleteProjectEntry, ProjectEntryResponse),
@@ -339,6 +362,8 @@ request_messages!(
     (ApplyCodeActionKind, ApplyCodeActionKindResponse),
     (FormatBuffers, FormatBuffersResponse),
     (FuzzySearchUsers, UsersResponse),
+    (GetChannelBufferRevisionText, GetChannelBufferRevisionTextResponse),
+    (GetChannelBufferRevisions, GetChannelBufferRevisionsResponse),
     (GetChannelMembers, GetChannelMembersResponse),
     (GetChannelMessages, GetChannelMessagesResponse),
     (GetChannelMessagesById, GetChannelMessagesResponse),
@@ -355,6 +380,7 @@ request_messages!(
     (GetPrivateUserInfo, GetPrivateUserInfoResponse),
     (GetProjectSymbols, GetProjectSymbolsResponse),
     (GetReferences, GetReferencesResponse),
+    (GetReviewThreads, GetReviewThreadsResponse),
     (GetSignatureHelp, GetSignatureHelpResponse),
     (OpenUnstagedDiff, OpenUnstagedDiffResponse),
     (OpenUncommittedDiff, OpenUncommittedDiffResponse),
@@ -377,6 +403,7 @@ request_messages!(
     (LoadCommitDiff, LoadCommitDiffResponse),
     (MarkNotificationRead, Ack),
     (MoveChannel, Ack),
+    (MoveReviewThread, ReviewThreadResponse),
     (OnTypeFormatting, OnTypeFormattingResponse),
     (OpenBufferById, OpenBufferResponse),
     (OpenBufferByPath, OpenBufferResponse),
@@ -398,6 +425,7 @@ request_messages!(
     (RenameChannel, RenameChannelResponse),
     (RenameProjectEntry, ProjectEntryResponse),
     (ReorderChannel, Ack),
+    (ReplyToReviewThread, ReviewThreadResponse),
     (RequestContact, Ack),
     (
         ResolveCompletionDocumentation,
@@ -408,13 +436,17 @@ request_messages!(
     (GetColorPresentation, GetColorPresentationResponse),
     (RespondToChannelInvite, Ack),
     (RespondToContactRequest, Ack),
+    (ResolveReviewThread, ReviewThreadResponse),
     (SaveBuffer, BufferSaved),
     (Stage, Ack),
     (FindSearchCandidates, FindSearchCandidatesResponse),
+    (SearchChannelMessages, GetChannelMessagesResponse),
     (SendChannelMessage, SendChannelMessageResponse),
     (SetChannelMemberRole, Ack),
     (SetChannelVisibility, Ack),
     (ShareProject, ShareProjectResponse),
+    (SharedTerminalInput, Ack),
+    (DebugSessionRequest, DebugSessionResponse),
     (SynchronizeBuffers, SynchronizeBuffersResponse),
     (TaskContextForLocation, TaskContext),
     (Test, Test),
@@ -609,7 +641,15 @@ entity_messages!(
     GetDebugAdapterBinary,
     LogToDebugConsole,
     GetDocumentDiagnostics,
-    PullWorkspaceDiagnostics
+    PullWorkspaceDiagnostics,
+    ShareTerminal,
+    UnshareTerminal,
+    UpdateSharedTerminal,
+    SharedTerminalInput,
+    ShareDebugSession,
+    UnshareDebugSession,
+    DebugSessionRequest,
+    DebugSessionEvent
 );
 
 entity_messages!(
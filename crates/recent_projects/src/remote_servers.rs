@@ -1016,6 +1016,7 @@ impl RemoteServerProjects {
                     nickname: None,
                     args: connection_options.args.unwrap_or_default(),
                     upload_binary_over_ssh: None,
+                    binary_cache: connection_options.binary_cache,
                     port_forwards: connection_options.port_forwards,
                 })
         });
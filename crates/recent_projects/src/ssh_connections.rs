@@ -52,6 +52,7 @@ impl SshSettings {
                 return SshConnectionOptions {
                     nickname: conn.nickname,
                     upload_binary_over_ssh: conn.upload_binary_over_ssh.unwrap_or_default(),
+                    binary_cache: conn.binary_cache,
                     args: Some(conn.args),
                     host,
                     port,
@@ -91,6 +92,13 @@ pub struct SshConnection {
     // limited outbound internet access.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub upload_binary_over_ssh: Option<bool>,
+    /// Path to a local directory, or a tarball of one, containing pre-provisioned
+    /// remote server binaries laid out as `<version>/zed-remote-server-<triple>.gz`,
+    /// each with a `.sha256` checksum file next to it. When set, Zed never downloads
+    /// the server and instead uploads the verified binary from this cache. Useful
+    /// for air-gapped hosts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary_cache: Option<PathBuf>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub port_forwards: Option<Vec<SshPortForwardOption>>,
@@ -106,6 +114,7 @@ impl From<SshConnection> for SshConnectionOptions {
             args: Some(val.args),
             nickname: val.nickname,
             upload_binary_over_ssh: val.upload_binary_over_ssh.unwrap_or_default(),
+            binary_cache: val.binary_cache,
             port_forwards: val.port_forwards,
         }
     }
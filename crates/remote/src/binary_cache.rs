@@ -0,0 +1,205 @@
+//! A local cache of pre-provisioned remote server binaries.
+//!
+//! Hosts without internet access (and clients that can't reach zed.dev either)
+//! can't use the usual download paths. Instead, binaries are copied ahead of
+//! time into a directory (or a tarball of that directory) laid out as:
+//!
+//! ```text
+//! <cache>/<version>/zed-remote-server-<triple>.gz
+//! <cache>/<version>/zed-remote-server-<triple>.gz.sha256
+//! ```
+//!
+//! Uncompressed binaries (without the `.gz` suffix) are accepted as well. The
+//! `.sha256` file contains the hex-encoded digest, optionally followed by the
+//! file name as written by `sha256sum`. Running `zed-remote-server version --cache-key`
+//! on a machine prints the entry it expects, which is handy for pre-provisioning.
+
+use crate::SshPlatform;
+use anyhow::{Context as _, Result};
+use async_compression::futures::bufread::GzipDecoder;
+use async_tar::Archive;
+use futures::io::BufReader;
+use sha2::{Digest, Sha256};
+use smol::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const BINARY_PREFIX: &str = "zed-remote-server";
+const CHECKSUM_EXTENSION: &str = "sha256";
+
+/// Returns the path of a cache entry, relative to the cache root, for the given
+/// server version and platform.
+pub fn cache_entry_path(version: &str, platform: SshPlatform) -> Option<PathBuf> {
+    let triple = platform.triple()?;
+    Some(Path::new(version).join(format!("{BINARY_PREFIX}-{triple}.gz")))
+}
+
+/// A binary found in the cache, whose checksum has been verified.
+pub struct CachedBinary {
+    pub path: PathBuf,
+    pub is_compressed: bool,
+    /// Keeps the extracted tarball alive while the binary is being uploaded.
+    _extracted: Option<TempDir>,
+}
+
+pub struct LocalBinaryCache {
+    root: PathBuf,
+}
+
+impl LocalBinaryCache {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Looks up the binary for the given version and platform, and verifies it
+    /// against its checksum file.
+    pub async fn find(&self, version: &str, platform: SshPlatform) -> Result<CachedBinary> {
+        let entry = cache_entry_path(version, platform).with_context(|| {
+            format!(
+                "no binary cache entry for unsupported platform {}-{}",
+                platform.os, platform.arch
+            )
+        })?;
+
+        let metadata = fs::metadata(&self.root)
+            .await
+            .with_context(|| format!("reading binary cache at {:?}", self.root))?;
+        let (root, extracted) = if metadata.is_dir() {
+            (self.root.clone(), None)
+        } else {
+            let temp_dir = extract_tarball(&self.root).await?;
+            (temp_dir.path().to_path_buf(), Some(temp_dir))
+        };
+
+        let compressed_path = root.join(&entry);
+        let uncompressed_path = compressed_path.with_extension("");
+        let (path, is_compressed) = if fs::metadata(&compressed_path).await.is_ok() {
+            (compressed_path, true)
+        } else if fs::metadata(&uncompressed_path).await.is_ok() {
+            (uncompressed_path, false)
+        } else {
+            anyhow::bail!(
+                "remote server binary {:?} not found in binary cache at {:?}",
+                entry,
+                self.root
+            );
+        };
+
+        verify_checksum(&path).await?;
+
+        Ok(CachedBinary {
+            path,
+            is_compressed,
+            _extracted: extracted,
+        })
+    }
+}
+
+async fn extract_tarball(path: &Path) -> Result<TempDir> {
+    let temp_dir = tempfile::tempdir()?;
+    let file = fs::File::open(path)
+        .await
+        .with_context(|| format!("opening binary cache tarball {path:?}"))?;
+    let is_gzipped = path
+        .extension()
+        .is_some_and(|extension| extension == "gz" || extension == "tgz");
+    if is_gzipped {
+        Archive::new(GzipDecoder::new(BufReader::new(file)))
+            .unpack(temp_dir.path())
+            .await
+    } else {
+        Archive::new(file).unpack(temp_dir.path()).await
+    }
+    .with_context(|| format!("extracting binary cache tarball {path:?}"))?;
+    Ok(temp_dir)
+}
+
+async fn verify_checksum(path: &Path) -> Result<()> {
+    let mut checksum_path = path.as_os_str().to_owned();
+    checksum_path.push(".");
+    checksum_path.push(CHECKSUM_EXTENSION);
+    let checksum_path = PathBuf::from(checksum_path);
+
+    let expected = fs::read_to_string(&checksum_path)
+        .await
+        .with_context(|| format!("reading checksum file {checksum_path:?}"))?;
+    let expected = expected
+        .split_whitespace()
+        .next()
+        .with_context(|| format!("checksum file {checksum_path:?} is empty"))?
+        .to_ascii_lowercase();
+
+    let contents = fs::read(path).await?;
+    let actual = hex::encode(Sha256::digest(&contents));
+    anyhow::ensure!(
+        actual == expected,
+        "checksum mismatch for {path:?}: expected {expected}, got {actual}"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINUX: SshPlatform = SshPlatform {
+        os: "linux",
+        arch: "x86_64",
+    };
+
+    fn write_entry(root: &Path, version: &str, contents: &[u8], checksum: &str) -> PathBuf {
+        let path = root.join(cache_entry_path(version, LINUX).unwrap());
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, contents).unwrap();
+        std::fs::write(
+            path.with_extension("gz.sha256"),
+            format!(
+                "{checksum}  {}\n",
+                path.file_name().unwrap().to_string_lossy()
+            ),
+        )
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn test_cache_entry_path() {
+        assert_eq!(
+            cache_entry_path("0.190.0", LINUX),
+            Some(PathBuf::from(
+                "0.190.0/zed-remote-server-x86_64-unknown-linux-gnu.gz"
+            ))
+        );
+        assert_eq!(
+            cache_entry_path(
+                "0.190.0",
+                SshPlatform {
+                    os: "freebsd",
+                    arch: "x86_64"
+                }
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_verifies_checksum() {
+        let root = tempfile::tempdir().unwrap();
+        let contents = b"remote server";
+        let checksum = hex::encode(Sha256::digest(contents));
+        let path = write_entry(root.path(), "0.190.0", contents, &checksum);
+        write_entry(root.path(), "0.191.0", contents, &"0".repeat(64));
+
+        let cache = LocalBinaryCache::new(root.path().to_path_buf());
+        smol::block_on(async {
+            let binary = cache.find("0.190.0", LINUX).await.unwrap();
+            assert_eq!(binary.path, path);
+            assert!(binary.is_compressed);
+
+            let error = cache.find("0.191.0", LINUX).await.err().unwrap();
+            assert!(error.to_string().contains("checksum mismatch"));
+
+            assert!(cache.find("0.192.0", LINUX).await.is_err());
+        });
+    }
+}
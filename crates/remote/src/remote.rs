@@ -1,3 +1,4 @@
+pub mod binary_cache;
 pub mod json_log;
 pub mod protocol;
 pub mod proxy;
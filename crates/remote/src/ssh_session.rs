@@ -1,4 +1,5 @@
 use crate::{
+    binary_cache::LocalBinaryCache,
     json_log::LogRecord,
     protocol::{
         MESSAGE_LEN_SIZE, MessageId, message_len_from_buffer, read_message_with_len, write_message,
@@ -83,6 +84,7 @@ pub struct SshConnectionOptions {
 
     pub nickname: Option<String>,
     pub upload_binary_over_ssh: bool,
+    pub binary_cache: Option<PathBuf>,
 }
 
 #[macro_export]
@@ -232,6 +234,7 @@ impl SshConnectionOptions {
             password: None,
             nickname: None,
             upload_binary_over_ssh: false,
+            binary_cache: None,
         })
     }
 
@@ -304,6 +307,14 @@ pub struct SshPlatform {
 }
 
 impl SshPlatform {
+    /// The platform this binary was compiled for.
+    pub fn current() -> Self {
+        Self {
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+        }
+    }
+
     pub fn triple(&self) -> Option<String> {
         Some(format!(
             "{}-{}",
@@ -317,6 +328,24 @@ impl SshPlatform {
     }
 }
 
+/// The version string used to name remote server binaries, both on the remote
+/// host and in a [`LocalBinaryCache`].
+pub fn remote_server_version_string(
+    release_channel: ReleaseChannel,
+    version: SemanticVersion,
+    commit: Option<AppCommitSha>,
+) -> String {
+    match release_channel {
+        ReleaseChannel::Nightly => {
+            let commit = commit.map(|s| s.full()).unwrap_or_default();
+
+            format!("{}-{}", version, commit)
+        }
+        ReleaseChannel::Dev => "build".to_string(),
+        _ => version.to_string(),
+    }
+}
+
 pub trait SshClientDelegate: Send + Sync {
     fn ask_password(&self, prompt: String, tx: oneshot::Sender<String>, cx: &mut AsyncApp);
     fn get_download_params(
@@ -1700,15 +1729,7 @@ impl SshRemoteConnection {
         commit: Option<AppCommitSha>,
         cx: &mut AsyncApp,
     ) -> Result<PathBuf> {
-        let version_str = match release_channel {
-            ReleaseChannel::Nightly => {
-                let commit = commit.map(|s| s.full()).unwrap_or_default();
-
-                format!("{}-{}", version, commit)
-            }
-            ReleaseChannel::Dev => "build".to_string(),
-            _ => version.to_string(),
-        };
+        let version_str = remote_server_version_string(release_channel, version, commit);
         let binary_name = format!(
             "zed-remote-server-{}-{}",
             release_channel.dev_name(),
@@ -1743,6 +1764,12 @@ impl SshRemoteConnection {
             return Ok(dst_path);
         }
 
+        if let Some(binary_cache) = self.socket.connection_options.binary_cache.clone() {
+            self.install_from_binary_cache(binary_cache, &version_str, &dst_path, delegate, cx)
+                .await?;
+            return Ok(dst_path);
+        }
+
         let wanted_version = cx.update(|cx| match release_channel {
             ReleaseChannel::Nightly => Ok(None),
             ReleaseChannel::Dev => {
@@ -1795,6 +1822,36 @@ impl SshRemoteConnection {
         return Ok(dst_path);
     }
 
+    async fn install_from_binary_cache(
+        &self,
+        binary_cache: PathBuf,
+        version: &str,
+        dst_path: &Path,
+        delegate: &Arc<dyn SshClientDelegate>,
+        cx: &mut AsyncApp,
+    ) -> Result<()> {
+        let platform = self.platform().await?;
+        delegate.set_status(
+            Some("Verifying remote development server from binary cache"),
+            cx,
+        );
+        let binary = LocalBinaryCache::new(binary_cache)
+            .find(version, platform)
+            .await
+            .context("locating remote server in binary cache")?;
+
+        let tmp_path = PathBuf::from(format!(
+            "{}-upload-{}{}",
+            dst_path.to_string_lossy(),
+            std::process::id(),
+            if binary.is_compressed { ".gz" } else { "" }
+        ));
+        self.upload_local_server_binary(&binary.path, &tmp_path, delegate, cx)
+            .await?;
+        self.extract_server_binary(dst_path, &tmp_path, delegate, cx)
+            .await
+    }
+
     async fn download_binary_on_server(
         &self,
         url: &str,
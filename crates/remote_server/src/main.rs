@@ -37,7 +37,12 @@ enum Commands {
         #[arg(long)]
         identifier: String,
     },
-    Version,
+    Version {
+        /// Print the version and target triple this binary is named by in a local
+        /// binary cache, along with its relative path within the cache.
+        #[arg(long)]
+        cache_key: bool,
+    },
 }
 
 #[cfg(windows)]
@@ -47,8 +52,12 @@ fn main() {
 
 #[cfg(not(windows))]
 fn main() {
-    use release_channel::{RELEASE_CHANNEL, ReleaseChannel};
-    use remote::proxy::ProxyLaunchError;
+    use gpui::SemanticVersion;
+    use release_channel::{AppCommitSha, RELEASE_CHANNEL, ReleaseChannel};
+    use remote::{
+        SshPlatform, binary_cache::cache_entry_path, proxy::ProxyLaunchError,
+        ssh_session::remote_server_version_string,
+    };
     use remote_server::unix::{execute_proxy, execute_run};
 
     let cli = Cli::parse();
@@ -89,7 +98,29 @@ fn main() {
                 Err(err)
             }
         },
-        Some(Commands::Version) => {
+        Some(Commands::Version { cache_key: true }) => {
+            let release_channel = *RELEASE_CHANNEL;
+            let version = env!("ZED_PKG_VERSION")
+                .parse::<SemanticVersion>()
+                .expect("invalid ZED_PKG_VERSION");
+            let commit = option_env!("ZED_COMMIT_SHA").map(|sha| AppCommitSha::new(sha.into()));
+            let version = remote_server_version_string(release_channel, version, commit);
+            let platform = SshPlatform::current();
+            let Some(triple) = platform.triple() else {
+                eprintln!(
+                    "no binary cache key for unsupported platform {}-{}",
+                    platform.os, platform.arch
+                );
+                std::process::exit(1);
+            };
+            println!("version: {version}");
+            println!("triple: {triple}");
+            if let Some(path) = cache_entry_path(&version, platform) {
+                println!("path: {}", path.display());
+            }
+            std::process::exit(0);
+        }
+        Some(Commands::Version { cache_key: false }) => {
             let release_channel = *RELEASE_CHANNEL;
             match release_channel {
                 ReleaseChannel::Stable | ReleaseChannel::Preview => {
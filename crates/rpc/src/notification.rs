@@ -38,6 +38,14 @@ pub enum Notification {
         sender_id: u64,
         channel_id: u64,
     },
+    ReviewCommentMention {
+        #[serde(rename = "entity_id")]
+        comment_id: u64,
+        thread_id: u64,
+        sender_id: u64,
+        channel_id: u64,
+        path: String,
+    },
 }
 
 impl Notification {
@@ -96,6 +104,13 @@ mod tests {
                 channel_id: 30,
                 message_id: 1,
             },
+            Notification::ReviewCommentMention {
+                comment_id: 7,
+                thread_id: 3,
+                sender_id: 200,
+                channel_id: 30,
+                path: "zed/src/main.rs".into(),
+            },
         ] {
             let message = notification.to_proto();
             let deserialized = Notification::from_proto(&message).unwrap();
@@ -106,6 +106,15 @@ impl LaunchRequest {
     }
 }
 
+/// Represents the request to load a core dump for post-mortem debugging
+#[derive(Deserialize, Serialize, Default, PartialEq, Eq, JsonSchema, Clone, Debug)]
+pub struct CoreDumpRequest {
+    /// The program that produced the core dump
+    pub program: String,
+    /// The core dump to load
+    pub core_file: PathBuf,
+}
+
 /// Represents the type that will determine which request to call on the debug adapter
 #[derive(Deserialize, Serialize, PartialEq, Eq, JsonSchema, Clone, Debug)]
 #[serde(rename_all = "lowercase", tag = "request")]
@@ -114,6 +123,8 @@ pub enum DebugRequest {
     Launch(LaunchRequest),
     /// Call the `attach` request on the debug adapter
     Attach(AttachRequest),
+    /// Load a core dump; adapters translate this into their own core-loading request
+    CoreDump(CoreDumpRequest),
 }
 
 impl DebugRequest {
@@ -145,6 +156,14 @@ impl DebugRequest {
                     },
                 )),
             },
+            DebugRequest::CoreDump(core_dump_request) => proto::DebugRequest {
+                request: Some(proto::debug_request::Request::DebugCoreDumpRequest(
+                    proto::DebugCoreDumpRequest {
+                        program: core_dump_request.program.clone(),
+                        core_file: core_dump_request.core_file.to_string_lossy().into_owned(),
+                    },
+                )),
+            },
         }
     }
 
@@ -168,6 +187,14 @@ impl DebugRequest {
             }) => Ok(DebugRequest::Attach(AttachRequest {
                 process_id: Some(process_id),
             })),
+
+            proto::debug_request::Request::DebugCoreDumpRequest(proto::DebugCoreDumpRequest {
+                program,
+                core_file,
+            }) => Ok(DebugRequest::CoreDump(CoreDumpRequest {
+                program,
+                core_file: core_file.into(),
+            })),
         }
     }
 }
@@ -184,6 +211,12 @@ impl From<AttachRequest> for DebugRequest {
     }
 }
 
+impl From<CoreDumpRequest> for DebugRequest {
+    fn from(core_dump_config: CoreDumpRequest) -> Self {
+        DebugRequest::CoreDump(core_dump_config)
+    }
+}
+
 #[derive(Serialize, PartialEq, Eq, JsonSchema, Clone, Debug)]
 #[serde(untagged)]
 pub enum BuildTaskDefinition {
@@ -382,7 +415,7 @@ impl DebugTaskFile {
 
 #[cfg(test)]
 mod tests {
-    use crate::DebugScenario;
+    use crate::{CoreDumpRequest, DebugRequest, DebugScenario, ZedDebugConfig};
     use serde_json::json;
 
     #[test]
@@ -465,6 +498,31 @@ mod tests {
         assert_eq!("Attach to process", deserialized.label.as_ref());
     }
 
+    #[test]
+    fn test_core_dump_request_deserialization() {
+        let json = r#"{
+            "label": "Inspect crash",
+            "adapter": "GDB",
+            "request": "coredump",
+            "program": "target/debug/myapp",
+            "core_file": "/tmp/core.1234"
+        }"#;
+
+        let deserialized: ZedDebugConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            DebugRequest::CoreDump(CoreDumpRequest {
+                program: "target/debug/myapp".into(),
+                core_file: "/tmp/core.1234".into(),
+            }),
+            deserialized.request
+        );
+        assert_eq!(
+            deserialized.request,
+            DebugRequest::from_proto(deserialized.request.to_proto()).unwrap()
+        );
+    }
+
     #[test]
     fn test_build_task_definition_without_label() {
         use crate::BuildTaskDefinition;
@@ -489,6 +489,7 @@ impl PickerDelegate for TasksModalDelegate {
             TaskSourceKind::UserInput => Some(Icon::new(IconName::Terminal)),
             TaskSourceKind::AbsPath { .. } => Some(Icon::new(IconName::Settings)),
             TaskSourceKind::Worktree { .. } => Some(Icon::new(IconName::FileTree)),
+            TaskSourceKind::Extension { .. } => Some(Icon::new(IconName::Blocks)),
             TaskSourceKind::Lsp {
                 language_name: name,
                 ..
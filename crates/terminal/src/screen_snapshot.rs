@@ -0,0 +1,300 @@
+//! Encodes the visible screen of a terminal as escape sequences, so that another
+//! terminal can reproduce it by parsing them. This is how terminals shared with
+//! collaborators are streamed to guests: the whole screen when they start watching,
+//! and the lines that changed afterwards.
+
+use alacritty_terminal::{
+    Term,
+    event::EventListener,
+    grid::{Dimensions, Row},
+    index::{Column, Line},
+    term::{
+        TermMode,
+        cell::{Cell, Flags},
+    },
+    vte::ansi::{Color, NamedColor},
+};
+use std::fmt::Write as _;
+
+const STYLE_FLAGS: Flags = Flags::BOLD
+    .union(Flags::DIM)
+    .union(Flags::ITALIC)
+    .union(Flags::ALL_UNDERLINES)
+    .union(Flags::INVERSE)
+    .union(Flags::HIDDEN)
+    .union(Flags::STRIKEOUT);
+
+/// The visible screen of a terminal, encoded line by line as escape sequences that another
+/// terminal can parse to reproduce it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScreenSnapshot {
+    columns: usize,
+    /// The text and attributes of each line, without the sequence positioning the cursor at its start.
+    lines: Vec<String>,
+    /// Positions the cursor and restores the terminal modes, after the lines are drawn.
+    cursor: String,
+}
+
+impl ScreenSnapshot {
+    pub(crate) fn new<T: EventListener>(term: &Term<T>) -> Self {
+        let grid = term.grid();
+        let lines = (0..grid.screen_lines())
+            .map(|line| encode_line(&grid[Line(line as i32)], grid.columns()))
+            .collect();
+
+        let cursor = grid.cursor.point;
+        let mut cursor_sequence = format!("\x1b[{};{}H", cursor.line.0 + 1, cursor.column.0 + 1);
+        if term.mode().contains(TermMode::LINE_WRAP) {
+            cursor_sequence.push_str("\x1b[?7h");
+        }
+        if term.mode().contains(TermMode::SHOW_CURSOR) {
+            cursor_sequence.push_str("\x1b[?25h");
+        } else {
+            cursor_sequence.push_str("\x1b[?25l");
+        }
+
+        Self {
+            columns: grid.columns(),
+            lines,
+            cursor: cursor_sequence,
+        }
+    }
+
+    /// Returns escape sequences that clear a terminal and redraw this screen into it,
+    /// including colors, text attributes and the cursor position.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // Erase below the top-left corner instead of clearing the whole screen, which would push the
+        // previous screen into the scrollback. Auto-wrap is disabled while redrawing, so that lines
+        // don't wrap and scroll when the receiving terminal is narrower.
+        let mut output = String::from("\x1b[0m\x1b[?7l\x1b[H\x1b[J");
+        for (line, contents) in self.lines.iter().enumerate() {
+            if !contents.is_empty() {
+                write!(output, "\x1b[{};1H{contents}", line + 1).ok();
+            }
+        }
+        output.push_str(&self.cursor);
+        output.into_bytes()
+    }
+
+    /// Returns escape sequences that turn a terminal showing `previous` into one showing this
+    /// screen, by redrawing only the lines that changed. Returns `None` if nothing changed.
+    ///
+    /// Falls back to redrawing the whole screen when the size of the screen changed.
+    pub fn diff(&self, previous: &ScreenSnapshot) -> Option<Vec<u8>> {
+        if self == previous {
+            return None;
+        }
+        if self.columns != previous.columns || self.lines.len() != previous.lines.len() {
+            return Some(self.to_bytes());
+        }
+
+        let mut output = String::from("\x1b[0m\x1b[?7l");
+        for (line, (contents, previous_contents)) in
+            self.lines.iter().zip(&previous.lines).enumerate()
+        {
+            if contents != previous_contents {
+                write!(output, "\x1b[{};1H\x1b[2K{contents}", line + 1).ok();
+            }
+        }
+        output.push_str(&self.cursor);
+        Some(output.into_bytes())
+    }
+}
+
+/// Encodes the text and attributes of a line, up to its last non-blank cell.
+fn encode_line(row: &Row<Cell>, columns: usize) -> String {
+    let mut output = String::new();
+    let Some(last_column) = (0..columns).rfind(|column| !is_blank(&row[Column(*column)])) else {
+        return output;
+    };
+
+    let mut current_style = CellStyle::default();
+    for column in 0..=last_column {
+        let cell = &row[Column(column)];
+        if cell
+            .flags
+            .intersects(Flags::WIDE_CHAR_SPACER | Flags::LEADING_WIDE_CHAR_SPACER)
+        {
+            continue;
+        }
+
+        let style = CellStyle::from(cell);
+        if style != current_style {
+            style.write_sgr(&mut output);
+            current_style = style;
+        }
+        output.push(cell.c);
+        if let Some(zerowidth) = cell.zerowidth() {
+            output.extend(zerowidth);
+        }
+    }
+    if current_style != CellStyle::default() {
+        output.push_str("\x1b[0m");
+    }
+    output
+}
+
+fn is_blank(cell: &Cell) -> bool {
+    cell.c == ' ' && cell.zerowidth().is_none() && CellStyle::from(cell) == CellStyle::default()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct CellStyle {
+    foreground: Color,
+    background: Color,
+    flags: Flags,
+}
+
+impl Default for CellStyle {
+    fn default() -> Self {
+        Self {
+            foreground: Color::Named(NamedColor::Foreground),
+            background: Color::Named(NamedColor::Background),
+            flags: Flags::empty(),
+        }
+    }
+}
+
+impl From<&Cell> for CellStyle {
+    fn from(cell: &Cell) -> Self {
+        Self {
+            foreground: cell.fg,
+            background: cell.bg,
+            flags: cell.flags & STYLE_FLAGS,
+        }
+    }
+}
+
+impl CellStyle {
+    /// Writes a "select graphic rendition" sequence that resets all attributes and then applies this style.
+    fn write_sgr(&self, output: &mut String) {
+        output.push_str("\x1b[0");
+        for (flag, parameter) in [
+            (Flags::BOLD, "1"),
+            (Flags::DIM, "2"),
+            (Flags::ITALIC, "3"),
+            (Flags::INVERSE, "7"),
+            (Flags::HIDDEN, "8"),
+            (Flags::STRIKEOUT, "9"),
+        ] {
+            if self.flags.contains(flag) {
+                output.push(';');
+                output.push_str(parameter);
+            }
+        }
+        if self.flags.contains(Flags::DOUBLE_UNDERLINE) {
+            output.push_str(";21");
+        } else if self.flags.intersects(Flags::ALL_UNDERLINES) {
+            output.push_str(";4");
+        }
+        write_color(output, self.foreground, 30, 90);
+        write_color(output, self.background, 40, 100);
+        output.push('m');
+    }
+}
+
+fn write_color(output: &mut String, color: Color, normal_base: u8, bright_base: u8) {
+    match color {
+        Color::Named(named) => match named as usize {
+            index @ 0..8 => {
+                write!(output, ";{}", normal_base as usize + index).ok();
+            }
+            index @ 8..16 => {
+                write!(output, ";{}", bright_base as usize + index - 8).ok();
+            }
+            // Default, dim and cursor colors have no SGR parameter. The reset at the
+            // start of the sequence already selected the default color.
+            _ => {}
+        },
+        Color::Indexed(index) => {
+            write!(output, ";{};5;{}", normal_base + 8, index).ok();
+        }
+        Color::Spec(rgb) => {
+            write!(
+                output,
+                ";{};2;{};{};{}",
+                normal_base + 8,
+                rgb.r,
+                rgb.g,
+                rgb.b
+            )
+            .ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alacritty_terminal::{
+        event::VoidListener,
+        term::{Config, test::TermSize},
+        vte::ansi::{Processor, StdSyncHandler},
+    };
+
+    fn term_with_output(output: &[u8]) -> Term<VoidListener> {
+        let mut term = Term::new(Config::default(), &TermSize::new(20, 4), VoidListener);
+        let mut parser: Processor<StdSyncHandler> = Processor::new();
+        parser.advance(&mut term, output);
+        term
+    }
+
+    fn cells(term: &Term<VoidListener>) -> Vec<Vec<(char, Color, Color, Flags)>> {
+        let grid = term.grid();
+        (0..grid.screen_lines())
+            .map(|line| {
+                (0..grid.columns())
+                    .map(|column| {
+                        let cell = &grid[Line(line as i32)][Column(column)];
+                        (cell.c, cell.fg, cell.bg, cell.flags & STYLE_FLAGS)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_encoded_screen_round_trips() {
+        let host = term_with_output(
+            b"$ ls\r\n\x1b[1;34msrc\x1b[0m  \x1b[38;5;208mCargo.toml\x1b[0m\r\n\x1b[4;41mwarn\x1b[0m \xe4\xbd\xa0\xe5\xa5\xbd\r\n$ ",
+        );
+        let guest = term_with_output(&ScreenSnapshot::new(&host).to_bytes());
+
+        assert_eq!(cells(&guest), cells(&host));
+        assert_eq!(guest.grid().cursor.point, host.grid().cursor.point);
+    }
+
+    #[test]
+    fn test_encoded_screen_replaces_previous_content() {
+        let first = term_with_output(b"first line\r\nsecond line");
+        let second = term_with_output(b"\x1b[3;5Hmoved");
+
+        let mut parser: Processor<StdSyncHandler> = Processor::new();
+        let mut guest = term_with_output(&ScreenSnapshot::new(&first).to_bytes());
+        parser.advance(&mut guest, &ScreenSnapshot::new(&second).to_bytes());
+
+        assert_eq!(cells(&guest), cells(&second));
+        assert_eq!(guest.grid().cursor.point, second.grid().cursor.point);
+    }
+
+    #[test]
+    fn test_diff_redraws_changed_lines() {
+        let before = term_with_output(b"\x1b[1mfirst\x1b[0m line\r\nsecond line\r\nthird");
+        let after = term_with_output(b"\x1b[1mfirst\x1b[0m line\r\nsecond\r\nthird line\r\n$ ");
+        let before_snapshot = ScreenSnapshot::new(&before);
+        let after_snapshot = ScreenSnapshot::new(&after);
+
+        let diff = after_snapshot.diff(&before_snapshot).unwrap();
+        let diff_text = String::from_utf8(diff.clone()).unwrap();
+        assert!(!diff_text.contains("first"), "unchanged lines are not sent");
+        assert!(diff.len() < after_snapshot.to_bytes().len());
+
+        let mut parser: Processor<StdSyncHandler> = Processor::new();
+        let mut guest = term_with_output(&before_snapshot.to_bytes());
+        parser.advance(&mut guest, &diff);
+        assert_eq!(cells(&guest), cells(&after));
+        assert_eq!(guest.grid().cursor.point, after.grid().cursor.point);
+
+        assert_eq!(after_snapshot.diff(&after_snapshot), None);
+    }
+}
@@ -3,6 +3,7 @@ pub mod mappings;
 pub use alacritty_terminal;
 
 mod pty_info;
+mod screen_snapshot;
 mod terminal_hyperlinks;
 pub mod terminal_settings;
 
@@ -23,6 +24,7 @@ use alacritty_terminal::{
     vi_mode::{ViModeCursor, ViMotion},
     vte::ansi::{
         ClearMode, CursorStyle as AlacCursorStyle, Handler, NamedPrivateMode, PrivateMode,
+        Processor,
     },
 };
 use anyhow::{Result, bail};
@@ -40,6 +42,7 @@ use mappings::mouse::{
 use collections::{HashMap, VecDeque};
 use futures::StreamExt;
 use pty_info::PtyProcessInfo;
+pub use screen_snapshot::ScreenSnapshot;
 use serde::{Deserialize, Serialize};
 use settings::Settings;
 use smol::channel::{Receiver, Sender};
@@ -160,6 +163,29 @@ impl EventListener for ZedListener {
     }
 }
 
+/// Where the bytes typed into a terminal are sent.
+enum TerminalInput {
+    /// The terminal runs a local process behind a PTY.
+    Pty(Notifier),
+    /// The terminal displays output produced elsewhere, e.g. a terminal shared by a collaborator,
+    /// and forwards its input to whoever produces that output.
+    Display {
+        input_tx: UnboundedSender<Vec<u8>>,
+        output_parser: Box<Processor>,
+    },
+}
+
+impl TerminalInput {
+    fn notify<B: Into<Cow<'static, [u8]>>>(&self, bytes: B) {
+        match self {
+            TerminalInput::Pty(notifier) => notifier.notify(bytes),
+            TerminalInput::Display { input_tx, .. } => {
+                input_tx.unbounded_send(bytes.into().into_owned()).ok();
+            }
+        }
+    }
+}
+
 pub fn init(cx: &mut App) {
     TerminalSettings::register(cx);
 }
@@ -463,7 +489,7 @@ impl TerminalBuilder {
 
         let terminal = Terminal {
             task,
-            pty_tx: Notifier(pty_tx),
+            pty_tx: TerminalInput::Pty(Notifier(pty_tx)),
             completion_tx,
             term,
             term_config: config,
@@ -473,7 +499,7 @@ impl TerminalBuilder {
             last_mouse: None,
             matches: Vec::new(),
             selection_head: None,
-            pty_info,
+            pty_info: Some(pty_info),
             breadcrumb_text: String::new(),
             scroll_px: px(0.),
             next_link_id: 0,
@@ -491,6 +517,68 @@ impl TerminalBuilder {
         })
     }
 
+    /// Creates a terminal without a PTY, which displays the output passed to [`Terminal::write_output`]
+    /// and sends everything typed into it to `input_tx`.
+    pub fn new_display_only(
+        title: Option<SharedString>,
+        cursor_shape: CursorShape,
+        alternate_scroll: AlternateScroll,
+        max_scroll_history_lines: Option<usize>,
+        input_tx: UnboundedSender<Vec<u8>>,
+    ) -> TerminalBuilder {
+        let config = Config {
+            scrolling_history: max_scroll_history_lines
+                .unwrap_or(DEFAULT_SCROLL_HISTORY_LINES)
+                .min(MAX_SCROLL_HISTORY_LINES),
+            default_cursor_style: AlacCursorStyle::from(cursor_shape),
+            ..Config::default()
+        };
+
+        let (events_tx, events_rx) = unbounded();
+        let mut term = Term::new(
+            config.clone(),
+            &TerminalBounds::default(),
+            ZedListener(events_tx),
+        );
+        if let AlternateScroll::Off = alternate_scroll {
+            term.unset_private_mode(PrivateMode::Named(NamedPrivateMode::AlternateScroll));
+        }
+
+        // Nothing ever waits for a display-only terminal to exit.
+        let (completion_tx, _) = smol::channel::unbounded();
+
+        let terminal = Terminal {
+            task: None,
+            pty_tx: TerminalInput::Display {
+                input_tx,
+                output_parser: Box::new(Processor::new()),
+            },
+            completion_tx,
+            term: Arc::new(FairMutex::new(term)),
+            term_config: config,
+            title_override: title,
+            events: VecDeque::with_capacity(10),
+            last_content: Default::default(),
+            last_mouse: None,
+            matches: Vec::new(),
+            selection_head: None,
+            pty_info: None,
+            breadcrumb_text: String::new(),
+            scroll_px: px(0.),
+            next_link_id: 0,
+            selection_phase: SelectionPhase::Ended,
+            hyperlink_regex_searches: RegexSearches::new(),
+            vi_mode_enabled: false,
+            is_ssh_terminal: false,
+            python_venv_directory: None,
+        };
+
+        TerminalBuilder {
+            terminal,
+            events_rx,
+        }
+    }
+
     pub fn subscribe(mut self, cx: &Context<Terminal>) -> Terminal {
         //Event loop
         cx.spawn(async move |terminal, cx| {
@@ -620,7 +708,7 @@ pub enum SelectionPhase {
 }
 
 pub struct Terminal {
-    pty_tx: Notifier,
+    pty_tx: TerminalInput,
     completion_tx: Sender<Option<ExitStatus>>,
     term: Arc<FairMutex<Term<ZedListener>>>,
     term_config: Config,
@@ -631,7 +719,8 @@ pub struct Terminal {
     pub last_content: TerminalContent,
     pub selection_head: Option<AlacPoint>,
     pub breadcrumb_text: String,
-    pub pty_info: PtyProcessInfo,
+    /// Information about the process running in the terminal, `None` for display-only terminals.
+    pub pty_info: Option<PtyProcessInfo>,
     title_override: Option<SharedString>,
     pub python_venv_directory: Option<PathBuf>,
     scroll_px: Pixels,
@@ -697,7 +786,7 @@ impl Terminal {
                 cx.write_to_clipboard(ClipboardItem::new_string(data))
             }
             AlacTermEvent::ClipboardLoad(_, format) => {
-                self.write_to_pty(
+                self.write_response_to_pty(
                     match &cx.read_from_clipboard().and_then(|item| item.text()) {
                         // The terminal only supports pasting strings, not images.
                         Some(text) => format(text),
@@ -706,10 +795,10 @@ impl Terminal {
                     .into_bytes(),
                 )
             }
-            AlacTermEvent::PtyWrite(out) => self.write_to_pty(out.into_bytes()),
-            AlacTermEvent::TextAreaSizeRequest(format) => {
-                self.write_to_pty(format(self.last_content.terminal_bounds.into()).into_bytes())
-            }
+            AlacTermEvent::PtyWrite(out) => self.write_response_to_pty(out.into_bytes()),
+            AlacTermEvent::TextAreaSizeRequest(format) => self.write_response_to_pty(
+                format(self.last_content.terminal_bounds.into()).into_bytes(),
+            ),
             AlacTermEvent::CursorBlinkingChange => {
                 let terminal = self.term.lock();
                 let blinking = terminal.cursor_style().blinking;
@@ -725,7 +814,11 @@ impl Terminal {
             AlacTermEvent::Wakeup => {
                 cx.emit(Event::Wakeup);
 
-                if self.pty_info.has_changed() {
+                if self
+                    .pty_info
+                    .as_mut()
+                    .is_some_and(|pty_info| pty_info.has_changed())
+                {
                     cx.emit(Event::TitleChanged);
                 }
             }
@@ -740,7 +833,7 @@ impl Terminal {
                 // followed by a color request sequence.
                 let color = self.term.lock().colors()[index]
                     .unwrap_or_else(|| to_alac_rgb(get_color_at_index(index, cx.theme().as_ref())));
-                self.write_to_pty(format(color).into_bytes());
+                self.write_response_to_pty(format(color).into_bytes());
             }
             AlacTermEvent::ChildExit(error_code) => {
                 self.register_task_finished(Some(error_code), cx);
@@ -767,7 +860,9 @@ impl Terminal {
 
                 self.last_content.terminal_bounds = new_bounds;
 
-                self.pty_tx.0.send(Msg::Resize(new_bounds.into())).ok();
+                if let TerminalInput::Pty(pty_tx) = &self.pty_tx {
+                    pty_tx.0.send(Msg::Resize(new_bounds.into())).ok();
+                }
 
                 term.resize(new_bounds);
             }
@@ -1117,6 +1212,44 @@ impl Terminal {
         self.pty_tx.notify(input.into());
     }
 
+    /// Answers a query made by the program running in the terminal.
+    ///
+    /// Display-only terminals ignore these, as the terminal that produced their output answers them.
+    fn write_response_to_pty(&self, response: impl Into<Cow<'static, [u8]>>) {
+        if let TerminalInput::Pty(pty_tx) = &self.pty_tx {
+            pty_tx.notify(response.into());
+        }
+    }
+
+    /// Whether this terminal displays output produced elsewhere instead of running a process.
+    pub fn is_display_only(&self) -> bool {
+        matches!(self.pty_tx, TerminalInput::Display { .. })
+    }
+
+    /// Feeds output into a display-only terminal, as if a process had written it to the PTY.
+    pub fn write_output(&mut self, output: &[u8], cx: &mut Context<Self>) {
+        let TerminalInput::Display { output_parser, .. } = &mut self.pty_tx else {
+            util::debug_panic!("cannot write output to a terminal that has a PTY");
+            return;
+        };
+        output_parser.advance(&mut *self.term.lock(), output);
+        cx.emit(Event::Wakeup);
+        cx.notify();
+    }
+
+    /// Returns the current screen of this terminal, which another terminal can reproduce by
+    /// passing the snapshot's bytes to [`Terminal::write_output`].
+    pub fn screen_snapshot(&self) -> ScreenSnapshot {
+        ScreenSnapshot::new(&self.term.lock())
+    }
+
+    pub fn set_title_override(&mut self, title: Option<SharedString>, cx: &mut Context<Self>) {
+        if self.title_override != title {
+            self.title_override = title;
+            cx.emit(Event::TitleChanged);
+        }
+    }
+
     pub fn input(&mut self, input: impl Into<Cow<'static, [u8]>>) {
         self.events
             .push_back(InternalEvent::Scroll(AlacScroll::Bottom));
@@ -1691,6 +1824,7 @@ impl Terminal {
     /// remote host, in case Zed is connected to a remote host.
     fn client_side_working_directory(&self) -> Option<PathBuf> {
         self.pty_info
+            .as_ref()?
             .current
             .as_ref()
             .map(|process| process.cwd.clone())
@@ -1712,8 +1846,8 @@ impl Terminal {
                 .map(|title_override| title_override.to_string())
                 .unwrap_or_else(|| {
                     self.pty_info
-                        .current
                         .as_ref()
+                        .and_then(|pty_info| pty_info.current.as_ref())
                         .map(|fpi| {
                             let process_file = fpi
                                 .cwd
@@ -1902,7 +2036,9 @@ unsafe fn append_text_to_term(term: &mut Term<ZedListener>, text_lines: &[&str])
 
 impl Drop for Terminal {
     fn drop(&mut self) {
-        self.pty_tx.0.send(Msg::Shutdown).ok();
+        if let TerminalInput::Pty(pty_tx) = &self.pty_tx {
+            pty_tx.0.send(Msg::Shutdown).ok();
+        }
     }
 }
 
@@ -6,6 +6,7 @@ mod terminal_slash_command;
 pub mod terminal_tab_tooltip;
 
 use assistant_slash_command::SlashCommandRegistry;
+use collections::HashSet;
 use editor::{Editor, EditorSettings, actions::SelectAll, scroll::ScrollbarAutoHide};
 use gpui::{
     Action, AnyElement, App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
@@ -38,8 +39,8 @@ use ui::{
 };
 use util::{ResultExt, debug_panic, paths::PathWithPosition};
 use workspace::{
-    CloseActiveItem, NewCenterTerminal, NewTerminal, OpenOptions, OpenVisible, ToolbarItemLocation,
-    Workspace, WorkspaceId, delete_unloaded_items,
+    CloseActiveItem, NewCenterTerminal, NewTerminal, OpenOptions, OpenVisible, SaveIntent,
+    ToolbarItemLocation, Workspace, WorkspaceId, delete_unloaded_items,
     item::{
         BreadcrumbText, Item, ItemEvent, SerializableItem, TabContentParams, TabTooltipContent,
     },
@@ -78,7 +79,16 @@ pub struct SendText(String);
 #[action(namespace = terminal)]
 pub struct SendKeystroke(String);
 
-actions!(terminal, [RerunTask]);
+/// Shares the terminal with the guests of the current shared project.
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Action)]
+#[action(namespace = terminal)]
+pub struct ShareWithCollaborators {
+    /// Whether guests that can edit the project may type into the terminal.
+    #[serde(default)]
+    pub read_write: bool,
+}
+
+actions!(terminal, [RerunTask, StopSharingWithCollaborators]);
 
 pub fn init(cx: &mut App) {
     assistant_slash_command::init(cx);
@@ -87,13 +97,94 @@ pub fn init(cx: &mut App) {
 
     register_serializable_item::<TerminalView>(cx);
 
-    cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
         workspace.register_action(TerminalView::deploy);
+
+        let Some(window) = window else {
+            return;
+        };
+        cx.subscribe_in(
+            workspace.project(),
+            window,
+            |workspace, _, event, window, cx| match event {
+                project::Event::RemoteTerminalOpened(terminal_id) => {
+                    open_remote_terminal(workspace, *terminal_id, window, cx)
+                }
+                project::Event::SharedTerminalsChanged => {
+                    close_unshared_remote_terminals(workspace, window, cx)
+                }
+                _ => {}
+            },
+        )
+        .detach();
     })
     .detach();
     SlashCommandRegistry::global(cx).register_command(TerminalSlashCommand, true);
 }
 
+fn open_remote_terminal(
+    workspace: &mut Workspace,
+    terminal_id: u64,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let project = workspace.project().clone();
+    let Some(terminal) = project
+        .read(cx)
+        .remote_terminals()
+        .get(&terminal_id)
+        .map(|remote| remote.terminal.clone())
+    else {
+        return;
+    };
+    let workspace_handle = workspace.weak_handle();
+    let workspace_id = workspace.database_id();
+    let view = cx.new(|cx| {
+        TerminalView::new(
+            terminal,
+            workspace_handle,
+            workspace_id,
+            project.downgrade(),
+            window,
+            cx,
+        )
+    });
+    workspace.add_item_to_active_pane(Box::new(view), None, false, window, cx);
+}
+
+/// Closes the views of terminals whose host stopped sharing them.
+fn close_unshared_remote_terminals(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let remote_terminals = workspace
+        .project()
+        .read(cx)
+        .remote_terminals()
+        .values()
+        .map(|remote| remote.terminal.entity_id())
+        .collect::<HashSet<_>>();
+    for pane in workspace.panes().to_vec() {
+        let unshared_views = pane
+            .read(cx)
+            .items_of_type::<TerminalView>()
+            .filter(|view| {
+                let terminal = view.read(cx).terminal();
+                terminal.read(cx).is_display_only()
+                    && !remote_terminals.contains(&terminal.entity_id())
+            })
+            .map(|view| view.entity_id())
+            .collect::<Vec<_>>();
+        pane.update(cx, |pane, cx| {
+            for view_id in unshared_views {
+                pane.close_item_by_id(view_id, SaveIntent::Skip, window, cx)
+                    .detach_and_log_err(cx);
+            }
+        });
+    }
+}
+
 pub struct BlockProperties {
     pub height: u8,
     pub render: Box<dyn Send + Fn(&mut BlockContext) -> AnyElement>,
@@ -379,6 +470,18 @@ impl TerminalView {
             .map_or(false, |terminal_panel| {
                 terminal_panel.read(cx).assistant_enabled()
             });
+        let sharing = self
+            .project
+            .upgrade()
+            .filter(|project| {
+                project.read(cx).is_shared() && !self.terminal.read(cx).is_display_only()
+            })
+            .map(|project| {
+                project
+                    .read(cx)
+                    .shared_terminal_id(&self.terminal)
+                    .is_some()
+            });
         let context_menu = ContextMenu::build(window, cx, |menu, _, _| {
             menu.context(self.focus_handle.clone())
                 .action("New Terminal", Box::new(NewTerminal))
@@ -391,6 +494,23 @@ impl TerminalView {
                     menu.separator()
                         .action("Inline Assist", Box::new(InlineAssist::default()))
                 })
+                .when_some(sharing, |menu, shared| {
+                    menu.separator()
+                        .action(
+                            "Share with Collaborators (Read-Only)",
+                            Box::new(ShareWithCollaborators { read_write: false }),
+                        )
+                        .action(
+                            "Share with Collaborators (Read-Write)",
+                            Box::new(ShareWithCollaborators { read_write: true }),
+                        )
+                        .when(shared, |menu| {
+                            menu.action(
+                                "Stop Sharing with Collaborators",
+                                Box::new(StopSharingWithCollaborators),
+                            )
+                        })
+                })
                 .separator()
                 .action(
                     "Close Terminal Tab",
@@ -474,6 +594,39 @@ impl TerminalView {
         window.dispatch_action(Box::new(task), cx);
     }
 
+    fn share_with_collaborators(
+        &mut self,
+        action: &ShareWithCollaborators,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let terminal = self.terminal.clone();
+        self.project
+            .update(cx, |project, cx| {
+                project.share_terminal(terminal, action.read_write, cx)
+            })
+            .and_then(|result| result)
+            .log_err();
+    }
+
+    fn stop_sharing_with_collaborators(
+        &mut self,
+        _: &StopSharingWithCollaborators,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let terminal = self.terminal.clone();
+        self.project
+            .update(cx, |project, cx| {
+                match project.shared_terminal_id(&terminal) {
+                    Some(terminal_id) => project.unshare_terminal(terminal_id, cx),
+                    None => Ok(()),
+                }
+            })
+            .and_then(|result| result)
+            .log_err();
+    }
+
     fn clear(&mut self, _: &Clear, _: &mut Window, cx: &mut Context<Self>) {
         self.scroll_top = px(0.);
         self.terminal.update(cx, |term, _| term.clear());
@@ -1510,6 +1663,8 @@ impl Render for TerminalView {
             .on_action(cx.listener(TerminalView::show_character_palette))
             .on_action(cx.listener(TerminalView::select_all))
             .on_action(cx.listener(TerminalView::rerun_task))
+            .on_action(cx.listener(TerminalView::share_with_collaborators))
+            .on_action(cx.listener(TerminalView::stop_sharing_with_collaborators))
             .on_key_down(cx.listener(Self::key_down))
             .on_mouse_down(
                 MouseButton::Right,
@@ -1570,7 +1725,10 @@ impl Item for TerminalView {
     fn tab_tooltip_content(&self, cx: &App) -> Option<TabTooltipContent> {
         let terminal = self.terminal().read(cx);
         let title = terminal.title(false);
-        let pid = terminal.pty_info.pid_getter().fallback_pid();
+        let Some(pty_info) = &terminal.pty_info else {
+            return Some(TabTooltipContent::Text(title.into()));
+        };
+        let pid = pty_info.pid_getter().fallback_pid();
 
         Some(TabTooltipContent::Custom(Box::new(move |_window, cx| {
             cx.new(|_| TerminalTooltip::new(title.clone(), pid)).into()
@@ -1647,6 +1805,9 @@ impl Item for TerminalView {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Option<Entity<Self>> {
+        if self.terminal.read(cx).is_display_only() {
+            return None;
+        }
         let window_handle = window.window_handle();
         let terminal = self
             .project
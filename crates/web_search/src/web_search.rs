@@ -1,5 +1,5 @@
 use anyhow::Result;
-use collections::HashMap;
+use collections::IndexMap;
 use gpui::{App, AppContext as _, Context, Entity, Global, SharedString, Task};
 use std::sync::Arc;
 use zed_llm_client::WebSearchResponse;
@@ -23,8 +23,11 @@ impl Global for GlobalWebSearchRegistry {}
 
 #[derive(Default)]
 pub struct WebSearchRegistry {
-    providers: HashMap<WebSearchProviderId, Arc<dyn WebSearchProvider>>,
+    /// The registered providers, in the order they were registered.
+    providers: IndexMap<WebSearchProviderId, Arc<dyn WebSearchProvider>>,
     active_provider: Option<Arc<dyn WebSearchProvider>>,
+    /// The provider chosen in the settings, which takes precedence over the active provider.
+    selected_provider: Option<WebSearchProviderId>,
 }
 
 impl WebSearchRegistry {
@@ -32,6 +35,11 @@ impl WebSearchRegistry {
         cx.global::<GlobalWebSearchRegistry>().0.clone()
     }
 
+    pub fn try_global(cx: &App) -> Option<Entity<Self>> {
+        cx.try_global::<GlobalWebSearchRegistry>()
+            .map(|registry| registry.0.clone())
+    }
+
     pub fn read_global(cx: &App) -> &Self {
         cx.global::<GlobalWebSearchRegistry>().0.read(cx)
     }
@@ -40,8 +48,16 @@ impl WebSearchRegistry {
         self.providers.values()
     }
 
+    /// Returns the provider to search with.
+    ///
+    /// If a provider has been selected, only that provider is used, so that searches are
+    /// never sent somewhere the user didn't choose. Otherwise, this is the first provider
+    /// that was registered.
     pub fn active_provider(&self) -> Option<Arc<dyn WebSearchProvider>> {
-        self.active_provider.clone()
+        match &self.selected_provider {
+            Some(id) => self.providers.get(id).cloned(),
+            None => self.active_provider.clone(),
+        }
     }
 
     pub fn set_active_provider(&mut self, provider: Arc<dyn WebSearchProvider>) {
@@ -49,23 +65,122 @@ impl WebSearchRegistry {
         self.providers.insert(provider.id(), provider);
     }
 
+    pub fn selected_provider(&self) -> Option<&WebSearchProviderId> {
+        self.selected_provider.as_ref()
+    }
+
+    pub fn set_selected_provider(
+        &mut self,
+        id: Option<WebSearchProviderId>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.selected_provider != id {
+            self.selected_provider = id;
+            cx.notify();
+        }
+    }
+
     pub fn register_provider<T: WebSearchProvider + 'static>(
         &mut self,
         provider: T,
-        _cx: &mut Context<Self>,
+        cx: &mut Context<Self>,
     ) {
         let id = provider.id();
         let provider = Arc::new(provider);
         self.providers.insert(id.clone(), provider.clone());
-        if self.active_provider.is_none() {
+        if self
+            .active_provider
+            .as_ref()
+            .map_or(true, |active_provider| active_provider.id() == id)
+        {
             self.active_provider = Some(provider);
         }
+        cx.notify();
     }
 
-    pub fn unregister_provider(&mut self, id: WebSearchProviderId) {
-        self.providers.remove(&id);
+    /// Unregisters the provider with the given ID. If it was the active provider, the first
+    /// remaining provider becomes the active one.
+    pub fn unregister_provider(&mut self, id: WebSearchProviderId, cx: &mut Context<Self>) {
+        self.providers.shift_remove(&id);
         if self.active_provider.as_ref().map(|provider| provider.id()) == Some(id) {
-            self.active_provider = None;
+            self.active_provider = self.providers.values().next().cloned();
+        }
+        cx.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeWebSearchProvider(&'static str);
+
+    impl WebSearchProvider for FakeWebSearchProvider {
+        fn id(&self) -> WebSearchProviderId {
+            WebSearchProviderId(self.0.into())
+        }
+
+        fn search(&self, _query: String, _cx: &mut App) -> Task<Result<WebSearchResponse>> {
+            Task::ready(Ok(WebSearchResponse {
+                results: Vec::new(),
+            }))
         }
     }
+
+    fn active_provider_id(registry: &WebSearchRegistry) -> Option<WebSearchProviderId> {
+        registry.active_provider().map(|provider| provider.id())
+    }
+
+    fn provider_id(id: &'static str) -> WebSearchProviderId {
+        WebSearchProviderId(id.into())
+    }
+
+    #[gpui::test]
+    fn test_selecting_providers(cx: &mut App) {
+        let registry = cx.new(|_| WebSearchRegistry::default());
+        registry.update(cx, |registry, cx| {
+            assert_eq!(active_provider_id(registry), None);
+
+            // Without a selection, the first provider that was registered is used.
+            registry.register_provider(FakeWebSearchProvider("first"), cx);
+            registry.register_provider(FakeWebSearchProvider("second"), cx);
+            assert_eq!(active_provider_id(registry), Some(provider_id("first")));
+
+            registry.set_selected_provider(Some(provider_id("second")), cx);
+            assert_eq!(active_provider_id(registry), Some(provider_id("second")));
+
+            // A selected provider that isn't registered is never replaced by another one.
+            registry.set_selected_provider(Some(provider_id("third")), cx);
+            assert_eq!(active_provider_id(registry), None);
+            registry.register_provider(FakeWebSearchProvider("third"), cx);
+            assert_eq!(active_provider_id(registry), Some(provider_id("third")));
+
+            registry.set_selected_provider(None, cx);
+            assert_eq!(active_provider_id(registry), Some(provider_id("first")));
+        });
+    }
+
+    #[gpui::test]
+    fn test_unregistering_providers(cx: &mut App) {
+        let registry = cx.new(|_| WebSearchRegistry::default());
+        registry.update(cx, |registry, cx| {
+            registry.register_provider(FakeWebSearchProvider("first"), cx);
+            registry.register_provider(FakeWebSearchProvider("second"), cx);
+            registry.register_provider(FakeWebSearchProvider("third"), cx);
+
+            registry.unregister_provider(provider_id("second"), cx);
+            assert_eq!(active_provider_id(registry), Some(provider_id("first")));
+
+            // When the active provider is unregistered, a remaining one takes its place.
+            registry.unregister_provider(provider_id("first"), cx);
+            assert_eq!(active_provider_id(registry), Some(provider_id("third")));
+
+            registry.unregister_provider(provider_id("third"), cx);
+            assert_eq!(active_provider_id(registry), None);
+            assert_eq!(registry.providers().count(), 0);
+
+            registry.register_provider(FakeWebSearchProvider("fourth"), cx);
+            assert_eq!(active_provider_id(registry), Some(provider_id("fourth")));
+        });
+    }
 }
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use extension::{Extension, ExtensionHostProxy, ExtensionWebSearchProviderProxy};
+use gpui::{App, Task};
+use web_search::{WebSearchProvider, WebSearchProviderId, WebSearchRegistry};
+use zed_llm_client::{WebSearchResponse, WebSearchResult};
+
+pub fn init(cx: &mut App) {
+    let proxy = ExtensionHostProxy::default_global(cx);
+    proxy.register_web_search_provider_proxy(WebSearchRegistryProxy);
+}
+
+struct WebSearchRegistryProxy;
+
+impl ExtensionWebSearchProviderProxy for WebSearchRegistryProxy {
+    fn register_web_search_provider(
+        &self,
+        extension: Arc<dyn Extension>,
+        provider_id: Arc<str>,
+        cx: &mut App,
+    ) {
+        WebSearchRegistry::global(cx).update(cx, |registry, cx| {
+            registry.register_provider(
+                ExtensionWebSearchProvider {
+                    id: extension_provider_id(&extension.manifest().id, &provider_id),
+                    extension,
+                    provider_id,
+                },
+                cx,
+            )
+        });
+    }
+
+    fn unregister_web_search_provider(
+        &self,
+        extension_id: Arc<str>,
+        provider_id: Arc<str>,
+        cx: &mut App,
+    ) {
+        WebSearchRegistry::global(cx).update(cx, |registry, cx| {
+            registry.unregister_provider(extension_provider_id(&extension_id, &provider_id), cx)
+        });
+    }
+}
+
+/// Returns the ID of a provider from an extension, which is namespaced by the extension so
+/// that providers with the same name from different extensions don't replace each other.
+fn extension_provider_id(extension_id: &str, provider_id: &str) -> WebSearchProviderId {
+    WebSearchProviderId(format!("extension/{extension_id}/{provider_id}").into())
+}
+
+/// A web search provider implemented by an extension.
+struct ExtensionWebSearchProvider {
+    id: WebSearchProviderId,
+    extension: Arc<dyn Extension>,
+    /// The ID of the provider within the extension.
+    provider_id: Arc<str>,
+}
+
+impl WebSearchProvider for ExtensionWebSearchProvider {
+    fn id(&self) -> WebSearchProviderId {
+        self.id.clone()
+    }
+
+    fn search(&self, query: String, cx: &mut App) -> Task<Result<WebSearchResponse>> {
+        let extension = self.extension.clone();
+        let provider_id = self.provider_id.clone();
+        cx.background_spawn(async move {
+            let results = extension.web_search(provider_id, query).await?;
+            Ok(WebSearchResponse {
+                results: results
+                    .into_iter()
+                    .map(|result| WebSearchResult {
+                        title: result.title,
+                        url: result.url,
+                        text: result.text,
+                    })
+                    .collect(),
+            })
+        })
+    }
+}
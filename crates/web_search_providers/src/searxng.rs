@@ -0,0 +1,201 @@
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result, anyhow};
+use futures::AsyncReadExt as _;
+use gpui::{App, Task};
+use http_client::{AsyncBody, HttpClient, Method, Url};
+use serde::Deserialize;
+use web_search::{WebSearchProvider, WebSearchProviderId};
+use zed_llm_client::{WebSearchResponse, WebSearchResult};
+
+pub const SEARXNG_WEB_SEARCH_PROVIDER_ID: &'static str = "searxng";
+
+/// The maximum number of results to return from a single search.
+const MAX_RESULTS: usize = 10;
+
+/// A web search provider that queries a SearxNG instance, or any other endpoint that
+/// implements SearxNG's JSON search API.
+pub struct SearxngWebSearchProvider {
+    http_client: Arc<dyn HttpClient>,
+    api_url: String,
+}
+
+impl SearxngWebSearchProvider {
+    pub fn new(http_client: Arc<dyn HttpClient>, api_url: String) -> Self {
+        Self {
+            http_client,
+            api_url,
+        }
+    }
+}
+
+impl WebSearchProvider for SearxngWebSearchProvider {
+    fn id(&self) -> WebSearchProviderId {
+        WebSearchProviderId(SEARXNG_WEB_SEARCH_PROVIDER_ID.into())
+    }
+
+    fn search(&self, query: String, cx: &mut App) -> Task<Result<WebSearchResponse>> {
+        let http_client = self.http_client.clone();
+        let api_url = self.api_url.clone();
+        cx.background_spawn(async move { perform_web_search(http_client, &api_url, &query).await })
+    }
+}
+
+#[derive(Deserialize)]
+struct SearxngResponse {
+    results: Vec<SearxngResult>,
+}
+
+#[derive(Deserialize)]
+struct SearxngResult {
+    title: String,
+    url: String,
+    #[serde(default)]
+    content: String,
+}
+
+async fn perform_web_search(
+    http_client: Arc<dyn HttpClient>,
+    api_url: &str,
+    query: &str,
+) -> Result<WebSearchResponse> {
+    let url = search_url(api_url, query)?;
+    let request = http_client::Request::builder()
+        .method(Method::GET)
+        .uri(url.as_str())
+        .header("Accept", "application/json")
+        .body(AsyncBody::default())?;
+    let mut response = http_client
+        .send(request)
+        .await
+        .context("failed to send web search request")?;
+
+    let mut body = String::new();
+    response.body_mut().read_to_string(&mut body).await?;
+    anyhow::ensure!(
+        response.status().is_success(),
+        "error performing web search.\nStatus: {:?}\nBody: {body}",
+        response.status(),
+    );
+
+    let response: SearxngResponse =
+        serde_json::from_str(&body).context("failed to parse web search response")?;
+    Ok(WebSearchResponse {
+        results: response
+            .results
+            .into_iter()
+            .take(MAX_RESULTS)
+            .map(|result| WebSearchResult {
+                title: result.title,
+                url: result.url,
+                text: result.content,
+            })
+            .collect(),
+    })
+}
+
+/// Returns the URL of the JSON search endpoint for the query.
+fn search_url(api_url: &str, query: &str) -> Result<Url> {
+    let mut url =
+        Url::parse(api_url).with_context(|| format!("invalid SearxNG URL {api_url:?}"))?;
+    url.path_segments_mut()
+        .map_err(|()| anyhow!("invalid SearxNG URL {api_url:?}"))?
+        .pop_if_empty()
+        .push("search");
+    url.query_pairs_mut()
+        .append_pair("q", query)
+        .append_pair("format", "json");
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_client::{FakeHttpClient, Response};
+    use serde_json::json;
+
+    #[test]
+    fn test_search_url() {
+        assert_eq!(
+            search_url("https://searx.example.com", "rust traits")
+                .unwrap()
+                .as_str(),
+            "https://searx.example.com/search?q=rust+traits&format=json"
+        );
+        assert_eq!(
+            search_url("http://localhost:8888/searxng/", "a&b")
+                .unwrap()
+                .as_str(),
+            "http://localhost:8888/searxng/search?q=a%26b&format=json"
+        );
+        assert!(search_url("not a url", "query").is_err());
+    }
+
+    #[gpui::test]
+    async fn test_perform_web_search() {
+        let http_client = FakeHttpClient::create(|request| async move {
+            assert_eq!(
+                request.uri().to_string(),
+                "https://searx.example.com/search?q=rust+traits&format=json"
+            );
+            assert_eq!(request.headers()["Accept"], "application/json");
+            let results = (0..MAX_RESULTS + 5)
+                .map(|ix| {
+                    json!({
+                        "title": format!("Result {ix}"),
+                        "url": format!("https://example.com/{ix}"),
+                        "content": format!("Text {ix}"),
+                    })
+                })
+                .collect::<Vec<_>>();
+            Ok(Response::new(
+                json!({ "results": results }).to_string().into(),
+            ))
+        });
+
+        let response = perform_web_search(http_client, "https://searx.example.com", "rust traits")
+            .await
+            .unwrap();
+        assert_eq!(response.results.len(), MAX_RESULTS);
+        assert_eq!(response.results[0].title, "Result 0");
+        assert_eq!(response.results[0].url, "https://example.com/0");
+        assert_eq!(response.results[0].text, "Text 0");
+    }
+
+    #[gpui::test]
+    async fn test_perform_web_search_with_missing_content() {
+        let http_client = FakeHttpClient::create(|_| async move {
+            Ok(Response::new(
+                json!({
+                    "results": [{ "title": "Rust", "url": "https://rust-lang.org" }]
+                })
+                .to_string()
+                .into(),
+            ))
+        });
+
+        let response = perform_web_search(http_client, "https://searx.example.com", "rust")
+            .await
+            .unwrap();
+        assert_eq!(response.results.len(), 1);
+        assert_eq!(response.results[0].title, "Rust");
+        assert_eq!(response.results[0].text, "");
+    }
+
+    #[gpui::test]
+    async fn test_perform_web_search_error() {
+        let http_client = FakeHttpClient::create(|_| async move {
+            Ok(Response::builder()
+                .status(403)
+                .body("json format is disabled".into())?)
+        });
+
+        let error = perform_web_search(http_client, "https://searx.example.com", "rust")
+            .await
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("json format is disabled"),
+            "{error}"
+        );
+    }
+}
@@ -1,12 +1,22 @@
 mod cloud;
+mod extension_web_search;
+mod searxng;
+mod web_search_settings;
 
 use client::Client;
 use gpui::{App, Context, Entity};
+use http_client::HttpClient;
 use language_model::LanguageModelRegistry;
+use settings::{Settings, SettingsStore};
 use std::sync::Arc;
 use web_search::{WebSearchProviderId, WebSearchRegistry};
 
+pub use web_search_settings::*;
+
 pub fn init(client: Arc<Client>, cx: &mut App) {
+    WebSearchSettings::register(cx);
+    extension_web_search::init(cx);
+
     let registry = WebSearchRegistry::global(cx);
     registry.update(cx, |registry, cx| {
         register_web_search_providers(registry, client, cx);
@@ -25,6 +35,14 @@ fn register_web_search_providers(
         cx,
     );
 
+    let http_client: Arc<dyn HttpClient> = client.http_client();
+    let mut searxng_api_url = None;
+    update_from_settings(registry, &http_client, &mut searxng_api_url, cx);
+    cx.observe_global::<SettingsStore>(move |registry, cx| {
+        update_from_settings(registry, &http_client, &mut searxng_api_url, cx);
+    })
+    .detach();
+
     cx.subscribe(
         &LanguageModelRegistry::global(cx),
         move |this, registry, event, cx| match event {
@@ -50,8 +68,42 @@ fn register_zed_web_search_provider(
     if using_zed_provider {
         registry.register_provider(cloud::CloudWebSearchProvider::new(client, cx), cx)
     } else {
-        registry.unregister_provider(WebSearchProviderId(
-            cloud::ZED_WEB_SEARCH_PROVIDER_ID.into(),
-        ));
+        registry.unregister_provider(
+            WebSearchProviderId(cloud::ZED_WEB_SEARCH_PROVIDER_ID.into()),
+            cx,
+        );
+    }
+}
+
+/// Selects the provider chosen in the settings, and registers the SearxNG provider
+/// whenever its URL changes.
+fn update_from_settings(
+    registry: &mut WebSearchRegistry,
+    http_client: &Arc<dyn HttpClient>,
+    searxng_api_url: &mut Option<String>,
+    cx: &mut Context<WebSearchRegistry>,
+) {
+    let settings = WebSearchSettings::get_global(cx);
+    let selected_provider = settings
+        .provider
+        .clone()
+        .map(|provider| WebSearchProviderId(provider.into()));
+    let api_url = settings.searxng.api_url.clone();
+
+    registry.set_selected_provider(selected_provider, cx);
+
+    if *searxng_api_url == api_url {
+        return;
+    }
+    match &api_url {
+        Some(api_url) => registry.register_provider(
+            searxng::SearxngWebSearchProvider::new(http_client.clone(), api_url.clone()),
+            cx,
+        ),
+        None => registry.unregister_provider(
+            WebSearchProviderId(searxng::SEARXNG_WEB_SEARCH_PROVIDER_ID.into()),
+            cx,
+        ),
     }
+    *searxng_api_url = api_url;
 }
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use gpui::App;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, JsonSchema)]
+pub struct WebSearchSettings {
+    /// The ID of the web search provider that the agent should use: `"zed.dev"`,
+    /// `"searxng"`, or `"extension/<extension-id>/<provider-id>"` for a provider from
+    /// an extension.
+    ///
+    /// When unset, the first available provider is used.
+    ///
+    /// Default: null
+    #[serde(default)]
+    pub provider: Option<Arc<str>>,
+    /// Settings for the built-in SearxNG provider.
+    #[serde(default)]
+    pub searxng: SearxngSettings,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, JsonSchema)]
+pub struct SearxngSettings {
+    /// The URL of the SearxNG instance to search with. Any endpoint that implements
+    /// SearxNG's JSON search API can be used.
+    ///
+    /// The SearxNG provider is only available when this is set.
+    ///
+    /// Default: null
+    #[serde(default)]
+    pub api_url: Option<String>,
+}
+
+impl Settings for WebSearchSettings {
+    const KEY: Option<&'static str> = Some("web_search");
+
+    type FileContent = Self;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _cx: &mut App) -> Result<Self> {
+        sources.json_merge()
+    }
+
+    fn import_from_vscode(_vscode: &settings::VsCodeSettings, _current: &mut Self::FileContent) {}
+}
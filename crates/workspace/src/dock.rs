@@ -1,5 +1,5 @@
 use crate::persistence::model::DockData;
-use crate::{DraggedDock, Event, ModalLayer, Pane};
+use crate::{DraggedDock, Event, ModalLayer, Pane, item::ItemHandle};
 use crate::{Workspace, status_bar::StatusItemView};
 use anyhow::Context as _;
 use client::proto;
@@ -51,6 +51,19 @@ pub trait Panel: Focusable + EventEmitter<PanelEvent> + Render + Sized {
     fn pane(&self) -> Option<Entity<Pane>> {
         None
     }
+    /// The item that followers see while this panel is focused.
+    fn active_item_for_followers(&self, cx: &App) -> Option<Box<dyn ItemHandle>> {
+        self.pane()?.read(cx).active_item()
+    }
+    /// Shows an item that is being followed, for panels that don't show their items in a
+    /// [`Pane`].
+    fn activate_item_for_follower(
+        &mut self,
+        _item: &dyn ItemHandle,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) {
+    }
     fn remote_id() -> Option<proto::PanelId> {
         None
     }
@@ -71,6 +84,8 @@ pub trait PanelHandle: Send + Sync {
     fn set_active(&self, active: bool, window: &mut Window, cx: &mut App);
     fn remote_id(&self) -> Option<proto::PanelId>;
     fn pane(&self, cx: &App) -> Option<Entity<Pane>>;
+    fn active_item_for_followers(&self, cx: &App) -> Option<Box<dyn ItemHandle>>;
+    fn activate_item_for_follower(&self, item: &dyn ItemHandle, window: &mut Window, cx: &mut App);
     fn size(&self, window: &Window, cx: &App) -> Pixels;
     fn set_size(&self, size: Option<Pixels>, window: &mut Window, cx: &mut App);
     fn icon(&self, window: &Window, cx: &App) -> Option<ui::IconName>;
@@ -138,6 +153,16 @@ where
         self.read(cx).pane()
     }
 
+    fn active_item_for_followers(&self, cx: &App) -> Option<Box<dyn ItemHandle>> {
+        self.read(cx).active_item_for_followers(cx)
+    }
+
+    fn activate_item_for_follower(&self, item: &dyn ItemHandle, window: &mut Window, cx: &mut App) {
+        self.update(cx, |this, cx| {
+            this.activate_item_for_follower(item, window, cx)
+        })
+    }
+
     fn remote_id(&self) -> Option<PanelId> {
         T::remote_id()
     }
@@ -152,6 +152,7 @@ pub struct Breakpoint {
     pub condition: Option<Arc<str>>,
     pub hit_condition: Option<Arc<str>>,
     pub state: BreakpointState,
+    pub group: Option<Arc<str>>,
 }
 
 /// Wrapper for DB type of a breakpoint
@@ -193,8 +194,8 @@ struct Breakpoints(Vec<Breakpoint>);
 
 impl sqlez::bindable::StaticColumnCount for Breakpoint {
     fn column_count() -> usize {
-        // Position, log message, condition message, and hit condition message
-        4 + BreakpointStateWrapper::column_count()
+        // Position, log message, condition message, hit condition message, and group name
+        5 + BreakpointStateWrapper::column_count()
     }
 }
 
@@ -208,10 +209,11 @@ impl sqlez::bindable::Bind for Breakpoint {
         let next_index = statement.bind(&self.message, next_index)?;
         let next_index = statement.bind(&self.condition, next_index)?;
         let next_index = statement.bind(&self.hit_condition, next_index)?;
-        statement.bind(
+        let next_index = statement.bind(
             &BreakpointStateWrapper(Cow::Borrowed(&self.state)),
             next_index,
-        )
+        )?;
+        statement.bind(&self.group, next_index)
     }
 }
 
@@ -225,6 +227,7 @@ impl Column for Breakpoint {
         let (condition, next_index) = Option::<String>::column(statement, next_index)?;
         let (hit_condition, next_index) = Option::<String>::column(statement, next_index)?;
         let (state, next_index) = BreakpointStateWrapper::column(statement, next_index)?;
+        let (group, next_index) = Option::<String>::column(statement, next_index)?;
 
         Ok((
             Breakpoint {
@@ -233,6 +236,7 @@ impl Column for Breakpoint {
                 condition: condition.map(Arc::from),
                 hit_condition: hit_condition.map(Arc::from),
                 state: state.0.into_owned(),
+                group: group.map(Arc::from),
             },
             next_index,
         ))
@@ -542,6 +546,21 @@ define_connection! {
         ALTER TABLE breakpoints ADD COLUMN condition TEXT;
         ALTER TABLE breakpoints ADD COLUMN hit_condition TEXT;
     ),
+    sql!(
+        ALTER TABLE breakpoints ADD COLUMN group_name TEXT;
+    ),
+    sql!(
+        ALTER TABLE breakpoints ADD COLUMN branch TEXT;
+    ),
+    sql!(
+        CREATE TABLE breakpoint_branches (
+            workspace_id INTEGER PRIMARY KEY,
+            branch TEXT NOT NULL,
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+        );
+    ),
     ];
 }
 
@@ -630,6 +649,8 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
+            breakpoints_branch: self.breakpoints_branch(workspace_id),
+            branch_breakpoints: self.branch_breakpoints(workspace_id),
             window_id,
         })
     }
@@ -684,6 +705,8 @@ impl WorkspaceDb {
             window_bounds,
             centered_layout: centered_layout.unwrap_or(false),
             breakpoints: self.breakpoints(workspace_id),
+            breakpoints_branch: self.breakpoints_branch(workspace_id),
+            branch_breakpoints: self.branch_breakpoints(workspace_id),
             display,
             docks,
             session_id: None,
@@ -694,9 +717,9 @@ impl WorkspaceDb {
     fn breakpoints(&self, workspace_id: WorkspaceId) -> BTreeMap<Arc<Path>, Vec<SourceBreakpoint>> {
         let breakpoints: Result<Vec<(PathBuf, Breakpoint)>> = self
             .select_bound(sql! {
-                SELECT path, breakpoint_location, log_message, condition, hit_condition, state
+                SELECT path, breakpoint_location, log_message, condition, hit_condition, state, group_name
                 FROM breakpoints
-                WHERE workspace_id = ?
+                WHERE workspace_id = ? AND branch IS NULL
             })
             .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
 
@@ -717,6 +740,7 @@ impl WorkspaceDb {
                         condition: breakpoint.condition,
                         hit_condition: breakpoint.hit_condition,
                         state: breakpoint.state,
+                        group: breakpoint.group,
                     });
                 }
 
@@ -737,6 +761,62 @@ impl WorkspaceDb {
         }
     }
 
+    /// Returns the git branch that was checked out when the workspace's breakpoints were saved.
+    fn breakpoints_branch(&self, workspace_id: WorkspaceId) -> Option<Arc<str>> {
+        self.select_row_bound::<WorkspaceId, String>(sql! {
+            SELECT branch
+            FROM breakpoint_branches
+            WHERE workspace_id = ?
+        })
+        .and_then(|mut prepared_statement| (prepared_statement)(workspace_id))
+        .context("Getting breakpoints branch")
+        .log_err()
+        .flatten()
+        .map(Arc::from)
+    }
+
+    /// Returns the breakpoints kept aside for the git branches that aren't checked out.
+    fn branch_breakpoints(
+        &self,
+        workspace_id: WorkspaceId,
+    ) -> BTreeMap<Arc<str>, BTreeMap<Arc<Path>, Vec<SourceBreakpoint>>> {
+        let breakpoints: Result<Vec<(String, PathBuf, Breakpoint)>> = self
+            .select_bound(sql! {
+                SELECT branch, path, breakpoint_location, log_message, condition, hit_condition, state, group_name
+                FROM breakpoints
+                WHERE workspace_id = ? AND branch IS NOT NULL
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match breakpoints {
+            Ok(breakpoints) => {
+                let mut map: BTreeMap<Arc<str>, BTreeMap<Arc<Path>, Vec<SourceBreakpoint>>> =
+                    Default::default();
+                for (branch, path, breakpoint) in breakpoints {
+                    let path: Arc<Path> = path.into();
+                    map.entry(branch.into())
+                        .or_default()
+                        .entry(path.clone())
+                        .or_default()
+                        .push(SourceBreakpoint {
+                            row: breakpoint.position,
+                            path,
+                            message: breakpoint.message,
+                            condition: breakpoint.condition,
+                            hit_condition: breakpoint.hit_condition,
+                            state: breakpoint.state,
+                            group: breakpoint.group,
+                        });
+                }
+                map
+            }
+            Err(msg) => {
+                log::error!("Branch breakpoints query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
     /// Saves a workspace using the worktree roots. Will garbage collect any workspaces
     /// that used this workspace previously
     pub(crate) async fn save_workspace(&self, workspace: SerializedWorkspace) {
@@ -750,13 +830,27 @@ impl WorkspaceDb {
                     .context("Clearing old panes")?;
 
                 conn.exec_bound(sql!(DELETE FROM breakpoints WHERE workspace_id = ?1))?(workspace.id).context("Clearing old breakpoints")?;
+                conn.exec_bound(sql!(DELETE FROM breakpoint_branches WHERE workspace_id = ?1))?(workspace.id).context("Clearing old breakpoints branch")?;
+                if let Some(branch) = workspace.breakpoints_branch.as_deref() {
+                    conn.exec_bound(sql!(
+                        INSERT INTO breakpoint_branches (workspace_id, branch)
+                        VALUES (?1, ?2);
+                    ))?((workspace.id, branch)).context("Storing breakpoints branch")?;
+                }
 
-                for (path, breakpoints) in workspace.breakpoints {
+                let breakpoint_sets = workspace
+                    .branch_breakpoints
+                    .into_iter()
+                    .map(|(branch, breakpoints)| (Some(branch), breakpoints))
+                    .chain([(None, workspace.breakpoints)]);
+                for (branch, (path, breakpoints)) in breakpoint_sets.flat_map(|(branch, breakpoints)| {
+                    breakpoints.into_iter().map(move |breakpoints| (branch.clone(), breakpoints))
+                }) {
                     for bp in breakpoints {
                         let state = BreakpointStateWrapper::from(bp.state);
                         match conn.exec_bound(sql!(
-                            INSERT INTO breakpoints (workspace_id, path, breakpoint_location,  log_message, condition, hit_condition, state)
-                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);))?
+                            INSERT INTO breakpoints (workspace_id, path, breakpoint_location,  log_message, condition, hit_condition, state, group_name, branch)
+                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9);))?
 
                         ((
                             workspace.id,
@@ -766,6 +860,8 @@ impl WorkspaceDb {
                             bp.condition,
                             bp.hit_condition,
                             state,
+                            bp.group,
+                            branch.as_deref(),
                         )) {
                             Ok(_) => {
                                 log::debug!("Stored breakpoint at row: {} in path: {}", bp.row, path.to_string_lossy())
@@ -1479,6 +1575,7 @@ mod tests {
             state: BreakpointState::Enabled,
             condition: None,
             hit_condition: None,
+            group: None,
         };
 
         let log_breakpoint = Breakpoint {
@@ -1487,6 +1584,7 @@ mod tests {
             state: BreakpointState::Enabled,
             condition: None,
             hit_condition: None,
+            group: None,
         };
 
         let disable_breakpoint = Breakpoint {
@@ -1495,6 +1593,7 @@ mod tests {
             state: BreakpointState::Disabled,
             condition: None,
             hit_condition: None,
+            group: None,
         };
 
         let condition_breakpoint = Breakpoint {
@@ -1503,6 +1602,7 @@ mod tests {
             state: BreakpointState::Enabled,
             condition: Some("x > 5".into()),
             hit_condition: None,
+            group: None,
         };
 
         let hit_condition_breakpoint = Breakpoint {
@@ -1511,6 +1611,7 @@ mod tests {
             state: BreakpointState::Enabled,
             condition: None,
             hit_condition: Some(">= 3".into()),
+            group: Some("startup".into()),
         };
 
         let workspace = SerializedWorkspace {
@@ -1533,6 +1634,7 @@ mod tests {
                             state: breakpoint.state,
                             condition: breakpoint.condition.clone(),
                             hit_condition: breakpoint.hit_condition.clone(),
+                            group: breakpoint.group.clone(),
                         },
                         SourceBreakpoint {
                             row: log_breakpoint.position,
@@ -1541,6 +1643,7 @@ mod tests {
                             state: log_breakpoint.state,
                             condition: log_breakpoint.condition.clone(),
                             hit_condition: log_breakpoint.hit_condition.clone(),
+                            group: log_breakpoint.group.clone(),
                         },
                         SourceBreakpoint {
                             row: disable_breakpoint.position,
@@ -1549,6 +1652,7 @@ mod tests {
                             state: disable_breakpoint.state,
                             condition: disable_breakpoint.condition.clone(),
                             hit_condition: disable_breakpoint.hit_condition.clone(),
+                            group: disable_breakpoint.group.clone(),
                         },
                         SourceBreakpoint {
                             row: condition_breakpoint.position,
@@ -1557,6 +1661,7 @@ mod tests {
                             state: condition_breakpoint.state,
                             condition: condition_breakpoint.condition.clone(),
                             hit_condition: condition_breakpoint.hit_condition.clone(),
+                            group: condition_breakpoint.group.clone(),
                         },
                         SourceBreakpoint {
                             row: hit_condition_breakpoint.position,
@@ -1565,11 +1670,14 @@ mod tests {
                             state: hit_condition_breakpoint.state,
                             condition: hit_condition_breakpoint.condition.clone(),
                             hit_condition: hit_condition_breakpoint.hit_condition.clone(),
+                            group: hit_condition_breakpoint.group.clone(),
                         },
                     ],
                 );
                 map
             },
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1646,9 +1754,66 @@ mod tests {
             hit_condition_breakpoint.hit_condition
         );
         assert_eq!(loaded_breakpoints[4].state, hit_condition_breakpoint.state);
+        assert_eq!(loaded_breakpoints[4].group, hit_condition_breakpoint.group);
         assert_eq!(loaded_breakpoints[4].path, Arc::from(path));
     }
 
+    #[gpui::test]
+    async fn test_branch_breakpoints() {
+        zlog::init_test();
+
+        let db = WorkspaceDb::open_test_db("test_branch_breakpoints").await;
+        let id = db.next_id().await.unwrap();
+
+        let path: Arc<Path> = Arc::from(Path::new("/tmp/test.rs"));
+        let source_breakpoint = |row| SourceBreakpoint {
+            row,
+            path: path.clone(),
+            message: None,
+            condition: None,
+            hit_condition: None,
+            state: BreakpointState::Enabled,
+            group: None,
+        };
+
+        let mut feature_breakpoints = collections::BTreeMap::default();
+        feature_breakpoints.insert(
+            path.clone(),
+            vec![source_breakpoint(7), source_breakpoint(9)],
+        );
+        let mut branch_breakpoints = collections::BTreeMap::default();
+        branch_breakpoints.insert(Arc::<str>::from("feature"), feature_breakpoints);
+
+        let workspace = SerializedWorkspace {
+            id,
+            location: SerializedWorkspaceLocation::from_local_paths(["/tmp"]),
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            breakpoints: collections::BTreeMap::from_iter([(
+                path.clone(),
+                vec![source_breakpoint(1)],
+            )]),
+            breakpoints_branch: Some("main".into()),
+            branch_breakpoints: branch_breakpoints.clone(),
+            session_id: None,
+            window_id: None,
+        };
+
+        db.save_workspace(workspace).await;
+
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        let active_rows = loaded.breakpoints[&path]
+            .iter()
+            .map(|breakpoint| breakpoint.row)
+            .collect::<Vec<_>>();
+        assert_eq!(active_rows, vec![1]);
+        assert_eq!(loaded.breakpoints_branch.as_deref(), Some("main"));
+        assert_eq!(loaded.branch_breakpoints, branch_breakpoints);
+    }
+
     #[gpui::test]
     async fn test_remove_last_breakpoint() {
         zlog::init_test();
@@ -1664,6 +1829,7 @@ mod tests {
             state: BreakpointState::Enabled,
             condition: None,
             hit_condition: None,
+            group: None,
         };
 
         let workspace = SerializedWorkspace {
@@ -1685,10 +1851,13 @@ mod tests {
                         state: BreakpointState::Enabled,
                         condition: None,
                         hit_condition: None,
+                        group: None,
                     }],
                 );
                 map
             },
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1721,6 +1890,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: collections::BTreeMap::default(),
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1815,6 +1986,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1828,6 +2001,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1933,6 +2108,8 @@ mod tests {
             center_group,
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -1968,6 +2145,8 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -1984,6 +2163,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             session_id: None,
             window_id: Some(2),
         };
@@ -2024,6 +2205,8 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2064,6 +2247,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(10),
         };
@@ -2077,6 +2262,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(20),
         };
@@ -2090,6 +2277,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(30),
         };
@@ -2103,6 +2292,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -2121,6 +2312,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(50),
         };
@@ -2134,6 +2327,8 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2192,6 +2387,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             breakpoints: Default::default(),
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             centered_layout: false,
             session_id: None,
             window_id: None,
@@ -2240,6 +2437,8 @@ mod tests {
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             window_id: Some(window_id),
         })
         .collect::<Vec<_>>();
@@ -2333,6 +2532,8 @@ mod tests {
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
+            breakpoints_branch: None,
+            branch_breakpoints: Default::default(),
             window_id: Some(window_id),
         })
         .collect::<Vec<_>>();
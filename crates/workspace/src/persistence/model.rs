@@ -265,6 +265,9 @@ pub(crate) struct SerializedWorkspace {
     pub(crate) docks: DockStructure,
     pub(crate) session_id: Option<String>,
     pub(crate) breakpoints: BTreeMap<Arc<Path>, Vec<SourceBreakpoint>>,
+    /// The git branch that was checked out when `breakpoints` were saved.
+    pub(crate) breakpoints_branch: Option<Arc<str>>,
+    pub(crate) branch_breakpoints: BTreeMap<Arc<str>, BTreeMap<Arc<Path>, Vec<SourceBreakpoint>>>,
     pub(crate) window_id: Option<u64>,
 }
 
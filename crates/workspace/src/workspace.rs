@@ -4664,12 +4664,10 @@ impl Workspace {
         for dock in self.all_docks() {
             if dock.focus_handle(cx).contains_focused(window, cx) {
                 if let Some(panel) = dock.read(cx).active_panel() {
-                    if let Some(pane) = panel.pane(cx) {
-                        if let Some(item) = pane.read(cx).active_item() {
-                            active_item = Some(item);
-                            panel_id = panel.remote_id();
-                            break;
-                        }
+                    if let Some(item) = panel.active_item_for_followers(cx) {
+                        active_item = Some(item);
+                        panel_id = panel.remote_id();
+                        break;
                     }
                 }
             }
@@ -4702,6 +4700,34 @@ impl Workspace {
         })
     }
 
+    /// Sends followers an update to an item that is shown in a panel without a pane, whose
+    /// events therefore aren't forwarded to followers automatically.
+    pub fn update_followers_for_panel_item(
+        &self,
+        item: &dyn FollowableItemHandle,
+        variant: proto::update_view::Variant,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        if !item.item_focus_handle(cx).contains_focused(window, cx) {
+            return;
+        }
+        let Some(id) = item.remote_id(&self.app_state.client, window, cx) else {
+            return;
+        };
+        let is_project_item = item.is_project_item(window, cx);
+        self.update_followers(
+            is_project_item,
+            proto::update_followers::Variant::UpdateView(proto::UpdateView {
+                id: id.to_proto(),
+                leader_id: None,
+                variant: Some(variant),
+            }),
+            window,
+            cx,
+        );
+    }
+
     pub fn leader_for_pane(&self, pane: &Entity<Pane>) -> Option<CollaboratorId> {
         self.follower_states.iter().find_map(|(leader_id, state)| {
             if state.center_pane == *pane || state.dock_pane.as_ref() == Some(pane) {
@@ -4730,9 +4756,14 @@ impl Workspace {
         let mut transfer_focus = state.center_pane.read(cx).has_focus(window, cx);
         let pane;
         if let Some(panel_id) = panel_id {
-            pane = self
-                .activate_panel_for_proto_id(panel_id, window, cx)?
-                .pane(cx)?;
+            let panel = self.activate_panel_for_proto_id(panel_id, window, cx)?;
+            let Some(panel_pane) = panel.pane(cx) else {
+                let state = self.follower_states.get_mut(&leader_id)?;
+                state.dock_pane.take();
+                panel.activate_item_for_follower(item.as_ref(), window, cx);
+                return Some(item);
+            };
+            pane = panel_pane;
             let state = self.follower_states.get_mut(&leader_id)?;
             state.dock_pane = Some(pane.clone());
         } else {
@@ -5076,12 +5107,15 @@ impl Workspace {
         }
 
         if let Some(location) = self.serialize_workspace_location(cx) {
-            let breakpoints = self.project.update(cx, |project, cx| {
-                project
-                    .breakpoint_store()
-                    .read(cx)
-                    .all_source_breakpoints(cx)
-            });
+            let (breakpoints, breakpoints_branch, branch_breakpoints) =
+                self.project.update(cx, |project, cx| {
+                    let breakpoint_store = project.breakpoint_store().read(cx);
+                    (
+                        breakpoint_store.all_source_breakpoints(cx),
+                        breakpoint_store.active_branch().cloned(),
+                        breakpoint_store.branch_breakpoints().clone(),
+                    )
+                });
 
             let center_group = build_serialized_pane_group(&self.center.root, window, cx);
             let docks = build_serialized_docks(self, window, cx);
@@ -5096,6 +5130,8 @@ impl Workspace {
                 centered_layout: self.centered_layout,
                 session_id: self.session_id.clone(),
                 breakpoints,
+                breakpoints_branch,
+                branch_breakpoints,
                 window_id: Some(window.window_handle().window_id().as_u64()),
             };
 
@@ -5267,8 +5303,12 @@ impl Workspace {
                     project
                         .breakpoint_store()
                         .update(cx, |breakpoint_store, cx| {
-                            breakpoint_store
-                                .with_serialized_breakpoints(serialized_workspace.breakpoints, cx)
+                            breakpoint_store.with_serialized_branch_breakpoints(
+                                serialized_workspace.breakpoints_branch,
+                                serialized_workspace.breakpoints,
+                                serialized_workspace.branch_breakpoints,
+                                cx,
+                            )
                         })
                 })?
                 .await;
@@ -519,6 +519,8 @@ fn initialize_panels(
             workspace.add_panel(chat_panel, window, cx);
             workspace.add_panel(notification_panel, window, cx);
             workspace.add_panel(debug_panel, window, cx);
+            let extension_panel = extensions_ui::ExtensionPanel::new(window, cx);
+            workspace.add_panel(extension_panel, window, cx);
         })?;
 
         let is_assistant2_enabled = !cfg!(test);
@@ -606,6 +606,7 @@ mod tests {
                 port_forwards: None,
                 nickname: None,
                 upload_binary_over_ssh: false,
+                binary_cache: None,
             }
         );
         assert_eq!(request.open_paths, vec!["/"]);